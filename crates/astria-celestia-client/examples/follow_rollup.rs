@@ -0,0 +1,170 @@
+//! Follows the data of a rollup as it is written to Celestia, printing its transactions in
+//! sequencer order.
+//!
+//! Every new Celestia height is read the way conductor reads it: the sequencer blobs of the
+//! sequencer network are fetched and verified, and the blobs of the rollup are verified against
+//! the sequencer blobs committing to them. Run it against a local devnet with:
+//!
+//! ```sh
+//! CELESTIA_BEARER_TOKEN=<token> cargo run -p astria-celestia-client --example follow_rollup
+//! ```
+//!
+//! It is configured through the following environment variables:
+//!
+//! - `CELESTIA_NODE_URL`: the JSON-RPC endpoint of the Celestia node. Defaults to
+//!   `http://127.0.0.1:26658`, the endpoint of a local devnet.
+//! - `CELESTIA_BEARER_TOKEN`: the auth token of the Celestia node, which must allow reading
+//!   blobs and headers. Requests are not authenticated if it is not set.
+//! - `SEQUENCER_CHAIN_ID`: the chain ID of the sequencer network whose blocks are followed.
+//!   Defaults to `example-sequencer`.
+//! - `ROLLUP_NAME`: the name of the rollup whose transactions are printed. Defaults to
+//!   `example-rollup`.
+//! - `START_HEIGHT`: the Celestia height to start reading at. Defaults to the latest height of
+//!   the network.
+//! - `POLL_INTERVAL_MS`: how often the latest height of the network is polled, in milliseconds.
+//!   Defaults to 1000.
+
+use std::time::Duration;
+
+use astria_celestia_client::{
+    celestia_namespace_v0_from_rollup_id,
+    celestia_namespace_v0_from_str,
+    celestia_rpc::HeaderClient as _,
+    celestia_types::nmt::Namespace,
+    is_blob_not_found,
+    jsonrpsee::http_client::HttpClient,
+    CelestiaClientExt as _,
+    ClientBuilder,
+    SequencerBlobFilter,
+    TracingSink,
+};
+use astria_core::primitive::v1::{
+    BlockHash,
+    RollupId,
+};
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use base64::{
+    display::Base64Display,
+    engine::general_purpose::STANDARD,
+};
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    astria_eyre::install()?;
+    let endpoint = env_or("CELESTIA_NODE_URL", "http://127.0.0.1:26658");
+    let chain_id = env_or("SEQUENCER_CHAIN_ID", "example-sequencer");
+    let rollup_name = env_or("ROLLUP_NAME", "example-rollup");
+    let poll_interval = env_or("POLL_INTERVAL_MS", "1000")
+        .parse()
+        .map(Duration::from_millis)
+        .wrap_err("failed parsing POLL_INTERVAL_MS as a number of milliseconds")?;
+
+    let mut builder = ClientBuilder::new(endpoint);
+    if let Ok(bearer_token) = std::env::var("CELESTIA_BEARER_TOKEN") {
+        builder = builder.bearer_token(bearer_token);
+    }
+    let client = builder
+        .build()
+        .wrap_err("failed constructing Celestia JSON-RPC client")?;
+
+    let rollup = Rollup {
+        id: RollupId::from_unhashed_bytes(&rollup_name),
+        sequencer_namespace: celestia_namespace_v0_from_str(&chain_id),
+    };
+    let mut next_height = match std::env::var("START_HEIGHT") {
+        Ok(height) => height
+            .parse()
+            .wrap_err("failed parsing START_HEIGHT as a Celestia height")?,
+        Err(_) => latest_height(&client).await?,
+    };
+    println!(
+        "following rollup `{rollup_name}` ({}) of sequencer network `{chain_id}` from Celestia \
+         height {next_height}",
+        rollup.id,
+    );
+
+    loop {
+        let latest_height = latest_height(&client).await?;
+        while next_height <= latest_height {
+            read_height(&client, next_height, &rollup).await?;
+            next_height += 1;
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// The rollup that is followed.
+struct Rollup {
+    id: RollupId,
+    /// The namespace the sequencer network writes its sequencer blobs to.
+    sequencer_namespace: Namespace,
+}
+
+/// Returns the value of the environment variable `name`, or `default` if it is not set.
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}
+
+/// Returns the latest height of the Celestia network.
+async fn latest_height(client: &HttpClient) -> eyre::Result<u64> {
+    let header = client
+        .header_network_head()
+        .await
+        .wrap_err("failed fetching the network head from Celestia")?;
+    Ok(header.height().value())
+}
+
+/// Prints the transactions of `rollup` written at the Celestia height `height`.
+async fn read_height(client: &HttpClient, height: u64, rollup: &Rollup) -> eyre::Result<()> {
+    let events = TracingSink::default();
+    let mut response = match client
+        .get_sequencer_blobs(
+            height,
+            rollup.sequencer_namespace,
+            &SequencerBlobFilter::default(),
+            &events,
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(error) if is_blob_not_found(&error) => return Ok(()),
+        Err(error) => {
+            return Err(error).wrap_err_with(|| {
+                format!("failed fetching sequencer blobs at Celestia height {height}")
+            });
+        }
+    };
+    let rollup_namespace = celestia_namespace_v0_from_rollup_id(rollup.id);
+    for sequencer_blob in response.take_ordered_sequencer_blobs().blobs() {
+        if !sequencer_blob.contains_rollup_id(rollup.id) {
+            continue;
+        }
+        let rollup_blobs = client
+            .get_rollup_blobs_matching_sequencer_blob(
+                height,
+                rollup_namespace,
+                sequencer_blob,
+                &events,
+            )
+            .await
+            .wrap_err_with(|| {
+                format!("failed fetching rollup blobs at Celestia height {height}")
+            })?;
+        println!(
+            "Celestia height {height}, sequencer height {}, block `{}`:",
+            sequencer_blob.height(),
+            BlockHash::new(sequencer_blob.block_hash()),
+        );
+        for transaction in rollup_blobs.iter().flat_map(|blob| blob.transactions()) {
+            println!(
+                "  {} bytes: {}",
+                transaction.len(),
+                Base64Display::new(transaction, &STANDARD),
+            );
+        }
+    }
+    Ok(())
+}
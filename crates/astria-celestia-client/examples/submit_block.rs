@@ -0,0 +1,121 @@
+//! Submits a sequencer block to a Celestia node and prints where its blobs were included.
+//!
+//! The block is synthetic, carrying a single transaction for one rollup, and is written the same
+//! way the sequencer relayer writes real blocks. Run it against a local devnet with:
+//!
+//! ```sh
+//! CELESTIA_BEARER_TOKEN=<token> cargo run -p astria-celestia-client --example submit_block
+//! ```
+//!
+//! It is configured through the following environment variables:
+//!
+//! - `CELESTIA_NODE_URL`: the JSON-RPC endpoint of the Celestia node. Defaults to
+//!   `http://127.0.0.1:26658`, the endpoint of a local devnet.
+//! - `CELESTIA_BEARER_TOKEN`: the auth token of the Celestia node, which must allow submitting
+//!   blobs. Requests are not authenticated if it is not set.
+//! - `SEQUENCER_CHAIN_ID`: the chain ID of the block, which determines the namespace of its
+//!   sequencer blob. Defaults to `example-sequencer`.
+//! - `ROLLUP_NAME`: the name of the rollup the block carries a transaction for, which
+//!   determines the namespace of its rollup blob. Defaults to `example-rollup`.
+//!
+//! Run the `follow_rollup` example with the same chain ID and rollup name to read the block
+//! back.
+
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use astria_celestia_client::{
+    celestia_types::blob::SubmitOptions,
+    CelestiaClientExt as _,
+    ClientBuilder,
+    TracingSink,
+};
+use astria_core::{
+    celestia::HexNamespace,
+    primitive::v1::{
+        BlockHash,
+        RollupId,
+    },
+    protocol::test_utils::ConfigureSequencerBlock,
+    sequencerblock::v1alpha1::SequencerBlock,
+};
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use base64::{
+    display::Base64Display,
+    engine::general_purpose::STANDARD,
+};
+use sha2::{
+    Digest as _,
+    Sha256,
+};
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    astria_eyre::install()?;
+    let endpoint = env_or("CELESTIA_NODE_URL", "http://127.0.0.1:26658");
+    let chain_id = env_or("SEQUENCER_CHAIN_ID", "example-sequencer");
+    let rollup_name = env_or("ROLLUP_NAME", "example-rollup");
+
+    let mut builder = ClientBuilder::new(endpoint);
+    if let Ok(bearer_token) = std::env::var("CELESTIA_BEARER_TOKEN") {
+        builder = builder.bearer_token(bearer_token);
+    }
+    let client = builder
+        .build()
+        .wrap_err("failed constructing Celestia JSON-RPC client")?;
+
+    let block = synthetic_block(chain_id, &rollup_name)?;
+    let block_hash = BlockHash::new(block.block_hash());
+    let sequencer_height = block.height();
+    println!("submitting sequencer block `{block_hash}` at sequencer height {sequencer_height}");
+
+    let response = client
+        .submit_sequencer_block(block, SubmitOptions::default(), &TracingSink::default())
+        .await
+        .wrap_err("failed submitting sequencer block to Celestia")?;
+    println!(
+        "sequencer block was included at Celestia height {} in {} blobs:",
+        response.height,
+        response.blobs.len(),
+    );
+    for blob in &response.blobs {
+        println!(
+            "  namespace {}, commitment {}",
+            HexNamespace(blob.namespace),
+            Base64Display::new(&blob.commitment.0, &STANDARD),
+        );
+    }
+    Ok(())
+}
+
+/// Returns the value of the environment variable `name`, or `default` if it is not set.
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}
+
+/// Returns a sequencer block of the chain `chain_id` with a single transaction for the rollup
+/// `rollup_name`.
+///
+/// The block is derived from the current time so that every run writes a distinct block.
+fn synthetic_block(chain_id: String, rollup_name: &str) -> eyre::Result<SequencerBlock> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .wrap_err("system time is before the unix epoch")?;
+    let height = u32::try_from(now.as_secs() % u64::from(u32::MAX))
+        .expect("the remainder of dividing by u32::MAX fits into u32");
+    let block_hash: [u8; 32] = Sha256::digest(now.as_nanos().to_le_bytes()).into();
+    let transaction = format!("hello from {rollup_name} at {}", now.as_nanos()).into_bytes();
+    Ok(ConfigureSequencerBlock {
+        block_hash: Some(block_hash),
+        chain_id: Some(chain_id),
+        height: height.max(1),
+        sequence_data: vec![(RollupId::from_unhashed_bytes(rollup_name), transaction)],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make())
+}
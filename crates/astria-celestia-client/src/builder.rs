@@ -0,0 +1,149 @@
+//! Construction of JSON-RPC clients for talking to a Celestia node.
+
+use std::time::Duration;
+
+use jsonrpsee::http_client::{
+    HeaderMap,
+    HeaderValue,
+    HttpClient,
+    HttpClientBuilder,
+};
+use tower::{
+    layer::util::{
+        Identity,
+        Stack,
+    },
+    Layer,
+    ServiceBuilder,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct BuildClientError(BuildClientErrorKind);
+
+impl BuildClientError {
+    fn invalid_bearer_token() -> Self {
+        Self(BuildClientErrorKind::InvalidBearerToken)
+    }
+
+    fn transport(source: jsonrpsee::core::Error) -> Self {
+        Self(BuildClientErrorKind::Transport(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum BuildClientErrorKind {
+    #[error("the bearer token cannot be used as the value of an HTTP header")]
+    InvalidBearerToken,
+    #[error("failed constructing the JSON-RPC HTTP transport")]
+    Transport(#[source] jsonrpsee::core::Error),
+}
+
+/// Builds a JSON-RPC client for a Celestia node, talking HTTP and wrapped in middleware.
+///
+/// The HTTP transport is configured with the node's endpoint, its bearer token, and any extra
+/// headers. The [middleware](crate::middleware) added with [`ClientBuilder::layer`] is stacked
+/// on top of it.
+#[derive(Clone, Debug)]
+pub struct ClientBuilder<L = Identity> {
+    endpoint: String,
+    bearer_token: Option<String>,
+    headers: HeaderMap,
+    request_timeout: Option<Duration>,
+    layers: ServiceBuilder<L>,
+}
+
+impl ClientBuilder {
+    /// Returns a builder for a client talking to the node at `endpoint`, without middleware.
+    #[must_use]
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            bearer_token: None,
+            headers: HeaderMap::new(),
+            request_timeout: None,
+            layers: ServiceBuilder::new(),
+        }
+    }
+}
+
+impl<L> ClientBuilder<L> {
+    /// Authenticates every request with `bearer_token`.
+    #[must_use]
+    pub fn bearer_token(self, bearer_token: impl Into<String>) -> Self {
+        Self {
+            bearer_token: Some(bearer_token.into()),
+            ..self
+        }
+    }
+
+    /// Sends the header `name` with `value` on every request.
+    #[must_use]
+    pub fn header(mut self, name: &'static str, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Fails requests that were not answered within `timeout`.
+    #[must_use]
+    pub fn request_timeout(self, timeout: Duration) -> Self {
+        Self {
+            request_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Wraps the client in the middleware `layer`.
+    ///
+    /// Layers added earlier wrap layers added later, so the first layer added sees requests
+    /// first and responses last.
+    #[must_use]
+    pub fn layer<T>(self, layer: T) -> ClientBuilder<Stack<T, L>> {
+        let Self {
+            endpoint,
+            bearer_token,
+            headers,
+            request_timeout,
+            layers,
+        } = self;
+        ClientBuilder {
+            endpoint,
+            bearer_token,
+            headers,
+            request_timeout,
+            layers: layers.layer(layer),
+        }
+    }
+
+    /// Builds the HTTP transport and wraps it in the configured middleware.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bearer token is not a valid header value, or if the HTTP transport
+    /// could not be constructed, for example because the endpoint is not a valid URL.
+    pub fn build(self) -> Result<L::Service, BuildClientError>
+    where
+        L: Layer<HttpClient>,
+    {
+        let Self {
+            endpoint,
+            bearer_token,
+            mut headers,
+            request_timeout,
+            layers,
+        } = self;
+        if let Some(bearer_token) = bearer_token {
+            let value = HeaderValue::from_str(&format!("Bearer {bearer_token}"))
+                .map_err(|_| BuildClientError::invalid_bearer_token())?;
+            headers.insert("authorization", value);
+        }
+        let mut transport = HttpClientBuilder::default().set_headers(headers);
+        if let Some(request_timeout) = request_timeout {
+            transport = transport.request_timeout(request_timeout);
+        }
+        let transport = transport
+            .build(endpoint)
+            .map_err(BuildClientError::transport)?;
+        Ok(layers.service(transport))
+    }
+}
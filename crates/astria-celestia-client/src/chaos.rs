@@ -0,0 +1,232 @@
+//! Fault injection around a Celestia JSON-RPC client.
+//!
+//! [`ChaosDaClient`] wraps any JSON-RPC client and degrades its requests as configured in
+//! [`ChaosConfig`]: it delays them, fails them outright, drops blobs from `blob.GetAll`
+//! responses, and corrupts the data of the blobs it returns. All random decisions are drawn from
+//! a generator seeded with [`ChaosConfig::seed`], so that the same sequence of requests sees the
+//! same faults in every run. This makes it possible to exercise retries, confirmation and
+//! resubmission logic deterministically.
+//!
+//! Only available with the `chaos` feature.
+
+use std::{
+    sync::Mutex,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use base64::{
+    engine::general_purpose::STANDARD,
+    Engine as _,
+};
+use jsonrpsee::core::{
+    client::{
+        BatchResponse,
+        ClientT,
+    },
+    params::BatchRequestBuilder,
+    traits::ToRpcParams,
+    Error,
+};
+use rand::{
+    rngs::StdRng,
+    Rng as _,
+    SeedableRng as _,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tower::Layer;
+use tracing::debug;
+
+use crate::CelestiaClientExt;
+
+const BLOB_GET_ALL: &str = "blob.GetAll";
+
+/// The faults injected by a [`ChaosDaClient`].
+///
+/// All rates are probabilities in `[0, 1]`. The default injects no faults.
+#[derive(Clone, Debug, Default)]
+pub struct ChaosConfig {
+    /// The seed of the generator all random decisions are drawn from.
+    pub seed: u64,
+    /// The delay added to every request before it is forwarded.
+    pub latency: Duration,
+    /// The probability that a request fails without being forwarded.
+    pub error_rate: f64,
+    /// The probability that a blob in a `blob.GetAll` response is dropped.
+    pub blob_loss_rate: f64,
+    /// The probability that the data of a blob in a `blob.GetAll` response is corrupted.
+    pub corruption_rate: f64,
+}
+
+impl ChaosConfig {
+    fn assert_rates_are_probabilities(&self) {
+        for (name, rate) in [
+            ("error_rate", self.error_rate),
+            ("blob_loss_rate", self.blob_loss_rate),
+            ("corruption_rate", self.corruption_rate),
+        ] {
+            assert!(
+                (0.0..=1.0).contains(&rate),
+                "`{name}` must be a probability in [0, 1], but was {rate}"
+            );
+        }
+    }
+}
+
+/// Wraps clients in a [`ChaosDaClient`], so that faults can be injected as
+/// [middleware](crate::middleware).
+#[derive(Clone, Debug)]
+pub struct ChaosLayer {
+    config: ChaosConfig,
+}
+
+impl ChaosLayer {
+    /// Returns a layer injecting the faults configured in `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any rate in `config` is not in `[0, 1]`.
+    #[must_use]
+    pub fn new(config: ChaosConfig) -> Self {
+        config.assert_rates_are_probabilities();
+        Self {
+            config,
+        }
+    }
+}
+
+impl<C> Layer<C> for ChaosLayer {
+    type Service = ChaosDaClient<C>;
+
+    fn layer(&self, inner: C) -> Self::Service {
+        ChaosDaClient::new(inner, self.config.clone())
+    }
+}
+
+/// A JSON-RPC client that injects the faults configured in [`ChaosConfig`] around `C`.
+pub struct ChaosDaClient<C> {
+    inner: C,
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+}
+
+impl<C> ChaosDaClient<C> {
+    /// Wraps `inner`, injecting the faults configured in `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any rate in `config` is not in `[0, 1]`.
+    #[must_use]
+    pub fn new(inner: C, config: ChaosConfig) -> Self {
+        config.assert_rates_are_probabilities();
+        let rng = Mutex::new(StdRng::seed_from_u64(config.seed));
+        Self {
+            inner,
+            config,
+            rng,
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn sample(&self, probability: f64) -> bool {
+        probability > 0.0
+            && self
+                .rng
+                .lock()
+                .expect("the lock is never held across a panic")
+                .gen_bool(probability)
+    }
+
+    /// Delays the request and decides whether it fails.
+    async fn disturb(&self, method: &str) -> Result<(), Error> {
+        if !self.config.latency.is_zero() {
+            tokio::time::sleep(self.config.latency).await;
+        }
+        if self.sample(self.config.error_rate) {
+            debug!(method, "injecting failure into request");
+            return Err(Error::Custom(format!(
+                "chaos: injected failure of request `{method}`"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Drops and corrupts the blobs of a `blob.GetAll` response.
+    fn degrade_blobs(&self, response: &mut Value) {
+        let Value::Array(blobs) = response else {
+            return;
+        };
+        blobs.retain(|_| !self.sample(self.config.blob_loss_rate));
+        for blob in blobs {
+            if self.sample(self.config.corruption_rate) {
+                self.corrupt_data(blob);
+            }
+        }
+    }
+
+    /// Flips the bits of a random byte of the base64 encoded `data` field of `blob`.
+    fn corrupt_data(&self, blob: &mut Value) {
+        let Some(Value::String(encoded)) = blob.get_mut("data") else {
+            return;
+        };
+        let Ok(mut data) = STANDARD.decode(&*encoded) else {
+            return;
+        };
+        if data.is_empty() {
+            return;
+        }
+        let index = self
+            .rng
+            .lock()
+            .expect("the lock is never held across a panic")
+            .gen_range(0..data.len());
+        data[index] = !data[index];
+        debug!(index, "injecting corruption into blob data");
+        *encoded = STANDARD.encode(data);
+    }
+}
+
+#[async_trait]
+impl<C> ClientT for ChaosDaClient<C>
+where
+    C: ClientT + Send + Sync,
+{
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        self.disturb(method).await?;
+        self.inner.notification(method, params).await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        self.disturb(method).await?;
+        let mut response: Value = self.inner.request(method, params).await?;
+        if method == BLOB_GET_ALL {
+            self.degrade_blobs(&mut response);
+        }
+        serde_json::from_value(response).map_err(Error::ParseError)
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        self.disturb("batch").await?;
+        self.inner.batch_request(batch).await
+    }
+}
+
+impl<C> CelestiaClientExt for ChaosDaClient<C> where C: ClientT + Send + Sync {}
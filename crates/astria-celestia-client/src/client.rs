@@ -1,8 +1,24 @@
-use astria_core::sequencerblock::v1alpha1::{
-    celestia::CelestiaSequencerBlobError,
-    CelestiaRollupBlob,
-    CelestiaSequencerBlob,
-    SequencerBlock,
+use std::ops::RangeInclusive;
+
+use astria_core::{
+    celestia::HexNamespace,
+    generated::sequencerblock::v1alpha1::{
+        CelestiaEmptyBlocksMarker as RawCelestiaEmptyBlocksMarker,
+        RollupResultNamespaceData as RawRollupResultNamespaceData,
+    },
+    primitive::v1::{
+        BlockHash,
+        RollupId,
+    },
+    sequencerblock::v1alpha1::{
+        celestia::CelestiaSequencerBlobError,
+        CelestiaAuxiliaryBlob,
+        CelestiaEmptyBlocksMarker,
+        CelestiaRollupBlob,
+        CelestiaSequencerBlob,
+        RollupResultNamespaceData,
+        SequencerBlock,
+    },
 };
 use async_trait::async_trait;
 use base64::{
@@ -25,7 +41,25 @@ use tracing::{
     instrument,
 };
 
-use crate::submission::ToBlobsError;
+use crate::{
+    decode::{
+        DecodedRollupBlob,
+        DecoderRegistry,
+    },
+    dedup::{
+        BlobReference,
+        PostedBlobs,
+    },
+    events::{
+        Event,
+        EventSink,
+        RejectReason,
+    },
+    heartbeat::HeartbeatReport,
+    ordered::OrderedSequencerBlobs,
+    squatting::SquattingReport,
+    submission::ToBlobsError,
+};
 
 impl CelestiaClientExt for jsonrpsee::http_client::HttpClient {}
 impl CelestiaClientExt for jsonrpsee::ws_client::WsClient {}
@@ -42,6 +76,10 @@ impl SubmitSequencerBlocksError {
     fn jsonrpc(source: jsonrpsee::core::Error) -> Self {
         Self(SubmitSequencerBlocksErrorKind::JsonRpc(source))
     }
+
+    fn reference(source: celestia_types::Error) -> Self {
+        Self(SubmitSequencerBlocksErrorKind::Reference(source))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -50,6 +88,8 @@ enum SubmitSequencerBlocksErrorKind {
     AssembleBlobs(#[source] ToBlobsError),
     #[error("the JSONRPC call failed")]
     JsonRpc(#[source] jsonrpsee::core::Error),
+    #[error("failed constructing a blob referencing an earlier rollup blob")]
+    Reference(#[source] celestia_types::Error),
 }
 
 pub struct BadBlob {
@@ -68,16 +108,218 @@ pub struct GetSequencerBlobsResponse {
     pub namespace: Namespace,
     pub sequencer_blobs: Vec<CelestiaSequencerBlob>,
     pub bad_blobs: Vec<BadBlob>,
+    /// The number of well-formed sequencer blobs that were dropped because they
+    /// did not match the [`SequencerBlobFilter`].
+    pub filtered_blobs: usize,
+}
+
+impl GetSequencerBlobsResponse {
+    /// Moves the sequencer blobs out of the response, grouped by sequencer height and block
+    /// hash and in sequencer order.
+    ///
+    /// Blobs of blocks returned more than once are dropped and counted in
+    /// [`OrderedSequencerBlobs::duplicates`].
+    pub fn take_ordered_sequencer_blobs(&mut self) -> OrderedSequencerBlobs {
+        std::mem::take(&mut self.sequencer_blobs)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// A blob as it was stored on Celestia, without any interpretation of its contents.
+pub struct RawBlob {
+    /// The namespace the blob was posted to.
+    pub namespace: Namespace,
+    /// The share commitment of the blob.
+    pub commitment: Commitment,
+    /// The size of `data` in bytes.
+    pub size: usize,
+    /// The opaque bytes of the blob.
+    pub data: Vec<u8>,
+}
+
+impl From<Blob> for RawBlob {
+    fn from(blob: Blob) -> Self {
+        Self {
+            namespace: blob.namespace,
+            commitment: blob.commitment,
+            size: blob.data.len(),
+            data: blob.data,
+        }
+    }
+}
+
+/// A blob posted by a submission, identified the way Celestia identifies it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmittedBlob {
+    /// The namespace the blob was posted to.
+    pub namespace: Namespace,
+    /// The share commitment of the blob, with which the blob and its inclusion proof can be
+    /// fetched from Celestia.
+    pub commitment: Commitment,
+}
+
+impl From<&Blob> for SubmittedBlob {
+    fn from(blob: &Blob) -> Self {
+        Self {
+            namespace: blob.namespace,
+            commitment: blob.commitment.clone(),
+        }
+    }
+}
+
+/// The outcome of submitting a sequencer block to Celestia.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmitBlockResponse {
+    /// The Celestia height the blobs were included at.
+    pub height: u64,
+    /// The blobs of the submission in the order they were posted: the sequencer blob first,
+    /// followed by the rollup blobs.
+    pub blobs: Vec<SubmittedBlob>,
+}
+
+impl SubmitBlockResponse {
+    fn new(height: u64, blobs: &[Blob]) -> Self {
+        Self {
+            height,
+            blobs: blobs.iter().map(SubmittedBlob::from).collect(),
+        }
+    }
+}
+
+/// Restricts the sequencer blobs returned by [`CelestiaClientExt::get_sequencer_blobs`]
+/// to those proposed by a specific sequencer validator.
+///
+/// The default filter accepts all blobs. If both a proposer address and a proposer
+/// public key are set, a blob must match both.
+#[derive(Clone, Debug, Default)]
+pub struct SequencerBlobFilter {
+    proposer_address: Option<tendermint::account::Id>,
+    proposer_key: Option<tendermint::PublicKey>,
+}
+
+impl SequencerBlobFilter {
+    /// Only accept blobs whose sequencer header was proposed by `address`.
+    #[must_use]
+    pub fn with_proposer_address(self, address: tendermint::account::Id) -> Self {
+        Self {
+            proposer_address: Some(address),
+            ..self
+        }
+    }
+
+    /// Only accept blobs whose sequencer header was proposed by the validator
+    /// identified by `key`.
+    ///
+    /// The key is matched against the header's proposer address, which is derived
+    /// from the validator's public key.
+    #[must_use]
+    pub fn with_proposer_key(self, key: tendermint::PublicKey) -> Self {
+        Self {
+            proposer_key: Some(key),
+            ..self
+        }
+    }
+
+    /// Returns if `blob` passes the filter.
+    #[must_use]
+    pub fn matches(&self, blob: &CelestiaSequencerBlob) -> bool {
+        let proposer = blob.header().proposer_address();
+        let address_matches = self
+            .proposer_address
+            .as_ref()
+            .map_or(true, |address| address == proposer);
+        let key_matches = self
+            .proposer_key
+            .map_or(true, |key| &tendermint::account::Id::from(key) == proposer);
+        address_matches && key_matches
+    }
 }
 
 #[async_trait]
 pub trait CelestiaClientExt: BlobClient {
+    /// Fetch all blobs at the given height under any of the given namespaces.
+    ///
+    /// The blobs are returned exactly as stored on Celestia. No attempt is made to
+    /// decompress or decode them, which makes this method useful for tooling that
+    /// needs to inspect what was actually posted.
+    ///
+    /// Returns an empty list if no blobs were found.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the underlying `blob.GetAll` JSONRPC failed.
+    async fn get_raw_blobs<T>(
+        &self,
+        height: T,
+        namespaces: &[Namespace],
+    ) -> Result<Vec<RawBlob>, jsonrpsee::core::Error>
+    where
+        T: Into<u64> + Send,
+    {
+        let blobs = match self.blob_get_all(height.into(), namespaces).await {
+            Ok(blobs) => blobs,
+            Err(err) if crate::is_blob_not_found(&err) => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        Ok(blobs.into_iter().map(RawBlob::from).collect())
+    }
+
+    /// Collects the blobs under `namespaces` over the Celestia heights in `heights` that
+    /// cannot be parsed as Astria blobs at all.
+    ///
+    /// Such blobs were most likely posted by third parties using the same namespaces. See
+    /// [`SquattingReport`] for the sizes and frequencies that are reported.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the underlying `blob.GetAll` JSONRPCs failed.
+    async fn get_squatting_report(
+        &self,
+        heights: RangeInclusive<u64>,
+        namespaces: &[Namespace],
+    ) -> Result<SquattingReport, jsonrpsee::core::Error> {
+        let mut report = SquattingReport::new(heights.clone(), namespaces);
+        for height in heights {
+            let blobs = self.get_raw_blobs(height, namespaces).await?;
+            report.record(height, blobs);
+        }
+        Ok(report)
+    }
+
+    /// Collects the signed relayer heartbeats under `namespace` over the Celestia heights in
+    /// `heights`.
+    ///
+    /// See [`HeartbeatReport`] for the liveness and lag that is reported per relayer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the underlying `blob.GetAll` JSONRPCs failed.
+    async fn get_heartbeat_report(
+        &self,
+        heights: RangeInclusive<u64>,
+        namespace: Namespace,
+    ) -> Result<HeartbeatReport, jsonrpsee::core::Error> {
+        let mut report = HeartbeatReport::new(heights.clone());
+        for height in heights {
+            let blobs = self.get_raw_blobs(height, &[namespace]).await?;
+            report.record(height, blobs);
+        }
+        Ok(report)
+    }
+
     /// Fetch sequencer blobs at the given height and namespace.
     ///
     /// Returns successfully deserialized blobs in the `.sequencer_blobs` field. The
     /// `.bad_blobs` field contains the celestia commitment for each blob
     /// that could not be turned into sequencer data and the reason for it.
     ///
+    /// Blobs that do not match `filter` are dropped and only counted in the
+    /// `.filtered_blobs` field. Pass [`SequencerBlobFilter::default`] to accept all
+    /// blobs.
+    ///
+    /// Every verified and rejected blob is reported to `events`.
+    ///
     /// # Errors
     ///
     /// Fails if the underlying `blob.GetAll` JSONRPC failed.
@@ -85,6 +327,8 @@ pub trait CelestiaClientExt: BlobClient {
         &self,
         height: T,
         namespace: Namespace,
+        filter: &SequencerBlobFilter,
+        events: &dyn EventSink,
     ) -> Result<GetSequencerBlobsResponse, jsonrpsee::core::Error>
     where
         T: Into<u64> + Send,
@@ -94,33 +338,58 @@ pub trait CelestiaClientExt: BlobClient {
 
         let mut sequencer_blobs = Vec::new();
         let mut bad_blobs = Vec::new();
+        let mut filtered_blobs = 0;
         for blob in blobs {
+            let reject = |reason| Event::BlobRejected {
+                height,
+                namespace: blob.namespace,
+                commitment: blob.commitment.clone(),
+                reason,
+            };
             if blob.namespace != namespace {
+                events.emit(reject(RejectReason::WrongNamespace));
                 bad_blobs.push(BadBlob {
                     reason: BadBlobReason::WrongNamespace(blob.namespace),
                     commitment: blob.commitment,
                 });
             }
-            'blob: {
-                let raw_blob =
-                    match astria_core::generated::sequencerblock::v1alpha1::CelestiaSequencerBlob::decode(
-                        &*blob.data,
-                    ) {
-                        Ok(blob) => blob,
-                        Err(err) => {
-                            bad_blobs.push(BadBlob {
-                                reason: BadBlobReason::Deserialization(err),
-                                commitment: blob.commitment,
-                            });
-                            break 'blob;
-                        }
-                    };
-                match CelestiaSequencerBlob::try_from_raw(raw_blob) {
-                    Ok(blob) => sequencer_blobs.push(blob),
-                    Err(err) => bad_blobs.push(BadBlob {
-                        reason: BadBlobReason::Conversion(err),
-                        commitment: blob.commitment,
-                    }),
+            // A blob that is not a sequencer blob may be a marker of several empty blocks, which
+            // is expanded into their sequencer blobs.
+            let decoded = match convert_sequencer_blob(&blob.data) {
+                Ok(sequencer_blob) => vec![sequencer_blob],
+                Err(reason) => {
+                    if let Some(empty_blocks) = convert_empty_blocks_marker(&blob.data) {
+                        empty_blocks
+                    } else {
+                        events.emit(reject(match reason {
+                            BadBlobReason::Conversion(_) => RejectReason::Conversion,
+                            _ => RejectReason::Deserialization,
+                        }));
+                        bad_blobs.push(BadBlob {
+                            reason,
+                            commitment: blob.commitment,
+                        });
+                        continue;
+                    }
+                }
+            };
+            for sequencer_blob in decoded {
+                if filter.matches(&sequencer_blob) {
+                    events.emit(Event::BlobVerified {
+                        height,
+                        namespace: blob.namespace,
+                        block_hash: sequencer_blob.block_hash(),
+                        proposer: *sequencer_blob.header().proposer_address(),
+                    });
+                    sequencer_blobs.push(sequencer_blob);
+                } else {
+                    debug!(
+                        block_hash = %BlockHash::new(sequencer_blob.block_hash()),
+                        proposer = %sequencer_blob.header().proposer_address(),
+                        "sequencer blob does not match filter; dropping it",
+                    );
+                    events.emit(reject(RejectReason::Filtered));
+                    filtered_blobs += 1;
                 }
             }
         }
@@ -130,11 +399,19 @@ pub trait CelestiaClientExt: BlobClient {
             namespace,
             sequencer_blobs,
             bad_blobs,
+            filtered_blobs,
         })
     }
 
     /// Returns the rollup blob for a given rollup namespace at a given height, if it exists.
     ///
+    /// The transactions of a returned blob are in the order in which the sequencer included
+    /// them. Rollup transactions carry no explicit index; their order is committed to by the
+    /// rollup transactions root of `sequencer_blob`, so a blob whose transactions were
+    /// reordered, duplicated, or dropped fails verification and is not returned.
+    ///
+    /// Every verified and rejected blob is reported to `events`.
+    ///
     /// # Errors
     ///
     /// Returns an error if:
@@ -142,42 +419,182 @@ pub trait CelestiaClientExt: BlobClient {
     /// + the RPC to fetch the blobs failed.
     #[instrument(skip_all, fields(
         height = height.into(),
-        namespace = %telemetry::display::base64(&namespace.as_bytes()),
-        block_hash = %telemetry::display::base64(&sequencer_blob.block_hash()),
+        namespace = %HexNamespace(namespace),
+        block_hash = %BlockHash::new(sequencer_blob.block_hash()),
     ))]
     async fn get_rollup_blobs_matching_sequencer_blob<T>(
         &self,
         height: T,
         namespace: Namespace,
         sequencer_blob: &CelestiaSequencerBlob,
+        events: &dyn EventSink,
     ) -> Result<Vec<CelestiaRollupBlob>, jsonrpsee::core::Error>
     where
         T: Into<u64> + Copy + Send,
     {
-        #[must_use]
-        fn is_blob_not_found(err: &jsonrpsee::core::Error) -> bool {
-            if let jsonrpsee::core::Error::Call(err) = err {
-                return err.message().contains("blob: not found");
-            }
-            false
-        }
-
         let height = height.into();
 
         let rsp = self.blob_get_all(height, &[namespace]).await;
         let blobs = match rsp {
             Ok(blobs) => blobs,
-            Err(err) if is_blob_not_found(&err) => {
+            Err(err) if crate::is_blob_not_found(&err) => {
                 return Ok(vec![]);
             }
             Err(err) => {
                 return Err(err);
             }
         };
-        let rollup_datas = convert_and_filter_rollup_blobs(blobs, namespace, sequencer_blob);
+        let blobs = self.resolve_blob_references(height, blobs, events).await?;
+        let rollup_datas =
+            convert_and_filter_rollup_blobs(blobs, height, namespace, sequencer_blob, events);
         Ok(rollup_datas)
     }
 
+    /// Replaces the [`BlobReference`]s among `blobs`, read at `height`, by the blobs they point to.
+    ///
+    /// See the [`dedup`](crate::dedup) module for how references are posted. References whose
+    /// blob could not be fetched are dropped and reported to `events`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an RPC to fetch a referenced blob failed for a reason other than the
+    /// node rejecting the call.
+    async fn resolve_blob_references(
+        &self,
+        height: u64,
+        blobs: Vec<Blob>,
+        events: &dyn EventSink,
+    ) -> Result<Vec<Blob>, jsonrpsee::core::Error> {
+        let mut resolved = Vec::with_capacity(blobs.len());
+        for blob in blobs {
+            let Some(reference) = BlobReference::decode(&blob.data) else {
+                resolved.push(blob);
+                continue;
+            };
+            match self
+                .blob_get(reference.celestia_height, blob.namespace, reference.commitment)
+                .await
+            {
+                Ok(referenced) => resolved.push(referenced),
+                Err(jsonrpsee::core::Error::Call(error)) => {
+                    debug!(
+                        error = &error as &dyn std::error::Error,
+                        reference.height = reference.celestia_height,
+                        "failed fetching the blob referenced by a blob; dropping it"
+                    );
+                    events.emit(Event::BlobRejected {
+                        height,
+                        namespace: blob.namespace,
+                        commitment: blob.commitment,
+                        reason: RejectReason::UnresolvedReference,
+                    });
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Returns the rollup blobs for a given rollup namespace at a given height, with their
+    /// transactions decoded by the decoder registered for `namespace` in `decoders`.
+    ///
+    /// Works like [`CelestiaClientExt::get_rollup_blobs_matching_sequencer_blob`]. If no decoder
+    /// is registered for `namespace`, the blobs are returned without decoded transactions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC to fetch the blobs failed.
+    async fn get_decoded_rollup_blobs_matching_sequencer_blob<T>(
+        &self,
+        height: T,
+        namespace: Namespace,
+        sequencer_blob: &CelestiaSequencerBlob,
+        decoders: &DecoderRegistry,
+        events: &dyn EventSink,
+    ) -> Result<Vec<DecodedRollupBlob>, jsonrpsee::core::Error>
+    where
+        T: Into<u64> + Copy + Send,
+    {
+        let rollup_blobs = self
+            .get_rollup_blobs_matching_sequencer_blob(height, namespace, sequencer_blob, events)
+            .await?;
+        Ok(rollup_blobs
+            .into_iter()
+            .map(|blob| decoders.decode(&namespace, blob))
+            .collect())
+    }
+
+    /// Returns the auxiliary blobs posted under `namespace` at `height` alongside the sequencer
+    /// block of `sequencer_blob`.
+    ///
+    /// Auxiliary blobs carry operator defined data that is not committed to by the sequencer
+    /// block. They are only checked to be bound to the hash of `sequencer_blob`, so their
+    /// contents should only be trusted as much as the account that posted them.
+    ///
+    /// Blobs that cannot be decompressed, decoded, or are bound to a different sequencer block
+    /// are dropped and reported to `events`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC to fetch the blobs failed.
+    #[instrument(skip_all, fields(
+        height = height.into(),
+        namespace = %HexNamespace(namespace),
+        block_hash = %BlockHash::new(sequencer_blob.block_hash()),
+    ))]
+    async fn get_auxiliary_blobs_matching_sequencer_blob<T>(
+        &self,
+        height: T,
+        namespace: Namespace,
+        sequencer_blob: &CelestiaSequencerBlob,
+        events: &dyn EventSink,
+    ) -> Result<Vec<CelestiaAuxiliaryBlob>, jsonrpsee::core::Error>
+    where
+        T: Into<u64> + Copy + Send,
+    {
+        let height = height.into();
+        let blobs = match self.blob_get_all(height, &[namespace]).await {
+            Ok(blobs) => blobs,
+            Err(err) if crate::is_blob_not_found(&err) => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        Ok(convert_and_filter_auxiliary_blobs(blobs, height, namespace, sequencer_blob, events))
+    }
+
+    /// Returns the execution results of the rollup with `rollup_id` written at `height`.
+    ///
+    /// Results are read from [`astria_core::celestia::rollup_result_namespace`] of the rollup.
+    /// Like auxiliary blobs they are not committed to by a sequencer block, so they should only
+    /// be trusted as much as the account that posted them. The sequencer block a result was
+    /// derived from is identified by [`RollupResultNamespaceData::sequencer_block_hash`] and
+    /// is usually written at an earlier height.
+    ///
+    /// Blobs that cannot be decompressed, decoded, or belong to a different rollup are dropped
+    /// and reported to `events`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC to fetch the blobs failed.
+    #[instrument(skip_all, fields(height = height.into(), %rollup_id))]
+    async fn get_rollup_results<T>(
+        &self,
+        height: T,
+        rollup_id: RollupId,
+        events: &dyn EventSink,
+    ) -> Result<Vec<RollupResultNamespaceData>, jsonrpsee::core::Error>
+    where
+        T: Into<u64> + Copy + Send,
+    {
+        let height = height.into();
+        let namespace = astria_core::celestia::rollup_result_namespace(rollup_id);
+        let blobs = match self.blob_get_all(height, &[namespace]).await {
+            Ok(blobs) => blobs,
+            Err(err) if crate::is_blob_not_found(&err) => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        Ok(convert_and_filter_rollup_results(blobs, height, rollup_id, events))
+    }
+
     /// Submits a sequencer `block` to celestia
     ///
     /// `Blocks` after converted into celestia blobs and then posted. Rollup
@@ -185,10 +602,12 @@ pub trait CelestiaClientExt: BlobClient {
     /// Sequencer data for each is posted to a namespace derived from the
     /// sequencer block's chain ID.
     ///
-    /// This calls the `blob.Submit` celestia-node RPC.
+    /// This calls the `blob.Submit` celestia-node RPC. A successful submission is reported to
+    /// `events`.
     ///
     /// Returns Result:
-    /// - Ok: the celestia block height blobs were included in.
+    /// - Ok: the celestia block height blobs were included in, and the namespace and share
+    ///   commitment of every posted blob.
     /// - Errors:
     ///     - SubmitSequencerBlocksError::AssembleBlobs when failed to assemble blob
     ///     - SubmitSequencerBlocksError::JsonRpc when Celestia `blob.Submit` fails
@@ -196,8 +615,10 @@ pub trait CelestiaClientExt: BlobClient {
         &self,
         block: SequencerBlock,
         submit_options: SubmitOptions,
-    ) -> Result<u64, SubmitSequencerBlocksError> {
+        events: &dyn EventSink,
+    ) -> Result<SubmitBlockResponse, SubmitSequencerBlocksError> {
         use crate::submission::ToBlobs as _;
+        let sequencer_height = block.height();
         let mut blobs = Vec::new();
 
         block
@@ -209,7 +630,70 @@ pub trait CelestiaClientExt: BlobClient {
             .await
             .map_err(SubmitSequencerBlocksError::jsonrpc)?;
 
-        Ok(height)
+        events.emit(Event::SubmissionConfirmed {
+            height,
+            sequencer_height,
+            number_of_blobs: blobs.len(),
+        });
+        Ok(SubmitBlockResponse::new(height, &blobs))
+    }
+
+    /// Submits a sequencer `block` to celestia, replacing rollup blobs that were included before
+    /// by references to them.
+    ///
+    /// Works like [`CelestiaClientExt::submit_sequencer_block`]. Rollup blobs found in `posted`
+    /// are posted as [`BlobReference`]s, and the full rollup blobs of the submission are
+    /// recorded in `posted` once it was included. The sequencer blob is always posted in full.
+    ///
+    /// The response lists the blobs as posted, so a rollup blob replaced by a reference is
+    /// listed with the commitment of the reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`CelestiaClientExt::submit_sequencer_block`].
+    async fn submit_sequencer_block_deduplicated(
+        &self,
+        block: SequencerBlock,
+        submit_options: SubmitOptions,
+        posted: &mut PostedBlobs,
+        events: &dyn EventSink,
+    ) -> Result<SubmitBlockResponse, SubmitSequencerBlocksError> {
+        use crate::submission::ToBlobs as _;
+        let sequencer_height = block.height();
+        let mut blobs = Vec::new();
+
+        block
+            .try_to_blobs(&mut blobs)
+            .map_err(SubmitSequencerBlocksError::assemble)?;
+        // The first blob is the sequencer blob, the rest are rollup blobs.
+        let rollup_blobs = blobs.split_off(1);
+        let mut number_of_references = 0;
+        for blob in rollup_blobs.iter().cloned() {
+            let blob = posted
+                .deduplicate(blob)
+                .map_err(SubmitSequencerBlocksError::reference)?;
+            if BlobReference::decode(&blob.data).is_some() {
+                number_of_references += 1;
+            }
+            blobs.push(blob);
+        }
+        debug!(
+            number_of_references,
+            "replaced rollup blobs that were included before by references"
+        );
+
+        let height = self
+            .blob_submit(&blobs, submit_options)
+            .await
+            .map_err(SubmitSequencerBlocksError::jsonrpc)?;
+        posted.record(height, &rollup_blobs);
+
+        events.emit(Event::SubmissionConfirmed {
+            height,
+            sequencer_height,
+            number_of_blobs: blobs.len(),
+        });
+        Ok(SubmitBlockResponse::new(height, &blobs))
     }
 }
 
@@ -234,23 +718,61 @@ pub enum BlobAssemblyError {
     ConstructProof { index: usize },
 }
 
+/// Decodes and verifies the bytes of a blob as a [`CelestiaSequencerBlob`].
+fn convert_sequencer_blob(data: &[u8]) -> Result<CelestiaSequencerBlob, BadBlobReason> {
+    let raw_blob = astria_core::generated::sequencerblock::v1alpha1::CelestiaSequencerBlob::decode(
+        data,
+    )
+    .map_err(BadBlobReason::Deserialization)?;
+    CelestiaSequencerBlob::try_from_raw(raw_blob).map_err(BadBlobReason::Conversion)
+}
+
+/// Decodes and verifies the bytes of a blob as a [`CelestiaEmptyBlocksMarker`], returning the
+/// sequencer blobs of the empty blocks it marks.
+///
+/// Returns `None` if the bytes are not a marker of at least one valid empty block.
+fn convert_empty_blocks_marker(data: &[u8]) -> Option<Vec<CelestiaSequencerBlob>> {
+    let raw_marker = RawCelestiaEmptyBlocksMarker::decode(data).ok()?;
+    if raw_marker.empty_blocks.is_empty() {
+        return None;
+    }
+    CelestiaEmptyBlocksMarker::try_from_raw(raw_marker)
+        .inspect_err(|err| {
+            debug!(
+                error = err as &dyn std::error::Error,
+                "failed verifying decoded empty blocks marker; dropping it",
+            );
+        })
+        .ok()
+        .map(CelestiaEmptyBlocksMarker::into_empty_blocks)
+}
+
 /// Attempts to convert the bytes stored in the celestia blobs to [`CelestiaRollupBlob`].
 ///
 /// Drops a blob under the following conditions:
 /// + the blob's namespace does not match the provided [`Namespace`]
 /// + cannot be decode/convert to [`CelestiaRollupBlob`]
-/// + block hash does not match that of [`CcelestiaSequencerBlob`]
-/// + the proof, ID, and transactions recorded in the blob cannot be verified against the seuencer
+/// + block hash does not match that of [`CelestiaSequencerBlob`]
+/// + the proof, ID, and transactions recorded in the blob cannot be verified against the sequencer
 ///   blob's `rollup_transaction_root`.
 fn convert_and_filter_rollup_blobs(
     blobs: Vec<Blob>,
+    height: u64,
     namespace: Namespace,
     sequencer_blob: &CelestiaSequencerBlob,
+    events: &dyn EventSink,
 ) -> Vec<CelestiaRollupBlob> {
     let mut rollups = Vec::with_capacity(blobs.len());
     for blob in blobs {
+        let reject = |reason| Event::BlobRejected {
+            height,
+            namespace: blob.namespace,
+            commitment: blob.commitment.clone(),
+            reason,
+        };
         if blob.namespace != namespace {
             debug!("blob does not belong to expected namespace; skipping");
+            events.emit(reject(RejectReason::WrongNamespace));
             continue;
         }
         let proto_blob =
@@ -264,6 +786,7 @@ fn convert_and_filter_rollup_blobs(
                         blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
                         "failed decoding blob as protobuf; skipping"
                     );
+                    events.emit(reject(RejectReason::Deserialization));
                     continue;
                 }
                 Ok(proto_blob) => proto_blob,
@@ -275,6 +798,7 @@ fn convert_and_filter_rollup_blobs(
                     blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
                     "failed converting raw protobuf blob to native type; skipping"
                 );
+                events.emit(reject(RejectReason::Conversion));
                 continue;
             }
             Ok(rollup_blob) => rollup_blob,
@@ -286,6 +810,7 @@ fn convert_and_filter_rollup_blobs(
                 "block hash in rollup blob does not match block hash in sequencer blob; dropping \
                  blob"
             );
+            events.emit(reject(RejectReason::BlockHashMismatch));
             continue;
         }
         if !does_rollup_blob_verify_against_sequencer_blob(&rollup_blob, sequencer_blob) {
@@ -293,13 +818,180 @@ fn convert_and_filter_rollup_blobs(
                 "the rollup blob proof applied to its chain ID and transactions did not match the \
                  rollup transactions root in the sequencer blob; dropping the blob"
             );
+            events.emit(reject(RejectReason::InvalidProof));
             continue;
         }
+        events.emit(Event::BlobVerified {
+            height,
+            namespace: blob.namespace,
+            block_hash: rollup_blob.sequencer_block_hash(),
+            proposer: *sequencer_blob.header().proposer_address(),
+        });
         rollups.push(rollup_blob);
     }
     rollups
 }
 
+/// Attempts to convert the bytes stored in the celestia blobs to [`CelestiaAuxiliaryBlob`].
+///
+/// Drops a blob under the following conditions:
+/// + the blob's namespace does not match the provided [`Namespace`]
+/// + cannot be decompressed, decoded, or converted to [`CelestiaAuxiliaryBlob`]
+/// + block hash does not match that of [`CelestiaSequencerBlob`]
+fn convert_and_filter_auxiliary_blobs(
+    blobs: Vec<Blob>,
+    height: u64,
+    namespace: Namespace,
+    sequencer_blob: &CelestiaSequencerBlob,
+    events: &dyn EventSink,
+) -> Vec<CelestiaAuxiliaryBlob> {
+    let mut auxiliary_blobs = Vec::with_capacity(blobs.len());
+    for blob in blobs {
+        let reject = |reason| Event::BlobRejected {
+            height,
+            namespace: blob.namespace,
+            commitment: blob.commitment.clone(),
+            reason,
+        };
+        if blob.namespace != namespace {
+            debug!("blob does not belong to expected namespace; skipping");
+            events.emit(reject(RejectReason::WrongNamespace));
+            continue;
+        }
+        let Ok(data) = astria_core::brotli::decompress_bytes(&blob.data) else {
+            debug!(
+                blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
+                "failed decompressing blob; skipping"
+            );
+            events.emit(reject(RejectReason::Deserialization));
+            continue;
+        };
+        let proto_blob =
+            match astria_core::generated::sequencerblock::v1alpha1::CelestiaAuxiliaryBlob::decode(
+                &*data,
+            ) {
+                Err(e) => {
+                    debug!(
+                        error = &e as &dyn std::error::Error,
+                        target = "astria.sequencerblock.v1alpha1.CelestiaAuxiliaryBlob",
+                        blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
+                        "failed decoding blob as protobuf; skipping"
+                    );
+                    events.emit(reject(RejectReason::Deserialization));
+                    continue;
+                }
+                Ok(proto_blob) => proto_blob,
+            };
+        let auxiliary_blob = match CelestiaAuxiliaryBlob::try_from_raw(proto_blob) {
+            Err(e) => {
+                debug!(
+                    error = &e as &dyn std::error::Error,
+                    blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
+                    "failed converting raw protobuf blob to native type; skipping"
+                );
+                events.emit(reject(RejectReason::Conversion));
+                continue;
+            }
+            Ok(auxiliary_blob) => auxiliary_blob,
+        };
+        if auxiliary_blob.sequencer_block_hash() != sequencer_blob.block_hash() {
+            debug!(
+                block_hash.auxiliary = hex::encode(auxiliary_blob.sequencer_block_hash()),
+                block_hash.sequencer = hex::encode(sequencer_blob.block_hash()),
+                "block hash in auxiliary blob does not match block hash in sequencer blob; \
+                 dropping blob"
+            );
+            events.emit(reject(RejectReason::BlockHashMismatch));
+            continue;
+        }
+        events.emit(Event::BlobVerified {
+            height,
+            namespace: blob.namespace,
+            block_hash: auxiliary_blob.sequencer_block_hash(),
+            proposer: *sequencer_blob.header().proposer_address(),
+        });
+        auxiliary_blobs.push(auxiliary_blob);
+    }
+    auxiliary_blobs
+}
+
+/// Attempts to convert the bytes stored in the celestia blobs to [`RollupResultNamespaceData`].
+///
+/// Drops a blob under the following conditions:
+/// + the blob's namespace is not the result namespace of `rollup_id`
+/// + cannot be decompressed, decoded, or converted to [`RollupResultNamespaceData`]
+/// + the rollup ID recorded in the blob is not `rollup_id`
+fn convert_and_filter_rollup_results(
+    blobs: Vec<Blob>,
+    height: u64,
+    rollup_id: RollupId,
+    events: &dyn EventSink,
+) -> Vec<RollupResultNamespaceData> {
+    let namespace = astria_core::celestia::rollup_result_namespace(rollup_id);
+    let mut results = Vec::with_capacity(blobs.len());
+    for blob in blobs {
+        let reject = |reason| Event::BlobRejected {
+            height,
+            namespace: blob.namespace,
+            commitment: blob.commitment.clone(),
+            reason,
+        };
+        if blob.namespace != namespace {
+            debug!("blob does not belong to expected namespace; skipping");
+            events.emit(reject(RejectReason::WrongNamespace));
+            continue;
+        }
+        let Ok(data) = astria_core::brotli::decompress_bytes(&blob.data) else {
+            debug!(
+                blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
+                "failed decompressing blob; skipping"
+            );
+            events.emit(reject(RejectReason::Deserialization));
+            continue;
+        };
+        let proto_blob = match RawRollupResultNamespaceData::decode(&*data) {
+            Err(e) => {
+                debug!(
+                    error = &e as &dyn std::error::Error,
+                    target = "astria.sequencerblock.v1alpha1.RollupResultNamespaceData",
+                    blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
+                    "failed decoding blob as protobuf; skipping"
+                );
+                events.emit(reject(RejectReason::Deserialization));
+                continue;
+            }
+            Ok(proto_blob) => proto_blob,
+        };
+        let result = match RollupResultNamespaceData::try_from_raw(proto_blob) {
+            Err(e) => {
+                debug!(
+                    error = &e as &dyn std::error::Error,
+                    blob.commitment = %Base64Display::new(&blob.commitment.0, &STANDARD),
+                    "failed converting raw protobuf blob to native type; skipping"
+                );
+                events.emit(reject(RejectReason::Conversion));
+                continue;
+            }
+            Ok(result) => result,
+        };
+        if result.rollup_id() != rollup_id {
+            debug!(
+                rollup_id.blob = %result.rollup_id(),
+                "rollup result belongs to a different rollup; skipping"
+            );
+            events.emit(reject(RejectReason::Filtered));
+            continue;
+        }
+        results.push(result);
+    }
+    results
+}
+
+/// Returns if the rollup ID and transactions of `rollup_blob` are included in the rollup
+/// transactions root of `sequencer_blob`.
+///
+/// The transactions are hashed into a merkle tree in the order they are stored in the blob,
+/// which makes this check sensitive to their order.
 fn does_rollup_blob_verify_against_sequencer_blob(
     rollup_blob: &CelestiaRollupBlob,
     sequencer_blob: &CelestiaSequencerBlob,
@@ -0,0 +1,213 @@
+//! Decoding rollup transaction payloads into typed structures.
+//!
+//! Sequencer treats rollup transactions as opaque bytes, but different rollups encode their
+//! payloads differently. A [`DecoderRegistry`] maps the Celestia namespace of a rollup to the
+//! [`PayloadDecoder`] that understands its encoding. Retrieval uses it through
+//! [`CelestiaClientExt::get_decoded_rollup_blobs_matching_sequencer_blob`](crate::CelestiaClientExt::get_decoded_rollup_blobs_matching_sequencer_blob).
+//! Rollups without a registered decoder are returned as raw bytes only.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use astria_core::{
+    primitive::v1::RollupId,
+    sequencerblock::v1alpha1::CelestiaRollupBlob,
+};
+use celestia_types::nmt::Namespace;
+
+use crate::celestia_namespace_v0_from_rollup_id;
+
+/// A rollup transaction decoded by a [`PayloadDecoder`].
+///
+/// Use [`DecodedPayload::downcast_ref`] to recover the concrete type the decoder produced.
+pub struct DecodedPayload(Box<dyn Any + Send + Sync>);
+
+impl DecodedPayload {
+    /// Wraps a decoded value.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// Returns a reference to the decoded value if it is of type `T`.
+    #[must_use]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+
+    /// Returns the decoded value if it is of type `T`, or `self` if it is not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` if the decoded value is not of type `T`.
+    pub fn downcast<T: Any>(self) -> Result<T, Self> {
+        self.0.downcast().map(|value| *value).map_err(Self)
+    }
+}
+
+impl std::fmt::Debug for DecodedPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DecodedPayload").finish_non_exhaustive()
+    }
+}
+
+/// An error returned by a [`PayloadDecoder`].
+#[derive(Debug, thiserror::Error)]
+#[error("decoder `{decoder}` failed decoding the rollup transaction")]
+pub struct DecodeError {
+    decoder: &'static str,
+    #[source]
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl DecodeError {
+    /// Constructs an error for the decoder named `decoder`.
+    pub fn new<E>(decoder: &'static str, source: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self {
+            decoder,
+            source: source.into(),
+        }
+    }
+
+    /// Returns the name of the decoder that failed.
+    #[must_use]
+    pub fn decoder(&self) -> &'static str {
+        self.decoder
+    }
+}
+
+/// Decodes the transactions of a rollup into typed structures.
+pub trait PayloadDecoder: Send + Sync {
+    /// A human readable name for the decoder, used in errors and logs.
+    fn name(&self) -> &'static str;
+
+    /// Decodes a single rollup transaction.
+    ///
+    /// # Errors
+    ///
+    /// Implementations should return an error if `payload` is not valid in their encoding.
+    fn decode(&self, payload: &[u8]) -> Result<DecodedPayload, DecodeError>;
+}
+
+/// Decodes rollup transactions as protobuf messages of type `T`.
+pub struct ProtobufDecoder<T> {
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T> ProtobufDecoder<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ProtobufDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PayloadDecoder for ProtobufDecoder<T>
+where
+    T: prost::Message + Default + Send + Sync + 'static,
+{
+    fn name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn decode(&self, payload: &[u8]) -> Result<DecodedPayload, DecodeError> {
+        T::decode(payload)
+            .map(DecodedPayload::new)
+            .map_err(|err| DecodeError::new(self.name(), err))
+    }
+}
+
+/// Maps Celestia namespaces to the decoder for the rollup posting under them.
+#[derive(Clone, Default)]
+pub struct DecoderRegistry {
+    decoders: HashMap<Namespace, Arc<dyn PayloadDecoder>>,
+}
+
+impl DecoderRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `decoder` for `namespace`, returning the decoder previously registered for it.
+    pub fn register<D>(
+        &mut self,
+        namespace: Namespace,
+        decoder: D,
+    ) -> Option<Arc<dyn PayloadDecoder>>
+    where
+        D: PayloadDecoder + 'static,
+    {
+        self.decoders.insert(namespace, Arc::new(decoder))
+    }
+
+    /// Registers `decoder` for the namespace derived from `rollup_id`.
+    pub fn register_rollup<D>(
+        &mut self,
+        rollup_id: RollupId,
+        decoder: D,
+    ) -> Option<Arc<dyn PayloadDecoder>>
+    where
+        D: PayloadDecoder + 'static,
+    {
+        self.register(celestia_namespace_v0_from_rollup_id(rollup_id), decoder)
+    }
+
+    /// Returns the decoder registered for `namespace`.
+    #[must_use]
+    pub fn get(&self, namespace: &Namespace) -> Option<&dyn PayloadDecoder> {
+        self.decoders.get(namespace).map(AsRef::as_ref)
+    }
+
+    /// Decodes the transactions of `blob` using the decoder registered for `namespace`.
+    ///
+    /// If no decoder is registered, the blob is returned without decoded transactions.
+    #[must_use]
+    pub fn decode(&self, namespace: &Namespace, blob: CelestiaRollupBlob) -> DecodedRollupBlob {
+        let transactions = self.get(namespace).map(|decoder| {
+            blob.transactions()
+                .iter()
+                .map(|tx| decoder.decode(tx))
+                .collect()
+        });
+        DecodedRollupBlob {
+            blob,
+            transactions,
+        }
+    }
+}
+
+impl std::fmt::Debug for DecoderRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.decoders
+                    .iter()
+                    .map(|(namespace, decoder)| (namespace, decoder.name())),
+            )
+            .finish()
+    }
+}
+
+/// A rollup blob together with its decoded transactions.
+#[derive(Debug)]
+pub struct DecodedRollupBlob {
+    /// The rollup blob as read from Celestia.
+    pub blob: CelestiaRollupBlob,
+    /// The decoded transactions in the same order as `blob.transactions()`, or `None` if no
+    /// decoder was registered for the blob's namespace.
+    pub transactions: Option<Vec<Result<DecodedPayload, DecodeError>>>,
+}
@@ -0,0 +1,168 @@
+//! Content-addressed deduplication of rollup blobs.
+//!
+//! When the same sequencer block is submitted more than once, for example because an upstream
+//! component retried, its rollup blobs carry exactly the same bytes as the first time. Paying
+//! for them again buys nothing. [`PostedBlobs`] remembers where the rollup blobs of earlier
+//! submissions were included, keyed by the hash of their namespace and data. A repeated blob is
+//! then replaced by a small [`BlobReference`] that points to the Celestia height and commitment
+//! of its first inclusion.
+//!
+//! The sequencer-relayer replaces repeated rollup blobs by references if its rollup blob
+//! deduplication is enabled, as does
+//! [`CelestiaClientExt::submit_sequencer_block_deduplicated`](crate::CelestiaClientExt::submit_sequencer_block_deduplicated).
+//!
+//! References are resolved transparently on read by
+//! [`CelestiaClientExt::get_rollup_blobs_matching_sequencer_blob`](crate::CelestiaClientExt::get_rollup_blobs_matching_sequencer_blob)
+//! and by conductor, which fetch the blob a reference points to before verifying it. Readers
+//! that decode blobs themselves without resolving references reject them as undecodable, so
+//! deduplication should only be enabled when all readers of the namespaces resolve them.
+
+use std::collections::{
+    hash_map::Entry,
+    HashMap,
+    VecDeque,
+};
+
+use celestia_types::{
+    nmt::Namespace,
+    Blob,
+    Commitment,
+};
+use sha2::{
+    Digest as _,
+    Sha256,
+};
+
+/// The prefix marking the data of a blob as a [`BlobReference`].
+///
+/// References are posted uncompressed, while the data of all other blobs Astria writes to
+/// Celestia is brotli compressed. References must therefore be decoded before decompressing.
+/// Compressed data is only mistaken for a reference if it is exactly as long as one and its
+/// first 16 bytes happen to spell out this prefix.
+const REFERENCE_MAGIC: &[u8; 16] = b"astria/blob-ref1";
+
+/// The length of an encoded [`BlobReference`]: the prefix, the height, and the commitment.
+const ENCODED_REFERENCE_LEN: usize = REFERENCE_MAGIC.len() + 8 + 32;
+
+/// Points to a blob included at an earlier Celestia height under the same namespace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobReference {
+    /// The Celestia height the referenced blob was included at.
+    pub celestia_height: u64,
+    /// The commitment of the referenced blob.
+    pub commitment: Commitment,
+}
+
+impl BlobReference {
+    /// Encodes the reference as the data of a blob.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(ENCODED_REFERENCE_LEN);
+        data.extend_from_slice(REFERENCE_MAGIC);
+        data.extend_from_slice(&self.celestia_height.to_be_bytes());
+        data.extend_from_slice(&self.commitment.0);
+        data
+    }
+
+    /// Decodes a reference from the data of a blob.
+    ///
+    /// Returns `None` if `data` is not an encoded reference.
+    #[must_use]
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() != ENCODED_REFERENCE_LEN {
+            return None;
+        }
+        let rest = data.strip_prefix(REFERENCE_MAGIC.as_slice())?;
+        let (height, commitment) = rest.split_at(8);
+        Some(Self {
+            celestia_height: u64::from_be_bytes(height.try_into().expect("the split is 8 bytes")),
+            commitment: Commitment(commitment.try_into().expect("the remainder is 32 bytes")),
+        })
+    }
+}
+
+/// The rollup blobs included in earlier submissions, used to replace repeated blobs by
+/// [`BlobReference`]s.
+///
+/// Only the most recent `capacity` blobs are remembered.
+#[derive(Debug)]
+pub struct PostedBlobs {
+    capacity: usize,
+    references: HashMap<[u8; 32], BlobReference>,
+    /// The keys of `references` in the order they were recorded, oldest first.
+    order: VecDeque<[u8; 32]>,
+}
+
+impl PostedBlobs {
+    /// Returns an empty record remembering at most `capacity` blobs.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            references: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of remembered blobs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.references.len()
+    }
+
+    /// Returns if no blobs are remembered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty()
+    }
+
+    /// Remembers that `blobs` were included at `celestia_height`.
+    ///
+    /// References are not remembered, so that a reference always points to a full blob. Blobs
+    /// that are already remembered keep pointing to their first inclusion.
+    pub fn record(&mut self, celestia_height: u64, blobs: &[Blob]) {
+        for blob in blobs {
+            if BlobReference::decode(&blob.data).is_some() {
+                continue;
+            }
+            let key = content_key(blob.namespace, &blob.data);
+            if let Entry::Vacant(entry) = self.references.entry(key) {
+                entry.insert(BlobReference {
+                    celestia_height,
+                    commitment: blob.commitment.clone(),
+                });
+                self.order.push_back(key);
+            }
+        }
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.references.remove(&oldest);
+            }
+        }
+    }
+
+    /// Returns the reference to an earlier inclusion of `blob`, if one is remembered.
+    #[must_use]
+    pub fn find(&self, blob: &Blob) -> Option<&BlobReference> {
+        self.references.get(&content_key(blob.namespace, &blob.data))
+    }
+
+    /// Replaces `blob` by a reference to its earlier inclusion, if one is remembered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference blob could not be constructed.
+    pub fn deduplicate(&self, blob: Blob) -> Result<Blob, celestia_types::Error> {
+        match self.find(&blob) {
+            Some(reference) => Blob::new(blob.namespace, reference.encode()),
+            None => Ok(blob),
+        }
+    }
+}
+
+fn content_key(namespace: Namespace, data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(namespace.as_bytes());
+    hasher.update(data);
+    hasher.finalize().into()
+}
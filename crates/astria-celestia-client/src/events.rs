@@ -0,0 +1,200 @@
+//! Typed events emitted while retrieving blobs from and submitting blobs to Celestia.
+//!
+//! The methods of [`CelestiaClientExt`](crate::CelestiaClientExt) report what happened to each
+//! blob to an [`EventSink`]. [`TracingSink`] turns the events into `tracing` events and is what
+//! most callers want. [`ChannelSink`] forwards them over a channel so that programmatic
+//! consumers like dashboards can act on them.
+
+use std::sync::Arc;
+
+use astria_core::{
+    celestia::HexNamespace,
+    primitive::v1::BlockHash,
+    signers::KnownSigners,
+};
+use celestia_types::{
+    nmt::Namespace,
+    Commitment,
+};
+use telemetry::display::base64;
+use tokio::sync::mpsc;
+use tracing::{
+    debug,
+    info,
+};
+
+/// An event emitted by the retrieval and submission paths.
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// A blob was decoded and verified.
+    BlobVerified {
+        /// The Celestia height the blob was read from.
+        height: u64,
+        /// The namespace the blob was stored under.
+        namespace: Namespace,
+        /// The hash of the sequencer block the blob belongs to.
+        block_hash: [u8; 32],
+        /// The address of the validator that proposed the sequencer block.
+        proposer: tendermint::account::Id,
+    },
+    /// A blob was dropped.
+    BlobRejected {
+        /// The Celestia height the blob was read from.
+        height: u64,
+        /// The namespace the blob was stored under.
+        namespace: Namespace,
+        /// The share commitment of the blob.
+        commitment: Commitment,
+        /// Why the blob was dropped.
+        reason: RejectReason,
+    },
+    /// Blobs of a sequencer block were included in a Celestia block.
+    SubmissionConfirmed {
+        /// The Celestia height the blobs were included at.
+        height: u64,
+        /// The height of the submitted sequencer block.
+        sequencer_height: tendermint::block::Height,
+        /// The number of blobs that were submitted.
+        number_of_blobs: usize,
+    },
+}
+
+/// The reason a blob was dropped during retrieval.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectReason {
+    /// The blob was stored under a different namespace than requested.
+    WrongNamespace,
+    /// The blob could not be decoded as protobuf.
+    Deserialization,
+    /// The decoded blob could not be converted to its native type.
+    Conversion,
+    /// The blob was well formed but did not match the requested filter.
+    Filtered,
+    /// The block hash stored in a rollup blob did not match that of its sequencer blob.
+    BlockHashMismatch,
+    /// The proof stored in a rollup blob did not verify against its sequencer blob.
+    InvalidProof,
+    /// The blob was a reference to an earlier blob that could not be fetched.
+    UnresolvedReference,
+}
+
+impl RejectReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::WrongNamespace => "wrong namespace",
+            Self::Deserialization => "failed decoding protobuf",
+            Self::Conversion => "failed converting to native type",
+            Self::Filtered => "did not match filter",
+            Self::BlockHashMismatch => "block hash did not match sequencer blob",
+            Self::InvalidProof => "proof did not verify against sequencer blob",
+            Self::UnresolvedReference => "referenced blob could not be fetched",
+        }
+    }
+}
+
+impl std::fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Receives the events emitted by the retrieval and submission paths.
+///
+/// Implementations are called inline and must not block.
+pub trait EventSink: Send + Sync {
+    fn emit(&self, event: Event);
+}
+
+/// Emits all events as `tracing` events.
+#[derive(Clone, Debug, Default)]
+pub struct TracingSink {
+    known_signers: Arc<KnownSigners>,
+}
+
+impl TracingSink {
+    /// Returns a sink that reports the proposers of sequencer blocks by their labels in
+    /// `known_signers`.
+    #[must_use]
+    pub fn with_known_signers(known_signers: Arc<KnownSigners>) -> Self {
+        Self {
+            known_signers,
+        }
+    }
+}
+
+impl EventSink for TracingSink {
+    fn emit(&self, event: Event) {
+        match event {
+            Event::BlobVerified {
+                height,
+                namespace,
+                block_hash,
+                proposer,
+            } => debug!(
+                height,
+                namespace = %HexNamespace(namespace),
+                block_hash = %BlockHash::new(block_hash),
+                proposer = %self.known_signers.display(&proposer),
+                "blob verified",
+            ),
+            Event::BlobRejected {
+                height,
+                namespace,
+                commitment,
+                reason,
+            } => debug!(
+                height,
+                namespace = %HexNamespace(namespace),
+                commitment = %base64(&commitment.0),
+                %reason,
+                "blob rejected",
+            ),
+            Event::SubmissionConfirmed {
+                height,
+                sequencer_height,
+                number_of_blobs,
+            } => info!(
+                height,
+                %sequencer_height,
+                number_of_blobs,
+                "submission confirmed",
+            ),
+        }
+    }
+}
+
+/// Forwards all events over a bounded channel.
+///
+/// Events are dropped if the channel is full or the receiver was dropped, so that a slow consumer
+/// never holds up retrieval or submission.
+#[derive(Clone, Debug)]
+pub struct ChannelSink {
+    sender: mpsc::Sender<Event>,
+}
+
+impl ChannelSink {
+    /// Returns a new sink together with the receiving end of its channel, which holds up to
+    /// `capacity` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[must_use]
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<Event>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        (
+            Self {
+                sender,
+            },
+            receiver,
+        )
+    }
+}
+
+impl EventSink for ChannelSink {
+    fn emit(&self, event: Event) {
+        if let Err(error) = self.sender.try_send(event) {
+            debug!(%error, "failed forwarding event to channel; dropping it");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Reading the signed heartbeats posted by sequencer-relayers.
+//!
+//! Relayers with heartbeats enabled periodically post a [`SignedHeartbeat`] under a dedicated
+//! namespace. A [`HeartbeatReport`] collects the heartbeats found over a range of Celestia
+//! heights per relayer, so that monitoring agents can tell which relayers are alive and how far
+//! each of them lags behind. It is created by
+//! [`CelestiaClientExt::get_heartbeat_report`](crate::CelestiaClientExt::get_heartbeat_report).
+//!
+//! Relayers are identified by the key that signed their heartbeats. Blobs that are not signed
+//! heartbeats, or whose signature does not verify, are only counted.
+
+use std::{
+    collections::HashMap,
+    ops::RangeInclusive,
+};
+
+use astria_core::heartbeat::SignedHeartbeat;
+use tracing::debug;
+
+use crate::client::RawBlob;
+
+/// The latest heartbeat of a single relayer.
+#[derive(Clone, Debug)]
+pub struct RelayerLiveness {
+    /// The address the relayer reported in its heartbeats.
+    pub relayer_address: String,
+    /// The number of valid heartbeats found.
+    pub heartbeats: usize,
+    /// The Celestia height at which the latest heartbeat was included.
+    pub last_heartbeat_height: u64,
+    /// The latest sequencer height the relayer reported as confirmed on Celestia.
+    pub last_relayed_sequencer_height: Option<u64>,
+    /// The version the relayer reported in its latest heartbeat.
+    pub version: String,
+}
+
+/// The heartbeats found in a namespace over a range of Celestia heights.
+#[derive(Clone, Debug)]
+pub struct HeartbeatReport {
+    /// The Celestia heights that were searched.
+    pub heights: RangeInclusive<u64>,
+    /// The latest heartbeat of each relayer, keyed by the ed25519 verification key that signed
+    /// it.
+    pub relayers: HashMap<[u8; 32], RelayerLiveness>,
+    /// The number of blobs that were not validly signed heartbeats.
+    pub invalid_blobs: usize,
+}
+
+impl HeartbeatReport {
+    pub(crate) fn new(heights: RangeInclusive<u64>) -> Self {
+        Self {
+            heights,
+            relayers: HashMap::new(),
+            invalid_blobs: 0,
+        }
+    }
+
+    /// Adds the heartbeats found at `height` to the report.
+    ///
+    /// Heights must be recorded in ascending order.
+    pub(crate) fn record(&mut self, height: u64, blobs: Vec<RawBlob>) {
+        for blob in blobs {
+            let verified = SignedHeartbeat::decode(&blob.data).and_then(|signed| {
+                let payload = signed.verify()?;
+                Ok((signed.verification_key(), payload))
+            });
+            let (verification_key, payload) = match verified {
+                Ok(verified) => verified,
+                Err(error) => {
+                    debug!(
+                        height,
+                        %error,
+                        "skipping blob in heartbeat namespace that is not a valid heartbeat"
+                    );
+                    self.invalid_blobs = self.invalid_blobs.saturating_add(1);
+                    continue;
+                }
+            };
+            let heartbeats = self
+                .relayers
+                .get(&verification_key)
+                .map_or(0, |liveness| liveness.heartbeats);
+            self.relayers.insert(
+                verification_key,
+                RelayerLiveness {
+                    relayer_address: payload.relayer_address,
+                    heartbeats: heartbeats.saturating_add(1),
+                    last_heartbeat_height: height,
+                    last_relayed_sequencer_height: payload.last_relayed_sequencer_height,
+                    version: payload.version,
+                },
+            );
+        }
+    }
+
+    /// Returns the greatest sequencer height reported by any relayer.
+    #[must_use]
+    pub fn latest_sequencer_height(&self) -> Option<u64> {
+        self.relayers
+            .values()
+            .filter_map(|liveness| liveness.last_relayed_sequencer_height)
+            .max()
+    }
+
+    /// Returns the number of Celestia blocks between the latest heartbeat of `relayer` and the
+    /// end of the searched heights.
+    #[must_use]
+    pub fn celestia_lag(&self, relayer: &RelayerLiveness) -> u64 {
+        self.heights
+            .end()
+            .saturating_sub(relayer.last_heartbeat_height)
+    }
+
+    /// Returns the number of sequencer blocks that `relayer` is behind the relayer reporting the
+    /// greatest sequencer height.
+    ///
+    /// Returns `None` if `relayer` has not reported a sequencer height.
+    #[must_use]
+    pub fn sequencer_lag(&self, relayer: &RelayerLiveness) -> Option<u64> {
+        let latest = self.latest_sequencer_height()?;
+        let relayed = relayer.last_relayed_sequencer_height?;
+        Some(latest.saturating_sub(relayed))
+    }
+}
@@ -0,0 +1,226 @@
+//! An index of the sequencer blocks written to Celestia, for archives and block explorers.
+//!
+//! [`Indexer`] walks Celestia heights, verifies the sequencer blobs found under the sequencer
+//! namespace, and records each sequencer block in a [`Store`] backed by sqlite. The store maps
+//! sequencer block hashes and heights to the Celestia height the block was written at, the
+//! namespaces of the block and its rollups, the size of the sequencer blob, and the proposer of
+//! the block. [`IndexServer`] serves the store over the `astria.daindex.v1alpha1.DaIndexService`
+//! gRPC service, and [`Pruner`] removes blocks from it according to a [`RetentionPolicy`].
+//!
+//! This module is only available with the `indexer` feature.
+
+use std::sync::Arc;
+
+use astria_core::sequencerblock::v1alpha1::{
+    CelestiaEmptyBlocksMarker,
+    CelestiaSequencerBlob,
+};
+use celestia_types::nmt::Namespace;
+use prost::Message as _;
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::{
+    celestia_namespace_v0_from_rollup_id,
+    events::{
+        Event,
+        EventSink,
+        RejectReason,
+    },
+    CelestiaClientExt,
+};
+
+mod retention;
+mod service;
+mod store;
+
+pub use retention::{
+    Pruned,
+    Pruner,
+    RetentionPolicy,
+    DEFAULT_PRUNE_INTERVAL,
+};
+pub use service::IndexServer;
+pub use store::{
+    IndexedBlock,
+    Store,
+    StoreError,
+};
+
+/// An error while indexing a Celestia height.
+#[derive(Debug, thiserror::Error)]
+pub enum IndexError {
+    #[error("failed fetching blobs from Celestia")]
+    Fetch(#[source] jsonrpsee::core::Error),
+    #[error("failed writing to the index")]
+    Store(#[source] StoreError),
+}
+
+/// Walks Celestia heights and records the sequencer blocks found at each of them.
+pub struct Indexer<C> {
+    client: C,
+    sequencer_namespace: Namespace,
+    store: Arc<Store>,
+}
+
+impl<C: CelestiaClientExt + Sync> Indexer<C> {
+    /// Creates an indexer reading sequencer blobs under `sequencer_namespace` using `client` and
+    /// writing them to `store`.
+    ///
+    /// The store is shared so that it can be served by an [`IndexServer`] at the same time.
+    pub fn new(client: C, sequencer_namespace: Namespace, store: Arc<Store>) -> Self {
+        Self {
+            client,
+            sequencer_namespace,
+            store,
+        }
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Returns the Celestia height the indexer should continue from.
+    ///
+    /// This is one above the highest Celestia height recorded in the store, or `start` if the
+    /// store has not recorded a height at or above it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store could not be read.
+    pub fn next_height(&self, start: u64) -> Result<u64, StoreError> {
+        Ok(self
+            .store
+            .latest_celestia_height()?
+            .map_or(start, |latest| std::cmp::max(start, latest.saturating_add(1))))
+    }
+
+    /// Indexes all Celestia heights in `from..=to`.
+    ///
+    /// Returns the number of sequencer blocks recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fetching the blobs at a height or writing to the store failed. All
+    /// heights before the failing one are indexed.
+    pub async fn index_range(
+        &self,
+        from: u64,
+        to: u64,
+        events: &dyn EventSink,
+    ) -> Result<usize, IndexError> {
+        let mut total = 0;
+        for height in from..=to {
+            total += self.index_height(height, events).await?;
+        }
+        info!(from, to, total, "indexed Celestia heights");
+        Ok(total)
+    }
+
+    /// Indexes the sequencer blobs at Celestia `height`.
+    ///
+    /// Blobs that cannot be decoded or verified are skipped and reported to `events`. Returns
+    /// the number of sequencer blocks recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fetching the blobs or writing to the store failed.
+    #[instrument(skip(self, events))]
+    pub async fn index_height(
+        &self,
+        height: u64,
+        events: &dyn EventSink,
+    ) -> Result<usize, IndexError> {
+        let raw_blobs = self
+            .client
+            .get_raw_blobs(height, &[self.sequencer_namespace])
+            .await
+            .map_err(IndexError::Fetch)?;
+
+        let mut blocks = Vec::with_capacity(raw_blobs.len());
+        for raw_blob in raw_blobs {
+            let reject = |reason| Event::BlobRejected {
+                height,
+                namespace: raw_blob.namespace,
+                commitment: raw_blob.commitment.clone(),
+                reason,
+            };
+            let Some(sequencer_blobs) = decode_sequencer_blobs(&raw_blob.data) else {
+                debug!("failed decoding sequencer blob; skipping it");
+                events.emit(reject(RejectReason::Deserialization));
+                continue;
+            };
+            for sequencer_blob in sequencer_blobs {
+                events.emit(Event::BlobVerified {
+                    height,
+                    namespace: raw_blob.namespace,
+                    block_hash: sequencer_blob.block_hash(),
+                    proposer: *sequencer_blob.header().proposer_address(),
+                });
+                blocks.push(IndexedBlock::new(
+                    &sequencer_blob,
+                    height,
+                    raw_blob.namespace,
+                    raw_blob.size,
+                ));
+            }
+        }
+
+        self.store
+            .insert_height(height, &blocks)
+            .map_err(IndexError::Store)?;
+        Ok(blocks.len())
+    }
+}
+
+/// Decompresses, decodes, and verifies a sequencer blob, or a marker of empty blocks which is
+/// expanded into their sequencer blobs.
+///
+/// Every block of a marker is indexed with the size of the whole marker blob.
+fn decode_sequencer_blobs(data: &[u8]) -> Option<Vec<CelestiaSequencerBlob>> {
+    let data = astria_core::brotli::decompress_bytes(data).ok()?;
+    let sequencer_blob =
+        astria_core::generated::sequencerblock::v1alpha1::CelestiaSequencerBlob::decode(&*data)
+            .ok()
+            .and_then(|raw| CelestiaSequencerBlob::try_from_raw(raw).ok());
+    if let Some(sequencer_blob) = sequencer_blob {
+        return Some(vec![sequencer_blob]);
+    }
+    let raw =
+        astria_core::generated::sequencerblock::v1alpha1::CelestiaEmptyBlocksMarker::decode(&*data)
+            .ok()?;
+    CelestiaEmptyBlocksMarker::try_from_raw(raw)
+        .ok()
+        .map(CelestiaEmptyBlocksMarker::into_empty_blocks)
+}
+
+impl IndexedBlock {
+    fn new(
+        blob: &CelestiaSequencerBlob,
+        celestia_height: u64,
+        namespace: Namespace,
+        size: usize,
+    ) -> Self {
+        let rollup_namespaces = blob
+            .clone()
+            .into_unchecked()
+            .rollup_ids
+            .into_iter()
+            .map(celestia_namespace_v0_from_rollup_id)
+            .collect();
+        Self {
+            block_hash: blob.block_hash(),
+            sequencer_height: blob.height().value(),
+            chain_id: blob.cometbft_chain_id().to_string(),
+            celestia_height,
+            namespace,
+            rollup_namespaces,
+            size,
+            proposer: blob.header().proposer_address().as_bytes().to_vec(),
+        }
+    }
+}
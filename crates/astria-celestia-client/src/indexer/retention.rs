@@ -0,0 +1,138 @@
+//! Retention of the blocks recorded in the index.
+//!
+//! An index that follows Celestia for a long time grows without bound. A [`RetentionPolicy`]
+//! bounds it by the age and the number of the recorded blocks, and [`Pruner`] applies the policy
+//! to a [`Store`] in the background.
+
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
+use tracing::{
+    debug,
+    info,
+    instrument,
+    warn,
+};
+
+use super::{
+    Store,
+    StoreError,
+};
+
+/// The interval at which [`Pruner::new`] applies the retention policy.
+pub const DEFAULT_PRUNE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Which of the recorded blocks are kept in the index.
+///
+/// The default policy keeps every block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Blocks written at Celestia heights that were indexed longer than this ago are removed.
+    pub max_age: Option<Duration>,
+    /// Only this many of the most recently written blocks are kept.
+    pub max_blocks: Option<u64>,
+    /// The number of Celestia heights below the latest indexed height that a block must have
+    /// been written at before it is considered confirmed. Blocks that are not confirmed are
+    /// never removed, regardless of `max_age` and `max_blocks`.
+    pub confirmation_depth: u64,
+}
+
+impl RetentionPolicy {
+    /// Returns if the policy keeps every block.
+    #[must_use]
+    pub fn retains_everything(&self) -> bool {
+        self.max_age.is_none() && self.max_blocks.is_none()
+    }
+}
+
+/// The number of entries removed from the index by a single pruning run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pruned {
+    /// The number of sequencer blocks removed.
+    pub blocks: usize,
+    /// The number of indexed Celestia heights removed that no longer had any blocks.
+    pub heights: usize,
+}
+
+/// Periodically removes the blocks from a [`Store`] that its [`RetentionPolicy`] does not keep.
+pub struct Pruner {
+    store: Arc<Store>,
+    policy: RetentionPolicy,
+    interval: Duration,
+}
+
+impl Pruner {
+    /// Creates a pruner applying `policy` to `store` every [`DEFAULT_PRUNE_INTERVAL`].
+    pub fn new(store: Arc<Store>, policy: RetentionPolicy) -> Self {
+        Self {
+            store,
+            policy,
+            interval: DEFAULT_PRUNE_INTERVAL,
+        }
+    }
+
+    /// Sets the interval at which the policy is applied.
+    #[must_use]
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self {
+            interval,
+            ..self
+        }
+    }
+
+    /// Applies the policy once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if pruning the store failed.
+    #[instrument(skip_all)]
+    pub async fn prune_once(&self) -> Result<Pruned, StoreError> {
+        let store = Arc::clone(&self.store);
+        let policy = self.policy.clone();
+        let (pruned, retained) = tokio::task::spawn_blocking(move || {
+            let pruned = store.prune(&policy, SystemTime::now())?;
+            let retained = store.count_blocks()?;
+            Ok::<_, StoreError>((pruned, retained))
+        })
+        .await
+        .expect("pruning the index panicked")?;
+        metrics::counter!(crate::metrics_init::INDEX_PRUNED_BLOCKS)
+            .increment(u64::try_from(pruned.blocks).unwrap_or(u64::MAX));
+        #[allow(clippy::cast_precision_loss)]
+        metrics::gauge!(crate::metrics_init::INDEX_RETAINED_BLOCKS).set(retained as f64);
+        if pruned.blocks > 0 {
+            info!(
+                pruned.blocks,
+                pruned.heights,
+                retained,
+                "pruned blocks from the index"
+            );
+        } else {
+            debug!(retained, "no blocks to prune from the index");
+        }
+        Ok(pruned)
+    }
+
+    /// Applies the policy at the configured interval until the returned future is dropped.
+    ///
+    /// Failed runs are logged and retried at the next interval.
+    pub async fn run(self) {
+        if self.policy.retains_everything() {
+            info!("retention policy keeps every block; not pruning the index");
+            return;
+        }
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(error) = self.prune_once().await {
+                warn!(%error, "failed pruning the index; retrying at the next interval");
+            }
+        }
+    }
+}
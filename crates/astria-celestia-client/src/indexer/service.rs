@@ -0,0 +1,364 @@
+//! A gRPC service to query the index, for block explorers and bridges that want to look up
+//! Astria's data availability history without talking JSON-RPC to Celestia.
+//!
+//! If the server is given a Celestia client, it also streams full sequencer blocks: the index
+//! locates the blobs of each block on Celestia, and the server fetches and verifies them.
+
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use astria_core::generated::daindex::v1alpha1::{
+    da_index_service_server::DaIndexService,
+    GetBlockByHashRequest,
+    GetBlocksByNamespaceRequest,
+    GetBlocksByNamespaceResponse,
+    GetBlocksBySequencerHeightRequest,
+    GetBlocksBySequencerHeightResponse,
+    IndexedSequencerBlock as RawIndexedSequencerBlock,
+    ReconstructedSequencerBlock as RawReconstructedSequencerBlock,
+    StreamRecentBlocksRequest,
+    StreamSequencerBlocksRequest,
+};
+use celestia_types::nmt::Namespace;
+use jsonrpsee::http_client::HttpClient;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{
+    Request,
+    Response,
+    Status,
+};
+use tracing::{
+    debug,
+    instrument,
+    warn,
+    Instrument as _,
+};
+
+use super::{
+    IndexedBlock,
+    Store,
+    StoreError,
+};
+use crate::{
+    events::TracingSink,
+    CelestiaClientExt as _,
+};
+
+/// The number of blocks returned if a request does not set a limit.
+const DEFAULT_LIMIT: u32 = 100;
+
+/// The maximum number of blocks returned for a single request.
+const MAX_LIMIT: u32 = 1000;
+
+/// The interval at which the store is polled for newly indexed blocks to stream.
+const STREAM_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The number of blocks buffered per stream before the stream waits for the client.
+const STREAM_BUFFER: usize = 64;
+
+/// The number of reconstructed blocks buffered per stream before reconstruction waits for the
+/// client.
+///
+/// Reconstructed blocks carry the data of all their rollups, so few of them are buffered. A
+/// client that reads slowly holds up fetching from Celestia instead of growing the buffer.
+const RECONSTRUCTED_STREAM_BUFFER: usize = 4;
+
+/// Serves [`DaIndexService`] from a [`Store`].
+///
+/// The store is only read, so the service can run alongside an [`super::Indexer`] writing to
+/// the same store.
+pub struct IndexServer {
+    store: Arc<Store>,
+    celestia_client: Option<HttpClient>,
+}
+
+impl IndexServer {
+    /// Creates a server answering queries from `store`.
+    ///
+    /// The server cannot stream reconstructed sequencer blocks unless it is also given a
+    /// Celestia client with [`IndexServer::with_celestia_client`].
+    pub fn new(store: Arc<Store>) -> Self {
+        Self {
+            store,
+            celestia_client: None,
+        }
+    }
+
+    /// Sets the client used to fetch the blobs of reconstructed sequencer blocks from Celestia.
+    #[must_use]
+    pub fn with_celestia_client(self, celestia_client: HttpClient) -> Self {
+        Self {
+            celestia_client: Some(celestia_client),
+            ..self
+        }
+    }
+
+    /// Runs the blocking store query `f` on the blocking thread pool.
+    async fn query<F, T>(&self, f: F) -> Result<T, Status>
+    where
+        F: FnOnce(&Store) -> Result<T, StoreError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = Arc::clone(&self.store);
+        tokio::task::spawn_blocking(move || f(&store))
+            .await
+            .map_err(|e| Status::internal(format!("index query panicked: {e}")))?
+            .map_err(|e| Status::internal(format!("failed reading from the index: {e}")))
+    }
+}
+
+#[async_trait::async_trait]
+impl DaIndexService for IndexServer {
+    type StreamRecentBlocksStream = ReceiverStream<Result<RawIndexedSequencerBlock, Status>>;
+    type StreamSequencerBlocksStream =
+        ReceiverStream<Result<RawReconstructedSequencerBlock, Status>>;
+
+    #[instrument(skip_all, fields(height = request.get_ref().height))]
+    async fn get_blocks_by_sequencer_height(
+        self: Arc<Self>,
+        request: Request<GetBlocksBySequencerHeightRequest>,
+    ) -> Result<Response<GetBlocksBySequencerHeightResponse>, Status> {
+        let height = request.into_inner().height;
+        let blocks = self
+            .query(move |store| store.get_by_sequencer_height(height))
+            .await?;
+        Ok(Response::new(GetBlocksBySequencerHeightResponse {
+            blocks: blocks.into_iter().map(IndexedBlock::into_raw).collect(),
+        }))
+    }
+
+    #[instrument(skip_all)]
+    async fn get_block_by_hash(
+        self: Arc<Self>,
+        request: Request<GetBlockByHashRequest>,
+    ) -> Result<Response<RawIndexedSequencerBlock>, Status> {
+        let block_hash: [u8; 32] = request
+            .into_inner()
+            .block_hash
+            .try_into()
+            .map_err(|_| Status::invalid_argument("block hash must be 32 bytes"))?;
+        let block = self
+            .query(move |store| store.get_by_block_hash(&block_hash))
+            .await?
+            .ok_or_else(|| Status::not_found("no sequencer block with the given hash is indexed"))?;
+        Ok(Response::new(block.into_raw()))
+    }
+
+    #[instrument(skip_all)]
+    async fn get_blocks_by_namespace(
+        self: Arc<Self>,
+        request: Request<GetBlocksByNamespaceRequest>,
+    ) -> Result<Response<GetBlocksByNamespaceResponse>, Status> {
+        let request = request.into_inner();
+        let namespace = Namespace::from_raw(&request.namespace)
+            .map_err(|e| Status::invalid_argument(format!("invalid namespace: {e}")))?;
+        let limit = clamp_limit(request.limit);
+        let blocks = self
+            .query(move |store| store.get_by_namespace(&namespace, limit))
+            .await?;
+        Ok(Response::new(GetBlocksByNamespaceResponse {
+            blocks: blocks.into_iter().map(IndexedBlock::into_raw).collect(),
+        }))
+    }
+
+    #[instrument(skip_all)]
+    async fn stream_recent_blocks(
+        self: Arc<Self>,
+        request: Request<StreamRecentBlocksRequest>,
+    ) -> Result<Response<Self::StreamRecentBlocksStream>, Status> {
+        let limit = clamp_limit(request.into_inner().limit);
+        let mut recent = self.query(move |store| store.get_recent(limit)).await?;
+        recent.reverse();
+
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        tokio::spawn(async move {
+            let mut last_celestia_height = recent.last().map_or(0, |block| block.celestia_height);
+            let mut blocks = recent;
+            loop {
+                for block in blocks {
+                    last_celestia_height = last_celestia_height.max(block.celestia_height);
+                    if tx.send(Ok(block.into_raw())).await.is_err() {
+                        debug!("client closed the stream of recent blocks");
+                        return;
+                    }
+                }
+                tokio::time::sleep(STREAM_POLL_INTERVAL).await;
+                if tx.is_closed() {
+                    debug!("client closed the stream of recent blocks");
+                    return;
+                }
+                blocks = match self
+                    .query(move |store| store.get_after_celestia_height(last_celestia_height))
+                    .await
+                {
+                    Ok(blocks) => blocks,
+                    Err(status) => {
+                        warn!(%status, "failed reading newly indexed blocks; ending the stream");
+                        let _ = tx.send(Err(status)).await;
+                        return;
+                    }
+                };
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    #[instrument(skip_all, fields(
+        start_height = request.get_ref().start_height,
+        chain_id = %request.get_ref().chain_id,
+    ))]
+    async fn stream_sequencer_blocks(
+        self: Arc<Self>,
+        request: Request<StreamSequencerBlocksRequest>,
+    ) -> Result<Response<Self::StreamSequencerBlocksStream>, Status> {
+        let Some(celestia_client) = self.celestia_client.clone() else {
+            return Err(Status::unimplemented(
+                "the index server has no Celestia client to reconstruct sequencer blocks with",
+            ));
+        };
+        let StreamSequencerBlocksRequest {
+            start_height,
+            chain_id,
+        } = request.into_inner();
+        if chain_id.is_empty() {
+            return Err(Status::invalid_argument("the sequencer chain ID must be set"));
+        }
+        let first = {
+            let chain_id = chain_id.clone();
+            self.query(move |store| store.get_next_of_chain(&chain_id, start_height))
+                .await?
+        };
+        if let Some(first) = first.filter(|first| first.sequencer_height > start_height) {
+            return Err(Status::out_of_range(format!(
+                "sequencer height {start_height} is not indexed; the lowest indexed height is {}",
+                first.sequencer_height,
+            )));
+        }
+
+        let (tx, rx) = mpsc::channel(RECONSTRUCTED_STREAM_BUFFER);
+        tokio::spawn(
+            async move {
+                let mut next_height = start_height;
+                loop {
+                    if tx.is_closed() {
+                        debug!("client closed the stream of sequencer blocks");
+                        return;
+                    }
+                    let next = {
+                        let chain_id = chain_id.clone();
+                        self.query(move |store| store.get_next_of_chain(&chain_id, next_height))
+                            .await
+                    };
+                    // Blocks are sent without gaps, so the stream waits until the next height
+                    // is indexed even if higher ones already are.
+                    let indexed = match next {
+                        Ok(Some(block)) if block.sequencer_height == next_height => block,
+                        Ok(_) => {
+                            tokio::time::sleep(STREAM_POLL_INTERVAL).await;
+                            continue;
+                        }
+                        Err(status) => {
+                            warn!(%status, "failed reading the index; ending the stream");
+                            let _ = tx.send(Err(status)).await;
+                            return;
+                        }
+                    };
+                    let reconstructed = reconstruct(&celestia_client, indexed).await;
+                    if let Err(status) = &reconstructed {
+                        warn!(
+                            %status,
+                            sequencer_height = next_height,
+                            "failed reconstructing sequencer block; ending the stream",
+                        );
+                    }
+                    let failed = reconstructed.is_err();
+                    // Waiting for capacity here is what keeps a slow client from being flooded.
+                    if tx.send(reconstructed).await.is_err() {
+                        debug!("client closed the stream of sequencer blocks");
+                        return;
+                    }
+                    if failed {
+                        return;
+                    }
+                    next_height = next_height.saturating_add(1);
+                }
+            }
+            .in_current_span(),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Fetches the blobs of the sequencer block `indexed` from Celestia.
+///
+/// The sequencer blob is decoded and verified like when indexing, and must have the indexed
+/// block hash. The rollup blobs are verified against the sequencer blob.
+async fn reconstruct(
+    celestia_client: &HttpClient,
+    indexed: IndexedBlock,
+) -> Result<RawReconstructedSequencerBlock, Status> {
+    let raw_blobs = celestia_client
+        .get_raw_blobs(indexed.celestia_height, &[indexed.namespace])
+        .await
+        .map_err(|e| Status::unavailable(format!("failed fetching sequencer blobs: {e}")))?;
+    let sequencer_blob = raw_blobs
+        .iter()
+        .filter_map(|raw_blob| super::decode_sequencer_blob(&raw_blob.data))
+        .find(|sequencer_blob| sequencer_blob.block_hash() == indexed.block_hash)
+        .ok_or_else(|| {
+            Status::data_loss(format!(
+                "the indexed sequencer blob is not at Celestia height {}",
+                indexed.celestia_height,
+            ))
+        })?;
+
+    let events = TracingSink::default();
+    let mut rollup_blobs = Vec::new();
+    for namespace in indexed.rollup_namespaces {
+        let blobs = celestia_client
+            .get_rollup_blobs_matching_sequencer_blob(
+                indexed.celestia_height,
+                namespace,
+                &sequencer_blob,
+                &events,
+            )
+            .await
+            .map_err(|e| Status::unavailable(format!("failed fetching rollup blobs: {e}")))?;
+        rollup_blobs.extend(blobs.into_iter().map(crate::CelestiaRollupBlob::into_raw));
+    }
+    Ok(RawReconstructedSequencerBlock {
+        celestia_height: indexed.celestia_height,
+        sequencer_blob: Some(sequencer_blob.into_raw()),
+        rollup_blobs,
+    })
+}
+
+fn clamp_limit(limit: u32) -> u32 {
+    if limit == 0 {
+        DEFAULT_LIMIT
+    } else {
+        limit.min(MAX_LIMIT)
+    }
+}
+
+impl IndexedBlock {
+    fn into_raw(self) -> RawIndexedSequencerBlock {
+        RawIndexedSequencerBlock {
+            block_hash: self.block_hash.to_vec(),
+            sequencer_height: self.sequencer_height,
+            chain_id: self.chain_id,
+            celestia_height: self.celestia_height,
+            namespace: self.namespace.as_bytes().to_vec(),
+            rollup_namespaces: self
+                .rollup_namespaces
+                .iter()
+                .map(|namespace| namespace.as_bytes().to_vec())
+                .collect(),
+            size: u64::try_from(self.size).unwrap_or(u64::MAX),
+            proposer: self.proposer,
+        }
+    }
+}
@@ -0,0 +1,475 @@
+//! The sqlite backed store of the indexer.
+
+use std::{
+    path::Path,
+    sync::{
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use celestia_types::nmt::Namespace;
+use rusqlite::{
+    params,
+    Connection,
+    OptionalExtension as _,
+    Row,
+    Transaction,
+};
+
+use super::retention::{
+    Pruned,
+    RetentionPolicy,
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sequencer_blocks (
+        block_hash BLOB PRIMARY KEY NOT NULL,
+        sequencer_height INTEGER NOT NULL,
+        chain_id TEXT NOT NULL,
+        celestia_height INTEGER NOT NULL,
+        namespace BLOB NOT NULL,
+        size INTEGER NOT NULL,
+        proposer BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS sequencer_blocks_by_sequencer_height
+        ON sequencer_blocks (sequencer_height);
+    CREATE INDEX IF NOT EXISTS sequencer_blocks_by_celestia_height
+        ON sequencer_blocks (celestia_height);
+    CREATE TABLE IF NOT EXISTS rollup_namespaces (
+        block_hash BLOB NOT NULL REFERENCES sequencer_blocks (block_hash) ON DELETE CASCADE,
+        namespace BLOB NOT NULL,
+        PRIMARY KEY (block_hash, namespace)
+    );
+    CREATE INDEX IF NOT EXISTS rollup_namespaces_by_namespace
+        ON rollup_namespaces (namespace);
+    CREATE TABLE IF NOT EXISTS indexed_celestia_heights (
+        celestia_height INTEGER PRIMARY KEY NOT NULL,
+        indexed_at INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS indexed_celestia_heights_by_indexed_at
+        ON indexed_celestia_heights (indexed_at);
+";
+
+const SELECT_BLOCK: &str = "SELECT block_hash, sequencer_height, chain_id, celestia_height, \
+                            namespace, size, proposer FROM sequencer_blocks";
+
+/// An error when reading from or writing to the [`Store`].
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[error("the sqlite database returned an error")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("the stored value `{column}` is not valid")]
+    InvalidValue { column: &'static str },
+}
+
+/// A sequencer block recorded in the index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedBlock {
+    /// The hash of the sequencer block.
+    pub block_hash: [u8; 32],
+    /// The height of the sequencer block.
+    pub sequencer_height: u64,
+    /// The `CometBFT` chain ID of the sequencer network.
+    pub chain_id: String,
+    /// The Celestia height the sequencer blob was written at.
+    pub celestia_height: u64,
+    /// The namespace the sequencer blob was written under.
+    pub namespace: Namespace,
+    /// The namespaces of the rollups with transactions in the sequencer block.
+    pub rollup_namespaces: Vec<Namespace>,
+    /// The size of the sequencer blob as stored on Celestia, in bytes.
+    pub size: usize,
+    /// The address of the validator that proposed the sequencer block.
+    pub proposer: Vec<u8>,
+}
+
+/// The index of sequencer blocks, stored in a sqlite database.
+pub struct Store {
+    connection: Mutex<Connection>,
+}
+
+impl Store {
+    /// Opens the store at `path`, creating it if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database could not be opened or its schema could not be created.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Opens a store that is only kept in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema could not be created.
+    pub fn open_in_memory() -> Result<Self, StoreError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, StoreError> {
+        connection.execute_batch("PRAGMA foreign_keys = ON;")?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        // A panic while holding the lock leaves no transaction open, so a poisoned lock is
+        // recovered.
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records the sequencer blocks found at `celestia_height` and marks the height as indexed
+    /// at the current time.
+    ///
+    /// Blocks that were already recorded are replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the database failed, in which case nothing is recorded.
+    pub fn insert_height(
+        &self,
+        celestia_height: u64,
+        blocks: &[IndexedBlock],
+    ) -> Result<(), StoreError> {
+        let mut connection = self.lock();
+        let tx = connection.transaction()?;
+        for block in blocks {
+            tx.execute(
+                "INSERT OR REPLACE INTO sequencer_blocks (block_hash, sequencer_height, chain_id, \
+                 celestia_height, namespace, size, proposer) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    block.block_hash.as_slice(),
+                    block.sequencer_height,
+                    block.chain_id,
+                    block.celestia_height,
+                    block.namespace.as_bytes(),
+                    block.size,
+                    block.proposer,
+                ],
+            )?;
+            for namespace in &block.rollup_namespaces {
+                tx.execute(
+                    "INSERT OR IGNORE INTO rollup_namespaces (block_hash, namespace) VALUES (?1, \
+                     ?2)",
+                    params![block.block_hash.as_slice(), namespace.as_bytes()],
+                )?;
+            }
+        }
+        tx.execute(
+            "INSERT OR IGNORE INTO indexed_celestia_heights (celestia_height, indexed_at) VALUES \
+             (?1, ?2)",
+            params![celestia_height, unix_seconds(SystemTime::now())],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the number of sequencer blocks recorded in the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn count_blocks(&self) -> Result<u64, StoreError> {
+        let count = self
+            .lock()
+            .query_row("SELECT COUNT(*) FROM sequencer_blocks", [], |row| row.get(0))?;
+        Ok(count)
+    }
+
+    /// Removes the sequencer blocks that `policy` does not retain at time `now`.
+    ///
+    /// Blocks written at Celestia heights within [`RetentionPolicy::confirmation_depth`] of the
+    /// latest indexed height are never removed. Of the remaining blocks, those written at
+    /// heights indexed longer than [`RetentionPolicy::max_age`] ago are removed, and then the
+    /// oldest blocks are removed until at most [`RetentionPolicy::max_blocks`] are left.
+    ///
+    /// The latest indexed height is always kept so that indexing continues where it left off.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the database failed, in which case nothing is removed.
+    pub fn prune(&self, policy: &RetentionPolicy, now: SystemTime) -> Result<Pruned, StoreError> {
+        let mut connection = self.lock();
+        let tx = connection.transaction()?;
+        let Some(latest) = tx.query_row(
+            "SELECT MAX(celestia_height) FROM indexed_celestia_heights",
+            [],
+            |row| row.get::<_, Option<u64>>(0),
+        )?
+        else {
+            return Ok(Pruned::default());
+        };
+        let Some(prunable_up_to) = latest.checked_sub(policy.confirmation_depth) else {
+            return Ok(Pruned::default());
+        };
+
+        let mut blocks = 0;
+        if let Some(max_age) = policy.max_age {
+            let cutoff = unix_seconds(now.checked_sub(max_age).unwrap_or(UNIX_EPOCH));
+            blocks += tx.execute(
+                "DELETE FROM sequencer_blocks WHERE celestia_height <= ?1 AND celestia_height IN \
+                 (SELECT celestia_height FROM indexed_celestia_heights WHERE indexed_at < ?2)",
+                params![prunable_up_to, cutoff],
+            )?;
+        }
+        if let Some(max_blocks) = policy.max_blocks {
+            blocks += tx.execute(
+                "DELETE FROM sequencer_blocks WHERE celestia_height <= ?1 AND block_hash NOT IN \
+                 (SELECT block_hash FROM sequencer_blocks ORDER BY celestia_height DESC, \
+                 sequencer_height DESC LIMIT ?2)",
+                params![prunable_up_to, max_blocks],
+            )?;
+        }
+        let heights = match latest.checked_sub(1) {
+            Some(below_latest) => delete_empty_heights(&tx, prunable_up_to.min(below_latest))?,
+            None => 0,
+        };
+        tx.commit()?;
+        Ok(Pruned {
+            blocks,
+            heights,
+        })
+    }
+
+    /// Returns the highest Celestia height that was indexed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn latest_celestia_height(&self) -> Result<Option<u64>, StoreError> {
+        let height = self.lock().query_row(
+            "SELECT MAX(celestia_height) FROM indexed_celestia_heights",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(height)
+    }
+
+    /// Returns the sequencer blocks recorded at `sequencer_height`.
+    ///
+    /// More than one block is returned if blocks of several sequencer networks were indexed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn get_by_sequencer_height(
+        &self,
+        sequencer_height: u64,
+    ) -> Result<Vec<IndexedBlock>, StoreError> {
+        self.query(
+            &format!("{SELECT_BLOCK} WHERE sequencer_height = ?1 ORDER BY chain_id"),
+            params![sequencer_height],
+        )
+    }
+
+    /// Returns the sequencer block with hash `block_hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn get_by_block_hash(
+        &self,
+        block_hash: &[u8; 32],
+    ) -> Result<Option<IndexedBlock>, StoreError> {
+        Ok(self
+            .query(
+                &format!("{SELECT_BLOCK} WHERE block_hash = ?1"),
+                params![block_hash.as_slice()],
+            )?
+            .pop())
+    }
+
+    /// Returns up to `limit` sequencer blocks with the highest sequencer heights that contain
+    /// transactions of the rollup under `namespace`, or that were written under `namespace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn get_by_namespace(
+        &self,
+        namespace: &Namespace,
+        limit: u32,
+    ) -> Result<Vec<IndexedBlock>, StoreError> {
+        self.query(
+            &format!(
+                "{SELECT_BLOCK} WHERE namespace = ?1 OR block_hash IN (SELECT block_hash FROM \
+                 rollup_namespaces WHERE namespace = ?1) ORDER BY sequencer_height DESC LIMIT ?2"
+            ),
+            params![namespace.as_bytes(), limit],
+        )
+    }
+
+    /// Returns up to `limit` of the most recently written sequencer blocks, ordered by
+    /// descending Celestia and sequencer height.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn get_recent(&self, limit: u32) -> Result<Vec<IndexedBlock>, StoreError> {
+        self.query(
+            &format!(
+                "{SELECT_BLOCK} ORDER BY celestia_height DESC, sequencer_height DESC LIMIT ?1"
+            ),
+            params![limit],
+        )
+    }
+
+    /// Returns the sequencer blocks written at Celestia heights above `celestia_height`,
+    /// ordered by ascending Celestia and sequencer height.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn get_after_celestia_height(
+        &self,
+        celestia_height: u64,
+    ) -> Result<Vec<IndexedBlock>, StoreError> {
+        self.query(
+            &format!(
+                "{SELECT_BLOCK} WHERE celestia_height > ?1 ORDER BY celestia_height ASC, \
+                 sequencer_height ASC"
+            ),
+            params![celestia_height],
+        )
+    }
+
+    /// Returns the sequencer block of the sequencer network `chain_id` with the lowest sequencer
+    /// height at or above `sequencer_height`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn get_next_of_chain(
+        &self,
+        chain_id: &str,
+        sequencer_height: u64,
+    ) -> Result<Option<IndexedBlock>, StoreError> {
+        Ok(self
+            .query(
+                &format!(
+                    "{SELECT_BLOCK} WHERE chain_id = ?1 AND sequencer_height >= ?2 ORDER BY \
+                     sequencer_height ASC LIMIT 1"
+                ),
+                params![chain_id, sequencer_height],
+            )?
+            .pop())
+    }
+
+    fn query(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<IndexedBlock>, StoreError> {
+        let connection = self.lock();
+        let mut blocks = connection
+            .prepare(sql)?
+            .query_map(params, read_block)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(RawBlock::into_block)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut statement =
+            connection.prepare("SELECT namespace FROM rollup_namespaces WHERE block_hash = ?1")?;
+        for block in &mut blocks {
+            block.rollup_namespaces = statement
+                .query_map(params![block.block_hash.as_slice()], |row| {
+                    row.get::<_, Vec<u8>>(0)
+                })?
+                .map(|namespace| {
+                    namespace
+                        .map_err(StoreError::from)
+                        .and_then(|namespace| to_namespace(&namespace))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(blocks)
+    }
+
+    /// Returns if the store has recorded a block with hash `block_hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the database failed.
+    pub fn contains(&self, block_hash: &[u8; 32]) -> Result<bool, StoreError> {
+        Ok(self
+            .lock()
+            .query_row(
+                "SELECT 1 FROM sequencer_blocks WHERE block_hash = ?1",
+                params![block_hash.as_slice()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+}
+
+/// A row of the `sequencer_blocks` table before validation.
+struct RawBlock {
+    block_hash: Vec<u8>,
+    sequencer_height: u64,
+    chain_id: String,
+    celestia_height: u64,
+    namespace: Vec<u8>,
+    size: usize,
+    proposer: Vec<u8>,
+}
+
+impl RawBlock {
+    fn into_block(self) -> Result<IndexedBlock, StoreError> {
+        Ok(IndexedBlock {
+            block_hash: self.block_hash.try_into().map_err(|_| StoreError::InvalidValue {
+                column: "block_hash",
+            })?,
+            sequencer_height: self.sequencer_height,
+            chain_id: self.chain_id,
+            celestia_height: self.celestia_height,
+            namespace: to_namespace(&self.namespace)?,
+            rollup_namespaces: vec![],
+            size: self.size,
+            proposer: self.proposer,
+        })
+    }
+}
+
+fn read_block(row: &Row<'_>) -> rusqlite::Result<RawBlock> {
+    Ok(RawBlock {
+        block_hash: row.get(0)?,
+        sequencer_height: row.get(1)?,
+        chain_id: row.get(2)?,
+        celestia_height: row.get(3)?,
+        namespace: row.get(4)?,
+        size: row.get(5)?,
+        proposer: row.get(6)?,
+    })
+}
+
+/// Removes the indexed heights up to and including `up_to` at which no block is recorded.
+fn delete_empty_heights(tx: &Transaction<'_>, up_to: u64) -> Result<usize, StoreError> {
+    Ok(tx.execute(
+        "DELETE FROM indexed_celestia_heights WHERE celestia_height <= ?1 AND celestia_height NOT \
+         IN (SELECT celestia_height FROM sequencer_blocks)",
+        params![up_to],
+    )?)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn to_namespace(bytes: &[u8]) -> Result<Namespace, StoreError> {
+    Namespace::from_raw(bytes).map_err(|_| StoreError::InvalidValue {
+        column: "namespace",
+    })
+}
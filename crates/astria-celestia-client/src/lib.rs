@@ -1,10 +1,31 @@
+mod builder;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod client;
+pub mod decode;
+pub mod dedup;
+pub mod events;
+pub mod heartbeat;
+#[cfg(feature = "indexer")]
+pub mod indexer;
 pub mod metrics_init;
+pub mod middleware;
+pub mod ordered;
+pub mod squatting;
 pub mod submission;
+pub mod timestamps;
+#[cfg(feature = "vcr")]
+pub mod vcr;
 
 pub use astria_core::sequencerblock::v1alpha1::{
+    CelestiaAuxiliaryBlob,
     CelestiaRollupBlob,
     CelestiaSequencerBlob,
+    RollupResultNamespaceData,
+};
+pub use builder::{
+    BuildClientError,
+    ClientBuilder,
 };
 pub use celestia_rpc;
 pub use celestia_tendermint;
@@ -13,8 +34,30 @@ use celestia_types::nmt::{
     Namespace,
     NS_ID_V0_SIZE,
 };
-pub use client::CelestiaClientExt;
+pub use client::{
+    CelestiaClientExt,
+    GetSequencerBlobsResponse,
+    RawBlob,
+    SequencerBlobFilter,
+    SubmitBlockResponse,
+    SubmitSequencerBlocksError,
+    SubmittedBlob,
+};
+pub use decode::{
+    DecoderRegistry,
+    PayloadDecoder,
+};
+pub use events::{
+    ChannelSink,
+    Event,
+    EventSink,
+    TracingSink,
+};
+pub use heartbeat::HeartbeatReport;
 pub use jsonrpsee;
+pub use ordered::OrderedSequencerBlobs;
+pub use squatting::SquattingReport;
+pub use timestamps::TimestampResolver;
 
 pub fn is_blob_not_found<T: IsBlobNotFound>(err: &T) -> bool {
     err.is_blob_not_found()
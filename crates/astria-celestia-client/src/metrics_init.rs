@@ -3,8 +3,12 @@
 //! Registers metrics & lists constants to be used as metric names throughout crate.
 
 use metrics::{
+    counter,
+    describe_counter,
     describe_gauge,
+    describe_histogram,
     gauge,
+    histogram,
     Unit,
 };
 
@@ -23,9 +27,45 @@ pub fn register() {
         Unit::Count,
         "The total number of rollup blobs included in the last Celestia submission"
     );
+
+    counter!(INDEX_PRUNED_BLOCKS, "lib" => env!("CARGO_CRATE_NAME"));
+    describe_counter!(
+        INDEX_PRUNED_BLOCKS,
+        Unit::Count,
+        "The number of sequencer blocks removed from the DA index by its retention policy"
+    );
+
+    gauge!(INDEX_RETAINED_BLOCKS, "lib" => env!("CARGO_CRATE_NAME"));
+    describe_gauge!(
+        INDEX_RETAINED_BLOCKS,
+        Unit::Count,
+        "The number of sequencer blocks recorded in the DA index after the last pruning run"
+    );
+
+    counter!(RPC_REQUESTS, "lib" => env!("CARGO_CRATE_NAME"));
+    describe_counter!(
+        RPC_REQUESTS,
+        Unit::Count,
+        "The number of JSON-RPC requests made to the Celestia node through the metrics \
+         middleware, labelled by method and outcome"
+    );
+
+    histogram!(RPC_REQUEST_DURATION, "lib" => env!("CARGO_CRATE_NAME"));
+    describe_histogram!(
+        RPC_REQUEST_DURATION,
+        Unit::Seconds,
+        "The time it took the Celestia node to answer JSON-RPC requests made through the \
+         metrics middleware, labelled by method"
+    );
 }
 
 pub const ROLLUP_BLOBS_PER_ASTRIA_BLOCK: &str =
     concat!(env!("CARGO_CRATE_NAME"), "_rollups_blobs_per_astria_block");
 pub const ROLLUP_BLOBS_PER_CELESTIA_TX: &str =
     concat!(env!("CARGO_CRATE_NAME"), "_rollup_blobs_per_celestia_tx");
+pub const INDEX_PRUNED_BLOCKS: &str = concat!(env!("CARGO_CRATE_NAME"), "_index_pruned_blocks");
+pub const INDEX_RETAINED_BLOCKS: &str =
+    concat!(env!("CARGO_CRATE_NAME"), "_index_retained_blocks");
+pub const RPC_REQUESTS: &str = concat!(env!("CARGO_CRATE_NAME"), "_rpc_requests");
+pub const RPC_REQUEST_DURATION: &str =
+    concat!(env!("CARGO_CRATE_NAME"), "_rpc_request_duration");
@@ -0,0 +1,390 @@
+//! Middleware around the JSON-RPC client talking to a Celestia node.
+//!
+//! A middleware is a [`tower::Layer`] that wraps a JSON-RPC client in another client
+//! implementing [`ClientT`], which sees every request before it is forwarded and every response
+//! before it is returned. Layers are stacked on top of the HTTP transport with
+//! [`ClientBuilder::layer`](crate::ClientBuilder::layer); the first layer added is the outermost.
+//!
+//! This module provides [`MetricsLayer`], recording the number and duration of requests per
+//! method, [`TracingLayer`], wrapping every request in a span, and [`ConcurrencyLimitLayer`],
+//! capping the number of requests in flight. Applications can add their own layers for request
+//! rewriting, or anything else that fits between their calls and the node. Clients produced by
+//! layers should implement [`CelestiaClientExt`], as the layers of this crate do.
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+        Arc,
+    },
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use jsonrpsee::core::{
+    client::{
+        BatchResponse,
+        ClientT,
+    },
+    params::BatchRequestBuilder,
+    traits::ToRpcParams,
+    Error,
+};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+use tokio::sync::Semaphore;
+use tower::Layer;
+use tracing::{
+    debug,
+    field,
+    info_span,
+    Instrument as _,
+    Span,
+};
+
+use crate::CelestiaClientExt;
+
+/// The method recorded for batch requests, which may contain calls to several methods.
+const BATCH: &str = "batch";
+
+/// Parameters that were already encoded, so that they can be inspected before being forwarded.
+#[derive(Clone)]
+pub(crate) struct EncodedParams(Option<Box<RawValue>>);
+
+impl EncodedParams {
+    pub(crate) fn encode<P: ToRpcParams>(params: P) -> Result<Self, Error> {
+        params.to_rpc_params().map(Self).map_err(Error::ParseError)
+    }
+
+    /// Returns the length of the encoded parameters in bytes.
+    fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |raw| raw.get().len())
+    }
+
+    #[cfg(feature = "vcr")]
+    pub(crate) fn to_value(&self) -> Option<serde_json::Value> {
+        self.0.as_ref().map(|raw| {
+            serde_json::from_str(raw.get()).expect("encoded parameters are always valid JSON")
+        })
+    }
+}
+
+impl ToRpcParams for EncodedParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Wraps clients in a [`MetricsClient`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsLayer;
+
+impl<C> Layer<C> for MetricsLayer {
+    type Service = MetricsClient<C>;
+
+    fn layer(&self, inner: C) -> Self::Service {
+        MetricsClient {
+            inner,
+        }
+    }
+}
+
+/// A JSON-RPC client recording the number, outcome, and duration of the requests made through
+/// it, labelled by method.
+#[derive(Clone, Debug)]
+pub struct MetricsClient<C> {
+    inner: C,
+}
+
+impl<C> MetricsClient<C> {
+    /// Returns the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+async fn record<T, F>(method: &str, request: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let start = Instant::now();
+    let result = request.await;
+    let outcome = if result.is_ok() { "success" } else { "failure" };
+    metrics::counter!(
+        crate::metrics_init::RPC_REQUESTS,
+        "method" => method.to_string(),
+        "outcome" => outcome
+    )
+    .increment(1);
+    metrics::histogram!(
+        crate::metrics_init::RPC_REQUEST_DURATION,
+        "method" => method.to_string()
+    )
+    .record(start.elapsed());
+    result
+}
+
+#[async_trait]
+impl<C> ClientT for MetricsClient<C>
+where
+    C: ClientT + Send + Sync,
+{
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        record(method, self.inner.notification(method, params)).await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        record(method, self.inner.request(method, params)).await
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        record(BATCH, self.inner.batch_request(batch)).await
+    }
+}
+
+impl<C> CelestiaClientExt for MetricsClient<C> where C: ClientT + Send + Sync {}
+
+/// Wraps clients in a [`ConcurrencyLimitClient`] allowing at most `max_in_flight` requests at
+/// the same time.
+#[derive(Clone, Copy, Debug)]
+pub struct ConcurrencyLimitLayer {
+    max_in_flight: usize,
+}
+
+impl ConcurrencyLimitLayer {
+    /// Returns a layer capping the requests in flight at `max_in_flight`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_in_flight` is 0.
+    #[must_use]
+    pub fn new(max_in_flight: usize) -> Self {
+        assert!(
+            max_in_flight > 0,
+            "at least one request must be allowed to be in flight"
+        );
+        Self {
+            max_in_flight,
+        }
+    }
+}
+
+impl<C> Layer<C> for ConcurrencyLimitLayer {
+    type Service = ConcurrencyLimitClient<C>;
+
+    fn layer(&self, inner: C) -> Self::Service {
+        ConcurrencyLimitClient {
+            inner,
+            permits: Arc::new(Semaphore::new(self.max_in_flight)),
+        }
+    }
+}
+
+/// A JSON-RPC client holding back requests while the configured number of requests is in
+/// flight.
+///
+/// Clones share the same limit.
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimitClient<C> {
+    inner: C,
+    permits: Arc<Semaphore>,
+}
+
+impl<C> ConcurrencyLimitClient<C> {
+    /// Returns the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    async fn limit<T, F>(&self, request: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        request.await
+    }
+}
+
+#[async_trait]
+impl<C> ClientT for ConcurrencyLimitClient<C>
+where
+    C: ClientT + Send + Sync,
+{
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        self.limit(self.inner.notification(method, params)).await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        self.limit(self.inner.request(method, params)).await
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        self.limit(self.inner.batch_request(batch)).await
+    }
+}
+
+impl<C> CelestiaClientExt for ConcurrencyLimitClient<C> where C: ClientT + Send + Sync {}
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// Runs `future` with `correlation_id` attached to every request it makes through a
+/// [`TracingClient`].
+///
+/// Callers pass the identifier of the operation the requests are made for, such as the
+/// submission a relayer is working on, so that the spans of the requests can be attributed to it
+/// even when they are exported separately from the caller's own spans.
+pub async fn with_correlation_id<F: Future>(
+    correlation_id: impl Into<String>,
+    future: F,
+) -> F::Output {
+    CORRELATION_ID.scope(correlation_id.into(), future).await
+}
+
+fn current_correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// Wraps clients in a [`TracingClient`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingLayer;
+
+impl<C> Layer<C> for TracingLayer {
+    type Service = TracingClient<C>;
+
+    fn layer(&self, inner: C) -> Self::Service {
+        TracingClient {
+            inner,
+            next_request_id: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+/// A JSON-RPC client running every request made through it in a `celestia_rpc` span.
+///
+/// The span is a child of the caller's span and carries the method, a request id unique to the
+/// client and its clones, the size of the encoded parameters and of the response in bytes, the
+/// latency of the request, and the correlation id set with [`with_correlation_id`], if any.
+#[derive(Clone, Debug)]
+pub struct TracingClient<C> {
+    inner: C,
+    next_request_id: Arc<AtomicU64>,
+}
+
+impl<C> TracingClient<C> {
+    /// Returns the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn span(&self, method: &str, params: Option<&EncodedParams>) -> Span {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        info_span!(
+            "celestia_rpc",
+            method,
+            request_id,
+            correlation_id = current_correlation_id().map(field::display),
+            params_bytes = params.map(EncodedParams::len),
+            response_bytes = field::Empty,
+            latency_ms = field::Empty,
+        )
+    }
+}
+
+/// Runs `request` in the current span, recording its latency and outcome on it.
+async fn trace<T, F>(request: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let start = Instant::now();
+    let result = request.await;
+    // allow: a request would have to take half a billion years to overflow
+    #[allow(clippy::cast_possible_truncation)]
+    let latency_ms = start.elapsed().as_millis() as u64;
+    Span::current().record("latency_ms", latency_ms);
+    match &result {
+        Ok(_) => debug!(latency_ms, "request succeeded"),
+        Err(error) => debug!(latency_ms, %error, "request failed"),
+    }
+    result
+}
+
+#[async_trait]
+impl<C> ClientT for TracingClient<C>
+where
+    C: ClientT + Send + Sync,
+{
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = EncodedParams::encode(params)?;
+        let span = self.span(method, Some(&params));
+        trace(self.inner.notification(method, params))
+            .instrument(span)
+            .await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = EncodedParams::encode(params)?;
+        let span = self.span(method, Some(&params));
+        let response: Box<RawValue> = trace(self.inner.request(method, params))
+            .instrument(span.clone())
+            .await?;
+        span.record("response_bytes", response.get().len());
+        serde_json::from_str(response.get()).map_err(Error::ParseError)
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        let span = self.span(BATCH, None);
+        trace(self.inner.batch_request(batch))
+            .instrument(span)
+            .await
+    }
+}
+
+impl<C> CelestiaClientExt for TracingClient<C> where C: ClientT + Send + Sync {}
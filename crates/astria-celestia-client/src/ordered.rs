@@ -0,0 +1,113 @@
+//! Ordering the sequencer blobs found at a single Celestia height.
+//!
+//! A relayer that catches up after a pause, or that batches blocks to save fees, posts many
+//! sequencer blocks in one Celestia height. `blob.GetAll` returns their blobs in the order they
+//! were laid out in the square, which need not be the order of the sequencer, and the same block
+//! is returned more than once if it was posted more than once, for example by a relayer that
+//! repeated a submission after a restart.
+//!
+//! [`OrderedSequencerBlobs`] groups the blobs by sequencer height and block hash so that they can
+//! be walked in sequencer order with every block seen once. Blobs read from several Celestia
+//! heights can be added to the same [`OrderedSequencerBlobs`] to page through them in order.
+
+use std::collections::BTreeMap;
+
+use astria_core::sequencerblock::v1alpha1::CelestiaSequencerBlob;
+
+/// Sequencer blobs grouped by sequencer height and block hash, in sequencer order.
+///
+/// Blobs of a block that was already added are dropped and only counted. Blobs of different
+/// blocks at the same sequencer height are all kept, in the order they were added, since only
+/// the sequencer's commit can tell which of them is canonical; see
+/// [`OrderedSequencerBlobs::conflicting_heights`].
+#[derive(Clone, Debug, Default)]
+pub struct OrderedSequencerBlobs {
+    heights: BTreeMap<u64, Vec<CelestiaSequencerBlob>>,
+    duplicates: usize,
+}
+
+impl OrderedSequencerBlobs {
+    /// Adds `blob`, returning `false` if a blob of the same block was already added.
+    pub fn insert(&mut self, blob: CelestiaSequencerBlob) -> bool {
+        let blobs = self.heights.entry(blob.height().value()).or_default();
+        if blobs
+            .iter()
+            .any(|added| added.block_hash() == blob.block_hash())
+        {
+            self.duplicates = self.duplicates.saturating_add(1);
+            return false;
+        }
+        blobs.push(blob);
+        true
+    }
+
+    /// Returns the number of distinct sequencer blocks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heights.values().map(Vec::len).sum()
+    }
+
+    /// Returns if no blobs were added.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+
+    /// Returns the number of blobs that were dropped because their block was already added.
+    #[must_use]
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Returns the blobs of the blocks at sequencer height `height`.
+    ///
+    /// This is a single blob unless the height is conflicting, and empty if no blob of the
+    /// height was added.
+    #[must_use]
+    pub fn get(&self, height: u64) -> &[CelestiaSequencerBlob] {
+        self.heights.get(&height).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the sequencer heights of the added blobs in ascending order.
+    pub fn heights(&self) -> impl Iterator<Item = u64> + '_ {
+        self.heights.keys().copied()
+    }
+
+    /// Returns the sequencer heights in ascending order at which blobs of more than one block
+    /// were added.
+    pub fn conflicting_heights(&self) -> impl Iterator<Item = u64> + '_ {
+        self.heights
+            .iter()
+            .filter(|(_, blobs)| blobs.len() > 1)
+            .map(|(height, _)| *height)
+    }
+
+    /// Returns the blobs in sequencer order.
+    pub fn blobs(&self) -> impl Iterator<Item = &CelestiaSequencerBlob> {
+        self.heights.values().flatten()
+    }
+
+    /// Returns the blobs in sequencer order.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<CelestiaSequencerBlob> {
+        self.heights.into_values().flatten().collect()
+    }
+}
+
+impl FromIterator<CelestiaSequencerBlob> for OrderedSequencerBlobs {
+    fn from_iter<I: IntoIterator<Item = CelestiaSequencerBlob>>(blobs: I) -> Self {
+        let mut ordered = Self::default();
+        for blob in blobs {
+            ordered.insert(blob);
+        }
+        ordered
+    }
+}
+
+impl Extend<CelestiaSequencerBlob> for OrderedSequencerBlobs {
+    fn extend<I: IntoIterator<Item = CelestiaSequencerBlob>>(&mut self, blobs: I) {
+        for blob in blobs {
+            self.insert(blob);
+        }
+    }
+}
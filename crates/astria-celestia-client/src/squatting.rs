@@ -0,0 +1,129 @@
+//! Detecting third-party traffic in the namespaces Astria posts to.
+//!
+//! Celestia namespaces are not permissioned: anybody can post blobs under the namespaces that
+//! sequencer headers and rollup data are written to. A [`SquattingReport`] collects the blobs
+//! over a range of Celestia heights that are not Astria blobs at all, together with their sizes
+//! and how often they occur, to inform decisions about migrating to other namespaces. It is
+//! created by [`CelestiaClientExt::get_squatting_report`](crate::CelestiaClientExt::get_squatting_report).
+
+use std::{
+    collections::HashMap,
+    ops::RangeInclusive,
+};
+
+use astria_core::brotli::decompress_bytes_with_limit;
+use celestia_types::{
+    nmt::Namespace,
+    Commitment,
+};
+use prost::Message as _;
+
+use crate::client::RawBlob;
+
+/// The maximum number of bytes a blob is decompressed to when checking its envelope.
+///
+/// Only the leading block hash is of interest, so this need not fit entire Astria blobs.
+const MAX_DECOMPRESSED_ENVELOPE_SIZE: usize = 1024 * 1024;
+
+/// The field shared by all Astria blob types: the 32 byte hash of the sequencer block the blob
+/// was derived from, at tag 1. All other fields are skipped when decoding.
+#[derive(Clone, PartialEq, prost::Message)]
+struct Envelope {
+    #[prost(bytes = "vec", tag = "1")]
+    sequencer_block_hash: Vec<u8>,
+}
+
+/// Returns if `data` parses as the envelope of an Astria blob, either as is or after brotli
+/// decompression.
+///
+/// This does not check that the blob is valid, only that it could have been written by an
+/// Astria sequencer-relayer.
+#[must_use]
+pub fn is_astria_envelope(data: &[u8]) -> bool {
+    fn has_block_hash(bytes: &[u8]) -> bool {
+        Envelope::decode(bytes).is_ok_and(|envelope| envelope.sequencer_block_hash.len() == 32)
+    }
+    if has_block_hash(data) {
+        return true;
+    }
+    decompress_bytes_with_limit(data, MAX_DECOMPRESSED_ENVELOPE_SIZE)
+        .is_ok_and(|decompressed| has_block_hash(&decompressed))
+}
+
+/// A blob that could not be parsed as an Astria blob.
+#[derive(Clone, Debug)]
+pub struct ForeignBlob {
+    /// The Celestia height the blob was found at.
+    pub height: u64,
+    /// The share commitment of the blob.
+    pub commitment: Commitment,
+    /// The size of the blob's data in bytes.
+    pub size: usize,
+}
+
+/// The blobs found under a single namespace.
+#[derive(Clone, Debug, Default)]
+pub struct NamespaceReport {
+    /// The number of all blobs found, including Astria blobs.
+    pub total_blobs: usize,
+    /// The blobs that could not be parsed as Astria blobs, in order of their heights.
+    pub foreign_blobs: Vec<ForeignBlob>,
+}
+
+impl NamespaceReport {
+    /// Returns the combined size of all foreign blobs in bytes.
+    #[must_use]
+    pub fn foreign_bytes(&self) -> usize {
+        self.foreign_blobs.iter().map(|blob| blob.size).sum()
+    }
+
+    /// Returns the size of the largest foreign blob in bytes.
+    #[must_use]
+    pub fn max_foreign_blob_size(&self) -> Option<usize> {
+        self.foreign_blobs.iter().map(|blob| blob.size).max()
+    }
+
+    /// Returns the number of heights at which at least one foreign blob was found.
+    #[must_use]
+    pub fn heights_with_foreign_blobs(&self) -> usize {
+        let mut heights: Vec<u64> = self.foreign_blobs.iter().map(|blob| blob.height).collect();
+        heights.dedup();
+        heights.len()
+    }
+}
+
+/// The foreign blobs found in a set of namespaces over a range of Celestia heights.
+#[derive(Clone, Debug)]
+pub struct SquattingReport {
+    /// The Celestia heights that were searched.
+    pub heights: RangeInclusive<u64>,
+    /// The blobs found under each of the searched namespaces.
+    pub namespaces: HashMap<Namespace, NamespaceReport>,
+}
+
+impl SquattingReport {
+    pub(crate) fn new(heights: RangeInclusive<u64>, namespaces: &[Namespace]) -> Self {
+        Self {
+            heights,
+            namespaces: namespaces
+                .iter()
+                .map(|namespace| (*namespace, NamespaceReport::default()))
+                .collect(),
+        }
+    }
+
+    /// Adds the blobs found at `height` to the report.
+    pub(crate) fn record(&mut self, height: u64, blobs: Vec<RawBlob>) {
+        for blob in blobs {
+            let report = self.namespaces.entry(blob.namespace).or_default();
+            report.total_blobs = report.total_blobs.saturating_add(1);
+            if !is_astria_envelope(&blob.data) {
+                report.foreign_blobs.push(ForeignBlob {
+                    height,
+                    commitment: blob.commitment,
+                    size: blob.size,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Resolving Celestia heights to the timestamps of their headers.
+//!
+//! Rollup bridges base their timeout logic on wall-clock time. A sequencer block becomes firm
+//! once it is included in a Celestia block, so the time of that Celestia block is the time of
+//! finality. [`TimestampResolver`] looks up these times and caches them, since a Celestia
+//! header never changes once it exists and the same heights are usually resolved many times.
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    sync::{
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+};
+
+use celestia_rpc::HeaderClient;
+use celestia_tendermint::Time;
+use tracing::{
+    instrument,
+    trace,
+};
+
+/// The number of heights cached by [`TimestampResolver::new`].
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Resolves Celestia heights to the timestamps of their headers.
+///
+/// The most recently resolved heights are cached. Once the cache is full, the height resolved
+/// first is evicted.
+pub struct TimestampResolver<C> {
+    client: C,
+    capacity: usize,
+    cache: Mutex<Cache>,
+}
+
+#[derive(Default)]
+struct Cache {
+    timestamps: HashMap<u64, Time>,
+    insertion_order: VecDeque<u64>,
+}
+
+impl<C: HeaderClient + Sync> TimestampResolver<C> {
+    /// Creates a resolver fetching headers with `client` that caches up to
+    /// [`DEFAULT_CACHE_CAPACITY`] heights.
+    pub fn new(client: C) -> Self {
+        Self::with_capacity(client, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Creates a resolver fetching headers with `client` that caches up to `capacity` heights.
+    ///
+    /// A `capacity` of 0 disables the cache.
+    pub fn with_capacity(client: C, capacity: usize) -> Self {
+        Self {
+            client,
+            capacity,
+            cache: Mutex::new(Cache::default()),
+        }
+    }
+
+    /// Returns the timestamp of the Celestia header at `height`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the timestamp is not cached and the underlying `header.GetByHeight` JSONRPC
+    /// failed, for example because Celestia has not reached `height` yet.
+    #[instrument(skip(self))]
+    pub async fn resolve(&self, height: u64) -> Result<Time, jsonrpsee::core::Error> {
+        if let Some(time) = self.cached(height) {
+            trace!("resolved Celestia height from cache");
+            return Ok(time);
+        }
+        let time = self.client.header_get_by_height(height).await?.time();
+        self.insert(height, time);
+        Ok(time)
+    }
+
+    /// Returns the timestamp of the Celestia header at `height` if it is cached.
+    pub fn cached(&self, height: u64) -> Option<Time> {
+        self.lock().timestamps.get(&height).copied()
+    }
+
+    fn insert(&self, height: u64, time: Time) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut cache = self.lock();
+        if cache.timestamps.insert(height, time).is_some() {
+            return;
+        }
+        cache.insertion_order.push_back(height);
+        if cache.insertion_order.len() > self.capacity {
+            if let Some(evicted) = cache.insertion_order.pop_front() {
+                cache.timestamps.remove(&evicted);
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache> {
+        // A panic while holding the lock can at worst leave a height cached that is never
+        // evicted, so a poisoned lock is recovered.
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
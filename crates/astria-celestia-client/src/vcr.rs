@@ -0,0 +1,346 @@
+//! Recording and replaying the JSON-RPC traffic between a client and a Celestia node.
+//!
+//! Tests of retrieval logic run once against a real node with a [`RecordLayer`] stacked on the
+//! client, which captures every request and its response in a [`Cassette`]. The cassette is
+//! saved to a fixture file and checked in. From then on the tests run offline against a
+//! [`Replayer`] loaded from the fixture, which answers each request with the recorded response.
+//!
+//! Requests are matched by method and parameters, in the order they were recorded. Batch
+//! requests are passed through unrecorded and cannot be replayed.
+//!
+//! Only available with the `vcr` feature.
+
+use std::{
+    fs::File,
+    io::{
+        BufReader,
+        BufWriter,
+    },
+    path::Path,
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError,
+    },
+};
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::{
+        client::{
+            BatchResponse,
+            ClientT,
+        },
+        params::BatchRequestBuilder,
+        traits::ToRpcParams,
+        Error,
+    },
+    types::ErrorObjectOwned,
+};
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+use tower::Layer;
+use tracing::debug;
+
+use crate::{
+    middleware::EncodedParams,
+    CelestiaClientExt,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct CassetteError(CassetteErrorKind);
+
+impl CassetteError {
+    fn io(source: std::io::Error) -> Self {
+        Self(CassetteErrorKind::Io(source))
+    }
+
+    fn json(source: serde_json::Error) -> Self {
+        Self(CassetteErrorKind::Json(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum CassetteErrorKind {
+    #[error("failed accessing the cassette file")]
+    Io(#[source] std::io::Error),
+    #[error("failed encoding or decoding the cassette as JSON")]
+    Json(#[source] serde_json::Error),
+}
+
+/// A request and the response the node gave to it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Interaction {
+    method: String,
+    params: Option<Value>,
+    response: RecordedResponse,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedResponse {
+    /// The result of a successful call; `null` for notifications.
+    Result(Value),
+    /// The error object the node returned.
+    Error(ErrorObjectOwned),
+    /// A failure before the node answered, such as a transport error, kept as its message.
+    Failure(String),
+}
+
+/// The JSON-RPC interactions recorded by a [`RecordLayer`], in the order they completed.
+///
+/// Cheap to clone; all clones share the same recording.
+#[derive(Clone, Debug, Default)]
+pub struct Cassette {
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+impl Cassette {
+    /// Returns an empty cassette.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a cassette from the fixture file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or does not contain a cassette.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, CassetteError> {
+        let file = File::open(path).map_err(CassetteError::io)?;
+        let interactions =
+            serde_json::from_reader(BufReader::new(file)).map_err(CassetteError::json)?;
+        Ok(Self {
+            interactions: Arc::new(Mutex::new(interactions)),
+        })
+    }
+
+    /// Writes the recorded interactions to the fixture file at `path`, replacing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), CassetteError> {
+        let file = File::create(path).map_err(CassetteError::io)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &*self.lock())
+            .map_err(CassetteError::json)
+    }
+
+    /// Returns the number of recorded interactions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns if no interactions were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn record_response(result: Result<&Value, &Error>) -> RecordedResponse {
+    match result {
+        Ok(value) => RecordedResponse::Result(value.clone()),
+        Err(Error::Call(error)) => RecordedResponse::Error(error.clone()),
+        Err(error) => RecordedResponse::Failure(error.to_string()),
+    }
+}
+
+fn replay_response(response: RecordedResponse) -> Result<Value, Error> {
+    match response {
+        RecordedResponse::Result(value) => Ok(value),
+        RecordedResponse::Error(error) => Err(Error::Call(error)),
+        RecordedResponse::Failure(message) => Err(Error::Custom(message)),
+    }
+}
+
+/// Wraps clients in a [`Recorder`] recording into a [`Cassette`].
+#[derive(Clone, Debug)]
+pub struct RecordLayer {
+    cassette: Cassette,
+}
+
+impl RecordLayer {
+    /// Returns a layer recording into `cassette`.
+    #[must_use]
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            cassette,
+        }
+    }
+}
+
+impl<C> Layer<C> for RecordLayer {
+    type Service = Recorder<C>;
+
+    fn layer(&self, inner: C) -> Self::Service {
+        Recorder {
+            inner,
+            cassette: self.cassette.clone(),
+        }
+    }
+}
+
+/// A JSON-RPC client recording the requests made through it and their responses.
+#[derive(Clone, Debug)]
+pub struct Recorder<C> {
+    inner: C,
+    cassette: Cassette,
+}
+
+impl<C> Recorder<C> {
+    /// Returns the cassette the interactions are recorded into.
+    pub fn cassette(&self) -> &Cassette {
+        &self.cassette
+    }
+
+    fn record(&self, method: &str, params: &EncodedParams, result: Result<&Value, &Error>) {
+        self.cassette.lock().push(Interaction {
+            method: method.to_string(),
+            params: params.to_value(),
+            response: record_response(result),
+        });
+    }
+}
+
+#[async_trait]
+impl<C> ClientT for Recorder<C>
+where
+    C: ClientT + Send + Sync,
+{
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = EncodedParams::encode(params)?;
+        let recorded_params = params.clone();
+        let result = self.inner.notification(method, params).await;
+        let null = Value::Null;
+        self.record(method, &recorded_params, result.as_ref().map(|()| &null));
+        result
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = EncodedParams::encode(params)?;
+        let recorded_params = params.clone();
+        let result: Result<Value, Error> = self.inner.request(method, params).await;
+        self.record(method, &recorded_params, result.as_ref());
+        serde_json::from_value(result?).map_err(Error::ParseError)
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        debug!("batch requests are not recorded; passing it through");
+        self.inner.batch_request(batch).await
+    }
+}
+
+impl<C> CelestiaClientExt for Recorder<C> where C: ClientT + Send + Sync {}
+
+/// A JSON-RPC client answering requests with the responses recorded in a [`Cassette`], without
+/// talking to a node.
+#[derive(Debug)]
+pub struct Replayer {
+    /// The recorded interactions, each taken out once it was replayed.
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl Replayer {
+    /// Returns a client replaying the interactions recorded in `cassette`.
+    #[must_use]
+    pub fn new(cassette: &Cassette) -> Self {
+        let interactions = cassette.lock().iter().cloned().map(Some).collect();
+        Self {
+            interactions: Mutex::new(interactions),
+        }
+    }
+
+    /// Returns the number of recorded interactions that were not replayed yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.lock().iter().flatten().count()
+    }
+
+    /// Takes the first interaction with `method` and `params` that was not replayed yet.
+    fn replay(&self, method: &str, params: EncodedParams) -> Result<Value, Error> {
+        let params = params.to_value();
+        let interaction = self
+            .lock()
+            .iter_mut()
+            .find(|slot| {
+                slot.as_ref().is_some_and(|interaction| {
+                    interaction.method == method && interaction.params == params
+                })
+            })
+            .and_then(Option::take)
+            .ok_or_else(|| {
+                Error::Custom(format!(
+                    "vcr: no recorded response left for request `{method}` with the given \
+                     parameters"
+                ))
+            })?;
+        replay_response(interaction.response)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Option<Interaction>>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl ClientT for Replayer {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        self.replay(method, EncodedParams::encode(params)?)
+            .map(|_| ())
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let value = self.replay(method, EncodedParams::encode(params)?)?;
+        serde_json::from_value(value).map_err(Error::ParseError)
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        _batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a,
+    {
+        Err(Error::Custom(
+            "vcr: batch requests are not recorded and cannot be replayed".to_string(),
+        ))
+    }
+}
+
+impl CelestiaClientExt for Replayer {}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use clap::{
+    Args,
+    Subcommand,
+    ValueEnum,
+};
+
+/// Inspect Sequencer data written to Celestia
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Replay the deserialization and verification of a Sequencer blob step by step
+    ReplayVerify(ReplayVerifyArgs),
+    /// Decode a blob and print its contents as canonical protobuf JSON, without verifying them
+    Decode(DecodeArgs),
+}
+
+/// The blob data exactly as stored on Celestia
+#[derive(Args, Debug)]
+pub struct BlobArgs {
+    /// Path to a file containing the blob data exactly as stored on Celestia
+    #[clap(long, conflicts_with = "hex", required_unless_present = "hex")]
+    pub(crate) file: Option<PathBuf>,
+    /// The blob data exactly as stored on Celestia, hex encoded
+    #[clap(long)]
+    pub(crate) hex: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReplayVerifyArgs {
+    #[clap(flatten)]
+    pub(crate) blob: BlobArgs,
+    /// The url of a Sequencer node to fetch the commit and validator set for the blob from.
+    /// If not set, only the blob itself is verified.
+    #[clap(long, env = "SEQUENCER_URL")]
+    pub(crate) sequencer_url: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DecodeArgs {
+    #[clap(flatten)]
+    pub(crate) blob: BlobArgs,
+    /// Which kind of blob to decode
+    #[clap(long, value_enum, default_value = "sequencer")]
+    pub(crate) kind: BlobKind,
+    /// Print the JSON on a single line instead of pretty-printing it
+    #[clap(long)]
+    pub(crate) compact: bool,
+}
+
+/// The kinds of blobs written to Celestia
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum BlobKind {
+    /// A blob in the Sequencer namespace, containing a Sequencer block header
+    Sequencer,
+    /// A blob in a rollup namespace, containing the transactions of a rollup
+    Rollup,
+}
@@ -1,3 +1,4 @@
+pub(crate) mod celestia;
 pub(crate) mod rollup;
 pub(crate) mod sequencer;
 
@@ -8,6 +9,7 @@ use clap::{
 use color_eyre::eyre;
 
 use crate::cli::{
+    celestia::Command as CelestiaCommand,
     rollup::Command as RollupCommand,
     sequencer::Command as SequencerCommand,
 };
@@ -38,6 +40,10 @@ impl Cli {
 /// Commands that can be run
 #[derive(Debug, Subcommand)]
 pub enum Command {
+    Celestia {
+        #[clap(subcommand)]
+        command: CelestiaCommand,
+    },
     Rollup {
         #[clap(subcommand)]
         command: RollupCommand,
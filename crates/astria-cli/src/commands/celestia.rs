@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+
+use astria_core::{
+    generated::sequencerblock::v1alpha1::{
+        CelestiaRollupBlob as RawCelestiaRollupBlob,
+        CelestiaSequencerBlob as RawCelestiaSequencerBlob,
+    },
+    primitive::v1::BlockHash,
+    sequencerblock::v1alpha1::{
+        BlobFormat,
+        CelestiaSequencerBlob,
+        NamespaceData,
+    },
+};
+use astria_sequencer_client::{
+    tendermint::{
+        self,
+        block::{
+            CommitSig,
+            Height,
+        },
+    },
+    tendermint_proto,
+    tendermint_rpc::{
+        endpoint::validators,
+        Paging,
+    },
+    Client as _,
+    HttpClient,
+};
+use color_eyre::{
+    eyre,
+    eyre::{
+        bail,
+        ensure,
+        eyre,
+        Context,
+    },
+};
+use ed25519_consensus::{
+    Signature,
+    VerificationKey,
+};
+use prost::Message as _;
+
+use crate::cli::celestia::{
+    BlobArgs,
+    BlobKind,
+    DecodeArgs,
+    ReplayVerifyArgs,
+};
+
+/// Replays the deserialization and verification of a Sequencer blob, printing every step
+///
+/// # Arguments
+///
+/// * `args` - The arguments passed to the command
+///
+/// # Errors
+///
+/// * If the blob cannot be read
+/// * If any step of deserializing or verifying the blob fails
+pub(crate) async fn replay_verify(args: &ReplayVerifyArgs) -> eyre::Result<()> {
+    let data = read_blob(&args.blob)?;
+    println!("Read blob:");
+    println!("    size: {} bytes", data.len());
+
+    let decompressed = astria_core::brotli::decompress_bytes(&data)
+        .wrap_err("failed decompressing blob with brotli")?;
+    println!("Decompressed blob:");
+    println!("    size: {} bytes", decompressed.len());
+
+    let raw = RawCelestiaSequencerBlob::decode(&*decompressed)
+        .wrap_err("failed decoding decompressed blob as sequencer blob protobuf")?;
+    println!("Decoded protobuf:");
+    println!("    block hash: 0x{}", hex::encode(&raw.block_hash));
+    println!("    rollup IDs: {}", raw.rollup_ids.len());
+
+    let blob = CelestiaSequencerBlob::try_from_raw(raw)
+        .wrap_err("sequencer blob failed verification of its contents")?;
+    let header = blob.header();
+    println!("Verified blob contents:");
+    println!("    chain ID: {}", header.chain_id());
+    println!("    height: {}", header.height());
+    println!("    time: {}", header.time());
+    println!("    proposer: {}", header.proposer_address());
+    println!("    data hash: {}", hex::encode(header.data_hash()));
+    println!(
+        "    rollup transactions root: {}",
+        hex::encode(header.rollup_transactions_root())
+    );
+    println!("    rollup transactions and rollup IDs are included in the data hash: ok");
+
+    let Some(sequencer_url) = &args.sequencer_url else {
+        println!("No Sequencer url given; skipping verification against the Sequencer commit");
+        return Ok(());
+    };
+    let sequencer_client = HttpClient::new(sequencer_url.as_str())
+        .wrap_err("failed constructing http sequencer client")?;
+    verify_against_commit(&sequencer_client, &blob).await
+}
+
+/// Decodes a blob and prints its contents as canonical protobuf JSON
+///
+/// Both protobuf and JSON encoded blobs are accepted. The contents are printed as decoded, without
+/// verifying them, so that the output of different blobs can be diffed and consumed by tools
+/// that do not link against the Rust types.
+///
+/// # Arguments
+///
+/// * `args` - The arguments passed to the command
+///
+/// # Errors
+///
+/// * If the blob cannot be read
+/// * If the blob cannot be decompressed or decoded as the given kind of blob
+pub(crate) fn decode(args: &DecodeArgs) -> eyre::Result<()> {
+    let data = read_blob(&args.blob)?;
+    let decompressed = astria_core::brotli::decompress_bytes(&data)
+        .wrap_err("failed decompressing blob with brotli")?;
+    let json = match args.kind {
+        BlobKind::Sequencer => {
+            to_proto_json::<RawCelestiaSequencerBlob>(&decompressed, args.compact)
+        }
+        BlobKind::Rollup => to_proto_json::<RawCelestiaRollupBlob>(&decompressed, args.compact),
+    }?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Decodes `bytes` as `T` in whichever format they were written, and encodes it as protobuf JSON
+fn to_proto_json<T: NamespaceData>(bytes: &[u8], compact: bool) -> eyre::Result<String> {
+    let (message, _) = BlobFormat::decode::<T>(bytes).wrap_err("failed decoding blob")?;
+    if compact {
+        serde_json::to_string(&message)
+    } else {
+        serde_json::to_string_pretty(&message)
+    }
+    .wrap_err("failed encoding blob as protobuf JSON")
+}
+
+fn read_blob(args: &BlobArgs) -> eyre::Result<Vec<u8>> {
+    match (&args.file, &args.hex) {
+        (Some(path), None) => std::fs::read(path)
+            .wrap_err_with(|| format!("failed reading blob from `{}`", path.display())),
+        (None, Some(hex)) => {
+            hex::decode(hex.trim().trim_start_matches("0x")).wrap_err("failed decoding hex blob")
+        }
+        _ => bail!("exactly one of `--file` and `--hex` must be given"),
+    }
+}
+
+/// Verifies `blob` against the commit and validator set of its height, like conductor does
+async fn verify_against_commit(
+    sequencer_client: &HttpClient,
+    blob: &CelestiaSequencerBlob,
+) -> eyre::Result<()> {
+    let height = blob.height();
+    ensure!(height.value() > 0, "cannot verify a sequencer height zero");
+    let prev_height = Height::try_from(height.value().saturating_sub(1))
+        .wrap_err("failed constructing the height of the validator set")?;
+
+    let commit_response = sequencer_client
+        .commit(height)
+        .await
+        .wrap_err_with(|| format!("failed fetching commit at height {height}"))?;
+    let signed_header = commit_response.signed_header;
+    let commit_hash = signed_header.commit.block_id.hash;
+    let header_hash = signed_header.header.hash();
+    println!("Fetched commit at height {height}:");
+    println!("    chain ID: {}", signed_header.header.chain_id);
+    println!("    committed block hash: 0x{}", hex::encode(commit_hash.as_bytes()));
+    println!("    computed header hash: 0x{}", hex::encode(header_hash.as_bytes()));
+    println!("    block hash in blob:   {}", BlockHash::new(blob.block_hash()));
+    ensure!(
+        &signed_header.header.chain_id == blob.cometbft_chain_id(),
+        "chain ID `{}` of the blob does not match chain ID `{}` of the commit",
+        blob.cometbft_chain_id(),
+        signed_header.header.chain_id,
+    );
+    ensure!(
+        commit_hash.as_bytes() == blob.block_hash(),
+        "block hash in blob does not match the committed block hash"
+    );
+    ensure!(
+        header_hash.as_bytes() == blob.block_hash(),
+        "block hash in blob does not match the computed hash of the committed header"
+    );
+
+    let validator_set = sequencer_client
+        .validators(prev_height, Paging::All)
+        .await
+        .wrap_err_with(|| format!("failed fetching validator set at height {prev_height}"))?;
+    println!("Fetched validator set at height {prev_height}:");
+    println!("    validators: {}", validator_set.validators.len());
+
+    verify_commit_signatures(
+        &signed_header.commit,
+        &validator_set,
+        &signed_header.header.chain_id,
+    )
+}
+
+/// Verifies every signature in `commit` and that the signing validators have quorum
+fn verify_commit_signatures(
+    commit: &tendermint::block::Commit,
+    validator_set: &validators::Response,
+    chain_id: &tendermint::chain::Id,
+) -> eyre::Result<()> {
+    let total_voting_power = validator_set
+        .validators
+        .iter()
+        .try_fold(0u64, |acc, validator| acc.checked_add(validator.power()))
+        .ok_or_else(|| eyre!("total voting power overflowed u64"))?;
+    let validators = validator_set
+        .validators
+        .iter()
+        .map(|validator| (tendermint::account::Id::from(validator.pub_key), validator))
+        .collect::<HashMap<_, _>>();
+
+    println!("Verifying commit signatures:");
+    let mut commit_voting_power = 0u64;
+    let mut failures = 0usize;
+    for vote in &commit.signatures {
+        let CommitSig::BlockIdFlagCommit {
+            validator_address,
+            timestamp,
+            signature,
+        } = vote
+        else {
+            println!("    (absent or nil vote; skipped)");
+            continue;
+        };
+        println!("    validator: {validator_address}");
+        let Some(validator) = validators.get(validator_address) else {
+            println!("        FAILED: validator is not in the validator set");
+            failures = failures.saturating_add(1);
+            continue;
+        };
+        let Some(signature) = signature else {
+            println!("        FAILED: commit contains an empty signature");
+            failures = failures.saturating_add(1);
+            continue;
+        };
+        let sign_bytes = tendermint_proto::types::CanonicalVote::from(
+            tendermint::vote::CanonicalVote {
+                vote_type: tendermint::vote::Type::Precommit,
+                height: commit.height,
+                round: commit.round,
+                block_id: Some(commit.block_id),
+                timestamp: Some(*timestamp),
+                chain_id: chain_id.clone(),
+            },
+        )
+        .encode_length_delimited_to_vec();
+        println!("        power: {}", validator.power());
+        println!("        key: {}", hex::encode(validator.pub_key.to_bytes()));
+        println!("        signature: {}", hex::encode(signature.as_bytes()));
+        println!("        sign bytes: {}", hex::encode(&sign_bytes));
+        match verify_signature(&validator.pub_key, signature.as_bytes(), &sign_bytes) {
+            Ok(()) => {
+                println!("        result: ok");
+                commit_voting_power = commit_voting_power.saturating_add(validator.power());
+            }
+            Err(error) => {
+                println!("        result: FAILED: {error:#}");
+                failures = failures.saturating_add(1);
+            }
+        }
+    }
+
+    println!("Quorum:");
+    println!("    voting power of valid signatures: {commit_voting_power}");
+    println!("    total voting power: {total_voting_power}");
+    ensure!(failures == 0, "{failures} commit signatures failed verification");
+    ensure!(
+        has_quorum(commit_voting_power, total_voting_power),
+        "voting power of the commit is not more than 2/3 of the total voting power"
+    );
+    println!("    quorum: ok");
+    Ok(())
+}
+
+fn verify_signature(
+    public_key: &tendermint::PublicKey,
+    signature: &[u8],
+    sign_bytes: &[u8],
+) -> eyre::Result<()> {
+    let public_key = VerificationKey::try_from(public_key.to_bytes().as_slice())
+        .wrap_err("failed constructing verification key")?;
+    let signature = Signature::try_from(signature).wrap_err("failed constructing signature")?;
+    public_key
+        .verify(&signature, sign_bytes)
+        .wrap_err("signature does not match sign bytes and key")
+}
+
+fn has_quorum(committed: u64, total: u64) -> bool {
+    if total < 3 {
+        committed.saturating_mul(3) > total.saturating_mul(2)
+    } else {
+        committed > total.saturating_div(3).saturating_mul(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use astria_core::{
+        primitive::v1::RollupId,
+        protocol::test_utils::ConfigureSequencerBlock,
+    };
+
+    use super::{
+        has_quorum,
+        to_proto_json,
+        BlobFormat,
+        RawCelestiaRollupBlob,
+    };
+
+    #[test]
+    fn quorum_requires_more_than_two_thirds() {
+        assert!(has_quorum(3, 4));
+        assert!(!has_quorum(2, 3));
+        assert!(has_quorum(7, 9));
+        assert!(!has_quorum(6, 9));
+    }
+
+    #[test]
+    fn blobs_in_either_format_decode_to_the_same_proto_json() {
+        let (_, rollup_blobs) = ConfigureSequencerBlock {
+            height: 1,
+            sequence_data: vec![(RollupId::new([1; 32]), vec![1, 2, 3])],
+            ..Default::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        let raw = rollup_blobs.into_iter().next().unwrap().into_raw();
+
+        let from_protobuf = to_proto_json::<RawCelestiaRollupBlob>(
+            &BlobFormat::Protobuf.encode(&raw).unwrap(),
+            true,
+        )
+        .unwrap();
+        let from_json =
+            to_proto_json::<RawCelestiaRollupBlob>(&BlobFormat::Json.encode(&raw).unwrap(), true)
+                .unwrap();
+        assert_eq!(from_protobuf, from_json);
+        assert!(from_protobuf.contains("\"rollupTxsRoot\""));
+    }
+}
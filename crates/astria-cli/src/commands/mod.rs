@@ -1,3 +1,4 @@
+mod celestia;
 mod rollup;
 mod sequencer;
 
@@ -8,6 +9,7 @@ use color_eyre::{
 use tracing::instrument;
 
 use crate::cli::{
+    celestia::Command as CelestiaCommand,
     rollup::{
         Command as RollupCommand,
         ConfigCommand,
@@ -43,6 +45,12 @@ use crate::cli::{
 pub async fn run(cli: Cli) -> eyre::Result<()> {
     if let Some(command) = cli.command {
         match command {
+            Command::Celestia {
+                command,
+            } => match command {
+                CelestiaCommand::ReplayVerify(args) => celestia::replay_verify(&args).await?,
+                CelestiaCommand::Decode(args) => celestia::decode(&args)?,
+            },
             Command::Rollup {
                 command,
             } => match command {
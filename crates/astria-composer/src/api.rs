@@ -11,6 +11,7 @@ use axum::{
     },
     routing::{
         get,
+        post,
         IntoMakeService,
     },
     Router,
@@ -20,7 +21,11 @@ use serde::Serialize;
 use tokio::sync::watch;
 use tracing::debug;
 
-use crate::composer;
+use crate::{
+    collectors,
+    composer,
+    executor,
+};
 
 pub(super) type ApiServer = axum::Server<AddrIncoming, IntoMakeService<Router>>;
 
@@ -30,6 +35,7 @@ type ComposerStatus = watch::Receiver<composer::Status>;
 #[derive(Clone)]
 struct AppState {
     composer_status: ComposerStatus,
+    executor: executor::Handle,
 }
 
 impl FromRef<AppState> for ComposerStatus {
@@ -38,11 +44,30 @@ impl FromRef<AppState> for ComposerStatus {
     }
 }
 
-pub(super) fn start(listen_addr: SocketAddr, composer_status: ComposerStatus) -> ApiServer {
+impl FromRef<AppState> for executor::Handle {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.executor.clone()
+    }
+}
+
+pub(super) fn start(
+    listen_addr: SocketAddr,
+    composer_status: ComposerStatus,
+    executor: executor::Handle,
+) -> ApiServer {
     let app = Router::new()
         .route("/readyz", get(readyz))
+        .route(
+            "/v1alpha1/rollups/:rollup_name/txs",
+            post(collectors::http::submit_rollup_transaction),
+        )
+        .route(
+            "/v1alpha1/rollups/:rollup_name/eth",
+            post(collectors::eth_rpc::handle_request),
+        )
         .with_state(AppState {
             composer_status,
+            executor,
         });
     axum::Server::bind(&listen_addr).serve(app.into_make_service())
 }
@@ -0,0 +1,163 @@
+//! `EthRpc` is an Ethereum JSON-RPC compatible endpoint for submitting rollup transactions.
+//!
+//! EVM wallets and tools submit signed transactions with
+//! [`eth_sendRawTransaction`](https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_sendrawtransaction).
+//! Pointing them at `/v1alpha1/rollups/:rollup_name/eth` of the composer's API server forwards
+//! their transactions to the executor for rollup `rollup_name`, the same as if they had been read
+//! by the [`Geth`](super::Geth) collector from that rollup's node.
+//!
+//! Only `eth_sendRawTransaction` is served. All other methods are answered with a JSON-RPC
+//! "method not found" error, so that clients can fall back to the rollup node for reads.
+
+use astria_core::primitive::v1::RollupId;
+use axum::{
+    extract::{
+        Path,
+        State,
+    },
+    Json,
+};
+use ethers::{
+    types::Transaction,
+    utils::rlp::{
+        Decodable as _,
+        Rlp,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+use tracing::debug;
+
+use crate::{
+    collectors::{
+        http::forward,
+        ETH_RPC,
+    },
+    executor,
+};
+
+const SEND_RAW_TRANSACTION: &str = "eth_sendRawTransaction";
+
+// JSON-RPC 2.0 error codes, see https://www.jsonrpc.org/specification#error_object
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+// The code used by go-ethereum when it cannot accept a transaction.
+const TRANSACTION_REJECTED: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Request {
+    jsonrpc: String,
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Vec<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorObject>,
+}
+
+impl Response {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn error(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(ErrorObject {
+                code,
+                message: message.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorObject {
+    code: i64,
+    message: String,
+}
+
+/// Serves a JSON-RPC request for rollup `rollup_name`.
+pub(crate) async fn handle_request(
+    State(executor): State<executor::Handle>,
+    Path(rollup_name): Path<String>,
+    Json(request): Json<Request>,
+) -> Json<Response> {
+    let Request {
+        jsonrpc,
+        id,
+        method,
+        params,
+    } = request;
+    if jsonrpc != "2.0" {
+        return Json(Response::error(
+            id,
+            INVALID_REQUEST,
+            "only JSON-RPC 2.0 is supported",
+        ));
+    }
+    if method != SEND_RAW_TRANSACTION {
+        return Json(Response::error(
+            id,
+            METHOD_NOT_FOUND,
+            format!("the method {method} does not exist/is not available"),
+        ));
+    }
+
+    let raw_tx = match decode_raw_transaction(&params) {
+        Ok(raw_tx) => raw_tx,
+        Err(message) => return Json(Response::error(id, INVALID_PARAMS, message)),
+    };
+    let tx_hash = match Transaction::decode(&Rlp::new(&raw_tx)) {
+        Ok(tx) => tx.hash,
+        Err(error) => {
+            return Json(Response::error(
+                id,
+                INVALID_PARAMS,
+                format!("invalid transaction: {error}"),
+            ));
+        }
+    };
+
+    let rollup_id = RollupId::from_unhashed_bytes(&rollup_name);
+    debug!(%rollup_name, %rollup_id, ?tx_hash, "received raw transaction over eth json-rpc");
+    match forward(&executor, rollup_id, raw_tx, ETH_RPC).await {
+        Ok(()) => Json(Response::result(id, Value::String(format!("{tx_hash:#x}")))),
+        Err(reason) => Json(Response::error(id, TRANSACTION_REJECTED, reason)),
+    }
+}
+
+/// Decodes the single parameter of `eth_sendRawTransaction`, a 0x-prefixed hex string.
+fn decode_raw_transaction(params: &[Value]) -> Result<Vec<u8>, &'static str> {
+    let [Value::String(raw_tx)] = params else {
+        return Err("expected a single hex encoded transaction as parameter");
+    };
+    let raw_tx = raw_tx
+        .strip_prefix("0x")
+        .ok_or("transaction must be 0x-prefixed")?;
+    let raw_tx = hex::decode(raw_tx).map_err(|_| "transaction is not valid hex")?;
+    if raw_tx.is_empty() {
+        return Err("empty transaction");
+    }
+    Ok(raw_tx)
+}
@@ -0,0 +1,97 @@
+//! `Http` accepts rollup transactions posted to the composer's API server.
+//!
+//! Transactions are posted as the raw request body to `/v1alpha1/rollups/:rollup_name/txs`.
+//! Like for the [`Geth`](super::Geth) collector, the rollup ID is the hash of the rollup name.
+//! Requests are rejected with `503 Service Unavailable` if the executor does not accept the
+//! transaction in time, so that clients can back off and retry.
+
+use astria_core::{
+    primitive::v1::{
+        asset::default_native_asset_id,
+        RollupId,
+    },
+    protocol::transaction::v1alpha1::action::SequenceAction,
+};
+use axum::{
+    body::Bytes,
+    extract::{
+        Path,
+        State,
+    },
+    http::StatusCode,
+};
+use tokio::sync::mpsc::error::SendTimeoutError;
+use tracing::debug;
+
+use crate::{
+    collectors::{
+        EXECUTOR_SEND_TIMEOUT,
+        HTTP,
+    },
+    executor,
+    metrics_init::{
+        COLLECTOR_TYPE_LABEL,
+        ROLLUP_ID_LABEL,
+    },
+};
+
+/// Forwards the body of the request as a transaction of rollup `rollup_name` to the executor.
+pub(crate) async fn submit_rollup_transaction(
+    State(executor): State<executor::Handle>,
+    Path(rollup_name): Path<String>,
+    body: Bytes,
+) -> (StatusCode, &'static str) {
+    if body.is_empty() {
+        return (StatusCode::BAD_REQUEST, "empty rollup transaction");
+    }
+    let rollup_id = RollupId::from_unhashed_bytes(&rollup_name);
+    debug!(%rollup_name, %rollup_id, "received rollup transaction over http");
+
+    match forward(&executor, rollup_id, body.to_vec(), HTTP).await {
+        Ok(()) => (StatusCode::ACCEPTED, "accepted"),
+        Err(reason) => (StatusCode::SERVICE_UNAVAILABLE, reason),
+    }
+}
+
+/// Sends `data` as a transaction of `rollup_id` to the executor, recording it under
+/// `collector_type` in the metrics.
+///
+/// Returns the reason if the executor did not accept the transaction.
+pub(super) async fn forward(
+    executor: &executor::Handle,
+    rollup_id: RollupId,
+    data: Vec<u8>,
+    collector_type: &'static str,
+) -> Result<(), &'static str> {
+    let sequence_action = SequenceAction {
+        rollup_id,
+        data,
+        fee_asset_id: default_native_asset_id(),
+    };
+
+    metrics::counter!(
+        crate::metrics_init::TRANSACTIONS_RECEIVED,
+        &[
+            (ROLLUP_ID_LABEL, rollup_id.to_string()),
+            (COLLECTOR_TYPE_LABEL, collector_type.to_string())
+        ]
+    )
+    .increment(1);
+    let reason = match executor
+        .send_timeout(sequence_action, EXECUTOR_SEND_TIMEOUT)
+        .await
+    {
+        Ok(()) => return Ok(()),
+        Err(SendTimeoutError::Timeout(_seq_action)) => "timeout while sending txs to composer",
+        Err(SendTimeoutError::Closed(_seq_action)) => "composer is not available",
+    };
+    metrics::counter!(
+        crate::metrics_init::TRANSACTIONS_DROPPED,
+        &[
+            (ROLLUP_ID_LABEL, rollup_id.to_string()),
+            (COLLECTOR_TYPE_LABEL, collector_type.to_string())
+        ]
+    )
+    .increment(1);
+    Err(reason)
+}
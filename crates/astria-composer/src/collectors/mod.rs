@@ -1,12 +1,16 @@
+pub(crate) mod eth_rpc;
 pub(crate) mod geth;
 pub(crate) mod grpc;
+pub(crate) mod http;
 
 use std::time::Duration;
 
 const EXECUTOR_SEND_TIMEOUT: Duration = Duration::from_millis(500);
 
+const ETH_RPC: &str = "eth_rpc";
 const GETH: &str = "geth";
 const GRPC: &str = "grpc";
+const HTTP: &str = "http";
 
 pub(crate) use geth::Geth;
 pub(crate) use grpc::Grpc;
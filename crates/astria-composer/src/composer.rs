@@ -58,7 +58,8 @@ const GETH_COLLECTOR_SHUTDOWN_DURATION: Duration = Duration::from_secs(5);
 /// downstream for the executor to process. Thus, a composer can have multiple collectors running
 /// at the same time funneling data from multiple rollup nodes.
 pub struct Composer {
-    /// used for monitoring the status of the Composer service.
+    /// used for monitoring the status of the Composer service and for submitting rollup
+    /// transactions over HTTP.
     api_server: ApiServer,
     /// used to announce the current status of the Composer for other
     /// modules in the crate to use.
@@ -143,7 +144,11 @@ impl Composer {
             "gRPC server listening"
         );
 
-        let api_server = api::start(cfg.api_listen_addr, composer_status_sender.subscribe());
+        let api_server = api::start(
+            cfg.api_listen_addr,
+            composer_status_sender.subscribe(),
+            executor_handle.clone(),
+        );
 
         info!(
             listen_addr = %api_server.local_addr(),
@@ -8,9 +8,9 @@ use metrics::{
     describe_histogram,
     Unit,
 };
+pub(crate) use telemetry::metrics::ROLLUP_ID_LABEL;
 
 /// Labels
-pub(crate) const ROLLUP_ID_LABEL: &str = "rollup_id";
 pub(crate) const COLLECTOR_TYPE_LABEL: &str = "collector_type";
 
 /// Registers all metrics used by this crate.
@@ -73,31 +73,27 @@ pub fn register() {
     );
 }
 
-pub const TRANSACTIONS_RECEIVED: &str = concat!(env!("CARGO_CRATE_NAME"), "_transactions_received");
+pub const TRANSACTIONS_RECEIVED: &str = telemetry::metric_name!("transactions_received");
 
-pub const TRANSACTIONS_DROPPED: &str = concat!(env!("CARGO_CRATE_NAME"), "_transactions_dropped");
+pub const TRANSACTIONS_DROPPED: &str = telemetry::metric_name!("transactions_dropped");
 
 pub const TRANSACTIONS_DROPPED_TOO_LARGE: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_transactions_dropped_too_large");
+    telemetry::metric_name!("transactions_dropped_too_large");
 
-pub const NONCE_FETCH_COUNT: &str = concat!(env!("CARGO_CRATE_NAME"), "_nonce_fetch_count");
+pub const NONCE_FETCH_COUNT: &str = telemetry::metric_name!("nonce_fetch_count");
 
-pub const NONCE_FETCH_FAILURE_COUNT: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_nonce_fetch_failure_count");
+pub const NONCE_FETCH_FAILURE_COUNT: &str = telemetry::metric_name!("nonce_fetch_failure_count");
 
-pub const NONCE_FETCH_LATENCY: &str = concat!(env!("CARGO_CRATE_NAME"), "_nonce_fetch_latency");
+pub const NONCE_FETCH_LATENCY: &str = telemetry::metric_name!("nonce_fetch_latency");
 
-pub const CURRENT_NONCE: &str = concat!(env!("CARGO_CRATE_NAME"), "_current_nonce");
+pub const CURRENT_NONCE: &str = telemetry::metric_name!("current_nonce");
 
 pub const SEQUENCER_SUBMISSION_LATENCY: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_sequencer_submission_latency");
+    telemetry::metric_name!("sequencer_submission_latency");
 
-pub const SEQUENCER_SUBMISSION_FAILURE_COUNT: &str = concat!(
-    env!("CARGO_CRATE_NAME"),
-    "_sequencer_submission_failure_count"
-);
+pub const SEQUENCER_SUBMISSION_FAILURE_COUNT: &str =
+    telemetry::metric_name!("sequencer_submission_failure_count");
 
-pub const TRANSACTIONS_PER_SUBMISSION: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_transaction_per_submission");
+pub const TRANSACTIONS_PER_SUBMISSION: &str = telemetry::metric_name!("transaction_per_submission");
 
-pub const BYTES_PER_SUBMISSION: &str = concat!(env!("CARGO_CRATE_NAME"), "_bytes_per_submission");
+pub const BYTES_PER_SUBMISSION: &str = telemetry::metric_name!("bytes_per_submission");
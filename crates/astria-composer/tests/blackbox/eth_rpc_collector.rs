@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use ethers::prelude::Transaction;
+use serde_json::json;
+
+use crate::helper::{
+    mount_matcher_verifying_tx_integrity,
+    spawn_composer,
+    TEST_ETH_TX_JSON,
+};
+
+#[tokio::test]
+async fn raw_tx_is_received_by_sequencer() {
+    let test_composer = spawn_composer(&[]).await;
+    tokio::time::timeout(
+        Duration::from_millis(100),
+        test_composer.setup_guard.wait_until_satisfied(),
+    )
+    .await
+    .expect("composer and sequencer were not setup successfully");
+
+    let tx: Transaction = serde_json::from_str(TEST_ETH_TX_JSON).unwrap();
+    let mock_guard =
+        mount_matcher_verifying_tx_integrity(&test_composer.sequencer, tx.clone()).await;
+
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(format!(
+            "http://{}/v1alpha1/rollups/test1/eth",
+            test_composer.api_addr
+        ))
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_sendRawTransaction",
+            "params": [format!("0x{}", hex::encode(tx.rlp()))],
+        }))
+        .send()
+        .await
+        .expect("raw transaction should have been posted to the eth json-rpc collector")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json!(format!("{:#x}", tx.hash)), response["result"]);
+
+    // wait for 1 sequencer block time to make sure the bundle is preempted
+    tokio::time::timeout(
+        Duration::from_millis(test_composer.cfg.block_time_ms),
+        mock_guard.wait_until_satisfied(),
+    )
+    .await
+    .expect("mocked sequencer should have received a broadcast message from composer");
+}
+
+#[tokio::test]
+async fn other_methods_are_not_found() {
+    let test_composer = spawn_composer(&[]).await;
+
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(format!(
+            "http://{}/v1alpha1/rollups/test1/eth",
+            test_composer.api_addr
+        ))
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_blockNumber",
+            "params": [],
+        }))
+        .send()
+        .await
+        .expect("request should have been sent to the eth json-rpc collector")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(json!(-32601), response["error"]["code"]);
+}
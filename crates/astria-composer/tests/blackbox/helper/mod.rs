@@ -62,6 +62,7 @@ pub struct TestComposer {
     pub sequencer: wiremock::MockServer,
     pub setup_guard: MockGuard,
     pub grpc_collector_addr: SocketAddr,
+    pub api_addr: SocketAddr,
 }
 
 /// Spawns composer in a test environment.
@@ -117,6 +118,7 @@ pub async fn spawn_composer(rollup_ids: &[&str]) -> TestComposer {
         sequencer,
         setup_guard: sequencer_setup_guard,
         grpc_collector_addr,
+        api_addr: composer_addr,
     }
 }
 
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use astria_core::primitive::v1::RollupId;
+use ethers::prelude::Transaction;
+
+use crate::helper::{
+    mount_broadcast_tx_sync_mock,
+    spawn_composer,
+};
+
+#[tokio::test]
+async fn tx_from_one_rollup_is_received_by_sequencer() {
+    let test_composer = spawn_composer(&[]).await;
+    tokio::time::timeout(
+        Duration::from_millis(100),
+        test_composer.setup_guard.wait_until_satisfied(),
+    )
+    .await
+    .expect("composer and sequencer were not setup successfully");
+
+    let expected_chain_ids = vec![RollupId::from_unhashed_bytes("test1")];
+    let mock_guard =
+        mount_broadcast_tx_sync_mock(&test_composer.sequencer, expected_chain_ids, vec![0]).await;
+
+    let tx = Transaction::default();
+    let response = reqwest::Client::new()
+        .post(format!(
+            "http://{}/v1alpha1/rollups/test1/txs",
+            test_composer.api_addr
+        ))
+        .body(tx.rlp().to_vec())
+        .send()
+        .await
+        .expect("rollup transaction should have been posted to the http collector");
+    assert_eq!(reqwest::StatusCode::ACCEPTED, response.status());
+
+    // wait for 1 sequencer block time to make sure the bundle is preempted
+    tokio::time::timeout(
+        Duration::from_millis(test_composer.cfg.block_time_ms),
+        mock_guard.wait_until_satisfied(),
+    )
+    .await
+    .expect("mocked sequencer should have received a broadcast message from composer");
+}
+
+#[tokio::test]
+async fn empty_tx_is_rejected() {
+    let test_composer = spawn_composer(&[]).await;
+
+    let response = reqwest::Client::new()
+        .post(format!(
+            "http://{}/v1alpha1/rollups/test1/txs",
+            test_composer.api_addr
+        ))
+        .send()
+        .await
+        .expect("request should have been sent to the http collector");
+    assert_eq!(reqwest::StatusCode::BAD_REQUEST, response.status());
+}
@@ -1,4 +1,6 @@
 pub mod api;
+mod eth_rpc_collector;
 pub mod geth_collector;
 mod grpc_collector;
 pub mod helper;
+mod http_collector;
@@ -0,0 +1,6 @@
+// Required to force the benchmark target to actually register the divan benchmark cases.
+use astria_conductor as _;
+
+fn main() {
+    divan::main();
+}
@@ -46,6 +46,11 @@ impl<T> BlockCache<T> {
         })
     }
 
+    /// Returns if the cache holds no blocks.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
     /// Returns the next sequential block if it exists in the cache.
     pub(crate) fn pop(&mut self) -> Option<T> {
         let block = self.inner.remove(&self.next_height)?;
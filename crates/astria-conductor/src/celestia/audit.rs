@@ -0,0 +1,224 @@
+//! Auditing blocks that were reconstructed from Celestia without verification.
+//!
+//! In fast sync mode the reader skips verifying Sequencer header blobs against Sequencer's
+//! commits and validator sets until it has caught up with the Celestia head. Every block
+//! reconstructed that way is handed to the [`Auditor`], which verifies it in the background
+//! after the fact. The heights awaiting an audit are exported as a gauge and logged on shutdown.
+//! Blocks that fail their audit are counted and reported to the reader as an [`AuditMismatch`],
+//! which halts it: the rollup has already executed them as firm blocks, so continuing would only
+//! build on top of them.
+
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
+
+use astria_core::primitive::v1::BlockHash;
+use astria_eyre::eyre;
+use sequencer_client::tendermint::block::Height as SequencerHeight;
+use tokio::{
+    select,
+    sync::mpsc,
+    task::JoinSet,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{
+    debug,
+    error,
+    instrument,
+    warn,
+    Instrument as _,
+};
+
+use super::{
+    verify::BlobVerifier,
+    ReconstructedBlock,
+};
+
+/// The maximum number of blocks audited at the same time.
+const MAX_CONCURRENT_AUDITS: usize = 16;
+
+/// A block that was reconstructed without verifying its header blob.
+#[derive(Clone, Copy, Debug)]
+struct UnverifiedBlock {
+    celestia_height: u64,
+    sequencer_height: SequencerHeight,
+    block_hash: [u8; 32],
+}
+
+/// A block that was executed as firm block after being reconstructed without verification, but
+/// failed its audit.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "block `{block_hash}` at sequencer height `{sequencer_height}`, reconstructed from Celestia \
+     height `{celestia_height}` without verification, failed its audit"
+)]
+pub(super) struct AuditMismatch {
+    celestia_height: u64,
+    sequencer_height: SequencerHeight,
+    block_hash: BlockHash,
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+/// Hands blocks reconstructed without verification to the [`Auditor`].
+#[derive(Clone, Debug)]
+pub(super) struct AuditorHandle {
+    tx: mpsc::UnboundedSender<UnverifiedBlock>,
+}
+
+impl AuditorHandle {
+    /// Schedules `blocks` to be audited.
+    pub(super) fn audit(&self, blocks: &[ReconstructedBlock]) {
+        for block in blocks {
+            let unverified = UnverifiedBlock {
+                celestia_height: block.celestia_height,
+                sequencer_height: block.sequencer_height(),
+                block_hash: block.block_hash,
+            };
+            if self.tx.send(unverified).is_err() {
+                warn!(
+                    sequencer_height = %unverified.sequencer_height,
+                    "auditor is no longer running; block will not be audited",
+                );
+            }
+        }
+    }
+}
+
+/// Verifies blocks that were reconstructed without verification after the fact.
+pub(super) struct Auditor {
+    blob_verifier: Arc<BlobVerifier>,
+    blocks: mpsc::UnboundedReceiver<UnverifiedBlock>,
+    /// The blocks awaiting or undergoing an audit, keyed by sequencer height.
+    pending: BTreeMap<SequencerHeight, UnverifiedBlock>,
+    /// Reports blocks that failed their audit to the reader.
+    mismatches: mpsc::UnboundedSender<AuditMismatch>,
+    shutdown: CancellationToken,
+}
+
+impl Auditor {
+    /// Returns a new auditor and a handle to schedule blocks for it.
+    ///
+    /// Blocks that fail their audit are sent to `mismatches`.
+    pub(super) fn new(
+        blob_verifier: Arc<BlobVerifier>,
+        mismatches: mpsc::UnboundedSender<AuditMismatch>,
+        shutdown: CancellationToken,
+    ) -> (Self, AuditorHandle) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let auditor = Self {
+            blob_verifier,
+            blocks: rx,
+            pending: BTreeMap::new(),
+            mismatches,
+            shutdown,
+        };
+        (
+            auditor,
+            AuditorHandle {
+                tx,
+            },
+        )
+    }
+
+    #[instrument(skip_all)]
+    pub(super) async fn run_until_stopped(mut self) {
+        let mut audits = JoinSet::new();
+        loop {
+            select!(
+                biased;
+
+                () = self.shutdown.cancelled() => break,
+
+                Some(result) = audits.join_next() => {
+                    match result {
+                        Ok((block, result)) => self.finish_audit(block, result),
+                        Err(error) => error!(%error, "audit of block panicked"),
+                    }
+                }
+
+                Some(block) = self.blocks.recv(), if audits.len() < MAX_CONCURRENT_AUDITS => {
+                    self.pending.insert(block.sequencer_height, block);
+                    self.report_pending();
+                    let blob_verifier = self.blob_verifier.clone();
+                    audits.spawn(
+                        async move {
+                            let result = blob_verifier
+                                .verify_block_hash(block.sequencer_height, block.block_hash)
+                                .await;
+                            (block, result)
+                        }
+                        .in_current_span(),
+                    );
+                }
+
+                else => break,
+            );
+        }
+        audits.abort_all();
+        while let Ok(block) = self.blocks.try_recv() {
+            self.pending.insert(block.sequencer_height, block);
+        }
+        if let (Some(lowest), Some(highest)) =
+            (self.pending.keys().next(), self.pending.keys().next_back())
+        {
+            warn!(
+                number_of_blocks = self.pending.len(),
+                lowest_sequencer_height = %lowest,
+                highest_sequencer_height = %highest,
+                "exiting with blocks that were reconstructed without verification and not audited",
+            );
+        }
+    }
+
+    fn finish_audit(&mut self, block: UnverifiedBlock, result: eyre::Result<()>) {
+        self.pending.remove(&block.sequencer_height);
+        self.report_pending();
+        match result {
+            Ok(()) => {
+                metrics::counter!(
+                    crate::metrics_init::AUDITED_BLOCKS,
+                    telemetry::metrics::RESULT_LABEL => "verified",
+                )
+                .increment(1);
+                debug!(
+                    sequencer_height = %block.sequencer_height,
+                    "audit verified block that was reconstructed without verification",
+                );
+            }
+            Err(error) => {
+                metrics::counter!(
+                    crate::metrics_init::AUDITED_BLOCKS,
+                    telemetry::metrics::RESULT_LABEL => "mismatch",
+                )
+                .increment(1);
+                error!(
+                    %error,
+                    celestia_height = block.celestia_height,
+                    sequencer_height = %block.sequencer_height,
+                    block_hash = %BlockHash::new(block.block_hash),
+                    "audit failed to verify a block that was already executed as firm block",
+                );
+                let mismatch = AuditMismatch {
+                    celestia_height: block.celestia_height,
+                    sequencer_height: block.sequencer_height,
+                    block_hash: BlockHash::new(block.block_hash),
+                    source: error.into(),
+                };
+                if self.mismatches.send(mismatch).is_err() {
+                    warn!(
+                        sequencer_height = %block.sequencer_height,
+                        "reader is no longer running; audit mismatch will not halt it",
+                    );
+                }
+            }
+        }
+    }
+
+    fn report_pending(&self) {
+        // allow: the number of pending blocks will always be low enough to not cause precision
+        // loss
+        #[allow(clippy::cast_precision_loss)]
+        metrics::gauge!(crate::metrics_init::UNAUDITED_BLOCKS).set(self.pending.len() as f64);
+    }
+}
@@ -0,0 +1,109 @@
+//! Benchmarks for decoding the blobs read from Celestia at a single height.
+//!
+//! Run with `cargo bench --features benchmark -p astria-conductor`.
+
+use astria_core::{
+    brotli::compress_bytes,
+    celestia::{
+        namespace_v0_from_rollup_id,
+        namespace_v0_from_sha256_of_bytes,
+    },
+    primitive::v1::RollupId,
+    protocol::test_utils::ConfigureSequencerBlock,
+};
+use celestia_types::{
+    nmt::Namespace,
+    Blob,
+};
+use prost::Message as _;
+
+use super::{
+    convert::{
+        decode_raw_blobs,
+        BlobLimits,
+    },
+    fetch::RawBlobs,
+};
+
+const ROLLUP_ID: RollupId = RollupId::new([42; 32]);
+
+/// The number of sequencer blocks whose blobs are posted at the benchmarked Celestia height.
+const NUMBER_OF_BLOCKS: u32 = 200;
+
+/// The number of transactions in each sequencer block.
+const TXS_PER_BLOCK: u8 = 50;
+
+const LIMITS: BlobLimits = BlobLimits {
+    max_blob_size: 16 * 1024 * 1024,
+    max_blobs_per_height: 1024,
+    max_rollup_txs_per_blob: 65536,
+    max_response_size: 256 * 1024 * 1024,
+};
+
+struct Namespaces {
+    rollup: Namespace,
+    sequencer: Namespace,
+}
+
+impl Namespaces {
+    fn new() -> Self {
+        Self {
+            rollup: namespace_v0_from_rollup_id(ROLLUP_ID),
+            sequencer: namespace_v0_from_sha256_of_bytes("benchmark"),
+        }
+    }
+}
+
+fn make_blob(namespace: Namespace, message: &impl prost::Message) -> Blob {
+    let data = compress_bytes(&message.encode_to_vec()).unwrap();
+    Blob::new(namespace, data).unwrap()
+}
+
+fn make_raw_blobs(namespaces: &Namespaces) -> RawBlobs {
+    let mut header_blobs = Vec::new();
+    let mut rollup_blobs = Vec::new();
+    for height in 1..=NUMBER_OF_BLOCKS {
+        let (header, rollups) = ConfigureSequencerBlock {
+            height,
+            sequence_data: (0..TXS_PER_BLOCK)
+                .map(|i| (ROLLUP_ID, vec![i; 1024]))
+                .collect(),
+            ..Default::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        header_blobs.push(make_blob(namespaces.sequencer, &header.into_raw()));
+        for rollup in rollups {
+            rollup_blobs.push(make_blob(namespaces.rollup, &rollup.into_raw()));
+        }
+    }
+    RawBlobs {
+        celestia_height: 1,
+        header_blobs,
+        rollup_blobs,
+    }
+}
+
+#[divan::bench(args = [1, 2, 4, 8])]
+fn decode(bencher: divan::Bencher, parallelism: usize) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let namespaces = Namespaces::new();
+    let raw_blobs = make_raw_blobs(&namespaces);
+    bencher
+        .with_inputs(|| RawBlobs {
+            celestia_height: raw_blobs.celestia_height,
+            header_blobs: raw_blobs.header_blobs.clone(),
+            rollup_blobs: raw_blobs.rollup_blobs.clone(),
+        })
+        .bench_values(|raw_blobs| {
+            runtime
+                .block_on(decode_raw_blobs(
+                    raw_blobs,
+                    namespaces.rollup,
+                    namespaces.sequencer,
+                    LIMITS,
+                    parallelism,
+                ))
+                .unwrap()
+        });
+}
@@ -2,23 +2,36 @@
 
 use std::time::Duration;
 
+use astria_core::celestia::Namespace;
 use astria_eyre::eyre::{
     self,
     WrapErr as _,
 };
 use jsonrpsee::http_client::HttpClient as CelestiaClient;
 use sequencer_client::HttpClient as SequencerClient;
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 
-use super::Reader;
+use super::{
+    BlobLimits,
+    Reader,
+    ShadowReader,
+};
 use crate::executor;
 
 pub(crate) struct Builder {
+    pub(crate) blob_limits: BlobLimits,
     pub(crate) celestia_block_time: Duration,
+    pub(crate) celestia_confirmation_depth: u64,
     pub(crate) celestia_http_endpoint: String,
     pub(crate) celestia_token: String,
+    pub(crate) celestia_shadow_http_endpoint: String,
+    pub(crate) celestia_shadow_token: String,
     pub(crate) executor: executor::Handle,
+    pub(crate) firm_synced: watch::Sender<bool>,
+    pub(crate) fast_sync: bool,
     pub(crate) sequencer_cometbft_client: SequencerClient,
+    pub(crate) sequencer_namespace: Option<Namespace>,
     pub(crate) shutdown: CancellationToken,
 }
 
@@ -26,28 +39,64 @@ impl Builder {
     /// Creates a new [`Reader`] instance,
     pub(crate) fn build(self) -> eyre::Result<Reader> {
         let Self {
+            blob_limits,
             celestia_block_time,
+            celestia_confirmation_depth,
             celestia_http_endpoint,
             celestia_token,
+            celestia_shadow_http_endpoint,
+            celestia_shadow_token,
             executor,
+            firm_synced,
+            fast_sync,
             sequencer_cometbft_client,
+            sequencer_namespace,
             shutdown,
         } = self;
 
-        let celestia_client = create_celestia_client(celestia_http_endpoint, &celestia_token)
-            .wrap_err("failed initializing client for Celestia HTTP RPC")?;
+        let celestia_client = create_celestia_client(
+            celestia_http_endpoint,
+            &celestia_token,
+            blob_limits.max_response_size,
+        )
+        .wrap_err("failed initializing client for Celestia HTTP RPC")?;
+
+        let shadow_reader = if celestia_shadow_http_endpoint.is_empty() {
+            None
+        } else {
+            let client = create_celestia_client(
+                celestia_shadow_http_endpoint,
+                &celestia_shadow_token,
+                blob_limits.max_response_size,
+            )
+            .wrap_err("failed initializing client for shadow Celestia HTTP RPC")?;
+            Some(ShadowReader::new(client, blob_limits.max_response_size))
+        };
 
         Ok(Reader {
+            blob_limits,
             celestia_block_time,
             celestia_client,
+            celestia_confirmation_depth,
             executor,
+            firm_synced,
+            fast_sync,
             sequencer_cometbft_client,
+            sequencer_namespace,
+            shadow_reader,
             shutdown,
         })
     }
 }
 
-fn create_celestia_client(endpoint: String, bearer_token: &str) -> eyre::Result<CelestiaClient> {
+/// Creates a client for the Celestia node at `endpoint`.
+///
+/// The client stops reading responses as soon as they exceed `max_response_size` bytes.
+fn create_celestia_client(
+    endpoint: String,
+    bearer_token: &str,
+    max_response_size: u32,
+) -> eyre::Result<CelestiaClient> {
     use jsonrpsee::http_client::{
         HeaderMap,
         HttpClientBuilder,
@@ -59,6 +108,7 @@ fn create_celestia_client(endpoint: String, bearer_token: &str) -> eyre::Result<
     headers.insert(http::header::AUTHORIZATION, auth_value);
     let client = HttpClientBuilder::default()
         .set_headers(headers)
+        .max_response_size(max_response_size)
         .build(endpoint)
         .wrap_err("failed constructing Celestia JSONRPC HTTP Client")?;
     Ok(client)
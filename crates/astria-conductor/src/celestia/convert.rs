@@ -1,6 +1,14 @@
+use std::num::NonZeroUsize;
+
 use astria_core::{
-    brotli::decompress_bytes,
+    brotli::{
+        decompress_bytes_with_limit,
+        DecompressedSizeExceeded,
+    },
+    celestia::HexNamespace,
     sequencerblock::v1alpha1::{
+        BlobFormat,
+        CelestiaEmptyBlocksMarker,
         CelestiaRollupBlob,
         CelestiaSequencerBlob,
     },
@@ -9,56 +17,164 @@ use celestia_types::{
     nmt::Namespace,
     Blob,
 };
-use prost::{
-    Message as _,
-    Name as _,
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use prost::Name as _;
+use tokio::{
+    task::spawn_blocking,
+    try_join,
 };
-use telemetry::display::base64;
 use tracing::{
     info,
     warn,
+    Span,
 };
 
-use super::fetch::RawBlobs;
+use super::{
+    fetch::RawBlobs,
+    rejection::RejectionReason,
+};
+use crate::metrics_init;
 
 type StdError = dyn std::error::Error;
 
+/// Limits on the blobs read from Celestia, protecting conductor from exhausting its resources
+/// on giant or numerous blobs posted to the namespaces it reads from.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BlobLimits {
+    /// The maximum size of a blob in bytes, both before and after decompression.
+    pub(crate) max_blob_size: usize,
+    /// The maximum number of blobs per namespace that are decoded at a single Celestia height.
+    pub(crate) max_blobs_per_height: usize,
+    /// The maximum number of rollup transactions in a single rollup blob.
+    pub(crate) max_rollup_txs_per_blob: usize,
+    /// The maximum size in bytes of the Celestia node's response to a blob fetch.
+    pub(crate) max_response_size: u32,
+}
+
+/// The maximum number of blocking tasks that the blobs of one namespace at a single Celestia
+/// height are decoded on.
+///
+/// Up to ten Celestia heights are processed concurrently, so this keeps the number of blocking
+/// threads spawned for decoding well below the size of tokio's blocking thread pool.
+const MAX_DECODING_PARALLELISM: usize = 8;
+
+/// Returns the number of blocking tasks to decode the blobs of one namespace on.
+///
+/// This is the available parallelism of the host, capped at [`MAX_DECODING_PARALLELISM`].
+pub(super) fn decoding_parallelism() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(MAX_DECODING_PARALLELISM)
+}
+
 /// Decodes blob bytes into sequencer header or rollup items, returning
 /// them grouped by their block hashes.
-pub(super) fn decode_raw_blobs(
+///
+/// A blob under the sequencer namespace holding a [`CelestiaEmptyBlocksMarker`] is expanded into
+/// the sequencer headers of the empty blocks it marks. Blobs exceeding `limits` are dropped.
+/// Only the first `limits.max_blobs_per_height` blobs of each namespace are decoded.
+///
+/// Decompressing, decoding and hashing the blobs is CPU bound, so the blobs of each namespace
+/// are split into up to `parallelism` chunks that are decoded on tokio's blocking thread pool.
+/// The order of the blobs is preserved.
+///
+/// # Errors
+/// Returns an error if a decoding task panicked.
+pub(super) async fn decode_raw_blobs(
     raw_blobs: RawBlobs,
     rollup_namespace: Namespace,
     sequencer_namespace: Namespace,
-) -> ConvertedBlobs {
-    let mut converted_blobs = ConvertedBlobs::new(raw_blobs.celestia_height);
-    for blob in raw_blobs.header_blobs {
+    limits: BlobLimits,
+    parallelism: usize,
+) -> eyre::Result<ConvertedBlobs> {
+    let RawBlobs {
+        celestia_height,
+        mut header_blobs,
+        mut rollup_blobs,
+    } = raw_blobs;
+    warn_if_exceeding_blobs_per_height(&header_blobs, sequencer_namespace, limits);
+    warn_if_exceeding_blobs_per_height(&rollup_blobs, rollup_namespace, limits);
+    header_blobs.truncate(limits.max_blobs_per_height);
+    rollup_blobs.truncate(limits.max_blobs_per_height);
+
+    let header_blobs = decode_in_parallel(header_blobs, parallelism, move |blob| {
         if blob.namespace == sequencer_namespace {
-            if let Some(header) = convert_header(&blob) {
-                converted_blobs.push_header(header);
-            }
+            convert_header(blob, limits)
         } else {
             warn!(
-                sequencer_namespace = %base64(sequencer_namespace.as_ref()),
-                namespace_in_blob = %base64(blob.namespace.as_ref()),
+                sequencer_namespace = %HexNamespace(sequencer_namespace),
+                namespace_in_blob = %HexNamespace(blob.namespace),
                 "blob's namespaces was not the expected sequencer namespace; dropping",
             );
+            None
         }
-    }
-
-    for blob in raw_blobs.rollup_blobs {
+    });
+    let rollup_blobs = decode_in_parallel(rollup_blobs, parallelism, move |blob| {
         if blob.namespace == rollup_namespace {
-            if let Some(rollup) = convert_rollup(&blob) {
-                converted_blobs.push_rollup(rollup);
-            }
+            convert_rollup(blob, limits)
         } else {
             warn!(
-                rollup_namespace = %base64(rollup_namespace.as_ref()),
-                namespace_in_blob = %base64(blob.namespace.as_ref()),
+                rollup_namespace = %HexNamespace(rollup_namespace),
+                namespace_in_blob = %HexNamespace(blob.namespace),
                 "blob's namespaces was not the expected rollup namespace; dropping",
             );
+            None
+        }
+    });
+    let (header_blobs, rollup_blobs) = try_join!(header_blobs, rollup_blobs)?;
+    record_blob_formats(
+        header_blobs
+            .iter()
+            .map(|(_, format)| *format)
+            .chain(rollup_blobs.iter().map(|(_, format)| *format)),
+    );
+    Ok(ConvertedBlobs {
+        celestia_height,
+        header_blobs: header_blobs
+            .into_iter()
+            .flat_map(|(blobs, _)| blobs)
+            .collect(),
+        rollup_blobs: rollup_blobs.into_iter().map(|(blob, _)| blob).collect(),
+    })
+}
+
+/// Splits `blobs` into up to `parallelism` evenly sized chunks and runs `decode` over each of
+/// them on a blocking task, collecting the results in the order of `blobs`.
+async fn decode_in_parallel<T, F>(
+    blobs: Vec<Blob>,
+    parallelism: usize,
+    decode: F,
+) -> eyre::Result<Vec<T>>
+where
+    T: Send + 'static,
+    F: Fn(&Blob) -> Option<T> + Clone + Send + 'static,
+{
+    let number_of_blobs = blobs.len();
+    let chunk_size = number_of_blobs.div_ceil(parallelism.max(1)).max(1);
+    let mut blobs = blobs.into_iter();
+    let mut tasks = Vec::with_capacity(parallelism);
+    loop {
+        let chunk: Vec<Blob> = blobs.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
         }
+        let decode = decode.clone();
+        let span = Span::current();
+        tasks.push(spawn_blocking(move || {
+            span.in_scope(|| chunk.iter().filter_map(decode).collect::<Vec<_>>())
+        }));
     }
-    converted_blobs
+    let mut decoded = Vec::with_capacity(number_of_blobs);
+    for task in tasks {
+        let chunk = task
+            .await
+            .wrap_err("encountered panic while decoding raw Celestia blobs")?;
+        decoded.extend(chunk);
+    }
+    Ok(decoded)
 }
 
 /// An unsorted [`CelestiaSequencerBlob`] and [`CelestiaRollupBlob`].
@@ -80,78 +196,217 @@ impl ConvertedBlobs {
     pub(super) fn into_parts(self) -> (u64, Vec<CelestiaSequencerBlob>, Vec<CelestiaRollupBlob>) {
         (self.celestia_height, self.header_blobs, self.rollup_blobs)
     }
+}
 
-    fn new(celestia_height: u64) -> Self {
-        Self {
-            celestia_height,
-            header_blobs: Vec::new(),
-            rollup_blobs: Vec::new(),
+/// Records the formats of the blobs decoded at a single Celestia height.
+fn record_blob_formats(formats: impl Iterator<Item = BlobFormat>) {
+    let mut json = 0u32;
+    let mut protobuf = 0u32;
+    let mut borsh = 0u32;
+    for format in formats {
+        match format {
+            BlobFormat::Json => json = json.saturating_add(1),
+            BlobFormat::Protobuf => protobuf = protobuf.saturating_add(1),
+            BlobFormat::Borsh => borsh = borsh.saturating_add(1),
         }
     }
-
-    fn push_header(&mut self, header: CelestiaSequencerBlob) {
-        self.header_blobs.push(header);
+    for (format, count) in [
+        (BlobFormat::Json, json),
+        (BlobFormat::Protobuf, protobuf),
+        (BlobFormat::Borsh, borsh),
+    ] {
+        metrics::counter!(
+            metrics_init::DECODED_CELESTIA_BLOBS,
+            "format" => format.as_str(),
+        )
+        .increment(u64::from(count));
+        metrics::gauge!(
+            metrics_init::DECODED_CELESTIA_BLOBS_AT_LATEST_HEIGHT,
+            "format" => format.as_str(),
+        )
+        .set(f64::from(count));
     }
+}
 
-    fn push_rollup(&mut self, rollup: CelestiaRollupBlob) {
-        self.rollup_blobs.push(rollup);
+fn warn_if_exceeding_blobs_per_height(blobs: &[Blob], namespace: Namespace, limits: BlobLimits) {
+    if blobs.len() > limits.max_blobs_per_height {
+        warn!(
+            namespace = %HexNamespace(namespace),
+            number_of_blobs = blobs.len(),
+            max_blobs_per_height = limits.max_blobs_per_height,
+            "received more blobs than permitted per Celestia height; dropping the excess blobs",
+        );
+        let excess = blobs.len() - limits.max_blobs_per_height;
+        RejectionReason::ExcessPerHeight.count(excess.try_into().unwrap_or(u64::MAX));
     }
 }
 
-fn convert_header(blob: &Blob) -> Option<CelestiaSequencerBlob> {
-    use astria_core::generated::sequencerblock::v1alpha1::CelestiaSequencerBlob as ProtoType;
-    let data = decompress_bytes(&blob.data)
+fn decompress(blob: &Blob, limits: BlobLimits) -> Option<Vec<u8>> {
+    if blob.data.len() > limits.max_blob_size {
+        info!(
+            blob_size = blob.data.len(),
+            max_blob_size = limits.max_blob_size,
+            "blob exceeds the maximum permitted size; dropping the blob",
+        );
+        RejectionReason::Oversize.count(1);
+        return None;
+    }
+    decompress_bytes_with_limit(&blob.data, limits.max_blob_size)
         .inspect_err(|err| {
             info!(
                 error = err as &StdError,
                 "failed decompressing blob data; dropping the blob",
             );
+            if DecompressedSizeExceeded::is_cause_of(err) {
+                RejectionReason::Oversize.count(1);
+            } else {
+                RejectionReason::DeserializeFailed.count(1);
+            }
         })
-        .ok()?;
-    let raw = ProtoType::decode(&*data)
-        .inspect_err(|err| {
+        .ok()
+}
+
+/// Converts a blob under the sequencer namespace into the sequencer headers it holds.
+///
+/// This is a single header, unless the blob is a [`CelestiaEmptyBlocksMarker`], which is only
+/// tried if the blob is not a valid sequencer header.
+fn convert_header(
+    blob: &Blob,
+    limits: BlobLimits,
+) -> Option<(Vec<CelestiaSequencerBlob>, BlobFormat)> {
+    use astria_core::generated::sequencerblock::v1alpha1::CelestiaSequencerBlob as ProtoType;
+    let data = decompress(blob, limits)?;
+    let (raw, format) = match BlobFormat::decode::<ProtoType>(&data) {
+        Ok(decoded) => decoded,
+        Err(err) => {
+            return convert_empty_blocks_marker(&data).or_else(|| {
+                info!(
+                    error = &err as &StdError,
+                    target = ProtoType::full_name(),
+                    "failed decoding blob bytes as sequencer header; dropping the blob",
+                );
+                RejectionReason::DeserializeFailed.count(1);
+                None
+            });
+        }
+    };
+    match CelestiaSequencerBlob::try_from_raw(raw) {
+        Ok(header) => Some((vec![header], format)),
+        Err(err) => convert_empty_blocks_marker(&data).or_else(|| {
             info!(
-                error = err as &StdError,
-                target = ProtoType::full_name(),
-                "failed decoding blob bytes as sequencer header; dropping the blob",
+                error = &err as &StdError,
+                "failed verifying decoded sequencer header; dropping it"
             );
-        })
-        .ok()?;
-    CelestiaSequencerBlob::try_from_raw(raw)
+            RejectionReason::DeserializeFailed.count(1);
+            None
+        }),
+    }
+}
+
+/// Converts blob bytes holding a [`CelestiaEmptyBlocksMarker`] into the sequencer headers of the
+/// empty blocks it marks.
+///
+/// Returns `None` without logging if the bytes do not decode to a marker with any blocks, since
+/// they are only tried as a marker after failing to convert them to a sequencer header.
+fn convert_empty_blocks_marker(data: &[u8]) -> Option<(Vec<CelestiaSequencerBlob>, BlobFormat)> {
+    use astria_core::generated::sequencerblock::v1alpha1::CelestiaEmptyBlocksMarker as ProtoType;
+    let (raw, format) = BlobFormat::decode::<ProtoType>(data).ok()?;
+    if raw.empty_blocks.is_empty() {
+        return None;
+    }
+    CelestiaEmptyBlocksMarker::try_from_raw(raw)
+        .map(|marker| (marker.into_empty_blocks(), format))
         .inspect_err(|err| {
             info!(
                 error = err as &StdError,
-                "failed verifying decoded sequencer header; dropping it"
+                "failed verifying decoded empty blocks marker; dropping it"
             );
+            RejectionReason::DeserializeFailed.count(1);
         })
         .ok()
 }
 
-fn convert_rollup(blob: &Blob) -> Option<CelestiaRollupBlob> {
+fn convert_rollup(blob: &Blob, limits: BlobLimits) -> Option<(CelestiaRollupBlob, BlobFormat)> {
     use astria_core::generated::sequencerblock::v1alpha1::CelestiaRollupBlob as ProtoType;
-    let data = decompress_bytes(&blob.data)
-        .inspect_err(|err| {
-            info!(
-                error = err as &StdError,
-                "failed decompressing rollup blob data; dropping the blob",
-            );
-        })
-        .ok()?;
-    let raw_blob = ProtoType::decode(&*data)
+    let data = decompress(blob, limits)?;
+    let (raw_blob, format) = BlobFormat::decode::<ProtoType>(&data)
         .inspect_err(|err| {
             info!(
                 error = err as &StdError,
                 target = ProtoType::full_name(),
                 "failed decoding blob bytes as rollup element; dropping the blob",
             );
+            RejectionReason::DeserializeFailed.count(1);
         })
         .ok()?;
+    if raw_blob.transactions.len() > limits.max_rollup_txs_per_blob {
+        info!(
+            number_of_transactions = raw_blob.transactions.len(),
+            max_rollup_txs_per_blob = limits.max_rollup_txs_per_blob,
+            "rollup blob contains more transactions than permitted; dropping the blob",
+        );
+        RejectionReason::Oversize.count(1);
+        return None;
+    }
     CelestiaRollupBlob::try_from_raw(raw_blob)
+        .map(|rollup| (rollup, format))
         .inspect_err(|err| {
             info!(
                 error = err as &StdError,
                 "failed verifying decoded rollup element; dropping it"
             );
+            RejectionReason::DeserializeFailed.count(1);
         })
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use astria_core::{
+        brotli::compress_bytes,
+        celestia::namespace_v0_from_first_10_bytes,
+        protocol::test_utils::ConfigureSequencerBlock,
+    };
+
+    use super::*;
+
+    const LIMITS: BlobLimits = BlobLimits {
+        max_blob_size: 1 << 20,
+        max_blobs_per_height: 10,
+        max_rollup_txs_per_blob: 10,
+        max_response_size: 1 << 20,
+    };
+
+    fn empty_block(height: u32) -> CelestiaSequencerBlob {
+        ConfigureSequencerBlock {
+            height,
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs()
+        .0
+    }
+
+    #[test]
+    fn empty_blocks_marker_is_expanded_into_its_headers() {
+        let marker = CelestiaEmptyBlocksMarker::new(vec![empty_block(2), empty_block(1)]).unwrap();
+        for format in [BlobFormat::Protobuf, BlobFormat::Json] {
+            let data = compress_bytes(&format.encode(&marker.clone().into_raw()).unwrap()).unwrap();
+            let blob = Blob::new(namespace_v0_from_first_10_bytes(&[1; 10]), data).unwrap();
+            let (headers, decoded_format) = convert_header(&blob, LIMITS).unwrap();
+            assert_eq!(format, decoded_format);
+            let heights: Vec<_> = headers.iter().map(|header| header.height().value()).collect();
+            assert_eq!(vec![1, 2], heights);
+        }
+    }
+
+    #[test]
+    fn sequencer_header_is_not_read_as_marker() {
+        let data =
+            compress_bytes(&BlobFormat::Protobuf.encode(&empty_block(1).into_raw()).unwrap())
+                .unwrap();
+        let blob = Blob::new(namespace_v0_from_first_10_bytes(&[1; 10]), data).unwrap();
+        let (headers, _) = convert_header(&blob, LIMITS).unwrap();
+        assert_eq!(1, headers.len());
+    }
+}
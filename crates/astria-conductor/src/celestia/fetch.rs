@@ -1,22 +1,28 @@
 use std::{
+    future::Future,
     sync::atomic::AtomicU32,
     time::Duration,
 };
 
+use astria_celestia_client::dedup::BlobReference;
+use astria_core::celestia::HexNamespace;
 use astria_eyre::{
     eyre,
-    eyre::WrapErr as _,
+    eyre::{
+        bail,
+        WrapErr as _,
+    },
 };
 use celestia_types::{
     nmt::Namespace,
     Blob,
+    Commitment,
 };
-use jsonrpsee::{
-    self,
-    http_client::HttpClient as CelestiaClient,
+use tokio::{
+    select,
+    try_join,
 };
-use telemetry::display::base64;
-use tokio::try_join;
+use tokio_util::sync::CancellationToken;
 use tracing::{
     instrument,
     warn,
@@ -26,6 +32,23 @@ use tryhard::{
     RetryPolicy,
 };
 
+use super::{
+    read_session::{
+        ReadSession,
+        RetrievalError,
+    },
+    rejection::RejectionReason,
+};
+
+/// Returned if the Celestia node's response to a blob fetch exceeded the configured limit.
+///
+/// Fetches failing with this error are not retried: the node would send the same response again.
+#[derive(Debug, thiserror::Error)]
+#[error("the Celestia node's response exceeded the limit of {limit} bytes and was aborted")]
+pub(super) struct ResponseTooLarge {
+    limit: u32,
+}
+
 pub(super) struct RawBlobs {
     pub(super) celestia_height: u64,
     pub(super) header_blobs: Vec<Blob>,
@@ -44,31 +67,61 @@ impl RawBlobs {
 
 /// Fetch Celestia blobs at `celestia_height` matching `sequencer_namespace` and `rollup_namespace`.
 ///
-/// Retries indefinitely if the underlying transport failed. Immediately returns with an error in
-/// all other cases.
+/// Both namespaces are read within `session`, so that they are read from a node that synced up
+/// to the same pinned head height.
+///
+/// Rollup blobs that are [`BlobReference`]s to rollup blobs included at earlier heights are
+/// replaced by the blobs they point to, see [`resolve_blob_references`].
+///
+/// Retries indefinitely if the underlying transport failed or the node has not yet synced up to
+/// the pinned height. Immediately returns with an error in all other cases, or if
+/// `cancellation_token` is cancelled before the blobs were fetched. A response exceeding
+/// `max_response_size` bytes fails the fetch with [`ResponseTooLarge`].
 #[instrument(skip_all, fields(
     celestia_height,
-    sequencer_namespace = %base64(sequencer_namespace.as_ref()),
-    rollup_namespace = %base64(rollup_namespace.as_ref()),
+    pinned_height = session.pinned_height(),
+    sequencer_namespace = %HexNamespace(sequencer_namespace),
+    rollup_namespace = %HexNamespace(rollup_namespace),
 ))]
 pub(super) async fn fetch_new_blobs(
-    client: CelestiaClient,
+    session: ReadSession,
     celestia_height: u64,
     rollup_namespace: Namespace,
     sequencer_namespace: Namespace,
+    max_response_size: u32,
+    cancellation_token: CancellationToken,
 ) -> eyre::Result<RawBlobs> {
     let header_blobs = async {
-        fetch_blobs_with_retry(client.clone(), celestia_height, sequencer_namespace)
-            .await
-            .wrap_err("failed to fetch header blobs")
+        fetch_blobs_with_retry(
+            session.clone(),
+            celestia_height,
+            sequencer_namespace,
+            max_response_size,
+        )
+        .await
+        .wrap_err("failed to fetch header blobs")
     };
     let rollup_blobs = async {
-        fetch_blobs_with_retry(client.clone(), celestia_height, rollup_namespace)
+        let blobs = fetch_blobs_with_retry(
+            session.clone(),
+            celestia_height,
+            rollup_namespace,
+            max_response_size,
+        )
+        .await
+        .wrap_err("failed to fetch rollup blobs")?;
+        resolve_blob_references(&session, celestia_height, blobs)
             .await
-            .wrap_err("failed to fetch rollup blobs")
+            .wrap_err("failed to resolve references among rollup blobs")
     };
 
-    let (header_blobs, rollup_blobs) = try_join!(header_blobs, rollup_blobs)?;
+    let (header_blobs, rollup_blobs) = select!(
+        biased;
+
+        () = cancellation_token.cancelled() => bail!("fetching blobs was cancelled"),
+
+        res = async { try_join!(header_blobs, rollup_blobs) } => res?,
+    );
 
     Ok(RawBlobs {
         celestia_height,
@@ -77,19 +130,107 @@ pub(super) async fn fetch_new_blobs(
     })
 }
 
+/// Replaces the [`BlobReference`]s among the rollup `blobs` read at `celestia_height` by the
+/// blobs they point to.
+///
+/// See [`astria_celestia_client::dedup`] for how references are posted. A reference must point
+/// to a blob included at an earlier height under the same namespace. References that do not, or
+/// whose blob the Celestia node does not know, are dropped and counted as rejected.
+///
+/// Fetching a referenced blob is retried like fetching all blobs at a height.
+async fn resolve_blob_references(
+    session: &ReadSession,
+    celestia_height: u64,
+    blobs: Vec<Blob>,
+) -> eyre::Result<Vec<Blob>> {
+    let mut resolved = Vec::with_capacity(blobs.len());
+    for blob in blobs {
+        let Some(reference) = BlobReference::decode(&blob.data) else {
+            resolved.push(blob);
+            continue;
+        };
+        if reference.celestia_height >= celestia_height {
+            warn!(
+                reference.celestia_height,
+                "rollup blob references a blob that is not at an earlier Celestia height; \
+                 dropping it"
+            );
+            RejectionReason::UnresolvedReference.count(1);
+            continue;
+        }
+        match fetch_blob_with_retry(
+            session.clone(),
+            reference.celestia_height,
+            blob.namespace,
+            reference.commitment,
+        )
+        .await
+        {
+            Ok(referenced) => resolved.push(referenced),
+            Err(RetrievalError::Rpc(jsonrpsee::core::Error::Call(error))) => {
+                warn!(
+                    error = &error as &dyn std::error::Error,
+                    reference.celestia_height,
+                    "failed fetching the blob referenced by a rollup blob; dropping it"
+                );
+                RejectionReason::UnresolvedReference.count(1);
+            }
+            Err(error) => {
+                return Err(error).wrap_err("failed fetching the blob referenced by a rollup blob");
+            }
+        }
+    }
+    Ok(resolved)
+}
+
 async fn fetch_blobs_with_retry(
-    client: CelestiaClient,
+    session: ReadSession,
     height: u64,
     namespace: Namespace,
+    max_response_size: u32,
 ) -> eyre::Result<Vec<Blob>> {
-    use celestia_rpc::BlobClient as _;
+    retrieve_with_retry(move || {
+        let session = session.clone();
+        async move { session.get_all_blobs(height, namespace).await }
+    })
+    .await
+    .map_err(|error| match error {
+        RetrievalError::Rpc(error) if is_response_too_large(&error) => {
+            eyre::Report::new(ResponseTooLarge {
+                limit: max_response_size,
+            })
+        }
+        error => eyre::Report::new(error),
+    })
+    .wrap_err("failed fetching blocks without being able to recover")
+}
 
+async fn fetch_blob_with_retry(
+    session: ReadSession,
+    height: u64,
+    namespace: Namespace,
+    commitment: Commitment,
+) -> Result<Blob, RetrievalError> {
+    retrieve_with_retry(move || {
+        let session = session.clone();
+        let commitment = commitment.clone();
+        async move { session.get_blob(height, namespace, commitment).await }
+    })
+    .await
+}
+
+/// Runs `retrieve` until it succeeds or fails with an error that is not retried.
+async fn retrieve_with_retry<T, F, Fut>(retrieve: F) -> Result<T, RetrievalError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RetrievalError>>,
+{
     let number_attempts = AtomicU32::new(0);
     let retry_config = tryhard::RetryFutureConfig::new(u32::MAX)
         .custom_backoff(FetchBlobsRetryStrategy::new(Duration::from_millis(100)))
         .max_delay(Duration::from_secs(20))
         .on_retry(
-            |attempt: u32, next_delay: Option<Duration>, error: &jsonrpsee::core::Error| {
+            |attempt: u32, next_delay: Option<Duration>, error: &RetrievalError| {
                 number_attempts.store(attempt, std::sync::atomic::Ordering::Relaxed);
                 let wait_duration = next_delay
                     .map(humantime::format_duration)
@@ -104,19 +245,7 @@ async fn fetch_blobs_with_retry(
             },
         );
 
-    tryhard::retry_fn(move || {
-        let client = client.clone();
-        async move {
-            match client.blob_get_all(height, &[namespace]).await {
-                Ok(blobs) => Ok(blobs),
-                Err(err) if is_blob_not_found(&err) => Ok(vec![]),
-                Err(err) => Err(err),
-            }
-        }
-    })
-    .with_config(retry_config)
-    .await
-    .wrap_err("failed fetching blocks without being able to recover")
+    tryhard::retry_fn(retrieve).with_config(retry_config).await
 }
 
 struct FetchBlobsRetryStrategy {
@@ -131,10 +260,10 @@ impl FetchBlobsRetryStrategy {
     }
 }
 
-impl<'a> BackoffStrategy<'a, jsonrpsee::core::Error> for FetchBlobsRetryStrategy {
+impl<'a> BackoffStrategy<'a, RetrievalError> for FetchBlobsRetryStrategy {
     type Output = RetryPolicy;
 
-    fn delay(&mut self, _attempt: u32, error: &'a jsonrpsee::core::Error) -> Self::Output {
+    fn delay(&mut self, _attempt: u32, error: &'a RetrievalError) -> Self::Output {
         if should_retry(error) {
             let prev_delay = self.delay;
             self.delay = self.delay.saturating_mul(2);
@@ -145,13 +274,87 @@ impl<'a> BackoffStrategy<'a, jsonrpsee::core::Error> for FetchBlobsRetryStrategy
     }
 }
 
-fn should_retry(error: &jsonrpsee::core::Error) -> bool {
-    matches!(error, jsonrpsee::core::Error::Transport(_))
+fn should_retry(error: &RetrievalError) -> bool {
+    match error {
+        RetrievalError::Rpc(error) if is_response_too_large(error) => false,
+        error => error.is_transient(),
+    }
 }
 
-fn is_blob_not_found(error: &jsonrpsee::core::Error) -> bool {
-    let jsonrpsee::core::Error::Call(error) = error else {
+/// Returns if the HTTP transport aborted reading a response because it exceeded the client's
+/// maximum response size.
+fn is_response_too_large(error: &jsonrpsee::core::Error) -> bool {
+    use jsonrpsee::http_client::transport;
+
+    let jsonrpsee::core::Error::Transport(source) = error else {
         return false;
     };
-    error.code() == 1 && error.message().contains("blob: not found")
+    // NOTE: jsonrpsee reports both oversized requests and oversized responses as
+    // `RequestTooLarge`. Blob fetches send tiny requests, so it is always the response here.
+    matches!(
+        source.downcast_ref::<transport::Error>(),
+        Some(transport::Error::RequestTooLarge)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::http_client::HttpClientBuilder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn cancelled_fetch_returns_error() {
+        // nothing is listening on the port, so requests fail and are retried indefinitely
+        let client = HttpClientBuilder::default()
+            .build("http://127.0.0.1:1")
+            .unwrap();
+        let namespace = astria_core::celestia::namespace_v0_from_first_10_bytes(&[1; 10]);
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+        let error = fetch_new_blobs(
+            ReadSession::new(client, 1),
+            1,
+            namespace,
+            namespace,
+            u32::MAX,
+            cancellation_token,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!("fetching blobs was cancelled", error.to_string());
+    }
+
+    #[tokio::test]
+    async fn references_not_pointing_to_earlier_heights_are_dropped() {
+        // nothing is listening on the port, so the test would hang retrying if the referenced
+        // blobs were fetched
+        let client = HttpClientBuilder::default()
+            .build("http://127.0.0.1:1")
+            .unwrap();
+        let namespace = astria_core::celestia::namespace_v0_from_first_10_bytes(&[1; 10]);
+        let plain = Blob::new(namespace, vec![1, 2, 3]).unwrap();
+        let reference = |celestia_height| {
+            let reference = BlobReference {
+                celestia_height,
+                commitment: plain.commitment.clone(),
+            };
+            Blob::new(namespace, reference.encode()).unwrap()
+        };
+        let blobs = vec![reference(5), plain.clone(), reference(6)];
+        let resolved = resolve_blob_references(&ReadSession::new(client, 5), 5, blobs)
+            .await
+            .unwrap();
+        assert_eq!(1, resolved.len());
+        assert_eq!(plain.data, resolved[0].data);
+    }
+
+    #[test]
+    fn oversized_responses_are_not_retried() {
+        let error = jsonrpsee::core::Error::Transport(
+            jsonrpsee::http_client::transport::Error::RequestTooLarge.into(),
+        );
+        assert!(is_response_too_large(&error));
+        assert!(!should_retry(&RetrievalError::Rpc(error)));
+    }
 }
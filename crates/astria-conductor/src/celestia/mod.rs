@@ -5,7 +5,11 @@ use std::{
 };
 
 use astria_core::{
-    primitive::v1::RollupId,
+    celestia::HexNamespace,
+    primitive::v1::{
+        BlockHash,
+        RollupId,
+    },
     sequencerblock::v1alpha1::block::SequencerBlockHeader,
 };
 use astria_eyre::eyre::{
@@ -29,13 +33,13 @@ use sequencer_client::{
     tendermint_rpc,
     HttpClient as SequencerClient,
 };
-use telemetry::display::{
-    base64,
-    json,
-};
+use telemetry::display::json;
 use tokio::{
     select,
-    sync::mpsc,
+    sync::{
+        mpsc,
+        watch,
+    },
     task::spawn_blocking,
     try_join,
 };
@@ -51,6 +55,7 @@ use tracing::{
     instrument,
     trace,
     warn,
+    Instrument as _,
 };
 
 use crate::{
@@ -63,26 +68,44 @@ use crate::{
     utils::flatten,
 };
 
+mod audit;
+#[cfg(feature = "benchmark")]
+mod benchmarks;
 mod block_verifier;
 mod builder;
 mod convert;
 mod fetch;
 mod latest_height_stream;
+mod read_session;
 mod reconstruct;
+mod rejection;
 mod reporting;
+mod shadow;
 mod verify;
 
 pub(crate) use builder::Builder;
+pub(crate) use convert::BlobLimits;
 use latest_height_stream::LatestHeightStream;
 use reporting::ReportReconstructedBlocks;
 
 use self::{
+    audit::{
+        AuditMismatch,
+        Auditor,
+        AuditorHandle,
+    },
     block_verifier::ensure_commit_has_quorum,
-    convert::decode_raw_blobs,
+    convert::{
+        decode_raw_blobs,
+        decoding_parallelism,
+    },
     fetch::fetch_new_blobs,
     latest_height_stream::stream_latest_heights,
+    read_session::ReadSession,
     reconstruct::reconstruct_blocks_from_verified_blobs,
+    shadow::ShadowReader,
     verify::{
+        accept_header_blobs_unverified,
         verify_header_blobs,
         BlobVerifier,
     },
@@ -121,17 +144,40 @@ pub(super) struct ReconstructedBlocks {
 }
 
 pub(crate) struct Reader {
+    /// The limits on the blobs read from Celestia.
+    blob_limits: BlobLimits,
+
     celestia_block_time: Duration,
 
     // Client to fetch heights and blocks from Celestia.
     celestia_client: CelestiaClient,
 
+    /// The number of Celestia blocks that must be built on top of a Celestia height before its
+    /// blobs are read.
+    celestia_confirmation_depth: u64,
+
     /// The channel used to send messages to the executor task.
     executor: executor::Handle,
 
+    /// Set to `true` once all blocks up to the Celestia head were forwarded to the executor.
+    firm_synced: watch::Sender<bool>,
+
+    /// If set, header blobs are not verified until all blocks up to the Celestia head were
+    /// forwarded to the executor. The blocks reconstructed in the meantime are audited in the
+    /// background.
+    fast_sync: bool,
+
     /// The client to get the sequencer namespace and verify blocks.
     sequencer_cometbft_client: SequencerClient,
 
+    /// The Celestia namespace under which sequencer blobs are read. Derived from the sequencer
+    /// chain ID if not set.
+    sequencer_namespace: Option<Namespace>,
+
+    /// If set, every blob fetch is also issued against a shadow Celestia node and the results
+    /// are compared.
+    shadow_reader: Option<ShadowReader>,
+
     /// Token to listen for Conductor being shut down.
     shutdown: CancellationToken,
 }
@@ -172,6 +218,8 @@ impl Reader {
 struct RunningReader {
     block_cache: BlockCache<ReconstructedBlock>,
 
+    blob_limits: BlobLimits,
+
     blob_verifier: Arc<BlobVerifier>,
 
     // Client to fetch heights and blocks from Celestia.
@@ -180,6 +228,20 @@ struct RunningReader {
     /// The channel used to send messages to the executor task.
     executor: executor::Handle<StateIsInit>,
 
+    /// Set to `true` once all blocks up to the Celestia head were forwarded to the executor.
+    firm_synced: watch::Sender<bool>,
+
+    /// Audits the blocks reconstructed without verification. Only set in fast sync mode.
+    auditor: Option<AuditorHandle>,
+
+    /// The blocks that failed their audit, which halt the reader. Never yields anything unless
+    /// in fast sync mode.
+    audit_mismatches: mpsc::UnboundedReceiver<AuditMismatch>,
+
+    /// Compares the blobs fetched from Celestia to those read from a shadow node. Only set if
+    /// a shadow node is configured.
+    shadow_reader: Option<ShadowReader>,
+
     /// Token to listen for Conductor being shut down.
     shutdown: CancellationToken,
 
@@ -196,10 +258,15 @@ struct RunningReader {
     /// time.
     enqueued_block: Fuse<BoxFuture<'static, Result<u64, FirmSendError>>>,
 
-    /// The latest observed head height of the Celestia network. Set by values read from
-    /// the `latest_height` stream.
+    /// The latest Celestia height with at least `celestia_confirmation_depth` blocks on top of
+    /// it, which is the highest height blobs are read from. Set by values read from the
+    /// `latest_height` stream.
     celestia_head_height: Option<u64>,
 
+    /// The number of Celestia blocks that must be built on top of a Celestia height before its
+    /// blobs are read.
+    celestia_confirmation_depth: u64,
+
     /// The next Celestia height that will be fetched.
     celestia_next_height: u64,
 
@@ -228,7 +295,7 @@ struct RunningReader {
     sequencer_chain_id: tendermint::chain::Id,
 
     /// The Celestia namespace for which Sequencer header blobs will be requested. Derived from
-    /// `sequencer_chain_id`.
+    /// `sequencer_chain_id` unless configured explicitly.
     sequencer_namespace: Namespace,
 }
 
@@ -239,9 +306,15 @@ impl RunningReader {
         sequencer_chain_id: tendermint::chain::Id,
     ) -> eyre::Result<Self> {
         let Reader {
+            blob_limits,
             celestia_block_time,
             celestia_client,
+            celestia_confirmation_depth,
+            firm_synced,
+            fast_sync,
             sequencer_cometbft_client,
+            sequencer_namespace,
+            shadow_reader,
             shutdown,
             ..
         } = exposed_reader;
@@ -252,24 +325,41 @@ impl RunningReader {
         let latest_heights = stream_latest_heights(celestia_client.clone(), celestia_block_time);
         let rollup_id = executor.rollup_id();
         let rollup_namespace = astria_core::celestia::namespace_v0_from_rollup_id(rollup_id);
-        let sequencer_namespace =
-            astria_core::celestia::namespace_v0_from_sha256_of_bytes(sequencer_chain_id.as_bytes());
+        let sequencer_namespace = astria_core::celestia::sequencer_namespace(
+            sequencer_chain_id.as_str(),
+            sequencer_namespace,
+        );
 
         let celestia_next_height = executor.celestia_base_block_height().value();
         let celestia_reference_height = executor.celestia_base_block_height().value();
         let celestia_variance = executor.celestia_block_variance().into();
 
+        let blob_verifier = Arc::new(BlobVerifier::new(sequencer_cometbft_client));
+        let (audit_mismatches_tx, audit_mismatches) = mpsc::unbounded_channel();
+        let auditor = fast_sync.then(|| {
+            let (auditor, handle) =
+                Auditor::new(blob_verifier.clone(), audit_mismatches_tx, shutdown.clone());
+            tokio::spawn(auditor.run_until_stopped());
+            handle
+        });
+
         Ok(Self {
             block_cache,
-            blob_verifier: Arc::new(BlobVerifier::new(sequencer_cometbft_client)),
+            blob_limits,
+            blob_verifier,
+            auditor,
+            audit_mismatches,
             celestia_client,
             enqueued_block: Fuse::terminated(),
             executor,
+            firm_synced,
             latest_heights,
+            shadow_reader,
             shutdown,
             reconstruction_tasks: JoinMap::new(),
 
             celestia_head_height: None,
+            celestia_confirmation_depth,
             celestia_next_height,
             celestia_reference_height,
             celestia_variance,
@@ -287,15 +377,16 @@ impl RunningReader {
             initial_celestia_height = self.celestia_next_height,
             initial_max_celestia_height = self.max_permitted_celestia_height(),
             celestia_variance = self.celestia_variance,
-            rollup_namespace = %base64(&self.rollup_namespace.as_bytes()),
+            rollup_namespace = %HexNamespace(self.rollup_namespace),
             rollup_id = %self.rollup_id,
             sequencer_chain_id = %self.sequencer_chain_id,
-            sequencer_namespace = %base64(&self.sequencer_namespace.as_bytes()),
+            sequencer_namespace = %HexNamespace(self.sequencer_namespace),
             "starting firm block read loop",
         );
 
         let reason = loop {
             self.schedule_new_blobs();
+            self.report_if_synced();
 
             select!(
                 biased;
@@ -314,6 +405,13 @@ impl RunningReader {
                     }
                 }
 
+                Some(mismatch) = self.audit_mismatches.recv() => {
+                    break Err(eyre::Report::new(mismatch)).wrap_err(
+                        "a block executed as firm block failed its audit; halting instead of \
+                        building on top of it"
+                    );
+                }
+
                 Some(block) = self.block_cache.next_block(), if !self.waiting_for_executor_capacity() => {
                     if let Err(err) = self.forward_block_to_executor(block) {
                         break Err(err);
@@ -333,7 +431,11 @@ impl RunningReader {
                 Some(res) = self.latest_heights.next() => {
                     match res {
                         Ok(height) => {
-                            info!(height, "observed latest height from Celestia");
+                            info!(
+                                height,
+                                confirmation_depth = self.celestia_confirmation_depth,
+                                "observed latest height from Celestia",
+                            );
                             self.record_latest_celestia_height(height);
                         }
                         Err(error) => {
@@ -372,7 +474,7 @@ impl RunningReader {
                     error = %eyre::Report::new(e),
                     source_celestia_height = celestia_height,
                     sequencer_height,
-                    block_hash = %base64(&block_hash),
+                    block_hash = %BlockHash::new(block_hash),
                     "failed pushing reconstructed block into sequential cache; dropping it",
                 );
             }
@@ -392,17 +494,32 @@ impl RunningReader {
     }
 
     fn schedule_new_blobs(&mut self) {
+        let Some(head_height) = self.celestia_head_height else {
+            return;
+        };
+        // All heights of a batch are read relative to the head observed when scheduling it.
+        let read_session = ReadSession::new(self.celestia_client.clone(), head_height);
         let mut scheduled = vec![];
+        // Verification is only skipped while catching up with the Celestia head.
+        let auditor = self
+            .auditor
+            .clone()
+            .filter(|_| !*self.firm_synced.borrow());
         while self.can_schedule_blobs() {
             let height = self.celestia_next_height;
             self.celestia_next_height = self.celestia_next_height.saturating_add(1);
             let task = FetchConvertVerifyAndReconstruct {
+                blob_limits: self.blob_limits,
                 blob_verifier: self.blob_verifier.clone(),
-                celestia_client: self.celestia_client.clone(),
+                auditor: auditor.clone(),
+                cancellation_token: self.shutdown.child_token(),
                 celestia_height: height,
                 rollup_id: self.rollup_id,
+                read_session: read_session.clone(),
                 rollup_namespace: self.rollup_namespace,
+                sequencer_chain_id: self.sequencer_chain_id.clone(),
                 sequencer_namespace: self.sequencer_namespace,
+                shadow_reader: self.shadow_reader.clone(),
             };
             self.reconstruction_tasks.spawn(height, task.execute());
             scheduled.push(height);
@@ -410,11 +527,35 @@ impl RunningReader {
         if !scheduled.is_empty() {
             info!(
                 heights = %json(&scheduled),
+                skip_verification = auditor.is_some(),
                 "scheduled next batch of Celestia heights",
             );
         }
     }
 
+    /// Returns if all blocks up to the latest observed Celestia head height were forwarded to
+    /// the executor.
+    fn is_synced(&self) -> bool {
+        let Some(head_height) = self.celestia_head_height else {
+            return false;
+        };
+        self.celestia_next_height > head_height
+            && self.reconstruction_tasks.is_empty()
+            && self.block_cache.is_empty()
+            && !self.waiting_for_executor_capacity()
+    }
+
+    /// Announces that the reader has caught up with the Celestia head the first time it does.
+    fn report_if_synced(&mut self) {
+        if !*self.firm_synced.borrow() && self.is_synced() {
+            info!(
+                celestia_head_height = self.celestia_head_height,
+                "forwarded all blocks up to the Celestia head to the executor",
+            );
+            self.firm_synced.send_replace(true);
+        }
+    }
+
     fn advance_reference_celestia_height(&mut self, candidate: u64) {
         let reference_height = &mut self.celestia_reference_height;
         *reference_height = max(*reference_height, candidate);
@@ -457,9 +598,12 @@ impl RunningReader {
         max_permitted_celestia_height(self.celestia_reference_height, self.celestia_variance)
     }
 
+    /// Records the latest height of the Celestia network. Blobs are only read up to
+    /// `celestia_confirmation_depth` blocks below it.
     fn record_latest_celestia_height(&mut self, height: u64) {
-        let head_height = self.celestia_head_height.get_or_insert(height);
-        *head_height = max(*head_height, height);
+        let firm_height = height.saturating_sub(self.celestia_confirmation_depth);
+        let head_height = self.celestia_head_height.get_or_insert(firm_height);
+        *head_height = max(*head_height, firm_height);
     }
 
     fn waiting_for_executor_capacity(&self) -> bool {
@@ -468,52 +612,80 @@ impl RunningReader {
 }
 
 struct FetchConvertVerifyAndReconstruct {
+    blob_limits: BlobLimits,
     blob_verifier: Arc<BlobVerifier>,
-    celestia_client: CelestiaClient,
+    /// If set, header blobs are not verified, and the reconstructed blocks are handed to the
+    /// auditor instead.
+    auditor: Option<AuditorHandle>,
+    /// Token to abandon fetching and verifying blobs, cancelled on shutdown.
+    cancellation_token: CancellationToken,
     celestia_height: u64,
+    /// The session all blobs at `celestia_height` are read in.
+    read_session: ReadSession,
     rollup_id: RollupId,
     rollup_namespace: Namespace,
+    sequencer_chain_id: tendermint::chain::Id,
     sequencer_namespace: Namespace,
+    shadow_reader: Option<ShadowReader>,
 }
 
 impl FetchConvertVerifyAndReconstruct {
     #[instrument( skip_all, fields(
         celestia_height = self.celestia_height,
-        rollup_namespace = %base64(self.rollup_namespace.as_bytes()),
-        sequencer_namespace = %base64(self.sequencer_namespace.as_bytes()),
+        rollup_namespace = %HexNamespace(self.rollup_namespace),
+        sequencer_namespace = %HexNamespace(self.sequencer_namespace),
     ))]
     async fn execute(self) -> eyre::Result<ReconstructedBlocks> {
         let Self {
+            blob_limits,
             blob_verifier,
-            celestia_client,
+            auditor,
+            cancellation_token,
             celestia_height,
+            read_session,
             rollup_id,
             rollup_namespace,
+            sequencer_chain_id,
             sequencer_namespace,
+            shadow_reader,
         } = self;
 
         let new_blobs = fetch_new_blobs(
-            celestia_client,
+            read_session,
             celestia_height,
             rollup_namespace,
             sequencer_namespace,
+            blob_limits.max_response_size,
+            cancellation_token.clone(),
         )
         .await
         .wrap_err("failed fetching blobs from Celestia")?;
 
+        if let Some(shadow_reader) = shadow_reader {
+            shadow_reader.spawn_compare(
+                &new_blobs,
+                rollup_namespace,
+                sequencer_namespace,
+                cancellation_token.clone(),
+            );
+        }
+
         info!(
             number_of_header_blobs = new_blobs.len_header_blobs(),
             number_of_rollup_blobs = new_blobs.len_rollup_blobs(),
             "received new Celestia blobs"
         );
 
-        let decode_span = info_span!("decode_blobs");
-        let decoded_blobs = spawn_blocking(move || {
-            decode_span
-                .in_scope(|| decode_raw_blobs(new_blobs, rollup_namespace, sequencer_namespace))
-        })
+        let decoded_blobs = decode_raw_blobs(
+            new_blobs,
+            rollup_namespace,
+            sequencer_namespace,
+            blob_limits,
+            decoding_parallelism(),
+        )
+        .instrument(info_span!("decode_blobs"))
         .await
-        .wrap_err("encountered panic while decoding raw Celestia blobs")?;
+        .wrap_err("failed decoding raw Celestia blobs")?;
 
         info!(
             number_of_header_blobs = decoded_blobs.len_header_blobs(),
@@ -521,13 +693,33 @@ impl FetchConvertVerifyAndReconstruct {
             "decoded Sequencer header and rollup info from raw Celestia blobs",
         );
 
-        let verified_blobs = verify_header_blobs(blob_verifier, decoded_blobs).await;
-
-        info!(
-            number_of_verified_header_blobs = verified_blobs.len_header_blobs(),
-            number_of_rollup_blobs = verified_blobs.len_rollup_blobs(),
-            "verified header blobs against Sequencer",
-        );
+        let verified_blobs = if auditor.is_some() {
+            let accepted_blobs = accept_header_blobs_unverified(
+                blob_verifier,
+                decoded_blobs,
+                &sequencer_chain_id,
+                cancellation_token,
+            )
+            .await
+            .wrap_err("failed verifying contested Sequencer header blobs")?;
+            info!(
+                number_of_unverified_header_blobs = accepted_blobs.len_header_blobs(),
+                number_of_rollup_blobs = accepted_blobs.len_rollup_blobs(),
+                "fast syncing; accepted header blobs without verifying them against Sequencer",
+            );
+            accepted_blobs
+        } else {
+            let verified_blobs =
+                verify_header_blobs(blob_verifier, decoded_blobs, cancellation_token)
+                    .await
+                    .wrap_err("failed verifying Sequencer header blobs")?;
+            info!(
+                number_of_verified_header_blobs = verified_blobs.len_header_blobs(),
+                number_of_rollup_blobs = verified_blobs.len_rollup_blobs(),
+                "verified header blobs against Sequencer",
+            );
+            verified_blobs
+        };
 
         let reconstruct_span = info_span!("reconstruct_blocks");
         let reconstructed = spawn_blocking(move || {
@@ -536,6 +728,9 @@ impl FetchConvertVerifyAndReconstruct {
         })
         .await
         .wrap_err("encountered panic while reconstructing blocks from verified blobs")?;
+        if let Some(auditor) = auditor {
+            auditor.audit(&reconstructed);
+        }
 
         let reconstructed_blocks = ReconstructedBlocks {
             celestia_height,
@@ -0,0 +1,190 @@
+//! Reads of Celestia pinned to a head height.
+//!
+//! Reconstructing the blocks at a Celestia height takes several retrievals: the Sequencer header
+//! blobs and the rollup blobs, each retried on its own. A Celestia node answers `blob: not found`
+//! for heights it has not synced yet, which cannot be told apart from a height without blobs. If
+//! the node falls behind mid-read (or a load balancer switches to a node in a different sync
+//! state), a reconstruction could pair the header blobs read from a synced node with the "empty"
+//! rollup blobs of a syncing one, and drop the rollup's transactions.
+//!
+//! A [`ReadSession`] pins the Celestia head height observed when it was created. Retrievals above
+//! the pinned height are refused, and an empty result is only accepted from a node that reports
+//! having synced up to the pinned height.
+
+use astria_celestia_client::is_blob_not_found;
+use celestia_rpc::{
+    BlobClient as _,
+    HeaderClient as _,
+};
+use celestia_types::{
+    nmt::Namespace,
+    Blob,
+    Commitment,
+};
+use jsonrpsee::http_client::HttpClient as CelestiaClient;
+
+/// An error retrieving data from Celestia within a [`ReadSession`].
+#[derive(Debug, thiserror::Error)]
+pub(super) enum RetrievalError {
+    #[error(
+        "Celestia height {height} is above the head height {pinned_height} pinned by the read \
+         session"
+    )]
+    AbovePinnedHeight { height: u64, pinned_height: u64 },
+    #[error(
+        "the Celestia node found no blobs, but has only synced up to height {local_head_height} \
+         of the pinned head height {pinned_height}"
+    )]
+    NodeBehindPinnedHeight {
+        local_head_height: u64,
+        pinned_height: u64,
+    },
+    #[error("the request to the Celestia node failed")]
+    Rpc(#[from] jsonrpsee::core::Error),
+}
+
+impl RetrievalError {
+    /// Returns if retrying the retrieval can succeed: either because the node catches up with
+    /// the pinned height, or because the underlying transport recovers.
+    pub(super) fn is_transient(&self) -> bool {
+        match self {
+            Self::AbovePinnedHeight {
+                ..
+            } => false,
+            Self::NodeBehindPinnedHeight {
+                ..
+            } => true,
+            Self::Rpc(error) => matches!(error, jsonrpsee::core::Error::Transport(_)),
+        }
+    }
+}
+
+/// Retrieves data from Celestia relative to a pinned head height.
+#[derive(Clone, Debug)]
+pub(super) struct ReadSession {
+    client: CelestiaClient,
+    pinned_height: u64,
+}
+
+impl ReadSession {
+    /// Starts a session pinned to the Celestia head height `pinned_height`.
+    pub(super) fn new(client: CelestiaClient, pinned_height: u64) -> Self {
+        Self {
+            client,
+            pinned_height,
+        }
+    }
+
+    pub(super) fn pinned_height(&self) -> u64 {
+        self.pinned_height
+    }
+
+    /// Returns all blobs under `namespace` at Celestia `height`.
+    ///
+    /// # Errors
+    /// Returns an error if `height` is above the pinned height, if the request fails, or if the
+    /// node found no blobs but has not synced up to the pinned height yet.
+    pub(super) async fn get_all_blobs(
+        &self,
+        height: u64,
+        namespace: Namespace,
+    ) -> Result<Vec<Blob>, RetrievalError> {
+        if height > self.pinned_height {
+            return Err(RetrievalError::AbovePinnedHeight {
+                height,
+                pinned_height: self.pinned_height,
+            });
+        }
+        match self.client.blob_get_all(height, &[namespace]).await {
+            Ok(blobs) => Ok(blobs),
+            Err(err) if is_blob_not_found(&err) => {
+                self.ensure_node_reached_pinned_height().await?;
+                Ok(vec![])
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Returns the blob under `namespace` at Celestia `height` with `commitment`.
+    ///
+    /// # Errors
+    /// Returns an error if `height` is above the pinned height, or if the request fails.
+    pub(super) async fn get_blob(
+        &self,
+        height: u64,
+        namespace: Namespace,
+        commitment: Commitment,
+    ) -> Result<Blob, RetrievalError> {
+        if height > self.pinned_height {
+            return Err(RetrievalError::AbovePinnedHeight {
+                height,
+                pinned_height: self.pinned_height,
+            });
+        }
+        Ok(self.client.blob_get(height, namespace, commitment).await?)
+    }
+
+    async fn ensure_node_reached_pinned_height(&self) -> Result<(), RetrievalError> {
+        let local_head_height = self.client.header_local_head().await?.height().value();
+        if local_head_height < self.pinned_height {
+            return Err(RetrievalError::NodeBehindPinnedHeight {
+                local_head_height,
+                pinned_height: self.pinned_height,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::http_client::HttpClientBuilder;
+
+    use super::*;
+
+    fn unreachable_client() -> CelestiaClient {
+        // nothing is listening on the port, so all requests fail
+        HttpClientBuilder::default()
+            .build("http://127.0.0.1:1")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn retrieval_above_pinned_height_is_refused() {
+        let session = ReadSession::new(unreachable_client(), 5);
+        let namespace = astria_core::celestia::namespace_v0_from_first_10_bytes(&[1; 10]);
+        let error = session.get_all_blobs(6, namespace).await.unwrap_err();
+        assert!(
+            matches!(
+                error,
+                RetrievalError::AbovePinnedHeight {
+                    height: 6,
+                    pinned_height: 5,
+                }
+            ),
+            "unexpected error: {error:?}"
+        );
+        assert!(!error.is_transient());
+    }
+
+    #[tokio::test]
+    async fn retrieval_at_pinned_height_reaches_the_node() {
+        let session = ReadSession::new(unreachable_client(), 5);
+        let namespace = astria_core::celestia::namespace_v0_from_first_10_bytes(&[1; 10]);
+        let error = session.get_all_blobs(5, namespace).await.unwrap_err();
+        assert!(
+            matches!(error, RetrievalError::Rpc(_)),
+            "unexpected error: {error:?}"
+        );
+        assert!(error.is_transient());
+    }
+
+    #[test]
+    fn node_behind_pinned_height_is_transient() {
+        let error = RetrievalError::NodeBehindPinnedHeight {
+            local_head_height: 4,
+            pinned_height: 5,
+        };
+        assert!(error.is_transient());
+    }
+}
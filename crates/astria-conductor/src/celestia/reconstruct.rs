@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 
 use astria_core::{
-    primitive::v1::RollupId,
+    primitive::v1::{
+        BlockHash,
+        RollupId,
+    },
     sequencerblock::v1alpha1::{
         CelestiaRollupBlob,
         CelestiaSequencerBlob,
     },
 };
-use telemetry::display::base64;
 use tracing::{
     info,
     warn,
@@ -66,7 +68,7 @@ pub(super) fn reconstruct_blocks_from_verified_blobs(
                 "no sequencer header blob matching the rollup blob's block hash found"
             };
             info!(
-                block_hash = %base64(&rollup.sequencer_block_hash()),
+                block_hash = %BlockHash::new(rollup.sequencer_block_hash()),
                 reason,
                 "dropping rollup blob",
             );
@@ -77,7 +79,7 @@ pub(super) fn reconstruct_blocks_from_verified_blobs(
     for header_blob in header_blobs.into_values() {
         if header_blob.contains_rollup_id(rollup_id) {
             warn!(
-                block_hash = %base64(&header_blob.block_hash()),
+                block_hash = %BlockHash::new(header_blob.block_hash()),
                 "sequencer header blob contains the target rollup ID, but no matching rollup blob was found; dropping it",
             );
         } else {
@@ -96,6 +98,10 @@ fn remove_header_blob_matching_rollup_blob(
     headers: &mut HashMap<[u8; 32], CelestiaSequencerBlob>,
     rollup: &CelestiaRollupBlob,
 ) -> Option<CelestiaSequencerBlob> {
+    // The rollup blob's block hash is guaranteed to be 32 bytes by its type. Because `headers`
+    // only contains header blobs whose block hash was verified against the hash of the
+    // Sequencer header, a lookup by the rollup blob's block hash ties it to that header.
+    //
     // chaining methods and returning () to use the ? operator and to not bind the value
     headers
         .get(&rollup.sequencer_block_hash())
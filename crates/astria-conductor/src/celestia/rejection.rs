@@ -0,0 +1,48 @@
+//! Counting blobs read from Celestia that were rejected.
+//!
+//! The namespaces conductor reads from can be written to by anyone. Labeling rejected blobs by
+//! the reason of their rejection lets operators tell benign third-party noise (which mostly
+//! fails to deserialize) apart from an attack or a bug (blobs that deserialize, but do not
+//! match what the sequencer committed to).
+
+/// Why a blob read from Celestia was rejected.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum RejectionReason {
+    /// The blob could not be decompressed, decoded, or validated as a header or rollup blob.
+    DeserializeFailed,
+    /// The blob was larger than permitted, or contained more items than permitted.
+    Oversize,
+    /// The blob was in excess of the blobs permitted per namespace and Celestia height.
+    ExcessPerHeight,
+    /// The chain ID in the header blob did not match the one of the sequencer.
+    WrongChainId,
+    /// The block hash in the header blob did not match the block committed to by the sequencer.
+    WrongBlockHash,
+    /// The sequencer commit for the header blob was not signed by a quorum of validators.
+    BadSignature,
+    /// The blob referenced an earlier rollup blob that could not be fetched.
+    UnresolvedReference,
+}
+
+impl RejectionReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::DeserializeFailed => "deserialize_failed",
+            Self::Oversize => "oversize",
+            Self::ExcessPerHeight => "excess_per_height",
+            Self::WrongChainId => "wrong_chain_id",
+            Self::WrongBlockHash => "wrong_block_hash",
+            Self::BadSignature => "bad_signature",
+            Self::UnresolvedReference => "unresolved_reference",
+        }
+    }
+
+    /// Counts `number_of_blobs` blobs as rejected for this reason.
+    pub(super) fn count(self, number_of_blobs: u64) {
+        metrics::counter!(
+            crate::metrics_init::REJECTED_CELESTIA_BLOBS,
+            telemetry::metrics::REASON_LABEL => self.as_str(),
+        )
+        .increment(number_of_blobs);
+    }
+}
@@ -0,0 +1,251 @@
+//! Shadow reads of Celestia blobs from a second Celestia node.
+//!
+//! If a shadow node is configured, every fetch of blobs from the primary Celestia node is issued
+//! against the shadow node as well, and the blobs returned by the two are compared. The shadow
+//! read runs in the background and never affects which blocks are executed: divergences are
+//! logged and counted, so that operators can detect a node that is malfunctioning or withholding
+//! blobs.
+//!
+//! Blobs are compared by their share commitment and the SHA256 digest of their data. A blob
+//! returned by only the primary node is counted as `missing`, one returned by only the shadow
+//! node as `extra`, and one returned by both with the same commitment but different data as
+//! `altered`.
+
+use std::{
+    collections::HashMap,
+    time::Duration,
+};
+
+use celestia_types::{
+    nmt::Namespace,
+    Blob,
+};
+use jsonrpsee::http_client::HttpClient as CelestiaClient;
+use sha2::{
+    Digest as _,
+    Sha256,
+};
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+use tracing::{
+    debug,
+    info_span,
+    warn,
+    Instrument as _,
+};
+
+use super::{
+    fetch::{
+        fetch_new_blobs,
+        RawBlobs,
+    },
+    read_session::ReadSession,
+};
+
+/// How long a shadow read may take before it is abandoned. Fetches retry transport errors
+/// indefinitely, so an unreachable shadow node would otherwise keep its reads alive forever.
+const SHADOW_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Reads blobs from the shadow node and compares them to those read from the primary node.
+#[derive(Clone, Debug)]
+pub(super) struct ShadowReader {
+    client: CelestiaClient,
+    max_response_size: u32,
+}
+
+impl ShadowReader {
+    pub(super) fn new(client: CelestiaClient, max_response_size: u32) -> Self {
+        Self {
+            client,
+            max_response_size,
+        }
+    }
+
+    /// Fetches the blobs at the Celestia height of `primary` from the shadow node in the
+    /// background and reports how they diverge from `primary`.
+    ///
+    /// Failing to read from the shadow node is logged and counted, but is otherwise ignored.
+    pub(super) fn spawn_compare(
+        &self,
+        primary: &RawBlobs,
+        rollup_namespace: Namespace,
+        sequencer_namespace: Namespace,
+        cancellation_token: CancellationToken,
+    ) {
+        let celestia_height = primary.celestia_height;
+        let expected_header_blobs = digest_blobs(&primary.header_blobs);
+        let expected_rollup_blobs = digest_blobs(&primary.rollup_blobs);
+        let client = self.client.clone();
+        let max_response_size = self.max_response_size;
+        let shadow_read = async move {
+            // The primary node has synced up to the height, so the shadow node must have as well.
+            let fetch = fetch_new_blobs(
+                ReadSession::new(client, celestia_height),
+                celestia_height,
+                rollup_namespace,
+                sequencer_namespace,
+                max_response_size,
+                cancellation_token.clone(),
+            );
+            let shadow = match timeout(SHADOW_READ_TIMEOUT, fetch).await {
+                Ok(Ok(shadow)) => shadow,
+                Ok(Err(_)) if cancellation_token.is_cancelled() => return,
+                Ok(Err(error)) => {
+                    count_shadow_read("failed");
+                    warn!(%error, "failed reading blobs from the shadow Celestia node");
+                    return;
+                }
+                Err(_) => {
+                    count_shadow_read("failed");
+                    warn!(
+                        timeout_secs = SHADOW_READ_TIMEOUT.as_secs(),
+                        "reading blobs from the shadow Celestia node timed out",
+                    );
+                    return;
+                }
+            };
+            let header_divergence =
+                Divergence::between(&expected_header_blobs, &digest_blobs(&shadow.header_blobs));
+            let rollup_divergence =
+                Divergence::between(&expected_rollup_blobs, &digest_blobs(&shadow.rollup_blobs));
+            if header_divergence.is_empty() && rollup_divergence.is_empty() {
+                count_shadow_read("matched");
+                debug!("blobs read from the shadow Celestia node match those of the primary");
+                return;
+            }
+            count_shadow_read("diverged");
+            header_divergence.count("header");
+            rollup_divergence.count("rollup");
+            warn!(
+                header_blobs.missing = header_divergence.missing,
+                header_blobs.extra = header_divergence.extra,
+                header_blobs.altered = header_divergence.altered,
+                rollup_blobs.missing = rollup_divergence.missing,
+                rollup_blobs.extra = rollup_divergence.extra,
+                rollup_blobs.altered = rollup_divergence.altered,
+                "blobs read from the shadow Celestia node diverge from those of the primary; one \
+                 of the nodes may be malfunctioning or withholding blobs",
+            );
+        };
+        tokio::spawn(shadow_read.instrument(info_span!("shadow_read", celestia_height)));
+    }
+}
+
+fn count_shadow_read(result: &'static str) {
+    metrics::counter!(
+        crate::metrics_init::SHADOW_READS,
+        telemetry::metrics::RESULT_LABEL => result,
+    )
+    .increment(1);
+}
+
+/// The share commitment of a blob and the SHA256 digest of its data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BlobDigest {
+    commitment: [u8; 32],
+    data: [u8; 32],
+}
+
+fn digest_blobs(blobs: &[Blob]) -> Vec<BlobDigest> {
+    blobs
+        .iter()
+        .map(|blob| BlobDigest {
+            commitment: blob.commitment.0,
+            data: Sha256::digest(&blob.data).into(),
+        })
+        .collect()
+}
+
+/// How the blobs read from the shadow node diverge from those read from the primary node.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Divergence {
+    /// Blobs returned by the primary node only.
+    missing: usize,
+    /// Blobs returned by the shadow node only.
+    extra: usize,
+    /// Blobs returned by both nodes with the same commitment, but different data.
+    altered: usize,
+}
+
+impl Divergence {
+    fn between(primary: &[BlobDigest], shadow: &[BlobDigest]) -> Self {
+        let mut unmatched: HashMap<[u8; 32], Vec<[u8; 32]>> = HashMap::new();
+        for blob in shadow {
+            unmatched.entry(blob.commitment).or_default().push(blob.data);
+        }
+        let mut divergence = Self::default();
+        for blob in primary {
+            let Some(candidates) = unmatched.get_mut(&blob.commitment) else {
+                divergence.missing += 1;
+                continue;
+            };
+            if let Some(index) = candidates.iter().position(|data| *data == blob.data) {
+                candidates.swap_remove(index);
+            } else {
+                candidates.pop();
+                divergence.altered += 1;
+            }
+            if candidates.is_empty() {
+                unmatched.remove(&blob.commitment);
+            }
+        }
+        divergence.extra = unmatched.values().map(Vec::len).sum();
+        divergence
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Counts the divergent blobs of the namespace of `kind`.
+    fn count(&self, kind: &'static str) {
+        for (divergence, number_of_blobs) in [
+            ("missing", self.missing),
+            ("extra", self.extra),
+            ("altered", self.altered),
+        ] {
+            if number_of_blobs > 0 {
+                metrics::counter!(
+                    crate::metrics_init::SHADOW_READ_DIVERGENT_BLOBS,
+                    "namespace" => kind,
+                    "divergence" => divergence,
+                )
+                .increment(number_of_blobs as u64);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(data: &[u8]) -> Blob {
+        let namespace = astria_core::celestia::namespace_v0_from_first_10_bytes(&[1; 10]);
+        Blob::new(namespace, data.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn identical_blobs_in_any_order_do_not_diverge() {
+        let primary = digest_blobs(&[blob(b"a"), blob(b"b"), blob(b"b")]);
+        let shadow = digest_blobs(&[blob(b"b"), blob(b"a"), blob(b"b")]);
+        assert!(Divergence::between(&primary, &shadow).is_empty());
+    }
+
+    #[test]
+    fn missing_extra_and_altered_blobs_are_told_apart() {
+        let primary = digest_blobs(&[blob(b"a"), blob(b"b"), blob(b"c")]);
+        let mut altered = digest_blobs(&[blob(b"c")]).remove(0);
+        altered.data = [0; 32];
+        let mut shadow = digest_blobs(&[blob(b"a"), blob(b"d"), blob(b"e")]);
+        shadow.push(altered);
+        assert_eq!(
+            Divergence {
+                missing: 1,
+                extra: 2,
+                altered: 1,
+            },
+            Divergence::between(&primary, &shadow),
+        );
+    }
+}
@@ -4,29 +4,38 @@ use std::{
     time::Duration,
 };
 
-use astria_core::sequencerblock::v1alpha1::{
-    CelestiaRollupBlob,
-    CelestiaSequencerBlob,
+use astria_core::{
+    primitive::v1::BlockHash,
+    sequencerblock::v1alpha1::{
+        CelestiaRollupBlob,
+        CelestiaSequencerBlob,
+    },
 };
 use astria_eyre::{
     eyre,
     eyre::{
-        ensure,
+        bail,
         WrapErr as _,
     },
 };
 use moka::future::Cache;
 use sequencer_client::{
-    tendermint::block::{
-        signed_header::SignedHeader,
-        Height as SequencerHeight,
+    tendermint::{
+        self,
+        block::{
+            signed_header::SignedHeader,
+            Height as SequencerHeight,
+        },
     },
     tendermint_rpc,
     Client as _,
     HttpClient as SequencerClient,
 };
-use telemetry::display::base64;
-use tokio_util::task::JoinMap;
+use tokio::select;
+use tokio_util::{
+    sync::CancellationToken,
+    task::JoinMap,
+};
 use tracing::{
     info,
     instrument,
@@ -43,6 +52,7 @@ use tryhard::{
 use super::{
     block_verifier,
     convert::ConvertedBlobs,
+    rejection::RejectionReason,
 };
 use crate::utils::flatten;
 
@@ -85,13 +95,32 @@ struct VerificationTaskKey {
 /// Verifies Sequencer header blobs against Sequencer commits and validator sets.
 ///
 /// Drops blobs that could not be verified.
+///
+/// # Errors
+/// Returns an error if `cancellation_token` is cancelled before all blobs were verified. The
+/// outstanding verifications are aborted.
 #[instrument(skip_all)]
 pub(super) async fn verify_header_blobs(
     blob_verifier: Arc<BlobVerifier>,
     converted_blobs: ConvertedBlobs,
-) -> VerifiedBlobs {
+    cancellation_token: CancellationToken,
+) -> eyre::Result<VerifiedBlobs> {
     let (celestia_height, header_blobs, rollup_blobs) = converted_blobs.into_parts();
+    let header_blobs = verify_blobs(blob_verifier, header_blobs, cancellation_token).await?;
+    Ok(VerifiedBlobs {
+        celestia_height,
+        header_blobs,
+        rollup_blobs,
+    })
+}
 
+/// Verifies `header_blobs` against Sequencer, returning those that could be verified keyed by
+/// their block hash.
+async fn verify_blobs(
+    blob_verifier: Arc<BlobVerifier>,
+    header_blobs: Vec<CelestiaSequencerBlob>,
+    cancellation_token: CancellationToken,
+) -> eyre::Result<HashMap<[u8; 32], CelestiaSequencerBlob>> {
     let mut verification_tasks = JoinMap::new();
     let mut verified_header_blobs = HashMap::with_capacity(header_blobs.len());
 
@@ -109,7 +138,17 @@ pub(super) async fn verify_header_blobs(
         );
     }
 
-    while let Some((key, verification_result)) = verification_tasks.join_next().await {
+    loop {
+        // Dropping `verification_tasks` on return aborts the verifications still running.
+        let (key, verification_result) = select!(
+            biased;
+
+            () = cancellation_token.cancelled() => bail!("verification of blobs was cancelled"),
+
+            Some(next) = verification_tasks.join_next() => next,
+
+            else => break,
+        );
         match flatten(verification_result) {
             Ok(verified_blob) => {
                 if let Some(dropped_entry) =
@@ -119,7 +158,7 @@ pub(super) async fn verify_header_blobs(
                         .get(&dropped_entry.block_hash())
                         .expect("must exist; just inserted an item under the same key");
                     info!(
-                        block_hash = %base64(&dropped_entry.block_hash()),
+                        block_hash = %BlockHash::new(dropped_entry.block_hash()),
                         dropped_blob.sequencer_height = dropped_entry.height().value(),
                         accepted_blob.sequencer_height = accepted_entry.height().value(),
                         "two Sequencer header blobs were well formed and validated against \
@@ -130,7 +169,7 @@ pub(super) async fn verify_header_blobs(
             }
             Err(error) => {
                 info!(
-                    block_hash = %base64(&key.block_hash),
+                    block_hash = %BlockHash::new(key.block_hash),
                     sequencer_height = %key.sequencer_height,
                     %error,
                     "verification of sequencer blob failed; dropping it"
@@ -139,11 +178,89 @@ pub(super) async fn verify_header_blobs(
         }
     }
 
-    VerifiedBlobs {
+    Ok(verified_header_blobs)
+}
+
+/// Accepts Sequencer header blobs without verifying them against Sequencer, for fast syncing.
+///
+/// Only blobs of `sequencer_chain_id` are accepted, and of several blobs sharing a block hash
+/// only one is kept. The blocks reconstructed from the accepted blobs must be audited later
+/// with [`BlobVerifier::verify_block_hash`].
+///
+/// Anybody can post blobs to the Sequencer namespace, so if a Sequencer height has more than one
+/// candidate header blob, there is no telling which of them is genuine without verifying them.
+/// The candidates of such heights are verified in full, as [`verify_header_blobs`] does.
+///
+/// # Errors
+/// Returns an error if `cancellation_token` is cancelled before all contested blobs were
+/// verified.
+#[instrument(skip_all)]
+pub(super) async fn accept_header_blobs_unverified(
+    blob_verifier: Arc<BlobVerifier>,
+    converted_blobs: ConvertedBlobs,
+    sequencer_chain_id: &tendermint::chain::Id,
+    cancellation_token: CancellationToken,
+) -> eyre::Result<VerifiedBlobs> {
+    let (celestia_height, header_blobs, rollup_blobs) = converted_blobs.into_parts();
+    let (mut accepted_header_blobs, contested_header_blobs) =
+        partition_contested_header_blobs(header_blobs, sequencer_chain_id);
+    if !contested_header_blobs.is_empty() {
+        info!(
+            number_of_contested_header_blobs = contested_header_blobs.len(),
+            "found several candidate header blobs for the same sequencer height; verifying them \
+             against Sequencer",
+        );
+        let verified_header_blobs =
+            verify_blobs(blob_verifier, contested_header_blobs, cancellation_token).await?;
+        accepted_header_blobs.extend(verified_header_blobs);
+    }
+    Ok(VerifiedBlobs {
         celestia_height,
-        header_blobs: verified_header_blobs,
+        header_blobs: accepted_header_blobs,
         rollup_blobs,
+    })
+}
+
+/// Splits `header_blobs` of `sequencer_chain_id` into those that are the only candidate for
+/// their Sequencer height, keyed by block hash, and those of heights with several candidates.
+///
+/// Blobs of other chains are dropped, as are all but one of several blobs sharing a block hash.
+fn partition_contested_header_blobs(
+    header_blobs: Vec<CelestiaSequencerBlob>,
+    sequencer_chain_id: &tendermint::chain::Id,
+) -> (
+    HashMap<[u8; 32], CelestiaSequencerBlob>,
+    Vec<CelestiaSequencerBlob>,
+) {
+    let mut candidates: HashMap<SequencerHeight, HashMap<[u8; 32], CelestiaSequencerBlob>> =
+        HashMap::new();
+    for blob in header_blobs {
+        if blob.cometbft_chain_id() != sequencer_chain_id {
+            RejectionReason::WrongChainId.count(1);
+            info!(
+                block_hash = %BlockHash::new(blob.block_hash()),
+                sequencer_height = blob.height().value(),
+                chain_id = %blob.cometbft_chain_id(),
+                "sequencer blob is not of the expected chain; dropping it",
+            );
+            continue;
+        }
+        candidates
+            .entry(blob.height())
+            .or_default()
+            .entry(blob.block_hash())
+            .or_insert(blob);
     }
+    let mut accepted = HashMap::with_capacity(candidates.len());
+    let mut contested = Vec::new();
+    for blobs_at_height in candidates.into_values() {
+        if blobs_at_height.len() == 1 {
+            accepted.extend(blobs_at_height);
+        } else {
+            contested.extend(blobs_at_height.into_values());
+        }
+    }
+    (accepted, contested)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -236,30 +353,87 @@ impl BlobVerifier {
         self: Arc<Self>,
         blob: CelestiaSequencerBlob,
     ) -> eyre::Result<CelestiaSequencerBlob> {
-        use base64::prelude::*;
-        let height = blob.height();
-        let meta = self
+        let meta = self.fetch_meta(blob.height()).await?;
+        if &meta.commit_header.header.chain_id != blob.cometbft_chain_id() {
+            RejectionReason::WrongChainId.count(1);
+            bail!(
+                "expected cometbft chain ID `{}`, got `{}`",
+                meta.commit_header.header.chain_id,
+                blob.cometbft_chain_id(),
+            );
+        }
+        if let Err(error) = ensure_block_hash_matches(&meta.commit_header, blob.block_hash()) {
+            RejectionReason::WrongBlockHash.count(1);
+            return Err(error);
+        }
+        Ok(blob)
+    }
+
+    /// Verifies that `block_hash` is the hash of the sequencer block at `height`, as committed
+    /// to by a quorum of sequencer validators.
+    ///
+    /// Used to audit blocks that were reconstructed without verifying their header blobs.
+    pub(super) async fn verify_block_hash(
+        &self,
+        height: SequencerHeight,
+        block_hash: [u8; 32],
+    ) -> eyre::Result<()> {
+        let meta = self.fetch_meta(height).await?;
+        ensure_block_hash_matches(&meta.commit_header, block_hash)
+    }
+
+    async fn fetch_meta(&self, height: SequencerHeight) -> eyre::Result<VerificationMeta> {
+        match self
             .cache
             .try_get_with(
                 height,
                 VerificationMeta::fetch(self.sequencer_cometbft_client.clone(), height),
             )
             .await
-            .wrap_err("failed getting data necessary to verify the sequencer header blob")?;
-        ensure!(
-            &meta.commit_header.header.chain_id == blob.cometbft_chain_id(),
-            "expected cometbft chain ID `{}`, got `{}`",
-            meta.commit_header.header.chain_id,
-            blob.cometbft_chain_id(),
-        );
-        ensure!(
-            meta.commit_header.commit.block_id.hash.as_bytes() == blob.block_hash(),
+        {
+            Ok(meta) => Ok(meta),
+            Err(error) => {
+                if let VerificationMetaError::NoQuorum {
+                    ..
+                } = *error
+                {
+                    RejectionReason::BadSignature.count(1);
+                }
+                Err(error)
+                    .wrap_err("failed getting data necessary to verify the sequencer header blob")
+            }
+        }
+    }
+}
+
+/// Ensures that `block_hash` is both the block ID in the commit of `signed_header`, and the hash
+/// of its header.
+fn ensure_block_hash_matches(
+    signed_header: &SignedHeader,
+    block_hash: [u8; 32],
+) -> eyre::Result<()> {
+    use base64::prelude::*;
+    if signed_header.commit.block_id.hash.as_bytes() != block_hash {
+        bail!(
             "block hash `{}` stored in blob does not match block hash `{}` of sequencer block",
-            BASE64_STANDARD.encode(blob.block_hash()),
-            BASE64_STANDARD.encode(meta.commit_header.commit.block_id.hash.as_bytes()),
+            BASE64_STANDARD.encode(block_hash),
+            BASE64_STANDARD.encode(signed_header.commit.block_id.hash.as_bytes()),
+        );
+    }
+    // The commit only attests to the block ID. Hashing the accompanying header ensures that
+    // the block hash is not just claimed by the commit but actually derived from the header.
+    // Rollup blobs are only ever matched against the block hashes of header blobs verified
+    // here, so this also covers the block hashes stored in rollup blobs.
+    let header_hash = signed_header.header.hash();
+    if header_hash.as_bytes() != block_hash {
+        bail!(
+            "block hash `{}` stored in blob does not match the hash `{}` of the sequencer block \
+             header",
+            BASE64_STANDARD.encode(block_hash),
+            BASE64_STANDARD.encode(header_hash.as_bytes()),
         );
-        Ok(blob)
     }
+    Ok(())
 }
 
 async fn fetch_commit_with_retry(
@@ -371,3 +545,53 @@ fn should_retry(error: &tendermint_rpc::Error) -> bool {
         Http(..) | HttpRequestFailed(..) | Timeout(..)
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use astria_core::protocol::test_utils::ConfigureSequencerBlock;
+
+    use super::*;
+
+    const CHAIN_ID: &str = "test-chain";
+
+    fn header_blob(chain_id: &str, height: u32, block_hash: u8) -> CelestiaSequencerBlob {
+        ConfigureSequencerBlock {
+            block_hash: Some([block_hash; 32]),
+            chain_id: Some(chain_id.to_string()),
+            height,
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs()
+        .0
+    }
+
+    #[test]
+    fn heights_with_several_candidate_header_blobs_are_contested() {
+        let (accepted, contested) = partition_contested_header_blobs(
+            vec![
+                header_blob(CHAIN_ID, 1, 1),
+                // A duplicate of the only candidate does not contest its height.
+                header_blob(CHAIN_ID, 1, 1),
+                header_blob(CHAIN_ID, 2, 2),
+                header_blob(CHAIN_ID, 2, 3),
+                // Blobs of other chains are no candidates.
+                header_blob("other-chain", 3, 4),
+                header_blob(CHAIN_ID, 3, 5),
+            ],
+            &CHAIN_ID.parse().unwrap(),
+        );
+
+        let mut accepted_heights: Vec<_> = accepted
+            .values()
+            .map(|blob| blob.height().value())
+            .collect();
+        accepted_heights.sort_unstable();
+        assert_eq!(vec![1, 3], accepted_heights);
+        assert!(accepted.contains_key(&[5; 32]));
+
+        let mut contested_hashes: Vec<_> = contested.iter().map(|blob| blob.block_hash()).collect();
+        contested_hashes.sort_unstable();
+        assert_eq!(vec![[2; 32], [3; 32]], contested_hashes);
+    }
+}
@@ -13,6 +13,7 @@ use tokio::{
         signal,
         SignalKind,
     },
+    sync::watch,
     time::timeout,
 };
 use tokio_util::{
@@ -65,6 +66,7 @@ impl Conductor {
         let executor_handle = {
             let (executor, handle) = executor::Builder {
                 mode: cfg.execution_commit_level,
+                commitment_mismatch_policy: cfg.execution_commitment_mismatch_policy,
                 rollup_address: cfg.execution_rpc_url,
                 shutdown: shutdown.clone(),
             }
@@ -75,6 +77,9 @@ impl Conductor {
             handle
         };
 
+        // Set by the Celestia reader once it caught up with the Celestia head.
+        let (firm_synced_tx, firm_synced_rx) = watch::channel(false);
+
         if cfg.execution_commit_level.is_with_soft() {
             let sequencer_grpc_client =
                 sequencer::SequencerGrpcClient::new(&cfg.sequencer_grpc_url)
@@ -89,18 +94,36 @@ impl Conductor {
                 sequencer_block_time: Duration::from_millis(cfg.sequencer_block_time_ms),
                 shutdown: shutdown.clone(),
                 executor: executor_handle.clone(),
+                wait_for_firm_sync: (cfg.execution_commit_level.is_soft_and_firm()
+                    && cfg.execution_firm_sync_before_soft)
+                    .then_some(firm_synced_rx),
             }
             .build();
             tasks.spawn(Self::SEQUENCER, sequencer_reader.run_until_stopped());
         }
 
         if cfg.execution_commit_level.is_with_firm() {
+            let sequencer_namespace = cfg
+                .celestia_sequencer_namespace()
+                .wrap_err("failed parsing the configured Celestia sequencer namespace")?;
             let reader = celestia::Builder {
+                blob_limits: celestia::BlobLimits {
+                    max_blob_size: cfg.celestia_max_blob_size_bytes,
+                    max_blobs_per_height: cfg.celestia_max_blobs_per_height,
+                    max_rollup_txs_per_blob: cfg.celestia_max_rollup_txs_per_blob,
+                    max_response_size: cfg.celestia_max_response_size_bytes,
+                },
                 celestia_http_endpoint: cfg.celestia_node_http_url,
                 celestia_token: cfg.celestia_bearer_token,
+                celestia_shadow_http_endpoint: cfg.celestia_shadow_node_http_url,
+                celestia_shadow_token: cfg.celestia_shadow_bearer_token,
                 celestia_block_time: Duration::from_millis(cfg.celestia_block_time_ms),
+                celestia_confirmation_depth: cfg.celestia_confirmation_depth,
                 executor: executor_handle.clone(),
+                firm_synced: firm_synced_tx,
+                fast_sync: cfg.celestia_fast_sync,
                 sequencer_cometbft_client: sequencer_cometbft_client.clone(),
+                sequencer_namespace,
                 shutdown: shutdown.clone(),
             }
             .build()
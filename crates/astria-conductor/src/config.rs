@@ -1,5 +1,6 @@
 //! The conductor configuration.
 
+use astria_core::celestia::Namespace;
 use serde::{
     Deserialize,
     Serialize,
@@ -37,6 +38,27 @@ impl std::fmt::Display for CommitLevel {
     }
 }
 
+/// What conductor does if a block executed as a soft commitment differs from the firm block at
+/// the same sequencer height.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum CommitmentMismatchPolicy {
+    /// Stop conductor with an error.
+    Halt,
+    /// Re-execute the firm block on top of the firm commitment, and set the soft commitment to
+    /// it.
+    Rollback,
+}
+
+impl std::fmt::Display for CommitmentMismatchPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            CommitmentMismatchPolicy::Halt => "halt",
+            CommitmentMismatchPolicy::Rollback => "rollback",
+        };
+        f.write_str(s)
+    }
+}
+
 // Allowed `struct_excessive_bools` because this is used as a container
 // for deserialization. Making this a builder-pattern is not actionable.
 #[allow(clippy::struct_excessive_bools)]
@@ -51,6 +73,44 @@ pub struct Config {
     /// The JWT bearer token supplied with each jsonrpc call
     pub celestia_bearer_token: String,
 
+    /// URL of the HTTP RPC of a second Celestia node that every blob fetch is also issued
+    /// against. Divergences between the blobs returned by the two nodes are logged and
+    /// counted. Shadow reads are disabled if empty.
+    pub celestia_shadow_node_http_url: String,
+
+    /// The JWT bearer token supplied with each jsonrpc call to the shadow Celestia node.
+    pub celestia_shadow_bearer_token: String,
+
+    /// The hex encoded ID of the Celestia namespace under which sequencer blocks are read.
+    /// Derived from the sequencer chain ID if empty.
+    pub celestia_sequencer_namespace: String,
+
+    /// The maximum size in bytes of a blob read from Celestia, both before and after
+    /// decompression. Larger blobs are dropped.
+    pub celestia_max_blob_size_bytes: usize,
+
+    /// The maximum number of blobs per namespace that are read at a single Celestia height.
+    /// Excess blobs are dropped.
+    pub celestia_max_blobs_per_height: usize,
+
+    /// The maximum number of rollup transactions in a single blob read from Celestia. Blobs
+    /// with more transactions are dropped.
+    pub celestia_max_rollup_txs_per_blob: usize,
+
+    /// The maximum size in bytes of the Celestia node's response to a blob fetch. Reading a
+    /// larger response is aborted and the fetch fails without being retried.
+    pub celestia_max_response_size_bytes: u32,
+
+    /// Skips verifying Sequencer header blobs against Sequencer until all blocks up to the
+    /// Celestia head were executed, and audits the blocks reconstructed in the meantime in the
+    /// background. Speeds up long backfills. A block failing its audit halts conductor.
+    pub celestia_fast_sync: bool,
+
+    /// The number of Celestia blocks that must be built on top of a Celestia block before the
+    /// blobs in it are read and the sequencer blocks reconstructed from them are treated as
+    /// firm. Set to 0 to read blobs up to the Celestia head.
+    pub celestia_confirmation_depth: u64,
+
     /// URL of the Sequencer Cometbft gRPC service.
     pub sequencer_grpc_url: String,
 
@@ -69,6 +129,14 @@ pub struct Config {
     /// the execution layer.
     pub execution_commit_level: CommitLevel,
 
+    /// In soft-and-firm mode, only executes firm blocks read from Celestia until all blocks up
+    /// to the Celestia head are executed, and only then starts following Sequencer.
+    pub execution_firm_sync_before_soft: bool,
+
+    /// What to do if a block executed as a soft commitment differs from the firm block at the
+    /// same sequencer height.
+    pub execution_commitment_mismatch_policy: CommitmentMismatchPolicy,
+
     /// Forces writing trace data to stdout no matter if connected to a tty or not.
     pub force_stdout: bool,
 
@@ -85,6 +153,23 @@ pub struct Config {
     pub pretty_print: bool,
 }
 
+impl Config {
+    /// Returns the configured Celestia namespace of sequencer blocks, or `None` if it should be
+    /// derived from the sequencer chain ID.
+    ///
+    /// # Errors
+    /// Returns an error if the configured namespace is not a hex encoded 10 byte namespace ID, or
+    /// is in the range of IDs reserved by Celestia.
+    pub fn celestia_sequencer_namespace(
+        &self,
+    ) -> Result<Option<Namespace>, astria_core::celestia::ParseNamespaceError> {
+        if self.celestia_sequencer_namespace.is_empty() {
+            return Ok(None);
+        }
+        astria_core::celestia::namespace_v0_from_hex(&self.celestia_sequencer_namespace).map(Some)
+    }
+}
+
 impl config::Config for Config {
     const PREFIX: &'static str = "ASTRIA_CONDUCTOR_";
 }
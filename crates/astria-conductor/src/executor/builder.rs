@@ -13,10 +13,14 @@ use super::{
     Handle,
     StateNotInit,
 };
-use crate::config::CommitLevel;
+use crate::config::{
+    CommitLevel,
+    CommitmentMismatchPolicy,
+};
 
 pub(crate) struct Builder {
     pub(crate) mode: CommitLevel,
+    pub(crate) commitment_mismatch_policy: CommitmentMismatchPolicy,
     pub(crate) rollup_address: String,
     pub(crate) shutdown: CancellationToken,
 }
@@ -25,6 +29,7 @@ impl Builder {
     pub(crate) fn build(self) -> eyre::Result<(Executor, Handle)> {
         let Self {
             mode,
+            commitment_mismatch_policy,
             rollup_address,
             shutdown,
         } = self;
@@ -53,6 +58,7 @@ impl Builder {
 
         let executor = Executor {
             mode,
+            commitment_mismatch_policy,
 
             firm_blocks: firm_block_rx,
             soft_blocks: soft_block_rx,
@@ -62,6 +68,7 @@ impl Builder {
             shutdown,
             state: state_tx,
             blocks_pending_finalization: HashMap::new(),
+            discard_soft_blocks_below: None,
         };
         let handle = Handle {
             firm_blocks: firm_block_tx,
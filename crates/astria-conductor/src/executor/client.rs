@@ -2,6 +2,7 @@ use std::ops::RangeInclusive;
 
 use ::astria_core::generated::execution::v1alpha2::Block as RawBlock;
 use astria_core::{
+    connect::ChannelBuilder,
     execution::v1alpha2::{
         Block,
         CommitmentState,
@@ -37,9 +38,12 @@ pub(crate) struct Client {
 impl Client {
     #[instrument(skip_all, fields(rollup_uri = %uri))]
     pub(crate) async fn connect(uri: tonic::transport::Uri) -> eyre::Result<Self> {
-        let inner = ExecutionServiceClient::connect(uri.clone())
+        let channel = ChannelBuilder::new()
+            .endpoint(uri.to_string())
+            .connect()
             .await
             .wrap_err("failed constructing execution service client")?;
+        let inner = ExecutionServiceClient::new(channel);
         Ok(Self {
             uri,
             inner,
@@ -61,9 +65,10 @@ impl Client {
         &mut self,
         block_numbers: RangeInclusive<u32>,
     ) -> eyre::Result<Vec<Block>> {
-        let request = raw::BatchGetBlocksRequest {
-            identifiers: block_numbers.clone().map(block_identifier).collect(),
-        };
+        let request = raw::BatchGetBlocksRequest::builder()
+            .block_numbers(block_numbers.clone())
+            .build()
+            .wrap_err("failed constructing batch get blocks request")?;
         let raw_blocks = self
             .inner
             .batch_get_blocks(request)
@@ -85,9 +90,10 @@ impl Client {
 
     #[instrument(skip_all, fields(uri = %self.uri), err)]
     pub(crate) async fn get_block(&mut self, block_number: u32) -> eyre::Result<Block> {
-        let request = raw::GetBlockRequest {
-            identifier: Some(block_identifier(block_number)),
-        };
+        let request = raw::GetBlockRequest::builder()
+            .block_number(block_number)
+            .build()
+            .wrap_err("failed constructing get block request")?;
         let raw_block = self
             .inner
             .get_block(request)
@@ -136,14 +142,15 @@ impl Client {
         let transactions = transactions
             .into_iter()
             .map(|tx| RollupData::decode(tx.as_slice()))
-            .collect::<Result<_, _>>()
+            .collect::<Result<Vec<_>, _>>()
             .wrap_err("failed to decode tx bytes as RollupData")?;
 
-        let request = raw::ExecuteBlockRequest {
-            prev_block_hash,
-            transactions,
-            timestamp: Some(timestamp),
-        };
+        let request = raw::ExecuteBlockRequest::builder()
+            .prev_block_hash(prev_block_hash)
+            .transactions(transactions)
+            .timestamp(timestamp)
+            .build()
+            .wrap_err("failed constructing execute block request")?;
         let response = self
             .inner
             .execute_block(request)
@@ -181,9 +188,7 @@ impl Client {
         &mut self,
         commitment_state: CommitmentState,
     ) -> eyre::Result<CommitmentState> {
-        let request = raw::UpdateCommitmentStateRequest {
-            commitment_state: Some(commitment_state.into_raw()),
-        };
+        let request = raw::UpdateCommitmentStateRequest::from(commitment_state);
         let response = self
             .inner
             .update_commitment_state(request)
@@ -268,14 +273,6 @@ fn ensure_batch_get_blocks_is_correct(
     Ok(())
 }
 
-/// Utility function to construct a `astria.execution.v1alpha2.BlockIdentifier` from `number`
-/// to use in RPC requests.
-fn block_identifier(number: u32) -> raw::BlockIdentifier {
-    raw::BlockIdentifier {
-        identifier: Some(raw::block_identifier::Identifier::BlockNumber(number)),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::{
@@ -36,11 +36,15 @@ use tracing::{
     error,
     info,
     instrument,
+    warn,
 };
 
 use crate::{
     celestia::ReconstructedBlock,
-    config::CommitLevel,
+    config::{
+        CommitLevel,
+        CommitmentMismatchPolicy,
+    },
 };
 
 mod builder;
@@ -218,6 +222,9 @@ pub(crate) struct Executor {
     /// The mode under which this executor (and hence conductor) runs.
     mode: CommitLevel,
 
+    /// What to do if a soft block differs from the firm block at the same height.
+    commitment_mismatch_policy: CommitmentMismatchPolicy,
+
     /// The channel of which this executor receives blocks for executing
     /// firm commitments.
     /// Only set if `mode` is `FirmOnly` or `SoftAndFirm`.
@@ -240,7 +247,14 @@ pub(crate) struct Executor {
     ///
     /// Required to mark firm blocks received from celestia as executed
     /// without re-executing on top of the rollup node.
-    blocks_pending_finalization: HashMap<u32, Block>,
+    blocks_pending_finalization: HashMap<u32, PendingBlock>,
+
+    /// Soft blocks below this height that are ahead of the next expected soft height are
+    /// dropped instead of being treated as out-of-order.
+    ///
+    /// Set when the soft commitment is rewound to the firm commitment, because the soft blocks
+    /// already sent to the executor were read before the rewind.
+    discard_soft_blocks_below: Option<SequencerHeight>,
 }
 
 impl Executor {
@@ -390,6 +404,17 @@ impl Executor {
                 );
                 return Ok(());
             }
+            std::cmp::Ordering::Greater
+                if self
+                    .discard_soft_blocks_below
+                    .is_some_and(|bound| executable_block.height < bound) =>
+            {
+                info!(
+                    expected_height.sequencer_block = %expected_height,
+                    "block received was read before the soft commitment was rewound; dropping",
+                );
+                return Ok(());
+            }
             std::cmp::Ordering::Greater => bail!(
                 "block received was out-of-order; was a block skipped? expected: \
                  {expected_height}, actual: {}",
@@ -413,6 +438,7 @@ impl Executor {
 
         // The parent hash of the next block is the hash of the block at the current head.
         let parent_hash = self.state.soft_hash();
+        let sequencer_block_hash = executable_block.hash;
         let executed_block = self
             .execute_block(client.clone(), parent_hash, executable_block)
             .await
@@ -425,8 +451,13 @@ impl Executor {
             .await
             .wrap_err("failed to update soft commitment state")?;
 
-        self.blocks_pending_finalization
-            .insert(block_number, executed_block);
+        self.blocks_pending_finalization.insert(
+            block_number,
+            PendingBlock {
+                block: executed_block,
+                sequencer_block_hash: Some(sequencer_block_hash),
+            },
+        );
 
         Ok(())
     }
@@ -469,12 +500,25 @@ impl Executor {
             self.does_block_response_fulfill_contract(ExecutionKind::Firm, &executed_block)
                 .wrap_err("execution API server violated contract")?;
             Update::ToSame(executed_block)
-        } else if let Some(block) = self.blocks_pending_finalization.remove(&block_number) {
-            info!(
-                block_number,
-                "found pending block; updating state but not not re-executing it"
-            );
-            Update::OnlyFirm(block)
+        } else if let Some(pending) = self.blocks_pending_finalization.remove(&block_number) {
+            if let Some(mismatch) = pending.mismatch_with(block_number, &executable_block) {
+                mismatch.report(self.commitment_mismatch_policy);
+                match self.commitment_mismatch_policy {
+                    CommitmentMismatchPolicy::Halt => {
+                        return Err(mismatch).wrap_err("soft and firm commitments diverged");
+                    }
+                    CommitmentMismatchPolicy::Rollback => self
+                        .rewind_soft_to_firm(client.clone(), executable_block)
+                        .await
+                        .wrap_err("failed rewinding soft commitment to firm block")?,
+                }
+            } else {
+                info!(
+                    block_number,
+                    "found pending block; updating state but not not re-executing it"
+                );
+                Update::OnlyFirm(pending.block)
+            }
         } else {
             // XXX: This case should never be reached because the firm block *must* exist in the
             // cache - either due to being pre-populated at startup (via
@@ -508,6 +552,32 @@ impl Executor {
         Ok(())
     }
 
+    /// Executes the firm `block` on top of the firm commitment, replacing the soft block at the
+    /// same rollup number.
+    ///
+    /// This happens if the sequencer block executed as a soft commitment is not the same as the
+    /// firm block read from Celestia at the same height. The soft blocks built on top of the
+    /// replaced block are discarded: the returned update sets the soft commitment to the
+    /// re-executed firm block, so that the sequencer reader follows from there again.
+    #[instrument(skip_all, err)]
+    async fn rewind_soft_to_firm(
+        &mut self,
+        client: Client,
+        block: ExecutableBlock,
+    ) -> eyre::Result<Update> {
+        self.discard_soft_blocks_below = Some(self.state.next_expected_soft_sequencer_height());
+        self.blocks_pending_finalization.clear();
+
+        let parent_hash = self.state.firm_hash();
+        let executed_block = self
+            .execute_block(client, parent_hash, block)
+            .await
+            .wrap_err("failed to execute block")?;
+        self.does_block_response_fulfill_contract(ExecutionKind::Firm, &executed_block)
+            .wrap_err("execution API server violated contract")?;
+        Ok(Update::ToSame(executed_block))
+    }
+
     /// Executes `block` on top of its `parent_hash`.
     ///
     /// This function is called via [`Executor::execute_firm`] or [`Executor::execute_soft`],
@@ -587,8 +657,13 @@ impl Executor {
         info!("received blocks pending finalization",);
 
         for block in blocks {
-            self.blocks_pending_finalization
-                .insert(block.number(), block);
+            self.blocks_pending_finalization.insert(
+                block.number(),
+                PendingBlock {
+                    block,
+                    sequencer_block_hash: None,
+                },
+            );
         }
         Ok(())
     }
@@ -685,6 +760,75 @@ impl Executor {
     }
 }
 
+/// A rollup block that was executed as a soft commitment but not yet as a firm commitment.
+struct PendingBlock {
+    block: Block,
+    /// The hash of the sequencer block the rollup block was executed from. Not known for blocks
+    /// that were executed before conductor started.
+    sequencer_block_hash: Option<[u8; 32]>,
+}
+
+impl PendingBlock {
+    /// Returns a mismatch if the pending block at `rollup_number` was not executed from the
+    /// same sequencer block as `firm`.
+    ///
+    /// Blocks of which the sequencer block is not known are assumed to be the same.
+    fn mismatch_with(
+        &self,
+        rollup_number: u32,
+        firm: &ExecutableBlock,
+    ) -> Option<CommitmentMismatch> {
+        let soft_sequencer_block_hash = self.sequencer_block_hash?;
+        (soft_sequencer_block_hash != firm.hash).then_some(CommitmentMismatch {
+            sequencer_height: firm.height,
+            rollup_number,
+            soft_sequencer_block_hash,
+            firm_sequencer_block_hash: firm.hash,
+        })
+    }
+}
+
+/// A block received from the sequencer and executed as a soft commitment differs from the block
+/// read from Celestia at the same sequencer height.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "soft block at sequencer height `{sequencer_height}` (rollup number `{rollup_number}`) was \
+     executed from sequencer block `{}`, but the firm block at the same height is `{}`",
+    telemetry::display::base64(.soft_sequencer_block_hash),
+    telemetry::display::base64(.firm_sequencer_block_hash),
+)]
+pub(crate) struct CommitmentMismatch {
+    sequencer_height: SequencerHeight,
+    rollup_number: u32,
+    soft_sequencer_block_hash: [u8; 32],
+    firm_sequencer_block_hash: [u8; 32],
+}
+
+impl CommitmentMismatch {
+    /// Emits the mismatch as a warning event and records it in the metrics.
+    fn report(&self, policy: CommitmentMismatchPolicy) {
+        let Self {
+            sequencer_height,
+            rollup_number,
+            soft_sequencer_block_hash,
+            firm_sequencer_block_hash,
+        } = self;
+        warn!(
+            %sequencer_height,
+            rollup_number,
+            soft.sequencer_block_hash = %telemetry::display::base64(soft_sequencer_block_hash),
+            firm.sequencer_block_hash = %telemetry::display::base64(firm_sequencer_block_hash),
+            %policy,
+            "soft block differs from the firm block at the same height",
+        );
+        metrics::counter!(
+            crate::metrics_init::COMMITMENT_MISMATCHES,
+            &[("policy", policy.to_string())]
+        )
+        .increment(1);
+    }
+}
+
 enum Update {
     OnlyFirm(Block),
     OnlySoft(Block),
@@ -725,7 +869,7 @@ impl ExecutableBlock {
             ..
         } = block.into_parts();
         let transactions = rollup_transactions
-            .swap_remove(&id)
+            .remove(&id)
             .map(|txs| txs.transactions().to_vec())
             .unwrap_or_default();
         Self {
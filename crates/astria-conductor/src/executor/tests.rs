@@ -12,6 +12,8 @@ use bytes::Bytes;
 
 use super::{
     should_execute_firm_block,
+    ExecutableBlock,
+    PendingBlock,
     state::{
         StateReceiver,
         StateSender,
@@ -206,3 +208,26 @@ fn should_execute_firm() {
          don't match"
     );
 }
+
+#[test]
+fn pending_block_is_compared_by_sequencer_block_hash() {
+    let pending = |sequencer_block_hash| PendingBlock {
+        block: Block::try_from_raw(make_block(2)).unwrap(),
+        sequencer_block_hash,
+    };
+    let firm = ExecutableBlock {
+        hash: [1; 32],
+        height: 3u32.into(),
+        timestamp: pbjson_types::Timestamp::default(),
+        transactions: vec![],
+    };
+    assert!(pending(Some([1; 32])).mismatch_with(2, &firm).is_none());
+    assert!(
+        pending(Some([2; 32])).mismatch_with(2, &firm).is_some(),
+        "a soft block executed from a different sequencer block must be reported"
+    );
+    assert!(
+        pending(None).mismatch_with(2, &firm).is_none(),
+        "soft blocks executed before conductor started are assumed to match"
+    );
+}
@@ -1,6 +1,7 @@
 use std::process::ExitCode;
 
 use astria_conductor::{
+    metrics_init,
     Conductor,
     Config,
     BUILD_INFO,
@@ -47,6 +48,7 @@ async fn main() -> ExitCode {
             .metrics_addr(&cfg.metrics_http_listener_addr)
             .service_name(env!("CARGO_PKG_NAME"));
     }
+    metrics_init::register();
 
     if let Err(e) = telemetry_conf
         .try_init()
@@ -0,0 +1,86 @@
+//! Crate-specific metrics functionality.
+//!
+//! Registers metrics & lists constants to be used as metric names throughout crate.
+
+use metrics::{
+    describe_counter,
+    describe_gauge,
+    Unit,
+};
+
+/// Registers all metrics used by this crate.
+pub fn register() {
+    describe_counter!(
+        REJECTED_CELESTIA_BLOBS,
+        Unit::Count,
+        "The number of blobs read from Celestia that were rejected, labeled by the reason of \
+         their rejection"
+    );
+
+    describe_counter!(
+        DECODED_CELESTIA_BLOBS,
+        Unit::Count,
+        "The number of blobs read from Celestia that were decoded, labeled by their format"
+    );
+
+    describe_gauge!(
+        DECODED_CELESTIA_BLOBS_AT_LATEST_HEIGHT,
+        Unit::Count,
+        "The number of blobs decoded at the most recently decoded Celestia height, labeled by \
+         their format"
+    );
+
+    describe_counter!(
+        COMMITMENT_MISMATCHES,
+        Unit::Count,
+        "The number of blocks executed as soft commitments that differed from the firm block at \
+         the same height, labeled by the policy applied"
+    );
+
+    describe_counter!(
+        AUDITED_BLOCKS,
+        Unit::Count,
+        "The number of blocks reconstructed without verification while fast syncing that were \
+         audited afterwards, labeled by the result of the audit"
+    );
+
+    describe_gauge!(
+        UNAUDITED_BLOCKS,
+        Unit::Count,
+        "The number of blocks reconstructed without verification while fast syncing that are \
+         awaiting their audit"
+    );
+
+    describe_counter!(
+        SHADOW_READS,
+        Unit::Count,
+        "The number of blob fetches that were also issued against the shadow Celestia node, \
+         labeled by whether the blobs of both nodes matched, diverged, or the shadow read failed"
+    );
+
+    describe_counter!(
+        SHADOW_READ_DIVERGENT_BLOBS,
+        Unit::Count,
+        "The number of blobs that diverged between the primary and the shadow Celestia node, \
+         labeled by namespace and by whether they were missing on the shadow node, extra on the \
+         shadow node, or altered"
+    );
+}
+
+pub const REJECTED_CELESTIA_BLOBS: &str = telemetry::metric_name!("rejected_celestia_blobs");
+
+pub const DECODED_CELESTIA_BLOBS: &str = telemetry::metric_name!("decoded_celestia_blobs");
+
+pub const DECODED_CELESTIA_BLOBS_AT_LATEST_HEIGHT: &str =
+    telemetry::metric_name!("decoded_celestia_blobs_at_latest_height");
+
+pub const COMMITMENT_MISMATCHES: &str = telemetry::metric_name!("commitment_mismatches");
+
+pub const AUDITED_BLOCKS: &str = telemetry::metric_name!("audited_blocks");
+
+pub const UNAUDITED_BLOCKS: &str = telemetry::metric_name!("unaudited_blocks");
+
+pub const SHADOW_READS: &str = telemetry::metric_name!("shadow_reads");
+
+pub const SHADOW_READ_DIVERGENT_BLOBS: &str =
+    telemetry::metric_name!("shadow_read_divergent_blobs");
@@ -91,6 +91,20 @@ impl Heights {
         }
         greater
     }
+
+    /// Sets the height expected by the rollup and forgets the greatest requested height if
+    /// the height is lower than what was previously set.
+    ///
+    /// Returns `true` if lower, `false` if not.
+    fn rewind_if_lower(&mut self, height: Height) -> bool {
+        let height = height.value();
+        let lower = height < self.rollup_expects;
+        if lower {
+            self.rollup_expects = height;
+            self.greatest_requested_height = None;
+        }
+        lower
+    }
 }
 
 pin_project! {
@@ -135,6 +149,26 @@ impl BlocksFromHeightStream {
         }
     }
 
+    /// Restarts fetching blocks at `height` if the rollup now expects a lower height than
+    /// previously recorded.
+    ///
+    /// This happens if the executor rewound its soft commitment. Returns `true` if the stream
+    /// was rewound.
+    #[instrument(
+        skip_all,
+        fields(
+            rollup_expects.provided = %height,
+            rollup_expects.recorded = %self.heights.rollup_expects,
+        )
+    )]
+    pub(super) fn rewind_if_lower(&mut self, height: Height) -> bool {
+        let rewound = self.heights.rewind_if_lower(height);
+        if rewound {
+            info!("next expected sequencer height lower than previous; rewinding stream");
+        }
+        rewound
+    }
+
     pub(super) fn new(
         rollup_id: RollupId,
         rollup_expects: Height,
@@ -314,4 +348,23 @@ mod tests {
         let next = heights.next_height_to_fetch();
         assert_eq!(None, next);
     }
+
+    #[test]
+    fn rewinding_refetches_from_what_rollup_expects() {
+        let mut heights = Heights {
+            rollup_expects: 6,
+            greatest_requested_height: Some(8),
+            latest_sequencer_height: 10,
+            max_ahead: 5,
+        };
+        assert!(!heights.rewind_if_lower(6u32.into()));
+        assert_eq!(Some(9), heights.next_height_to_fetch());
+
+        assert!(heights.rewind_if_lower(4u32.into()));
+        assert_eq!(
+            Some(4),
+            heights.next_height_to_fetch(),
+            "a rewound heights tracker should fetch what the rollup expects again"
+        );
+    }
 }
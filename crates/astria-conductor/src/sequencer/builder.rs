@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 
 use super::SequencerGrpcClient;
@@ -11,6 +12,7 @@ pub(crate) struct Builder {
     pub(crate) sequencer_cometbft_client: sequencer_client::HttpClient,
     pub(crate) sequencer_block_time: Duration,
     pub(crate) shutdown: CancellationToken,
+    pub(crate) wait_for_firm_sync: Option<watch::Receiver<bool>>,
 }
 
 impl Builder {
@@ -21,6 +23,7 @@ impl Builder {
             sequencer_cometbft_client,
             sequencer_block_time,
             shutdown,
+            wait_for_firm_sync,
         } = self;
         super::Reader {
             executor,
@@ -28,6 +31,7 @@ impl Builder {
             sequencer_cometbft_client,
             sequencer_block_time,
             shutdown,
+            wait_for_firm_sync,
         }
     }
 }
@@ -3,6 +3,7 @@
 use std::time::Duration;
 
 use astria_core::{
+    connect::sequencer_service_client,
     generated::sequencerblock::v1alpha1::{
         sequencer_service_client::SequencerServiceClient,
         GetFilteredSequencerBlockRequest,
@@ -16,7 +17,6 @@ use astria_eyre::eyre::{
 };
 use tonic::transport::{
     Channel,
-    Endpoint,
     Uri,
 };
 use tracing::{
@@ -38,8 +38,8 @@ impl SequencerGrpcClient {
         let uri: Uri = sequencer_uri
             .parse()
             .wrap_err("failed parsing provided string as Uri")?;
-        let endpoint = Endpoint::from(uri.clone());
-        let inner = SequencerServiceClient::new(endpoint.connect_lazy());
+        let inner = sequencer_service_client([sequencer_uri])
+            .wrap_err("failed constructing sequencer service client")?;
         Ok(Self {
             inner,
             uri,
@@ -18,7 +18,10 @@ use futures::{
     StreamExt as _,
 };
 use sequencer_client::HttpClient;
-use tokio::select;
+use tokio::{
+    select,
+    sync::watch,
+};
 use tokio_util::sync::CancellationToken;
 use tracing::{
     debug,
@@ -55,6 +58,10 @@ pub(crate) struct Reader {
 
     /// Token to listen for Conductor being shut down.
     shutdown: CancellationToken,
+
+    /// If set, blocks are only read from Sequencer after the Celestia reader forwarded all firm
+    /// blocks up to the Celestia head to the executor.
+    wait_for_firm_sync: Option<watch::Receiver<bool>>,
 }
 
 impl Reader {
@@ -67,12 +74,27 @@ impl Reader {
             sequencer_cometbft_client,
             sequencer_block_time,
             shutdown,
+            wait_for_firm_sync,
         } = self;
 
         let mut executor = executor
             .wait_for_init()
             .await
             .wrap_err("handle to executor failed while waiting for it being initialized")?;
+
+        if let Some(mut firm_synced) = wait_for_firm_sync {
+            info!("waiting for firm blocks to catch up with Celestia before reading Sequencer");
+            select! {
+                () = shutdown.cancelled() => {
+                    info!(reason = "received shutdown signal", "shutting down");
+                    return Ok(());
+                }
+                res = firm_synced.wait_for(|synced| *synced) => {
+                    res.wrap_err("channel to Celestia reader closed before firm blocks caught up")?;
+                }
+            }
+            info!("firm blocks caught up with Celestia; reading blocks from Sequencer");
+        }
         let next_expected_height = executor.next_expected_soft_sequencer_height();
 
         let mut latest_height_stream = {
@@ -122,10 +144,19 @@ impl Reader {
                     }
                 }
 
-                // Skip heights that executor has already executed (e.g. firm blocks from Celestia)
+                // Skip heights that executor has already executed (e.g. firm blocks from Celestia),
+                // or read blocks again if executor rewound its soft commitment.
                 Ok(next_height) = executor.next_expected_soft_height_if_changed() => {
-                    blocks_from_heights.set_next_expected_height_if_greater(next_height);
-                    sequential_blocks.drop_obsolete(next_height);
+                    if blocks_from_heights.rewind_if_lower(next_height) {
+                        sequential_blocks = match BlockCache::with_next_height(next_height) {
+                            Ok(cache) => cache,
+                            Err(error) => break Err(error).wrap_err("failed resetting sequential block cache"),
+                        };
+                        enqueued_block = future::Fuse::terminated();
+                    } else {
+                        blocks_from_heights.set_next_expected_height_if_greater(next_height);
+                        sequential_blocks.drop_obsolete(next_height);
+                    }
                 }
 
                 // Forward the next block to executor. Enqueue if the executor channel is full.
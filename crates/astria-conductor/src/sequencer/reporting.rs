@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use astria_core::{
     primitive::v1::RollupId,
     sequencerblock::v1alpha1::block::{
@@ -5,7 +7,6 @@ use astria_core::{
         RollupTransactions,
     },
 };
-use indexmap::IndexMap;
 use serde::ser::{
     Serialize,
     SerializeMap as _,
@@ -25,7 +26,7 @@ impl<'a> Serialize for ReportFilteredSequencerBlock<'a> {
     }
 }
 
-struct ReportRollups<'a>(&'a IndexMap<RollupId, RollupTransactions>);
+struct ReportRollups<'a>(&'a BTreeMap<RollupId, RollupTransactions>);
 
 impl<'a> Serialize for ReportRollups<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -338,16 +338,10 @@ macro_rules! mount_pending_blocks {
                 parent: $parent,
             )),*
         ];
-        let identifiers: Vec<_> = blocks.iter().map(
-            |block| ::astria_core::generated::execution::v1alpha2::BlockIdentifier {
-                identifier: Some(::astria_core::generated::execution::v1alpha2::block_identifier::Identifier::BlockNumber(block.number)),
-        }).collect();
-        $test_env.mount_batch_get_blocks(
-            ::astria_core::generated::execution::v1alpha2::BatchGetBlocksRequest {
-                identifiers,
-            },
-            blocks,
-        )
-        .await
+        let request = ::astria_core::generated::execution::v1alpha2::BatchGetBlocksRequest::builder()
+            .block_numbers(blocks.iter().map(|block| block.number))
+            .build()
+            .unwrap();
+        $test_env.mount_batch_get_blocks(request, blocks).await
     }};
 }
@@ -18,6 +18,8 @@ use astria_core::generated::{
         GetBlockRequest,
         GetCommitmentStateRequest,
         GetGenesisInfoRequest,
+        StreamExecuteBlocksRequest,
+        StreamExecuteBlocksResponse,
         UpdateCommitmentStateRequest,
     },
     sequencerblock::v1alpha1::{
@@ -41,6 +43,7 @@ use tonic::{
     transport::Server,
     Request,
     Response,
+    Streaming,
 };
 
 pub struct MockGrpc {
@@ -113,7 +116,12 @@ impl SequencerService for SequencerServiceImpl {
 }
 
 macro_rules! define_and_impl_service {
-    (impl $trait:ident for $target:ident { $( ($rpc:ident: $request:ty => $response:ty) )* }) => {
+    (
+        impl $trait:ident for $target:ident {
+            $( ($rpc:ident: $request:ty => $response:ty) )*
+        }
+        $( manual { $( $manual:tt )* } )?
+    ) => {
         struct $target {
             mock_server: ::astria_grpc_mock::MockServer,
         }
@@ -131,6 +139,8 @@ macro_rules! define_and_impl_service {
                     self.mock_server.handle_request(stringify!($rpc), request).await
             }
             )+
+
+            $( $( $manual )* )?
         }
     }
 }
@@ -142,4 +152,14 @@ define_and_impl_service!(impl ExecutionService for ExecutionServiceImpl {
     (execute_block: ExecuteBlockRequest => Block)
     (get_commitment_state: GetCommitmentStateRequest => CommitmentState)
     (update_commitment_state: UpdateCommitmentStateRequest => CommitmentState)
+} manual {
+    type StreamExecuteBlocksStream =
+        tokio_stream::Empty<tonic::Result<StreamExecuteBlocksResponse>>;
+
+    async fn stream_execute_blocks(
+        self: Arc<Self>,
+        _request: Request<Streaming<StreamExecuteBlocksRequest>>,
+    ) -> tonic::Result<Response<Self::StreamExecuteBlocksStream>> {
+        Err(tonic::Status::unimplemented("the mock only serves unary execution calls"))
+    }
 });
@@ -441,12 +441,24 @@ fn make_config() -> Config {
         celestia_block_time_ms: 12000,
         celestia_node_http_url: "http://127.0.0.1:26658".into(),
         celestia_bearer_token: CELESTIA_BEARER_TOKEN.into(),
+        celestia_shadow_node_http_url: String::new(),
+        celestia_shadow_bearer_token: String::new(),
+        celestia_sequencer_namespace: String::new(),
+        celestia_max_blob_size_bytes: 16 * 1024 * 1024,
+        celestia_max_blobs_per_height: 1024,
+        celestia_max_rollup_txs_per_blob: 65536,
+        celestia_max_response_size_bytes: 256 * 1024 * 1024,
+        celestia_fast_sync: false,
+        celestia_confirmation_depth: 0,
         sequencer_grpc_url: "http://127.0.0.1:8080".into(),
         sequencer_cometbft_url: "http://127.0.0.1:26657".into(),
         sequencer_block_time_ms: 2000,
         execution_rpc_url: "http://127.0.0.1:50051".into(),
         log: "info".into(),
         execution_commit_level: astria_conductor::config::CommitLevel::SoftAndFirm,
+        execution_firm_sync_before_soft: false,
+        execution_commitment_mismatch_policy:
+            astria_conductor::config::CommitmentMismatchPolicy::Rollback,
         force_stdout: false,
         no_otel: false,
         no_metrics: true,
@@ -457,7 +469,14 @@ fn make_config() -> Config {
 
 #[must_use]
 pub fn make_sequencer_block(height: u32) -> astria_core::sequencerblock::v1alpha1::SequencerBlock {
+    // The block hash must be the hash of the cometbft header because conductor verifies it.
+    let block_hash = make_cometbft_header(height)
+        .hash()
+        .as_bytes()
+        .try_into()
+        .unwrap();
     astria_core::protocol::test_utils::ConfigureSequencerBlock {
+        block_hash: Some(block_hash),
         chain_id: Some(crate::SEQUENCER_CHAIN_ID.to_string()),
         height,
         sequence_data: vec![(crate::ROLLUP_ID, data())],
@@ -559,28 +578,33 @@ pub fn make_commit(height: u32) -> tendermint::block::Commit {
     }
 }
 
+#[must_use]
+pub fn make_cometbft_header(height: u32) -> tendermint::block::Header {
+    tendermint::block::Header {
+        version: tendermint::block::header::Version {
+            block: 1,
+            app: 1,
+        },
+        chain_id: crate::SEQUENCER_CHAIN_ID.try_into().unwrap(),
+        height: height.into(),
+        time: tendermint::time::Time::from_unix_timestamp(1, 1).unwrap(),
+        last_block_id: None,
+        last_commit_hash: None,
+        data_hash: None,
+        validators_hash: tendermint::Hash::Sha256([0; 32]),
+        next_validators_hash: tendermint::Hash::Sha256([0; 32]),
+        consensus_hash: tendermint::Hash::Sha256([0; 32]),
+        app_hash: tendermint::AppHash::default(),
+        last_results_hash: None,
+        evidence_hash: None,
+        proposer_address: validator().address,
+    }
+}
+
 #[must_use]
 pub fn make_signed_header(height: u32) -> tendermint::block::signed_header::SignedHeader {
     tendermint::block::signed_header::SignedHeader::new(
-        tendermint::block::Header {
-            version: tendermint::block::header::Version {
-                block: 1,
-                app: 1,
-            },
-            chain_id: crate::SEQUENCER_CHAIN_ID.try_into().unwrap(),
-            height: height.into(),
-            time: tendermint::time::Time::from_unix_timestamp(1, 1).unwrap(),
-            last_block_id: None,
-            last_commit_hash: None,
-            data_hash: None,
-            validators_hash: tendermint::Hash::Sha256([0; 32]),
-            next_validators_hash: tendermint::Hash::Sha256([0; 32]),
-            consensus_hash: tendermint::Hash::Sha256([0; 32]),
-            app_hash: tendermint::AppHash::default(),
-            last_results_hash: None,
-            evidence_hash: None,
-            proposer_address: validator().address,
-        },
+        make_cometbft_header(height),
         make_commit(height),
     )
     .unwrap()
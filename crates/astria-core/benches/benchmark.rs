@@ -0,0 +1,9 @@
+// Required to force the benchmark target to actually register the divan benchmark cases.
+use astria_core as _;
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+fn main() {
+    divan::main();
+}
@@ -0,0 +1,203 @@
+//! Signed attestations by sequencer-relayers that a submission was confirmed on Celestia.
+//!
+//! After a submission is included in a Celestia block, the relayer signs a
+//! [`SubmissionAttestation`] naming the sequencer blocks and blob commitments it contained, the
+//! Celestia height it was included at, and when the relayer observed the inclusion. Bridges and
+//! auditors can present a [`SignedSubmissionAttestation`] as evidence that the relayer posted
+//! those blocks, and check it against Celestia using the blob commitments.
+//!
+//! Like heartbeats, the ed25519 `signature` is over the bytes of `payload`, which holds the JSON
+//! encoding of the attestation, so that readers can verify it without re-encoding the payload.
+
+use ed25519_consensus::{
+    Signature,
+    SigningKey,
+    VerificationKey,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// An error when decoding or verifying a [`SignedSubmissionAttestation`].
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct AttestationError(AttestationErrorKind);
+
+impl AttestationError {
+    fn decode(source: serde_json::Error) -> Self {
+        Self(AttestationErrorKind::Decode(source))
+    }
+
+    fn verification_key(source: ed25519_consensus::Error) -> Self {
+        Self(AttestationErrorKind::VerificationKey(source))
+    }
+
+    fn signature(source: ed25519_consensus::Error) -> Self {
+        Self(AttestationErrorKind::Signature(source))
+    }
+
+    fn payload(source: serde_json::Error) -> Self {
+        Self(AttestationErrorKind::Payload(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum AttestationErrorKind {
+    #[error("failed decoding signed attestation as JSON")]
+    Decode(#[source] serde_json::Error),
+    #[error("the verification key of the attestation is not a valid ed25519 key")]
+    VerificationKey(#[source] ed25519_consensus::Error),
+    #[error("the signature of the attestation does not match its payload")]
+    Signature(#[source] ed25519_consensus::Error),
+    #[error("failed decoding attestation payload as JSON")]
+    Payload(#[source] serde_json::Error),
+}
+
+/// A sequencer block contained in an attested submission.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestedSequencerBlock {
+    /// The chain ID of the sequencer network of the block.
+    pub chain_id: String,
+    /// The height of the block.
+    pub height: u64,
+    /// The hash of the block.
+    #[serde(with = "crate::serde::hex")]
+    pub block_hash: [u8; 32],
+}
+
+/// A blob contained in an attested submission.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestedBlob {
+    /// The hex encoded Celestia namespace the blob was written under.
+    pub namespace: String,
+    /// The share commitment of the blob, as included in the Celestia transaction.
+    #[serde(with = "crate::serde::hex")]
+    pub commitment: [u8; 32],
+}
+
+/// The contents of an attestation that a submission was confirmed on Celestia.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmissionAttestation {
+    /// The address of the key signing the attestation, identifying the relayer.
+    pub relayer_address: String,
+    /// The Celestia height at which the submission was included.
+    pub celestia_height: u64,
+    /// The unix timestamp in seconds at which the relayer observed the inclusion.
+    pub timestamp: u64,
+    /// The sequencer blocks contained in the submission.
+    pub sequencer_blocks: Vec<AttestedSequencerBlock>,
+    /// The blobs contained in the submission, in the order they were submitted.
+    pub blobs: Vec<AttestedBlob>,
+}
+
+impl SubmissionAttestation {
+    /// Returns if the attested submission contains the sequencer block with `block_hash`.
+    #[must_use]
+    pub fn contains_block(&self, block_hash: [u8; 32]) -> bool {
+        self.sequencer_blocks
+            .iter()
+            .any(|block| block.block_hash == block_hash)
+    }
+}
+
+/// A [`SubmissionAttestation`] together with its signature.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedSubmissionAttestation {
+    #[serde(with = "crate::serde::streaming_base64")]
+    payload: Vec<u8>,
+    #[serde(with = "crate::serde::hex")]
+    verification_key: [u8; 32],
+    #[serde(with = "crate::serde::hex")]
+    signature: [u8; 64],
+}
+
+impl SignedSubmissionAttestation {
+    /// Signs `attestation` with `signing_key`.
+    #[must_use]
+    pub fn sign(attestation: &SubmissionAttestation, signing_key: &SigningKey) -> Self {
+        let payload = serde_json::to_vec(attestation)
+            .expect("serializing a struct of strings, integers and byte arrays cannot fail");
+        let signature = signing_key.sign(&payload);
+        Self {
+            payload,
+            verification_key: signing_key.verification_key().to_bytes(),
+            signature: signature.to_bytes(),
+        }
+    }
+
+    /// Returns the bytes of the ed25519 key that signed the attestation.
+    #[must_use]
+    pub fn verification_key(&self) -> [u8; 32] {
+        self.verification_key
+    }
+
+    /// Encodes the signed attestation as JSON.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        serde_json::to_vec(self)
+            .expect("serializing a struct of byte fields to JSON cannot fail")
+    }
+
+    /// Decodes a signed attestation from its JSON encoding.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` is not the JSON encoding of a signed attestation.
+    pub fn decode(bytes: &[u8]) -> Result<Self, AttestationError> {
+        serde_json::from_slice(bytes).map_err(AttestationError::decode)
+    }
+
+    /// Verifies the signature of the attestation and returns its contents.
+    ///
+    /// # Errors
+    /// Returns an error if the signature does not match the payload or if the payload is not
+    /// the JSON encoding of a [`SubmissionAttestation`].
+    pub fn verify(&self) -> Result<SubmissionAttestation, AttestationError> {
+        VerificationKey::try_from(self.verification_key)
+            .map_err(AttestationError::verification_key)?
+            .verify(&Signature::from(self.signature), &self.payload)
+            .map_err(AttestationError::signature)?;
+        serde_json::from_slice(&self.payload).map_err(AttestationError::payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation() -> SubmissionAttestation {
+        SubmissionAttestation {
+            relayer_address: "relayer".to_string(),
+            celestia_height: 10,
+            timestamp: 1_700_000_000,
+            sequencer_blocks: vec![AttestedSequencerBlock {
+                chain_id: "sequencer".to_string(),
+                height: 100,
+                block_hash: [1; 32],
+            }],
+            blobs: vec![AttestedBlob {
+                namespace: "00000000000000000000000000000000000000000000000000000000ab".to_string(),
+                commitment: [2; 32],
+            }],
+        }
+    }
+
+    #[test]
+    fn signed_attestation_roundtrips_and_verifies() {
+        let signed = SignedSubmissionAttestation::sign(&attestation(), &SigningKey::from([1; 32]));
+        let decoded = SignedSubmissionAttestation::decode(&signed.encode()).unwrap();
+        assert_eq!(signed, decoded);
+        let verified = decoded.verify().unwrap();
+        assert_eq!(attestation(), verified);
+        assert!(verified.contains_block([1; 32]));
+        assert!(!verified.contains_block([2; 32]));
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let mut signed =
+            SignedSubmissionAttestation::sign(&attestation(), &SigningKey::from([1; 32]));
+        signed.payload[0] ^= 1;
+        assert!(signed.verify().is_err());
+    }
+}
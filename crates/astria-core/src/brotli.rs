@@ -1,8 +1,12 @@
-use std::io::Write as _;
+use std::io::{
+    Read as _,
+    Write as _,
+};
 
 use brotli::{
     enc::BrotliEncoderParams,
     CompressorWriter,
+    Decompressor,
     DecompressorWriter,
 };
 
@@ -27,6 +31,53 @@ pub fn decompress_bytes(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
     Ok(output)
 }
 
+/// The error wrapped by [`decompress_bytes_with_limit`] if the decompressed bytes are too long.
+#[derive(Debug, thiserror::Error)]
+#[error("decompressed bytes exceed the maximum of {max_len} bytes")]
+pub struct DecompressedSizeExceeded {
+    max_len: usize,
+}
+
+impl DecompressedSizeExceeded {
+    /// Returns if `error` was caused by decompressed bytes exceeding their maximum length.
+    #[must_use]
+    pub fn is_cause_of(error: &std::io::Error) -> bool {
+        error
+            .get_ref()
+            .is_some_and(|inner| inner.is::<DecompressedSizeExceeded>())
+    }
+}
+
+/// Decompresses the given bytes using the Brotli algorithm, reading at most `max_len` bytes.
+///
+/// Unlike [`decompress_bytes`] this is safe to use on untrusted input: decompression stops as
+/// soon as the output exceeds `max_len`, so that small, highly compressed inputs cannot exhaust
+/// memory.
+///
+/// # Errors
+///
+/// Returns an error if the decompression fails, or an error of kind
+/// [`std::io::ErrorKind::InvalidData`] wrapping a [`DecompressedSizeExceeded`] if the
+/// decompressed bytes are longer than `max_len`.
+pub fn decompress_bytes_with_limit(
+    data: &[u8],
+    max_len: usize,
+) -> Result<Vec<u8>, std::io::Error> {
+    let mut output = Vec::with_capacity(data.len().min(max_len));
+    Decompressor::new(data, BROTLI_BUFFER_SIZE)
+        .take(u64::try_from(max_len).unwrap_or(u64::MAX).saturating_add(1))
+        .read_to_end(&mut output)?;
+    if output.len() > max_len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            DecompressedSizeExceeded {
+                max_len,
+            },
+        ));
+    }
+    Ok(output)
+}
+
 /// Compresses the given bytes using the Brotli algorithm at setting 5.
 ///
 /// Returns the compressed bytes.
@@ -51,3 +102,18 @@ pub fn compress_bytes(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
 
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompression_is_limited() {
+        let data = vec![42; 1024];
+        let compressed = compress_bytes(&data).unwrap();
+        assert_eq!(data, decompress_bytes_with_limit(&compressed, 1024).unwrap());
+        let error = decompress_bytes_with_limit(&compressed, 1023).unwrap_err();
+        assert!(DecompressedSizeExceeded::is_cause_of(&error));
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+    }
+}
@@ -34,3 +34,338 @@ pub fn namespace_v0_from_sha256_of_bytes<T: AsRef<[u8]>>(bytes: T) -> Namespace
     };
     namespace_v0_from_first_10_bytes(&Sha256::digest(bytes))
 }
+
+/// Returns the Celestia namespace under which the sequencer blobs of the sequencer network with
+/// `chain_id` are written.
+///
+/// This is `base_namespace` if it is set, and otherwise derived from the first 10 bytes of the
+/// sha256 hash of `chain_id`. The writers and readers of sequencer blobs must pass the same
+/// arguments for the readers to find the blobs.
+#[must_use = "a celestia namespace must be used in order to be useful"]
+pub fn sequencer_namespace(chain_id: &str, base_namespace: Option<Namespace>) -> Namespace {
+    base_namespace.unwrap_or_else(|| namespace_v0_from_sha256_of_bytes(chain_id))
+}
+
+/// The prefix hashed together with a rollup ID to derive the namespace of its execution results.
+const ROLLUP_RESULT_NAMESPACE_PREFIX: &[u8] = b"astria-rollup-results";
+
+/// Returns the Celestia namespace under which the execution results of the rollup with
+/// `rollup_id` are written.
+///
+/// The namespace is derived from the first 10 bytes of the sha256 hash of a fixed prefix and
+/// the rollup ID, so that it differs from the namespace holding the rollup's transactions.
+#[must_use = "a celestia namespace must be used in order to be useful"]
+pub fn rollup_result_namespace(rollup_id: crate::primitive::v1::RollupId) -> Namespace {
+    namespace_v0_from_sha256_of_bytes([ROLLUP_RESULT_NAMESPACE_PREFIX, &rollup_id.get()].concat())
+}
+
+/// The number of leading zero bytes of a version 0 namespace before its 10 byte ID.
+const NS_V0_ZERO_PREFIX_SIZE: usize =
+    celestia_types::nmt::NS_ID_SIZE - celestia_types::nmt::NS_ID_V0_SIZE;
+
+/// An error when constructing a [`NamespaceV0`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum NamespaceError {
+    #[error("the namespace ID must be {expected} bytes, but was {actual} bytes")]
+    Length { expected: usize, actual: usize },
+    #[error("the namespace is of version `{0}`, but only version 0 namespaces hold blobs")]
+    Version(u8),
+    #[error("the namespace is of version 0, but its ID is not prefixed by zero bytes")]
+    Prefix,
+    #[error("the namespace ID is in the range reserved by Celestia")]
+    Reserved,
+}
+
+/// An error when parsing a Celestia v0 namespace from its hex encoded ID.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseNamespaceError {
+    #[error("the namespace ID is not hex encoded")]
+    Hex(#[source] hex::FromHexError),
+    #[error("the namespace ID is not valid")]
+    Invalid(#[source] NamespaceError),
+}
+
+/// Parses a Celestia v0 namespace from the hex encoding of its 10 byte ID, with or without a
+/// `0x` prefix.
+///
+/// # Errors
+/// Returns an error if `input` is not hex encoded, does not decode to exactly 10 bytes, or is in
+/// the range of IDs reserved by Celestia.
+pub fn namespace_v0_from_hex(input: &str) -> Result<Namespace, ParseNamespaceError> {
+    input.parse::<NamespaceV0>().map(NamespaceV0::get)
+}
+
+/// A Celestia version 0 namespace that blobs can be written under.
+///
+/// Unlike [`Namespace`], which holds any namespace including those Celestia reserves for its own
+/// shares, a `NamespaceV0` is guaranteed to have a 10 byte ID outside of the reserved range: its
+/// first 9 bytes are not all zero. Celestia rejects blobs under any other namespace.
+///
+/// It is displayed, parsed, and serialized as the `0x` prefixed hex encoding of its ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NamespaceV0 {
+    id: [u8; celestia_types::nmt::NS_ID_V0_SIZE],
+}
+
+impl NamespaceV0 {
+    /// The greatest version 0 namespace.
+    pub const MAX: Self = Self {
+        id: [0xff; celestia_types::nmt::NS_ID_V0_SIZE],
+    };
+    /// The least version 0 namespace outside of the range reserved by Celestia.
+    pub const MIN: Self = Self {
+        id: [0, 0, 0, 0, 0, 0, 0, 0, 1, 0],
+    };
+
+    /// Constructs a namespace from its 10 byte ID.
+    ///
+    /// # Errors
+    /// Returns an error if `id` is in the range reserved by Celestia.
+    pub const fn new(id: [u8; celestia_types::nmt::NS_ID_V0_SIZE]) -> Result<Self, NamespaceError> {
+        if is_reserved_id(&id) {
+            return Err(NamespaceError::Reserved);
+        }
+        Ok(Self {
+            id,
+        })
+    }
+
+    /// Constructs the namespace of a rollup from the first 10 bytes of its ID, like
+    /// [`namespace_v0_from_rollup_id`].
+    ///
+    /// # Errors
+    /// Returns an error if the first 10 bytes of `rollup_id` are in the range reserved by
+    /// Celestia.
+    pub const fn from_rollup_id(
+        rollup_id: crate::primitive::v1::RollupId,
+    ) -> Result<Self, NamespaceError> {
+        let bytes = rollup_id.get();
+        Self::new([
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+            bytes[8], bytes[9],
+        ])
+    }
+
+    /// Constructs a namespace from a slice holding its 10 byte ID.
+    ///
+    /// # Errors
+    /// Returns an error if `id` is not exactly 10 bytes or is in the range reserved by Celestia.
+    pub fn try_from_slice(id: &[u8]) -> Result<Self, NamespaceError> {
+        let id = id.try_into().map_err(|_| NamespaceError::Length {
+            expected: celestia_types::nmt::NS_ID_V0_SIZE,
+            actual: id.len(),
+        })?;
+        Self::new(id)
+    }
+
+    /// Returns the 10 byte ID of the namespace.
+    #[must_use]
+    pub const fn id(&self) -> [u8; celestia_types::nmt::NS_ID_V0_SIZE] {
+        self.id
+    }
+
+    /// Returns the namespace as a [`Namespace`].
+    #[must_use]
+    pub const fn get(self) -> Namespace {
+        Namespace::const_v0(self.id)
+    }
+}
+
+/// Returns if a version 0 namespace `id` is in the range reserved by Celestia, which are all
+/// IDs with their first 9 bytes zero.
+const fn is_reserved_id(id: &[u8; celestia_types::nmt::NS_ID_V0_SIZE]) -> bool {
+    let mut i = 0;
+    while i < id.len() - 1 {
+        if id[i] != 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+impl TryFrom<Namespace> for NamespaceV0 {
+    type Error = NamespaceError;
+
+    fn try_from(namespace: Namespace) -> Result<Self, Self::Error> {
+        let bytes = namespace.as_bytes();
+        if bytes[0] != 0 {
+            return Err(NamespaceError::Version(bytes[0]));
+        }
+        let (prefix, id) = bytes[1..].split_at(NS_V0_ZERO_PREFIX_SIZE);
+        if prefix.iter().any(|byte| *byte != 0) {
+            return Err(NamespaceError::Prefix);
+        }
+        Self::try_from_slice(id)
+    }
+}
+
+impl From<NamespaceV0> for Namespace {
+    fn from(namespace: NamespaceV0) -> Self {
+        namespace.get()
+    }
+}
+
+impl std::fmt::Display for NamespaceV0 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::primitive::v1::hex::fmt_hex(&self.id, f)
+    }
+}
+
+impl std::str::FromStr for NamespaceV0 {
+    type Err = ParseNamespaceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let bytes =
+            hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(ParseNamespaceError::Hex)?;
+        Self::try_from_slice(&bytes).map_err(ParseNamespaceError::Invalid)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NamespaceV0 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NamespaceV0 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A Celestia namespace displayed and parsed as the `0x` prefixed hex encoding of its ID.
+///
+/// Version 0 namespaces are displayed by their 10 byte ID, as accepted by
+/// [`namespace_v0_from_hex`]. Namespaces of other versions are displayed in full. Unlike
+/// [`NamespaceV0`], any namespace can be displayed, which suits namespaces read from Celestia
+/// that are not known to be valid. Parsing is as strict as for [`NamespaceV0`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HexNamespace(pub Namespace);
+
+impl std::fmt::Display for HexNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes = self.0.as_bytes();
+        let bytes = if bytes[0] == 0 {
+            &bytes[bytes.len() - celestia_types::nmt::NS_ID_V0_SIZE..]
+        } else {
+            bytes
+        };
+        crate::primitive::v1::hex::fmt_hex(bytes, f)
+    }
+}
+
+impl std::str::FromStr for HexNamespace {
+    type Err = ParseNamespaceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        namespace_v0_from_hex(s).map(Self)
+    }
+}
+
+impl From<NamespaceV0> for HexNamespace {
+    fn from(namespace: NamespaceV0) -> Self {
+        Self(namespace.get())
+    }
+}
+
+impl From<Namespace> for HexNamespace {
+    fn from(namespace: Namespace) -> Self {
+        Self(namespace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequencer_namespace_is_derived_from_chain_id_without_base() {
+        assert_eq!(
+            namespace_v0_from_sha256_of_bytes("astria"),
+            sequencer_namespace("astria", None),
+        );
+    }
+
+    #[test]
+    fn sequencer_namespace_is_base_if_set() {
+        let base = namespace_v0_from_hex("00112233445566778899").unwrap();
+        assert_eq!(base, sequencer_namespace("astria", Some(base)));
+    }
+
+    #[test]
+    fn rollup_result_namespace_differs_from_rollup_namespace() {
+        let rollup_id = crate::primitive::v1::RollupId::new([1; 32]);
+        assert_ne!(
+            namespace_v0_from_rollup_id(rollup_id),
+            rollup_result_namespace(rollup_id),
+        );
+    }
+
+    #[test]
+    fn hex_namespace_roundtrips_through_display() {
+        let namespace: HexNamespace = "0x00112233445566778899".parse().unwrap();
+        assert_eq!("0x00112233445566778899", namespace.to_string());
+        assert_eq!(
+            namespace_v0_from_hex("00112233445566778899").unwrap(),
+            namespace.0
+        );
+    }
+
+    #[test]
+    fn namespace_with_wrong_length_is_rejected() {
+        assert!(matches!(
+            namespace_v0_from_hex("0011"),
+            Err(ParseNamespaceError::Invalid(NamespaceError::Length {
+                expected: 10,
+                actual: 2,
+            }))
+        ));
+    }
+
+    #[test]
+    fn reserved_namespace_is_rejected() {
+        assert_eq!(Err(NamespaceError::Reserved), NamespaceV0::new([0; 10]));
+        assert_eq!(
+            Err(NamespaceError::Reserved),
+            NamespaceV0::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff])
+        );
+        assert!(matches!(
+            namespace_v0_from_hex("0x00000000000000000001"),
+            Err(ParseNamespaceError::Invalid(NamespaceError::Reserved))
+        ));
+        assert_eq!(
+            Ok(NamespaceV0::MIN),
+            NamespaceV0::new([0, 0, 0, 0, 0, 0, 0, 0, 1, 0])
+        );
+    }
+
+    #[test]
+    fn namespace_v0_roundtrips_through_celestia_namespace() {
+        let namespace = NamespaceV0::new([1; 10]).unwrap();
+        assert_eq!(namespace_v0_from_first_10_bytes(&[1; 10]), namespace.get());
+        assert_eq!(Ok(namespace), NamespaceV0::try_from(namespace.get()));
+        assert_eq!(
+            Err(NamespaceError::Reserved),
+            NamespaceV0::try_from(Namespace::TRANSACTION)
+        );
+        assert_eq!(
+            Err(NamespaceError::Version(255)),
+            NamespaceV0::try_from(Namespace::PARITY_SHARE)
+        );
+    }
+
+    #[test]
+    fn namespace_v0_roundtrips_through_display() {
+        let namespace: NamespaceV0 = "0x00112233445566778899".parse().unwrap();
+        assert_eq!("0x00112233445566778899", namespace.to_string());
+        assert_eq!(
+            namespace,
+            NamespaceV0::try_from_slice(&hex::decode("00112233445566778899").unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Conversions between the `CometBFT` types of `tendermint-rs` and their protobuf representation.
+//!
+//! Astria's protobuf schemas refer to the `CometBFT` messages vendored under
+//! `astria_vendored.tendermint.types`, which are generated as the types of `tendermint-proto`.
+//! Implementing [`Protobuf`] for the native `tendermint-rs` types lets consumers move between
+//! the two without writing lossy conversions by hand.
+
+use tendermint::block::{
+    Commit,
+    Header,
+};
+use tendermint_proto::types as raw;
+
+use crate::Protobuf;
+
+/// An error when converting a raw `CometBFT` protobuf message to its native type.
+#[derive(Debug, thiserror::Error)]
+#[error("failed converting raw `CometBFT` {type_name} to its native type")]
+pub struct CometBftConversionError {
+    type_name: &'static str,
+    #[source]
+    source: tendermint::Error,
+}
+
+impl CometBftConversionError {
+    fn header(source: tendermint::Error) -> Self {
+        Self {
+            type_name: "header",
+            source,
+        }
+    }
+
+    fn commit(source: tendermint::Error) -> Self {
+        Self {
+            type_name: "commit",
+            source,
+        }
+    }
+}
+
+impl Protobuf for Header {
+    type Error = CometBftConversionError;
+    type Raw = raw::Header;
+
+    fn try_from_raw_ref(raw: &Self::Raw) -> Result<Self, Self::Error> {
+        Self::try_from_raw(raw.clone())
+    }
+
+    fn try_from_raw(raw: Self::Raw) -> Result<Self, Self::Error> {
+        Self::try_from(raw).map_err(CometBftConversionError::header)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.clone().into_raw()
+    }
+
+    fn into_raw(self) -> Self::Raw {
+        self.into()
+    }
+}
+
+impl Protobuf for Commit {
+    type Error = CometBftConversionError;
+    type Raw = raw::Commit;
+
+    fn try_from_raw_ref(raw: &Self::Raw) -> Result<Self, Self::Error> {
+        Self::try_from_raw(raw.clone())
+    }
+
+    fn try_from_raw(raw: Self::Raw) -> Result<Self, Self::Error> {
+        Self::try_from(raw).map_err(CometBftConversionError::commit)
+    }
+
+    fn to_raw(&self) -> Self::Raw {
+        self.clone().into_raw()
+    }
+
+    fn into_raw(self) -> Self::Raw {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::{
+        account,
+        block::{
+            self,
+            parts,
+            CommitSig,
+        },
+        chain,
+        AppHash,
+        Hash,
+        Signature,
+        Time,
+    };
+
+    use super::*;
+
+    fn hash(byte: u8) -> Hash {
+        Hash::Sha256([byte; 32])
+    }
+
+    fn block_id() -> block::Id {
+        block::Id {
+            hash: hash(1),
+            part_set_header: parts::Header::new(1, hash(2)).unwrap(),
+        }
+    }
+
+    fn time() -> Time {
+        Time::from_unix_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    fn header() -> Header {
+        Header {
+            version: block::header::Version {
+                block: 11,
+                app: 0,
+            },
+            chain_id: chain::Id::try_from("test").unwrap(),
+            height: block::Height::from(5u32),
+            time: time(),
+            last_block_id: Some(block_id()),
+            last_commit_hash: Some(hash(3)),
+            data_hash: Some(hash(4)),
+            validators_hash: hash(5),
+            next_validators_hash: hash(6),
+            consensus_hash: hash(7),
+            app_hash: AppHash::try_from(vec![8; 32]).unwrap(),
+            last_results_hash: Some(hash(9)),
+            evidence_hash: Some(hash(10)),
+            proposer_address: account::Id::new([11; 20]),
+        }
+    }
+
+    #[test]
+    fn header_roundtrips_through_raw() {
+        let header = header();
+        let raw = header.to_raw();
+        assert_eq!(header, Header::try_from_raw(raw).unwrap());
+    }
+
+    #[test]
+    fn commit_roundtrips_through_raw() {
+        let commit = Commit {
+            height: block::Height::from(5u32),
+            round: block::Round::from(1u8),
+            block_id: block_id(),
+            signatures: vec![
+                CommitSig::BlockIdFlagCommit {
+                    validator_address: account::Id::new([1; 20]),
+                    timestamp: time(),
+                    signature: Signature::new(vec![2; 64]).unwrap(),
+                },
+                CommitSig::BlockIdFlagAbsent,
+            ],
+        };
+        let raw = commit.to_raw();
+        assert_eq!(commit, Commit::try_from_raw(raw).unwrap());
+    }
+
+    #[test]
+    fn raw_header_with_invalid_field_is_rejected() {
+        let raw = raw::Header {
+            proposer_address: vec![1; 3],
+            ..header().into_raw()
+        };
+        assert!(Header::try_from_raw(raw).is_err());
+    }
+}
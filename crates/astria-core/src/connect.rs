@@ -0,0 +1,222 @@
+//! Preconfigured tonic channels for the Astria gRPC services.
+//!
+//! [`ChannelBuilder`] applies the same timeouts and keep-alive settings for every consumer of
+//! the execution and sequencer services. If more than one endpoint is configured, requests are
+//! load balanced across all of them.
+//!
+//! Channels reconnect transparently when a connection to an endpoint is lost. Retrying
+//! individual requests is left to the callers, because only they can tell whether a request is
+//! safe to repeat.
+
+use std::time::Duration;
+
+use tonic::transport::{
+    Channel,
+    Endpoint,
+};
+
+use crate::generated::{
+    execution::v1alpha2::execution_service_client::ExecutionServiceClient,
+    sequencerblock::v1alpha1::sequencer_service_client::SequencerServiceClient,
+};
+
+/// The default time to wait for a connection to an endpoint to be established.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The default time to wait for the response to a request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default interval of HTTP2 keep-alive pings.
+pub const DEFAULT_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// An error when building a [`Channel`].
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct ConnectError(ConnectErrorKind);
+
+impl ConnectError {
+    fn no_endpoints() -> Self {
+        Self(ConnectErrorKind::NoEndpoints)
+    }
+
+    fn invalid_endpoint(endpoint: String, source: tonic::transport::Error) -> Self {
+        Self(ConnectErrorKind::InvalidEndpoint {
+            endpoint,
+            source,
+        })
+    }
+
+    fn connect(source: tonic::transport::Error) -> Self {
+        Self(ConnectErrorKind::Connect(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ConnectErrorKind {
+    #[error("at least one endpoint must be provided")]
+    NoEndpoints,
+    #[error("failed parsing `{endpoint}` as an endpoint")]
+    InvalidEndpoint {
+        endpoint: String,
+        source: tonic::transport::Error,
+    },
+    #[error("failed connecting to the endpoint")]
+    Connect(#[source] tonic::transport::Error),
+}
+
+/// Builds a [`Channel`] to one or more endpoints of a gRPC service.
+#[derive(Debug)]
+pub struct ChannelBuilder {
+    endpoints: Vec<String>,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    keep_alive_interval: Duration,
+}
+
+impl ChannelBuilder {
+    #[must_use = "a channel builder must be built to be useful"]
+    pub fn new() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            keep_alive_interval: DEFAULT_KEEP_ALIVE_INTERVAL,
+        }
+    }
+
+    /// Adds `endpoint` to the endpoints that requests are balanced across.
+    #[must_use]
+    pub fn endpoint<T: Into<String>>(mut self, endpoint: T) -> Self {
+        self.endpoints.push(endpoint.into());
+        self
+    }
+
+    /// Adds `endpoints` to the endpoints that requests are balanced across.
+    #[must_use]
+    pub fn endpoints<I, T>(mut self, endpoints: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.endpoints.extend(endpoints.into_iter().map(Into::into));
+        self
+    }
+
+    #[must_use]
+    pub fn connect_timeout(self, connect_timeout: Duration) -> Self {
+        Self {
+            connect_timeout,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn request_timeout(self, request_timeout: Duration) -> Self {
+        Self {
+            request_timeout,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn keep_alive_interval(self, keep_alive_interval: Duration) -> Self {
+        Self {
+            keep_alive_interval,
+            ..self
+        }
+    }
+
+    /// Builds a channel that connects on its first request.
+    ///
+    /// Must be called from within a tokio runtime if more than one endpoint was provided.
+    ///
+    /// # Errors
+    /// Returns an error if no endpoints were provided or if an endpoint could not be parsed.
+    pub fn connect_lazy(self) -> Result<Channel, ConnectError> {
+        let mut endpoints = self.configured_endpoints()?;
+        if endpoints.len() == 1 {
+            let endpoint = endpoints.pop().expect("checked to contain exactly one endpoint");
+            return Ok(endpoint.connect_lazy());
+        }
+        Ok(Channel::balance_list(endpoints.into_iter()))
+    }
+
+    /// Builds a channel and, for a single endpoint, connects to it immediately.
+    ///
+    /// Balanced channels connect to their endpoints in the background, so this is equivalent to
+    /// [`ChannelBuilder::connect_lazy`] if more than one endpoint was provided.
+    ///
+    /// # Errors
+    /// Returns an error if no endpoints were provided, if an endpoint could not be parsed, or if
+    /// connecting to a single endpoint failed.
+    pub async fn connect(self) -> Result<Channel, ConnectError> {
+        let mut endpoints = self.configured_endpoints()?;
+        if endpoints.len() == 1 {
+            let endpoint = endpoints.pop().expect("checked to contain exactly one endpoint");
+            return endpoint.connect().await.map_err(ConnectError::connect);
+        }
+        Ok(Channel::balance_list(endpoints.into_iter()))
+    }
+
+    fn configured_endpoints(self) -> Result<Vec<Endpoint>, ConnectError> {
+        let Self {
+            endpoints,
+            connect_timeout,
+            request_timeout,
+            keep_alive_interval,
+        } = self;
+        if endpoints.is_empty() {
+            return Err(ConnectError::no_endpoints());
+        }
+        endpoints
+            .into_iter()
+            .map(|endpoint| {
+                Endpoint::from_shared(endpoint.clone())
+                    .map(|parsed| {
+                        parsed
+                            .connect_timeout(connect_timeout)
+                            .timeout(request_timeout)
+                            .http2_keep_alive_interval(keep_alive_interval)
+                            .keep_alive_while_idle(true)
+                    })
+                    .map_err(|source| ConnectError::invalid_endpoint(endpoint, source))
+            })
+            .collect()
+    }
+}
+
+impl Default for ChannelBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Constructs a lazily connecting client of the rollup execution service at `endpoints`.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`ChannelBuilder::connect_lazy`].
+pub fn execution_service_client<I, T>(
+    endpoints: I,
+) -> Result<ExecutionServiceClient<Channel>, ConnectError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<String>,
+{
+    let channel = ChannelBuilder::new().endpoints(endpoints).connect_lazy()?;
+    Ok(ExecutionServiceClient::new(channel))
+}
+
+/// Constructs a lazily connecting client of the sequencer service at `endpoints`.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`ChannelBuilder::connect_lazy`].
+pub fn sequencer_service_client<I, T>(
+    endpoints: I,
+) -> Result<SequencerServiceClient<Channel>, ConnectError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<String>,
+{
+    let channel = ChannelBuilder::new().endpoints(endpoints).connect_lazy()?;
+    Ok(SequencerServiceClient::new(channel))
+}
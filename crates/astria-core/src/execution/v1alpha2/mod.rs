@@ -1,3 +1,12 @@
+mod request;
+
+pub use request::{
+    BatchGetBlocksRequestBuilder,
+    ExecuteBlockRequestBuilder,
+    GetBlockRequestBuilder,
+    RequestBuilderError,
+};
+
 use bytes::Bytes;
 use pbjson_types::Timestamp;
 
@@ -0,0 +1,274 @@
+//! Builders for the requests of the `astria.execution.v1alpha2.ExecutionService`.
+//!
+//! The generated request types nest their required fields in `Option`s. The builders in this
+//! module check that all required fields are set before a request is sent to a rollup.
+
+use bytes::Bytes;
+use pbjson_types::Timestamp;
+
+use super::CommitmentState;
+use crate::{
+    generated::{
+        execution::v1alpha2::{
+            self as raw,
+            block_identifier::Identifier,
+        },
+        sequencerblock::v1alpha1::RollupData,
+    },
+    Protobuf as _,
+};
+
+/// An error when building an execution service request.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct RequestBuilderError(RequestBuilderErrorKind);
+
+impl RequestBuilderError {
+    fn field_not_set(field: &'static str) -> Self {
+        Self(RequestBuilderErrorKind::FieldNotSet(field))
+    }
+
+    fn no_identifiers() -> Self {
+        Self(RequestBuilderErrorKind::NoIdentifiers)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum RequestBuilderErrorKind {
+    #[error("{0} field not set")]
+    FieldNotSet(&'static str),
+    #[error("at least one block identifier must be provided")]
+    NoIdentifiers,
+}
+
+impl raw::BlockIdentifier {
+    /// Identifies a block by its number.
+    #[must_use]
+    pub fn by_number(number: u32) -> Self {
+        Self {
+            identifier: Some(Identifier::BlockNumber(number)),
+        }
+    }
+
+    /// Identifies a block by its hash.
+    #[must_use]
+    pub fn by_hash(hash: Bytes) -> Self {
+        Self {
+            identifier: Some(Identifier::BlockHash(hash)),
+        }
+    }
+}
+
+impl raw::GetBlockRequest {
+    #[must_use = "a get block request must be built to be useful"]
+    pub fn builder() -> GetBlockRequestBuilder {
+        GetBlockRequestBuilder::default()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GetBlockRequestBuilder {
+    identifier: Option<raw::BlockIdentifier>,
+}
+
+impl GetBlockRequestBuilder {
+    #[must_use]
+    pub fn block_number(self, number: u32) -> Self {
+        Self {
+            identifier: Some(raw::BlockIdentifier::by_number(number)),
+        }
+    }
+
+    #[must_use]
+    pub fn block_hash(self, hash: Bytes) -> Self {
+        Self {
+            identifier: Some(raw::BlockIdentifier::by_hash(hash)),
+        }
+    }
+
+    /// Finalize the request.
+    ///
+    /// # Errors
+    /// Returns an error if neither a block number nor a block hash was set.
+    pub fn build(self) -> Result<raw::GetBlockRequest, RequestBuilderError> {
+        let Some(identifier) = self.identifier else {
+            return Err(RequestBuilderError::field_not_set(".identifier"));
+        };
+        Ok(raw::GetBlockRequest {
+            identifier: Some(identifier),
+        })
+    }
+}
+
+impl raw::BatchGetBlocksRequest {
+    #[must_use = "a batch get blocks request must be built to be useful"]
+    pub fn builder() -> BatchGetBlocksRequestBuilder {
+        BatchGetBlocksRequestBuilder::default()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BatchGetBlocksRequestBuilder {
+    identifiers: Vec<raw::BlockIdentifier>,
+}
+
+impl BatchGetBlocksRequestBuilder {
+    /// Requests the blocks at `numbers`, after all previously requested blocks.
+    #[must_use]
+    pub fn block_numbers<I: IntoIterator<Item = u32>>(mut self, numbers: I) -> Self {
+        self.identifiers
+            .extend(numbers.into_iter().map(raw::BlockIdentifier::by_number));
+        self
+    }
+
+    /// Requests the blocks with `hashes`, after all previously requested blocks.
+    #[must_use]
+    pub fn block_hashes<I: IntoIterator<Item = Bytes>>(mut self, hashes: I) -> Self {
+        self.identifiers
+            .extend(hashes.into_iter().map(raw::BlockIdentifier::by_hash));
+        self
+    }
+
+    /// Finalize the request.
+    ///
+    /// # Errors
+    /// Returns an error if no blocks were requested.
+    pub fn build(self) -> Result<raw::BatchGetBlocksRequest, RequestBuilderError> {
+        if self.identifiers.is_empty() {
+            return Err(RequestBuilderError::no_identifiers());
+        }
+        Ok(raw::BatchGetBlocksRequest {
+            identifiers: self.identifiers,
+        })
+    }
+}
+
+impl raw::ExecuteBlockRequest {
+    #[must_use = "an execute block request must be built to be useful"]
+    pub fn builder() -> ExecuteBlockRequestBuilder {
+        ExecuteBlockRequestBuilder::default()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExecuteBlockRequestBuilder {
+    prev_block_hash: Option<Bytes>,
+    transactions: Vec<RollupData>,
+    timestamp: Option<Timestamp>,
+}
+
+impl ExecuteBlockRequestBuilder {
+    #[must_use]
+    pub fn prev_block_hash(self, prev_block_hash: Bytes) -> Self {
+        Self {
+            prev_block_hash: Some(prev_block_hash),
+            ..self
+        }
+    }
+
+    /// Appends `transactions` to the transactions of the block.
+    #[must_use]
+    pub fn transactions<I: IntoIterator<Item = RollupData>>(mut self, transactions: I) -> Self {
+        self.transactions.extend(transactions);
+        self
+    }
+
+    #[must_use]
+    pub fn timestamp(self, timestamp: Timestamp) -> Self {
+        Self {
+            timestamp: Some(timestamp),
+            ..self
+        }
+    }
+
+    /// Finalize the request.
+    ///
+    /// # Errors
+    /// Returns an error if the hash of the parent block or the timestamp was not set.
+    pub fn build(self) -> Result<raw::ExecuteBlockRequest, RequestBuilderError> {
+        let Self {
+            prev_block_hash,
+            transactions,
+            timestamp,
+        } = self;
+        let Some(prev_block_hash) = prev_block_hash else {
+            return Err(RequestBuilderError::field_not_set(".prev_block_hash"));
+        };
+        let Some(timestamp) = timestamp else {
+            return Err(RequestBuilderError::field_not_set(".timestamp"));
+        };
+        Ok(raw::ExecuteBlockRequest {
+            prev_block_hash,
+            transactions,
+            timestamp: Some(timestamp),
+        })
+    }
+}
+
+impl From<CommitmentState> for raw::UpdateCommitmentStateRequest {
+    fn from(value: CommitmentState) -> Self {
+        Self {
+            commitment_state: Some(value.into_raw()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_block_request_requires_identifier() {
+        assert!(raw::GetBlockRequest::builder().build().is_err());
+        let request = raw::GetBlockRequest::builder()
+            .block_number(3)
+            .build()
+            .unwrap();
+        assert_eq!(Some(raw::BlockIdentifier::by_number(3)), request.identifier);
+    }
+
+    #[test]
+    fn batch_get_blocks_request_keeps_identifier_order() {
+        assert!(raw::BatchGetBlocksRequest::builder().build().is_err());
+        let request = raw::BatchGetBlocksRequest::builder()
+            .block_numbers(1..=2)
+            .block_hashes([Bytes::from_static(&[3])])
+            .build()
+            .unwrap();
+        assert_eq!(
+            vec![
+                raw::BlockIdentifier::by_number(1),
+                raw::BlockIdentifier::by_number(2),
+                raw::BlockIdentifier::by_hash(Bytes::from_static(&[3])),
+            ],
+            request.identifiers,
+        );
+    }
+
+    #[test]
+    fn execute_block_request_requires_prev_block_hash_and_timestamp() {
+        let timestamp = Timestamp {
+            seconds: 1,
+            nanos: 0,
+        };
+        assert!(
+            raw::ExecuteBlockRequest::builder()
+                .timestamp(timestamp.clone())
+                .build()
+                .is_err()
+        );
+        assert!(
+            raw::ExecuteBlockRequest::builder()
+                .prev_block_hash(Bytes::from_static(&[1]))
+                .build()
+                .is_err()
+        );
+        let request = raw::ExecuteBlockRequest::builder()
+            .prev_block_hash(Bytes::from_static(&[1]))
+            .timestamp(timestamp.clone())
+            .build()
+            .unwrap();
+        assert_eq!(Some(timestamp), request.timestamp);
+        assert!(request.transactions.is_empty());
+    }
+}
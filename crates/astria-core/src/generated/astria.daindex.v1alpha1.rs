@@ -0,0 +1,826 @@
+/// A sequencer block as recorded in the index of sequencer blocks written to Celestia.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndexedSequencerBlock {
+    /// The 32 byte hash of the sequencer block.
+    #[prost(bytes = "vec", tag = "1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The height of the sequencer block.
+    #[prost(uint64, tag = "2")]
+    pub sequencer_height: u64,
+    /// The CometBFT chain ID of the sequencer network.
+    #[prost(string, tag = "3")]
+    pub chain_id: ::prost::alloc::string::String,
+    /// The Celestia height at which the sequencer blob was written.
+    #[prost(uint64, tag = "4")]
+    pub celestia_height: u64,
+    /// The 29 byte Celestia namespace under which the sequencer blob was written.
+    #[prost(bytes = "vec", tag = "5")]
+    pub namespace: ::prost::alloc::vec::Vec<u8>,
+    /// The 29 byte Celestia namespaces of the rollups with transactions in the sequencer block.
+    #[prost(bytes = "vec", repeated, tag = "6")]
+    pub rollup_namespaces: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// The size of the sequencer blob as stored on Celestia, in bytes.
+    #[prost(uint64, tag = "7")]
+    pub size: u64,
+    /// The address of the validator that proposed the sequencer block.
+    #[prost(bytes = "vec", tag = "8")]
+    pub proposer: ::prost::alloc::vec::Vec<u8>,
+}
+impl ::prost::Name for IndexedSequencerBlock {
+    const NAME: &'static str = "IndexedSequencerBlock";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlocksBySequencerHeightRequest {
+    /// The sequencer height to look up.
+    #[prost(uint64, tag = "1")]
+    pub height: u64,
+}
+impl ::prost::Name for GetBlocksBySequencerHeightRequest {
+    const NAME: &'static str = "GetBlocksBySequencerHeightRequest";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlocksBySequencerHeightResponse {
+    /// The sequencer blocks at the requested height. Contains more than one block if
+    /// blocks of several sequencer networks were indexed.
+    #[prost(message, repeated, tag = "1")]
+    pub blocks: ::prost::alloc::vec::Vec<IndexedSequencerBlock>,
+}
+impl ::prost::Name for GetBlocksBySequencerHeightResponse {
+    const NAME: &'static str = "GetBlocksBySequencerHeightResponse";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockByHashRequest {
+    /// The 32 byte hash of the sequencer block to look up.
+    #[prost(bytes = "vec", tag = "1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+}
+impl ::prost::Name for GetBlockByHashRequest {
+    const NAME: &'static str = "GetBlockByHashRequest";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlocksByNamespaceRequest {
+    /// The 29 byte Celestia namespace to look up.
+    #[prost(bytes = "vec", tag = "1")]
+    pub namespace: ::prost::alloc::vec::Vec<u8>,
+    /// The maximum number of blocks to return. Limited by the server.
+    #[prost(uint32, tag = "2")]
+    pub limit: u32,
+}
+impl ::prost::Name for GetBlocksByNamespaceRequest {
+    const NAME: &'static str = "GetBlocksByNamespaceRequest";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlocksByNamespaceResponse {
+    /// The sequencer blocks written under or containing transactions of the requested
+    /// namespace, ordered by descending sequencer height.
+    #[prost(message, repeated, tag = "1")]
+    pub blocks: ::prost::alloc::vec::Vec<IndexedSequencerBlock>,
+}
+impl ::prost::Name for GetBlocksByNamespaceResponse {
+    const NAME: &'static str = "GetBlocksByNamespaceResponse";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamRecentBlocksRequest {
+    /// The number of most recently indexed blocks to send before following newly
+    /// indexed blocks. Limited by the server.
+    #[prost(uint32, tag = "1")]
+    pub limit: u32,
+}
+impl ::prost::Name for StreamRecentBlocksRequest {
+    const NAME: &'static str = "StreamRecentBlocksRequest";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamSequencerBlocksRequest {
+    /// The sequencer height of the first block to send.
+    #[prost(uint64, tag = "1")]
+    pub start_height: u64,
+    /// The CometBFT chain ID of the sequencer network whose blocks are sent.
+    #[prost(string, tag = "2")]
+    pub chain_id: ::prost::alloc::string::String,
+}
+impl ::prost::Name for StreamSequencerBlocksRequest {
+    const NAME: &'static str = "StreamSequencerBlocksRequest";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+/// A sequencer block reconstructed from the blobs written to Celestia.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReconstructedSequencerBlock {
+    /// The Celestia height at which the blobs of the sequencer block were written.
+    #[prost(uint64, tag = "1")]
+    pub celestia_height: u64,
+    /// The sequencer blob of the block.
+    #[prost(message, optional, tag = "2")]
+    pub sequencer_blob: ::core::option::Option<
+        super::super::sequencerblock::v1alpha1::CelestiaSequencerBlob,
+    >,
+    /// The rollup blobs of the block, verified against the sequencer blob.
+    #[prost(message, repeated, tag = "3")]
+    pub rollup_blobs: ::prost::alloc::vec::Vec<
+        super::super::sequencerblock::v1alpha1::CelestiaRollupBlob,
+    >,
+}
+impl ::prost::Name for ReconstructedSequencerBlock {
+    const NAME: &'static str = "ReconstructedSequencerBlock";
+    const PACKAGE: &'static str = "astria.daindex.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.daindex.v1alpha1.{}", Self::NAME)
+    }
+}
+/// Generated client implementations.
+#[cfg(feature = "client")]
+pub mod da_index_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// A read-only service over the index of sequencer blocks written to Celestia, so that
+    /// block explorers and bridges can query Astria's data availability history.
+    #[derive(Debug, Clone)]
+    pub struct DaIndexServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl DaIndexServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> DaIndexServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> DaIndexServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            DaIndexServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Returns the sequencer blocks at a sequencer height.
+        pub async fn get_blocks_by_sequencer_height(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBlocksBySequencerHeightRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlocksBySequencerHeightResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/astria.daindex.v1alpha1.DaIndexService/GetBlocksBySequencerHeight",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("astria.daindex.v1alpha1.DaIndexService", "GetBlocksBySequencerHeight"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Returns the sequencer block with a block hash.
+        pub async fn get_block_by_hash(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBlockByHashRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::IndexedSequencerBlock>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/astria.daindex.v1alpha1.DaIndexService/GetBlockByHash",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("astria.daindex.v1alpha1.DaIndexService", "GetBlockByHash"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Returns the sequencer blocks related to a Celestia namespace.
+        pub async fn get_blocks_by_namespace(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBlocksByNamespaceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlocksByNamespaceResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/astria.daindex.v1alpha1.DaIndexService/GetBlocksByNamespace",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("astria.daindex.v1alpha1.DaIndexService", "GetBlocksByNamespace"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Streams the most recently indexed sequencer blocks, followed by blocks as they are indexed.
+        pub async fn stream_recent_blocks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamRecentBlocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::IndexedSequencerBlock>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/astria.daindex.v1alpha1.DaIndexService/StreamRecentBlocks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("astria.daindex.v1alpha1.DaIndexService", "StreamRecentBlocks"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Streams the sequencer blocks of a sequencer network reconstructed from Celestia, starting
+        /// at a sequencer height and following newly indexed blocks.
+        pub async fn stream_sequencer_blocks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamSequencerBlocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ReconstructedSequencerBlock>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/astria.daindex.v1alpha1.DaIndexService/StreamSequencerBlocks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "astria.daindex.v1alpha1.DaIndexService",
+                        "StreamSequencerBlocks",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+#[cfg(feature = "server")]
+pub mod da_index_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with DaIndexServiceServer.
+    #[async_trait]
+    pub trait DaIndexService: Send + Sync + 'static {
+        /// Returns the sequencer blocks at a sequencer height.
+        async fn get_blocks_by_sequencer_height(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<super::GetBlocksBySequencerHeightRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlocksBySequencerHeightResponse>,
+            tonic::Status,
+        >;
+        /// Returns the sequencer block with a block hash.
+        async fn get_block_by_hash(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<super::GetBlockByHashRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::IndexedSequencerBlock>,
+            tonic::Status,
+        >;
+        /// Returns the sequencer blocks related to a Celestia namespace.
+        async fn get_blocks_by_namespace(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<super::GetBlocksByNamespaceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlocksByNamespaceResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamRecentBlocks method.
+        type StreamRecentBlocksStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::IndexedSequencerBlock, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Streams the most recently indexed sequencer blocks, followed by blocks as they are indexed.
+        async fn stream_recent_blocks(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<super::StreamRecentBlocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamRecentBlocksStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamSequencerBlocks method.
+        type StreamSequencerBlocksStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::ReconstructedSequencerBlock,
+                    tonic::Status,
+                >,
+            >
+            + Send
+            + 'static;
+        /// Streams the sequencer blocks of a sequencer network reconstructed from Celestia, starting
+        /// at a sequencer height and following newly indexed blocks.
+        async fn stream_sequencer_blocks(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<super::StreamSequencerBlocksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamSequencerBlocksStream>,
+            tonic::Status,
+        >;
+    }
+    /// A read-only service over the index of sequencer blocks written to Celestia, so that
+    /// block explorers and bridges can query Astria's data availability history.
+    #[derive(Debug)]
+    pub struct DaIndexServiceServer<T: DaIndexService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: DaIndexService> DaIndexServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for DaIndexServiceServer<T>
+    where
+        T: DaIndexService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/astria.daindex.v1alpha1.DaIndexService/GetBlocksBySequencerHeight" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlocksBySequencerHeightSvc<T: DaIndexService>(pub Arc<T>);
+                    impl<
+                        T: DaIndexService,
+                    > tonic::server::UnaryService<super::GetBlocksBySequencerHeightRequest>
+                    for GetBlocksBySequencerHeightSvc<T> {
+                        type Response = super::GetBlocksBySequencerHeightResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlocksBySequencerHeightRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as DaIndexService>::get_blocks_by_sequencer_height(inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlocksBySequencerHeightSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/astria.daindex.v1alpha1.DaIndexService/GetBlockByHash" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlockByHashSvc<T: DaIndexService>(pub Arc<T>);
+                    impl<
+                        T: DaIndexService,
+                    > tonic::server::UnaryService<super::GetBlockByHashRequest>
+                    for GetBlockByHashSvc<T> {
+                        type Response = super::IndexedSequencerBlock;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlockByHashRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as DaIndexService>::get_block_by_hash(inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlockByHashSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/astria.daindex.v1alpha1.DaIndexService/GetBlocksByNamespace" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlocksByNamespaceSvc<T: DaIndexService>(pub Arc<T>);
+                    impl<
+                        T: DaIndexService,
+                    > tonic::server::UnaryService<super::GetBlocksByNamespaceRequest>
+                    for GetBlocksByNamespaceSvc<T> {
+                        type Response = super::GetBlocksByNamespaceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlocksByNamespaceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as DaIndexService>::get_blocks_by_namespace(inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlocksByNamespaceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/astria.daindex.v1alpha1.DaIndexService/StreamRecentBlocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamRecentBlocksSvc<T: DaIndexService>(pub Arc<T>);
+                    impl<
+                        T: DaIndexService,
+                    > tonic::server::ServerStreamingService<super::StreamRecentBlocksRequest>
+                    for StreamRecentBlocksSvc<T> {
+                        type Response = super::IndexedSequencerBlock;
+                        type ResponseStream = T::StreamRecentBlocksStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamRecentBlocksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as DaIndexService>::stream_recent_blocks(inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamRecentBlocksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/astria.daindex.v1alpha1.DaIndexService/StreamSequencerBlocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamSequencerBlocksSvc<T: DaIndexService>(pub Arc<T>);
+                    impl<
+                        T: DaIndexService,
+                    > tonic::server::ServerStreamingService<
+                        super::StreamSequencerBlocksRequest,
+                    > for StreamSequencerBlocksSvc<T> {
+                        type Response = super::ReconstructedSequencerBlock;
+                        type ResponseStream = T::StreamSequencerBlocksStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamSequencerBlocksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as DaIndexService>::stream_sequencer_blocks(
+                                        inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamSequencerBlocksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: DaIndexService> Clone for DaIndexServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: DaIndexService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: DaIndexService> tonic::server::NamedService for DaIndexServiceServer<T> {
+        const NAME: &'static str = "astria.daindex.v1alpha1.DaIndexService";
+    }
+}
@@ -205,6 +205,66 @@ impl ::prost::Name for UpdateCommitmentStateRequest {
         ::prost::alloc::format!("astria.execution.v1alpha2.{}", Self::NAME)
     }
 }
+/// A request sent by the conductor over the StreamExecuteBlocks stream.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamExecuteBlocksRequest {
+    /// Chosen by the conductor and echoed in the response to this request.
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(oneof = "stream_execute_blocks_request::Value", tags = "2, 3")]
+    pub value: ::core::option::Option<stream_execute_blocks_request::Value>,
+}
+/// Nested message and enum types in `StreamExecuteBlocksRequest`.
+pub mod stream_execute_blocks_request {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        /// Execute a block, answered with the executed block.
+        #[prost(message, tag = "2")]
+        ExecuteBlock(super::ExecuteBlockRequest),
+        /// Update the commitment state, answered with the new commitment state.
+        #[prost(message, tag = "3")]
+        UpdateCommitmentState(super::UpdateCommitmentStateRequest),
+    }
+}
+impl ::prost::Name for StreamExecuteBlocksRequest {
+    const NAME: &'static str = "StreamExecuteBlocksRequest";
+    const PACKAGE: &'static str = "astria.execution.v1alpha2";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.execution.v1alpha2.{}", Self::NAME)
+    }
+}
+/// A response sent by the execution node over the StreamExecuteBlocks stream.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamExecuteBlocksResponse {
+    /// The sequence of the request this is a response to.
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(oneof = "stream_execute_blocks_response::Value", tags = "2, 3")]
+    pub value: ::core::option::Option<stream_execute_blocks_response::Value>,
+}
+/// Nested message and enum types in `StreamExecuteBlocksResponse`.
+pub mod stream_execute_blocks_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        /// The block derived from an ExecuteBlockRequest.
+        #[prost(message, tag = "2")]
+        ExecutedBlock(super::Block),
+        /// The commitment state after an UpdateCommitmentStateRequest.
+        #[prost(message, tag = "3")]
+        CommitmentState(super::CommitmentState),
+    }
+}
+impl ::prost::Name for StreamExecuteBlocksResponse {
+    const NAME: &'static str = "StreamExecuteBlocksResponse";
+    const PACKAGE: &'static str = "astria.execution.v1alpha2";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.execution.v1alpha2.{}", Self::NAME)
+    }
+}
 /// Generated client implementations.
 #[cfg(feature = "client")]
 pub mod execution_service_client {
@@ -476,6 +536,42 @@ pub mod execution_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// StreamExecuteBlocks executes blocks and updates the commitment state over a
+        /// single bidirectional stream, avoiding the overhead of a unary call per
+        /// block. Requests are handled in the order they are sent, and every request
+        /// is answered with a response carrying the same sequence.
+        pub async fn stream_execute_blocks(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<
+                Message = super::StreamExecuteBlocksRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::StreamExecuteBlocksResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/astria.execution.v1alpha2.ExecutionService/StreamExecuteBlocks",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "astria.execution.v1alpha2.ExecutionService",
+                        "StreamExecuteBlocks",
+                    ),
+                );
+            self.inner.streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -522,6 +618,26 @@ pub mod execution_service_server {
             self: std::sync::Arc<Self>,
             request: tonic::Request<super::UpdateCommitmentStateRequest>,
         ) -> std::result::Result<tonic::Response<super::CommitmentState>, tonic::Status>;
+        /// Server streaming response type for the StreamExecuteBlocks method.
+        type StreamExecuteBlocksStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::StreamExecuteBlocksResponse,
+                    tonic::Status,
+                >,
+            >
+            + Send
+            + 'static;
+        /// StreamExecuteBlocks executes blocks and updates the commitment state over a
+        /// single bidirectional stream, avoiding the overhead of a unary call per
+        /// block. Requests are handled in the order they are sent, and every request
+        /// is answered with a response carrying the same sequence.
+        async fn stream_execute_blocks(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<tonic::Streaming<super::StreamExecuteBlocksRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamExecuteBlocksStream>,
+            tonic::Status,
+        >;
     }
     /// ExecutionService is used to drive deterministic production of blocks.
     ///
@@ -893,6 +1009,59 @@ pub mod execution_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/astria.execution.v1alpha2.ExecutionService/StreamExecuteBlocks" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamExecuteBlocksSvc<T: ExecutionService>(pub Arc<T>);
+                    impl<
+                        T: ExecutionService,
+                    > tonic::server::StreamingService<super::StreamExecuteBlocksRequest>
+                    for StreamExecuteBlocksSvc<T> {
+                        type Response = super::StreamExecuteBlocksResponse;
+                        type ResponseStream = T::StreamExecuteBlocksStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::StreamExecuteBlocksRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ExecutionService>::stream_execute_blocks(
+                                        inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamExecuteBlocksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -1066,6 +1066,268 @@ impl<'de> serde::Deserialize<'de> for GetGenesisInfoRequest {
         deserializer.deserialize_struct("astria.execution.v1alpha2.GetGenesisInfoRequest", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for StreamExecuteBlocksRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sequence != 0 {
+            len += 1;
+        }
+        if self.value.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("astria.execution.v1alpha2.StreamExecuteBlocksRequest", len)?;
+        if self.sequence != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("sequence", ToString::to_string(&self.sequence).as_str())?;
+        }
+        if let Some(v) = self.value.as_ref() {
+            match v {
+                stream_execute_blocks_request::Value::ExecuteBlock(v) => {
+                    struct_ser.serialize_field("execute_block", v)?;
+                }
+                stream_execute_blocks_request::Value::UpdateCommitmentState(v) => {
+                    struct_ser.serialize_field("update_commitment_state", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for StreamExecuteBlocksRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sequence",
+            "execute_block",
+            "executeBlock",
+            "update_commitment_state",
+            "updateCommitmentState",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sequence,
+            ExecuteBlock,
+            UpdateCommitmentState,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sequence" => Ok(GeneratedField::Sequence),
+                            "executeBlock" | "execute_block" => Ok(GeneratedField::ExecuteBlock),
+                            "updateCommitmentState" | "update_commitment_state" => Ok(GeneratedField::UpdateCommitmentState),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = StreamExecuteBlocksRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct astria.execution.v1alpha2.StreamExecuteBlocksRequest")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<StreamExecuteBlocksRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sequence__ = None;
+                let mut value__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Sequence => {
+                            if sequence__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sequence"));
+                            }
+                            sequence__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExecuteBlock => {
+                            if value__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executeBlock"));
+                            }
+                            value__ = map_.next_value::<::std::option::Option<_>>()?.map(stream_execute_blocks_request::Value::ExecuteBlock)
+;
+                        }
+                        GeneratedField::UpdateCommitmentState => {
+                            if value__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("updateCommitmentState"));
+                            }
+                            value__ = map_.next_value::<::std::option::Option<_>>()?.map(stream_execute_blocks_request::Value::UpdateCommitmentState)
+;
+                        }
+                    }
+                }
+                Ok(StreamExecuteBlocksRequest {
+                    sequence: sequence__.unwrap_or_default(),
+                    value: value__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("astria.execution.v1alpha2.StreamExecuteBlocksRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for StreamExecuteBlocksResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sequence != 0 {
+            len += 1;
+        }
+        if self.value.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("astria.execution.v1alpha2.StreamExecuteBlocksResponse", len)?;
+        if self.sequence != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("sequence", ToString::to_string(&self.sequence).as_str())?;
+        }
+        if let Some(v) = self.value.as_ref() {
+            match v {
+                stream_execute_blocks_response::Value::ExecutedBlock(v) => {
+                    struct_ser.serialize_field("executed_block", v)?;
+                }
+                stream_execute_blocks_response::Value::CommitmentState(v) => {
+                    struct_ser.serialize_field("commitment_state", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for StreamExecuteBlocksResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sequence",
+            "executed_block",
+            "executedBlock",
+            "commitment_state",
+            "commitmentState",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sequence,
+            ExecutedBlock,
+            CommitmentState,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sequence" => Ok(GeneratedField::Sequence),
+                            "executedBlock" | "executed_block" => Ok(GeneratedField::ExecutedBlock),
+                            "commitmentState" | "commitment_state" => Ok(GeneratedField::CommitmentState),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = StreamExecuteBlocksResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct astria.execution.v1alpha2.StreamExecuteBlocksResponse")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<StreamExecuteBlocksResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sequence__ = None;
+                let mut value__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Sequence => {
+                            if sequence__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sequence"));
+                            }
+                            sequence__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExecutedBlock => {
+                            if value__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executedBlock"));
+                            }
+                            value__ = map_.next_value::<::std::option::Option<_>>()?.map(stream_execute_blocks_response::Value::ExecutedBlock)
+;
+                        }
+                        GeneratedField::CommitmentState => {
+                            if value__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("commitmentState"));
+                            }
+                            value__ = map_.next_value::<::std::option::Option<_>>()?.map(stream_execute_blocks_response::Value::CommitmentState)
+;
+                        }
+                    }
+                }
+                Ok(StreamExecuteBlocksResponse {
+                    sequence: sequence__.unwrap_or_default(),
+                    value: value__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("astria.execution.v1alpha2.StreamExecuteBlocksResponse", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for UpdateCommitmentStateRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -235,6 +235,31 @@ pub struct CelestiaRollupBlob {
     /// `astria.sequencer.v1alpha.SequencerBlock.rollup_transactions_proof`.
     #[prost(message, optional, tag = "4")]
     pub proof: ::core::option::Option<super::super::primitive::v1::Proof>,
+    /// The key that signed this blob on behalf of the rollup, encoded as given by
+    /// `signature_scheme`. Empty if the blob is not signed.
+    #[prost(bytes = "vec", tag = "5")]
+    pub verification_key: ::prost::alloc::vec::Vec<u8>,
+    /// The signature over the sequencer block hash, the rollup ID, and the Merkle
+    /// root of the transactions, encoded as given by `signature_scheme`. Empty if
+    /// the blob is not signed.
+    #[prost(bytes = "vec", tag = "6")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// The 32 byte Merkle root over `transactions`, computed the same way as the
+    /// root proven by `proof`. Lets readers detect tampering with the transactions
+    /// before verifying `proof` against the sequencer block. Empty in blobs
+    /// written before the root was added.
+    #[prost(bytes = "vec", tag = "7")]
+    pub rollup_txs_root: ::prost::alloc::vec::Vec<u8>,
+    /// The 4 byte big-endian CRC-32 checksum over `transactions`, each prefixed by
+    /// its length as a big-endian 64 bit integer. Cheap to check, it lets readers
+    /// reject corrupted blobs before computing `rollup_txs_root` or verifying
+    /// `signature`. Empty in blobs written before the checksum was added.
+    #[prost(bytes = "vec", tag = "8")]
+    pub transactions_checksum: ::prost::alloc::vec::Vec<u8>,
+    /// The scheme of `verification_key` and `signature`. Blobs written before the
+    /// scheme was added to the envelope do not set it and are signed with ed25519.
+    #[prost(enumeration = "SignatureScheme", tag = "9")]
+    pub signature_scheme: i32,
 }
 impl ::prost::Name for CelestiaRollupBlob {
     const NAME: &'static str = "CelestiaRollupBlob";
@@ -283,6 +308,89 @@ impl ::prost::Name for CelestiaSequencerBlob {
         ::prost::alloc::format!("astria.sequencerblock.v1alpha1.{}", Self::NAME)
     }
 }
+/// A compact marker for a run of sequencer blocks without rollup transactions.
+///
+/// Relayers configured to do so write a single marker under the sequencer namespace in place
+/// of one `CelestiaSequencerBlob` per empty block. Every entry still carries the header and
+/// proofs of its block so that readers can verify that the block is empty.
+///
+/// The field number is chosen not to overlap with those of `CelestiaSequencerBlob`, so that a
+/// marker never decodes as a valid `CelestiaSequencerBlob` and vice versa.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CelestiaEmptyBlocksMarker {
+    /// The sequencer blobs of the empty blocks, ordered by sequencer height.
+    #[prost(message, repeated, tag = "15")]
+    pub empty_blocks: ::prost::alloc::vec::Vec<CelestiaSequencerBlob>,
+}
+impl ::prost::Name for CelestiaEmptyBlocksMarker {
+    const NAME: &'static str = "CelestiaEmptyBlocksMarker";
+    const PACKAGE: &'static str = "astria.sequencerblock.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.sequencerblock.v1alpha1.{}", Self::NAME)
+    }
+}
+/// Operator defined data that is submitted to celestia alongside a sequencer block.
+///
+/// Auxiliary blobs (for example batch proofs or state diffs) are posted under their own
+/// namespace in the same celestia transaction as the `CelestiaSequencerBlob` and
+/// `CelestiaRollupBlob`s of the sequencer block identified by `sequencer_block_hash`.
+/// Their contents are not committed to by the sequencer block.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CelestiaAuxiliaryBlob {
+    /// The hash of the sequencer block. Must be 32 bytes.
+    #[prost(bytes = "vec", tag = "1")]
+    pub sequencer_block_hash: ::prost::alloc::vec::Vec<u8>,
+    /// An operator defined identifier of the kind of data contained in the blob.
+    #[prost(string, tag = "2")]
+    pub kind: ::prost::alloc::string::String,
+    /// The opaque data of the blob.
+    #[prost(bytes = "vec", tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+impl ::prost::Name for CelestiaAuxiliaryBlob {
+    const NAME: &'static str = "CelestiaAuxiliaryBlob";
+    const PACKAGE: &'static str = "astria.sequencerblock.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.sequencerblock.v1alpha1.{}", Self::NAME)
+    }
+}
+/// The result of executing a rollup block, submitted to celestia by the relayer on behalf of
+/// a rollup's execution node.
+///
+/// Results are written under a namespace derived from the rollup ID (see
+/// `astria_core::celestia::rollup_result_namespace`) so that the state of a rollup can be
+/// checked against data availability alone, without executing its transactions.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RollupResultNamespaceData {
+    /// The hash of the sequencer block the rollup block was derived from. Must be 32 bytes.
+    #[prost(bytes = "vec", tag = "1")]
+    pub sequencer_block_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The rollup that executed the block.
+    #[prost(message, optional, tag = "2")]
+    pub rollup_id: ::core::option::Option<super::super::primitive::v1::RollupId>,
+    /// The number of the executed rollup block.
+    #[prost(uint64, tag = "3")]
+    pub rollup_block_number: u64,
+    /// The hash of the executed rollup block. Must be 32 bytes.
+    #[prost(bytes = "vec", tag = "4")]
+    pub rollup_block_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The state root after executing the rollup block. Must be 32 bytes.
+    #[prost(bytes = "vec", tag = "5")]
+    pub state_root: ::prost::alloc::vec::Vec<u8>,
+    /// The root of the receipts of executing the rollup block. Must be 32 bytes.
+    #[prost(bytes = "vec", tag = "6")]
+    pub receipts_root: ::prost::alloc::vec::Vec<u8>,
+}
+impl ::prost::Name for RollupResultNamespaceData {
+    const NAME: &'static str = "RollupResultNamespaceData";
+    const PACKAGE: &'static str = "astria.sequencerblock.v1alpha1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("astria.sequencerblock.v1alpha1.{}", Self::NAME)
+    }
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetSequencerBlockRequest {
@@ -314,6 +422,41 @@ impl ::prost::Name for GetFilteredSequencerBlockRequest {
         ::prost::alloc::format!("astria.sequencerblock.v1alpha1.{}", Self::NAME)
     }
 }
+/// The signature scheme with which a `CelestiaRollupBlob` was signed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SignatureScheme {
+    /// Not set. Read as `SIGNATURE_SCHEME_ED25519`, the scheme of all blobs
+    /// written before the scheme was added to the envelope.
+    Unspecified = 0,
+    /// ed25519. Verification keys are 32 bytes, signatures 64 bytes.
+    Ed25519 = 1,
+    /// ECDSA over secp256k1 with SHA-256 as the message digest. Verification keys
+    /// are 33 byte compressed SEC1 points, signatures the 64 bytes of `r || s`.
+    Secp256k1 = 2,
+}
+impl SignatureScheme {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SignatureScheme::Unspecified => "SIGNATURE_SCHEME_UNSPECIFIED",
+            SignatureScheme::Ed25519 => "SIGNATURE_SCHEME_ED25519",
+            SignatureScheme::Secp256k1 => "SIGNATURE_SCHEME_SECP256K1",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SIGNATURE_SCHEME_UNSPECIFIED" => Some(Self::Unspecified),
+            "SIGNATURE_SCHEME_ED25519" => Some(Self::Ed25519),
+            "SIGNATURE_SCHEME_SECP256K1" => Some(Self::Secp256k1),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 #[cfg(feature = "client")]
 pub mod sequencer_service_client {
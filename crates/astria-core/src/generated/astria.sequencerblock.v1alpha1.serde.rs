@@ -1,3 +1,227 @@
+impl serde::Serialize for CelestiaAuxiliaryBlob {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.sequencer_block_hash.is_empty() {
+            len += 1;
+        }
+        if !self.kind.is_empty() {
+            len += 1;
+        }
+        if !self.data.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("astria.sequencerblock.v1alpha1.CelestiaAuxiliaryBlob", len)?;
+        if !self.sequencer_block_hash.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("sequencer_block_hash", pbjson::private::base64::encode(&self.sequencer_block_hash).as_str())?;
+        }
+        if !self.kind.is_empty() {
+            struct_ser.serialize_field("kind", &self.kind)?;
+        }
+        if !self.data.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("data", pbjson::private::base64::encode(&self.data).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for CelestiaAuxiliaryBlob {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sequencer_block_hash",
+            "sequencerBlockHash",
+            "kind",
+            "data",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            SequencerBlockHash,
+            Kind,
+            Data,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sequencerBlockHash" | "sequencer_block_hash" => Ok(GeneratedField::SequencerBlockHash),
+                            "kind" => Ok(GeneratedField::Kind),
+                            "data" => Ok(GeneratedField::Data),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CelestiaAuxiliaryBlob;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct astria.sequencerblock.v1alpha1.CelestiaAuxiliaryBlob")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<CelestiaAuxiliaryBlob, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sequencer_block_hash__ = None;
+                let mut kind__ = None;
+                let mut data__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::SequencerBlockHash => {
+                            if sequencer_block_hash__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sequencerBlockHash"));
+                            }
+                            sequencer_block_hash__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Kind => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::Data => {
+                            if data__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("data"));
+                            }
+                            data__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(CelestiaAuxiliaryBlob {
+                    sequencer_block_hash: sequencer_block_hash__.unwrap_or_default(),
+                    kind: kind__.unwrap_or_default(),
+                    data: data__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("astria.sequencerblock.v1alpha1.CelestiaAuxiliaryBlob", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for CelestiaEmptyBlocksMarker {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.empty_blocks.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("astria.sequencerblock.v1alpha1.CelestiaEmptyBlocksMarker", len)?;
+        if !self.empty_blocks.is_empty() {
+            struct_ser.serialize_field("empty_blocks", &self.empty_blocks)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for CelestiaEmptyBlocksMarker {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "empty_blocks",
+            "emptyBlocks",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            EmptyBlocks,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "emptyBlocks" | "empty_blocks" => Ok(GeneratedField::EmptyBlocks),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CelestiaEmptyBlocksMarker;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct astria.sequencerblock.v1alpha1.CelestiaEmptyBlocksMarker")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<CelestiaEmptyBlocksMarker, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut empty_blocks__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::EmptyBlocks => {
+                            if empty_blocks__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("emptyBlocks"));
+                            }
+                            empty_blocks__ = Some(map_.next_value()?);
+                        }
+                    }
+                }
+                Ok(CelestiaEmptyBlocksMarker {
+                    empty_blocks: empty_blocks__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("astria.sequencerblock.v1alpha1.CelestiaEmptyBlocksMarker", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for CelestiaRollupBlob {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -18,6 +242,21 @@ impl serde::Serialize for CelestiaRollupBlob {
         if self.proof.is_some() {
             len += 1;
         }
+        if !self.verification_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        if !self.rollup_txs_root.is_empty() {
+            len += 1;
+        }
+        if !self.transactions_checksum.is_empty() {
+            len += 1;
+        }
+        if self.signature_scheme != 0 {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("astria.sequencerblock.v1alpha1.CelestiaRollupBlob", len)?;
         if !self.sequencer_block_hash.is_empty() {
             #[allow(clippy::needless_borrow)]
@@ -32,6 +271,27 @@ impl serde::Serialize for CelestiaRollupBlob {
         if let Some(v) = self.proof.as_ref() {
             struct_ser.serialize_field("proof", v)?;
         }
+        if !self.verification_key.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("verification_key", pbjson::private::base64::encode(&self.verification_key).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        if !self.rollup_txs_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("rollup_txs_root", pbjson::private::base64::encode(&self.rollup_txs_root).as_str())?;
+        }
+        if !self.transactions_checksum.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("transactions_checksum", pbjson::private::base64::encode(&self.transactions_checksum).as_str())?;
+        }
+        if self.signature_scheme != 0 {
+            let v = SignatureScheme::try_from(self.signature_scheme)
+                .map_err(|_| serde::ser::Error::custom(format!("Invalid variant {}", self.signature_scheme)))?;
+            struct_ser.serialize_field("signature_scheme", &v)?;
+        }
         struct_ser.end()
     }
 }
@@ -48,6 +308,15 @@ impl<'de> serde::Deserialize<'de> for CelestiaRollupBlob {
             "rollupId",
             "transactions",
             "proof",
+            "verification_key",
+            "verificationKey",
+            "signature",
+            "rollup_txs_root",
+            "rollupTxsRoot",
+            "transactions_checksum",
+            "transactionsChecksum",
+            "signature_scheme",
+            "signatureScheme",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -56,6 +325,11 @@ impl<'de> serde::Deserialize<'de> for CelestiaRollupBlob {
             RollupId,
             Transactions,
             Proof,
+            VerificationKey,
+            Signature,
+            RollupTxsRoot,
+            TransactionsChecksum,
+            SignatureScheme,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -81,6 +355,11 @@ impl<'de> serde::Deserialize<'de> for CelestiaRollupBlob {
                             "rollupId" | "rollup_id" => Ok(GeneratedField::RollupId),
                             "transactions" => Ok(GeneratedField::Transactions),
                             "proof" => Ok(GeneratedField::Proof),
+                            "verificationKey" | "verification_key" => Ok(GeneratedField::VerificationKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            "rollupTxsRoot" | "rollup_txs_root" => Ok(GeneratedField::RollupTxsRoot),
+                            "transactionsChecksum" | "transactions_checksum" => Ok(GeneratedField::TransactionsChecksum),
+                            "signatureScheme" | "signature_scheme" => Ok(GeneratedField::SignatureScheme),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -104,6 +383,11 @@ impl<'de> serde::Deserialize<'de> for CelestiaRollupBlob {
                 let mut rollup_id__ = None;
                 let mut transactions__ = None;
                 let mut proof__ = None;
+                let mut verification_key__ = None;
+                let mut signature__ = None;
+                let mut rollup_txs_root__ = None;
+                let mut transactions_checksum__ = None;
+                let mut signature_scheme__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::SequencerBlockHash => {
@@ -135,6 +419,44 @@ impl<'de> serde::Deserialize<'de> for CelestiaRollupBlob {
                             }
                             proof__ = map_.next_value()?;
                         }
+                        GeneratedField::VerificationKey => {
+                            if verification_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("verificationKey"));
+                            }
+                            verification_key__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::RollupTxsRoot => {
+                            if rollup_txs_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("rollupTxsRoot"));
+                            }
+                            rollup_txs_root__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::TransactionsChecksum => {
+                            if transactions_checksum__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionsChecksum"));
+                            }
+                            transactions_checksum__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::SignatureScheme => {
+                            if signature_scheme__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signatureScheme"));
+                            }
+                            signature_scheme__ = Some(map_.next_value::<SignatureScheme>()? as i32);
+                        }
                     }
                 }
                 Ok(CelestiaRollupBlob {
@@ -142,6 +464,11 @@ impl<'de> serde::Deserialize<'de> for CelestiaRollupBlob {
                     rollup_id: rollup_id__,
                     transactions: transactions__.unwrap_or_default(),
                     proof: proof__,
+                    verification_key: verification_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                    rollup_txs_root: rollup_txs_root__.unwrap_or_default(),
+                    transactions_checksum: transactions_checksum__.unwrap_or_default(),
+                    signature_scheme: signature_scheme__.unwrap_or_default(),
                 })
             }
         }
@@ -983,6 +1310,203 @@ impl<'de> serde::Deserialize<'de> for RollupData {
         deserializer.deserialize_struct("astria.sequencerblock.v1alpha1.RollupData", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for RollupResultNamespaceData {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.sequencer_block_hash.is_empty() {
+            len += 1;
+        }
+        if self.rollup_id.is_some() {
+            len += 1;
+        }
+        if self.rollup_block_number != 0 {
+            len += 1;
+        }
+        if !self.rollup_block_hash.is_empty() {
+            len += 1;
+        }
+        if !self.state_root.is_empty() {
+            len += 1;
+        }
+        if !self.receipts_root.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("astria.sequencerblock.v1alpha1.RollupResultNamespaceData", len)?;
+        if !self.sequencer_block_hash.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("sequencer_block_hash", pbjson::private::base64::encode(&self.sequencer_block_hash).as_str())?;
+        }
+        if let Some(v) = self.rollup_id.as_ref() {
+            struct_ser.serialize_field("rollup_id", v)?;
+        }
+        if self.rollup_block_number != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("rollup_block_number", ToString::to_string(&self.rollup_block_number).as_str())?;
+        }
+        if !self.rollup_block_hash.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("rollup_block_hash", pbjson::private::base64::encode(&self.rollup_block_hash).as_str())?;
+        }
+        if !self.state_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("state_root", pbjson::private::base64::encode(&self.state_root).as_str())?;
+        }
+        if !self.receipts_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("receipts_root", pbjson::private::base64::encode(&self.receipts_root).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RollupResultNamespaceData {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sequencer_block_hash",
+            "sequencerBlockHash",
+            "rollup_id",
+            "rollupId",
+            "rollup_block_number",
+            "rollupBlockNumber",
+            "rollup_block_hash",
+            "rollupBlockHash",
+            "state_root",
+            "stateRoot",
+            "receipts_root",
+            "receiptsRoot",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            SequencerBlockHash,
+            RollupId,
+            RollupBlockNumber,
+            RollupBlockHash,
+            StateRoot,
+            ReceiptsRoot,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sequencerBlockHash" | "sequencer_block_hash" => Ok(GeneratedField::SequencerBlockHash),
+                            "rollupId" | "rollup_id" => Ok(GeneratedField::RollupId),
+                            "rollupBlockNumber" | "rollup_block_number" => Ok(GeneratedField::RollupBlockNumber),
+                            "rollupBlockHash" | "rollup_block_hash" => Ok(GeneratedField::RollupBlockHash),
+                            "stateRoot" | "state_root" => Ok(GeneratedField::StateRoot),
+                            "receiptsRoot" | "receipts_root" => Ok(GeneratedField::ReceiptsRoot),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RollupResultNamespaceData;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct astria.sequencerblock.v1alpha1.RollupResultNamespaceData")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<RollupResultNamespaceData, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sequencer_block_hash__ = None;
+                let mut rollup_id__ = None;
+                let mut rollup_block_number__ = None;
+                let mut rollup_block_hash__ = None;
+                let mut state_root__ = None;
+                let mut receipts_root__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::SequencerBlockHash => {
+                            if sequencer_block_hash__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sequencerBlockHash"));
+                            }
+                            sequencer_block_hash__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::RollupId => {
+                            if rollup_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("rollupId"));
+                            }
+                            rollup_id__ = map_.next_value()?;
+                        }
+                        GeneratedField::RollupBlockNumber => {
+                            if rollup_block_number__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("rollupBlockNumber"));
+                            }
+                            rollup_block_number__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::RollupBlockHash => {
+                            if rollup_block_hash__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("rollupBlockHash"));
+                            }
+                            rollup_block_hash__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::StateRoot => {
+                            if state_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stateRoot"));
+                            }
+                            state_root__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ReceiptsRoot => {
+                            if receipts_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("receiptsRoot"));
+                            }
+                            receipts_root__ = 
+                                Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(RollupResultNamespaceData {
+                    sequencer_block_hash: sequencer_block_hash__.unwrap_or_default(),
+                    rollup_id: rollup_id__,
+                    rollup_block_number: rollup_block_number__.unwrap_or_default(),
+                    rollup_block_hash: rollup_block_hash__.unwrap_or_default(),
+                    state_root: state_root__.unwrap_or_default(),
+                    receipts_root: receipts_root__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("astria.sequencerblock.v1alpha1.RollupResultNamespaceData", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for RollupTransactions {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -1470,3 +1994,77 @@ impl<'de> serde::Deserialize<'de> for SequencerBlockHeader {
         deserializer.deserialize_struct("astria.sequencerblock.v1alpha1.SequencerBlockHeader", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for SignatureScheme {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "SIGNATURE_SCHEME_UNSPECIFIED",
+            Self::Ed25519 => "SIGNATURE_SCHEME_ED25519",
+            Self::Secp256k1 => "SIGNATURE_SCHEME_SECP256K1",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for SignatureScheme {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "SIGNATURE_SCHEME_UNSPECIFIED",
+            "SIGNATURE_SCHEME_ED25519",
+            "SIGNATURE_SCHEME_SECP256K1",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SignatureScheme;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .ok()
+                    .and_then(|x| x.try_into().ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                i32::try_from(v)
+                    .ok()
+                    .and_then(|x| x.try_into().ok())
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "SIGNATURE_SCHEME_UNSPECIFIED" => Ok(SignatureScheme::Unspecified),
+                    "SIGNATURE_SCHEME_ED25519" => Ok(SignatureScheme::Ed25519),
+                    "SIGNATURE_SCHEME_SECP256K1" => Ok(SignatureScheme::Secp256k1),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
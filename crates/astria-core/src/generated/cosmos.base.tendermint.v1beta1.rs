@@ -1,3 +1,32 @@
+/// GetLatestBlockRequest is the request type for the Query/GetLatestBlock RPC
+/// method.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetLatestBlockRequest {}
+impl ::prost::Name for GetLatestBlockRequest {
+    const NAME: &'static str = "GetLatestBlockRequest";
+    const PACKAGE: &'static str = "cosmos.base.tendermint.v1beta1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("cosmos.base.tendermint.v1beta1.{}", Self::NAME)
+    }
+}
+/// GetLatestBlockResponse is the response type for the Query/GetLatestBlock RPC
+/// method.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetLatestBlockResponse {
+    #[prost(message, optional, tag = "2")]
+    pub block: ::core::option::Option<
+        super::super::super::super::tendermint::types::Block,
+    >,
+}
+impl ::prost::Name for GetLatestBlockResponse {
+    const NAME: &'static str = "GetLatestBlockResponse";
+    const PACKAGE: &'static str = "cosmos.base.tendermint.v1beta1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("cosmos.base.tendermint.v1beta1.{}", Self::NAME)
+    }
+}
 /// GetNodeInfoRequest is the request type for the Query/GetNodeInfo RPC method.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -196,6 +225,37 @@ pub mod service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// GetLatestBlock returns the latest block.
+        pub async fn get_latest_block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetLatestBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetLatestBlockResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "cosmos.base.tendermint.v1beta1.Service",
+                        "GetLatestBlock",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -214,6 +274,14 @@ pub mod service_server {
             tonic::Response<super::GetNodeInfoResponse>,
             tonic::Status,
         >;
+        /// GetLatestBlock returns the latest block.
+        async fn get_latest_block(
+            self: std::sync::Arc<Self>,
+            request: tonic::Request<super::GetLatestBlockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetLatestBlockResponse>,
+            tonic::Status,
+        >;
     }
     /// Service defines the gRPC querier service for tendermint queries.
     #[derive(Debug)]
@@ -341,6 +409,52 @@ pub mod service_server {
                     };
                     Box::pin(fut)
                 }
+                "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetLatestBlockSvc<T: Service>(pub Arc<T>);
+                    impl<
+                        T: Service,
+                    > tonic::server::UnaryService<super::GetLatestBlockRequest>
+                    for GetLatestBlockSvc<T> {
+                        type Response = super::GetLatestBlockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetLatestBlockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Service>::get_latest_block(inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetLatestBlockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -72,6 +72,12 @@ pub mod composer {
     pub mod v1alpha1;
 }
 
+#[path = ""]
+pub mod daindex {
+    #[path = "astria.daindex.v1alpha1.rs"]
+    pub mod v1alpha1;
+}
+
 #[path = ""]
 pub mod celestia {
     #[path = "celestia.blob.v1.rs"]
@@ -86,6 +92,12 @@ pub mod cosmos {
         }
     }
 
+    pub mod bank {
+        pub mod v1beta1 {
+            include!("cosmos.bank.v1beta1.rs");
+        }
+    }
+
     pub mod base {
         pub mod abci {
             pub mod v1beta1 {
@@ -41,3 +41,37 @@ impl ::prost::Name for BlobTx {
         ::prost::alloc::format!("tendermint.types.{}", Self::NAME)
     }
 }
+/// Block defines the atomic unit of a Tendermint blockchain.
+///
+/// Only the header is copied; the remaining fields are not needed.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Block {
+    #[prost(message, optional, tag = "1")]
+    pub header: ::core::option::Option<Header>,
+}
+impl ::prost::Name for Block {
+    const NAME: &'static str = "Block";
+    const PACKAGE: &'static str = "tendermint.types";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("tendermint.types.{}", Self::NAME)
+    }
+}
+/// Header defines the structure of a Tendermint block header.
+///
+/// Only the chain ID and height are copied; the remaining fields are not needed.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Header {
+    #[prost(string, tag = "2")]
+    pub chain_id: ::prost::alloc::string::String,
+    #[prost(int64, tag = "3")]
+    pub height: i64,
+}
+impl ::prost::Name for Header {
+    const NAME: &'static str = "Header";
+    const PACKAGE: &'static str = "tendermint.types";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("tendermint.types.{}", Self::NAME)
+    }
+}
@@ -0,0 +1,152 @@
+//! Signed heartbeats posted to Celestia by sequencer-relayers.
+//!
+//! A heartbeat blob is the JSON encoding of a [`SignedHeartbeat`], whose `payload` holds the
+//! JSON encoding of a [`HeartbeatPayload`]. The ed25519 `signature` is over the bytes of
+//! `payload`, so that readers can verify it without re-encoding the payload.
+
+use ed25519_consensus::{
+    Signature,
+    SigningKey,
+    VerificationKey,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// An error when decoding or verifying a [`SignedHeartbeat`].
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct HeartbeatError(HeartbeatErrorKind);
+
+impl HeartbeatError {
+    fn decode(source: serde_json::Error) -> Self {
+        Self(HeartbeatErrorKind::Decode(source))
+    }
+
+    fn verification_key(source: ed25519_consensus::Error) -> Self {
+        Self(HeartbeatErrorKind::VerificationKey(source))
+    }
+
+    fn signature(source: ed25519_consensus::Error) -> Self {
+        Self(HeartbeatErrorKind::Signature(source))
+    }
+
+    fn payload(source: serde_json::Error) -> Self {
+        Self(HeartbeatErrorKind::Payload(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum HeartbeatErrorKind {
+    #[error("failed decoding signed heartbeat as JSON")]
+    Decode(#[source] serde_json::Error),
+    #[error("the verification key of the heartbeat is not a valid ed25519 key")]
+    VerificationKey(#[source] ed25519_consensus::Error),
+    #[error("the signature of the heartbeat does not match its payload")]
+    Signature(#[source] ed25519_consensus::Error),
+    #[error("failed decoding heartbeat payload as JSON")]
+    Payload(#[source] serde_json::Error),
+}
+
+/// The contents of a heartbeat.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatPayload {
+    /// The address of the key signing the heartbeat, identifying the relayer.
+    pub relayer_address: String,
+    /// The latest confirmed Celestia height at the time the heartbeat was created.
+    pub celestia_height: Option<u64>,
+    /// The latest sequencer height that the relayer confirmed on Celestia.
+    pub last_relayed_sequencer_height: Option<u64>,
+    /// The version of the relayer.
+    pub version: String,
+}
+
+/// A [`HeartbeatPayload`] together with its signature.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedHeartbeat {
+    #[serde(with = "crate::serde::streaming_base64")]
+    payload: Vec<u8>,
+    #[serde(with = "crate::serde::hex")]
+    verification_key: [u8; 32],
+    #[serde(with = "crate::serde::hex")]
+    signature: [u8; 64],
+}
+
+impl SignedHeartbeat {
+    /// Signs `payload` with `signing_key`.
+    #[must_use]
+    pub fn sign(payload: &HeartbeatPayload, signing_key: &SigningKey) -> Self {
+        let payload = serde_json::to_vec(payload)
+            .expect("serializing a struct of strings and integers to JSON cannot fail");
+        let signature = signing_key.sign(&payload);
+        Self {
+            payload,
+            verification_key: signing_key.verification_key().to_bytes(),
+            signature: signature.to_bytes(),
+        }
+    }
+
+    /// Returns the bytes of the ed25519 key that signed the heartbeat.
+    #[must_use]
+    pub fn verification_key(&self) -> [u8; 32] {
+        self.verification_key
+    }
+
+    /// Encodes the heartbeat as the data of a Celestia blob.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        serde_json::to_vec(self)
+            .expect("serializing a struct of byte fields to JSON cannot fail")
+    }
+
+    /// Decodes a heartbeat from the data of a Celestia blob.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` is not the JSON encoding of a signed heartbeat.
+    pub fn decode(bytes: &[u8]) -> Result<Self, HeartbeatError> {
+        serde_json::from_slice(bytes).map_err(HeartbeatError::decode)
+    }
+
+    /// Verifies the signature of the heartbeat and returns its payload.
+    ///
+    /// # Errors
+    /// Returns an error if the signature does not match the payload or if the payload is not
+    /// the JSON encoding of a [`HeartbeatPayload`].
+    pub fn verify(&self) -> Result<HeartbeatPayload, HeartbeatError> {
+        VerificationKey::try_from(self.verification_key)
+            .map_err(HeartbeatError::verification_key)?
+            .verify(&Signature::from(self.signature), &self.payload)
+            .map_err(HeartbeatError::signature)?;
+        serde_json::from_slice(&self.payload).map_err(HeartbeatError::payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> HeartbeatPayload {
+        HeartbeatPayload {
+            relayer_address: "relayer".to_string(),
+            celestia_height: Some(10),
+            last_relayed_sequencer_height: Some(100),
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn signed_heartbeat_roundtrips_and_verifies() {
+        let signed = SignedHeartbeat::sign(&payload(), &SigningKey::from([1; 32]));
+        let decoded = SignedHeartbeat::decode(&signed.encode()).unwrap();
+        assert_eq!(signed, decoded);
+        assert_eq!(payload(), decoded.verify().unwrap());
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let mut signed = SignedHeartbeat::sign(&payload(), &SigningKey::from([1; 32]));
+        signed.payload[0] ^= 1;
+        assert!(signed.verify().is_err());
+    }
+}
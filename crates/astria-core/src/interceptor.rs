@@ -0,0 +1,277 @@
+//! tonic interceptors to authenticate calls to execution service endpoints.
+//!
+//! Clients attach a bearer token and the ID of the chain they are executing for to every
+//! outgoing request with an [`AuthInterceptor`]. Servers check both with a
+//! [`ValidateInterceptor`], which maps each accepted token to a [`Tenant`] and inserts it into
+//! the extensions of the request, so that a single endpoint can serve several rollups.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
+
+use tonic::{
+    metadata::{
+        errors::InvalidMetadataValue,
+        Ascii,
+        MetadataMap,
+        MetadataValue,
+    },
+    service::Interceptor,
+    Request,
+    Status,
+};
+
+/// The metadata key of the chain ID a request was made for.
+pub const CHAIN_ID_KEY: &str = "x-astria-chain-id";
+
+/// The metadata key of the bearer token.
+pub const AUTHORIZATION_KEY: &str = "authorization";
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// An error when configuring an [`AuthInterceptor`].
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct AuthInterceptorError(AuthInterceptorErrorKind);
+
+impl AuthInterceptorError {
+    fn bearer_token(source: InvalidMetadataValue) -> Self {
+        Self(AuthInterceptorErrorKind::BearerToken(source))
+    }
+
+    fn chain_id(source: InvalidMetadataValue) -> Self {
+        Self(AuthInterceptorErrorKind::ChainId(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum AuthInterceptorErrorKind {
+    #[error("bearer token is not a valid metadata value")]
+    BearerToken(#[source] InvalidMetadataValue),
+    #[error("chain ID is not a valid metadata value")]
+    ChainId(#[source] InvalidMetadataValue),
+}
+
+/// Attaches a bearer token and a chain ID to outgoing requests.
+#[derive(Clone, Debug, Default)]
+pub struct AuthInterceptor {
+    authorization: Option<MetadataValue<Ascii>>,
+    chain_id: Option<MetadataValue<Ascii>>,
+}
+
+impl AuthInterceptor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `token` as a bearer token in the `authorization` metadata of every request.
+    ///
+    /// # Errors
+    /// Returns an error if `token` contains characters not permitted in gRPC metadata.
+    pub fn bearer_token(self, token: &str) -> Result<Self, AuthInterceptorError> {
+        let mut authorization: MetadataValue<Ascii> = format!("{BEARER_PREFIX}{token}")
+            .parse()
+            .map_err(AuthInterceptorError::bearer_token)?;
+        authorization.set_sensitive(true);
+        Ok(Self {
+            authorization: Some(authorization),
+            ..self
+        })
+    }
+
+    /// Sends `chain_id` in the [`CHAIN_ID_KEY`] metadata of every request.
+    ///
+    /// # Errors
+    /// Returns an error if `chain_id` contains characters not permitted in gRPC metadata.
+    pub fn chain_id(self, chain_id: &str) -> Result<Self, AuthInterceptorError> {
+        let chain_id = chain_id.parse().map_err(AuthInterceptorError::chain_id)?;
+        Ok(Self {
+            chain_id: Some(chain_id),
+            ..self
+        })
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let metadata = request.metadata_mut();
+        if let Some(authorization) = &self.authorization {
+            metadata.insert(AUTHORIZATION_KEY, authorization.clone());
+        }
+        if let Some(chain_id) = &self.chain_id {
+            metadata.insert(CHAIN_ID_KEY, chain_id.clone());
+        }
+        Ok(request)
+    }
+}
+
+/// The tenant a request was authenticated as, inserted into the request's extensions by a
+/// [`ValidateInterceptor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tenant {
+    name: String,
+    chain_id: Option<String>,
+}
+
+impl Tenant {
+    /// Constructs a tenant named `name`.
+    ///
+    /// If `chain_id` is set, requests authenticated as this tenant must be made for that chain.
+    #[must_use]
+    pub fn new(name: String, chain_id: Option<String>) -> Self {
+        Self {
+            name,
+            chain_id,
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn chain_id(&self) -> Option<&str> {
+        self.chain_id.as_deref()
+    }
+}
+
+type Hook = dyn Fn(&Tenant, &MetadataMap) -> Result<(), Status> + Send + Sync;
+
+/// Validates the bearer token and chain ID of incoming requests.
+///
+/// Requests without a known bearer token are rejected with `UNAUTHENTICATED`. Requests for a
+/// chain other than the one of their tenant are rejected with `PERMISSION_DENIED`.
+#[derive(Clone, Default)]
+pub struct ValidateInterceptor {
+    tenants: Arc<HashMap<String, Tenant>>,
+    hook: Option<Arc<Hook>>,
+}
+
+impl ValidateInterceptor {
+    /// Constructs an interceptor accepting the bearer tokens in `tenants`.
+    #[must_use]
+    pub fn new(tenants: HashMap<String, Tenant>) -> Self {
+        Self {
+            tenants: Arc::new(tenants),
+            hook: None,
+        }
+    }
+
+    /// Runs `hook` on every request after its tenant was authenticated.
+    ///
+    /// The hook can perform additional checks on the request metadata and reject the request
+    /// by returning an error status.
+    #[must_use]
+    pub fn with_hook<F>(self, hook: F) -> Self
+    where
+        F: Fn(&Tenant, &MetadataMap) -> Result<(), Status> + Send + Sync + 'static,
+    {
+        Self {
+            hook: Some(Arc::new(hook)),
+            ..self
+        }
+    }
+
+    fn authenticate(&self, metadata: &MetadataMap) -> Result<Tenant, Status> {
+        let token = metadata
+            .get(AUTHORIZATION_KEY)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .ok_or_else(|| Status::unauthenticated("missing bearer token"))?;
+        let tenant = self
+            .tenants
+            .get(token)
+            .ok_or_else(|| Status::unauthenticated("unknown bearer token"))?;
+        if let Some(expected) = tenant.chain_id() {
+            let chain_id = metadata
+                .get(CHAIN_ID_KEY)
+                .and_then(|value| value.to_str().ok());
+            if chain_id != Some(expected) {
+                return Err(Status::permission_denied(format!(
+                    "tenant `{}` is not permitted to make requests for this chain",
+                    tenant.name(),
+                )));
+            }
+        }
+        if let Some(hook) = &self.hook {
+            hook(tenant, metadata)?;
+        }
+        Ok(tenant.clone())
+    }
+}
+
+impl std::fmt::Debug for ValidateInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidateInterceptor")
+            .field("tenants", &self.tenants.values().collect::<Vec<_>>())
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+impl Interceptor for ValidateInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let tenant = self.authenticate(request.metadata())?;
+        request.extensions_mut().insert(tenant);
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    fn validator() -> ValidateInterceptor {
+        let tenants = HashMap::from([(
+            "secret".to_string(),
+            Tenant::new("rollup".to_string(), Some("test-1".to_string())),
+        )]);
+        ValidateInterceptor::new(tenants)
+    }
+
+    fn outgoing(token: &str, chain_id: &str) -> Request<()> {
+        AuthInterceptor::new()
+            .bearer_token(token)
+            .unwrap()
+            .chain_id(chain_id)
+            .unwrap()
+            .call(Request::new(()))
+            .unwrap()
+    }
+
+    #[test]
+    fn authenticated_request_carries_tenant() {
+        let request = validator().call(outgoing("secret", "test-1")).unwrap();
+        assert_eq!(
+            Some("rollup"),
+            request.extensions().get::<Tenant>().map(Tenant::name),
+        );
+    }
+
+    #[test]
+    fn unknown_token_is_rejected() {
+        let status = validator().call(outgoing("guess", "test-1")).unwrap_err();
+        assert_eq!(Code::Unauthenticated, status.code());
+        let status = validator().call(Request::new(())).unwrap_err();
+        assert_eq!(Code::Unauthenticated, status.code());
+    }
+
+    #[test]
+    fn other_chain_is_rejected() {
+        let status = validator().call(outgoing("secret", "test-2")).unwrap_err();
+        assert_eq!(Code::PermissionDenied, status.code());
+    }
+
+    #[test]
+    fn hook_can_reject_request() {
+        let mut validator =
+            validator().with_hook(|_, _| Err(Status::resource_exhausted("rate limited")));
+        let status = validator.call(outgoing("secret", "test-1")).unwrap_err();
+        assert_eq!(Code::ResourceExhausted, status.code());
+    }
+}
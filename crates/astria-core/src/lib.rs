@@ -6,17 +6,30 @@ compile_error!(
 #[rustfmt::skip]
 pub mod generated;
 
+pub mod cometbft;
 pub mod execution;
 pub mod primitive;
 pub mod protocol;
 pub mod sequencerblock;
+pub mod signers;
 
+#[cfg(feature = "serde")]
+pub mod attestation;
 #[cfg(feature = "brotli")]
 pub mod brotli;
 #[cfg(feature = "celestia")]
 pub mod celestia;
+#[cfg(feature = "client")]
+pub mod connect;
+#[cfg(feature = "serde")]
+pub mod heartbeat;
+#[cfg(any(feature = "client", feature = "server"))]
+pub mod interceptor;
 #[cfg(feature = "serde")]
-pub(crate) mod serde;
+pub mod serde;
+
+#[cfg(test)]
+mod wire_compatibility;
 
 /// A trait to convert from raw decoded protobuf types to idiomatic astria types.
 ///
@@ -0,0 +1,157 @@
+//! Fixed size byte strings displayed and parsed as `0x` prefixed lower case hex.
+
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+/// An error when parsing a `0x` prefixed hex string.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ParseHexError {
+    #[error("invalid hex character `{character}` at position {index}")]
+    InvalidCharacter { character: char, index: usize },
+    #[error("hex string has an odd number of digits")]
+    OddLength,
+    #[error("expected {expected} bytes, got {actual}")]
+    Length { expected: usize, actual: usize },
+}
+
+/// Writes `bytes` as `0x` prefixed lower case hex.
+pub(crate) fn fmt_hex(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("0x")?;
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+/// Parses hex with an optional `0x` prefix into exactly `N` bytes.
+pub(crate) fn parse_hex<const N: usize>(input: &str) -> Result<[u8; N], ParseHexError> {
+    let digits = input.strip_prefix("0x").unwrap_or(input);
+    if digits.len() % 2 != 0 {
+        return Err(ParseHexError::OddLength);
+    }
+    if digits.len() / 2 != N {
+        return Err(ParseHexError::Length {
+            expected: N,
+            actual: digits.len() / 2,
+        });
+    }
+    let mut bytes = [0; N];
+    for (index, character) in digits.char_indices() {
+        let Some(nibble) = character.to_digit(16) else {
+            return Err(ParseHexError::InvalidCharacter {
+                character,
+                index,
+            });
+        };
+        // allow: `to_digit(16)` returns values below 16.
+        #[allow(clippy::cast_possible_truncation)]
+        let nibble = nibble as u8;
+        bytes[index / 2] = (bytes[index / 2] << 4) | nibble;
+    }
+    Ok(bytes)
+}
+
+macro_rules! hex_bytes {
+    ($(#[$attr:meta])* $name:ident, $len:literal) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name([u8; $len]);
+
+        impl $name {
+            #[must_use]
+            pub const fn new(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            #[must_use]
+            pub const fn get(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_hex(&self.0, f)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_hex(&self.0, f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseHexError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse_hex(s).map(Self)
+            }
+        }
+    };
+}
+
+hex_bytes!(
+    /// The 32 byte hash of a sequencer block.
+    BlockHash,
+    32
+);
+
+hex_bytes!(
+    /// A 64 byte ed25519 signature.
+    Signature,
+    64
+);
+
+impl From<ed25519_consensus::Signature> for Signature {
+    fn from(signature: ed25519_consensus::Signature) -> Self {
+        Self(signature.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_hash_roundtrips_through_display() {
+        let hash = BlockHash::new([0xab; 32]);
+        let displayed = hash.to_string();
+        assert_eq!(format!("0x{}", "ab".repeat(32)), displayed);
+        assert_eq!(hash, displayed.parse().unwrap());
+        assert_eq!(hash, displayed[2..].parse().unwrap());
+    }
+
+    #[test]
+    fn invalid_hex_is_rejected() {
+        assert_eq!(
+            Err(ParseHexError::Length {
+                expected: 32,
+                actual: 1,
+            }),
+            "0xab".parse::<BlockHash>(),
+        );
+        assert_eq!(Err(ParseHexError::OddLength), "0xabc".parse::<BlockHash>());
+        assert_eq!(
+            Err(ParseHexError::InvalidCharacter {
+                character: 'z',
+                index: 0,
+            }),
+            format!("z{}", "0".repeat(63)).parse::<BlockHash>(),
+        );
+    }
+}
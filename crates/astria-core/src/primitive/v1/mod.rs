@@ -1,6 +1,13 @@
 pub mod asset;
+pub mod hex;
 pub mod u128;
 
+pub use hex::{
+    BlockHash,
+    ParseHexError,
+    Signature,
+};
+
 use base64::{
     display::Base64Display,
     prelude::BASE64_STANDARD,
@@ -80,7 +87,7 @@ impl Protobuf for merkle::Proof {
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
 pub struct RollupId {
-    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serde::base64"))]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serde::base64::serialize"))]
     inner: [u8; 32],
 }
 
@@ -253,7 +260,7 @@ pub struct IncorrectAddressLength {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Address(
-    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serde::base64"))]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serde::base64::serialize"))]
     [u8; ADDRESS_LEN],
 );
 
@@ -1,4 +1,5 @@
-use indexmap::IndexMap;
+use std::collections::BTreeMap;
+
 use transaction::v1alpha1::SignedTransaction;
 
 use crate::primitive::v1::RollupId;
@@ -16,12 +17,12 @@ pub mod test_utils;
 /// TODO: This can all be done in-place once <https://github.com/rust-lang/rust/issues/80552> is stabilized.
 pub fn group_sequence_actions_in_signed_transaction_transactions_by_rollup_id(
     signed_transactions: &[SignedTransaction],
-) -> IndexMap<RollupId, Vec<Vec<u8>>> {
+) -> BTreeMap<RollupId, Vec<Vec<u8>>> {
     use prost::Message as _;
 
     use crate::sequencerblock::v1alpha1::block::RollupData;
 
-    let mut map = IndexMap::new();
+    let mut map = BTreeMap::new();
     for action in signed_transactions
         .iter()
         .flat_map(SignedTransaction::actions)
@@ -32,6 +33,5 @@ pub fn group_sequence_actions_in_signed_transaction_transactions_by_rollup_id(
             txs_for_rollup.push(rollup_data.into_raw().encode_to_vec());
         }
     }
-    map.sort_unstable_keys();
     map
 }
@@ -45,6 +45,8 @@ impl From<(i64, u32)> for UnixTimeStamp {
 /// If the proposer address is not set it will be generated from the signing key.
 #[derive(Default)]
 pub struct ConfigureSequencerBlock {
+    /// The hash of the block, `[1; 32]` if not set. It is not all zeros by default because
+    /// Celestia blobs of blocks with an all-zero hash are rejected when decoded.
     pub block_hash: Option<[u8; 32]>,
     pub chain_id: Option<String>,
     pub height: u32,
@@ -78,7 +80,7 @@ impl ConfigureSequencerBlock {
             deposits,
         } = self;
 
-        let block_hash = block_hash.unwrap_or_default();
+        let block_hash = block_hash.unwrap_or([1; 32]);
         let chain_id = chain_id.unwrap_or_else(|| "test".to_string());
 
         let signing_key =
@@ -132,7 +134,6 @@ impl ConfigureSequencerBlock {
                     .map(|deposit| RollupData::Deposit(deposit).into_raw().encode_to_vec()),
             );
         }
-        rollup_transactions.sort_unstable_keys();
         let rollup_transactions_tree = derive_merkle_tree_from_rollup_txs(&rollup_transactions);
 
         let rollup_ids_root = merkle::Tree::from_leaves(
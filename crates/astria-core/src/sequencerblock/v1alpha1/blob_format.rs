@@ -0,0 +1,522 @@
+//! The encodings of the blobs posted to Celestia.
+//!
+//! Blobs were originally written as protobuf. To let networks migrate between encodings without
+//! coordinating a flag-day, writers pick a [`BlobFormat`] while readers accept all of them through
+//! [`BlobFormat::decode`], which reads the format of every blob from its bytes.
+//!
+//! Protobuf blobs are written as is, so that blobs written before other formats existed remain
+//! readable. Blobs in any other format are wrapped in an envelope: the byte `0x00` followed by a
+//! byte naming the format, followed by the encoded message. A protobuf encoded message never
+//! starts with `0x00`, because that would be the tag of field number 0, which protobuf reserves.
+//! The format can therefore not be mistaken, whatever bytes the message itself starts with.
+//!
+//! Every format is serialized by a [`Codec`]. The messages posted as blobs implement
+//! [`NamespaceData`], which lets [`BlobFormat::encode`] and [`BlobFormat::decode`] dispatch to the
+//! codec of a format.
+//!
+//! JSON blobs use the proto3 JSON mapping of the same protobuf messages. Borsh blobs use a fixed,
+//! compact layout that only rollup blobs have; see [`BorshCodec`].
+
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+
+use crate::generated::{
+    primitive::v1 as primitive,
+    sequencerblock::v1alpha1 as raw,
+};
+
+#[cfg(feature = "benchmark")]
+mod benchmarks;
+
+/// The first byte of blobs wrapped in an envelope naming their format.
+const ENVELOPE_MARKER: u8 = 0x00;
+
+/// The byte naming [`BlobFormat::Json`] in the envelope.
+const JSON_ID: u8 = 0x01;
+
+/// The byte naming [`BlobFormat::Borsh`] in the envelope.
+const BORSH_ID: u8 = 0x02;
+
+/// An error when encoding or decoding a blob.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct BlobFormatError(BlobFormatErrorKind);
+
+impl BlobFormatError {
+    fn json(source: serde_json::Error) -> Self {
+        Self(BlobFormatErrorKind::Json(source))
+    }
+
+    fn protobuf(source: prost::DecodeError) -> Self {
+        Self(BlobFormatErrorKind::Protobuf(source))
+    }
+
+    fn borsh(source: std::io::Error) -> Self {
+        Self(BlobFormatErrorKind::Borsh(source))
+    }
+
+    fn borsh_layout(field: &'static str) -> Self {
+        Self(BlobFormatErrorKind::BorshLayout {
+            field,
+        })
+    }
+
+    fn unsupported<T>(format: BlobFormat) -> Self {
+        Self(BlobFormatErrorKind::Unsupported {
+            format,
+            message: std::any::type_name::<T>(),
+        })
+    }
+
+    fn missing_format() -> Self {
+        Self(BlobFormatErrorKind::MissingFormat)
+    }
+
+    fn unknown_format(id: u8) -> Self {
+        Self(BlobFormatErrorKind::UnknownFormat {
+            id,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum BlobFormatErrorKind {
+    #[error("failed encoding or decoding blob as JSON")]
+    Json(#[source] serde_json::Error),
+    #[error("failed decoding blob as protobuf")]
+    Protobuf(#[source] prost::DecodeError),
+    #[error("failed encoding or decoding blob as borsh")]
+    Borsh(#[source] std::io::Error),
+    #[error("field `{field}` does not fit the borsh layout of rollup blobs")]
+    BorshLayout { field: &'static str },
+    #[error("messages of type `{message}` cannot be encoded as {format}")]
+    Unsupported {
+        format: BlobFormat,
+        message: &'static str,
+    },
+    #[error("blob envelope does not name a format")]
+    MissingFormat,
+    #[error("blob envelope names unknown format `{id:#04x}`")]
+    UnknownFormat { id: u8 },
+}
+
+/// An error when parsing a [`BlobFormat`] from a string.
+#[derive(Debug, thiserror::Error)]
+#[error("unknown blob format `{0}`; expected `json`, `protobuf`, or `borsh`")]
+pub struct ParseBlobFormatError(String);
+
+/// The encoding of a blob posted to Celestia.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlobFormat {
+    /// The proto3 JSON mapping of the blob's protobuf message.
+    Json,
+    /// The protobuf encoding of the blob's message.
+    #[default]
+    Protobuf,
+    /// The compact borsh layout of the blob's message. Only rollup blobs have one.
+    Borsh,
+}
+
+impl BlobFormat {
+    /// Returns the format of the uncompressed blob `bytes` together with the encoded message,
+    /// stripped of its envelope.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` are wrapped in an envelope naming an unknown format.
+    pub fn detect(bytes: &[u8]) -> Result<(Self, &[u8]), BlobFormatError> {
+        let Some((&ENVELOPE_MARKER, rest)) = bytes.split_first() else {
+            return Ok((Self::Protobuf, bytes));
+        };
+        let Some((&id, message)) = rest.split_first() else {
+            return Err(BlobFormatError::missing_format());
+        };
+        match id {
+            JSON_ID => Ok((Self::Json, message)),
+            BORSH_ID => Ok((Self::Borsh, message)),
+            other => Err(BlobFormatError::unknown_format(other)),
+        }
+    }
+
+    /// Returns the byte naming the format in the envelope, or `None` for protobuf, which is
+    /// written without an envelope.
+    fn envelope_id(self) -> Option<u8> {
+        match self {
+            Self::Json => Some(JSON_ID),
+            Self::Protobuf => None,
+            Self::Borsh => Some(BORSH_ID),
+        }
+    }
+
+    /// Returns this format if messages of type `T` can be encoded in it, or protobuf otherwise.
+    ///
+    /// Writers configured to write borsh use this to write the messages without a borsh layout
+    /// as protobuf.
+    #[must_use]
+    pub fn supported_by<T: NamespaceData>(self) -> Self {
+        if self == Self::Borsh && !T::HAS_BORSH_LAYOUT {
+            Self::Protobuf
+        } else {
+            self
+        }
+    }
+
+    /// Returns the name of the format, as accepted by [`BlobFormat::from_str`].
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Protobuf => "protobuf",
+            Self::Borsh => "borsh",
+        }
+    }
+
+    /// Encodes `message` with the codec of this format, wrapped in the envelope naming the
+    /// format unless it is protobuf.
+    ///
+    /// # Errors
+    /// Returns an error if the codec failed serializing `message`, or if `T` cannot be encoded
+    /// in this format.
+    pub fn encode<T: NamespaceData>(self, message: &T) -> Result<Vec<u8>, BlobFormatError> {
+        let encoded = match self {
+            Self::Json => JsonCodec::encode(message)?,
+            Self::Protobuf => ProtobufCodec::encode(message)?,
+            Self::Borsh => message.to_borsh()?,
+        };
+        let Some(id) = self.envelope_id() else {
+            return Ok(encoded);
+        };
+        let mut bytes = Vec::with_capacity(encoded.len().saturating_add(2));
+        bytes.extend_from_slice(&[ENVELOPE_MARKER, id]);
+        bytes.extend_from_slice(&encoded);
+        Ok(bytes)
+    }
+
+    /// Decodes `bytes` in whichever format they were encoded in, returning the message together
+    /// with its detected format.
+    ///
+    /// # Errors
+    /// Returns an error if the envelope of `bytes` names an unknown format, or if they are not
+    /// a valid encoding of `T` in the detected format.
+    pub fn decode<T: NamespaceData>(bytes: &[u8]) -> Result<(T, Self), BlobFormatError> {
+        let (format, message) = Self::detect(bytes)?;
+        let message = match format {
+            Self::Json => JsonCodec::decode(message)?,
+            Self::Protobuf => ProtobufCodec::decode(message)?,
+            Self::Borsh => T::from_borsh(message)?,
+        };
+        Ok((message, format))
+    }
+}
+
+/// A serialization of the messages posted as blobs.
+///
+/// Codecs encode the bare message. The envelope naming the format of a blob is added and
+/// stripped by [`BlobFormat::encode`] and [`BlobFormat::decode`].
+pub trait Codec<T> {
+    /// Encodes `message`.
+    ///
+    /// # Errors
+    /// Returns an error if `message` could not be serialized.
+    fn encode(message: &T) -> Result<Vec<u8>, BlobFormatError>;
+
+    /// Decodes a message from `bytes`.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` are not a valid encoding of `T`.
+    fn decode(bytes: &[u8]) -> Result<T, BlobFormatError>;
+}
+
+/// The codec of [`BlobFormat::Json`], using the proto3 JSON mapping of protobuf messages.
+#[derive(Clone, Copy, Debug)]
+pub struct JsonCodec;
+
+impl<T: Serialize + DeserializeOwned> Codec<T> for JsonCodec {
+    fn encode(message: &T) -> Result<Vec<u8>, BlobFormatError> {
+        serde_json::to_vec(message).map_err(BlobFormatError::json)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, BlobFormatError> {
+        serde_json::from_slice(bytes).map_err(BlobFormatError::json)
+    }
+}
+
+/// The codec of [`BlobFormat::Protobuf`].
+#[derive(Clone, Copy, Debug)]
+pub struct ProtobufCodec;
+
+impl<T: prost::Message + Default> Codec<T> for ProtobufCodec {
+    fn encode(message: &T) -> Result<Vec<u8>, BlobFormatError> {
+        Ok(message.encode_to_vec())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, BlobFormatError> {
+        T::decode(bytes).map_err(BlobFormatError::protobuf)
+    }
+}
+
+/// The codec of [`BlobFormat::Borsh`].
+///
+/// Rollup blobs are laid out as their fields in protobuf order, with the sequencer block hash
+/// and the rollup ID as fixed 32 byte arrays and the proof flattened into its audit path, leaf
+/// index, and tree size. Unlike protobuf and JSON, no field tags or names are written.
+#[derive(Clone, Copy, Debug)]
+pub struct BorshCodec;
+
+impl Codec<raw::CelestiaRollupBlob> for BorshCodec {
+    fn encode(blob: &raw::CelestiaRollupBlob) -> Result<Vec<u8>, BlobFormatError> {
+        let sequencer_block_hash: &[u8; 32] = blob
+            .sequencer_block_hash
+            .as_slice()
+            .try_into()
+            .map_err(|_| BlobFormatError::borsh_layout("sequencer_block_hash"))?;
+        let rollup_id: &[u8; 32] = blob
+            .rollup_id
+            .as_ref()
+            .and_then(|rollup_id| rollup_id.inner[..].try_into().ok())
+            .ok_or_else(|| BlobFormatError::borsh_layout("rollup_id"))?;
+        let proof = blob
+            .proof
+            .as_ref()
+            .ok_or_else(|| BlobFormatError::borsh_layout("proof"))?;
+
+        let mut bytes = Vec::new();
+        let write = |bytes: &mut Vec<u8>| -> std::io::Result<()> {
+            // `serialize` is called through the trait because serde's is in scope as well.
+            BorshSerialize::serialize(sequencer_block_hash, bytes)?;
+            BorshSerialize::serialize(rollup_id, bytes)?;
+            BorshSerialize::serialize(&blob.transactions, bytes)?;
+            BorshSerialize::serialize(&proof.audit_path[..], bytes)?;
+            BorshSerialize::serialize(&proof.leaf_index, bytes)?;
+            BorshSerialize::serialize(&proof.tree_size, bytes)?;
+            BorshSerialize::serialize(&blob.verification_key, bytes)?;
+            BorshSerialize::serialize(&blob.signature, bytes)?;
+            BorshSerialize::serialize(&blob.rollup_txs_root, bytes)?;
+            BorshSerialize::serialize(&blob.transactions_checksum, bytes)?;
+            BorshSerialize::serialize(&blob.signature_scheme, bytes)
+        };
+        write(&mut bytes).map_err(BlobFormatError::borsh)?;
+        Ok(bytes)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<raw::CelestiaRollupBlob, BlobFormatError> {
+        let read = |reader: &mut &[u8]| -> std::io::Result<raw::CelestiaRollupBlob> {
+            let sequencer_block_hash = <[u8; 32]>::deserialize_reader(reader)?;
+            let rollup_id = <[u8; 32]>::deserialize_reader(reader)?;
+            let transactions = Vec::<Vec<u8>>::deserialize_reader(reader)?;
+            let audit_path = Vec::<u8>::deserialize_reader(reader)?;
+            let leaf_index = u64::deserialize_reader(reader)?;
+            let tree_size = u64::deserialize_reader(reader)?;
+            Ok(raw::CelestiaRollupBlob {
+                sequencer_block_hash: sequencer_block_hash.to_vec(),
+                rollup_id: Some(primitive::RollupId {
+                    inner: rollup_id.to_vec().into(),
+                }),
+                transactions,
+                proof: Some(primitive::Proof {
+                    audit_path: audit_path.into(),
+                    leaf_index,
+                    tree_size,
+                }),
+                verification_key: Vec::deserialize_reader(reader)?,
+                signature: Vec::deserialize_reader(reader)?,
+                rollup_txs_root: Vec::deserialize_reader(reader)?,
+                transactions_checksum: Vec::deserialize_reader(reader)?,
+                signature_scheme: i32::deserialize_reader(reader)?,
+            })
+        };
+        let mut reader = bytes;
+        let blob = read(&mut reader).map_err(BlobFormatError::borsh)?;
+        if !reader.is_empty() {
+            return Err(BlobFormatError::borsh(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unexpected bytes after the encoded rollup blob",
+            )));
+        }
+        Ok(blob)
+    }
+}
+
+/// A message posted to Celestia as the data of a blob.
+///
+/// Every message can be encoded as JSON and protobuf. Messages with a borsh layout override
+/// [`NamespaceData::to_borsh`] and [`NamespaceData::from_borsh`] to use the [`BorshCodec`].
+pub trait NamespaceData: prost::Message + Default + Serialize + DeserializeOwned {
+    /// Whether the message has a borsh layout and can be encoded as [`BlobFormat::Borsh`].
+    const HAS_BORSH_LAYOUT: bool = false;
+
+    /// Encodes the message with its borsh layout.
+    ///
+    /// # Errors
+    /// Returns an error if the message has no borsh layout or does not fit it.
+    fn to_borsh(&self) -> Result<Vec<u8>, BlobFormatError> {
+        Err(BlobFormatError::unsupported::<Self>(BlobFormat::Borsh))
+    }
+
+    /// Decodes the message from its borsh layout.
+    ///
+    /// # Errors
+    /// Returns an error if the message has no borsh layout or `bytes` are not a valid encoding.
+    fn from_borsh(_bytes: &[u8]) -> Result<Self, BlobFormatError> {
+        Err(BlobFormatError::unsupported::<Self>(BlobFormat::Borsh))
+    }
+}
+
+impl NamespaceData for raw::CelestiaSequencerBlob {}
+
+impl NamespaceData for raw::CelestiaRollupBlob {
+    const HAS_BORSH_LAYOUT: bool = true;
+
+    fn to_borsh(&self) -> Result<Vec<u8>, BlobFormatError> {
+        BorshCodec::encode(self)
+    }
+
+    fn from_borsh(bytes: &[u8]) -> Result<Self, BlobFormatError> {
+        BorshCodec::decode(bytes)
+    }
+}
+
+impl NamespaceData for raw::CelestiaEmptyBlocksMarker {}
+
+impl fmt::Display for BlobFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BlobFormat {
+    type Err = ParseBlobFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "protobuf" => Ok(Self::Protobuf),
+            "borsh" => Ok(Self::Borsh),
+            other => Err(ParseBlobFormatError(other.to_string())),
+        }
+    }
+}
+
+impl Serialize for BlobFormat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BlobFormat {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generated::sequencerblock::v1alpha1::{
+            CelestiaRollupBlob as RawCelestiaRollupBlob,
+            CelestiaSequencerBlob as RawCelestiaSequencerBlob,
+        },
+        primitive::v1::RollupId,
+        protocol::test_utils::ConfigureSequencerBlock,
+    };
+
+    #[test]
+    fn all_formats_are_read_transparently() {
+        let (_, rollup_blobs) = ConfigureSequencerBlock {
+            height: 1,
+            sequence_data: vec![(RollupId::new([1; 32]), vec![1, 2, 3])],
+            ..Default::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        let raw = rollup_blobs.into_iter().next().unwrap().into_raw();
+        for format in [BlobFormat::Json, BlobFormat::Protobuf, BlobFormat::Borsh] {
+            let bytes = format.encode(&raw).unwrap();
+            let (decoded, detected) = BlobFormat::decode::<RawCelestiaRollupBlob>(&bytes).unwrap();
+            assert_eq!(format, detected);
+            assert_eq!(raw, decoded);
+        }
+    }
+
+    #[test]
+    fn protobuf_blobs_starting_like_json_are_read_as_protobuf() {
+        let json_like_hash = [0x7b; 32];
+        let mut whitespace_then_json_like_hash = [0x7b; 32];
+        whitespace_then_json_like_hash[0] = 0x20;
+        for block_hash in [json_like_hash, whitespace_then_json_like_hash] {
+            let (sequencer_blob, rollup_blobs) = ConfigureSequencerBlock {
+                block_hash: Some(block_hash),
+                height: 1,
+                sequence_data: vec![(RollupId::new([1; 32]), vec![1, 2, 3])],
+                ..Default::default()
+            }
+            .make()
+            .into_celestia_blobs();
+
+            let raw = sequencer_blob.into_raw();
+            let bytes = BlobFormat::Protobuf.encode(&raw).unwrap();
+            let (decoded, detected) =
+                BlobFormat::decode::<RawCelestiaSequencerBlob>(&bytes).unwrap();
+            assert_eq!(BlobFormat::Protobuf, detected);
+            assert_eq!(raw, decoded);
+
+            let raw = rollup_blobs.into_iter().next().unwrap().into_raw();
+            let bytes = BlobFormat::Protobuf.encode(&raw).unwrap();
+            let (decoded, detected) = BlobFormat::decode::<RawCelestiaRollupBlob>(&bytes).unwrap();
+            assert_eq!(BlobFormat::Protobuf, detected);
+            assert_eq!(raw, decoded);
+        }
+    }
+
+    #[test]
+    fn borsh_falls_back_to_protobuf_for_messages_without_a_layout() {
+        assert_eq!(
+            BlobFormat::Borsh,
+            BlobFormat::Borsh.supported_by::<RawCelestiaRollupBlob>()
+        );
+        assert_eq!(
+            BlobFormat::Protobuf,
+            BlobFormat::Borsh.supported_by::<RawCelestiaSequencerBlob>()
+        );
+        assert_eq!(
+            BlobFormat::Json,
+            BlobFormat::Json.supported_by::<RawCelestiaSequencerBlob>()
+        );
+
+        let (sequencer_blob, _) = ConfigureSequencerBlock {
+            height: 1,
+            ..Default::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        let raw = sequencer_blob.into_raw();
+        assert!(BlobFormat::Borsh.encode(&raw).is_err());
+    }
+
+    #[test]
+    fn envelopes_naming_unknown_formats_are_rejected() {
+        for bytes in [&[ENVELOPE_MARKER][..], &[ENVELOPE_MARKER, 0xff, b'{', b'}']] {
+            assert!(BlobFormat::decode::<RawCelestiaRollupBlob>(bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn format_roundtrips_through_str() {
+        for format in [BlobFormat::Json, BlobFormat::Protobuf, BlobFormat::Borsh] {
+            assert_eq!(format, format.to_string().parse().unwrap());
+        }
+        assert!("yaml".parse::<BlobFormat>().is_err());
+    }
+}
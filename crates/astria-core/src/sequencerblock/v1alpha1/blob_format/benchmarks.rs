@@ -0,0 +1,56 @@
+//! Benchmarks comparing the [`BlobFormat`]s on a rollup blob.
+//!
+//! Run with `cargo bench --features benchmark -p astria-core`. Every benchmark counts the bytes
+//! of the blob in the benchmarked format, so divan reports its size as throughput alongside the
+//! timings, and the allocation profiler reports the bytes allocated per iteration.
+
+use divan::counter::BytesCount;
+
+use super::BlobFormat;
+use crate::generated::{
+    primitive::v1 as primitive,
+    sequencerblock::v1alpha1::CelestiaRollupBlob as RawCelestiaRollupBlob,
+};
+
+const FORMATS: [BlobFormat; 3] = [BlobFormat::Json, BlobFormat::Protobuf, BlobFormat::Borsh];
+
+/// The number of transactions in the benchmarked blob.
+const NUMBER_OF_TRANSACTIONS: usize = 100;
+
+/// The size of every transaction in the benchmarked blob.
+const TRANSACTION_SIZE: usize = 1024;
+
+fn rollup_blob() -> RawCelestiaRollupBlob {
+    RawCelestiaRollupBlob {
+        sequencer_block_hash: vec![1; 32],
+        rollup_id: Some(primitive::RollupId {
+            inner: vec![2; 32].into(),
+        }),
+        transactions: vec![vec![3; TRANSACTION_SIZE]; NUMBER_OF_TRANSACTIONS],
+        proof: Some(primitive::Proof {
+            audit_path: vec![4; 7 * 32].into(),
+            leaf_index: 5,
+            tree_size: 100,
+        }),
+        rollup_txs_root: vec![6; 32],
+        transactions_checksum: vec![7; 4],
+        ..RawCelestiaRollupBlob::default()
+    }
+}
+
+#[divan::bench(args = FORMATS)]
+fn encode(bencher: divan::Bencher, format: BlobFormat) {
+    let blob = rollup_blob();
+    let encoded = format.encode(&blob).unwrap();
+    bencher
+        .counter(BytesCount::of_slice(&encoded))
+        .bench_local(|| format.encode(&blob).unwrap());
+}
+
+#[divan::bench(args = FORMATS)]
+fn decode(bencher: divan::Bencher, format: BlobFormat) {
+    let encoded = format.encode(&rollup_blob()).unwrap();
+    bencher
+        .counter(BytesCount::of_slice(&encoded))
+        .bench_local(|| BlobFormat::decode::<RawCelestiaRollupBlob>(&encoded).unwrap());
+}
@@ -1,6 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
 
-use indexmap::IndexMap;
 use sha2::Sha256;
 use tendermint::{
     account,
@@ -15,6 +17,10 @@ use super::{
         CelestiaRollupBlob,
         CelestiaSequencerBlob,
     },
+    extract::{
+        group_rollup_data_by_rollup_id,
+        ExtractedRollupData,
+    },
     raw,
 };
 use crate::{
@@ -26,11 +32,6 @@ use crate::{
         IncorrectRollupIdLength,
         RollupId,
     },
-    protocol::transaction::v1alpha1::{
-        action,
-        SignedTransaction,
-        SignedTransactionError,
-    },
     Protobuf as _,
 };
 
@@ -216,17 +217,6 @@ impl SequencerBlockError {
         Self(SequencerBlockErrorKind::RollupIdsNotInSequencerBlock)
     }
 
-    fn signed_transaction_protobuf_decode(source: prost::DecodeError) -> Self {
-        Self(SequencerBlockErrorKind::SignedTransactionProtobufDecode(
-            source,
-        ))
-    }
-
-    fn raw_signed_transaction_conversion(source: SignedTransactionError) -> Self {
-        Self(SequencerBlockErrorKind::RawSignedTransactionConversion(
-            source,
-        ))
-    }
 
     fn rollup_transactions_root_does_not_match_reconstructed() -> Self {
         Self(SequencerBlockErrorKind::RollupTransactionsRootDoesNotMatchReconstructed)
@@ -289,16 +279,6 @@ enum SequencerBlockErrorKind {
          block could not be verified against their proof and the block's data hash"
     )]
     RollupIdsNotInSequencerBlock,
-    #[error(
-        "failed decoding an entry in the cometbft block.data field as a protobuf signed astria \
-         transaction"
-    )]
-    SignedTransactionProtobufDecode(#[source] prost::DecodeError),
-    #[error(
-        "failed converting a raw protobuf signed transaction decoded from the cometbft block.data
-        field to a native astria signed transaction"
-    )]
-    RawSignedTransactionConversion(#[source] SignedTransactionError),
     #[error(
         "the root derived from the rollup transactions in the cometbft block.data field did not \
          match the root stored in the same block.data field"
@@ -529,7 +509,7 @@ enum SequencerBlockHeaderErrorKind {
 pub struct SequencerBlockParts {
     pub block_hash: [u8; 32],
     pub header: SequencerBlockHeader,
-    pub rollup_transactions: IndexMap<RollupId, RollupTransactions>,
+    pub rollup_transactions: BTreeMap<RollupId, RollupTransactions>,
     pub rollup_transactions_proof: merkle::Proof,
     pub rollup_ids_proof: merkle::Proof,
 }
@@ -546,7 +526,7 @@ pub struct SequencerBlock {
     /// commitments.
     header: SequencerBlockHeader,
     /// The collection of rollup transactions that were included in this block.
-    rollup_transactions: IndexMap<RollupId, RollupTransactions>,
+    rollup_transactions: BTreeMap<RollupId, RollupTransactions>,
     // The proof that the rollup transactions are included in the `CometBFT` block this
     // sequencer block is derived form. This proof together with
     // `Sha256(MTH(rollup_transactions))` must match `header.data_hash`.
@@ -582,7 +562,7 @@ impl SequencerBlock {
     }
 
     #[must_use]
-    pub fn rollup_transactions(&self) -> &IndexMap<RollupId, RollupTransactions> {
+    pub fn rollup_transactions(&self) -> &BTreeMap<RollupId, RollupTransactions> {
         &self.rollup_transactions
     }
 
@@ -607,7 +587,7 @@ impl SequencerBlock {
 
     /// Returns the map of rollup transactions, consuming `self`.
     #[must_use]
-    pub fn into_rollup_transactions(self) -> IndexMap<RollupId, RollupTransactions> {
+    pub fn into_rollup_transactions(self) -> BTreeMap<RollupId, RollupTransactions> {
         self.rollup_transactions
     }
 
@@ -640,10 +620,10 @@ impl SequencerBlock {
     {
         let all_rollup_ids: Vec<RollupId> = self.rollup_transactions.keys().copied().collect();
 
-        let mut filtered_rollup_transactions = IndexMap::new();
+        let mut filtered_rollup_transactions = BTreeMap::new();
         for id in rollup_ids {
             let id = id.into();
-            if let Some(rollup_transactions) = self.rollup_transactions.shift_remove(&id) {
+            if let Some(rollup_transactions) = self.rollup_transactions.remove(&id) {
                 filtered_rollup_transactions.insert(id, rollup_transactions);
             };
         }
@@ -666,7 +646,7 @@ impl SequencerBlock {
     {
         let all_rollup_ids: Vec<RollupId> = self.rollup_transactions.keys().copied().collect();
 
-        let mut filtered_rollup_transactions = IndexMap::new();
+        let mut filtered_rollup_transactions = BTreeMap::new();
         for id in rollup_ids {
             let id = id.into();
             if let Some(rollup_transactions) = self.rollup_transactions.get(&id).cloned() {
@@ -728,28 +708,16 @@ impl SequencerBlock {
             .try_into()
             .map_err(|e: Vec<_>| SequencerBlockError::incorrect_rollup_ids_root_length(e.len()))?;
 
-        let mut rollup_datas = IndexMap::new();
-        for elem in data_list {
-            let raw_tx =
-                crate::generated::protocol::transaction::v1alpha1::SignedTransaction::decode(
-                    &*elem,
-                )
-                .map_err(SequencerBlockError::signed_transaction_protobuf_decode)?;
-            let signed_tx = SignedTransaction::try_from_raw(raw_tx)
-                .map_err(SequencerBlockError::raw_signed_transaction_conversion)?;
-            for action in signed_tx.into_unsigned().actions {
-                if let action::Action::Sequence(action::SequenceAction {
-                    rollup_id,
-                    data,
-                    fee_asset_id: _,
-                }) = action
-                {
-                    let elem = rollup_datas.entry(rollup_id).or_insert(vec![]);
-                    let data = RollupData::SequencedData(data).into_raw().encode_to_vec();
-                    elem.push(data);
-                }
-            }
-        }
+        // The rollup data must be sorted by its keys before constructing the merkle tree. Since it's
+        // constructed from non-deterministically ordered sources, a `BTreeMap` is used to
+        // guarantee that the same data will give the same root.
+        //
+        // Transactions that cannot be decoded are quarantined and skipped: their payloads were not
+        // committed to, which is verified by the rollup IDs and rollup transactions roots below.
+        let ExtractedRollupData {
+            rollup_data: mut rollup_datas,
+            quarantined: _,
+        } = group_rollup_data_by_rollup_id(data_list);
         for (id, deposits) in deposits {
             rollup_datas.entry(id).or_default().extend(
                 deposits
@@ -758,11 +726,6 @@ impl SequencerBlock {
             );
         }
 
-        // XXX: The rollup data must be sorted by its keys before constructing the merkle tree.
-        // Since it's constructed from non-deterministically ordered sources, there is otherwise no
-        // guarantee that the same data will give the root.
-        rollup_datas.sort_unstable_keys();
-
         // ensure the rollup IDs commitment matches the one calculated from the rollup data
         if rollup_ids_root != merkle::Tree::from_leaves(rollup_datas.keys()).root() {
             return Err(SequencerBlockError::rollup_ids_root_does_not_match_reconstructed());
@@ -775,7 +738,7 @@ impl SequencerBlock {
             );
         }
 
-        let mut rollup_transactions = IndexMap::new();
+        let mut rollup_transactions = BTreeMap::new();
         for (i, (rollup_id, data)) in rollup_datas.into_iter().enumerate() {
             let proof = rollup_transaction_tree
                 .construct_proof(i)
@@ -789,7 +752,6 @@ impl SequencerBlock {
                 },
             );
         }
-        rollup_transactions.sort_unstable_keys();
 
         // action tree root is always the first tx in a block
         let rollup_transactions_proof = tree.construct_proof(0).expect(
@@ -867,7 +829,7 @@ impl SequencerBlock {
             SequencerBlockHeader::try_from_raw(header).map_err(SequencerBlockError::header)
         }?;
 
-        let rollup_transactions: IndexMap<RollupId, RollupTransactions> = rollup_transactions
+        let rollup_transactions: BTreeMap<RollupId, RollupTransactions> = rollup_transactions
             .into_iter()
             .map(rollup_txs_to_tuple)
             .collect::<Result<_, _>>()
@@ -930,7 +892,7 @@ pub struct FilteredSequencerBlockParts {
     pub block_hash: [u8; 32],
     pub header: SequencerBlockHeader,
     // filtered set of rollup transactions
-    pub rollup_transactions: IndexMap<RollupId, RollupTransactions>,
+    pub rollup_transactions: BTreeMap<RollupId, RollupTransactions>,
     // proof that `rollup_transactions_root` is included in `data_hash`
     pub rollup_transactions_proof: merkle::Proof,
     // all rollup ids in the sequencer block
@@ -945,7 +907,7 @@ pub struct FilteredSequencerBlock {
     block_hash: [u8; 32],
     header: SequencerBlockHeader,
     // filtered set of rollup transactions
-    rollup_transactions: IndexMap<RollupId, RollupTransactions>,
+    rollup_transactions: BTreeMap<RollupId, RollupTransactions>,
     // proof that `rollup_transactions_root` is included in `data_hash`
     rollup_transactions_proof: merkle::Proof,
     // all rollup ids in the sequencer block
@@ -971,7 +933,7 @@ impl FilteredSequencerBlock {
     }
 
     #[must_use]
-    pub fn rollup_transactions(&self) -> &IndexMap<RollupId, RollupTransactions> {
+    pub fn rollup_transactions(&self) -> &BTreeMap<RollupId, RollupTransactions> {
         &self.rollup_transactions
     }
 
@@ -1087,12 +1049,10 @@ impl FilteredSequencerBlock {
                 .map_err(FilteredSequencerBlockError::invalid_header)
         }?;
 
-        // XXX: These rollup transactions are not sorted compared to those used for
-        // deriving the rollup transactions merkle tree in `SequencerBlock`.
         let rollup_transactions = rollup_transactions
             .into_iter()
             .map(rollup_txs_to_tuple)
-            .collect::<Result<IndexMap<_, _>, _>>()
+            .collect::<Result<BTreeMap<_, _>, _>>()
             .map_err(FilteredSequencerBlockError::parse_rollup_transactions)?;
 
         let all_rollup_ids: Vec<RollupId> = all_rollup_ids
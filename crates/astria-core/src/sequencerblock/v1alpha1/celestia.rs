@@ -1,3 +1,12 @@
+use ed25519_consensus::{
+    Signature,
+    SigningKey,
+    VerificationKey,
+};
+use k256::ecdsa::signature::{
+    Signer,
+    Verifier,
+};
 use sha2::{
     Digest as _,
     Sha256,
@@ -57,6 +66,7 @@ impl CelestiaBlobBundle {
                 rollup_id,
                 transactions,
                 proof,
+                signature: None,
             });
         }
         Self {
@@ -80,6 +90,30 @@ pub struct CelestiaRollupBlobError {
 }
 
 impl CelestiaRollupBlobError {
+    /// Returns if the signature scheme, the verification key, or the signature of the blob
+    /// could not be parsed.
+    #[must_use]
+    pub fn is_malformed_signature(&self) -> bool {
+        match &self.kind {
+            CelestiaRollupBlobErrorKind::SignatureScheme(_) => true,
+            CelestiaRollupBlobErrorKind::Signature {
+                source,
+            } => source.is_malformed(),
+            _ => false,
+        }
+    }
+
+    /// Returns if the signature of the blob does not match its contents.
+    #[must_use]
+    pub fn is_signature_mismatch(&self) -> bool {
+        matches!(
+            &self.kind,
+            CelestiaRollupBlobErrorKind::Signature {
+                source,
+            } if !source.is_malformed()
+        )
+    }
+
     fn field_not_set(field: &'static str) -> Self {
         Self {
             kind: CelestiaRollupBlobErrorKind::FieldNotSet {
@@ -109,6 +143,58 @@ impl CelestiaRollupBlobError {
             kind: CelestiaRollupBlobErrorKind::SequencerBlockHash(actual_len),
         }
     }
+
+    fn signature_scheme(scheme: i32) -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::SignatureScheme(scheme),
+        }
+    }
+
+    fn signature(source: SignatureError) -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::Signature {
+                source,
+            },
+        }
+    }
+
+    fn rollup_txs_root(actual_len: usize) -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::RollupTxsRoot(actual_len),
+        }
+    }
+
+    fn rollup_txs_root_mismatch() -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::RollupTxsRootMismatch,
+        }
+    }
+
+    fn transactions_checksum(actual_len: usize) -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::TransactionsChecksum(actual_len),
+        }
+    }
+
+    fn transactions_checksum_mismatch() -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::TransactionsChecksumMismatch,
+        }
+    }
+
+    fn empty_sequencer_block_hash() -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::EmptySequencerBlockHash,
+        }
+    }
+
+    fn too_many_transactions(count: usize) -> Self {
+        Self {
+            kind: CelestiaRollupBlobErrorKind::TooManyTransactions {
+                count,
+            },
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -122,16 +208,350 @@ enum CelestiaRollupBlobErrorKind {
         source: <merkle::Proof as Protobuf>::Error,
     },
     #[error(
-        "the provided bytes were too short for a sequencer block hash. Expected: 32 bytes, \
+        "the provided bytes did not have the length of a sequencer block hash. Expected: 32 bytes, \
          provided: {0}"
     )]
     SequencerBlockHash(usize),
+    #[error("the signature scheme `{0}` of the blob is not known")]
+    SignatureScheme(i32),
+    #[error("the signature of the blob could not be verified against its contents")]
+    Signature { source: SignatureError },
+    #[error(
+        "the provided bytes did not have the length of a rollup transactions root. Expected: 32 \
+         bytes, provided: {0}"
+    )]
+    RollupTxsRoot(usize),
+    #[error("the rollup transactions root of the blob does not match its transactions")]
+    RollupTxsRootMismatch,
+    #[error(
+        "the provided bytes did not have the length of a transactions checksum. Expected: 4 \
+         bytes, provided: {0}"
+    )]
+    TransactionsChecksum(usize),
+    #[error("the transactions checksum of the blob does not match its transactions")]
+    TransactionsChecksumMismatch,
+    #[error("the sequencer block hash of the blob was all zeros")]
+    EmptySequencerBlockHash,
+    #[error(
+        "the blob contained {count} transactions, more than the maximum of \
+         {MAX_ROLLUP_TRANSACTIONS_PER_BLOB}"
+    )]
+    TooManyTransactions { count: usize },
+}
+
+/// The maximum number of transactions in a [`CelestiaRollupBlob`] constructed by
+/// [`CelestiaRollupBlob::try_new`] or [`CelestiaRollupBlob::try_from_unchecked`].
+pub const MAX_ROLLUP_TRANSACTIONS_PER_BLOB: usize = 65536;
+
+/// The domain separator prepended to the message signed by a [`RollupBlobSignature`].
+const ROLLUP_BLOB_SIGNATURE_DOMAIN: &[u8] = b"astria/rollup-blob-signature/v1";
+
+/// The signature schemes with which a [`CelestiaRollupBlob`] can be signed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SignatureSchemeId {
+    /// ed25519, the scheme of all blobs signed before the scheme was added to the envelope.
+    Ed25519,
+    /// ECDSA over secp256k1 with SHA-256 as the message digest.
+    Secp256k1,
+}
+
+impl SignatureSchemeId {
+    /// Returns the name of the scheme, like `secp256k1`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "ed25519",
+            Self::Secp256k1 => "secp256k1",
+        }
+    }
+
+    /// Verifies that `signature` over `message` was created by the key `verification_key`,
+    /// dispatching to the [`SignatureScheme`] identified by `self`.
+    ///
+    /// # Errors
+    /// Returns an error if `verification_key` or `signature` are not valid encodings of the
+    /// scheme, or if the signature does not match `message`.
+    pub fn verify(
+        self,
+        verification_key: &[u8],
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<(), SignatureError> {
+        match self {
+            Self::Ed25519 => {
+                <SigningKey as SignatureScheme>::verify(verification_key, signature, message)
+            }
+            Self::Secp256k1 => <k256::ecdsa::SigningKey as SignatureScheme>::verify(
+                verification_key,
+                signature,
+                message,
+            ),
+        }
+    }
+
+    /// Reads the scheme of a signed blob. Blobs written before the scheme was added to the
+    /// envelope leave it unspecified and are signed with ed25519.
+    fn try_from_raw(raw: i32) -> Result<Self, CelestiaRollupBlobError> {
+        match raw::SignatureScheme::try_from(raw) {
+            Ok(raw::SignatureScheme::Unspecified | raw::SignatureScheme::Ed25519) => {
+                Ok(Self::Ed25519)
+            }
+            Ok(raw::SignatureScheme::Secp256k1) => Ok(Self::Secp256k1),
+            Err(_) => Err(CelestiaRollupBlobError::signature_scheme(raw)),
+        }
+    }
+
+    fn to_raw(self) -> raw::SignatureScheme {
+        match self {
+            Self::Ed25519 => raw::SignatureScheme::Ed25519,
+            Self::Secp256k1 => raw::SignatureScheme::Secp256k1,
+        }
+    }
+}
+
+impl std::fmt::Display for SignatureSchemeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A signature scheme with which rollups sign their [`CelestiaRollupBlob`]s.
+///
+/// Implemented for the signing keys of the schemes in [`SignatureSchemeId`], so that rollups can
+/// sign and verify their blobs with the key infrastructure their ecosystem is standardized on.
+pub trait SignatureScheme {
+    /// The identifier of the scheme, written to the envelope next to the signature.
+    const ID: SignatureSchemeId;
+
+    /// Returns the encoded key verifying the signatures created by `self`.
+    fn verification_key_bytes(&self) -> Vec<u8>;
+
+    /// Signs `message`, returning the encoded signature.
+    fn sign_message(&self, message: &[u8]) -> Vec<u8>;
+
+    /// Verifies that `signature` over `message` was created by the key `verification_key`.
+    ///
+    /// # Errors
+    /// Returns an error if `verification_key` or `signature` are not valid encodings of the
+    /// scheme, or if the signature does not match `message`.
+    fn verify(
+        verification_key: &[u8],
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<(), SignatureError>;
+}
+
+/// Verification keys are 32 bytes, signatures 64 bytes.
+impl SignatureScheme for SigningKey {
+    const ID: SignatureSchemeId = SignatureSchemeId::Ed25519;
+
+    fn verification_key_bytes(&self) -> Vec<u8> {
+        RollupVerificationKey::from(self.verification_key()).bytes
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Vec<u8> {
+        self.sign(message).to_bytes().to_vec()
+    }
+
+    fn verify(
+        verification_key: &[u8],
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<(), SignatureError> {
+        let verification_key = VerificationKey::try_from(verification_key)
+            .map_err(|source| SignatureError::verification_key(Self::ID, source))?;
+        let signature = Signature::try_from(signature)
+            .map_err(|source| SignatureError::signature(Self::ID, source))?;
+        verification_key
+            .verify(&signature, message)
+            .map_err(|source| SignatureError::mismatch(Self::ID, source))
+    }
+}
+
+/// Verification keys are 33 byte compressed SEC1 points, signatures the 64 bytes of `r || s`.
+/// Signatures with a high `s` are rejected, so that they cannot be malleated.
+impl SignatureScheme for k256::ecdsa::SigningKey {
+    const ID: SignatureSchemeId = SignatureSchemeId::Secp256k1;
+
+    fn verification_key_bytes(&self) -> Vec<u8> {
+        RollupVerificationKey::from(*self.verifying_key()).bytes
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Vec<u8> {
+        let signature: k256::ecdsa::Signature = Signer::sign(self, message);
+        signature.to_bytes().to_vec()
+    }
+
+    fn verify(
+        verification_key: &[u8],
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<(), SignatureError> {
+        let verification_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(verification_key)
+            .map_err(|source| SignatureError::verification_key(Self::ID, source))?;
+        let signature = k256::ecdsa::Signature::from_slice(signature)
+            .map_err(|source| SignatureError::signature(Self::ID, source))?;
+        Verifier::verify(&verification_key, message, &signature)
+            .map_err(|source| SignatureError::mismatch(Self::ID, source))
+    }
+}
+
+/// An error verifying a signature with a [`SignatureScheme`].
+#[derive(Debug, thiserror::Error)]
+#[error("failed verifying a {scheme} signature")]
+pub struct SignatureError {
+    scheme: SignatureSchemeId,
+    #[source]
+    kind: SignatureErrorKind,
+}
+
+impl SignatureError {
+    /// Returns the scheme of the signature.
+    #[must_use]
+    pub fn scheme(&self) -> SignatureSchemeId {
+        self.scheme
+    }
+
+    /// Returns if the verification key or the signature could not be parsed, as opposed to the
+    /// signature not matching the message.
+    #[must_use]
+    pub fn is_malformed(&self) -> bool {
+        !matches!(
+            self.kind,
+            SignatureErrorKind::Mismatch {
+                ..
+            }
+        )
+    }
+
+    fn verification_key(
+        scheme: SignatureSchemeId,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            scheme,
+            kind: SignatureErrorKind::VerificationKey {
+                source: Box::new(source),
+            },
+        }
+    }
+
+    fn signature(
+        scheme: SignatureSchemeId,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            scheme,
+            kind: SignatureErrorKind::Signature {
+                source: Box::new(source),
+            },
+        }
+    }
+
+    fn mismatch(
+        scheme: SignatureSchemeId,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            scheme,
+            kind: SignatureErrorKind::Mismatch {
+                source: Box::new(source),
+            },
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum SignatureErrorKind {
+    #[error("the provided bytes were not a valid verification key")]
+    VerificationKey {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("the provided bytes were not a valid signature")]
+    Signature {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("the signature does not match the message")]
+    Mismatch {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// The key verifying a [`RollupBlobSignature`], together with its signature scheme.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RollupVerificationKey {
+    scheme: SignatureSchemeId,
+    bytes: Vec<u8>,
+}
+
+impl RollupVerificationKey {
+    /// Returns the signature scheme of the key.
+    #[must_use]
+    pub fn scheme(&self) -> SignatureSchemeId {
+        self.scheme
+    }
+
+    /// Returns the key, encoded as given by its scheme.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<VerificationKey> for RollupVerificationKey {
+    fn from(key: VerificationKey) -> Self {
+        Self {
+            scheme: SignatureSchemeId::Ed25519,
+            bytes: key.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<k256::ecdsa::VerifyingKey> for RollupVerificationKey {
+    fn from(key: k256::ecdsa::VerifyingKey) -> Self {
+        Self {
+            scheme: SignatureSchemeId::Secp256k1,
+            bytes: key.to_encoded_point(true).as_bytes().to_vec(),
+        }
+    }
+}
+
+/// A signature of a [`CelestiaRollupBlob`] by the key authorized to post it on behalf of its
+/// rollup.
+///
+/// The signed message is the concatenation of a domain separator, the sequencer block hash,
+/// the rollup ID, and the Merkle root of the blob's transactions. It does not cover the proof,
+/// which rollups verify against the sequencer block independently.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RollupBlobSignature {
+    verification_key: RollupVerificationKey,
+    signature: Vec<u8>,
+}
+
+impl RollupBlobSignature {
+    /// Returns the signature scheme.
+    #[must_use]
+    pub fn scheme(&self) -> SignatureSchemeId {
+        self.verification_key.scheme
+    }
+
+    /// Returns the key that created the signature.
+    #[must_use]
+    pub fn verification_key(&self) -> &RollupVerificationKey {
+        &self.verification_key
+    }
+
+    /// Returns the signature, encoded as given by its scheme.
+    #[must_use]
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
 }
 
 /// A shadow of [`CelestiaRollupBlob`] with public access to all its fields.
 ///
-/// At the moment there are no invariants upheld by [`CelestiaRollupBlob`] so
-/// they can be converted directly into one another. This can change in the future.
+/// Use [`CelestiaRollupBlob::try_from_unchecked`] to convert it while checking the invariants
+/// of the blob, or [`CelestiaRollupBlob::from_unchecked`] to convert it as is.
 pub struct UncheckedCelestiaRollupBlob {
     /// The hash of the sequencer block. Must be 32 bytes.
     pub sequencer_block_hash: [u8; 32],
@@ -142,6 +562,8 @@ pub struct UncheckedCelestiaRollupBlob {
     pub transactions: Vec<Vec<u8>>,
     /// The proof that these rollup transactions are included in sequencer block.
     pub proof: merkle::Proof,
+    /// The signature of the blob on behalf of its rollup, if it was signed.
+    pub signature: Option<RollupBlobSignature>,
 }
 
 impl UncheckedCelestiaRollupBlob {
@@ -163,32 +585,155 @@ pub struct CelestiaRollupBlob {
     transactions: Vec<Vec<u8>>,
     /// The proof that these rollup transactions are included in sequencer block.
     proof: merkle::Proof,
+    /// The signature of the blob on behalf of its rollup, if it was signed.
+    signature: Option<RollupBlobSignature>,
 }
 
 impl CelestiaRollupBlob {
+    /// Constructs an unsigned blob of the transactions of rollup `rollup_id` in the sequencer
+    /// block with hash `sequencer_block_hash`, with `proof` proving their inclusion in it.
+    ///
+    /// # Errors
+    /// Returns an error if `sequencer_block_hash` is all zeros, or if there are more than
+    /// [`MAX_ROLLUP_TRANSACTIONS_PER_BLOB`] transactions.
+    pub fn try_new(
+        sequencer_block_hash: [u8; 32],
+        rollup_id: RollupId,
+        transactions: Vec<Vec<u8>>,
+        proof: merkle::Proof,
+    ) -> Result<Self, CelestiaRollupBlobError> {
+        Self::try_from_unchecked(UncheckedCelestiaRollupBlob {
+            sequencer_block_hash,
+            rollup_id,
+            transactions,
+            proof,
+            signature: None,
+        })
+    }
+
+    /// Returns the proof that the blob's transactions are included in the sequencer block.
     #[must_use]
     pub fn proof(&self) -> &merkle::Proof {
         &self.proof
     }
 
+    /// Returns the serialized rollup transactions.
     #[must_use]
     pub fn transactions(&self) -> &[Vec<u8>] {
         &self.transactions
     }
 
+    /// Returns the ID of the rollup the blob belongs to.
     #[must_use]
     pub fn rollup_id(&self) -> RollupId {
         self.rollup_id
     }
 
+    /// Returns the hash of the sequencer block the blob's transactions were included in.
     #[must_use]
     pub fn sequencer_block_hash(&self) -> [u8; 32] {
         self.sequencer_block_hash
     }
 
-    /// Converts from the unchecked representation of this type (its shadow).
+    /// Returns the Merkle root over the blob's transactions.
     ///
-    /// This type does not uphold any extra invariants so there are no extra checks necessary.
+    /// This is the root that [`CelestiaRollupBlob::proof`] proves to be included in the sequencer
+    /// block, with the transactions themselves as the leaves of the tree.
+    #[must_use]
+    pub fn rollup_txs_root(&self) -> [u8; 32] {
+        merkle::Tree::from_leaves(&self.transactions).root()
+    }
+
+    /// Returns the CRC-32 checksum over the blob's transactions, each prefixed by its length as a
+    /// big-endian `u64`.
+    ///
+    /// Unlike [`CelestiaRollupBlob::rollup_txs_root`] it offers no protection against deliberate
+    /// tampering, but is cheap enough to reject corrupted blobs before any hashing or signature
+    /// verification.
+    #[must_use]
+    pub fn transactions_checksum(&self) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        for transaction in &self.transactions {
+            hasher.update(&(transaction.len() as u64).to_be_bytes());
+            hasher.update(transaction);
+        }
+        hasher.finalize()
+    }
+
+    /// Returns the signature of the blob on behalf of its rollup, if it was signed.
+    ///
+    /// A signature returned by this method was verified against the contents of the blob when
+    /// it was signed or decoded. Rollups should additionally check that its verification key
+    /// is the one they authorized.
+    #[must_use]
+    pub fn signature(&self) -> Option<&RollupBlobSignature> {
+        self.signature.as_ref()
+    }
+
+    /// Signs the blob with `signing_key` on behalf of its rollup, replacing any previous
+    /// signature.
+    ///
+    /// `signing_key` can be the key of any [`SignatureScheme`].
+    pub fn sign<S: SignatureScheme>(&mut self, signing_key: &S) {
+        let signature = signing_key.sign_message(&self.signing_message());
+        self.signature = Some(RollupBlobSignature {
+            verification_key: RollupVerificationKey {
+                scheme: S::ID,
+                bytes: signing_key.verification_key_bytes(),
+            },
+            signature,
+        });
+    }
+
+    /// Returns the message that is signed by a [`RollupBlobSignature`] of this blob.
+    fn signing_message(&self) -> Vec<u8> {
+        let transactions_root = self.rollup_txs_root();
+        let parts: [&[u8]; 4] = [
+            ROLLUP_BLOB_SIGNATURE_DOMAIN,
+            &self.sequencer_block_hash,
+            self.rollup_id.as_ref(),
+            &transactions_root,
+        ];
+        parts.concat()
+    }
+
+    /// Converts from the unchecked representation of this type (its shadow), checking its
+    /// invariants.
+    ///
+    /// # Errors
+    /// Returns an error if the sequencer block hash of `unchecked` is all zeros, if it has more
+    /// than [`MAX_ROLLUP_TRANSACTIONS_PER_BLOB`] transactions, or if its signature does not
+    /// verify against its contents.
+    pub fn try_from_unchecked(
+        unchecked: UncheckedCelestiaRollupBlob,
+    ) -> Result<Self, CelestiaRollupBlobError> {
+        if unchecked.sequencer_block_hash == [0; 32] {
+            return Err(CelestiaRollupBlobError::empty_sequencer_block_hash());
+        }
+        if unchecked.transactions.len() > MAX_ROLLUP_TRANSACTIONS_PER_BLOB {
+            return Err(CelestiaRollupBlobError::too_many_transactions(
+                unchecked.transactions.len(),
+            ));
+        }
+        let blob = Self::from_unchecked(unchecked);
+        if let Some(signature) = &blob.signature {
+            signature
+                .scheme()
+                .verify(
+                    signature.verification_key.as_bytes(),
+                    &signature.signature,
+                    &blob.signing_message(),
+                )
+                .map_err(CelestiaRollupBlobError::signature)?;
+        }
+        Ok(blob)
+    }
+
+    /// Converts from the unchecked representation of this type (its shadow) without checking
+    /// its invariants.
+    ///
+    /// Note that this means the signature of `unchecked` is not verified. Prefer
+    /// [`CelestiaRollupBlob::try_from_unchecked`] for blobs not constructed by this crate.
     #[must_use]
     pub fn from_unchecked(unchecked: UncheckedCelestiaRollupBlob) -> Self {
         let UncheckedCelestiaRollupBlob {
@@ -196,12 +741,14 @@ impl CelestiaRollupBlob {
             rollup_id,
             transactions,
             proof,
+            signature,
         } = unchecked;
         Self {
             sequencer_block_hash,
             rollup_id,
             transactions,
             proof,
+            signature,
         }
     }
 
@@ -215,12 +762,14 @@ impl CelestiaRollupBlob {
             rollup_id,
             transactions,
             proof,
+            signature,
         } = self;
         UncheckedCelestiaRollupBlob {
             sequencer_block_hash,
             rollup_id,
             transactions,
             proof,
+            signature,
         }
     }
 
@@ -229,22 +778,53 @@ impl CelestiaRollupBlob {
     /// Useful for then encoding it as protobuf.
     #[must_use]
     pub fn into_raw(self) -> raw::CelestiaRollupBlob {
+        let rollup_txs_root = self.rollup_txs_root().to_vec();
+        let transactions_checksum = self.transactions_checksum().to_be_bytes().to_vec();
         let Self {
             sequencer_block_hash,
             rollup_id,
             transactions,
             proof,
+            signature,
         } = self;
+        let (verification_key, signature, signature_scheme) = signature.map_or_else(
+            || (vec![], vec![], raw::SignatureScheme::Unspecified),
+            |signature| {
+                (
+                    signature.verification_key.bytes,
+                    signature.signature,
+                    signature.verification_key.scheme.to_raw(),
+                )
+            },
+        );
         raw::CelestiaRollupBlob {
             sequencer_block_hash: sequencer_block_hash.to_vec(),
             rollup_id: Some(rollup_id.to_raw()),
             transactions,
             proof: Some(proof.into_raw()),
+            verification_key,
+            signature,
+            rollup_txs_root,
+            transactions_checksum,
+            signature_scheme: signature_scheme.into(),
         }
     }
 
     /// Converts from the raw decoded protobuf representation of this type.
     ///
+    /// A blob without verification key and signature is unsigned. If either is set, the
+    /// signature is verified against the contents of the blob with its signature scheme, which
+    /// is ed25519 if the scheme is not set. If the rollup transactions root is
+    /// set, it is verified against the blob's transactions; blobs written before the root was
+    /// added to the envelope do not set it. The same holds for the transactions checksum, which
+    /// is checked first so that corrupted blobs are rejected cheaply.
+    ///
+    /// Like [`CelestiaRollupBlob::try_from_unchecked`], this rejects blobs with an all-zero
+    /// sequencer block hash. Unlike it, the number of transactions is not bounded by
+    /// [`MAX_ROLLUP_TRANSACTIONS_PER_BLOB`]: the blobs of a sequencer block carry all of a
+    /// rollup's transactions in that block, however many there are, and rejecting them on read
+    /// would leave the rollup without a firm block at that height.
+    ///
     /// # Errors
     /// TODO(https://github.com/astriaorg/astria/issues/612)
     pub fn try_from_raw(raw: raw::CelestiaRollupBlob) -> Result<Self, CelestiaRollupBlobError> {
@@ -253,27 +833,67 @@ impl CelestiaRollupBlob {
             rollup_id,
             transactions,
             proof,
+            verification_key,
+            signature,
+            rollup_txs_root,
+            transactions_checksum,
+            signature_scheme,
         } = raw;
         let Some(rollup_id) = rollup_id else {
             return Err(CelestiaRollupBlobError::field_not_set("rollup_id"));
         };
         let rollup_id =
             RollupId::try_from_raw(&rollup_id).map_err(CelestiaRollupBlobError::rollup_id)?;
-        let sequencer_block_hash = sequencer_block_hash
+        let sequencer_block_hash: [u8; 32] = sequencer_block_hash
             .try_into()
             .map_err(|bytes: Vec<u8>| CelestiaRollupBlobError::sequencer_block_hash(bytes.len()))?;
+        if sequencer_block_hash == [0; 32] {
+            return Err(CelestiaRollupBlobError::empty_sequencer_block_hash());
+        }
         let proof = 'proof: {
             let Some(proof) = proof else {
                 break 'proof Err(CelestiaRollupBlobError::field_not_set("proof"));
             };
             merkle::Proof::try_from_raw(proof).map_err(CelestiaRollupBlobError::proof)
         }?;
-        Ok(Self {
+        let mut blob = Self {
             sequencer_block_hash,
             rollup_id,
             transactions,
             proof,
-        })
+            signature: None,
+        };
+        if !transactions_checksum.is_empty() {
+            let transactions_checksum: [u8; 4] =
+                transactions_checksum.try_into().map_err(|bytes: Vec<u8>| {
+                    CelestiaRollupBlobError::transactions_checksum(bytes.len())
+                })?;
+            if u32::from_be_bytes(transactions_checksum) != blob.transactions_checksum() {
+                return Err(CelestiaRollupBlobError::transactions_checksum_mismatch());
+            }
+        }
+        if !rollup_txs_root.is_empty() {
+            let rollup_txs_root: [u8; 32] = rollup_txs_root.try_into().map_err(|bytes: Vec<u8>| {
+                CelestiaRollupBlobError::rollup_txs_root(bytes.len())
+            })?;
+            if rollup_txs_root != blob.rollup_txs_root() {
+                return Err(CelestiaRollupBlobError::rollup_txs_root_mismatch());
+            }
+        }
+        if !verification_key.is_empty() || !signature.is_empty() {
+            let scheme = SignatureSchemeId::try_from_raw(signature_scheme)?;
+            scheme
+                .verify(&verification_key, &signature, &blob.signing_message())
+                .map_err(CelestiaRollupBlobError::signature)?;
+            blob.signature = Some(RollupBlobSignature {
+                verification_key: RollupVerificationKey {
+                    scheme,
+                    bytes: verification_key,
+                },
+                signature,
+            });
+        }
+        Ok(blob)
     }
 }
 
@@ -346,7 +966,8 @@ impl CelestiaSequencerBlobError {
 #[derive(Debug, thiserror::Error)]
 enum CelestiaSequencerBlobErrorKind {
     #[error(
-        "the provided bytes were too short for a block hash; expected: 32 bytes, actual: {0} bytes"
+        "the provided bytes did not have the length of a block hash; expected: 32 bytes, actual: \
+         {0} bytes"
     )]
     BlockHash(usize),
     #[error("failed constructing the sequencer block header from its raw source value")]
@@ -537,6 +1158,13 @@ impl CelestiaSequencerBlob {
         self.rollup_ids.contains(&rollup_id)
     }
 
+    /// Returns if the [`SequencerBlock`] this blob was derived from contained no rollup
+    /// transactions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rollup_ids.is_empty()
+    }
+
     /// Converts into the unchecked representation fo this type.
     #[must_use]
     pub fn into_unchecked(self) -> UncheckedCelestiaSequencerBlob {
@@ -625,3 +1253,480 @@ impl CelestiaSequencerBlob {
             .and_then(UncheckedCelestiaSequencerBlob::try_into_celestia_sequencer_blob)
     }
 }
+
+#[derive(Debug, thiserror::Error)]
+#[error("failed constructing a celestia empty blocks marker")]
+#[allow(clippy::module_name_repetitions)]
+pub struct CelestiaEmptyBlocksMarkerError {
+    #[source]
+    kind: CelestiaEmptyBlocksMarkerErrorKind,
+}
+
+impl CelestiaEmptyBlocksMarkerError {
+    fn no_blocks() -> Self {
+        Self {
+            kind: CelestiaEmptyBlocksMarkerErrorKind::NoBlocks,
+        }
+    }
+
+    fn not_empty(height: tendermint::block::Height) -> Self {
+        Self {
+            kind: CelestiaEmptyBlocksMarkerErrorKind::NotEmpty {
+                height,
+            },
+        }
+    }
+
+    fn block(index: usize, source: CelestiaSequencerBlobError) -> Self {
+        Self {
+            kind: CelestiaEmptyBlocksMarkerErrorKind::Block {
+                index,
+                source,
+            },
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum CelestiaEmptyBlocksMarkerErrorKind {
+    #[error("the marker did not contain any blocks")]
+    NoBlocks,
+    #[error("the block at sequencer height `{height}` contained rollup transactions")]
+    NotEmpty { height: tendermint::block::Height },
+    #[error("the sequencer blob at index `{index}` of the marker was invalid")]
+    Block {
+        index: usize,
+        source: CelestiaSequencerBlobError,
+    },
+}
+
+/// A compact marker for a run of sequencer blocks without rollup transactions.
+///
+/// Written under the sequencer namespace in place of one [`CelestiaSequencerBlob`] per empty
+/// block, so that a run of empty blocks takes up a single blob. Every block in the marker is
+/// verified exactly like a [`CelestiaSequencerBlob`], so readers can tell that it is empty and
+/// reconstruct it without a gap in the sequencer heights.
+#[derive(Clone, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CelestiaEmptyBlocksMarker {
+    empty_blocks: Vec<CelestiaSequencerBlob>,
+}
+
+impl CelestiaEmptyBlocksMarker {
+    /// Constructs a marker for `empty_blocks`, which are ordered by sequencer height.
+    ///
+    /// # Errors
+    /// Returns an error if `empty_blocks` is empty or one of its blocks contained rollup
+    /// transactions.
+    pub fn new(
+        mut empty_blocks: Vec<CelestiaSequencerBlob>,
+    ) -> Result<Self, CelestiaEmptyBlocksMarkerError> {
+        if empty_blocks.is_empty() {
+            return Err(CelestiaEmptyBlocksMarkerError::no_blocks());
+        }
+        if let Some(block) = empty_blocks.iter().find(|block| !block.is_empty()) {
+            return Err(CelestiaEmptyBlocksMarkerError::not_empty(block.height()));
+        }
+        empty_blocks.sort_by_key(CelestiaSequencerBlob::height);
+        Ok(Self {
+            empty_blocks,
+        })
+    }
+
+    /// Returns the sequencer blobs of the empty blocks, ordered by sequencer height.
+    #[must_use]
+    pub fn empty_blocks(&self) -> &[CelestiaSequencerBlob] {
+        &self.empty_blocks
+    }
+
+    /// Returns the sequencer blobs of the empty blocks, ordered by sequencer height.
+    #[must_use]
+    pub fn into_empty_blocks(self) -> Vec<CelestiaSequencerBlob> {
+        self.empty_blocks
+    }
+
+    /// Converts into the raw decoded protobuf representation of this type.
+    #[must_use]
+    pub fn into_raw(self) -> raw::CelestiaEmptyBlocksMarker {
+        raw::CelestiaEmptyBlocksMarker {
+            empty_blocks: self
+                .empty_blocks
+                .into_iter()
+                .map(CelestiaSequencerBlob::into_raw)
+                .collect(),
+        }
+    }
+
+    /// Converts from the raw decoded protobuf representation of this type.
+    ///
+    /// # Errors
+    /// Returns an error if the marker contains no blocks, or if one of its blocks is not a valid
+    /// [`CelestiaSequencerBlob`] or contained rollup transactions.
+    pub fn try_from_raw(
+        raw: raw::CelestiaEmptyBlocksMarker,
+    ) -> Result<Self, CelestiaEmptyBlocksMarkerError> {
+        let empty_blocks = raw
+            .empty_blocks
+            .into_iter()
+            .enumerate()
+            .map(|(index, block)| {
+                CelestiaSequencerBlob::try_from_raw(block)
+                    .map_err(|source| CelestiaEmptyBlocksMarkerError::block(index, source))
+            })
+            .collect::<Result<_, _>>()?;
+        Self::new(empty_blocks)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("failed constructing a celestia auxiliary blob")]
+#[allow(clippy::module_name_repetitions)]
+pub struct CelestiaAuxiliaryBlobError {
+    #[source]
+    kind: CelestiaAuxiliaryBlobErrorKind,
+}
+
+impl CelestiaAuxiliaryBlobError {
+    fn sequencer_block_hash(actual_len: usize) -> Self {
+        Self {
+            kind: CelestiaAuxiliaryBlobErrorKind::SequencerBlockHash(actual_len),
+        }
+    }
+
+    fn empty_kind() -> Self {
+        Self {
+            kind: CelestiaAuxiliaryBlobErrorKind::EmptyKind,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum CelestiaAuxiliaryBlobErrorKind {
+    #[error(
+        "the provided bytes did not have the length of a sequencer block hash. Expected: 32 bytes, \
+         provided: {0}"
+    )]
+    SequencerBlockHash(usize),
+    #[error("the kind of the auxiliary blob was empty")]
+    EmptyKind,
+}
+
+/// Operator defined data submitted to celestia alongside a sequencer block.
+///
+/// Auxiliary blobs are posted under their own namespace in the same celestia transaction as
+/// the [`CelestiaSequencerBlob`] and [`CelestiaRollupBlob`]s of the sequencer block they are
+/// bound to. Unlike those, their data is not committed to by the sequencer block and is only
+/// as trustworthy as the account that posted it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CelestiaAuxiliaryBlob {
+    /// The hash of the sequencer block the blob was submitted with.
+    sequencer_block_hash: [u8; 32],
+    /// The operator defined kind of the data, e.g. `batch-proof`.
+    kind: String,
+    /// The opaque data of the blob.
+    data: Vec<u8>,
+}
+
+impl CelestiaAuxiliaryBlob {
+    /// Constructs an auxiliary blob bound to the sequencer block `sequencer_block_hash`.
+    ///
+    /// # Errors
+    /// Returns an error if `kind` is empty.
+    pub fn new(
+        sequencer_block_hash: [u8; 32],
+        kind: String,
+        data: Vec<u8>,
+    ) -> Result<Self, CelestiaAuxiliaryBlobError> {
+        if kind.is_empty() {
+            return Err(CelestiaAuxiliaryBlobError::empty_kind());
+        }
+        Ok(Self {
+            sequencer_block_hash,
+            kind,
+            data,
+        })
+    }
+
+    #[must_use]
+    pub fn sequencer_block_hash(&self) -> [u8; 32] {
+        self.sequencer_block_hash
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Converts to the raw decoded protobuf representation of this type.
+    ///
+    /// Useful for then encoding it as protobuf.
+    #[must_use]
+    pub fn into_raw(self) -> raw::CelestiaAuxiliaryBlob {
+        let Self {
+            sequencer_block_hash,
+            kind,
+            data,
+        } = self;
+        raw::CelestiaAuxiliaryBlob {
+            sequencer_block_hash: sequencer_block_hash.to_vec(),
+            kind,
+            data,
+        }
+    }
+
+    /// Converts from the raw decoded protobuf representation of this type.
+    ///
+    /// # Errors
+    /// Returns an error if the sequencer block hash is not 32 bytes long or if the kind is empty.
+    pub fn try_from_raw(
+        raw: raw::CelestiaAuxiliaryBlob,
+    ) -> Result<Self, CelestiaAuxiliaryBlobError> {
+        let raw::CelestiaAuxiliaryBlob {
+            sequencer_block_hash,
+            kind,
+            data,
+        } = raw;
+        let sequencer_block_hash = sequencer_block_hash.try_into().map_err(|bytes: Vec<u8>| {
+            CelestiaAuxiliaryBlobError::sequencer_block_hash(bytes.len())
+        })?;
+        Self::new(sequencer_block_hash, kind, data)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("failed constructing rollup result namespace data")]
+#[allow(clippy::module_name_repetitions)]
+pub struct RollupResultNamespaceDataError {
+    #[source]
+    kind: RollupResultNamespaceDataErrorKind,
+}
+
+impl RollupResultNamespaceDataError {
+    fn field_not_set(field: &'static str) -> Self {
+        Self {
+            kind: RollupResultNamespaceDataErrorKind::FieldNotSet {
+                field,
+            },
+        }
+    }
+
+    fn rollup_id(source: IncorrectRollupIdLength) -> Self {
+        Self {
+            kind: RollupResultNamespaceDataErrorKind::RollupId {
+                source,
+            },
+        }
+    }
+
+    fn hash_length(field: &'static str, actual_len: usize) -> Self {
+        Self {
+            kind: RollupResultNamespaceDataErrorKind::HashLength {
+                field,
+                actual_len,
+            },
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum RollupResultNamespaceDataErrorKind {
+    #[error("the expected field in the raw source type was not set: `{field}`")]
+    FieldNotSet { field: &'static str },
+    #[error("failed converting the provided bytes to Rollup ID")]
+    RollupId { source: IncorrectRollupIdLength },
+    #[error("field `{field}` must be 32 bytes, but was {actual_len} bytes")]
+    HashLength {
+        field: &'static str,
+        actual_len: usize,
+    },
+}
+
+/// A shadow of [`RollupResultNamespaceData`] with public access to all its fields.
+///
+/// At the moment there are no invariants upheld by [`RollupResultNamespaceData`] so
+/// they can be converted directly into one another. This can change in the future.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UncheckedRollupResultNamespaceData {
+    /// The hash of the sequencer block the rollup block was derived from.
+    pub sequencer_block_hash: [u8; 32],
+    /// The rollup that executed the block.
+    pub rollup_id: RollupId,
+    /// The number of the executed rollup block.
+    pub rollup_block_number: u64,
+    /// The hash of the executed rollup block.
+    pub rollup_block_hash: [u8; 32],
+    /// The state root after executing the rollup block.
+    pub state_root: [u8; 32],
+    /// The root of the receipts of executing the rollup block.
+    pub receipts_root: [u8; 32],
+}
+
+impl UncheckedRollupResultNamespaceData {
+    #[must_use]
+    pub fn into_rollup_result_namespace_data(self) -> RollupResultNamespaceData {
+        RollupResultNamespaceData::from_unchecked(self)
+    }
+}
+
+/// The result of executing a rollup block, written to celestia so that the state of a rollup
+/// can be checked against data availability alone.
+///
+/// Results are written under [`crate::celestia::rollup_result_namespace`] of their rollup. Like
+/// [`CelestiaAuxiliaryBlob`]s, they are not committed to by the sequencer block and are only
+/// as trustworthy as the account that posted them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RollupResultNamespaceData {
+    sequencer_block_hash: [u8; 32],
+    rollup_id: RollupId,
+    rollup_block_number: u64,
+    rollup_block_hash: [u8; 32],
+    state_root: [u8; 32],
+    receipts_root: [u8; 32],
+}
+
+impl RollupResultNamespaceData {
+    #[must_use]
+    pub fn sequencer_block_hash(&self) -> [u8; 32] {
+        self.sequencer_block_hash
+    }
+
+    #[must_use]
+    pub fn rollup_id(&self) -> RollupId {
+        self.rollup_id
+    }
+
+    #[must_use]
+    pub fn rollup_block_number(&self) -> u64 {
+        self.rollup_block_number
+    }
+
+    #[must_use]
+    pub fn rollup_block_hash(&self) -> [u8; 32] {
+        self.rollup_block_hash
+    }
+
+    #[must_use]
+    pub fn state_root(&self) -> [u8; 32] {
+        self.state_root
+    }
+
+    #[must_use]
+    pub fn receipts_root(&self) -> [u8; 32] {
+        self.receipts_root
+    }
+
+    /// Converts from the unchecked representation of this type (its shadow).
+    ///
+    /// This type does not uphold any extra invariants so there are no extra checks necessary.
+    #[must_use]
+    pub fn from_unchecked(unchecked: UncheckedRollupResultNamespaceData) -> Self {
+        let UncheckedRollupResultNamespaceData {
+            sequencer_block_hash,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash,
+            state_root,
+            receipts_root,
+        } = unchecked;
+        Self {
+            sequencer_block_hash,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash,
+            state_root,
+            receipts_root,
+        }
+    }
+
+    /// Converts to the unchecked representation of this type (its shadow).
+    ///
+    /// Useful to get public access to the type's fields.
+    #[must_use]
+    pub fn into_unchecked(self) -> UncheckedRollupResultNamespaceData {
+        let Self {
+            sequencer_block_hash,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash,
+            state_root,
+            receipts_root,
+        } = self;
+        UncheckedRollupResultNamespaceData {
+            sequencer_block_hash,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash,
+            state_root,
+            receipts_root,
+        }
+    }
+
+    /// Converts to the raw decoded protobuf representation of this type.
+    ///
+    /// Useful for then encoding it as protobuf.
+    #[must_use]
+    pub fn into_raw(self) -> raw::RollupResultNamespaceData {
+        let Self {
+            sequencer_block_hash,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash,
+            state_root,
+            receipts_root,
+        } = self;
+        raw::RollupResultNamespaceData {
+            sequencer_block_hash: sequencer_block_hash.to_vec(),
+            rollup_id: Some(rollup_id.to_raw()),
+            rollup_block_number,
+            rollup_block_hash: rollup_block_hash.to_vec(),
+            state_root: state_root.to_vec(),
+            receipts_root: receipts_root.to_vec(),
+        }
+    }
+
+    /// Converts from the raw decoded protobuf representation of this type.
+    ///
+    /// # Errors
+    /// Returns an error if the rollup ID is not set or invalid, or if any of the hashes and
+    /// roots is not 32 bytes long.
+    pub fn try_from_raw(
+        raw: raw::RollupResultNamespaceData,
+    ) -> Result<Self, RollupResultNamespaceDataError> {
+        fn hash(
+            field: &'static str,
+            bytes: Vec<u8>,
+        ) -> Result<[u8; 32], RollupResultNamespaceDataError> {
+            bytes.try_into().map_err(|bytes: Vec<u8>| {
+                RollupResultNamespaceDataError::hash_length(field, bytes.len())
+            })
+        }
+
+        let raw::RollupResultNamespaceData {
+            sequencer_block_hash,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash,
+            state_root,
+            receipts_root,
+        } = raw;
+        let Some(rollup_id) = rollup_id else {
+            return Err(RollupResultNamespaceDataError::field_not_set("rollup_id"));
+        };
+        let rollup_id =
+            RollupId::try_from_raw(&rollup_id).map_err(RollupResultNamespaceDataError::rollup_id)?;
+        Ok(Self {
+            sequencer_block_hash: hash("sequencer_block_hash", sequencer_block_hash)?,
+            rollup_id,
+            rollup_block_number,
+            rollup_block_hash: hash("rollup_block_hash", rollup_block_hash)?,
+            state_root: hash("state_root", state_root)?,
+            receipts_root: hash("receipts_root", receipts_root)?,
+        })
+    }
+}
@@ -0,0 +1,249 @@
+//! Extraction of rollup data from the transactions of a sequencer block.
+//!
+//! The transactions in the data of a sequencer block carry the payloads destined for rollups as
+//! [`action::SequenceAction`]s. [`group_rollup_data_by_rollup_id`] collects these payloads per
+//! rollup, which is how they are committed to in the block and written to Celestia. The Celestia
+//! namespace of each rollup is derived from its ID with
+//! [`namespace_v0_from_rollup_id`](crate::celestia::namespace_v0_from_rollup_id).
+
+use std::collections::BTreeMap;
+
+use prost::Message as _;
+use sha2::{
+    Digest as _,
+    Sha256,
+};
+
+use super::block::RollupData;
+use crate::{
+    generated::protocol::transaction::v1alpha1 as raw,
+    primitive::v1::RollupId,
+    protocol::transaction::v1alpha1::{
+        action,
+        SignedTransaction,
+        SignedTransactionError,
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct ExtractError(ExtractErrorKind);
+
+impl ExtractError {
+    fn protobuf_decode(source: prost::DecodeError) -> Self {
+        Self(ExtractErrorKind::ProtobufDecode(source))
+    }
+
+    fn signed_transaction(source: SignedTransactionError) -> Self {
+        Self(ExtractErrorKind::SignedTransaction(source))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ExtractErrorKind {
+    #[error("failed decoding transaction as a protobuf signed astria transaction")]
+    ProtobufDecode(#[source] prost::DecodeError),
+    #[error(
+        "failed converting the raw protobuf signed transaction to a native astria signed \
+         transaction"
+    )]
+    SignedTransaction(#[source] SignedTransactionError),
+}
+
+/// A transaction whose rollup data could not be extracted because it could not be decoded.
+#[derive(Debug)]
+pub struct QuarantinedTransaction {
+    /// The position of the transaction among the transactions passed to
+    /// [`group_rollup_data_by_rollup_id`].
+    pub index: usize,
+    /// The CometBFT hash of the transaction, i.e. the sha256 digest of its bytes.
+    pub hash: [u8; 32],
+    /// Why the transaction could not be decoded.
+    pub error: ExtractError,
+}
+
+/// The rollup data extracted from the transactions of a sequencer block.
+#[derive(Debug, Default)]
+pub struct ExtractedRollupData {
+    /// The payloads of all sequence actions, grouped by the ID of the rollup they are destined
+    /// for.
+    pub rollup_data: BTreeMap<RollupId, Vec<Vec<u8>>>,
+    /// The transactions that could not be decoded, in block order.
+    pub quarantined: Vec<QuarantinedTransaction>,
+}
+
+/// Groups the payloads of the sequence actions in `txs` by the ID of the rollup they are destined
+/// for.
+///
+/// `txs` are the protobuf encoded signed transactions of a sequencer block, i.e. the CometBFT
+/// block data without the rollup transactions and rollup IDs commitments preceding them. Each
+/// payload is encoded as [`RollupData::SequencedData`], in the order in which it appears in the
+/// block. Actions other than sequence actions are ignored.
+///
+/// Transactions that cannot be decoded do not fail the extraction. Sequencer excludes them from
+/// its blocks, so a block containing them was built by a faulty proposer and their payloads were
+/// not committed to. They are reported in [`ExtractedRollupData::quarantined`] instead.
+pub fn group_rollup_data_by_rollup_id<I, B>(txs: I) -> ExtractedRollupData
+where
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]>,
+{
+    let mut extracted = ExtractedRollupData::default();
+    for (index, tx) in txs.into_iter().enumerate() {
+        let signed_tx = match decode_signed_transaction(tx.as_ref()) {
+            Ok(signed_tx) => signed_tx,
+            Err(error) => {
+                extracted.quarantined.push(QuarantinedTransaction {
+                    index,
+                    hash: Sha256::digest(tx.as_ref()).into(),
+                    error,
+                });
+                continue;
+            }
+        };
+        for action in signed_tx.into_unsigned().actions {
+            if let action::Action::Sequence(action::SequenceAction {
+                rollup_id,
+                data,
+                fee_asset_id: _,
+            }) = action
+            {
+                extracted
+                    .rollup_data
+                    .entry(rollup_id)
+                    .or_insert_with(Vec::new)
+                    .push(RollupData::SequencedData(data).into_raw().encode_to_vec());
+            }
+        }
+    }
+    extracted
+}
+
+fn decode_signed_transaction(bytes: &[u8]) -> Result<SignedTransaction, ExtractError> {
+    let raw_tx = raw::SignedTransaction::decode(bytes).map_err(ExtractError::protobuf_decode)?;
+    SignedTransaction::try_from_raw(raw_tx).map_err(ExtractError::signed_transaction)
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_consensus::SigningKey;
+    use prost::Message as _;
+    use sha2::{
+        Digest as _,
+        Sha256,
+    };
+
+    use super::group_rollup_data_by_rollup_id;
+    use crate::{
+        primitive::v1::{
+            asset::default_native_asset_id,
+            Address,
+            RollupId,
+        },
+        protocol::transaction::v1alpha1::{
+            action::{
+                Action,
+                SequenceAction,
+                TransferAction,
+            },
+            TransactionParams,
+            UnsignedTransaction,
+        },
+        sequencerblock::v1alpha1::block::RollupData,
+    };
+
+    const ROLLUP_A: RollupId = RollupId::new([1; 32]);
+    const ROLLUP_B: RollupId = RollupId::new([2; 32]);
+
+    fn sequence(rollup_id: RollupId, data: &[u8]) -> Action {
+        SequenceAction {
+            rollup_id,
+            data: data.to_vec(),
+            fee_asset_id: default_native_asset_id(),
+        }
+        .into()
+    }
+
+    fn encoded_tx(nonce: u32, actions: Vec<Action>) -> Vec<u8> {
+        UnsignedTransaction {
+            actions,
+            params: TransactionParams {
+                nonce,
+                chain_id: "test".to_string(),
+            },
+        }
+        .into_signed(&SigningKey::from([1; 32]))
+        .into_raw()
+        .encode_to_vec()
+    }
+
+    fn sequenced(data: &[u8]) -> Vec<u8> {
+        RollupData::SequencedData(data.to_vec())
+            .into_raw()
+            .encode_to_vec()
+    }
+
+    /// The transactions of a block with payloads for two rollups spread over two transactions,
+    /// and a transfer that carries no rollup data.
+    fn fixture_block_txs() -> Vec<Vec<u8>> {
+        let transfer = TransferAction {
+            to: Address::from([3; 20]),
+            amount: 100,
+            asset_id: default_native_asset_id(),
+            fee_asset_id: default_native_asset_id(),
+        };
+        vec![
+            encoded_tx(
+                0,
+                vec![
+                    sequence(ROLLUP_A, b"a-0"),
+                    transfer.into(),
+                    sequence(ROLLUP_B, b"b-0"),
+                ],
+            ),
+            encoded_tx(1, vec![sequence(ROLLUP_A, b"a-1")]),
+        ]
+    }
+
+    #[test]
+    fn payloads_are_grouped_by_rollup_id_in_block_order() {
+        let extracted = group_rollup_data_by_rollup_id(fixture_block_txs());
+        assert!(extracted.quarantined.is_empty());
+
+        let rollup_datas = extracted.rollup_data;
+        assert_eq!(2, rollup_datas.len());
+        assert_eq!(
+            vec![sequenced(b"a-0"), sequenced(b"a-1")],
+            rollup_datas[&ROLLUP_A]
+        );
+        assert_eq!(vec![sequenced(b"b-0")], rollup_datas[&ROLLUP_B]);
+    }
+
+    #[test]
+    fn block_without_sequence_actions_has_no_rollup_data() {
+        let txs: Vec<Vec<u8>> = vec![];
+        let extracted = group_rollup_data_by_rollup_id(txs);
+        assert!(extracted.rollup_data.is_empty());
+        assert!(extracted.quarantined.is_empty());
+    }
+
+    #[test]
+    fn undecodable_tx_is_quarantined() {
+        let undecodable = vec![0xff; 8];
+        let mut txs = fixture_block_txs();
+        txs.insert(1, undecodable.clone());
+        let extracted = group_rollup_data_by_rollup_id(txs);
+
+        assert_eq!(1, extracted.quarantined.len());
+        let quarantined = &extracted.quarantined[0];
+        assert_eq!(1, quarantined.index);
+        assert_eq!(<[u8; 32]>::from(Sha256::digest(&undecodable)), quarantined.hash);
+
+        // the data of the decodable transactions around it is still extracted
+        assert_eq!(
+            vec![sequenced(b"a-0"), sequenced(b"a-1")],
+            extracted.rollup_data[&ROLLUP_A]
+        );
+        assert_eq!(vec![sequenced(b"b-0")], extracted.rollup_data[&ROLLUP_B]);
+    }
+}
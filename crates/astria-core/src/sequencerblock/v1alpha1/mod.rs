@@ -1,15 +1,32 @@
 pub mod block;
+#[cfg(feature = "serde")]
+pub mod blob_format;
 pub mod celestia;
+pub mod extract;
 
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+pub use blob_format::{
+    BlobFormat,
+    NamespaceData,
+};
 pub use block::{
     RollupTransactions,
     SequencerBlock,
 };
 pub use celestia::{
+    CelestiaAuxiliaryBlob,
+    CelestiaEmptyBlocksMarker,
     CelestiaRollupBlob,
     CelestiaSequencerBlob,
+    RollupBlobSignature,
+    RollupResultNamespaceData,
+    RollupVerificationKey,
+    SignatureError,
+    SignatureScheme,
+    SignatureSchemeId,
 };
-use indexmap::IndexMap;
 use sha2::{
     Digest as _,
     Sha256,
@@ -38,7 +55,7 @@ where
 }
 
 pub(crate) fn are_rollup_txs_included(
-    rollup_datas: &IndexMap<RollupId, RollupTransactions>,
+    rollup_datas: &BTreeMap<RollupId, RollupTransactions>,
     rollup_proof: &merkle::Proof,
     data_hash: [u8; 32],
 ) -> bool {
@@ -1,3 +1,4 @@
+use prost::Message as _;
 use sha2::Digest as _;
 
 use super::*;
@@ -58,3 +59,303 @@ fn block_to_filtered_roundtrip() {
 
     assert_eq!(filtered_sequencer_block, from_raw);
 }
+
+#[test]
+fn signed_rollup_blob_roundtrips_and_rejects_tampering() {
+    let (_, mut rollup_blobs) = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let mut blob = rollup_blobs.pop().unwrap();
+    let signing_key = ed25519_consensus::SigningKey::from([1; 32]);
+    blob.sign(&signing_key);
+
+    let from_raw = CelestiaRollupBlob::try_from_raw(blob.clone().into_raw()).unwrap();
+    assert_eq!(
+        &RollupVerificationKey::from(signing_key.verification_key()),
+        from_raw.signature().unwrap().verification_key(),
+    );
+
+    let mut tampered = blob.into_raw();
+    tampered.transactions.push(vec![1, 2, 3]);
+    // Only the signature covers the transactions once the root and checksum are dropped.
+    tampered.rollup_txs_root.clear();
+    tampered.transactions_checksum.clear();
+    assert!(CelestiaRollupBlob::try_from_raw(tampered).is_err());
+}
+
+#[test]
+fn secp256k1_signed_rollup_blob_roundtrips_and_rejects_tampering() {
+    let (_, mut rollup_blobs) = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let mut blob = rollup_blobs.pop().unwrap();
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap();
+    blob.sign(&signing_key);
+
+    let raw = blob.into_raw();
+    assert_eq!(i32::from(raw::SignatureScheme::Secp256k1), raw.signature_scheme);
+    let from_raw = CelestiaRollupBlob::try_from_raw(raw.clone()).unwrap();
+    let signature = from_raw.signature().unwrap();
+    assert_eq!(SignatureSchemeId::Secp256k1, signature.scheme());
+    assert_eq!(
+        &RollupVerificationKey::from(*signing_key.verifying_key()),
+        signature.verification_key(),
+    );
+
+    // A secp256k1 key is not a valid ed25519 key.
+    let mut wrong_scheme = raw.clone();
+    wrong_scheme.signature_scheme = raw::SignatureScheme::Ed25519.into();
+    assert!(
+        CelestiaRollupBlob::try_from_raw(wrong_scheme)
+            .unwrap_err()
+            .is_malformed_signature()
+    );
+
+    let mut unknown_scheme = raw.clone();
+    unknown_scheme.signature_scheme = 42;
+    assert!(
+        CelestiaRollupBlob::try_from_raw(unknown_scheme)
+            .unwrap_err()
+            .is_malformed_signature()
+    );
+
+    let mut tampered = raw;
+    tampered.transactions.push(vec![1, 2, 3]);
+    tampered.rollup_txs_root.clear();
+    tampered.transactions_checksum.clear();
+    assert!(
+        CelestiaRollupBlob::try_from_raw(tampered)
+            .unwrap_err()
+            .is_signature_mismatch()
+    );
+}
+
+#[test]
+fn rollup_blob_roundtrips_with_rollup_txs_root_and_rejects_tampering() {
+    let sequencer_block = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make();
+    let rollup_transactions_root = sequencer_block.header().rollup_transactions_root();
+    let (_, mut rollup_blobs) = sequencer_block.into_celestia_blobs();
+    let blob = rollup_blobs.pop().unwrap();
+
+    // The root is the one the blob's proof commits to in the sequencer block.
+    assert!(
+        blob.proof()
+            .audit()
+            .with_root(rollup_transactions_root)
+            .with_leaf_builder()
+            .write(blob.rollup_id().as_ref())
+            .write(&blob.rollup_txs_root())
+            .finish_leaf()
+            .perform()
+    );
+
+    let raw = blob.clone().into_raw();
+    assert_eq!(blob.rollup_txs_root().to_vec(), raw.rollup_txs_root);
+    CelestiaRollupBlob::try_from_raw(raw.clone()).unwrap();
+
+    let mut without_root = raw.clone();
+    without_root.rollup_txs_root.clear();
+    CelestiaRollupBlob::try_from_raw(without_root).unwrap();
+
+    let mut tampered = raw.clone();
+    tampered.transactions.push(vec![1, 2, 3]);
+    tampered.transactions_checksum.clear();
+    assert!(CelestiaRollupBlob::try_from_raw(tampered).is_err());
+
+    let mut truncated_root = raw;
+    truncated_root.rollup_txs_root.pop();
+    assert!(CelestiaRollupBlob::try_from_raw(truncated_root).is_err());
+}
+
+#[test]
+fn rollup_blob_with_corrupted_transactions_fails_checksum() {
+    let (_, mut rollup_blobs) = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let blob = rollup_blobs.pop().unwrap();
+
+    let raw = blob.clone().into_raw();
+    assert_eq!(
+        blob.transactions_checksum().to_be_bytes().to_vec(),
+        raw.transactions_checksum
+    );
+
+    let mut without_checksum = raw.clone();
+    without_checksum.transactions_checksum.clear();
+    CelestiaRollupBlob::try_from_raw(without_checksum).unwrap();
+
+    // Flipping a bit is caught by the checksum even if the root is dropped.
+    let mut corrupted = raw.clone();
+    corrupted.transactions[0][0] ^= 1;
+    corrupted.rollup_txs_root.clear();
+    let error = CelestiaRollupBlob::try_from_raw(corrupted).unwrap_err();
+    assert!(
+        format!("{error:?}").contains("TransactionsChecksumMismatch"),
+        "unexpected error: {error:?}"
+    );
+
+    let mut truncated_checksum = raw;
+    truncated_checksum.transactions_checksum.pop();
+    assert!(CelestiaRollupBlob::try_from_raw(truncated_checksum).is_err());
+}
+
+#[test]
+fn empty_blocks_marker_roundtrips_and_rejects_blocks_with_rollup_data() {
+    let empty_block = |height| {
+        ConfigureSequencerBlock {
+            height,
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs()
+        .0
+    };
+    let marker = CelestiaEmptyBlocksMarker::new(vec![empty_block(3), empty_block(2)]).unwrap();
+    let heights: Vec<_> = marker
+        .empty_blocks()
+        .iter()
+        .map(|block| block.height().value())
+        .collect();
+    assert_eq!(vec![2, 3], heights);
+
+    let from_raw = CelestiaEmptyBlocksMarker::try_from_raw(marker.clone().into_raw()).unwrap();
+    assert_eq!(
+        marker.into_raw().encode_to_vec(),
+        from_raw.into_raw().encode_to_vec(),
+    );
+
+    let (non_empty_block, _) = ConfigureSequencerBlock {
+        height: 4,
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    assert!(CelestiaEmptyBlocksMarker::new(vec![empty_block(2), non_empty_block]).is_err());
+    assert!(CelestiaEmptyBlocksMarker::new(vec![]).is_err());
+}
+
+#[test]
+fn empty_blocks_marker_and_sequencer_blob_do_not_decode_as_each_other() {
+    let (sequencer_blob, _) = ConfigureSequencerBlock {
+        height: 2,
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let marker = CelestiaEmptyBlocksMarker::new(vec![sequencer_blob.clone()]).unwrap();
+
+    let marker_bytes = marker.into_raw().encode_to_vec();
+    let as_sequencer_blob = raw::CelestiaSequencerBlob::decode(&*marker_bytes).unwrap();
+    assert!(CelestiaSequencerBlob::try_from_raw(as_sequencer_blob).is_err());
+
+    let sequencer_blob_bytes = sequencer_blob.into_raw().encode_to_vec();
+    let as_marker = raw::CelestiaEmptyBlocksMarker::decode(&*sequencer_blob_bytes).unwrap();
+    assert!(CelestiaEmptyBlocksMarker::try_from_raw(as_marker).is_err());
+}
+
+#[test]
+fn checked_rollup_blob_constructors_uphold_invariants() {
+    let (_, mut rollup_blobs) = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let blob = rollup_blobs.pop().unwrap();
+
+    let new = CelestiaRollupBlob::try_new(
+        blob.sequencer_block_hash(),
+        blob.rollup_id(),
+        blob.transactions().to_vec(),
+        blob.proof().clone(),
+    )
+    .unwrap();
+    assert_eq!(blob.rollup_txs_root(), new.rollup_txs_root());
+    assert!(new.signature().is_none());
+
+    let empty_hash = CelestiaRollupBlob::try_new(
+        [0; 32],
+        blob.rollup_id(),
+        blob.transactions().to_vec(),
+        blob.proof().clone(),
+    )
+    .unwrap_err();
+    assert!(
+        format!("{empty_hash:?}").contains("EmptySequencerBlockHash"),
+        "unexpected error: {empty_hash:?}"
+    );
+
+    let too_many = CelestiaRollupBlob::try_new(
+        blob.sequencer_block_hash(),
+        blob.rollup_id(),
+        vec![vec![]; celestia::MAX_ROLLUP_TRANSACTIONS_PER_BLOB + 1],
+        blob.proof().clone(),
+    )
+    .unwrap_err();
+    assert!(
+        format!("{too_many:?}").contains("TooManyTransactions"),
+        "unexpected error: {too_many:?}"
+    );
+
+    let mut signed = blob;
+    signed.sign(&k256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap());
+    let mut tampered = signed.clone().into_unchecked();
+    CelestiaRollupBlob::try_from_unchecked(signed.into_unchecked()).unwrap();
+    tampered.transactions.push(vec![1, 2, 3]);
+    assert!(
+        CelestiaRollupBlob::try_from_unchecked(tampered)
+            .unwrap_err()
+            .is_signature_mismatch()
+    );
+}
+
+#[test]
+fn decoding_rollup_blob_upholds_invariants() {
+    let (_, mut rollup_blobs) = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1, 2, 3])],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let raw = rollup_blobs.pop().unwrap().into_raw();
+
+    let mut empty_hash = raw;
+    empty_hash.sequencer_block_hash = vec![0; 32];
+    let error = CelestiaRollupBlob::try_from_raw(empty_hash).unwrap_err();
+    assert!(
+        format!("{error:?}").contains("EmptySequencerBlockHash"),
+        "unexpected error: {error:?}"
+    );
+}
+
+#[test]
+fn rollup_blob_beyond_transaction_limit_of_constructors_roundtrips() {
+    let number_of_transactions = celestia::MAX_ROLLUP_TRANSACTIONS_PER_BLOB + 1;
+    let (_, mut rollup_blobs) = ConfigureSequencerBlock {
+        sequence_data: vec![(RollupId::new([2; 32]), vec![1]); number_of_transactions],
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    let blob = rollup_blobs.pop().unwrap();
+    assert_eq!(number_of_transactions, blob.transactions().len());
+
+    let bytes = blob.clone().into_raw().encode_to_vec();
+    let decoded = raw::CelestiaRollupBlob::decode(&*bytes).unwrap();
+    let from_raw = CelestiaRollupBlob::try_from_raw(decoded).unwrap();
+    assert_eq!(blob.into_raw(), from_raw.into_raw());
+}
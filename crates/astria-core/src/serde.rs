@@ -1,11 +1,358 @@
+//! Serde adapters for the encodings of byte fields used in Astria's JSON formats.
+//!
+//! The modules are intended for use with `#[serde(with = "...")]`:
+//!
+//! ```
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Blob {
+//!     #[serde(with = "astria_core::serde::hex")]
+//!     block_hash: [u8; 32],
+//!     #[serde(with = "astria_core::serde::base64")]
+//!     data: Vec<u8>,
+//!     #[serde(default, with = "astria_core::serde::option_base64")]
+//!     proof: Option<Vec<u8>>,
+//! }
+//! ```
+//!
+//! Bytes are deserialized into any type implementing `TryFrom<Vec<u8>>`, so that fixed size
+//! arrays are checked for their length.
+//!
+//! Payloads of several megabytes should use [`streaming_base64`] instead of [`base64`], which
+//! avoids holding the encoded and decoded payload in memory at the same time where possible.
+
 use base64_serde::base64_serde_type;
-use serde::Serializer;
 
-base64_serde_type!(pub(crate) Base64Standard, base64::engine::general_purpose::STANDARD);
-pub(crate) fn base64<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+#[cfg(feature = "benchmark")]
+mod benchmarks;
+
+base64_serde_type!(pub Base64Standard, ::base64::engine::general_purpose::STANDARD);
+
+fn try_from_bytes<'de, T, D>(bytes: Vec<u8>) -> Result<T, D::Error>
 where
-    T: AsRef<[u8]>,
-    S: Serializer,
+    T: TryFrom<Vec<u8>>,
+    D: serde::Deserializer<'de>,
 {
-    Base64Standard::serialize(value, serializer)
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| {
+        serde::de::Error::invalid_length(len, &"a byte sequence of the expected length")
+    })
+}
+
+/// Standard base64 encoding with padding.
+pub mod base64 {
+    use serde::{
+        Deserializer,
+        Serializer,
+    };
+
+    use super::Base64Standard;
+
+    /// Serializes `value` as a standard base64 string.
+    ///
+    /// # Errors
+    /// Returns the errors of the underlying serializer.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        Base64Standard::serialize(value, serializer)
+    }
+
+    /// Deserializes a standard base64 string.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not valid base64 or has the wrong length for `T`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        let bytes = Base64Standard::deserialize(deserializer)?;
+        super::try_from_bytes::<T, D>(bytes)
+    }
+}
+
+/// Standard base64 encoding with padding, for large payloads.
+///
+/// Produces and accepts the same strings as [`base64`], but is cheaper on memory:
+///
+/// + serializing streams the encoding into the serializer instead of materializing it as a
+///   string first. `serde_json` writes it straight to its output; serializers that do not
+///   support streaming fall back to building the string.
+/// + [`deserialize_into`] decodes into a caller provided buffer, so that a buffer can be reused
+///   across payloads instead of allocating a new one for each.
+pub mod streaming_base64 {
+    use std::fmt;
+
+    use base64::{
+        display::Base64Display,
+        engine::general_purpose::STANDARD,
+        Engine as _,
+    };
+    use serde::{
+        de,
+        Deserializer,
+        Serializer,
+    };
+
+    /// Serializes `value` as a standard base64 string, encoding it chunk by chunk.
+    ///
+    /// # Errors
+    /// Returns the errors of the underlying serializer.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.collect_str(&Base64Display::new(value.as_ref(), &STANDARD))
+    }
+
+    /// Deserializes a standard base64 string.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not valid base64 or has the wrong length for `T`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        let mut bytes = Vec::new();
+        deserialize_into(deserializer, &mut bytes)?;
+        super::try_from_bytes::<T, D>(bytes)
+    }
+
+    /// Deserializes a standard base64 string into `buffer`, replacing its contents.
+    ///
+    /// The capacity of `buffer` is reused, so decoding a payload no larger than the previous one
+    /// does not allocate.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not valid base64. The contents of `buffer` are
+    /// unspecified in that case.
+    pub fn deserialize_into<'de, D>(deserializer: D, buffer: &mut Vec<u8>) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Visitor(buffer))
+    }
+
+    struct Visitor<'a>(&'a mut Vec<u8>);
+
+    impl<'de> de::Visitor<'de> for Visitor<'_> {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a standard base64 string")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<(), E>
+        where
+            E: de::Error,
+        {
+            self.visit_bytes(value.as_bytes())
+        }
+
+        fn visit_bytes<E>(self, value: &[u8]) -> Result<(), E>
+        where
+            E: de::Error,
+        {
+            self.0.clear();
+            STANDARD.decode_vec(value, self.0).map_err(E::custom)
+        }
+    }
+}
+
+/// Lower case hex encoding, accepting an optional `0x` prefix when deserializing.
+pub mod hex {
+    use serde::{
+        Deserialize as _,
+        Deserializer,
+        Serializer,
+    };
+
+    /// Serializes `value` as a lower case hex string without prefix.
+    ///
+    /// # Errors
+    /// Returns the errors of the underlying serializer.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.serialize_str(&::hex::encode(value))
+    }
+
+    /// Deserializes a hex string with or without `0x` prefix.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not valid hex or has the wrong length for `T`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        let input = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        let bytes = ::hex::decode(input.strip_prefix("0x").unwrap_or(&input))
+            .map_err(serde::de::Error::custom)?;
+        super::try_from_bytes::<T, D>(bytes)
+    }
+}
+
+/// [`base64`] for optional fields, mapping `None` to `null`.
+pub mod option_base64 {
+    use serde::{
+        Deserialize as _,
+        Deserializer,
+        Serializer,
+    };
+
+    /// Serializes `Some` values as standard base64 strings, and `None` as `null`.
+    ///
+    /// # Errors
+    /// Returns the errors of the underlying serializer.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::base64::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes `null` as `None`, and anything else as a standard base64 string.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not valid base64 or has the wrong length for `T`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Wrapper(#[serde(with = "super::Base64Standard")] Vec<u8>);
+
+        Option::<Wrapper>::deserialize(deserializer)?
+            .map(|Wrapper(bytes)| super::try_from_bytes::<T, D>(bytes))
+            .transpose()
+    }
+}
+
+/// [`hex`] for optional fields, mapping `None` to `null`.
+pub mod option_hex {
+    use serde::{
+        Deserialize as _,
+        Deserializer,
+        Serializer,
+    };
+
+    /// Serializes `Some` values as lower case hex strings, and `None` as `null`.
+    ///
+    /// # Errors
+    /// Returns the errors of the underlying serializer.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::hex::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes `null` as `None`, and anything else as a hex string.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not valid hex or has the wrong length for `T`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Wrapper(#[serde(with = "super::hex")] Vec<u8>);
+
+        Option::<Wrapper>::deserialize(deserializer)?
+            .map(|Wrapper(bytes)| super::try_from_bytes::<T, D>(bytes))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{
+        engine::general_purpose::STANDARD,
+        Engine as _,
+    };
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Blob {
+        #[serde(with = "super::hex")]
+        block_hash: [u8; 4],
+        #[serde(with = "super::base64")]
+        data: Vec<u8>,
+        #[serde(default, with = "super::option_base64")]
+        proof: Option<Vec<u8>>,
+        #[serde(default, with = "super::option_hex")]
+        parent: Option<[u8; 2]>,
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let blob = Blob {
+            block_hash: [0xde, 0xad, 0xbe, 0xef],
+            data: vec![1, 2, 3],
+            proof: Some(vec![4]),
+            parent: None,
+        };
+        let json = serde_json::to_string(&blob).unwrap();
+        assert_eq!(
+            r#"{"block_hash":"deadbeef","data":"AQID","proof":"BA==","parent":null}"#,
+            json,
+        );
+        assert_eq!(blob, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn hex_accepts_prefix_and_checks_length() {
+        let blob: Blob =
+            serde_json::from_str(r#"{"block_hash":"0xdeadbeef","data":"","parent":"0x0102"}"#)
+                .unwrap();
+        assert_eq!([0xde, 0xad, 0xbe, 0xef], blob.block_hash);
+        assert_eq!(Some([1, 2]), blob.parent);
+        assert!(serde_json::from_str::<Blob>(r#"{"block_hash":"dead","data":""}"#).is_err());
+    }
+
+    #[test]
+    fn streaming_base64_matches_base64() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Streamed {
+            #[serde(with = "super::streaming_base64")]
+            data: Vec<u8>,
+        }
+
+        let streamed = Streamed {
+            data: (0..=255).collect(),
+        };
+        let json = serde_json::to_string(&streamed).unwrap();
+        assert_eq!(
+            format!(r#"{{"data":"{}"}}"#, STANDARD.encode(&streamed.data)),
+            json
+        );
+        assert_eq!(streamed, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<Streamed>(r#"{"data":"not base64"}"#).is_err());
+    }
+
+    #[test]
+    fn streaming_base64_reuses_buffer() {
+        let mut buffer = Vec::with_capacity(16);
+        let capacity = buffer.capacity();
+        let mut deserializer = serde_json::Deserializer::from_str(r#""AQID""#);
+        super::streaming_base64::deserialize_into(&mut deserializer, &mut buffer).unwrap();
+        assert_eq!(vec![1, 2, 3], buffer);
+        assert_eq!(capacity, buffer.capacity());
+    }
 }
@@ -0,0 +1,76 @@
+//! Benchmarks comparing [`Base64Standard`](super::Base64Standard) and
+//! [`streaming_base64`](super::streaming_base64) on a large payload.
+//!
+//! Run with `cargo bench --features benchmark -p astria-core`. The benchmark binary installs
+//! divan's allocation profiler, so the bytes allocated per iteration are reported alongside the
+//! timings.
+
+use base64::{
+    engine::general_purpose::STANDARD,
+    Engine as _,
+};
+
+/// The size of the benchmarked payload, on the order of a large Celestia blob.
+const PAYLOAD_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Materialized {
+    #[serde(with = "super::Base64Standard")]
+    payload: Vec<u8>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Streamed {
+    #[serde(with = "super::streaming_base64")]
+    payload: Vec<u8>,
+}
+
+fn payload() -> Vec<u8> {
+    std::iter::successors(Some(0u8), |byte| Some(byte.wrapping_add(1)))
+        .take(PAYLOAD_SIZE)
+        .collect()
+}
+
+#[divan::bench]
+fn serialize_materialized(bencher: divan::Bencher) {
+    let value = Materialized {
+        payload: payload(),
+    };
+    bencher.bench_local(|| serde_json::to_vec(&value).unwrap());
+}
+
+#[divan::bench]
+fn serialize_streamed(bencher: divan::Bencher) {
+    let value = Streamed {
+        payload: payload(),
+    };
+    bencher.bench_local(|| serde_json::to_vec(&value).unwrap());
+}
+
+#[divan::bench]
+fn deserialize_materialized(bencher: divan::Bencher) {
+    let json = serde_json::to_vec(&Materialized {
+        payload: payload(),
+    })
+    .unwrap();
+    bencher.bench_local(|| serde_json::from_slice::<Materialized>(&json).unwrap());
+}
+
+#[divan::bench]
+fn deserialize_streamed(bencher: divan::Bencher) {
+    let json = serde_json::to_vec(&Streamed {
+        payload: payload(),
+    })
+    .unwrap();
+    bencher.bench_local(|| serde_json::from_slice::<Streamed>(&json).unwrap());
+}
+
+#[divan::bench]
+fn deserialize_into_reused_buffer(bencher: divan::Bencher) {
+    let json = format!(r#""{}""#, STANDARD.encode(payload()));
+    let mut buffer = Vec::new();
+    bencher.bench_local(|| {
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        super::streaming_base64::deserialize_into(&mut deserializer, &mut buffer).unwrap();
+    });
+}
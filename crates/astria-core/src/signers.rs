@@ -0,0 +1,203 @@
+//! Human readable labels for the validators that propose and sign sequencer blocks.
+//!
+//! Sequencer blocks only identify their proposer by its `CometBFT` address, which is hard to tell
+//! apart when debugging a network with many validators. [`KnownSigners`] maps these addresses to
+//! operator chosen labels like `sequencer-node-3`.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+};
+
+use base64::{
+    engine::general_purpose::STANDARD,
+    Engine as _,
+};
+use tendermint::{
+    account,
+    PublicKey,
+};
+
+/// An error when parsing [`KnownSigners`] from a string.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseKnownSignersError {
+    #[error("entry `{entry}` is not of the form `<label>=<key>`")]
+    MissingSeparator { entry: String },
+    #[error("entry `{entry}` has an empty label")]
+    EmptyLabel { entry: String },
+    #[error(
+        "key of signer `{label}` is neither a hex encoded 20 byte address nor a base64 encoded 32 \
+         byte ed25519 public key"
+    )]
+    InvalidKey { label: String },
+    #[error("signer `{address}` is labeled more than once")]
+    DuplicateSigner { address: account::Id },
+}
+
+/// Labels for sequencer validators, keyed by their address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownSigners {
+    labels: HashMap<account::Id, String>,
+}
+
+impl KnownSigners {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Labels the validator at `address`, returning its previous label if it had one.
+    pub fn insert(&mut self, address: account::Id, label: impl Into<String>) -> Option<String> {
+        self.labels.insert(address, label.into())
+    }
+
+    /// Labels the validator with public key `key`, returning its previous label if it had one.
+    pub fn insert_key(&mut self, key: PublicKey, label: impl Into<String>) -> Option<String> {
+        self.insert(account::Id::from(key), label)
+    }
+
+    /// Returns the label of the validator at `address`.
+    #[must_use]
+    pub fn label(&self, address: &account::Id) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns a value displaying the validator at `address` as `<label> (<address>)` if it is
+    /// labeled, and only as `<address>` otherwise.
+    #[must_use]
+    pub fn display<'a>(&'a self, address: &'a account::Id) -> SignerDisplay<'a> {
+        SignerDisplay {
+            address,
+            label: self.label(address),
+        }
+    }
+}
+
+/// Parses a comma separated list of `<label>=<key>` entries.
+///
+/// Each key is either the hex encoded 20 byte address of a validator, or its base64 encoded 32
+/// byte ed25519 public key. Whitespace around entries is ignored, and an empty string parses to
+/// an empty set of signers.
+impl FromStr for KnownSigners {
+    type Err = ParseKnownSignersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut signers = Self::new();
+        for entry in s.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (label, key) =
+                entry
+                    .split_once('=')
+                    .ok_or_else(|| ParseKnownSignersError::MissingSeparator {
+                        entry: entry.to_string(),
+                    })?;
+            let (label, key) = (label.trim(), key.trim());
+            if label.is_empty() {
+                return Err(ParseKnownSignersError::EmptyLabel {
+                    entry: entry.to_string(),
+                });
+            }
+            let address = parse_address(key).ok_or_else(|| ParseKnownSignersError::InvalidKey {
+                label: label.to_string(),
+            })?;
+            if signers.insert(address, label).is_some() {
+                return Err(ParseKnownSignersError::DuplicateSigner {
+                    address,
+                });
+            }
+        }
+        Ok(signers)
+    }
+}
+
+fn parse_address(key: &str) -> Option<account::Id> {
+    if let Ok(address) = account::Id::from_str(key) {
+        return Some(address);
+    }
+    let bytes = STANDARD.decode(key).ok()?;
+    PublicKey::from_raw_ed25519(&bytes).map(account::Id::from)
+}
+
+/// Displays a validator by its label if it has one. Returned by [`KnownSigners::display`].
+#[derive(Clone, Copy, Debug)]
+pub struct SignerDisplay<'a> {
+    address: &'a account::Id,
+    label: Option<&'a str>,
+}
+
+impl<'a> fmt::Display for SignerDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.label {
+            Some(label) => write!(f, "{label} ({})", self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{
+        engine::general_purpose::STANDARD,
+        Engine as _,
+    };
+    use tendermint::{
+        account,
+        PublicKey,
+    };
+
+    use super::{
+        KnownSigners,
+        ParseKnownSignersError,
+    };
+
+    const KEY: [u8; 32] = [7; 32];
+
+    fn address_of_key() -> account::Id {
+        account::Id::from(PublicKey::from_raw_ed25519(&KEY).unwrap())
+    }
+
+    #[test]
+    fn parses_base64_keys_and_hex_addresses() {
+        let address = account::Id::new([1; 20]);
+        let input = format!(
+            "sequencer-node-1={}, sequencer-node-2 = {address}",
+            STANDARD.encode(KEY)
+        );
+        let signers: KnownSigners = input.parse().unwrap();
+        assert_eq!(signers.label(&address_of_key()), Some("sequencer-node-1"));
+        assert_eq!(signers.label(&address), Some("sequencer-node-2"));
+    }
+
+    #[test]
+    fn empty_string_parses_to_no_signers() {
+        assert!("".parse::<KnownSigners>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn duplicate_signers_are_rejected() {
+        let key = STANDARD.encode(KEY);
+        let input = format!("a={key},b={key}");
+        assert!(matches!(
+            input.parse::<KnownSigners>(),
+            Err(ParseKnownSignersError::DuplicateSigner { .. })
+        ));
+    }
+
+    #[test]
+    fn labeled_signers_are_displayed_with_their_label() {
+        let mut signers = KnownSigners::new();
+        let labeled = address_of_key();
+        let unlabeled = account::Id::new([1; 20]);
+        signers.insert(labeled, "sequencer-node-3");
+        assert_eq!(
+            signers.display(&labeled).to_string(),
+            format!("sequencer-node-3 ({labeled})")
+        );
+        assert_eq!(signers.display(&unlabeled).to_string(), unlabeled.to_string());
+    }
+}
@@ -0,0 +1,435 @@
+//! Schema compatibility tests for the generated protobuf types of the public APIs.
+//!
+//! Every message of `astria.primitive.v1`, `astria.execution.v1alpha2`, and
+//! `astria.sequencerblock.v1alpha1` is constructed with all of its fields set, and its encoding
+//! is compared against a fixed snapshot.
+//!
+//! The struct literals below deliberately list every field without `..Default::default()`.
+//! A regeneration that removes a field or changes its type therefore fails to compile, while a
+//! change of a field's number or wire type fails the snapshot comparison. Adding fields only
+//! requires extending the literals. If a snapshot has to change, the change breaks the wire
+//! format for downstream consumers and must be released as a new API version instead.
+
+use bytes::Bytes;
+use pbjson_types::Timestamp;
+use prost::Message;
+
+use crate::generated::{
+    execution::v1alpha2 as execution,
+    primitive::v1 as primitive,
+    sequencerblock::v1alpha1 as sequencerblock,
+};
+
+#[track_caller]
+fn assert_encoding<M: Message>(message: &M, expected: &str) {
+    let actual: String = message
+        .encode_to_vec()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    assert_eq!(
+        expected, actual,
+        "the wire encoding of `{}` changed",
+        std::any::type_name::<M>(),
+    );
+}
+
+fn timestamp() -> Timestamp {
+    Timestamp {
+        seconds: 5,
+        nanos: 6,
+    }
+}
+
+mod primitive_v1 {
+    use super::*;
+
+    pub(super) fn uint128() -> primitive::Uint128 {
+        primitive::Uint128 {
+            lo: 1,
+            hi: 2,
+        }
+    }
+
+    pub(super) fn proof() -> primitive::Proof {
+        primitive::Proof {
+            audit_path: Bytes::from_static(&[0xaa]),
+            leaf_index: 3,
+            tree_size: 4,
+        }
+    }
+
+    pub(super) fn rollup_id() -> primitive::RollupId {
+        primitive::RollupId {
+            inner: Bytes::from_static(&[1]),
+        }
+    }
+
+    pub(super) fn address() -> primitive::Address {
+        primitive::Address {
+            inner: Bytes::from_static(&[2]),
+        }
+    }
+
+    #[test]
+    fn uint128_encoding_is_stable() {
+        assert_encoding(&uint128(), "08011002");
+    }
+
+    #[test]
+    fn proof_encoding_is_stable() {
+        assert_encoding(&proof(), "0a01aa10031804");
+    }
+
+    #[test]
+    fn denom_encoding_is_stable() {
+        let denom = primitive::Denom {
+            id: Bytes::from_static(&[0xbb]),
+            base_denom: "nria".to_string(),
+        };
+        assert_encoding(&denom, "0a01bb12046e726961");
+    }
+
+    #[test]
+    fn rollup_id_encoding_is_stable() {
+        assert_encoding(&rollup_id(), "0a0101");
+    }
+
+    #[test]
+    fn address_encoding_is_stable() {
+        assert_encoding(&address(), "0a0102");
+    }
+}
+
+mod execution_v1alpha2 {
+    use execution::block_identifier::Identifier;
+
+    use super::*;
+
+    fn block() -> execution::Block {
+        execution::Block {
+            number: 1,
+            hash: Bytes::from_static(&[2]),
+            parent_block_hash: Bytes::from_static(&[3]),
+            timestamp: Some(timestamp()),
+        }
+    }
+
+    fn block_number_identifier() -> execution::BlockIdentifier {
+        execution::BlockIdentifier {
+            identifier: Some(Identifier::BlockNumber(7)),
+        }
+    }
+
+    fn block_hash_identifier() -> execution::BlockIdentifier {
+        execution::BlockIdentifier {
+            identifier: Some(Identifier::BlockHash(Bytes::from_static(&[8]))),
+        }
+    }
+
+    fn commitment_state() -> execution::CommitmentState {
+        execution::CommitmentState {
+            soft: Some(block()),
+            firm: Some(block()),
+        }
+    }
+
+    #[test]
+    fn genesis_info_encoding_is_stable() {
+        let genesis_info = execution::GenesisInfo {
+            rollup_id: Bytes::from_static(&[1]),
+            sequencer_genesis_block_height: 2,
+            celestia_base_block_height: 3,
+            celestia_block_variance: 4,
+        };
+        assert_encoding(&genesis_info, "0a0101100218032004");
+    }
+
+    #[test]
+    fn block_encoding_is_stable() {
+        assert_encoding(&block(), "08011201021a0103220408051006");
+    }
+
+    #[test]
+    fn block_identifier_encoding_is_stable() {
+        assert_encoding(&block_number_identifier(), "0807");
+        assert_encoding(&block_hash_identifier(), "120108");
+    }
+
+    #[test]
+    fn get_genesis_info_request_encoding_is_stable() {
+        assert_encoding(&execution::GetGenesisInfoRequest {}, "");
+    }
+
+    #[test]
+    fn get_block_request_encoding_is_stable() {
+        let request = execution::GetBlockRequest {
+            identifier: Some(block_number_identifier()),
+        };
+        assert_encoding(&request, "0a020807");
+    }
+
+    #[test]
+    fn batch_get_blocks_request_encoding_is_stable() {
+        let request = execution::BatchGetBlocksRequest {
+            identifiers: vec![block_number_identifier(), block_hash_identifier()],
+        };
+        assert_encoding(&request, "0a0208070a03120108");
+    }
+
+    #[test]
+    fn batch_get_blocks_response_encoding_is_stable() {
+        let response = execution::BatchGetBlocksResponse {
+            blocks: vec![block()],
+        };
+        assert_encoding(&response, "0a0e08011201021a0103220408051006");
+    }
+
+    #[test]
+    fn execute_block_request_encoding_is_stable() {
+        let request = execution::ExecuteBlockRequest {
+            prev_block_hash: Bytes::from_static(&[9]),
+            transactions: vec![
+                super::sequencerblock_v1alpha1::sequenced_data(),
+                super::sequencerblock_v1alpha1::deposit_data(),
+            ],
+            timestamp: Some(timestamp()),
+        };
+        assert_encoding(
+            &request,
+            "0a010912030a0101121b12190a030a010212030a01011a04080110022201062a0464657374\
+             1a0408051006",
+        );
+    }
+
+    #[test]
+    fn commitment_state_encoding_is_stable() {
+        assert_encoding(
+            &commitment_state(),
+            "0a0e08011201021a0103220408051006120e08011201021a0103220408051006",
+        );
+    }
+
+    #[test]
+    fn get_commitment_state_request_encoding_is_stable() {
+        assert_encoding(&execution::GetCommitmentStateRequest {}, "");
+    }
+
+    #[test]
+    fn update_commitment_state_request_encoding_is_stable() {
+        let request = execution::UpdateCommitmentStateRequest {
+            commitment_state: Some(commitment_state()),
+        };
+        assert_encoding(
+            &request,
+            "0a200a0e08011201021a0103220408051006120e08011201021a0103220408051006",
+        );
+    }
+
+    #[test]
+    fn stream_execute_blocks_request_encoding_is_stable() {
+        let request = execution::StreamExecuteBlocksRequest {
+            sequence: 1,
+            value: Some(execution::stream_execute_blocks_request::Value::ExecuteBlock(
+                execution::ExecuteBlockRequest {
+                    prev_block_hash: Bytes::from_static(&[9]),
+                    transactions: vec![],
+                    timestamp: Some(timestamp()),
+                },
+            )),
+        };
+        assert_encoding(&request, "080112090a01091a0408051006");
+    }
+
+    #[test]
+    fn stream_execute_blocks_response_encoding_is_stable() {
+        let response = execution::StreamExecuteBlocksResponse {
+            sequence: 1,
+            value: Some(execution::stream_execute_blocks_response::Value::ExecutedBlock(
+                block(),
+            )),
+        };
+        assert_encoding(&response, "0801120e08011201021a0103220408051006");
+    }
+}
+
+mod sequencerblock_v1alpha1 {
+    use sequencerblock::rollup_data::Value;
+
+    use super::{
+        primitive_v1::{
+            address,
+            proof,
+            rollup_id,
+            uint128,
+        },
+        *,
+    };
+
+    fn header() -> sequencerblock::SequencerBlockHeader {
+        sequencerblock::SequencerBlockHeader {
+            chain_id: "test".to_string(),
+            height: 1,
+            time: Some(timestamp()),
+            data_hash: vec![2],
+            proposer_address: vec![3],
+            rollup_transactions_root: vec![4],
+        }
+    }
+
+    fn rollup_transactions() -> sequencerblock::RollupTransactions {
+        sequencerblock::RollupTransactions {
+            rollup_id: Some(rollup_id()),
+            transactions: vec![vec![1], vec![2]],
+            proof: Some(proof()),
+        }
+    }
+
+    fn deposit() -> sequencerblock::Deposit {
+        sequencerblock::Deposit {
+            bridge_address: Some(address()),
+            rollup_id: Some(rollup_id()),
+            amount: Some(uint128()),
+            asset_id: vec![6],
+            destination_chain_address: "dest".to_string(),
+        }
+    }
+
+    pub(super) fn sequenced_data() -> sequencerblock::RollupData {
+        sequencerblock::RollupData {
+            value: Some(Value::SequencedData(vec![1])),
+        }
+    }
+
+    pub(super) fn deposit_data() -> sequencerblock::RollupData {
+        sequencerblock::RollupData {
+            value: Some(Value::Deposit(deposit())),
+        }
+    }
+
+    #[test]
+    fn rollup_transactions_encoding_is_stable() {
+        assert_encoding(&rollup_transactions(), "0a030a01011201011201021a070a01aa10031804");
+    }
+
+    #[test]
+    fn sequencer_block_header_encoding_is_stable() {
+        assert_encoding(&header(), "0a047465737410011a04080510062201022a0103320104");
+    }
+
+    #[test]
+    fn sequencer_block_encoding_is_stable() {
+        let block = sequencerblock::SequencerBlock {
+            header: Some(header()),
+            rollup_transactions: vec![rollup_transactions()],
+            rollup_transactions_proof: Some(proof()),
+            rollup_ids_proof: Some(proof()),
+            block_hash: vec![5],
+        };
+        assert_encoding(
+            &block,
+            "0a170a047465737410011a04080510062201022a010332010412140a030a01011201011201021a070a01\
+             aa100318041a070a01aa1003180422070a01aa100318042a0105",
+        );
+    }
+
+    #[test]
+    fn deposit_encoding_is_stable() {
+        assert_encoding(&deposit(), "0a030a010212030a01011a04080110022201062a0464657374");
+    }
+
+    #[test]
+    fn filtered_sequencer_block_encoding_is_stable() {
+        let block = sequencerblock::FilteredSequencerBlock {
+            block_hash: vec![5],
+            header: Some(header()),
+            rollup_transactions: vec![rollup_transactions()],
+            rollup_transactions_proof: Some(proof()),
+            all_rollup_ids: vec![vec![1]],
+            rollup_ids_proof: Some(proof()),
+        };
+        assert_encoding(
+            &block,
+            "0a010512170a047465737410011a04080510062201022a01033201041a140a030a01011201011201021a\
+             070a01aa1003180422070a01aa100318042a010132070a01aa10031804",
+        );
+    }
+
+    #[test]
+    fn rollup_data_encoding_is_stable() {
+        assert_encoding(&sequenced_data(), "0a0101");
+        assert_encoding(&deposit_data(), "12190a030a010212030a01011a04080110022201062a0464657374");
+    }
+
+    #[test]
+    fn celestia_rollup_blob_encoding_is_stable() {
+        let blob = sequencerblock::CelestiaRollupBlob {
+            sequencer_block_hash: vec![5],
+            rollup_id: Some(rollup_id()),
+            transactions: vec![vec![1]],
+            proof: Some(proof()),
+            verification_key: vec![],
+            signature: vec![],
+            rollup_txs_root: vec![],
+            transactions_checksum: vec![],
+            signature_scheme: 0,
+        };
+        assert_encoding(&blob, "0a010512030a01011a010122070a01aa10031804");
+    }
+
+    #[test]
+    fn celestia_sequencer_blob_encoding_is_stable() {
+        let blob = sequencerblock::CelestiaSequencerBlob {
+            block_hash: vec![5],
+            header: Some(header()),
+            rollup_ids: vec![rollup_id()],
+            rollup_transactions_proof: Some(proof()),
+            rollup_ids_proof: Some(proof()),
+        };
+        assert_encoding(
+            &blob,
+            "0a010512170a047465737410011a04080510062201022a01033201041a030a010122070a01aa10031804\
+             2a070a01aa10031804",
+        );
+    }
+
+    #[test]
+    fn celestia_auxiliary_blob_encoding_is_stable() {
+        let blob = sequencerblock::CelestiaAuxiliaryBlob {
+            sequencer_block_hash: vec![5],
+            kind: "proof".to_string(),
+            data: vec![7],
+        };
+        assert_encoding(&blob, "0a0105120570726f6f661a0107");
+    }
+
+    #[test]
+    fn rollup_result_namespace_data_encoding_is_stable() {
+        let result = sequencerblock::RollupResultNamespaceData {
+            sequencer_block_hash: vec![5],
+            rollup_id: Some(rollup_id()),
+            rollup_block_number: 8,
+            rollup_block_hash: vec![9],
+            state_root: vec![10],
+            receipts_root: vec![11],
+        };
+        assert_encoding(&result, "0a010512030a010118082201092a010a32010b");
+    }
+
+    #[test]
+    fn get_sequencer_block_request_encoding_is_stable() {
+        let request = sequencerblock::GetSequencerBlockRequest {
+            height: 1,
+        };
+        assert_encoding(&request, "0801");
+    }
+
+    #[test]
+    fn get_filtered_sequencer_block_request_encoding_is_stable() {
+        let request = sequencerblock::GetFilteredSequencerBlockRequest {
+            height: 1,
+            rollup_ids: vec![rollup_id()],
+        };
+        assert_encoding(&request, "080112030a0101");
+    }
+}
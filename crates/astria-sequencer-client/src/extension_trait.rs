@@ -29,6 +29,7 @@ use std::{
 pub use astria_core::{
     primitive::v1::Address,
     protocol::{
+        abci::AbciErrorCode,
         account::v1alpha1::{
             BalanceResponse,
             NonceResponse,
@@ -52,9 +53,12 @@ use tendermint_rpc::HttpClient;
 #[cfg(feature = "websocket")]
 use tendermint_rpc::WebSocketClient;
 use tendermint_rpc::{
-    endpoint::broadcast::{
-        tx_commit,
-        tx_sync,
+    endpoint::{
+        broadcast::{
+            tx_commit,
+            tx_sync,
+        },
+        tx,
     },
     event::EventData,
     Client,
@@ -329,6 +333,62 @@ impl Stream for NewBlocksStream {
     }
 }
 
+/// The inclusion status of a transaction submitted to the sequencer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TxStatus {
+    /// The transaction was not yet included in a block.
+    Pending,
+    /// The transaction was included in the block at `height` and executed successfully.
+    Included { height: Height },
+    /// The transaction was included in the block at `height` but failed to execute.
+    Failed {
+        height: Height,
+        code: AbciErrorCode,
+        log: String,
+    },
+}
+
+impl TxStatus {
+    fn from_tx_response(response: tx::Response) -> Self {
+        match response.tx_result.code {
+            tendermint::abci::Code::Ok => Self::Included {
+                height: response.height,
+            },
+            tendermint::abci::Code::Err(code) => Self::Failed {
+                height: response.height,
+                code: code.into(),
+                log: response.tx_result.log,
+            },
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TxStatusStreamError {
+    #[error("encountered an error while receiving events over subscription")]
+    Rpc(#[source] tendermint_rpc::Error),
+    #[error("failed looking up the result of the included transaction")]
+    Lookup(#[source] tendermint_rpc::Error),
+}
+
+/// The inclusion statuses of a single transaction.
+///
+/// Created by [`SequencerSubscriptionClientExt::subscribe_tx_status`].
+pub struct TxStatusStream {
+    inner: Pin<Box<dyn Stream<Item = Result<TxStatus, TxStatusStreamError>> + Send>>,
+}
+
+impl Stream for TxStatusStream {
+    type Item = Result<TxStatus, TxStatusStreamError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
 pub struct LatestHeightStream {
     inner: Pin<Box<dyn Stream<Item = Result<Height, NewBlockStreamError>> + Send>>,
 }
@@ -378,6 +438,66 @@ pub trait SequencerSubscriptionClientExt: SubscriptionClient {
             inner: stream,
         })
     }
+
+    /// Subscribes to the inclusion status of the transaction with `tx_hash`.
+    ///
+    /// `tx_hash` is the sha256 hash of the transaction bytes as broadcast, as returned by
+    /// [`SignedTransaction::sha256_of_proto_encoding`]. The subscription should be established
+    /// before the transaction is broadcast, but transactions that were already included are
+    /// found as well.
+    ///
+    /// The returned stream first yields [`TxStatus::Pending`], then either
+    /// [`TxStatus::Included`] or [`TxStatus::Failed`] once the transaction was included in a
+    /// block, and ends afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if subscribing to transaction events failed.
+    async fn subscribe_tx_status(
+        &self,
+        tx_hash: [u8; 32],
+    ) -> Result<TxStatusStream, SubscriptionFailed>
+    where
+        Self: Client + Clone + Sized + 'static,
+    {
+        use futures::stream::{
+            self,
+            StreamExt as _,
+        };
+        use tendermint_rpc::query::{
+            EventType,
+            Query,
+        };
+        let hash = tendermint::Hash::Sha256(tx_hash);
+        let events = self
+            .subscribe(Query::from(EventType::Tx).and_eq("tx.hash", hex::encode_upper(tx_hash)))
+            .await?;
+        // The transaction might have been included before the subscription was established. A
+        // failed lookup means that it was not.
+        let included = self.tx(hash, false).await.ok().map(TxStatus::from_tx_response);
+
+        let pending = stream::once(future::ready(Ok(TxStatus::Pending)));
+        let inner = if let Some(status) = included {
+            pending.chain(stream::once(future::ready(Ok(status)))).boxed()
+        } else {
+            let client = self.clone();
+            let final_status = events.take(1).then(move |event| {
+                let client = client.clone();
+                async move {
+                    event.map_err(TxStatusStreamError::Rpc)?;
+                    let response = client
+                        .tx(hash, false)
+                        .await
+                        .map_err(TxStatusStreamError::Lookup)?;
+                    Ok(TxStatus::from_tx_response(response))
+                }
+            });
+            pending.chain(final_status).boxed()
+        };
+        Ok(TxStatusStream {
+            inner,
+        })
+    }
 }
 
 /// Tendermint HTTP client which is used to interact with the Sequencer node.
@@ -1,5 +1,9 @@
 #[cfg(any(feature = "http", feature = "websocket"))]
 pub mod extension_trait;
+#[cfg(any(feature = "http", feature = "websocket"))]
+pub mod nonce;
+#[cfg(any(feature = "http", feature = "websocket"))]
+pub mod submitter;
 
 #[cfg(not(any(feature = "http", feature = "websocket")))]
 compile_error!("at least one of the `http` or `websocket` features must be enabled");
@@ -48,6 +52,16 @@ mod __feature_gated_exports {
         NewBlockStreamError,
         SequencerClientExt,
         SequencerSubscriptionClientExt,
+        TxStatus,
+        TxStatusStream,
+        TxStatusStreamError,
+    };
+    pub use crate::{
+        nonce::NonceTracker,
+        submitter::{
+            RollupTxSubmitter,
+            SubmitError,
+        },
     };
 }
 
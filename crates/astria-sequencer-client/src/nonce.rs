@@ -0,0 +1,193 @@
+//! Tracking the nonces of an account that submits many transactions.
+//!
+//! A [`NonceTracker`] hands out nonces without querying the sequencer for every transaction and
+//! remembers the transactions that were broadcast but not yet included in a block. Because the
+//! sequencer only executes transactions in nonce order, a single nonce that is never used blocks
+//! all transactions after it. The tracker therefore:
+//!
+//! - reuses nonces that were given back with [`NonceTracker::release`] before handing out new
+//!   ones, filling the gaps they would leave;
+//! - reports nonces below the highest pending transaction that have no transaction, see
+//!   [`NonceTracker::gaps`];
+//! - reports pending transactions that were not included for a while, see
+//!   [`NonceTracker::stuck`], which can be replaced by a new transaction with the same nonce
+//!   through [`NonceTracker::record_pending`].
+
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use astria_core::{
+    primitive::v1::Address,
+    protocol::transaction::v1alpha1::SignedTransaction,
+};
+
+use crate::extension_trait::{
+    Error,
+    SequencerClientExt,
+};
+
+#[derive(Clone, Debug)]
+struct Pending {
+    tx: SignedTransaction,
+    submitted_at: Instant,
+}
+
+/// The nonces and pending transactions of a single account.
+#[derive(Clone, Debug)]
+pub struct NonceTracker {
+    address: Address,
+    // The nonce of the next transaction the sequencer will execute for the account.
+    confirmed: u32,
+    // The nonce handed out next if no nonce was released.
+    next: u32,
+    released: BTreeSet<u32>,
+    pending: BTreeMap<u32, Pending>,
+}
+
+impl NonceTracker {
+    /// Constructs a tracker for `address`, whose next nonce on the sequencer is `nonce`.
+    #[must_use]
+    pub fn new(address: Address, nonce: u32) -> Self {
+        Self {
+            address,
+            confirmed: nonce,
+            next: nonce,
+            released: BTreeSet::new(),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Constructs a tracker for `address` with its latest nonce on the sequencer.
+    ///
+    /// # Errors
+    ///
+    /// This has the same error conditions as [`SequencerClientExt::get_latest_nonce`].
+    pub async fn fetch<C>(client: &C, address: Address) -> Result<Self, Error>
+    where
+        C: SequencerClientExt + Sync,
+    {
+        let nonce = client.get_latest_nonce(address).await?.nonce;
+        Ok(Self::new(address, nonce))
+    }
+
+    #[must_use]
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the nonce of the next transaction the sequencer will execute for the account, as
+    /// of the last update.
+    #[must_use]
+    pub fn confirmed_nonce(&self) -> u32 {
+        self.confirmed
+    }
+
+    /// Reserves a nonce for a new transaction.
+    ///
+    /// Released nonces are handed out first, lowest first.
+    pub fn next_nonce(&mut self) -> u32 {
+        if let Some(nonce) = self.released.pop_first() {
+            return nonce;
+        }
+        let nonce = self.next;
+        self.next = self.next.saturating_add(1);
+        nonce
+    }
+
+    /// Gives back a reserved `nonce` whose transaction was not accepted by the sequencer, so that
+    /// it is used for the next transaction.
+    pub fn release(&mut self, nonce: u32) {
+        if nonce < self.confirmed || nonce >= self.next || self.pending.contains_key(&nonce) {
+            return;
+        }
+        self.released.insert(nonce);
+        // Nonces released at the end of the range need not be filled.
+        while self.next > self.confirmed && self.released.remove(&(self.next - 1)) {
+            self.next -= 1;
+        }
+    }
+
+    /// Records `tx` as broadcast but not yet executed.
+    ///
+    /// Returns the transaction previously recorded with the same nonce, if any: recording a
+    /// transaction for the nonce of a pending one replaces it.
+    pub fn record_pending(&mut self, tx: SignedTransaction) -> Option<SignedTransaction> {
+        let nonce = tx.unsigned_transaction().params.nonce;
+        self.released.remove(&nonce);
+        if nonce >= self.next {
+            self.next = nonce.saturating_add(1);
+        }
+        self.pending
+            .insert(
+                nonce,
+                Pending {
+                    tx,
+                    submitted_at: Instant::now(),
+                },
+            )
+            .map(|replaced| replaced.tx)
+    }
+
+    /// Updates the nonce of the next transaction the sequencer will execute for the account,
+    /// dropping all pending transactions below it.
+    pub fn update_confirmed(&mut self, nonce: u32) {
+        if nonce <= self.confirmed {
+            return;
+        }
+        self.confirmed = nonce;
+        self.next = self.next.max(nonce);
+        self.pending = self.pending.split_off(&nonce);
+        self.released = self.released.split_off(&nonce);
+    }
+
+    /// Fetches the latest nonce of the account and updates the tracker with it.
+    ///
+    /// # Errors
+    ///
+    /// This has the same error conditions as [`SequencerClientExt::get_latest_nonce`].
+    pub async fn refresh<C>(&mut self, client: &C) -> Result<(), Error>
+    where
+        C: SequencerClientExt + Sync,
+    {
+        let nonce = client.get_latest_nonce(self.address).await?.nonce;
+        self.update_confirmed(nonce);
+        Ok(())
+    }
+
+    /// Returns the nonces below the highest pending transaction without a pending transaction.
+    ///
+    /// The pending transactions above a gap are not executed until the gap is filled. Nonces
+    /// that were reserved but neither recorded as pending nor released are reported as gaps.
+    #[must_use]
+    pub fn gaps(&self) -> Vec<u32> {
+        let Some(highest) = self.pending.keys().next_back() else {
+            return Vec::new();
+        };
+        (self.confirmed..*highest)
+            .filter(|nonce| !self.pending.contains_key(nonce))
+            .collect()
+    }
+
+    /// Returns the pending transactions that were broadcast more than `older_than` ago, lowest
+    /// nonce first.
+    pub fn stuck(&self, older_than: Duration) -> impl Iterator<Item = &SignedTransaction> {
+        self.pending
+            .values()
+            .filter(move |pending| pending.submitted_at.elapsed() > older_than)
+            .map(|pending| &pending.tx)
+    }
+
+    /// Returns the number of pending transactions.
+    #[must_use]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
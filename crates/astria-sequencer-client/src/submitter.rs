@@ -0,0 +1,258 @@
+//! A high level helper to submit rollup transactions to the sequencer.
+//!
+//! [`RollupTxSubmitter`] wraps a single rollup transaction in a sequence action, signs it, and
+//! broadcasts it with `broadcast_tx_sync`. It tracks the nonces and pending transactions of every
+//! signer it has seen with a [`NonceTracker`], so that callers need not query the nonce before
+//! every submission.
+//!
+//! # Examples
+//! The example below works with the feature `"http"` set.
+//! ```no_run
+//! # tokio_test::block_on(async {
+//! use astria_sequencer_client::{
+//!     HttpClient,
+//!     RollupTxSubmitter,
+//! };
+//! use ed25519_consensus::SigningKey;
+//!
+//! let client = HttpClient::new("http://127.0.0.1:26657")?;
+//! let submitter = RollupTxSubmitter::new(client, "astria");
+//! let signer = SigningKey::from([1; 32]);
+//! let response = submitter
+//!     .submit_rollup_tx("my-rollup", b"rollup tx".to_vec(), &signer)
+//!     .await?;
+//! println!("{}", response.hash);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::{
+    collections::{
+        hash_map::Entry,
+        HashMap,
+    },
+    time::Duration,
+};
+
+use astria_core::{
+    primitive::v1::{
+        asset::{
+            self,
+            default_native_asset_id,
+        },
+        Address,
+        RollupId,
+    },
+    protocol::{
+        abci::AbciErrorCode,
+        transaction::v1alpha1::{
+            action::SequenceAction,
+            SignedTransaction,
+            TransactionParams,
+            UnsignedTransaction,
+        },
+    },
+};
+use ed25519_consensus::SigningKey;
+use tendermint_rpc::endpoint::broadcast::tx_sync;
+use tokio::sync::Mutex;
+
+use crate::{
+    extension_trait::{
+        Error,
+        SequencerClientExt,
+    },
+    nonce::NonceTracker,
+};
+
+/// An error when submitting a rollup transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+    /// The nonce of the signer could not be fetched from the sequencer.
+    #[error("failed fetching the nonce of the signer")]
+    GetNonce(#[source] Error),
+    /// The transaction could not be broadcast. It is unknown whether the sequencer received it.
+    #[error("failed broadcasting the transaction")]
+    Broadcast(#[source] Error),
+    /// The sequencer rejected the transaction in `CheckTx`.
+    #[error("the sequencer rejected the transaction: {code}; log: {log}")]
+    Rejected { code: AbciErrorCode, log: String },
+}
+
+impl SubmitError {
+    /// Returns the ABCI error code if the sequencer rejected the transaction.
+    #[must_use]
+    pub fn rejection_code(&self) -> Option<AbciErrorCode> {
+        match self {
+            Self::Rejected {
+                code, ..
+            } => Some(*code),
+            Self::GetNonce(_) | Self::Broadcast(_) => None,
+        }
+    }
+}
+
+/// Signs and submits rollup transactions, managing the nonces of their signers.
+pub struct RollupTxSubmitter<C> {
+    client: C,
+    sequencer_chain_id: String,
+    fee_asset_id: asset::Id,
+    // The nonces of every signer, if known. Held for the duration of a submission so that
+    // concurrent submissions do not use the same nonce.
+    nonces: Mutex<HashMap<Address, NonceTracker>>,
+}
+
+impl<C> RollupTxSubmitter<C>
+where
+    C: SequencerClientExt + Sync,
+{
+    /// Constructs a submitter of transactions to the sequencer network `sequencer_chain_id`.
+    ///
+    /// Fees are paid in the native asset unless configured otherwise with
+    /// [`RollupTxSubmitter::fee_asset_id`].
+    pub fn new<T: Into<String>>(client: C, sequencer_chain_id: T) -> Self {
+        Self {
+            client,
+            sequencer_chain_id: sequencer_chain_id.into(),
+            fee_asset_id: default_native_asset_id(),
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Pays the fees of submitted transactions in `fee_asset_id`.
+    #[must_use]
+    pub fn fee_asset_id(self, fee_asset_id: asset::Id) -> Self {
+        Self {
+            fee_asset_id,
+            ..self
+        }
+    }
+
+    /// Submits `tx_bytes` as a transaction of the rollup `chain_id`, signed by `signer`.
+    ///
+    /// The nonce of `signer` is fetched from the sequencer on first use and tracked locally
+    /// afterwards. If the sequencer rejects the transaction because of an invalid nonce, the
+    /// nonce is fetched again and the transaction resubmitted once.
+    ///
+    /// # Errors
+    ///
+    /// - If the nonce of `signer` could not be fetched.
+    /// - If broadcasting the transaction failed.
+    /// - If the sequencer rejected the transaction.
+    pub async fn submit_rollup_tx(
+        &self,
+        chain_id: &str,
+        tx_bytes: Vec<u8>,
+        signer: &SigningKey,
+    ) -> Result<tx_sync::Response, SubmitError> {
+        let address = Address::from_verification_key(signer.verification_key());
+        let action = SequenceAction {
+            rollup_id: RollupId::from_unhashed_bytes(chain_id),
+            data: tx_bytes,
+            fee_asset_id: self.fee_asset_id,
+        };
+
+        let mut nonces = self.nonces.lock().await;
+        let mut refetched = false;
+        loop {
+            let tracker = match nonces.entry(address) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    NonceTracker::fetch(&self.client, address)
+                        .await
+                        .map_err(SubmitError::GetNonce)?,
+                ),
+            };
+            let nonce = tracker.next_nonce();
+            let tx = UnsignedTransaction {
+                actions: vec![action.clone().into()],
+                params: TransactionParams {
+                    nonce,
+                    chain_id: self.sequencer_chain_id.clone(),
+                },
+            }
+            .into_signed(signer);
+            let response = match self.client.submit_transaction_sync(tx.clone()).await {
+                Ok(response) => response,
+                Err(error) => {
+                    // The transaction might have reached the mempool and consumed the nonce.
+                    nonces.remove(&address);
+                    return Err(SubmitError::Broadcast(error));
+                }
+            };
+            let tendermint::abci::Code::Err(code) = response.code else {
+                tracker.record_pending(tx);
+                return Ok(response);
+            };
+            let code = AbciErrorCode::from(code);
+            if code == AbciErrorCode::INVALID_NONCE && !refetched {
+                nonces.remove(&address);
+                refetched = true;
+                continue;
+            }
+            tracker.release(nonce);
+            return Err(SubmitError::Rejected {
+                code,
+                log: response.log,
+            });
+        }
+    }
+
+    /// Broadcasts `tx` again, replacing any pending transaction of its signer with the same
+    /// nonce.
+    ///
+    /// This is intended for transactions returned by [`RollupTxSubmitter::stuck_transactions`],
+    /// possibly re-signed with changed actions.
+    ///
+    /// # Errors
+    ///
+    /// - If broadcasting the transaction failed.
+    /// - If the sequencer rejected the transaction.
+    pub async fn resubmit(&self, tx: SignedTransaction) -> Result<tx_sync::Response, SubmitError> {
+        let address = Address::from_verification_key(tx.verification_key());
+        let mut nonces = self.nonces.lock().await;
+        let response = self
+            .client
+            .submit_transaction_sync(tx.clone())
+            .await
+            .map_err(SubmitError::Broadcast)?;
+        if let tendermint::abci::Code::Err(code) = response.code {
+            return Err(SubmitError::Rejected {
+                code: code.into(),
+                log: response.log,
+            });
+        }
+        if let Some(tracker) = nonces.get_mut(&address) {
+            tracker.record_pending(tx);
+        }
+        Ok(response)
+    }
+
+    /// Fetches the latest nonces of all known signers, dropping the pending transactions that
+    /// were executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the nonce of any signer could not be fetched.
+    pub async fn refresh_nonces(&self) -> Result<(), Error> {
+        let mut nonces = self.nonces.lock().await;
+        for tracker in nonces.values_mut() {
+            tracker.refresh(&self.client).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the pending transactions of all signers that were broadcast more than
+    /// `older_than` ago.
+    ///
+    /// Call [`RollupTxSubmitter::refresh_nonces`] first to drop the transactions that were
+    /// executed in the meantime.
+    pub async fn stuck_transactions(&self, older_than: Duration) -> Vec<SignedTransaction> {
+        self.nonces
+            .lock()
+            .await
+            .values()
+            .flat_map(|tracker| tracker.stuck(older_than).cloned())
+            .collect()
+    }
+}
@@ -36,7 +36,9 @@ use wiremock::{
 use crate::{
     tendermint_rpc::endpoint::broadcast::tx_sync,
     HttpClient,
+    RollupTxSubmitter,
     SequencerClientExt as _,
+    SubmitError,
 };
 
 const ALICE_ADDRESS: [u8; 20] = hex!("1c0c490f1b5528d8173c5de46d131160e4b2c0c3");
@@ -246,3 +248,76 @@ async fn submit_tx_commit() {
     assert_eq!(response.check_tx.code, 0.into());
     assert_eq!(response.tx_result.code, 0.into());
 }
+
+#[tokio::test]
+async fn submit_rollup_tx_fetches_nonce_and_broadcasts() {
+    use astria_core::generated::protocol::account::v1alpha1::NonceResponse;
+
+    let MockSequencer {
+        server,
+        client,
+    } = MockSequencer::start().await;
+
+    let _nonce_guard = register_abci_query_response(
+        &server,
+        "accounts/nonce/",
+        NonceResponse {
+            height: 10,
+            nonce: 3,
+        },
+    )
+    .await;
+    let server_response = tx_sync::Response {
+        code: 0.into(),
+        data: vec![].into(),
+        log: String::new(),
+        hash: Hash::Sha256([1; 32]),
+    };
+    let _broadcast_guard =
+        register_broadcast_tx_sync_response(&server, server_response.clone()).await;
+
+    let submitter = RollupTxSubmitter::new(client, "test");
+    let response = submitter
+        .submit_rollup_tx("rollup", b"tx".to_vec(), &SigningKey::from([1; 32]))
+        .await
+        .unwrap();
+    assert_eq!(server_response.hash, response.hash);
+}
+
+#[tokio::test]
+async fn submit_rollup_tx_reports_rejection() {
+    use astria_core::{
+        generated::protocol::account::v1alpha1::NonceResponse,
+        protocol::abci::AbciErrorCode,
+    };
+
+    let MockSequencer {
+        server,
+        client,
+    } = MockSequencer::start().await;
+
+    let _nonce_guard = register_abci_query_response(
+        &server,
+        "accounts/nonce/",
+        NonceResponse {
+            height: 10,
+            nonce: 3,
+        },
+    )
+    .await;
+    let server_response = tx_sync::Response {
+        code: AbciErrorCode::INSUFFICIENT_FUNDS.into(),
+        data: vec![].into(),
+        log: "not enough".to_string(),
+        hash: Hash::Sha256([1; 32]),
+    };
+    let _broadcast_guard = register_broadcast_tx_sync_response(&server, server_response).await;
+
+    let submitter = RollupTxSubmitter::new(client, "test");
+    let error = submitter
+        .submit_rollup_tx("rollup", b"tx".to_vec(), &SigningKey::from([1; 32]))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SubmitError::Rejected { .. }));
+    assert_eq!(Some(AbciErrorCode::INSUFFICIENT_FUNDS), error.rejection_code());
+}
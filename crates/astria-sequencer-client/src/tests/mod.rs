@@ -1,5 +1,6 @@
 #[cfg(feature = "http")]
 mod http;
+mod nonce;
 
 #[test]
 fn constructing_path_gives_expected_string() {
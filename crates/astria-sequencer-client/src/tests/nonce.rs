@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use astria_core::{
+    primitive::v1::Address,
+    protocol::transaction::v1alpha1::{
+        SignedTransaction,
+        TransactionParams,
+        UnsignedTransaction,
+    },
+};
+use ed25519_consensus::SigningKey;
+
+use crate::nonce::NonceTracker;
+
+fn tracker(nonce: u32) -> NonceTracker {
+    NonceTracker::new(Address::from_array([1; 20]), nonce)
+}
+
+fn transaction(nonce: u32) -> SignedTransaction {
+    UnsignedTransaction {
+        actions: vec![],
+        params: TransactionParams {
+            nonce,
+            chain_id: "test".to_string(),
+        },
+    }
+    .into_signed(&SigningKey::from([2; 32]))
+}
+
+#[test]
+fn released_nonces_are_reused_first() {
+    let mut tracker = tracker(5);
+    assert_eq!(5, tracker.next_nonce());
+    assert_eq!(6, tracker.next_nonce());
+    assert_eq!(7, tracker.next_nonce());
+    tracker.record_pending(transaction(5));
+    tracker.record_pending(transaction(7));
+    tracker.release(6);
+    assert_eq!(6, tracker.next_nonce());
+    assert_eq!(8, tracker.next_nonce());
+}
+
+#[test]
+fn releasing_the_last_nonce_does_not_leave_a_gap() {
+    let mut tracker = tracker(5);
+    assert_eq!(5, tracker.next_nonce());
+    tracker.release(5);
+    assert_eq!(5, tracker.next_nonce());
+}
+
+#[test]
+fn gaps_below_pending_transactions_are_reported() {
+    let mut tracker = tracker(5);
+    for _ in 0..4 {
+        tracker.next_nonce();
+    }
+    tracker.record_pending(transaction(5));
+    tracker.record_pending(transaction(8));
+    assert_eq!(vec![6, 7], tracker.gaps());
+    tracker.update_confirmed(7);
+    assert_eq!(vec![7], tracker.gaps());
+    assert_eq!(1, tracker.pending_count());
+}
+
+#[test]
+fn recording_a_pending_nonce_replaces_the_transaction() {
+    let mut tracker = tracker(5);
+    tracker.next_nonce();
+    assert!(tracker.record_pending(transaction(5)).is_none());
+    assert!(tracker.record_pending(transaction(5)).is_some());
+    assert_eq!(1, tracker.pending_count());
+    assert_eq!(1, tracker.stuck(Duration::ZERO).count());
+}
@@ -0,0 +1,371 @@
+//! Alerts raised when the relayer needs the attention of an operator.
+//!
+//! The relayer raises an [`Alert`] when submissions to Celestia keep failing, when its Celestia
+//! account runs out of funds, when submissions fail with an error that only an operator can
+//! resolve, when submissions are not confirmed in time, when it reads
+//! sequencer blocks that fail verification, and when the blocks confirmed on Celestia fall too
+//! far behind the sequencer. Alerts are delivered by a [`Notifier`] on a
+//! separate task so that a slow endpoint never holds up relaying.
+//!
+//! Every kind of alert is rate limited on its own: after an alert was raised, alerts of the
+//! same kind are dropped until `min_interval` has passed.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{
+            AtomicU32,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+        PoisonError,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use astria_eyre::eyre::{
+    self,
+    bail,
+    ensure,
+    WrapErr as _,
+};
+use tracing::{
+    debug,
+    warn,
+};
+
+use crate::{
+    clock::Clock,
+    secret::SecretString,
+};
+
+mod notifier;
+
+pub(crate) use notifier::{
+    Noop,
+    Notifier,
+    PagerDuty,
+    Stdout,
+    Webhook,
+};
+
+/// A condition that needs the attention of an operator.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum Alert {
+    /// Submitting to Celestia failed repeatedly without succeeding in between.
+    SustainedSubmissionFailures { consecutive_failures: u32 },
+    /// Celestia rejected a submission because the relayer's account could not pay for it.
+    LowBalance { log: String },
+    /// Submitting to Celestia failed with an error classified as requiring an operator.
+    OperatorInterventionRequired { error: String },
+    /// A submission was not included in a Celestia block in time and had to be resubmitted.
+    ConfirmationMissed {
+        tx_hash: String,
+        resubmission: u32,
+        max_resubmissions: u32,
+    },
+    /// A sequencer block failed verification and was not relayed.
+    VerificationAnomaly {
+        chain_id: String,
+        sequencer_height: u64,
+        reason: String,
+    },
+    /// The sequencer blocks confirmed on Celestia lag behind the sequencer by more than
+    /// `threshold` blocks.
+    SequencerHeightLag {
+        lag: u64,
+        threshold: u64,
+        critical: bool,
+    },
+}
+
+/// The kind of an [`Alert`], by which alerts are rate limited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum AlertKind {
+    SustainedSubmissionFailures,
+    LowBalance,
+    OperatorInterventionRequired,
+    ConfirmationMissed,
+    VerificationAnomaly,
+    SequencerHeightLag,
+}
+
+impl Alert {
+    pub(crate) fn kind(&self) -> AlertKind {
+        match self {
+            Self::SustainedSubmissionFailures {
+                ..
+            } => AlertKind::SustainedSubmissionFailures,
+            Self::LowBalance {
+                ..
+            } => AlertKind::LowBalance,
+            Self::OperatorInterventionRequired {
+                ..
+            } => AlertKind::OperatorInterventionRequired,
+            Self::ConfirmationMissed {
+                ..
+            } => AlertKind::ConfirmationMissed,
+            Self::VerificationAnomaly {
+                ..
+            } => AlertKind::VerificationAnomaly,
+            Self::SequencerHeightLag {
+                ..
+            } => AlertKind::SequencerHeightLag,
+        }
+    }
+
+    /// Returns if the alert means that the relayer has stopped making progress.
+    pub(crate) fn is_critical(&self) -> bool {
+        matches!(
+            self,
+            Self::SustainedSubmissionFailures { .. }
+                | Self::LowBalance { .. }
+                | Self::OperatorInterventionRequired { .. }
+                | Self::SequencerHeightLag {
+                    critical: true,
+                    ..
+                }
+        )
+    }
+}
+
+impl std::fmt::Display for Alert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SustainedSubmissionFailures {
+                consecutive_failures,
+            } => write!(
+                f,
+                "submitting to Celestia failed {consecutive_failures} times in a row"
+            ),
+            Self::LowBalance {
+                log,
+            } => write!(
+                f,
+                "Celestia rejected a submission for lack of funds in the relayer account: {log}"
+            ),
+            Self::OperatorInterventionRequired {
+                error,
+            } => write!(
+                f,
+                "submitting to Celestia failed with an error that requires an operator: {error}"
+            ),
+            Self::ConfirmationMissed {
+                tx_hash,
+                resubmission,
+                max_resubmissions,
+            } => write!(
+                f,
+                "Celestia transaction `{tx_hash}` was not confirmed in time; resubmission \
+                 {resubmission} of at most {max_resubmissions}"
+            ),
+            Self::VerificationAnomaly {
+                chain_id,
+                sequencer_height,
+                reason,
+            } => write!(
+                f,
+                "sequencer block at height {sequencer_height} of `{chain_id}` failed \
+                 verification: {reason}"
+            ),
+            Self::SequencerHeightLag {
+                lag,
+                threshold,
+                ..
+            } => write!(
+                f,
+                "the sequencer blocks confirmed on Celestia lag {lag} blocks behind the \
+                 sequencer, more than the threshold of {threshold}"
+            ),
+        }
+    }
+}
+
+/// Raises alerts, applying the configured thresholds and rate limits.
+///
+/// Cheap to clone; all clones share the same counters and rate limits.
+#[derive(Clone)]
+pub(crate) struct Alerter {
+    notifier: Arc<dyn Notifier>,
+    submission_failure_threshold: u32,
+    consecutive_submission_failures: Arc<AtomicU32>,
+    rate_limiter: Arc<Mutex<RateLimiter>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Alerter {
+    fn new(
+        notifier: Arc<dyn Notifier>,
+        submission_failure_threshold: u32,
+        min_interval: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            notifier,
+            submission_failure_threshold,
+            consecutive_submission_failures: Arc::new(AtomicU32::new(0)),
+            rate_limiter: Arc::new(Mutex::new(RateLimiter::new(min_interval))),
+            clock,
+        }
+    }
+
+    /// Records a failed attempt to submit to Celestia, raising an alert once
+    /// `submission_failure_threshold` attempts failed in a row.
+    pub(crate) fn submission_failed(&self) {
+        let consecutive_failures = self
+            .consecutive_submission_failures
+            .fetch_add(1, Ordering::Relaxed)
+            .saturating_add(1);
+        if consecutive_failures >= self.submission_failure_threshold {
+            self.raise(Alert::SustainedSubmissionFailures {
+                consecutive_failures,
+            });
+        }
+    }
+
+    /// Records a successful submission to Celestia.
+    pub(crate) fn submission_succeeded(&self) {
+        self.consecutive_submission_failures
+            .store(0, Ordering::Relaxed);
+    }
+
+    /// Raises `alert` unless an alert of the same kind was raised recently.
+    pub(crate) fn raise(&self, alert: Alert) {
+        let allowed = self
+            .rate_limiter
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .allow(alert.kind(), self.clock.now());
+        if !allowed {
+            debug!(%alert, "alert of the same kind was raised recently; dropping it");
+            return;
+        }
+        let notifier = Arc::clone(&self.notifier);
+        tokio::spawn(async move {
+            if let Err(error) = notifier.notify(&alert).await {
+                warn!(%error, %alert, "failed delivering alert");
+            }
+        });
+    }
+}
+
+/// Drops alerts of a kind raised within `min_interval` of the last delivered alert of that kind.
+struct RateLimiter {
+    min_interval: Duration,
+    last_raised: HashMap<AlertKind, Instant>,
+}
+
+impl RateLimiter {
+    fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_raised: HashMap::new(),
+        }
+    }
+
+    fn allow(&mut self, kind: AlertKind, now: Instant) -> bool {
+        if let Some(last) = self.last_raised.get(&kind) {
+            if now.saturating_duration_since(*last) < self.min_interval {
+                return false;
+            }
+        }
+        self.last_raised.insert(kind, now);
+        true
+    }
+}
+
+/// Constructs an [`Alerter`] from the relayer config.
+pub(crate) struct Builder {
+    /// One of `noop`, `stdout`, `webhook`, or `pagerduty`.
+    pub(crate) notifier: String,
+    pub(crate) webhook_url: SecretString,
+    pub(crate) pagerduty_routing_key: SecretString,
+    pub(crate) submission_failure_threshold: u32,
+    pub(crate) min_interval: Duration,
+    pub(crate) clock: Arc<dyn Clock>,
+}
+
+impl Builder {
+    pub(crate) fn build(self) -> eyre::Result<Alerter> {
+        let Self {
+            notifier,
+            webhook_url,
+            pagerduty_routing_key,
+            submission_failure_threshold,
+            min_interval,
+            clock,
+        } = self;
+        ensure!(
+            submission_failure_threshold > 0,
+            "the number of consecutive submission failures before alerting must be greater than 0"
+        );
+        let notifier: Arc<dyn Notifier> = match notifier.as_str() {
+            "" | "noop" => Arc::new(Noop),
+            "stdout" => Arc::new(Stdout),
+            "webhook" => {
+                ensure!(
+                    !webhook_url.is_empty(),
+                    "the webhook alert notifier requires a webhook URL"
+                );
+                Arc::new(
+                    Webhook::new(webhook_url.expose_secret())
+                        .wrap_err("failed constructing webhook")?,
+                )
+            }
+            "pagerduty" => {
+                ensure!(
+                    !pagerduty_routing_key.is_empty(),
+                    "the PagerDuty alert notifier requires a routing key"
+                );
+                Arc::new(PagerDuty::new(pagerduty_routing_key).wrap_err(
+                    "failed constructing PagerDuty notifier",
+                )?)
+            }
+            other => bail!(
+                "unknown alert notifier `{other}`; expected one of `noop`, `stdout`, `webhook`, or \
+                 `pagerduty`"
+            ),
+        };
+        Ok(Alerter::new(
+            notifier,
+            submission_failure_threshold,
+            min_interval,
+            clock,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{
+        Duration,
+        Instant,
+    };
+
+    use super::{
+        AlertKind,
+        RateLimiter,
+    };
+
+    #[test]
+    fn alerts_of_the_same_kind_are_rate_limited() {
+        let mut limiter = RateLimiter::new(Duration::from_secs(60));
+        let start = Instant::now();
+        assert!(limiter.allow(AlertKind::LowBalance, start));
+        assert!(!limiter.allow(AlertKind::LowBalance, start + Duration::from_secs(59)));
+        assert!(limiter.allow(AlertKind::LowBalance, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn alerts_of_different_kinds_are_rate_limited_independently() {
+        let mut limiter = RateLimiter::new(Duration::from_secs(60));
+        let start = Instant::now();
+        assert!(limiter.allow(AlertKind::LowBalance, start));
+        assert!(limiter.allow(AlertKind::ConfirmationMissed, start));
+    }
+}
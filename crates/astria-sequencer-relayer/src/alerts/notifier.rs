@@ -0,0 +1,154 @@
+//! The ways alerts are delivered to operators.
+
+use std::{
+    io::Write as _,
+    time::Duration,
+};
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use futures::{
+    future::BoxFuture,
+    FutureExt as _,
+};
+use serde_json::json;
+
+use super::Alert;
+use crate::secret::SecretString;
+
+/// The maximum time spent delivering a single alert over HTTP.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The endpoint of the PagerDuty Events API v2.
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// The name by which the relayer identifies itself in delivered alerts.
+const SOURCE: &str = env!("CARGO_PKG_NAME");
+
+/// Delivers alerts to an operator.
+pub(crate) trait Notifier: Send + Sync {
+    fn notify<'a>(&'a self, alert: &'a Alert) -> BoxFuture<'a, eyre::Result<()>>;
+}
+
+/// Drops all alerts.
+pub(crate) struct Noop;
+
+impl Notifier for Noop {
+    fn notify<'a>(&'a self, _alert: &'a Alert) -> BoxFuture<'a, eyre::Result<()>> {
+        futures::future::ready(Ok(())).boxed()
+    }
+}
+
+/// Writes every alert to stdout as a single line of JSON.
+pub(crate) struct Stdout;
+
+impl Notifier for Stdout {
+    fn notify<'a>(&'a self, alert: &'a Alert) -> BoxFuture<'a, eyre::Result<()>> {
+        let line = json!({
+            "source": SOURCE,
+            "message": alert.to_string(),
+            "alert": alert,
+        });
+        let result = writeln!(std::io::stdout().lock(), "{line}")
+            .wrap_err("failed writing alert to stdout");
+        futures::future::ready(result).boxed()
+    }
+}
+
+/// Posts every alert as JSON to an HTTP endpoint.
+pub(crate) struct Webhook {
+    client: reqwest::Client,
+    url: reqwest::Url,
+}
+
+impl Webhook {
+    pub(crate) fn new(url: &str) -> eyre::Result<Self> {
+        // The URL is not included in the error because it commonly embeds an access token.
+        let url = url.parse().wrap_err("failed parsing webhook URL")?;
+        Ok(Self {
+            client: http_client()?,
+            url,
+        })
+    }
+}
+
+impl Notifier for Webhook {
+    fn notify<'a>(&'a self, alert: &'a Alert) -> BoxFuture<'a, eyre::Result<()>> {
+        let body = json!({
+            "source": SOURCE,
+            "message": alert.to_string(),
+            "critical": alert.is_critical(),
+            "alert": alert,
+        });
+        async move {
+            self.client
+                .post(self.url.clone())
+                .json(&body)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(reqwest::Error::without_url)
+                .wrap_err("failed posting alert to webhook")?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+/// Triggers a PagerDuty incident for every alert using the Events API v2.
+///
+/// Alerts of the same kind are deduplicated into the same incident by PagerDuty.
+pub(crate) struct PagerDuty {
+    client: reqwest::Client,
+    routing_key: SecretString,
+}
+
+impl PagerDuty {
+    pub(crate) fn new(routing_key: SecretString) -> eyre::Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            routing_key,
+        })
+    }
+}
+
+impl Notifier for PagerDuty {
+    fn notify<'a>(&'a self, alert: &'a Alert) -> BoxFuture<'a, eyre::Result<()>> {
+        let severity = if alert.is_critical() {
+            "critical"
+        } else {
+            "error"
+        };
+        let body = json!({
+            "routing_key": self.routing_key.expose_secret(),
+            "event_action": "trigger",
+            "dedup_key": format!("{SOURCE}-{:?}", alert.kind()),
+            "payload": {
+                "summary": alert.to_string(),
+                "source": SOURCE,
+                "severity": severity,
+                "custom_details": alert,
+            },
+        });
+        async move {
+            self.client
+                .post(PAGERDUTY_EVENTS_URL)
+                .json(&body)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .wrap_err("failed sending alert to PagerDuty")?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+fn http_client() -> eyre::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .wrap_err("failed constructing HTTP client")
+}
@@ -1,8 +1,13 @@
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::SystemTime,
+};
 
 use axum::{
     extract::{
         FromRef,
+        Query,
         State,
     },
     response::{
@@ -11,26 +16,69 @@ use axum::{
     },
     routing::{
         get,
+        post,
         IntoMakeService,
     },
     Json,
     Router,
 };
+use astria_core::{
+    attestation::SignedSubmissionAttestation,
+    primitive::v1::RollupId,
+    sequencerblock::v1alpha1::{
+        celestia::UncheckedRollupResultNamespaceData,
+        RollupResultNamespaceData,
+    },
+};
+use base64::{
+    engine::general_purpose::STANDARD,
+    Engine as _,
+};
 use http::status::StatusCode;
 use hyper::server::conn::AddrIncoming;
-use serde::Serialize;
+use sequencer_client::tendermint::block::Height as SequencerHeight;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use tokio::sync::watch;
+use tracing::info;
 
-use crate::relayer;
+use crate::{
+    attestations::AttestationStore,
+    costs::{
+        self,
+        CostLedger,
+        CostReport,
+    },
+    height_index::{
+        HeightIndex,
+        HeightMapping,
+    },
+    relayer,
+};
 
 pub(crate) type ApiServer = axum::Server<AddrIncoming, IntoMakeService<Router>>;
 
 type RelayerState = watch::Receiver<relayer::StateSnapshot>;
+type Latencies = Arc<relayer::LatencyTracker>;
+type AuxiliaryBlobs = Arc<relayer::AuxiliaryBlobs>;
+type Heights = Option<Arc<HeightIndex>>;
+type Attestations = Option<Arc<AttestationStore>>;
+type Telemetry = Option<telemetry::Handle>;
+type SanitizedConfig = Arc<serde_json::Value>;
 
 #[derive(Clone)]
 /// `AppState` is used for as an axum extractor in its method handlers.
 struct AppState {
     relayer_state: RelayerState,
+    latencies: Latencies,
+    auxiliary_blobs: AuxiliaryBlobs,
+    cost_ledger: Option<CostLedger>,
+    height_index: Heights,
+    attestations: Attestations,
+    telemetry: Telemetry,
+    config: SanitizedConfig,
 }
 
 impl FromRef<AppState> for RelayerState {
@@ -39,13 +87,83 @@ impl FromRef<AppState> for RelayerState {
     }
 }
 
-pub(crate) fn start(socket_addr: SocketAddr, relayer_state: RelayerState) -> ApiServer {
+impl FromRef<AppState> for Latencies {
+    fn from_ref(app_state: &AppState) -> Self {
+        Arc::clone(&app_state.latencies)
+    }
+}
+
+impl FromRef<AppState> for AuxiliaryBlobs {
+    fn from_ref(app_state: &AppState) -> Self {
+        Arc::clone(&app_state.auxiliary_blobs)
+    }
+}
+
+impl FromRef<AppState> for Option<CostLedger> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.cost_ledger.clone()
+    }
+}
+
+impl FromRef<AppState> for Heights {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.height_index.clone()
+    }
+}
+
+impl FromRef<AppState> for Attestations {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.attestations.clone()
+    }
+}
+
+impl FromRef<AppState> for Telemetry {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.telemetry.clone()
+    }
+}
+
+impl FromRef<AppState> for SanitizedConfig {
+    fn from_ref(app_state: &AppState) -> Self {
+        Arc::clone(&app_state.config)
+    }
+}
+
+#[allow(clippy::too_many_arguments)] // Permit because each argument is separate router state
+pub(crate) fn start(
+    socket_addr: SocketAddr,
+    relayer_state: RelayerState,
+    latencies: Latencies,
+    auxiliary_blobs: AuxiliaryBlobs,
+    cost_ledger: Option<CostLedger>,
+    height_index: Heights,
+    attestations: Attestations,
+    telemetry: Telemetry,
+    config: SanitizedConfig,
+) -> ApiServer {
     let app = Router::new()
         .route("/healthz", get(get_healthz))
         .route("/readyz", get(get_readyz))
         .route("/status", get(get_status))
+        .route("/latencies", get(get_latencies))
+        .route("/overdue", get(get_overdue))
+        .route("/costs", get(get_costs))
+        .route("/heights", get(get_heights))
+        .route("/attestations", get(get_attestations))
+        .route("/auxiliary_blobs", post(post_auxiliary_blob))
+        .route("/rollup_results", post(post_rollup_result))
+        .route("/admin/log_filter", get(get_log_filter).put(put_log_filter))
+        .route("/admin/trace_sampling", get(get_trace_sampling).put(put_trace_sampling))
+        .route("/admin/config", get(get_config))
         .with_state(AppState {
             relayer_state,
+            latencies,
+            auxiliary_blobs,
+            cost_ledger,
+            height_index,
+            attestations,
+            telemetry,
+            config,
         });
     axum::Server::bind(&socket_addr).serve(app.into_make_service())
 }
@@ -65,6 +183,8 @@ async fn get_healthz(State(relayer_state): State<RelayerState>) -> Healthz {
 ///
 /// + there is a current sequencer height (implying a block from sequencer was received)
 /// + there is a current data availability height (implying a height was received from the DA)
+/// + the sequencer blocks confirmed on Celestia do not lag behind the sequencer by more than the
+///   configured error threshold
 #[allow(clippy::unused_async)] // Permit because axum handlers must be async
 async fn get_readyz(State(relayer_state): State<RelayerState>) -> Readyz {
     let is_relayer_online = relayer_state.borrow().is_ready();
@@ -80,6 +200,431 @@ async fn get_status(State(relayer_state): State<RelayerState>) -> Json<relayer::
     Json(*relayer_state.borrow())
 }
 
+/// Handler of a call to `/latencies`.
+///
+/// Returns the stage latencies of the most recently confirmed sequencer blocks, oldest first.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_latencies(State(latencies): State<Latencies>) -> Json<Vec<relayer::BlockLatency>> {
+    Json(latencies.recent())
+}
+
+/// Handler of a call to `/overdue`.
+///
+/// Returns the sequencer blocks that are past their submission deadline but not yet confirmed on
+/// Celestia, oldest first. Empty if no deadline is configured.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_overdue(State(latencies): State<Latencies>) -> Json<Vec<relayer::OverdueBlock>> {
+    Json(latencies.overdue())
+}
+
+/// The query of a call to `/costs`.
+#[derive(Debug, Deserialize)]
+struct CostsQuery {
+    /// The start of the report: a duration before now like `7d`, or an RFC 3339 timestamp.
+    /// Defaults to 30 days.
+    since: Option<String>,
+}
+
+/// Handler of a call to `/costs`.
+///
+/// Returns the data posted to Celestia and the fees paid for it since the requested time,
+/// rolled up by the rollup or sequencer network the data was posted for.
+async fn get_costs(
+    State(cost_ledger): State<Option<CostLedger>>,
+    Query(query): Query<CostsQuery>,
+) -> CostsResponse {
+    let Some(cost_ledger) = cost_ledger else {
+        return CostsResponse::Disabled;
+    };
+    let since = query.since.as_deref().unwrap_or("30d");
+    let since = match costs::parse_since(since, SystemTime::now()) {
+        Ok(since) => since,
+        Err(error) => return CostsResponse::Invalid(error.to_string()),
+    };
+    match crate::utils::flatten(
+        tokio::task::spawn_blocking(move || cost_ledger.report(since)).await,
+    ) {
+        Ok(report) => CostsResponse::Ok(report),
+        Err(error) => CostsResponse::Failed(format!("{error:#}")),
+    }
+}
+
+/// The query of a call to `/heights`. Exactly one of the heights must be set.
+#[derive(Debug, Deserialize)]
+struct HeightsQuery {
+    /// Looks up the Celestia heights of the sequencer blocks at this height.
+    sequencer_height: Option<u64>,
+    /// Looks up the sequencer blocks written at this Celestia height.
+    celestia_height: Option<u64>,
+}
+
+/// Handler of a call to `/heights`.
+///
+/// Returns the Celestia heights at which the sequencer blocks at the requested sequencer height
+/// were written, or the sequencer blocks written at the requested Celestia height.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_heights(
+    State(height_index): State<Heights>,
+    Query(query): Query<HeightsQuery>,
+) -> HeightsResponse {
+    let Some(height_index) = height_index else {
+        return HeightsResponse::Disabled;
+    };
+    match (query.sequencer_height, query.celestia_height) {
+        (Some(sequencer_height), None) => {
+            HeightsResponse::Ok(height_index.by_sequencer_height(sequencer_height))
+        }
+        (None, Some(celestia_height)) => {
+            HeightsResponse::Ok(height_index.by_celestia_height(celestia_height))
+        }
+        _ => HeightsResponse::Invalid,
+    }
+}
+
+/// The query of a call to `/attestations`. Exactly one of the fields must be set.
+#[derive(Debug, Deserialize)]
+struct AttestationsQuery {
+    /// Looks up the attestations of the submissions included at this Celestia height.
+    celestia_height: Option<u64>,
+    /// Looks up the attestations of the submissions containing the sequencer block with this
+    /// hex encoded hash.
+    block_hash: Option<String>,
+}
+
+/// Handler of a call to `/attestations`.
+///
+/// Returns the signed attestations of the submissions included at the requested Celestia
+/// height, or of the submissions containing the requested sequencer block.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_attestations(
+    State(attestations): State<Attestations>,
+    Query(query): Query<AttestationsQuery>,
+) -> AttestationsResponse {
+    let Some(attestations) = attestations else {
+        return AttestationsResponse::Disabled;
+    };
+    match (query.celestia_height, query.block_hash) {
+        (Some(celestia_height), None) => {
+            AttestationsResponse::Ok(attestations.by_celestia_height(celestia_height))
+        }
+        (None, Some(block_hash)) => {
+            let Some(block_hash) = hex::decode(block_hash.trim_start_matches("0x"))
+                .ok()
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            else {
+                return AttestationsResponse::Invalid(
+                    "block_hash is not a hex encoded 32 byte hash",
+                );
+            };
+            AttestationsResponse::Ok(attestations.by_block_hash(block_hash))
+        }
+        _ => AttestationsResponse::Invalid(
+            "exactly one of `celestia_height` and `block_hash` must be set",
+        ),
+    }
+}
+
+/// The body of a call to `/admin/log_filter` or `/admin/trace_sampling`, and of their
+/// responses.
+#[derive(Debug, Deserialize, Serialize)]
+struct DirectivesBody {
+    /// The filter directives, like `astria_sequencer_relayer=debug`, or the trace sampling
+    /// directives, like `0.1,astria_sequencer_relayer::relayer::write=1`.
+    directives: String,
+}
+
+/// Handler of a `GET` call to `/admin/log_filter`.
+///
+/// Returns the filter directives currently in effect.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_log_filter(State(telemetry): State<Telemetry>) -> DirectivesResponse {
+    let Some(telemetry) = telemetry else {
+        return DirectivesResponse::Disabled;
+    };
+    match telemetry.filter_directives() {
+        Ok(directives) => DirectivesResponse::Ok(directives),
+        Err(error) => DirectivesResponse::Failed(format!("{error:#}")),
+    }
+}
+
+/// Handler of a `PUT` call to `/admin/log_filter`.
+///
+/// Replaces the filter directives of the relayer's logs and traces until it is restarted.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn put_log_filter(
+    State(telemetry): State<Telemetry>,
+    Json(request): Json<DirectivesBody>,
+) -> DirectivesResponse {
+    let Some(telemetry) = telemetry else {
+        return DirectivesResponse::Disabled;
+    };
+    if let Err(error) = telemetry.set_filter_directives(&request.directives) {
+        return DirectivesResponse::Invalid(format!("{error:#}"));
+    }
+    info!(directives = %request.directives, "replaced log filter directives");
+    DirectivesResponse::Ok(request.directives)
+}
+
+/// Handler of a `GET` call to `/admin/trace_sampling`.
+///
+/// Returns the trace sampling directives currently in effect.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_trace_sampling(State(telemetry): State<Telemetry>) -> DirectivesResponse {
+    let Some(telemetry) = telemetry else {
+        return DirectivesResponse::Disabled;
+    };
+    DirectivesResponse::Ok(telemetry.trace_sampling())
+}
+
+/// Handler of a `PUT` call to `/admin/trace_sampling`.
+///
+/// Replaces the per-module trace sampling ratios until the relayer is restarted.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn put_trace_sampling(
+    State(telemetry): State<Telemetry>,
+    Json(request): Json<DirectivesBody>,
+) -> DirectivesResponse {
+    let Some(telemetry) = telemetry else {
+        return DirectivesResponse::Disabled;
+    };
+    if let Err(error) = telemetry.set_trace_sampling(&request.directives) {
+        return DirectivesResponse::Invalid(format!("{error:#}"));
+    }
+    info!(directives = %request.directives, "replaced trace sampling directives");
+    DirectivesResponse::Ok(telemetry.trace_sampling())
+}
+
+/// The body of a call to `/auxiliary_blobs`.
+/// Returns the config the relayer was started with, with all secrets redacted.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn get_config(State(config): State<SanitizedConfig>) -> Json<serde_json::Value> {
+    Json((*config).clone())
+}
+
+#[derive(Debug, Deserialize)]
+struct AuxiliaryBlobRequest {
+    /// The chain ID of the sequencer network of the block the blob is submitted with.
+    sequencer_chain_id: String,
+    /// The height of the sequencer block the blob is submitted with.
+    sequencer_height: u64,
+    /// The hex encoded 10 byte Celestia namespace ID under which the blob is written.
+    namespace: String,
+    /// The operator defined kind of the blob, e.g. `batch-proof`.
+    kind: String,
+    /// The base64 encoded data of the blob.
+    data: String,
+}
+
+/// Handler of a call to `/auxiliary_blobs`.
+///
+/// Queues an operator defined blob to be submitted to Celestia in the same transaction as the
+/// sequencer block at the given height, which must not have been converted to blobs yet.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn post_auxiliary_blob(
+    State(auxiliary_blobs): State<AuxiliaryBlobs>,
+    Json(request): Json<AuxiliaryBlobRequest>,
+) -> AuxiliaryBlobResponse {
+    let AuxiliaryBlobRequest {
+        sequencer_chain_id,
+        sequencer_height,
+        namespace,
+        kind,
+        data,
+    } = request;
+    let Ok(height) = SequencerHeight::try_from(sequencer_height) else {
+        return AuxiliaryBlobResponse::Invalid("sequencer height is out of range");
+    };
+    let Ok(namespace) = astria_core::celestia::namespace_v0_from_hex(&namespace) else {
+        return AuxiliaryBlobResponse::Invalid("namespace is not a valid hex encoded namespace ID");
+    };
+    let Ok(data) = STANDARD.decode(data) else {
+        return AuxiliaryBlobResponse::Invalid("data is not base64 encoded");
+    };
+    let blob = relayer::PendingAuxiliaryBlob {
+        namespace,
+        kind,
+        data,
+    };
+    match auxiliary_blobs.insert(sequencer_chain_id, height, blob) {
+        Ok(()) => AuxiliaryBlobResponse::Accepted,
+        Err(error) => AuxiliaryBlobResponse::Rejected(error),
+    }
+}
+
+/// The body of a call to `/rollup_results`. All hashes and roots are hex encoded 32 bytes.
+#[derive(Debug, Deserialize)]
+struct RollupResultRequest {
+    /// The hash of the sequencer block the rollup block was derived from.
+    sequencer_block_hash: String,
+    /// The hex encoded ID of the rollup that executed the block.
+    rollup_id: String,
+    /// The number of the executed rollup block.
+    rollup_block_number: u64,
+    /// The hash of the executed rollup block.
+    rollup_block_hash: String,
+    /// The state root after executing the rollup block.
+    state_root: String,
+    /// The root of the receipts of executing the rollup block.
+    receipts_root: String,
+}
+
+/// Handler of a call to `/rollup_results`.
+///
+/// Queues the result of executing a rollup block to be submitted to Celestia with the next
+/// sequencer block, under the result namespace of the rollup.
+#[allow(clippy::unused_async)] // Permit because axum handlers must be async
+async fn post_rollup_result(
+    State(auxiliary_blobs): State<AuxiliaryBlobs>,
+    Json(request): Json<RollupResultRequest>,
+) -> AuxiliaryBlobResponse {
+    let result = match parse_rollup_result(request) {
+        Ok(result) => result,
+        Err(reason) => return AuxiliaryBlobResponse::Invalid(reason),
+    };
+    match auxiliary_blobs.insert_rollup_result(result) {
+        Ok(()) => AuxiliaryBlobResponse::Accepted,
+        Err(error) => AuxiliaryBlobResponse::Rejected(error),
+    }
+}
+
+fn parse_rollup_result(
+    request: RollupResultRequest,
+) -> Result<RollupResultNamespaceData, &'static str> {
+    fn parse_hash(input: &str, reason: &'static str) -> Result<[u8; 32], &'static str> {
+        hex::decode(input.trim_start_matches("0x"))
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(reason)
+    }
+
+    let RollupResultRequest {
+        sequencer_block_hash,
+        rollup_id,
+        rollup_block_number,
+        rollup_block_hash,
+        state_root,
+        receipts_root,
+    } = request;
+    Ok(UncheckedRollupResultNamespaceData {
+        sequencer_block_hash: parse_hash(
+            &sequencer_block_hash,
+            "sequencer_block_hash is not a hex encoded 32 byte hash",
+        )?,
+        rollup_id: RollupId::new(parse_hash(
+            &rollup_id,
+            "rollup_id is not a hex encoded 32 byte rollup ID",
+        )?),
+        rollup_block_number,
+        rollup_block_hash: parse_hash(
+            &rollup_block_hash,
+            "rollup_block_hash is not a hex encoded 32 byte hash",
+        )?,
+        state_root: parse_hash(&state_root, "state_root is not a hex encoded 32 byte hash")?,
+        receipts_root: parse_hash(
+            &receipts_root,
+            "receipts_root is not a hex encoded 32 byte hash",
+        )?,
+    }
+    .into_rollup_result_namespace_data())
+}
+
+enum AuxiliaryBlobResponse {
+    Accepted,
+    Invalid(&'static str),
+    Rejected(relayer::AuxiliaryBlobInsertError),
+}
+
+impl IntoResponse for AuxiliaryBlobResponse {
+    fn into_response(self) -> Response {
+        use relayer::AuxiliaryBlobInsertError as Error;
+
+        #[derive(Debug, Serialize)]
+        struct AuxiliaryBlobBody {
+            status: String,
+        }
+        let (status, msg) = match self {
+            Self::Accepted => (StatusCode::ACCEPTED, "accepted".to_string()),
+            Self::Invalid(reason) => (StatusCode::BAD_REQUEST, reason.to_string()),
+            Self::Rejected(error) => {
+                let status = match &error {
+                    Error::EmptyKind | Error::ReservedNamespace => StatusCode::BAD_REQUEST,
+                    Error::TooLarge {
+                        ..
+                    } => StatusCode::PAYLOAD_TOO_LARGE,
+                    Error::AlreadyConverted {
+                        ..
+                    } => StatusCode::CONFLICT,
+                    Error::Full => StatusCode::SERVICE_UNAVAILABLE,
+                };
+                (status, error.to_string())
+            }
+        };
+        let mut response = Json(AuxiliaryBlobBody {
+            status: msg,
+        })
+        .into_response();
+        *response.status_mut() = status;
+        response
+    }
+}
+
+enum CostsResponse {
+    Ok(CostReport),
+    Disabled,
+    Invalid(String),
+    Failed(String),
+}
+
+impl IntoResponse for CostsResponse {
+    fn into_response(self) -> Response {
+        #[derive(Debug, Serialize)]
+        struct CostsErrorBody {
+            status: String,
+        }
+        let (status, msg) = match self {
+            Self::Ok(report) => return Json(report).into_response(),
+            Self::Disabled => (StatusCode::NOT_FOUND, "cost accounting is disabled".to_string()),
+            Self::Invalid(reason) => (StatusCode::BAD_REQUEST, reason),
+            Self::Failed(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason),
+        };
+        let mut response = Json(CostsErrorBody {
+            status: msg,
+        })
+        .into_response();
+        *response.status_mut() = status;
+        response
+    }
+}
+
+enum HeightsResponse {
+    Ok(Vec<HeightMapping>),
+    Disabled,
+    Invalid,
+}
+
+impl IntoResponse for HeightsResponse {
+    fn into_response(self) -> Response {
+        #[derive(Debug, Serialize)]
+        struct HeightsErrorBody {
+            status: &'static str,
+        }
+        let (status, msg) = match self {
+            Self::Ok(mappings) => return Json(mappings).into_response(),
+            Self::Disabled => (StatusCode::NOT_FOUND, "the height index is disabled"),
+            Self::Invalid => (
+                StatusCode::BAD_REQUEST,
+                "exactly one of `sequencer_height` and `celestia_height` must be set",
+            ),
+        };
+        let mut response = Json(HeightsErrorBody {
+            status: msg,
+        })
+        .into_response();
+        *response.status_mut() = status;
+        response
+    }
+}
+
 enum Healthz {
     Ok,
     Degraded,
@@ -127,3 +672,65 @@ impl IntoResponse for Readyz {
         response
     }
 }
+
+enum AttestationsResponse {
+    Ok(Vec<SignedSubmissionAttestation>),
+    Disabled,
+    Invalid(&'static str),
+}
+
+impl IntoResponse for AttestationsResponse {
+    fn into_response(self) -> Response {
+        #[derive(Debug, Serialize)]
+        struct AttestationsErrorBody {
+            status: &'static str,
+        }
+        let (status, msg) = match self {
+            Self::Ok(attestations) => return Json(attestations).into_response(),
+            Self::Disabled => (StatusCode::NOT_FOUND, "attestations are disabled"),
+            Self::Invalid(reason) => (StatusCode::BAD_REQUEST, reason),
+        };
+        let mut response = Json(AttestationsErrorBody {
+            status: msg,
+        })
+        .into_response();
+        *response.status_mut() = status;
+        response
+    }
+}
+
+enum DirectivesResponse {
+    Ok(String),
+    Disabled,
+    Invalid(String),
+    Failed(String),
+}
+
+impl IntoResponse for DirectivesResponse {
+    fn into_response(self) -> Response {
+        #[derive(Debug, Serialize)]
+        struct DirectivesErrorBody {
+            status: String,
+        }
+        let (status, msg) = match self {
+            Self::Ok(directives) => {
+                return Json(DirectivesBody {
+                    directives,
+                })
+                .into_response();
+            }
+            Self::Disabled => (
+                StatusCode::NOT_FOUND,
+                "telemetry was not initialized with a handle".to_string(),
+            ),
+            Self::Invalid(reason) => (StatusCode::BAD_REQUEST, reason),
+            Self::Failed(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason),
+        };
+        let mut response = Json(DirectivesErrorBody {
+            status: msg,
+        })
+        .into_response();
+        *response.status_mut() = status;
+        response
+    }
+}
@@ -0,0 +1,260 @@
+//! Signed attestations of the submissions the relayer confirmed on Celestia.
+//!
+//! If attestations are configured, the relayer signs a [`SubmissionAttestation`] for every
+//! confirmed submission and appends it to a file, one [`SignedSubmissionAttestation`] per line.
+//! The file is read back into memory on startup, so that attestations can be looked up by the
+//! Celestia height of their submission or by the hash of a sequencer block they cover. They are
+//! served by the `/attestations` endpoint of the relayer's API.
+
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fs::{
+        File,
+        OpenOptions,
+    },
+    io::{
+        BufRead as _,
+        BufReader,
+        ErrorKind,
+        Write as _,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        PoisonError,
+        RwLock,
+    },
+};
+
+use astria_core::attestation::{
+    AttestedBlob,
+    AttestedSequencerBlock,
+    SignedSubmissionAttestation,
+    SubmissionAttestation,
+};
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+
+use crate::validator::Validator;
+
+/// The append-only file the relayer records its signed attestations in, and the key signing
+/// them.
+#[derive(Debug)]
+pub struct AttestationStore {
+    path: PathBuf,
+    key: Validator,
+    inner: RwLock<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    attestations: Vec<SignedSubmissionAttestation>,
+    /// The indices of the attestations of each Celestia height.
+    by_celestia_height: BTreeMap<u64, Vec<usize>>,
+    /// The indices of the attestations covering each sequencer block.
+    by_block_hash: HashMap<[u8; 32], Vec<usize>>,
+}
+
+impl Inner {
+    fn insert(&mut self, signed: SignedSubmissionAttestation, attestation: &SubmissionAttestation) {
+        let index = self.attestations.len();
+        self.attestations.push(signed);
+        self.by_celestia_height
+            .entry(attestation.celestia_height)
+            .or_default()
+            .push(index);
+        for block in &attestation.sequencer_blocks {
+            self.by_block_hash
+                .entry(block.block_hash)
+                .or_default()
+                .push(index);
+        }
+    }
+
+    fn get(&self, indices: Option<&Vec<usize>>) -> Vec<SignedSubmissionAttestation> {
+        indices
+            .into_iter()
+            .flatten()
+            .map(|index| self.attestations[*index].clone())
+            .collect()
+    }
+}
+
+impl AttestationStore {
+    /// Opens the attestations stored in the file at `path`, which are signed with `key`.
+    ///
+    /// A file that does not exist yet contains no attestations; it is created when the first
+    /// attestation is recorded. This is a blocking operation and should not be called from an
+    /// async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or contains an attestation that is
+    /// malformed or whose signature does not match.
+    pub(crate) fn open(path: impl Into<PathBuf>, key: Validator) -> eyre::Result<Self> {
+        let path = path.into();
+        let mut inner = Inner::default();
+        for (signed, attestation) in read(&path)? {
+            inner.insert(signed, &attestation);
+        }
+        Ok(Self {
+            path,
+            key,
+            inner: RwLock::new(inner),
+        })
+    }
+
+    /// Returns the path of the file the attestations are stored in.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Signs an attestation of a submission included at `celestia_height`, appends it to the
+    /// file and adds it to the store.
+    ///
+    /// This is a blocking operation and should not be called from an async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be opened or written to. The attestation is not
+    /// added to the store in that case.
+    pub(crate) fn attest(
+        &self,
+        celestia_height: u64,
+        timestamp: u64,
+        sequencer_blocks: Vec<AttestedSequencerBlock>,
+        blobs: Vec<AttestedBlob>,
+    ) -> eyre::Result<SignedSubmissionAttestation> {
+        let attestation = SubmissionAttestation {
+            relayer_address: self.key.address.to_string(),
+            celestia_height,
+            timestamp,
+            sequencer_blocks,
+            blobs,
+        };
+        let signed = SignedSubmissionAttestation::sign(&attestation, &self.key.signing_key);
+        let mut line = signed.encode();
+        line.push(b'\n');
+        // Holding the write lock while appending keeps the file in the same order as the store.
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .wrap_err("failed opening attestations for appending")?;
+        file.write_all(&line)
+            .wrap_err("failed writing attestation to file")?;
+        file.sync_data()
+            .wrap_err("failed syncing attestations to disk")?;
+        inner.insert(signed.clone(), &attestation);
+        Ok(signed)
+    }
+
+    /// Returns the attestations of the submissions included at `celestia_height`.
+    #[must_use]
+    pub fn by_celestia_height(&self, celestia_height: u64) -> Vec<SignedSubmissionAttestation> {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        inner.get(inner.by_celestia_height.get(&celestia_height))
+    }
+
+    /// Returns the attestations of the submissions containing the sequencer block with
+    /// `block_hash`, in the order they were confirmed.
+    #[must_use]
+    pub fn by_block_hash(&self, block_hash: [u8; 32]) -> Vec<SignedSubmissionAttestation> {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        inner.get(inner.by_block_hash.get(&block_hash))
+    }
+}
+
+/// Reads all signed attestations recorded in the file at `path`.
+///
+/// This is a blocking operation and should not be called from an async context.
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read or contains an attestation that is malformed
+/// or whose signature does not match.
+pub fn read_all(path: &Path) -> eyre::Result<Vec<SignedSubmissionAttestation>> {
+    Ok(read(path)?.into_iter().map(|(signed, _)| signed).collect())
+}
+
+fn read(path: &Path) -> eyre::Result<Vec<(SignedSubmissionAttestation, SubmissionAttestation)>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(error).wrap_err("failed opening attestations for reading"),
+    };
+    let mut attestations = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.wrap_err("failed reading line from attestations")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let signed = SignedSubmissionAttestation::decode(line.as_bytes())
+            .wrap_err_with(|| format!("failed decoding line {} of attestations", index + 1))?;
+        let attestation = signed
+            .verify()
+            .wrap_err_with(|| format!("failed verifying attestation on line {}", index + 1))?;
+        attestations.push((signed, attestation));
+    }
+    Ok(attestations)
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_consensus::SigningKey;
+    use tendermint::account;
+
+    use super::*;
+
+    fn key() -> Validator {
+        let signing_key = SigningKey::from([1; 32]);
+        Validator {
+            address: account::Id::new([2; 20]),
+            verification_key: signing_key.verification_key(),
+            signing_key,
+        }
+    }
+
+    fn block(height: u64, block_hash: [u8; 32]) -> AttestedSequencerBlock {
+        AttestedSequencerBlock {
+            chain_id: "sequencer".to_string(),
+            height,
+            block_hash,
+        }
+    }
+
+    #[test]
+    fn recorded_attestations_are_found_after_reopening() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let store = AttestationStore::open(file.path(), key()).unwrap();
+        store
+            .attest(100, 1, vec![block(5, [5; 32]), block(6, [6; 32])], vec![])
+            .unwrap();
+        store.attest(101, 2, vec![block(6, [6; 32])], vec![]).unwrap();
+
+        let reopened = AttestationStore::open(file.path(), key()).unwrap();
+        let at_100 = reopened.by_celestia_height(100);
+        assert_eq!(1, at_100.len());
+        let attestation = at_100[0].verify().unwrap();
+        assert_eq!(key().address.to_string(), attestation.relayer_address);
+        assert_eq!(vec![block(5, [5; 32]), block(6, [6; 32])], attestation.sequencer_blocks);
+
+        let heights: Vec<_> = reopened
+            .by_block_hash([6; 32])
+            .iter()
+            .map(|signed| signed.verify().unwrap().celestia_height)
+            .collect();
+        assert_eq!(vec![100, 101], heights);
+        assert!(reopened.by_block_hash([7; 32]).is_empty());
+        assert!(reopened.by_celestia_height(102).is_empty());
+    }
+}
@@ -0,0 +1,377 @@
+//! A read-only mode of the relayer for third parties auditing the data it writes to Celestia.
+//!
+//! The `audit` subcommand of the relayer binary follows Celestia and checks every blob written
+//! under the sequencer namespace of a sequencer network, accepting exactly the sequencer blobs
+//! and markers of empty blocks that [`crate::verify`] accepts. It reads no signing key and has no
+//! way to submit anything: all it needs is the JSON-RPC endpoint of a Celestia node.
+//!
+//! Every sequencer block found valid is recorded in a [`HeightIndex`], which can be queried with
+//! the `heights` subcommand, and the outcome of every blob is exported as metrics. Blobs that
+//! fail verification are logged, since they mean that the relayer wrote invalid data or that a
+//! third party is writing to its namespace.
+//!
+//! Rollup blobs are not audited. Conductor verifies them against the sequencer blobs when it
+//! reads them.
+
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+    sync::Arc,
+    time::Duration,
+};
+
+use astria_celestia_client::{
+    celestia_rpc::HeaderClient as _,
+    jsonrpsee::http_client::HttpClient,
+    CelestiaClientExt as _,
+    ClientBuilder,
+};
+use astria_core::{
+    celestia::{
+        namespace_v0_from_hex,
+        namespace_v0_from_sha256_of_bytes,
+        HexNamespace,
+    },
+    sequencerblock::v1alpha1::CelestiaSequencerBlob,
+};
+use astria_eyre::eyre::{
+    self,
+    bail,
+    WrapErr as _,
+};
+use celestia_types::nmt::Namespace;
+use clap::Parser;
+use tokio::signal::unix::{
+    signal,
+    SignalKind,
+};
+use tracing::{
+    info,
+    instrument,
+    warn,
+};
+
+use crate::{
+    height_index::{
+        HeightIndex,
+        HeightMapping,
+    },
+    metrics_init,
+    verify::{
+        self,
+        VerifiedData,
+        VerifyOptions,
+    },
+};
+
+/// The arguments of the `audit` subcommand of the relayer binary.
+#[derive(Debug, Parser)]
+#[command(
+    name = "audit",
+    about = "Follows Celestia and verifies the sequencer blocks written by a relayer, without \
+             signing or submitting anything"
+)]
+pub struct AuditArgs {
+    /// The JSON-RPC endpoint of the Celestia node to read blobs from.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_AUDIT_CELESTIA_NODE_URL")]
+    celestia_node_url: String,
+    /// The bearer token authenticating requests to the Celestia node, if it requires one.
+    #[arg(
+        long,
+        env = "ASTRIA_SEQUENCER_RELAYER_AUDIT_CELESTIA_NODE_TOKEN",
+        hide_env_values = true
+    )]
+    celestia_node_token: Option<String>,
+    /// The chain ID of the sequencer network whose blocks are audited. Blocks of any other
+    /// network are rejected.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_AUDIT_SEQUENCER_CHAIN_ID")]
+    sequencer_chain_id: String,
+    /// The hex encoded Celestia namespace the blocks are written under. Derived from the chain
+    /// ID if empty, like the relayer does.
+    #[arg(
+        long,
+        env = "ASTRIA_SEQUENCER_RELAYER_CELESTIA_SEQUENCER_NAMESPACE",
+        default_value = ""
+    )]
+    sequencer_namespace: String,
+    /// The Celestia height to start auditing at, unless the height index records a later one.
+    #[arg(long, default_value_t = 1)]
+    start_height: u64,
+    /// The path of the height index to record the verified sequencer blocks in.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_HEIGHT_INDEX_PATH")]
+    index: Option<PathBuf>,
+    /// How often to ask the Celestia node for new heights, like `6s`.
+    #[arg(long, default_value = "6s", value_parser = humantime::parse_duration)]
+    poll_interval: Duration,
+    /// The address to serve Prometheus metrics on. No metrics are served if unset.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_METRICS_HTTP_LISTENER_ADDR")]
+    metrics_addr: Option<String>,
+    /// The filter directives of the logs.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_LOG", default_value = "info")]
+    log: String,
+    /// Disables exporting traces over OTLP.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_NO_OTEL")]
+    no_otel: bool,
+}
+
+/// Runs the `audit` subcommand with `args`, the first of which must be the subcommand's name.
+///
+/// Audits Celestia heights as they are produced until `SIGTERM` is received.
+pub async fn run_cli<I, T>(args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let args = AuditArgs::parse_from(args);
+
+    let mut telemetry_conf = telemetry::configure()
+        .set_no_otel(args.no_otel)
+        .filter_directives(&args.log);
+    if let Some(metrics_addr) = &args.metrics_addr {
+        telemetry_conf = telemetry_conf
+            .metrics_addr(metrics_addr)
+            .service_name(env!("CARGO_PKG_NAME"));
+    }
+    metrics_init::register();
+    if let Err(error) = telemetry_conf.try_init() {
+        eprintln!("initializing auditor failed:\n{error:?}");
+        return ExitCode::FAILURE;
+    }
+
+    let poll_interval = args.poll_interval;
+    let auditor = match Auditor::new(args) {
+        Ok(auditor) => auditor,
+        Err(error) => {
+            eprintln!("initializing auditor failed:\n{error:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut sigterm = signal(SignalKind::terminate())
+        .expect("setting a SIGTERM listener should always work on Unix");
+    tokio::select! {
+        () = auditor.run(poll_interval) => unreachable!("the auditor runs until it is stopped"),
+        _ = sigterm.recv() => {
+            info!("received SIGTERM, stopping auditor");
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// Walks Celestia heights and verifies the blobs under the sequencer namespace at each of them.
+struct Auditor {
+    client: HttpClient,
+    namespace: Namespace,
+    options: VerifyOptions,
+    index: Option<Arc<HeightIndex>>,
+    next_height: u64,
+}
+
+impl Auditor {
+    fn new(args: AuditArgs) -> eyre::Result<Self> {
+        let AuditArgs {
+            celestia_node_url,
+            celestia_node_token,
+            sequencer_chain_id,
+            sequencer_namespace,
+            start_height,
+            index,
+            ..
+        } = args;
+        let mut client = ClientBuilder::new(celestia_node_url);
+        if let Some(token) = celestia_node_token {
+            client = client.bearer_token(token);
+        }
+        let client = client
+            .build()
+            .wrap_err("failed constructing Celestia node client")?;
+        let namespace = if sequencer_namespace.is_empty() {
+            namespace_v0_from_sha256_of_bytes(&sequencer_chain_id)
+        } else {
+            namespace_v0_from_hex(&sequencer_namespace).wrap_err_with(|| {
+                format!("`{sequencer_namespace}` is not a valid hex encoded Celestia namespace ID")
+            })?
+        };
+        let index = index
+            .map(HeightIndex::open)
+            .transpose()
+            .wrap_err("failed opening height index")?;
+        let next_height = index
+            .as_ref()
+            .and_then(HeightIndex::latest_celestia_height)
+            .map_or(start_height, |latest| {
+                start_height.max(latest.saturating_add(1))
+            });
+        info!(
+            %sequencer_chain_id,
+            namespace = %HexNamespace(namespace),
+            next_height,
+            "auditing sequencer blocks written to Celestia",
+        );
+        Ok(Self {
+            client,
+            namespace,
+            options: VerifyOptions::default().expected_chain_id(sequencer_chain_id),
+            index: index.map(Arc::new),
+            next_height,
+        })
+    }
+
+    /// Audits all Celestia heights up to the latest one every `poll_interval`.
+    ///
+    /// A height that could not be audited is retried at the next poll, so no height is skipped.
+    async fn run(mut self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let latest_height = match self.client.header_network_head().await {
+                Ok(header) => header.height().value(),
+                Err(error) => {
+                    metrics::counter!(metrics_init::AUDIT_FETCH_FAILURE_COUNT).increment(1);
+                    warn!(%error, "failed fetching the latest Celestia height; retrying");
+                    continue;
+                }
+            };
+            while self.next_height <= latest_height {
+                if let Err(error) = self.audit_height(self.next_height).await {
+                    warn!(%error, height = self.next_height, "failed auditing Celestia height");
+                    break;
+                }
+                self.next_height = self.next_height.saturating_add(1);
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn audit_height(&self, height: u64) -> eyre::Result<()> {
+        let blobs = match self.client.get_raw_blobs(height, &[self.namespace]).await {
+            Ok(blobs) => blobs,
+            Err(error) => {
+                metrics::counter!(metrics_init::AUDIT_FETCH_FAILURE_COUNT).increment(1);
+                return Err(error).wrap_err("failed fetching blobs under the sequencer namespace");
+            }
+        };
+
+        let mut mappings = Vec::new();
+        for blob in blobs {
+            match audit_blob(&blob.data, &self.options) {
+                Ok(sequencer_blobs) => {
+                    metrics::counter!(
+                        metrics_init::AUDITED_SEQUENCER_BLOBS,
+                        telemetry::metrics::RESULT_LABEL => "verified",
+                    )
+                    .increment(1);
+                    mappings.extend(sequencer_blobs.iter().map(|sequencer_blob| HeightMapping {
+                        sequencer_chain_id: sequencer_blob.header().chain_id().to_string(),
+                        sequencer_height: sequencer_blob.height().value(),
+                        celestia_height: height,
+                    }));
+                }
+                Err(error) => {
+                    metrics::counter!(
+                        metrics_init::AUDITED_SEQUENCER_BLOBS,
+                        telemetry::metrics::RESULT_LABEL => "rejected",
+                    )
+                    .increment(1);
+                    warn!(
+                        %error,
+                        commitment = %telemetry::display::base64(&blob.commitment.0),
+                        "blob under the sequencer namespace failed verification; the relayer \
+                         wrote invalid data or a third party is writing to its namespace",
+                    );
+                }
+            }
+        }
+
+        if let Some(index) = self.index.clone() {
+            if !mappings.is_empty() {
+                crate::utils::flatten(
+                    tokio::task::spawn_blocking(move || index.record(&mappings)).await,
+                )
+                .wrap_err("failed recording verified sequencer blocks in height index")?;
+            }
+        }
+        // allow: Celestia heights are far below 2^52, where conversion to f64 loses precision
+        #[allow(clippy::cast_precision_loss)]
+        let audited_height = height as f64;
+        metrics::gauge!(metrics_init::AUDITED_CELESTIA_HEIGHT).set(audited_height);
+        Ok(())
+    }
+}
+
+/// Verifies `data`, the data of a blob under the sequencer namespace, returning the sequencer
+/// blobs of the blocks it contains.
+fn audit_blob(data: &[u8], options: &VerifyOptions) -> eyre::Result<Vec<CelestiaSequencerBlob>> {
+    let error = match verify::verify_signed_namespace_data(data, options) {
+        Ok(VerifiedData::SequencerBlob(sequencer_blob)) => return Ok(vec![sequencer_blob]),
+        Ok(VerifiedData::RollupBlob(rollup_blob)) => bail!(
+            "found a blob of rollup `{}` under the sequencer namespace",
+            rollup_blob.rollup_id()
+        ),
+        Err(error) => error,
+    };
+    verify::verify_empty_blocks_marker(data, options).map_err(|marker_error| {
+        eyre::eyre!("{error:#}; as a marker of empty blocks: {marker_error:#}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use astria_core::{
+        brotli::compress_bytes,
+        protocol::test_utils::ConfigureSequencerBlock,
+        sequencerblock::v1alpha1::CelestiaEmptyBlocksMarker,
+    };
+    use prost::Message as _;
+
+    use super::*;
+
+    fn sequencer_blob(height: u32) -> CelestiaSequencerBlob {
+        ConfigureSequencerBlock {
+            chain_id: Some("sequencer-test".to_string()),
+            height,
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs()
+        .0
+    }
+
+    #[test]
+    fn sequencer_blobs_and_markers_of_the_audited_chain_are_accepted() {
+        let options = VerifyOptions::default().expected_chain_id("sequencer-test");
+
+        let bytes = compress_bytes(&sequencer_blob(2).into_raw().encode_to_vec()).unwrap();
+        let blocks = audit_blob(&bytes, &options).unwrap();
+        assert_eq!(vec![2], heights(&blocks));
+
+        let marker =
+            CelestiaEmptyBlocksMarker::new(vec![sequencer_blob(3), sequencer_blob(4)]).unwrap();
+        let bytes = compress_bytes(&marker.into_raw().encode_to_vec()).unwrap();
+        let blocks = audit_blob(&bytes, &options).unwrap();
+        assert_eq!(vec![3, 4], heights(&blocks));
+
+        let options = VerifyOptions::default().expected_chain_id("other-chain");
+        assert!(audit_blob(&bytes, &options).is_err());
+    }
+
+    #[test]
+    fn rollup_blobs_and_garbage_are_rejected() {
+        let options = VerifyOptions::default();
+        let (_, rollup_blobs) = ConfigureSequencerBlock {
+            sequence_data: vec![(astria_core::primitive::v1::RollupId::new([2; 32]), vec![1])],
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        let bytes = compress_bytes(&rollup_blobs[0].clone().into_raw().encode_to_vec()).unwrap();
+        assert!(audit_blob(&bytes, &options).is_err());
+
+        let bytes = compress_bytes(b"not a blob").unwrap();
+        assert!(audit_blob(&bytes, &options).is_err());
+    }
+
+    fn heights(blocks: &[CelestiaSequencerBlob]) -> Vec<u64> {
+        blocks.iter().map(|block| block.height().value()).collect()
+    }
+}
@@ -0,0 +1,568 @@
+//! A self-check of the relayer's configuration, to catch misconfigurations before a relayer goes
+//! live.
+//!
+//! The `check` subcommand of the relayer binary reads the configuration exactly like the relayer
+//! does, and then checks everything the relayer depends on at runtime: that its settings are
+//! valid, that the keys it signs with can be read, that the Celestia app and every sequencer
+//! network can be reached, that the Celestia namespaces of the sequencer networks can be derived,
+//! and that the Celestia account can pay for a typical submission. Nothing is submitted and no
+//! state of the relayer is written.
+//!
+//! The outcome of every check is collected in a [`Report`], which is printed and optionally
+//! written as JSON. The subcommand fails if any of the checks failed.
+
+use std::{
+    fmt,
+    fs,
+    future::Future,
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
+
+use astria_core::{
+    celestia::HexNamespace,
+    generated::sequencerblock::v1alpha1::{
+        sequencer_service_client::SequencerServiceClient,
+        GetSequencerBlockRequest,
+    },
+};
+use astria_eyre::eyre::{
+    self,
+    bail,
+    ensure,
+    WrapErr as _,
+};
+use clap::Parser;
+use sequencer_client::{
+    Client as _,
+    HttpClient as SequencerClient,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use tonic::transport::{
+    Endpoint,
+    Uri,
+};
+
+use crate::{
+    config::{
+        RollupSigningKeyFile,
+        SequencerNetworkConfig,
+    },
+    relayer::{
+        CelestiaConnection,
+        CelestiaPreflight,
+        RollupSigningKey,
+    },
+    validator::Validator,
+    Config,
+};
+
+/// How long a single request to the Celestia app or a sequencer network may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default size in bytes of the data of a blob in a typical submission.
+const DEFAULT_BLOB_SIZE: u32 = 2048;
+
+/// The outcome of a single check.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CheckOutcome {
+    /// What was checked.
+    pub name: String,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// What was found if the check passed, or why it failed.
+    pub detail: String,
+}
+
+/// The outcomes of all checks of a relayer configuration.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Report {
+    /// Whether all checks passed.
+    pub passed: bool,
+    /// The outcomes of the checks in the order they were run.
+    pub checks: Vec<CheckOutcome>,
+}
+
+impl Report {
+    fn new() -> Self {
+        Self {
+            passed: true,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Records the outcome of the check `name`, returning the value it produced if it passed.
+    fn record<T>(
+        &mut self,
+        name: impl Into<String>,
+        result: eyre::Result<(T, String)>,
+    ) -> Option<T> {
+        let (value, passed, detail) = match result {
+            Ok((value, detail)) => (Some(value), true, detail),
+            Err(error) => (None, false, format!("{error:#}")),
+        };
+        self.passed &= passed;
+        self.checks.push(CheckOutcome {
+            name: name.into(),
+            passed,
+            detail,
+        });
+        value
+    }
+
+    fn failed_count(&self) -> usize {
+        self.checks.iter().filter(|check| !check.passed).count()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let status = if check.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "{status}  {}: {}", check.name, check.detail)?;
+        }
+        if self.passed {
+            write!(f, "all {} checks passed", self.checks.len())
+        } else {
+            write!(
+                f,
+                "{} of {} checks failed",
+                self.failed_count(),
+                self.checks.len()
+            )
+        }
+    }
+}
+
+/// The size of the typical submission the Celestia account must be able to pay for.
+#[derive(Clone, Copy, Debug)]
+struct TypicalSubmission {
+    blob_count: u32,
+    blob_size: u32,
+}
+
+/// Runs all checks of the relayer configuration `cfg`.
+async fn run(cfg: &Config, submission: TypicalSubmission) -> Report {
+    let mut report = Report::new();
+
+    report.record(
+        "settings",
+        check_settings(cfg).map(|()| ((), "valid".to_string())),
+    );
+    let additional_networks = report.record(
+        "additional sequencer networks",
+        cfg.additional_sequencer_networks().map(|networks| {
+            let detail = format!("{} configured", networks.len());
+            (networks, detail)
+        }),
+    );
+    let additional_networks = additional_networks.unwrap_or_default();
+
+    check_keys(&mut report, cfg, &additional_networks);
+
+    let primary_chain_id = check_sequencer_network(
+        &mut report,
+        "primary",
+        None,
+        &cfg.cometbft_endpoint,
+        &cfg.sequencer_grpc_endpoint,
+    )
+    .await;
+    for network in &additional_networks {
+        check_sequencer_network(
+            &mut report,
+            &network.chain_id,
+            Some(&network.chain_id),
+            &network.cometbft_endpoint,
+            &network.sequencer_grpc_endpoint,
+        )
+        .await;
+    }
+
+    report.record(
+        "sequencer namespaces",
+        describe_sequencer_namespaces(cfg, primary_chain_id.as_deref(), &additional_networks)
+            .map(|detail| ((), detail)),
+    );
+
+    let preflight = report.record(
+        "celestia key",
+        CelestiaPreflight::new(&cfg.celestia_app_grpc_endpoint, &cfg.celestia_app_key_file).map(
+            |preflight| {
+                let detail = format!("account {}", preflight.address());
+                (preflight, detail)
+            },
+        ),
+    );
+    let Some(preflight) = preflight else {
+        return report;
+    };
+    let connection = report.record(
+        "celestia app",
+        with_timeout(preflight.connect()).await.map(|connection| {
+            let detail = format!("connected to chain `{}`", connection.chain_id());
+            (connection, detail)
+        }),
+    );
+    if let Some(connection) = connection {
+        report.record(
+            "celestia funds",
+            check_funds(&connection, submission)
+                .await
+                .map(|detail| ((), detail)),
+        );
+    }
+    report
+}
+
+/// Parses every setting that is only validated once the relayer starts.
+fn check_settings(cfg: &Config) -> eyre::Result<()> {
+    cfg.only_include_rollups()?;
+    cfg.rollup_quotas()?;
+    cfg.known_signers()?;
+    cfg.celestia_error_policy()?;
+    cfg.batching_bounds()?;
+    cfg.storage()?;
+    cfg.leader_election()?;
+    Ok(())
+}
+
+/// Reads every key the relayer signs with.
+fn check_keys(report: &mut Report, cfg: &Config, additional_networks: &[SequencerNetworkConfig]) {
+    if cfg.relay_only_validator_key_blocks {
+        report.record("validator key", read_key(&cfg.validator_key_file));
+    }
+    for network in additional_networks {
+        if let Some(path) = &network.validator_key_file {
+            report.record(
+                format!("validator key ({})", network.chain_id),
+                read_key(path),
+            );
+        }
+    }
+    match cfg.heartbeat_namespace() {
+        Ok(Some(namespace)) => {
+            report.record(
+                "heartbeat key",
+                read_key(&cfg.heartbeat_key_file).map(|((), detail)| {
+                    (
+                        (),
+                        format!(
+                            "{detail}, heartbeats written under {}",
+                            HexNamespace(namespace)
+                        ),
+                    )
+                }),
+            );
+        }
+        Ok(None) => {}
+        Err(error) => {
+            report.record::<()>("heartbeat key", Err(error));
+        }
+    }
+    match cfg.rollup_signing_key_files() {
+        Ok(files) => {
+            for (rollup_id, file) in files {
+                report.record(
+                    format!("rollup signing key ({rollup_id})"),
+                    read_rollup_signing_key(&file),
+                );
+            }
+        }
+        Err(error) => {
+            report.record::<()>("rollup signing keys", Err(error));
+        }
+    }
+    if !cfg.attestation_path.is_empty() {
+        let result = if cfg.attestation_key_file.is_empty() {
+            Err(eyre::eyre!(
+                "an attestation key file must be set if attestations are enabled"
+            ))
+        } else {
+            read_key(&cfg.attestation_key_file)
+        };
+        report.record("attestation key", result);
+    }
+}
+
+fn read_key(path: impl AsRef<std::path::Path>) -> eyre::Result<((), String)> {
+    let path = path.as_ref();
+    let key = Validator::from_path(path)
+        .wrap_err_with(|| format!("failed reading key from file at `{}`", path.display()))?;
+    Ok((
+        (),
+        format!(
+            "address {}, verification key {}",
+            key.address,
+            hex::encode(key.verification_key.as_bytes())
+        ),
+    ))
+}
+
+fn read_rollup_signing_key(file: &RollupSigningKeyFile) -> eyre::Result<((), String)> {
+    let key = RollupSigningKey::from_file(file)
+        .wrap_err_with(|| format!("failed reading key from file at `{}`", file.path.display()))?;
+    let verification_key = key.verification_key();
+    Ok((
+        (),
+        format!(
+            "{} verification key {}",
+            verification_key.scheme(),
+            hex::encode(verification_key.as_bytes())
+        ),
+    ))
+}
+
+/// Checks that the CometBFT and gRPC endpoints of a sequencer network can be reached, and
+/// returns the chain ID of the network if CometBFT could be reached.
+async fn check_sequencer_network(
+    report: &mut Report,
+    label: &str,
+    expected_chain_id: Option<&str>,
+    cometbft_endpoint: &str,
+    grpc_endpoint: &str,
+) -> Option<String> {
+    let status = report.record(
+        format!("sequencer cometbft ({label})"),
+        with_timeout(fetch_cometbft_status(cometbft_endpoint, expected_chain_id))
+            .await
+            .map(|(chain_id, height)| {
+                let detail = format!("chain `{chain_id}` at height {height}");
+                ((chain_id, height), detail)
+            }),
+    );
+    let grpc_result = match &status {
+        Some((_, height)) => with_timeout(fetch_sequencer_block(grpc_endpoint, *height))
+            .await
+            .map(|()| ((), format!("served the block at height {height}"))),
+        None => Err(eyre::eyre!(
+            "not checked because the CometBFT endpoint of the network could not be reached"
+        )),
+    };
+    report.record(format!("sequencer grpc ({label})"), grpc_result);
+    status.map(|(chain_id, _)| chain_id)
+}
+
+async fn fetch_cometbft_status(
+    endpoint: &str,
+    expected_chain_id: Option<&str>,
+) -> eyre::Result<(String, u64)> {
+    let client = SequencerClient::new(endpoint)
+        .wrap_err_with(|| format!("failed constructing cometbft http client for `{endpoint}`"))?;
+    let status = client
+        .status()
+        .await
+        .wrap_err_with(|| format!("failed fetching the status of `{endpoint}`"))?;
+    let chain_id = status.node_info.network.to_string();
+    if let Some(expected_chain_id) = expected_chain_id {
+        ensure!(
+            chain_id == expected_chain_id,
+            "`{endpoint}` serves chain `{chain_id}` instead of `{expected_chain_id}`"
+        );
+    }
+    Ok((chain_id, status.sync_info.latest_block_height.value()))
+}
+
+async fn fetch_sequencer_block(endpoint: &str, height: u64) -> eyre::Result<()> {
+    let uri: Uri = endpoint
+        .parse()
+        .wrap_err("failed parsing provided sequencer grpc endpoint as Uri")?;
+    let channel = Endpoint::from(uri)
+        .connect()
+        .await
+        .wrap_err_with(|| format!("failed connecting to `{endpoint}`"))?;
+    SequencerServiceClient::new(channel)
+        .get_sequencer_block(GetSequencerBlockRequest {
+            height,
+        })
+        .await
+        .wrap_err_with(|| {
+            format!("failed fetching the block at height {height} from `{endpoint}`")
+        })?;
+    Ok(())
+}
+
+/// Describes the Celestia namespaces the blocks of each sequencer network are written under.
+fn describe_sequencer_namespaces(
+    cfg: &Config,
+    primary_chain_id: Option<&str>,
+    additional_networks: &[SequencerNetworkConfig],
+) -> eyre::Result<String> {
+    let namespaces = cfg.sequencer_namespaces(additional_networks)?;
+    let Some(primary_chain_id) = primary_chain_id else {
+        bail!("the namespace of the primary network depends on its chain ID, which is unknown");
+    };
+    let mut descriptions = vec![format!(
+        "`{primary_chain_id}` under {}",
+        HexNamespace(namespaces.get(primary_chain_id))
+    )];
+    for network in additional_networks {
+        descriptions.push(format!(
+            "`{}` under {}",
+            network.chain_id,
+            HexNamespace(namespaces.get(&network.chain_id))
+        ));
+    }
+    Ok(descriptions.join(", "))
+}
+
+/// Checks that the Celestia account can pay for a typical submission.
+async fn check_funds(
+    connection: &CelestiaConnection,
+    submission: TypicalSubmission,
+) -> eyre::Result<String> {
+    let TypicalSubmission {
+        blob_count,
+        blob_size,
+    } = submission;
+    let blob_sizes = vec![blob_size; usize::try_from(blob_count).unwrap_or(usize::MAX)];
+    let funds = with_timeout(connection.check_funds(&blob_sizes)).await?;
+    let submission = format!(
+        "a submission of {blob_count} blobs of {blob_size} bytes (gas limit {} at a minimum gas \
+         price of {} utia)",
+        funds.gas_limit, funds.min_gas_price,
+    );
+    ensure!(
+        funds.are_sufficient(),
+        "the balance of {} utia of account {} does not cover the fee of {} utia for {submission}",
+        funds.balance,
+        funds.address,
+        funds.fee,
+    );
+    Ok(format!(
+        "the balance of {} utia of account {} (number {}) covers the fee of {} utia for \
+         {submission}",
+        funds.balance, funds.address, funds.account_number, funds.fee,
+    ))
+}
+
+async fn with_timeout<T>(future: impl Future<Output = eyre::Result<T>>) -> eyre::Result<T> {
+    tokio::time::timeout(REQUEST_TIMEOUT, future)
+        .await
+        .wrap_err_with(|| format!("no response within {} seconds", REQUEST_TIMEOUT.as_secs()))?
+}
+
+/// The arguments of the `check` subcommand of the relayer binary.
+#[derive(Debug, Parser)]
+#[command(
+    name = "check",
+    about = "Checks the relayer configuration before the relayer is started"
+)]
+pub struct CheckArgs {
+    /// Writes the report as JSON to this path in addition to printing it.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// The number of blobs of the typical submission the Celestia account must be able to pay
+    /// for. Defaults to the configured minimum number of blobs per submission.
+    #[arg(long)]
+    blob_count: Option<u32>,
+    /// The size in bytes of each blob of the typical submission.
+    #[arg(long, default_value_t = DEFAULT_BLOB_SIZE)]
+    blob_size: u32,
+}
+
+/// Runs the `check` subcommand with `args`, the first of which must be the subcommand's name.
+///
+/// Prints the [`Report`] to stdout, and fails if any of its checks failed.
+pub async fn run_cli<I, T>(args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let CheckArgs {
+        output,
+        blob_count,
+        blob_size,
+    } = CheckArgs::parse_from(args);
+    let cfg = match config::get::<Config>().wrap_err("failed reading relayer configuration") {
+        Ok(cfg) => cfg,
+        Err(error) => {
+            eprintln!("check failed: {error:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let submission = TypicalSubmission {
+        blob_count: blob_count.unwrap_or(cfg.batching_min_blobs),
+        blob_size,
+    };
+    let report = run(&cfg, submission).await;
+    println!("{report}");
+
+    if let Some(output) = output {
+        let written = serde_json::to_vec_pretty(&report)
+            .wrap_err("failed encoding report as JSON")
+            .and_then(|contents| {
+                fs::write(&output, contents)
+                    .wrap_err_with(|| format!("failed writing report to `{}`", output.display()))
+            });
+        if let Err(error) = written {
+            eprintln!("check failed: {error:?}");
+            return ExitCode::FAILURE;
+        }
+    }
+    if report.passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_fails_if_any_check_fails() {
+        let mut report = Report::new();
+        assert_eq!(Some(1), report.record("first", Ok((1, "fine".to_string()))));
+        assert!(report.passed);
+
+        let failed: Option<u32> = report.record(
+            "second",
+            Err(eyre::eyre!("broken").wrap_err("failed second")),
+        );
+        assert_eq!(None, failed);
+        report.record("third", Ok(((), "fine".to_string())));
+        assert!(!report.passed);
+        assert_eq!(
+            CheckOutcome {
+                name: "second".to_string(),
+                passed: false,
+                detail: "failed second: broken".to_string(),
+            },
+            report.checks[1],
+        );
+        assert_eq!(
+            "PASS  first: fine\nFAIL  second: failed second: broken\nPASS  third: fine\n1 of 3 \
+             checks failed",
+            report.to_string(),
+        );
+    }
+
+    #[test]
+    fn report_roundtrips_through_json() {
+        let mut report = Report::new();
+        report.record("first", Ok(((), "fine".to_string())));
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(report, serde_json::from_str(&json).unwrap());
+    }
+
+    #[tokio::test]
+    async fn unreachable_sequencer_network_fails_both_checks() {
+        let mut report = Report::new();
+        let chain_id = check_sequencer_network(
+            &mut report,
+            "primary",
+            None,
+            "http://127.0.0.1:1",
+            "http://127.0.0.1:1",
+        )
+        .await;
+        assert_eq!(None, chain_id);
+        assert_eq!(2, report.failed_count());
+    }
+}
@@ -0,0 +1,114 @@
+//! A source of time for the relayer's time-based behavior.
+//!
+//! Confirmation timeouts and polling of Celestia submissions, alert rate limiting, and the
+//! latencies recorded for sequencer blocks all read the current time and wait through a
+//! [`Clock`] instead of calling into `std::time` or `tokio::time` directly. The relayer runs on
+//! the [`TokioClock`]; tests substitute a [`MockClock`] whose time only moves when they advance
+//! it, so that timeouts can be exercised without waiting for them.
+//!
+//! Retries with backoff are driven by `tryhard`, which sleeps on tokio's timer itself. Tests
+//! exercising them pause tokio's clock with `tokio::time::pause` instead; the [`TokioClock`]
+//! follows the paused clock, so both sources of time stay in step.
+
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use futures::future::{
+    BoxFuture,
+    FutureExt as _,
+};
+
+/// Reads the current time and waits for durations to pass.
+pub(crate) trait Clock: Debug + Send + Sync + 'static {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+
+    /// Returns a future that resolves once `duration` has passed.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Returns the clock the relayer runs on.
+pub(crate) fn system() -> Arc<dyn Clock> {
+    Arc::new(TokioClock)
+}
+
+/// The clock of the tokio runtime.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+/// A clock whose time only moves when it is advanced.
+///
+/// Sleeping on it advances its time by the slept duration and resolves immediately.
+#[cfg(test)]
+#[derive(Clone, Debug)]
+pub(crate) struct MockClock {
+    start: Instant,
+    elapsed: Arc<std::sync::Mutex<Duration>>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    pub(crate) fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::new(std::sync::Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Moves the time of this clock and all its clones forward by `duration`.
+    pub(crate) fn advance(&self, duration: Duration) {
+        let mut elapsed = self.elapsed.lock().unwrap();
+        *elapsed = elapsed.saturating_add(duration);
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.advance(duration);
+        futures::future::ready(()).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        Clock as _,
+        MockClock,
+    };
+
+    #[tokio::test]
+    async fn mock_clock_only_moves_when_advanced_or_slept_on() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        assert_eq!(start, clock.now());
+
+        clock.clone().advance(Duration::from_secs(5));
+        assert_eq!(Duration::from_secs(5), clock.now() - start);
+
+        clock.sleep(Duration::from_secs(60)).await;
+        assert_eq!(Duration::from_secs(65), clock.now() - start);
+    }
+}
@@ -0,0 +1,375 @@
+//! Accounting of the data posted to Celestia and the fees paid for it.
+//!
+//! If a cost ledger is configured, the relayer appends a [`CostRecord`] to it for every
+//! confirmed submission, one JSON object per line. Every record lists the bytes posted under
+//! each namespace of the submission, together with the owner of the namespace: the rollup, the
+//! sequencer network, or the kind of auxiliary data written under it.
+//!
+//! The ledger is rolled up into a [`CostReport`] by the `/costs` endpoint of the relayer's API
+//! and by the `costs` subcommand of the relayer binary. The fee of a submission is attributed to
+//! its owners in proportion to the bytes posted for them, which allows operators of a shared
+//! sequencer to charge rollups for the data availability they used.
+
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    fs::{
+        File,
+        OpenOptions,
+    },
+    io::{
+        BufRead as _,
+        BufReader,
+        ErrorKind,
+        Write as _,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::ExitCode,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use astria_eyre::eyre::{
+    self,
+    eyre,
+    WrapErr as _,
+};
+use clap::Parser;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The data and fees of a single confirmed submission.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CostRecord {
+    /// The time the submission was confirmed, in seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The Celestia height the submission was included at.
+    pub celestia_height: u64,
+    /// The fee paid for the submission in utia.
+    pub fee_utia: u64,
+    /// The bytes posted under each namespace of the submission.
+    pub namespaces: Vec<NamespaceBytes>,
+}
+
+/// The bytes posted under a single namespace of a submission.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NamespaceBytes {
+    /// The hex encoded Celestia namespace.
+    pub namespace: String,
+    /// Whom the data under the namespace was posted for, e.g. `rollup <rollup ID>`.
+    pub owner: String,
+    /// The number of bytes of blob data posted under the namespace.
+    pub bytes: u64,
+}
+
+/// The append-only file the relayer records its [`CostRecord`]s in.
+#[derive(Clone, Debug)]
+pub struct CostLedger {
+    path: PathBuf,
+}
+
+impl CostLedger {
+    /// Returns the ledger stored in the file at `path`.
+    ///
+    /// The file is created when the first record is appended.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+        }
+    }
+
+    /// Returns the path of the file the ledger is stored in.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `record` to the ledger.
+    ///
+    /// This is a blocking operation and should not be called from an async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger file could not be opened or written to.
+    pub fn append(&self, record: &CostRecord) -> eyre::Result<()> {
+        let mut line = serde_json::to_vec(record)
+            .wrap_err("failed encoding cost record as JSON")?;
+        line.push(b'\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .wrap_err("failed opening cost ledger for appending")?;
+        file.write_all(&line)
+            .wrap_err("failed writing cost record to ledger")?;
+        file.sync_data().wrap_err("failed syncing cost ledger to disk")
+    }
+
+    /// Rolls up all records of submissions confirmed at or after `since`.
+    ///
+    /// A ledger file that does not exist yet contains no records. This is a blocking operation
+    /// and should not be called from an async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger file could not be read or contains a malformed record.
+    pub fn report(&self, since: SystemTime) -> eyre::Result<CostReport> {
+        let since = unix_seconds(since);
+        let mut report = CostReport {
+            since,
+            ..CostReport::default()
+        };
+        for record in self.records()? {
+            if record.timestamp >= since {
+                report.add(&record);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Reads all records of the ledger, in the order they were appended.
+    ///
+    /// A ledger file that does not exist yet contains no records. This is a blocking operation
+    /// and should not be called from an async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ledger file could not be read or contains a malformed record.
+    pub fn records(&self) -> eyre::Result<Vec<CostRecord>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error).wrap_err("failed opening cost ledger for reading"),
+        };
+        let mut records = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.wrap_err("failed reading line from cost ledger")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: CostRecord = serde_json::from_str(&line).wrap_err_with(|| {
+                format!("failed decoding line {} of cost ledger as a record", index + 1)
+            })?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+/// The data posted and fees paid over a span of time, rolled up by owner.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CostReport {
+    /// The start of the span covered by the report, in seconds since the unix epoch.
+    pub since: u64,
+    /// The number of confirmed submissions.
+    pub submissions: u64,
+    /// The number of bytes posted over all submissions.
+    pub bytes: u64,
+    /// The fees paid over all submissions in utia.
+    pub fee_utia: u64,
+    /// The data posted and fees attributed per owner.
+    pub owners: BTreeMap<String, OwnerCosts>,
+}
+
+/// The data posted for and the fees attributed to a single owner.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OwnerCosts {
+    /// The number of submissions that contained data of the owner.
+    pub submissions: u64,
+    /// The number of bytes posted for the owner.
+    pub bytes: u64,
+    /// The share of the fees attributed to the owner in utia.
+    ///
+    /// Rounded down per submission, so the shares of all owners can add up to slightly less
+    /// than the total fees.
+    pub fee_utia: u64,
+}
+
+impl CostReport {
+    fn add(&mut self, record: &CostRecord) {
+        let total_bytes: u64 = record
+            .namespaces
+            .iter()
+            .map(|namespace| namespace.bytes)
+            .sum();
+        self.submissions = self.submissions.saturating_add(1);
+        self.bytes = self.bytes.saturating_add(total_bytes);
+        self.fee_utia = self.fee_utia.saturating_add(record.fee_utia);
+
+        let mut owners = BTreeSet::new();
+        for namespace in &record.namespaces {
+            let costs = self.owners.entry(namespace.owner.clone()).or_default();
+            if owners.insert(&namespace.owner) {
+                costs.submissions = costs.submissions.saturating_add(1);
+            }
+            costs.bytes = costs.bytes.saturating_add(namespace.bytes);
+            costs.fee_utia = costs
+                .fee_utia
+                .saturating_add(fee_share(record.fee_utia, namespace.bytes, total_bytes));
+        }
+    }
+}
+
+/// Returns the share of `fee` attributed to `bytes` out of `total_bytes`, rounded down.
+pub(crate) fn fee_share(fee: u64, bytes: u64, total_bytes: u64) -> u64 {
+    if total_bytes == 0 {
+        return 0;
+    }
+    let share = u128::from(fee) * u128::from(bytes) / u128::from(total_bytes);
+    u64::try_from(share).expect("the share of a fee is never greater than the fee")
+}
+
+/// Returns `time` in seconds since the unix epoch, or 0 if it lies before it.
+pub(crate) fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Parses the start of a report from either a duration before `now`, like `7d` or `12h`, or an
+/// RFC 3339 timestamp, like `2024-05-01T00:00:00Z`.
+///
+/// # Errors
+///
+/// Returns an error if `since` is neither a duration nor a timestamp.
+pub fn parse_since(since: &str, now: SystemTime) -> eyre::Result<SystemTime> {
+    if let Ok(duration) = humantime::parse_duration(since) {
+        return Ok(now.checked_sub(duration).unwrap_or(UNIX_EPOCH));
+    }
+    humantime::parse_rfc3339_weak(since).map_err(|_| {
+        eyre!(
+            "`{since}` is neither a duration like `7d` nor a timestamp like \
+             `2024-05-01T00:00:00Z`"
+        )
+    })
+}
+
+/// The arguments of the `costs` subcommand of the relayer binary.
+#[derive(Debug, Parser)]
+#[command(name = "costs", about = "Reports the data posted to Celestia and the fees paid for it")]
+pub struct CostsArgs {
+    /// The path of the cost ledger written by the relayer.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_COST_LEDGER_PATH")]
+    ledger: PathBuf,
+    /// The start of the report: a duration before now like `7d`, or an RFC 3339 timestamp.
+    #[arg(long, default_value = "30d")]
+    since: String,
+}
+
+/// Runs the `costs` subcommand with `args`, the first of which must be the subcommand's name.
+///
+/// Prints the [`CostReport`] as JSON to stdout.
+pub fn run_cli<I, T>(args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let CostsArgs {
+        ledger,
+        since,
+    } = CostsArgs::parse_from(args);
+    let report = parse_since(&since, SystemTime::now())
+        .and_then(|since| CostLedger::new(ledger).report(since));
+    match report {
+        Ok(report) => {
+            println!("{}", telemetry::display::json(&report));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("failed reporting costs: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn record(timestamp: u64, fee_utia: u64, namespaces: &[(&str, u64)]) -> CostRecord {
+        CostRecord {
+            timestamp,
+            celestia_height: timestamp,
+            fee_utia,
+            namespaces: namespaces
+                .iter()
+                .map(|(owner, bytes)| NamespaceBytes {
+                    namespace: format!("{owner} namespace"),
+                    owner: (*owner).to_string(),
+                    bytes: *bytes,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn report_attributes_fees_in_proportion_to_bytes_since_the_given_time() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let ledger = CostLedger::new(file.path());
+        ledger
+            .append(&record(100, 1_000, &[("sequencer", 100), ("rollup a", 900)]))
+            .unwrap();
+        ledger
+            .append(&record(200, 300, &[("rollup a", 100), ("rollup b", 200)]))
+            .unwrap();
+        ledger
+            .append(&record(300, 600, &[("rollup b", 300), ("rollup b", 300)]))
+            .unwrap();
+
+        let report = ledger
+            .report(UNIX_EPOCH + Duration::from_secs(200))
+            .unwrap();
+        assert_eq!(200, report.since);
+        assert_eq!(2, report.submissions);
+        assert_eq!(900, report.bytes);
+        assert_eq!(900, report.fee_utia);
+        assert_eq!(
+            OwnerCosts {
+                submissions: 1,
+                bytes: 100,
+                fee_utia: 100,
+            },
+            report.owners["rollup a"],
+        );
+        assert_eq!(
+            OwnerCosts {
+                submissions: 2,
+                bytes: 800,
+                fee_utia: 800,
+            },
+            report.owners["rollup b"],
+        );
+        assert!(!report.owners.contains_key("sequencer"));
+    }
+
+    #[test]
+    fn missing_ledger_reports_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = CostLedger::new(dir.path().join("costs.jsonl"));
+        assert_eq!(CostReport::default(), ledger.report(UNIX_EPOCH).unwrap());
+    }
+
+    #[test]
+    fn since_is_parsed_from_durations_and_timestamps() {
+        let now = UNIX_EPOCH + Duration::from_secs(10 * 86_400);
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(3 * 86_400),
+            parse_since("7d", now).unwrap(),
+        );
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(86_400),
+            parse_since("1970-01-02T00:00:00Z", now).unwrap(),
+        );
+        assert!(parse_since("last week", now).is_err());
+    }
+}
@@ -0,0 +1,445 @@
+//! Exports of the relayer's submission history for offline analysis.
+//!
+//! The `export` subcommand of the relayer binary flattens the [`CostRecord`]s of the cost
+//! ledger into one row per namespace of every confirmed submission, joined with the sequencer
+//! blocks the height index recorded at the submission's Celestia height. The rows are written
+//! as CSV, or as Parquet if the relayer was built with the `parquet` feature.
+//!
+//! The cost ledger and the height index are only ever appended to, so an export can be taken
+//! while the relayer is running, for example periodically by a cron job.
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{
+        BufWriter,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::ExitCode,
+    time::SystemTime,
+};
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use clap::{
+    Parser,
+    ValueEnum,
+};
+
+use crate::{
+    costs::{
+        self,
+        CostLedger,
+        CostRecord,
+    },
+    height_index::HeightIndex,
+};
+
+/// The columns of an export, in the order they are written.
+const COLUMNS: [&str; 9] = [
+    "timestamp",
+    "celestia_height",
+    "fee_utia",
+    "submission_bytes",
+    "namespace",
+    "owner",
+    "bytes",
+    "owner_fee_utia",
+    "sequencer_blocks",
+];
+
+/// The data posted under a single namespace of a confirmed submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportRow {
+    /// The time the submission was confirmed, in seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The Celestia height the submission was included at.
+    pub celestia_height: u64,
+    /// The fee paid for the whole submission in utia.
+    pub fee_utia: u64,
+    /// The number of bytes of blob data posted by the whole submission.
+    pub submission_bytes: u64,
+    /// The hex encoded Celestia namespace.
+    pub namespace: String,
+    /// Whom the data under the namespace was posted for.
+    pub owner: String,
+    /// The number of bytes of blob data posted under the namespace.
+    pub bytes: u64,
+    /// The share of the submission's fee attributed to the namespace in utia, rounded down.
+    pub owner_fee_utia: u64,
+    /// The sequencer blocks written at the Celestia height, as space separated
+    /// `<chain ID>:<height>` pairs. Empty if no height index was given.
+    pub sequencer_blocks: String,
+}
+
+/// A single field of an [`ExportRow`].
+#[derive(Clone, Copy)]
+enum Value<'a> {
+    Integer(u64),
+    Text(&'a str),
+}
+
+impl ExportRow {
+    fn values(&self) -> [Value<'_>; COLUMNS.len()] {
+        [
+            Value::Integer(self.timestamp),
+            Value::Integer(self.celestia_height),
+            Value::Integer(self.fee_utia),
+            Value::Integer(self.submission_bytes),
+            Value::Text(&self.namespace),
+            Value::Text(&self.owner),
+            Value::Integer(self.bytes),
+            Value::Integer(self.owner_fee_utia),
+            Value::Text(&self.sequencer_blocks),
+        ]
+    }
+}
+
+/// Flattens `records` confirmed at or after `since` into one row per namespace, in the order
+/// the records were appended.
+///
+/// If `index` is given, every row lists the sequencer blocks written at its Celestia height.
+#[must_use]
+pub fn rows(records: &[CostRecord], index: Option<&HeightIndex>, since: u64) -> Vec<ExportRow> {
+    let mut rows = Vec::new();
+    for record in records.iter().filter(|record| record.timestamp >= since) {
+        let submission_bytes: u64 = record
+            .namespaces
+            .iter()
+            .map(|namespace| namespace.bytes)
+            .sum();
+        let mut sequencer_blocks = String::new();
+        for mapping in index
+            .map(|index| index.by_celestia_height(record.celestia_height))
+            .unwrap_or_default()
+        {
+            if !sequencer_blocks.is_empty() {
+                sequencer_blocks.push(' ');
+            }
+            write!(
+                sequencer_blocks,
+                "{}:{}",
+                mapping.sequencer_chain_id, mapping.sequencer_height
+            )
+            .expect("writing to a string never fails");
+        }
+        rows.extend(record.namespaces.iter().map(|namespace| ExportRow {
+            timestamp: record.timestamp,
+            celestia_height: record.celestia_height,
+            fee_utia: record.fee_utia,
+            submission_bytes,
+            namespace: namespace.namespace.clone(),
+            owner: namespace.owner.clone(),
+            bytes: namespace.bytes,
+            owner_fee_utia: costs::fee_share(record.fee_utia, namespace.bytes, submission_bytes),
+            sequencer_blocks: sequencer_blocks.clone(),
+        }));
+    }
+    rows
+}
+
+/// The file formats an export can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Comma separated values with a header line, as described in RFC 4180.
+    Csv,
+    /// Apache Parquet. Requires the relayer to be built with the `parquet` feature.
+    Parquet,
+}
+
+/// Writes `rows` to `writer` in `format`.
+///
+/// # Errors
+///
+/// Returns an error if writing fails, or if `format` is Parquet but the relayer was built
+/// without the `parquet` feature.
+pub fn write<W: Write + Send>(rows: &[ExportRow], format: Format, writer: W) -> eyre::Result<()> {
+    match format {
+        Format::Csv => write_csv(rows, writer),
+        #[cfg(feature = "parquet")]
+        Format::Parquet => write_parquet(rows, writer),
+        #[cfg(not(feature = "parquet"))]
+        Format::Parquet => Err(eyre::eyre!(
+            "exporting Parquet requires the relayer to be built with the `parquet` feature"
+        )),
+    }
+}
+
+fn write_csv<W: Write>(rows: &[ExportRow], writer: W) -> eyre::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    writeln!(writer, "{}", COLUMNS.join(",")).wrap_err("failed writing CSV header")?;
+    for row in rows {
+        let mut line = String::new();
+        for (index, value) in row.values().iter().enumerate() {
+            if index > 0 {
+                line.push(',');
+            }
+            match value {
+                Value::Integer(integer) => {
+                    write!(line, "{integer}").expect("writing to a string never fails");
+                }
+                Value::Text(text) => push_csv_field(&mut line, text),
+            }
+        }
+        writeln!(writer, "{line}").wrap_err("failed writing CSV row")?;
+    }
+    writer.flush().wrap_err("failed flushing CSV rows")
+}
+
+/// Appends `field` to `line`, quoted if it contains a separator, quote or line break.
+fn push_csv_field(line: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        line.push('"');
+        line.push_str(&field.replace('"', "\"\""));
+        line.push('"');
+    } else {
+        line.push_str(field);
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet<W: Write + Send>(rows: &[ExportRow], writer: W) -> eyre::Result<()> {
+    use std::sync::Arc;
+
+    use parquet::{
+        column::writer::ColumnWriter,
+        data_type::ByteArray,
+        file::{
+            properties::WriterProperties,
+            writer::SerializedFileWriter,
+        },
+        schema::parser::parse_message_type,
+    };
+
+    let schema = parse_message_type(
+        "message submission {
+            required int64 timestamp (INTEGER(64, false));
+            required int64 celestia_height (INTEGER(64, false));
+            required int64 fee_utia (INTEGER(64, false));
+            required int64 submission_bytes (INTEGER(64, false));
+            required binary namespace (UTF8);
+            required binary owner (UTF8);
+            required int64 bytes (INTEGER(64, false));
+            required int64 owner_fee_utia (INTEGER(64, false));
+            required binary sequencer_blocks (UTF8);
+        }",
+    )
+    .wrap_err("failed parsing Parquet schema")?;
+    let mut writer = SerializedFileWriter::new(
+        writer,
+        Arc::new(schema),
+        Arc::new(WriterProperties::builder().build()),
+    )
+    .wrap_err("failed creating Parquet writer")?;
+    let mut row_group = writer
+        .next_row_group()
+        .wrap_err("failed starting Parquet row group")?;
+    let mut index = 0;
+    while let Some(mut column) = row_group
+        .next_column()
+        .wrap_err("failed starting Parquet column")?
+    {
+        let values = rows.iter().map(|row| row.values()[index]);
+        match column.untyped() {
+            ColumnWriter::Int64ColumnWriter(writer) => {
+                let values: Vec<i64> = values
+                    .map(|value| match value {
+                        // Unsigned integers are stored as the bits of signed ones, as annotated
+                        // in the schema.
+                        Value::Integer(integer) => i64::from_ne_bytes(integer.to_ne_bytes()),
+                        Value::Text(_) => unreachable!("column {index} holds integers"),
+                    })
+                    .collect();
+                writer.write_batch(&values, None, None)
+            }
+            ColumnWriter::ByteArrayColumnWriter(writer) => {
+                let values: Vec<ByteArray> = values
+                    .map(|value| match value {
+                        Value::Text(text) => ByteArray::from(text),
+                        Value::Integer(_) => unreachable!("column {index} holds text"),
+                    })
+                    .collect();
+                writer.write_batch(&values, None, None)
+            }
+            _ => unreachable!("the schema only has int64 and binary columns"),
+        }
+        .wrap_err_with(|| format!("failed writing Parquet column `{}`", COLUMNS[index]))?;
+        column
+            .close()
+            .wrap_err_with(|| format!("failed closing Parquet column `{}`", COLUMNS[index]))?;
+        index += 1;
+    }
+    row_group
+        .close()
+        .wrap_err("failed closing Parquet row group")?;
+    writer.close().wrap_err("failed closing Parquet file")?;
+    Ok(())
+}
+
+fn export(
+    ledger: PathBuf,
+    index: Option<PathBuf>,
+    since: Option<&str>,
+    format: Format,
+    output: &Path,
+) -> eyre::Result<()> {
+    let since = match since {
+        Some(since) => costs::unix_seconds(costs::parse_since(since, SystemTime::now())?),
+        None => 0,
+    };
+    let records = CostLedger::new(ledger).records()?;
+    let index = index
+        .map(HeightIndex::open)
+        .transpose()
+        .wrap_err("failed opening height index")?;
+    let rows = rows(&records, index.as_ref(), since);
+    let file =
+        File::create(output).wrap_err_with(|| format!("failed creating `{}`", output.display()))?;
+    write(&rows, format, file)
+        .wrap_err_with(|| format!("failed writing export to `{}`", output.display()))
+}
+
+/// The arguments of the `export` subcommand of the relayer binary.
+#[derive(Debug, Parser)]
+#[command(
+    name = "export",
+    about = "Exports the submission history of the relayer as CSV or Parquet"
+)]
+pub struct ExportArgs {
+    /// The path of the cost ledger written by the relayer.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_COST_LEDGER_PATH")]
+    ledger: PathBuf,
+    /// The path of the height index written by the relayer. If given, every row lists the
+    /// sequencer blocks written at its Celestia height.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_HEIGHT_INDEX_PATH")]
+    index: Option<PathBuf>,
+    /// The start of the export: a duration before now like `7d`, or an RFC 3339 timestamp.
+    /// Exports the whole history if not given.
+    #[arg(long)]
+    since: Option<String>,
+    /// The format to write the export in.
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+    /// The file to write the export to.
+    #[arg(long)]
+    output: PathBuf,
+}
+
+/// Runs the `export` subcommand with `args`, the first of which must be the subcommand's name.
+pub fn run_cli<I, T>(args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let ExportArgs {
+        ledger,
+        index,
+        since,
+        format,
+        output,
+    } = ExportArgs::parse_from(args);
+    match export(ledger, index, since.as_deref(), format, &output) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("export failed: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        costs::NamespaceBytes,
+        height_index::HeightMapping,
+    };
+
+    fn record(timestamp: u64, fee_utia: u64, namespaces: &[(&str, u64)]) -> CostRecord {
+        CostRecord {
+            timestamp,
+            celestia_height: timestamp + 1,
+            fee_utia,
+            namespaces: namespaces
+                .iter()
+                .map(|(owner, bytes)| NamespaceBytes {
+                    namespace: format!("{owner} namespace"),
+                    owner: (*owner).to_string(),
+                    bytes: *bytes,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn rows_are_flattened_per_namespace_and_joined_with_the_height_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = HeightIndex::open(dir.path().join("heights.jsonl")).unwrap();
+        index
+            .record(&[
+                HeightMapping {
+                    sequencer_chain_id: "seq".to_string(),
+                    sequencer_height: 7,
+                    celestia_height: 201,
+                },
+                HeightMapping {
+                    sequencer_chain_id: "seq".to_string(),
+                    sequencer_height: 8,
+                    celestia_height: 201,
+                },
+            ])
+            .unwrap();
+        let records = [
+            record(100, 1_000, &[("sequencer", 100)]),
+            record(200, 300, &[("sequencer", 100), ("rollup a", 200)]),
+        ];
+
+        let rows = rows(&records, Some(&index), 200);
+        assert_eq!(
+            vec![
+                ExportRow {
+                    timestamp: 200,
+                    celestia_height: 201,
+                    fee_utia: 300,
+                    submission_bytes: 300,
+                    namespace: "sequencer namespace".to_string(),
+                    owner: "sequencer".to_string(),
+                    bytes: 100,
+                    owner_fee_utia: 100,
+                    sequencer_blocks: "seq:7 seq:8".to_string(),
+                },
+                ExportRow {
+                    timestamp: 200,
+                    celestia_height: 201,
+                    fee_utia: 300,
+                    submission_bytes: 300,
+                    namespace: "rollup a namespace".to_string(),
+                    owner: "rollup a".to_string(),
+                    bytes: 200,
+                    owner_fee_utia: 200,
+                    sequencer_blocks: "seq:7 seq:8".to_string(),
+                },
+            ],
+            rows,
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_containing_separators() {
+        let rows = rows(&[record(1, 10, &[("rollup \"a\", b", 5)])], None, 0);
+        let mut csv = Vec::new();
+        write(&rows, Format::Csv, &mut csv).unwrap();
+        assert_eq!(
+            "timestamp,celestia_height,fee_utia,submission_bytes,namespace,owner,bytes,\
+             owner_fee_utia,sequencer_blocks\n1,2,10,5,\"rollup \"\"a\"\", b namespace\",\"rollup \
+             \"\"a\"\", b\",5,10,\n",
+            String::from_utf8(csv).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! A persistent index of the Celestia heights at which sequencer blocks were written.
+//!
+//! If a height index is configured, the relayer records a [`HeightMapping`] for every sequencer
+//! block of a confirmed submission, one JSON object per line. The file is read back into memory
+//! on startup, so that the index can be queried in both directions: which Celestia height the
+//! blobs of a sequencer block landed at, and which sequencer blocks were written at a Celestia
+//! height.
+//!
+//! The index is served by the `/heights` endpoints of the relayer's API and by the `heights`
+//! subcommand of the relayer binary.
+
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    fs::{
+        File,
+        OpenOptions,
+    },
+    io::{
+        BufRead as _,
+        BufReader,
+        ErrorKind,
+        Write as _,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::ExitCode,
+    sync::{
+        PoisonError,
+        RwLock,
+    },
+};
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use clap::Parser;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The Celestia height at which the blobs of a sequencer block were written.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HeightMapping {
+    /// The chain ID of the sequencer network of the block.
+    pub sequencer_chain_id: String,
+    /// The height of the sequencer block.
+    pub sequencer_height: u64,
+    /// The Celestia height of the submission containing the block.
+    pub celestia_height: u64,
+}
+
+/// The append-only file the relayer records its [`HeightMapping`]s in, and the index built
+/// from it.
+#[derive(Debug)]
+pub struct HeightIndex {
+    path: PathBuf,
+    inner: RwLock<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The Celestia height of each sequencer block, keyed by sequencer height and chain ID.
+    by_sequencer_height: BTreeMap<(u64, String), u64>,
+    /// The sequencer blocks written at each Celestia height.
+    by_celestia_height: BTreeMap<u64, BTreeSet<(String, u64)>>,
+}
+
+impl Inner {
+    /// Adds `mapping` to the index.
+    ///
+    /// If a sequencer block was written more than once, for example because a submission was
+    /// repeated after a restart, the earliest Celestia height is kept for it, since that is
+    /// where readers find it first.
+    fn insert(&mut self, mapping: &HeightMapping) {
+        let HeightMapping {
+            sequencer_chain_id,
+            sequencer_height,
+            celestia_height,
+        } = mapping;
+        let earliest = self
+            .by_sequencer_height
+            .entry((*sequencer_height, sequencer_chain_id.clone()))
+            .or_insert(*celestia_height);
+        *earliest = (*earliest).min(*celestia_height);
+        self.by_celestia_height
+            .entry(*celestia_height)
+            .or_default()
+            .insert((sequencer_chain_id.clone(), *sequencer_height));
+    }
+}
+
+impl HeightIndex {
+    /// Opens the index stored in the file at `path`, reading all mappings recorded so far.
+    ///
+    /// A file that does not exist yet contains no mappings; it is created when the first
+    /// mapping is recorded. This is a blocking operation and should not be called from an async
+    /// context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or contains a malformed mapping.
+    pub fn open(path: impl Into<PathBuf>) -> eyre::Result<Self> {
+        let path = path.into();
+        let mut inner = Inner::default();
+        match File::open(&path) {
+            Ok(file) => {
+                for (index, line) in BufReader::new(file).lines().enumerate() {
+                    let line = line.wrap_err("failed reading line from height index")?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let mapping: HeightMapping = serde_json::from_str(&line).wrap_err_with(|| {
+                        format!("failed decoding line {} of height index as a mapping", index + 1)
+                    })?;
+                    inner.insert(&mapping);
+                }
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => {}
+            Err(error) => return Err(error).wrap_err("failed opening height index for reading"),
+        }
+        Ok(Self {
+            path,
+            inner: RwLock::new(inner),
+        })
+    }
+
+    /// Returns the path of the file the index is stored in.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `mappings` to the file and adds them to the index.
+    ///
+    /// This is a blocking operation and should not be called from an async context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be opened or written to. The mappings are not
+    /// added to the index in that case.
+    pub fn record(&self, mappings: &[HeightMapping]) -> eyre::Result<()> {
+        let mut lines = Vec::new();
+        for mapping in mappings {
+            serde_json::to_writer(&mut lines, mapping)
+                .wrap_err("failed encoding height mapping as JSON")?;
+            lines.push(b'\n');
+        }
+        // Holding the write lock while appending keeps the file in the same order as the index.
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .wrap_err("failed opening height index for appending")?;
+        file.write_all(&lines)
+            .wrap_err("failed writing height mappings to index")?;
+        file.sync_data()
+            .wrap_err("failed syncing height index to disk")?;
+        for mapping in mappings {
+            inner.insert(mapping);
+        }
+        Ok(())
+    }
+
+    /// Returns the Celestia heights at which the sequencer blocks at `sequencer_height` were
+    /// written, one per sequencer network.
+    #[must_use]
+    pub fn by_sequencer_height(&self, sequencer_height: u64) -> Vec<HeightMapping> {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .by_sequencer_height
+            .range((sequencer_height, String::new())..)
+            .take_while(|((height, _), _)| *height == sequencer_height)
+            .map(|((height, chain_id), celestia_height)| HeightMapping {
+                sequencer_chain_id: chain_id.clone(),
+                sequencer_height: *height,
+                celestia_height: *celestia_height,
+            })
+            .collect()
+    }
+
+    /// Returns all mappings of the index, ordered by Celestia height.
+    ///
+    /// A sequencer block that was written more than once has a mapping for every Celestia height
+    /// it was written at.
+    #[must_use]
+    pub fn mappings(&self) -> Vec<HeightMapping> {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .by_celestia_height
+            .iter()
+            .flat_map(|(celestia_height, blocks)| {
+                blocks
+                    .iter()
+                    .map(|(chain_id, sequencer_height)| HeightMapping {
+                        sequencer_chain_id: chain_id.clone(),
+                        sequencer_height: *sequencer_height,
+                        celestia_height: *celestia_height,
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the highest Celestia height at which a sequencer block was recorded, or `None` if
+    /// the index is empty.
+    #[must_use]
+    pub fn latest_celestia_height(&self) -> Option<u64> {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        inner.by_celestia_height.keys().next_back().copied()
+    }
+
+    /// Returns the sequencer blocks written at `celestia_height`.
+    #[must_use]
+    pub fn by_celestia_height(&self, celestia_height: u64) -> Vec<HeightMapping> {
+        let inner = self.inner.read().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .by_celestia_height
+            .get(&celestia_height)
+            .into_iter()
+            .flatten()
+            .map(|(chain_id, sequencer_height)| HeightMapping {
+                sequencer_chain_id: chain_id.clone(),
+                sequencer_height: *sequencer_height,
+                celestia_height,
+            })
+            .collect()
+    }
+}
+
+/// The arguments of the `heights` subcommand of the relayer binary.
+#[derive(Debug, Parser)]
+#[command(
+    name = "heights",
+    about = "Looks up the Celestia heights at which sequencer blocks were written, or vice versa"
+)]
+pub struct HeightsArgs {
+    /// The path of the height index written by the relayer.
+    #[arg(long, env = "ASTRIA_SEQUENCER_RELAYER_HEIGHT_INDEX_PATH")]
+    index: PathBuf,
+    /// Looks up the Celestia heights of the sequencer blocks at this height.
+    #[arg(long, conflicts_with = "celestia_height", required_unless_present = "celestia_height")]
+    sequencer_height: Option<u64>,
+    /// Looks up the sequencer blocks written at this Celestia height.
+    #[arg(long)]
+    celestia_height: Option<u64>,
+}
+
+/// Runs the `heights` subcommand with `args`, the first of which must be the subcommand's name.
+///
+/// Prints the matching [`HeightMapping`]s as JSON to stdout.
+pub fn run_cli<I, T>(args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let HeightsArgs {
+        index,
+        sequencer_height,
+        celestia_height,
+    } = HeightsArgs::parse_from(args);
+    let index = match HeightIndex::open(index) {
+        Ok(index) => index,
+        Err(error) => {
+            eprintln!("failed opening height index: {error:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mappings = match (sequencer_height, celestia_height) {
+        (Some(sequencer_height), _) => index.by_sequencer_height(sequencer_height),
+        (None, Some(celestia_height)) => index.by_celestia_height(celestia_height),
+        (None, None) => unreachable!("clap requires one of the heights"),
+    };
+    println!("{}", telemetry::display::json(&mappings));
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(chain_id: &str, sequencer_height: u64, celestia_height: u64) -> HeightMapping {
+        HeightMapping {
+            sequencer_chain_id: chain_id.to_string(),
+            sequencer_height,
+            celestia_height,
+        }
+    }
+
+    #[test]
+    fn recorded_mappings_are_found_in_both_directions_after_reopening() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let index = HeightIndex::open(file.path()).unwrap();
+        index
+            .record(&[mapping("a", 5, 100), mapping("b", 5, 100), mapping("a", 6, 100)])
+            .unwrap();
+        index.record(&[mapping("a", 7, 101)]).unwrap();
+
+        let reopened = HeightIndex::open(file.path()).unwrap();
+        assert_eq!(
+            vec![mapping("a", 5, 100), mapping("b", 5, 100)],
+            reopened.by_sequencer_height(5),
+        );
+        assert_eq!(
+            vec![mapping("a", 5, 100), mapping("a", 6, 100), mapping("b", 5, 100)],
+            reopened.by_celestia_height(100),
+        );
+        assert!(reopened.by_sequencer_height(8).is_empty());
+        assert_eq!(Some(101), reopened.latest_celestia_height());
+        assert!(reopened.by_celestia_height(102).is_empty());
+    }
+
+    #[test]
+    fn repeated_blocks_map_to_their_earliest_celestia_height() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = HeightIndex::open(dir.path().join("heights.jsonl")).unwrap();
+        index.record(&[mapping("a", 5, 101)]).unwrap();
+        index.record(&[mapping("a", 5, 100)]).unwrap();
+        index.record(&[mapping("a", 5, 102)]).unwrap();
+        assert_eq!(vec![mapping("a", 5, 100)], index.by_sequencer_height(5));
+        assert_eq!(vec![mapping("a", 5, 102)], index.by_celestia_height(102));
+    }
+}
@@ -1,11 +1,24 @@
+pub(crate) mod alerts;
 pub(crate) mod api;
+pub mod attestations;
+pub mod audit;
 mod build_info;
+pub mod check;
+pub(crate) mod clock;
 pub mod config;
+pub mod costs;
+pub mod export;
+pub mod height_index;
 pub mod metrics_init;
 pub(crate) mod relayer;
+pub mod secret;
 pub mod sequencer_relayer;
+pub mod snapshot;
+pub(crate) mod storage;
+pub(crate) mod supervisor;
 pub(crate) mod utils;
 pub(crate) mod validator;
+pub mod verify;
 
 pub use build_info::BUILD_INFO;
 pub use config::{
@@ -2,7 +2,13 @@ use std::process::ExitCode;
 
 use astria_eyre::eyre::WrapErr as _;
 use astria_sequencer_relayer::{
+    audit,
+    check,
+    costs,
+    export,
+    height_index,
     metrics_init,
+    snapshot,
     Config,
     SequencerRelayer,
     BUILD_INFO,
@@ -21,6 +27,38 @@ use tracing::{
 async fn main() -> ExitCode {
     astria_eyre::install().expect("astria eyre hook must be the first hook installed");
 
+    // `sequencer-relayer audit --celestia-node-url <url> --sequencer-chain-id <chain-id>
+    // [--index <path>]` verifies the sequencer blocks written to Celestia by another relayer
+    // without signing or submitting anything.
+    if std::env::args().nth(1).as_deref() == Some("audit") {
+        return audit::run_cli(std::env::args().skip(1)).await;
+    }
+    // `sequencer-relayer check [--output <path>] [--blob-count <count>] [--blob-size <bytes>]`
+    // checks the configuration of a relayer before it is started.
+    if std::env::args().nth(1).as_deref() == Some("check") {
+        return check::run_cli(std::env::args().skip(1)).await;
+    }
+    // `sequencer-relayer costs [--ledger <path>] [--since <since>]` reports the costs recorded
+    // by a running relayer instead of starting one.
+    if std::env::args().nth(1).as_deref() == Some("costs") {
+        return costs::run_cli(std::env::args().skip(1));
+    }
+    // `sequencer-relayer export --output <path> [--format (csv | parquet)] [--ledger <path>]
+    // [--index <path>] [--since <since>]` exports the submission history recorded by a relayer.
+    if std::env::args().nth(1).as_deref() == Some("export") {
+        return export::run_cli(std::env::args().skip(1));
+    }
+    // `sequencer-relayer heights [--index <path>] (--sequencer-height <height> |
+    // --celestia-height <height>)` looks up the height index recorded by a running relayer.
+    if std::env::args().nth(1).as_deref() == Some("heights") {
+        return height_index::run_cli(std::env::args().skip(1));
+    }
+    // `sequencer-relayer snapshot (export --output <path> | import --input <path> [--force])`
+    // moves the state of a stopped relayer to a new host.
+    if std::env::args().nth(1).as_deref() == Some("snapshot") {
+        return snapshot::run_cli(std::env::args().skip(1));
+    }
+
     eprintln!("{}", telemetry::display::json(&BUILD_INFO),);
 
     let cfg: Config = config::get().expect("failed to read configuration");
@@ -30,7 +68,8 @@ async fn main() -> ExitCode {
         .set_no_otel(cfg.no_otel)
         .set_force_stdout(cfg.force_stdout)
         .set_pretty_print(cfg.pretty_print)
-        .filter_directives(&cfg.log);
+        .filter_directives(&cfg.log)
+        .trace_sampling(&cfg.trace_sampling);
 
     if !cfg.no_metrics {
         telemetry_conf = telemetry_conf
@@ -39,13 +78,16 @@ async fn main() -> ExitCode {
     }
     metrics_init::register();
 
-    if let Err(e) = telemetry_conf
+    let telemetry = match telemetry_conf
         .try_init()
         .wrap_err("failed to setup telemetry")
     {
-        eprintln!("initializing sequencer-relayer failed:\n{e:?}");
-        return ExitCode::FAILURE;
-    }
+        Ok(telemetry) => telemetry,
+        Err(e) => {
+            eprintln!("initializing sequencer-relayer failed:\n{e:?}");
+            return ExitCode::FAILURE;
+        }
+    };
 
     info!(
         config = %telemetry::display::json(&cfg),
@@ -54,8 +96,8 @@ async fn main() -> ExitCode {
 
     let mut sigterm = signal(SignalKind::terminate())
         .expect("setting a SIGTERM listener should always work on Unix");
-    let (sequencer_relayer, shutdown_handle) =
-        SequencerRelayer::new(cfg).expect("could not initialize sequencer relayer");
+    let (sequencer_relayer, shutdown_handle) = SequencerRelayer::new(cfg, Some(telemetry))
+        .expect("could not initialize sequencer relayer");
     let sequencer_relayer_handle = tokio::spawn(sequencer_relayer.run());
 
     let shutdown_token = shutdown_handle.token();
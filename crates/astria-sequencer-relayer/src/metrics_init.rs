@@ -29,6 +29,13 @@ pub fn register() {
         "The number of calls made to submit to celestia which have failed"
     );
 
+    describe_counter!(
+        CELESTIA_RESUBMISSION_COUNT,
+        Unit::Count,
+        "The number of times blobs were resubmitted to Celestia because a previous submission \
+         was not confirmed in time"
+    );
+
     describe_counter!(
         SEQUENCER_BLOCK_FETCH_FAILURE_COUNT,
         Unit::Count,
@@ -41,6 +48,46 @@ pub fn register() {
         "The number of calls made to fetch the current height from sequencer which have failed"
     );
 
+    describe_counter!(
+        SEQUENCER_BLOCKS_BY_PROPOSER,
+        Unit::Count,
+        "The number of sequencer blocks read from sequencer, labeled by the known signer label \
+         of their proposer"
+    );
+
+    describe_counter!(
+        INVALID_SEQUENCER_BLOCK_COUNT,
+        Unit::Count,
+        "The number of sequencer blocks that failed validation and were not submitted to Celestia"
+    );
+
+    describe_counter!(
+        ROLLUP_QUOTA_EXCEEDED_COUNT,
+        Unit::Count,
+        "The number of times the data of a rollup in a sequencer block exceeded its quota and was \
+         rejected or truncated"
+    );
+
+    describe_counter!(
+        QUARANTINED_SEQUENCER_TRANSACTIONS,
+        Unit::Count,
+        "The number of transactions in sequencer blocks fetched from CometBFT that could not be \
+         decoded and whose rollup data was not extracted"
+    );
+
+    describe_counter!(
+        LEADERSHIP_CHANGES,
+        Unit::Count,
+        "The number of times this relayer instance acquired, lost, or released the leader lease, \
+         labeled by the change"
+    );
+
+    describe_gauge!(
+        IS_LEADER,
+        Unit::Count,
+        "Whether this relayer instance holds the leader lease: 1 if it does, 0 while on standby"
+    );
+
     describe_gauge!(
         BLOCKS_PER_CELESTIA_TX,
         Unit::Count,
@@ -54,12 +101,127 @@ pub fn register() {
          Celestia submission"
     );
 
+    describe_gauge!(
+        INGEST_QUEUED_SEQUENCER_BLOCKS,
+        Unit::Count,
+        "The number of sequencer blocks fetched from sequencer and waiting to be picked up for \
+         conversion to Celestia blobs"
+    );
+
+    describe_gauge!(
+        ASSEMBLY_QUEUED_SEQUENCER_BLOCKS,
+        Unit::Count,
+        "The number of sequencer blocks being converted to Celestia blobs or waiting to be \
+         batched for submission"
+    );
+
+    describe_gauge!(
+        IN_FLIGHT_CELESTIA_SUBMISSIONS,
+        Unit::Count,
+        "The number of batches of blobs handed to the submission stage that are waiting to be \
+         submitted or are being submitted to Celestia"
+    );
+
+    describe_gauge!(
+        UNCONFIRMED_CELESTIA_SUBMISSIONS,
+        Unit::Count,
+        "The number of blob transactions broadcast to Celestia that are waiting to be included \
+         in a Celestia block"
+    );
+
+    describe_gauge!(
+        BATCHING_WINDOW,
+        Unit::Seconds,
+        "How long blobs are collected into a batch before it is submitted to Celestia, as chosen \
+         from the observed Celestia gas prices and inclusion latencies"
+    );
+
+    describe_gauge!(
+        BATCHING_MAX_BLOBS,
+        Unit::Count,
+        "The number of blobs after which a batch is submitted to Celestia right away, as chosen \
+         from the observed Celestia gas prices and inclusion latencies"
+    );
+
+    describe_gauge!(
+        SEQUENCER_HEIGHT_LAG,
+        Unit::Count,
+        "The number of sequencer blocks between the latest observed sequencer height and the \
+         latest sequencer height confirmed on Celestia"
+    );
+
     describe_histogram!(
         CELESTIA_SUBMISSION_LATENCY,
         Unit::Seconds,
         "The time it takes to submit a blob to Celestia"
     );
 
+    describe_histogram!(
+        SEQUENCER_BLOCK_ASSEMBLY_LATENCY,
+        Unit::Seconds,
+        "The time from observing a sequencer block until it is converted to Celestia blobs"
+    );
+
+    describe_histogram!(
+        SEQUENCER_BLOCK_QUEUE_LATENCY,
+        Unit::Seconds,
+        "The time from converting a sequencer block to Celestia blobs until the submission \
+         containing it is started"
+    );
+
+    describe_histogram!(
+        SEQUENCER_BLOCK_CONFIRMATION_LATENCY,
+        Unit::Seconds,
+        "The time from starting the submission of a sequencer block until it is confirmed on \
+         Celestia"
+    );
+
+    describe_histogram!(
+        SEQUENCER_BLOCK_END_TO_END_LATENCY,
+        Unit::Seconds,
+        "The time from observing a sequencer block until it is confirmed on Celestia"
+    );
+
+    describe_counter!(
+        SEQUENCER_BLOCKS_MISSED_DEADLINE_COUNT,
+        Unit::Count,
+        "The number of sequencer blocks confirmed on Celestia after their submission deadline"
+    );
+
+    describe_gauge!(
+        SEQUENCER_BLOCKS_OVERDUE,
+        Unit::Count,
+        "The number of sequencer blocks past their submission deadline that are not yet \
+         confirmed on Celestia"
+    );
+
+    describe_counter!(
+        CELESTIA_DEADLINE_ESCALATION_COUNT,
+        Unit::Count,
+        "The number of attempts to submit blobs to Celestia with a raised fee because they \
+         contained sequencer blocks past their submission deadline"
+    );
+
+    describe_counter!(
+        AUDITED_SEQUENCER_BLOBS,
+        Unit::Count,
+        "The number of blobs under the sequencer namespace checked by the auditor, labeled by \
+         whether they were verified or rejected"
+    );
+
+    describe_gauge!(
+        AUDITED_CELESTIA_HEIGHT,
+        Unit::Count,
+        "The latest Celestia height whose blobs were checked by the auditor"
+    );
+
+    describe_counter!(
+        AUDIT_FETCH_FAILURE_COUNT,
+        Unit::Count,
+        "The number of calls made by the auditor to fetch blobs or the latest height from \
+         Celestia which have failed"
+    );
+
     describe_gauge!(
         TOTAL_BLOB_DATA_SIZE_FOR_ASTRIA_BLOCK,
         Unit::Bytes,
@@ -78,40 +240,90 @@ pub fn register() {
 // output, and may need to be updated over time.
 pub const HISTOGRAM_BUCKETS: &[f64; 5] = &[0.00001, 0.0001, 0.001, 0.01, 0.1];
 
-pub const CELESTIA_SUBMISSION_HEIGHT: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_celestia_submission_height");
+pub const CELESTIA_SUBMISSION_HEIGHT: &str = telemetry::metric_name!("celestia_submission_height");
+
+pub const CELESTIA_SUBMISSION_COUNT: &str = telemetry::metric_name!("celestia_submission_count");
+
+pub const CELESTIA_SUBMISSION_FAILURE_COUNT: &str =
+    telemetry::metric_name!("celestia_submission_failure_count");
+
+pub const CELESTIA_RESUBMISSION_COUNT: &str =
+    telemetry::metric_name!("celestia_resubmission_count");
+
+pub const SEQUENCER_BLOCKS_BY_PROPOSER: &str =
+    telemetry::metric_name!("sequencer_blocks_by_proposer");
+
+pub const QUARANTINED_SEQUENCER_TRANSACTIONS: &str =
+    telemetry::metric_name!("quarantined_sequencer_transactions");
+
+pub const LEADERSHIP_CHANGES: &str = telemetry::metric_name!("leadership_changes");
+
+pub const IS_LEADER: &str = telemetry::metric_name!("is_leader");
+
+pub const BLOCKS_PER_CELESTIA_TX: &str = telemetry::metric_name!("blocks_per_celestia_tx");
+
+pub const BLOBS_PER_CELESTIA_TX: &str = telemetry::metric_name!("blobs_per_celestia_tx");
+
+pub const INGEST_QUEUED_SEQUENCER_BLOCKS: &str =
+    telemetry::metric_name!("ingest_queued_sequencer_blocks");
+
+pub const ASSEMBLY_QUEUED_SEQUENCER_BLOCKS: &str =
+    telemetry::metric_name!("assembly_queued_sequencer_blocks");
+
+pub const IN_FLIGHT_CELESTIA_SUBMISSIONS: &str =
+    telemetry::metric_name!("in_flight_celestia_submissions");
 
-pub const CELESTIA_SUBMISSION_COUNT: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_celestia_submission_count");
+pub const UNCONFIRMED_CELESTIA_SUBMISSIONS: &str =
+    telemetry::metric_name!("unconfirmed_celestia_submissions");
 
-pub const CELESTIA_SUBMISSION_FAILURE_COUNT: &str = concat!(
-    env!("CARGO_CRATE_NAME"),
-    "_celestia_submission_failure_count"
-);
+pub const BATCHING_WINDOW: &str = telemetry::metric_name!("batching_window");
 
-pub const BLOCKS_PER_CELESTIA_TX: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_blocks_per_celestia_tx");
+pub const BATCHING_MAX_BLOBS: &str = telemetry::metric_name!("batching_max_blobs");
 
-pub const BLOBS_PER_CELESTIA_TX: &str = concat!(env!("CARGO_CRATE_NAME"), "_blobs_per_celestia_tx");
+pub const SEQUENCER_HEIGHT_LAG: &str = telemetry::metric_name!("sequencer_height_lag");
 
 pub const CELESTIA_SUBMISSION_LATENCY: &str =
-    concat!(env!("CARGO_CRATE_NAME"), "_celestia_submission_latency");
-
-pub const SEQUENCER_BLOCK_FETCH_FAILURE_COUNT: &str = concat!(
-    env!("CARGO_CRATE_NAME"),
-    "_sequencer_block_fetch_failure_count",
-);
-
-pub const SEQUENCER_HEIGHT_FETCH_FAILURE_COUNT: &str = concat!(
-    env!("CARGO_CRATE_NAME"),
-    "_sequencer_height_fetch_failure_count",
-);
-
-pub const TOTAL_BLOB_DATA_SIZE_FOR_ASTRIA_BLOCK: &str = concat!(
-    env!("CARGO_CRATE_NAME"),
-    "_total_blob_data_size_for_astria_block"
-);
-pub const COMPRESSION_RATIO_FOR_ASTRIA_BLOCK: &str = concat!(
-    env!("CARGO_CRATE_NAME"),
-    "_compression_ratio_for_astria_block"
-);
+    telemetry::metric_name!("celestia_submission_latency");
+
+pub const SEQUENCER_BLOCK_ASSEMBLY_LATENCY: &str =
+    telemetry::metric_name!("sequencer_block_assembly_latency");
+
+pub const SEQUENCER_BLOCK_QUEUE_LATENCY: &str =
+    telemetry::metric_name!("sequencer_block_queue_latency");
+
+pub const SEQUENCER_BLOCK_CONFIRMATION_LATENCY: &str =
+    telemetry::metric_name!("sequencer_block_confirmation_latency");
+
+pub const SEQUENCER_BLOCK_END_TO_END_LATENCY: &str =
+    telemetry::metric_name!("sequencer_block_end_to_end_latency");
+
+pub const SEQUENCER_BLOCKS_MISSED_DEADLINE_COUNT: &str =
+    telemetry::metric_name!("sequencer_blocks_missed_deadline_count");
+
+pub const SEQUENCER_BLOCKS_OVERDUE: &str = telemetry::metric_name!("sequencer_blocks_overdue");
+
+pub const CELESTIA_DEADLINE_ESCALATION_COUNT: &str =
+    telemetry::metric_name!("celestia_deadline_escalation_count");
+
+pub const SEQUENCER_BLOCK_FETCH_FAILURE_COUNT: &str =
+    telemetry::metric_name!("sequencer_block_fetch_failure_count");
+
+pub const SEQUENCER_HEIGHT_FETCH_FAILURE_COUNT: &str =
+    telemetry::metric_name!("sequencer_height_fetch_failure_count");
+
+pub const INVALID_SEQUENCER_BLOCK_COUNT: &str =
+    telemetry::metric_name!("invalid_sequencer_block_count");
+
+pub const ROLLUP_QUOTA_EXCEEDED_COUNT: &str =
+    telemetry::metric_name!("rollup_quota_exceeded_count");
+
+pub const AUDITED_SEQUENCER_BLOBS: &str = telemetry::metric_name!("audited_sequencer_blobs");
+
+pub const AUDITED_CELESTIA_HEIGHT: &str = telemetry::metric_name!("audited_celestia_height");
+
+pub const AUDIT_FETCH_FAILURE_COUNT: &str = telemetry::metric_name!("audit_fetch_failure_count");
+
+pub const TOTAL_BLOB_DATA_SIZE_FOR_ASTRIA_BLOCK: &str =
+    telemetry::metric_name!("total_blob_data_size_for_astria_block");
+pub const COMPRESSION_RATIO_FOR_ASTRIA_BLOCK: &str =
+    telemetry::metric_name!("compression_ratio_for_astria_block");
@@ -0,0 +1,313 @@
+//! Operator defined auxiliary blobs submitted alongside sequencer blocks.
+//!
+//! Operators post auxiliary data (for example batch proofs or state diffs) for a sequencer
+//! block through the admin API before the relayer has converted that block to blobs. When the
+//! block is converted, its pending auxiliary blobs are wrapped in a
+//! [`CelestiaAuxiliaryBlob`] bound to the block hash and are submitted under their own
+//! namespace in the same Celestia transaction as the block.
+//!
+//! Auxiliary blobs posted for a block that was already converted are rejected, as are blobs
+//! under the namespace of a sequencer network.
+//!
+//! Execution nodes post the [`RollupResultNamespaceData`] of their rollup blocks once they have
+//! executed them, which is usually after the sequencer block they were derived from was
+//! converted. Rollup results are therefore not tied to a sequencer height, and are submitted
+//! with whichever sequencer block is converted next under the result namespace of their rollup.
+
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+        VecDeque,
+    },
+    sync::{
+        Mutex,
+        PoisonError,
+    },
+};
+
+use astria_core::sequencerblock::v1alpha1::{
+    CelestiaAuxiliaryBlob,
+    RollupResultNamespaceData,
+};
+use celestia_types::nmt::Namespace;
+use sequencer_client::tendermint::block::Height as SequencerHeight;
+use tracing::warn;
+
+use crate::config::SequencerNamespaces;
+
+/// The maximum number of auxiliary blobs and rollup results waiting to be submitted.
+const MAX_PENDING_BLOBS: usize = 256;
+
+/// The maximum size of the data of a single auxiliary blob.
+pub(crate) const MAX_BLOB_DATA_SIZE: usize = 512 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum InsertError {
+    #[error("the kind of an auxiliary blob must not be empty")]
+    EmptyKind,
+    #[error(
+        "auxiliary blob data of {actual} bytes exceeds the maximum of {MAX_BLOB_DATA_SIZE} bytes"
+    )]
+    TooLarge { actual: usize },
+    #[error("namespace is reserved for the blocks of a sequencer network")]
+    ReservedNamespace,
+    #[error(
+        "sequencer block at height {height} of network `{chain_id}` was already converted to \
+         blobs"
+    )]
+    AlreadyConverted {
+        chain_id: String,
+        height: SequencerHeight,
+    },
+    #[error("{MAX_PENDING_BLOBS} auxiliary blobs and rollup results are already waiting")]
+    Full,
+}
+
+/// An auxiliary blob waiting for its sequencer block to be converted.
+#[derive(Debug)]
+pub(crate) struct PendingAuxiliaryBlob {
+    pub(crate) namespace: Namespace,
+    pub(crate) kind: String,
+    pub(crate) data: Vec<u8>,
+}
+
+impl PendingAuxiliaryBlob {
+    /// Binds the blob to the sequencer block `sequencer_block_hash`.
+    pub(crate) fn into_celestia_blob(
+        self,
+        sequencer_block_hash: [u8; 32],
+    ) -> (Namespace, CelestiaAuxiliaryBlob) {
+        let Self {
+            namespace,
+            kind,
+            data,
+        } = self;
+        let blob = CelestiaAuxiliaryBlob::new(sequencer_block_hash, kind, data)
+            .expect("the kind of pending auxiliary blobs is checked to not be empty on insertion");
+        (namespace, blob)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The pending blobs, keyed by the chain ID and height of their sequencer block.
+    pending: BTreeMap<(String, SequencerHeight), Vec<PendingAuxiliaryBlob>>,
+    /// The rollup results waiting for the next converted sequencer block.
+    rollup_results: VecDeque<RollupResultNamespaceData>,
+    /// The number of pending blobs across all blocks, including rollup results.
+    num_pending: usize,
+    /// The height of the most recently converted block of each sequencer network.
+    converted: HashMap<String, SequencerHeight>,
+}
+
+/// The auxiliary blobs waiting for their sequencer blocks to be converted.
+///
+/// Shared between the write path and the admin API.
+#[derive(Debug)]
+pub(crate) struct AuxiliaryBlobs {
+    sequencer_namespaces: SequencerNamespaces,
+    inner: Mutex<Inner>,
+}
+
+impl AuxiliaryBlobs {
+    pub(crate) fn new(sequencer_namespaces: SequencerNamespaces) -> Self {
+        Self {
+            sequencer_namespaces,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Queues `blob` for submission with the block at `height` of the network `chain_id`.
+    pub(crate) fn insert(
+        &self,
+        chain_id: String,
+        height: SequencerHeight,
+        blob: PendingAuxiliaryBlob,
+    ) -> Result<(), InsertError> {
+        if blob.kind.is_empty() {
+            return Err(InsertError::EmptyKind);
+        }
+        if blob.data.len() > MAX_BLOB_DATA_SIZE {
+            return Err(InsertError::TooLarge {
+                actual: blob.data.len(),
+            });
+        }
+        if self.sequencer_namespaces.is_sequencer_namespace(&chain_id, blob.namespace) {
+            return Err(InsertError::ReservedNamespace);
+        }
+        let mut inner = self.lock();
+        if inner.converted.get(&chain_id).is_some_and(|converted| height <= *converted) {
+            return Err(InsertError::AlreadyConverted {
+                chain_id,
+                height,
+            });
+        }
+        if inner.num_pending >= MAX_PENDING_BLOBS {
+            return Err(InsertError::Full);
+        }
+        inner.num_pending += 1;
+        inner.pending.entry((chain_id, height)).or_default().push(blob);
+        Ok(())
+    }
+
+    /// Queues `result` for submission with the next converted sequencer block.
+    pub(crate) fn insert_rollup_result(
+        &self,
+        result: RollupResultNamespaceData,
+    ) -> Result<(), InsertError> {
+        let mut inner = self.lock();
+        if inner.num_pending >= MAX_PENDING_BLOBS {
+            return Err(InsertError::Full);
+        }
+        inner.num_pending += 1;
+        inner.rollup_results.push_back(result);
+        Ok(())
+    }
+
+    /// Takes all queued rollup results, oldest first.
+    pub(crate) fn take_rollup_results(&self) -> Vec<RollupResultNamespaceData> {
+        let mut inner = self.lock();
+        let results = inner.rollup_results.drain(..).collect::<Vec<_>>();
+        inner.num_pending = inner.num_pending.saturating_sub(results.len());
+        results
+    }
+
+    /// Takes the blobs pending for the block at `height` of the network `chain_id`.
+    ///
+    /// Blobs pending for earlier blocks of the same network are dropped: those blocks were
+    /// converted before the blobs were posted, or were skipped.
+    pub(crate) fn take(
+        &self,
+        chain_id: &str,
+        height: SequencerHeight,
+    ) -> Vec<PendingAuxiliaryBlob> {
+        let mut inner = self.lock();
+        let converted = inner.converted.entry(chain_id.to_string()).or_insert(height);
+        *converted = (*converted).max(height);
+
+        let stale = inner
+            .pending
+            .keys()
+            .filter(|(pending_chain_id, pending_height)| {
+                pending_chain_id == chain_id && *pending_height < height
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in stale {
+            if let Some(blobs) = inner.pending.remove(&key) {
+                warn!(
+                    sequencer_chain_id = chain_id,
+                    sequencer_height = %key.1,
+                    num_blobs = blobs.len(),
+                    "dropping auxiliary blobs whose sequencer block was not converted",
+                );
+                inner.num_pending = inner.num_pending.saturating_sub(blobs.len());
+            }
+        }
+
+        let blobs = inner
+            .pending
+            .remove(&(chain_id.to_string(), height))
+            .unwrap_or_default();
+        inner.num_pending = inner.num_pending.saturating_sub(blobs.len());
+        blobs
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn namespaces() -> SequencerNamespaces {
+        SequencerNamespaces::new(None, &[]).unwrap()
+    }
+
+    fn blob(kind: &str) -> PendingAuxiliaryBlob {
+        PendingAuxiliaryBlob {
+            namespace: Namespace::const_v0([7; 10]),
+            kind: kind.to_string(),
+            data: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn blobs_are_taken_with_their_block() {
+        let blobs = AuxiliaryBlobs::new(namespaces());
+        blobs.insert("test".to_string(), 5u32.into(), blob("proof")).unwrap();
+        assert!(blobs.take("other", 5u32.into()).is_empty());
+        assert!(blobs.take("test", 4u32.into()).is_empty());
+        let taken = blobs.take("test", 5u32.into());
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].kind, "proof");
+    }
+
+    #[test]
+    fn blobs_for_converted_blocks_are_rejected() {
+        let blobs = AuxiliaryBlobs::new(namespaces());
+        let _ = blobs.take("test", 5u32.into());
+        assert!(matches!(
+            blobs.insert("test".to_string(), 5u32.into(), blob("proof")),
+            Err(InsertError::AlreadyConverted { .. })
+        ));
+        blobs.insert("test".to_string(), 6u32.into(), blob("proof")).unwrap();
+    }
+
+    #[test]
+    fn blobs_under_sequencer_namespaces_are_rejected() {
+        let blobs = AuxiliaryBlobs::new(namespaces());
+        let namespace = astria_core::celestia::sequencer_namespace("test", None);
+        let blob = PendingAuxiliaryBlob {
+            namespace,
+            ..blob("proof")
+        };
+        assert!(matches!(
+            blobs.insert("test".to_string(), 1u32.into(), blob),
+            Err(InsertError::ReservedNamespace)
+        ));
+    }
+
+    #[test]
+    fn rollup_results_are_taken_in_order() {
+        use astria_core::{
+            primitive::v1::RollupId,
+            sequencerblock::v1alpha1::celestia::UncheckedRollupResultNamespaceData,
+        };
+
+        let blobs = AuxiliaryBlobs::new(namespaces());
+        for rollup_block_number in [1, 2] {
+            let result = UncheckedRollupResultNamespaceData {
+                sequencer_block_hash: [1; 32],
+                rollup_id: RollupId::new([2; 32]),
+                rollup_block_number,
+                rollup_block_hash: [3; 32],
+                state_root: [4; 32],
+                receipts_root: [5; 32],
+            }
+            .into_rollup_result_namespace_data();
+            blobs.insert_rollup_result(result).unwrap();
+        }
+        let taken = blobs.take_rollup_results();
+        assert_eq!(
+            taken
+                .iter()
+                .map(RollupResultNamespaceData::rollup_block_number)
+                .collect::<Vec<_>>(),
+            vec![1, 2],
+        );
+        assert!(blobs.take_rollup_results().is_empty());
+        assert_eq!(blobs.lock().num_pending, 0);
+    }
+
+    #[test]
+    fn stale_blobs_are_dropped() {
+        let blobs = AuxiliaryBlobs::new(namespaces());
+        blobs.insert("test".to_string(), 3u32.into(), blob("proof")).unwrap();
+        assert!(blobs.take("test", 4u32.into()).is_empty());
+        assert_eq!(blobs.lock().num_pending, 0);
+    }
+}
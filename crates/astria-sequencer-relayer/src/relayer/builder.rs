@@ -1,41 +1,108 @@
 use std::{
+    collections::HashMap,
     path::PathBuf,
     sync::Arc,
     time::Duration,
 };
 
-use astria_core::generated::sequencerblock::v1alpha1::sequencer_service_client::SequencerServiceClient;
+use astria_core::{
+    celestia::Namespace,
+    generated::sequencerblock::v1alpha1::sequencer_service_client::SequencerServiceClient,
+    primitive::v1::RollupId,
+    sequencerblock::v1alpha1::BlobFormat,
+    signers::KnownSigners,
+};
 use astria_eyre::eyre::{
     self,
+    ensure,
     WrapErr as _,
 };
-use sequencer_client::HttpClient as SequencerClient;
+use sequencer_client::{
+    tendermint::chain,
+    HttpClient as SequencerClient,
+};
+use tokio_util::sync::CancellationToken;
 use tonic::transport::{
     Endpoint,
     Uri,
 };
 
 use super::{
+    auxiliary::AuxiliaryBlobs,
+    lag::LagThresholds,
+    latency::{
+        LatencyTracker,
+        SubmissionDeadline,
+    },
+    leader,
+    network::SequencerNetwork,
     state::State,
+    write::{
+        RollupSigningKey,
+        RollupSigningKeys,
+    },
     CelestiaClientBuilder,
     CelestiaKeys,
+    Heartbeat,
 };
 use crate::{
+    alerts::Alerter,
+    attestations::AttestationStore,
+    clock::Clock,
+    config::{
+        EmptyBlockPolicy,
+        RollupQuotas,
+        RollupSigningKeyFile,
+        SequencerNamespaces,
+        SequencerNetworkConfig,
+    },
+    costs::CostLedger,
+    height_index::HeightIndex,
+    storage,
     validator::Validator,
     IncludeRollup,
 };
 
 pub(crate) struct Builder {
-    pub(crate) shutdown_token: tokio_util::sync::CancellationToken,
+    pub(crate) shutdown_token: CancellationToken,
     pub(crate) celestia_app_grpc_endpoint: String,
     pub(crate) celestia_app_key_file: String,
     pub(crate) cometbft_endpoint: String,
     pub(crate) sequencer_poll_period: Duration,
     pub(crate) sequencer_grpc_endpoint: String,
+    pub(crate) pull_blocks_from_cometbft: bool,
     pub(crate) validator_key_path: Option<String>,
     pub(crate) rollup_filter: IncludeRollup,
+    pub(crate) sequencer_namespaces: SequencerNamespaces,
     pub(crate) pre_submit_path: PathBuf,
     pub(crate) post_submit_path: PathBuf,
+    pub(crate) celestia_resubmit_after_blocks: u32,
+    pub(crate) celestia_max_resubmissions: u32,
+    pub(crate) celestia_mempool_ttl_blocks: u32,
+    pub(crate) celestia_confirmation_depth: u64,
+    pub(crate) celestia_max_stale_height: Option<Duration>,
+    pub(crate) submission_deadline: Option<SubmissionDeadline>,
+    pub(crate) celestia_error_policy: super::CelestiaErrorPolicy,
+    pub(crate) batching_bounds: super::BatchingBounds,
+    pub(crate) max_concurrent_submissions: u32,
+    pub(crate) blob_format: BlobFormat,
+    pub(crate) empty_block_policy: EmptyBlockPolicy,
+    pub(crate) rollup_blob_dedup_capacity: u32,
+    pub(crate) heartbeat_namespace: Option<Namespace>,
+    pub(crate) heartbeat_interval_celestia_blocks: u64,
+    pub(crate) heartbeat_key_path: String,
+    pub(crate) rollup_signing_key_files: HashMap<RollupId, RollupSigningKeyFile>,
+    pub(crate) rollup_quotas: RollupQuotas,
+    pub(crate) additional_networks: Vec<SequencerNetworkConfig>,
+    pub(crate) alerts: Alerter,
+    pub(crate) cost_ledger: Option<CostLedger>,
+    pub(crate) height_index: Option<Arc<HeightIndex>>,
+    pub(crate) attestations: Option<Arc<AttestationStore>>,
+    pub(crate) lag_thresholds: LagThresholds,
+    pub(crate) known_signers: KnownSigners,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) storage: storage::Builder,
+    pub(crate) leader_election: Option<leader::Settings>,
 }
 
 impl Builder {
@@ -48,8 +115,272 @@ impl Builder {
             cometbft_endpoint,
             sequencer_poll_period,
             sequencer_grpc_endpoint,
+            pull_blocks_from_cometbft,
+            validator_key_path,
+            rollup_filter,
+            sequencer_namespaces,
+            pre_submit_path,
+            post_submit_path,
+            celestia_resubmit_after_blocks,
+            celestia_max_resubmissions,
+            celestia_mempool_ttl_blocks,
+            celestia_confirmation_depth,
+            celestia_max_stale_height,
+            submission_deadline,
+            celestia_error_policy,
+            batching_bounds,
+            max_concurrent_submissions,
+            blob_format,
+            empty_block_policy,
+            rollup_blob_dedup_capacity,
+            heartbeat_namespace,
+            heartbeat_interval_celestia_blocks,
+            heartbeat_key_path,
+            rollup_signing_key_files,
+            rollup_quotas,
+            additional_networks,
+            alerts,
+            cost_ledger,
+            height_index,
+            attestations,
+            lag_thresholds,
+            known_signers,
+            clock,
+            storage,
+            leader_election,
+        } = self;
+        ensure!(
+            celestia_resubmit_after_blocks > 0,
+            "the number of Celestia blocks after which to resubmit unconfirmed blobs must be \
+             greater than 0"
+        );
+        ensure!(
+            max_concurrent_submissions > 0,
+            "the maximum number of concurrent submissions to Celestia must be greater than 0"
+        );
+        ensure!(
+            batching_bounds.min_window <= batching_bounds.max_window,
+            "the minimum batching window must not be greater than the maximum batching window"
+        );
+        ensure!(
+            batching_bounds.min_blobs > 0,
+            "the minimum number of blobs per submission must be greater than 0"
+        );
+        ensure!(
+            batching_bounds.min_blobs <= batching_bounds.max_blobs,
+            "the minimum number of blobs per submission must not be greater than the maximum"
+        );
+        ensure!(
+            lag_thresholds.warn == 0
+                || lag_thresholds.error == 0
+                || lag_thresholds.warn <= lag_thresholds.error,
+            "the sequencer height lag warn threshold must not be greater than the error threshold"
+        );
+        let max_concurrent_submissions = usize::try_from(max_concurrent_submissions)
+            .wrap_err("failed converting the maximum number of concurrent submissions to usize")?;
+        let rollup_blob_dedup_capacity = usize::try_from(rollup_blob_dedup_capacity)
+            .wrap_err("failed converting the rollup blob deduplication capacity to usize")?;
+
+        let heartbeat = heartbeat_namespace
+            .map(|namespace| {
+                let key = Validator::from_path(&heartbeat_key_path).wrap_err_with(|| {
+                    format!("failed reading heartbeat key from file at `{heartbeat_key_path}`")
+                })?;
+                eyre::Ok(Heartbeat::new(
+                    namespace,
+                    key,
+                    heartbeat_interval_celestia_blocks,
+                ))
+            })
+            .transpose()?;
+
+        let rollup_signing_keys = rollup_signing_key_files
+            .into_iter()
+            .map(|(rollup_id, file)| {
+                let key = RollupSigningKey::from_file(&file).wrap_err_with(|| {
+                    format!(
+                        "failed reading signing key of rollup `{rollup_id}` from file at `{}`",
+                        file.path.display()
+                    )
+                })?;
+                eyre::Ok((rollup_id, key))
+            })
+            .collect::<eyre::Result<HashMap<_, _>>>()?;
+        let rollup_signing_keys = RollupSigningKeys::new(rollup_signing_keys);
+
+        let state = Arc::new(State::new());
+        let latencies = Arc::new(LatencyTracker::new(clock.clone(), submission_deadline));
+        let auxiliary_blobs = Arc::new(AuxiliaryBlobs::new(sequencer_namespaces.clone()));
+        let known_signers = Arc::new(known_signers);
+
+        let primary_network = NetworkBuilder {
+            chain_id: None,
+            cometbft_endpoint,
+            sequencer_grpc_endpoint,
+            sequencer_poll_period,
+            pull_blocks_from_cometbft,
             validator_key_path,
+            state: state.clone(),
+            latencies: latencies.clone(),
+            alerts: alerts.clone(),
+            known_signers: known_signers.clone(),
+            shutdown_token: shutdown_token.clone(),
+            pre_submit_path,
+            post_submit_path,
+        }
+        .build()?;
+
+        let additional_networks = additional_networks
+            .into_iter()
+            .map(|config| {
+                let chain_id = config.chain_id.clone();
+                build_additional_network(
+                    config,
+                    sequencer_poll_period,
+                    NetworkShared {
+                        pull_blocks_from_cometbft,
+                        latencies: latencies.clone(),
+                        alerts: alerts.clone(),
+                        known_signers: known_signers.clone(),
+                    },
+                    shutdown_token.clone(),
+                )
+                .wrap_err_with(|| {
+                    format!("failed to create additional sequencer network `{chain_id}`")
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let celestia_client_builder = {
+            let uri: Uri = celestia_app_grpc_endpoint
+                .parse()
+                .wrap_err("failed parsing provided celestia app grpc endpoint as Uri")?;
+            let celestia_keys = CelestiaKeys::from_path(celestia_app_key_file)
+                .wrap_err("failed to get celestia keys from file")?;
+            CelestiaClientBuilder::new(
+                uri,
+                Arc::new(celestia_keys),
+                state.clone(),
+                celestia_resubmit_after_blocks,
+                celestia_max_resubmissions,
+                celestia_mempool_ttl_blocks,
+                celestia_confirmation_depth,
+                celestia_max_stale_height,
+                celestia_error_policy,
+                clock,
+            )
+            .wrap_err("failed to create celestia client builder")?
+        };
+
+        Ok(super::Relayer {
+            shutdown_token,
+            primary_network,
+            additional_networks,
+            celestia_client_builder,
             rollup_filter,
+            sequencer_namespaces,
+            batching_bounds,
+            max_concurrent_submissions,
+            blob_format,
+            empty_block_policy,
+            rollup_blob_dedup_capacity,
+            rollup_signing_keys,
+            rollup_quotas,
+            heartbeat,
+            state,
+            latencies,
+            auxiliary_blobs,
+            alerts,
+            cost_ledger,
+            height_index,
+            attestations,
+            lag_thresholds,
+            storage,
+            leader_election,
+        })
+    }
+}
+
+/// The parts shared by all sequencer networks.
+struct NetworkShared {
+    pull_blocks_from_cometbft: bool,
+    latencies: Arc<LatencyTracker>,
+    alerts: Alerter,
+    known_signers: Arc<KnownSigners>,
+}
+
+fn build_additional_network(
+    config: SequencerNetworkConfig,
+    sequencer_poll_period: Duration,
+    shared: NetworkShared,
+    shutdown_token: CancellationToken,
+) -> eyre::Result<SequencerNetwork> {
+    let NetworkShared {
+        pull_blocks_from_cometbft,
+        latencies,
+        alerts,
+        known_signers,
+    } = shared;
+    let SequencerNetworkConfig {
+        chain_id,
+        cometbft_endpoint,
+        sequencer_grpc_endpoint,
+        validator_key_file,
+        pre_submit_path,
+        post_submit_path,
+        // The namespaces of all networks are resolved ahead of time by `SequencerNamespaces`.
+        celestia_sequencer_namespace: _,
+    } = config;
+    let chain_id = chain::Id::try_from(chain_id).wrap_err("failed parsing chain ID")?;
+    NetworkBuilder {
+        chain_id: Some(chain_id),
+        cometbft_endpoint,
+        sequencer_grpc_endpoint,
+        sequencer_poll_period,
+        pull_blocks_from_cometbft,
+        validator_key_path: validator_key_file,
+        // The state of additional networks is not served by the API.
+        state: Arc::new(State::new()),
+        latencies,
+        alerts,
+        known_signers,
+        shutdown_token,
+        pre_submit_path,
+        post_submit_path,
+    }
+    .build()
+}
+
+struct NetworkBuilder {
+    chain_id: Option<chain::Id>,
+    cometbft_endpoint: String,
+    sequencer_grpc_endpoint: String,
+    sequencer_poll_period: Duration,
+    pull_blocks_from_cometbft: bool,
+    validator_key_path: Option<String>,
+    state: Arc<State>,
+    latencies: Arc<LatencyTracker>,
+    alerts: Alerter,
+    known_signers: Arc<KnownSigners>,
+    shutdown_token: CancellationToken,
+    pre_submit_path: PathBuf,
+    post_submit_path: PathBuf,
+}
+
+impl NetworkBuilder {
+    fn build(self) -> eyre::Result<SequencerNetwork> {
+        let Self {
+            chain_id,
+            cometbft_endpoint,
+            sequencer_grpc_endpoint,
+            sequencer_poll_period,
+            pull_blocks_from_cometbft,
+            validator_key_path,
+            state,
+            latencies,
+            alerts,
+            known_signers,
+            shutdown_token,
             pre_submit_path,
             post_submit_path,
         } = self;
@@ -69,27 +400,18 @@ impl Builder {
             .transpose()
             .wrap_err("failed to get validator info from file")?;
 
-        let state = Arc::new(State::new());
-
-        let celestia_client_builder = {
-            let uri: Uri = celestia_app_grpc_endpoint
-                .parse()
-                .wrap_err("failed parsing provided celestia app grpc endpoint as Uri")?;
-            let celestia_keys = CelestiaKeys::from_path(celestia_app_key_file)
-                .wrap_err("failed to get celestia keys from file")?;
-            CelestiaClientBuilder::new(uri, celestia_keys, state.clone())
-                .wrap_err("failed to create celestia client builder")?
-        };
-
-        Ok(super::Relayer {
-            shutdown_token,
+        Ok(SequencerNetwork {
+            chain_id,
             sequencer_cometbft_client,
             sequencer_grpc_client,
             sequencer_poll_period,
-            celestia_client_builder,
+            pull_blocks_from_cometbft,
             validator,
-            rollup_filter,
             state,
+            latencies,
+            alerts,
+            known_signers,
+            shutdown_token,
             pre_submit_path,
             post_submit_path,
         })
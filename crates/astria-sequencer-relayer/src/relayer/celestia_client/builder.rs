@@ -1,11 +1,11 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
-use astria_core::generated::cosmos::{
-    base::tendermint::v1beta1::{
-        service_client::ServiceClient as NodeInfoClient,
-        GetNodeInfoRequest,
-    },
-    tx::v1beta1::service_client::ServiceClient as TxClient,
+use astria_core::generated::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient as NodeInfoClient,
+    GetNodeInfoRequest,
 };
 use http::Uri;
 use tendermint::account::Id as AccountId;
@@ -19,10 +19,16 @@ use tracing::trace;
 use super::{
     super::State,
     Bech32Address,
+    BroadcastTracker,
     CelestiaClient,
-    CelestiaKeys,
+    ErrorPolicy,
+    GrpcCelestiaRpc,
     GrpcResponseError,
+    LatestHeightCache,
+    SequenceTracker,
+    TxSigner,
 };
+use crate::clock::Clock;
 
 /// An error when building the `CelestiaClient`.
 #[derive(Error, Clone, Debug)]
@@ -52,50 +58,115 @@ pub(in crate::relayer) struct Bech32EncodeError(#[from] bech32::EncodeError);
 pub(in crate::relayer) struct Builder {
     /// The inner `tonic` gRPC channel shared by the various generated gRPC clients.
     grpc_channel: Channel,
-    /// The crypto keys associated with our Celestia account.
-    signing_keys: CelestiaKeys,
+    /// Signs the transactions paying for the blobs from our Celestia account.
+    signer: Arc<dyn TxSigner>,
     /// The Bech32-encoded address of our Celestia account.
     address: Bech32Address,
     /// A handle to the mutable state of the relayer.
     state: Arc<State>,
+    /// The number of Celestia blocks to wait for a submission to be confirmed before resubmitting
+    /// it.
+    resubmit_after_blocks: u64,
+    /// The maximum number of times a single submission is resubmitted.
+    max_resubmissions: u32,
+    /// The number of Celestia blocks after which the Celestia app evicts a transaction from its
+    /// mempool.
+    mempool_ttl_blocks: u64,
+    /// The number of Celestia blocks on top of the inclusion height before a submission is firm.
+    confirmation_depth: u64,
+    /// The maximum age of the last fetched Celestia height used while the latest height cannot
+    /// be fetched, or `None` if a stale height is never used.
+    max_stale_latest_height_age: Option<Duration>,
+    /// Classifies the errors returned while submitting.
+    error_policy: ErrorPolicy,
+    /// The clock that confirmations are polled on.
+    clock: Arc<dyn Clock>,
 }
 
 impl Builder {
-    /// Returns a new `Builder`, or an error if Bech32-encoding the `signing_keys` address fails.
+    /// Returns a new `Builder`, or an error if Bech32-encoding the `signer` address fails.
+    ///
+    /// Submissions that are not included in a Celestia block after `resubmit_after_blocks`
+    /// Celestia blocks are resubmitted up to `max_resubmissions` times, each time once the
+    /// transaction given up on was evicted after `mempool_ttl_blocks`. Included submissions are
+    /// only treated as firm once `confirmation_depth` Celestia blocks were produced on top of
+    /// them. Errors returned while submitting are handled according to their class in
+    /// `error_policy`.
+    ///
+    /// If the latest Celestia height cannot be fetched, the height fetched last is used in its
+    /// place as long as it is at most `max_stale_latest_height_age` old. If that is `None`, the
+    /// error is returned instead.
+    // allow: each argument is a separate setting of the client, all of them taken from the
+    // relayer config.
+    #[allow(clippy::too_many_arguments)]
     pub(in crate::relayer) fn new(
         uri: Uri,
-        signing_keys: CelestiaKeys,
+        signer: Arc<dyn TxSigner>,
         state: Arc<State>,
+        resubmit_after_blocks: u32,
+        max_resubmissions: u32,
+        mempool_ttl_blocks: u32,
+        confirmation_depth: u64,
+        max_stale_latest_height_age: Option<Duration>,
+        error_policy: ErrorPolicy,
+        clock: Arc<dyn Clock>,
     ) -> Result<Self, BuilderError> {
         let grpc_channel = Endpoint::from(uri).connect_lazy();
-        let address = bech32_encode(&signing_keys.address)?;
+        let address = bech32_encode(&signer.address())?;
         Ok(Self {
             grpc_channel,
-            signing_keys,
+            signer,
             address,
             state,
+            resubmit_after_blocks: u64::from(resubmit_after_blocks),
+            max_resubmissions,
+            mempool_ttl_blocks: u64::from(mempool_ttl_blocks),
+            confirmation_depth,
+            max_stale_latest_height_age,
+            error_policy,
+            clock,
         })
     }
 
+    /// Returns the Bech32-encoded address of our Celestia account.
+    pub(in crate::relayer) fn address(&self) -> &str {
+        &self.address.0
+    }
+
     /// Returns a new `CelestiaClient` initialized with info retrieved from the Celestia app.
     pub(in crate::relayer) async fn try_build(self) -> Result<CelestiaClient, BuilderError> {
         let chain_id = self.fetch_chain_id().await?;
 
         let Self {
             grpc_channel,
-            signing_keys,
+            signer,
             address,
             state,
+            resubmit_after_blocks,
+            max_resubmissions,
+            mempool_ttl_blocks,
+            confirmation_depth,
+            max_stale_latest_height_age,
+            error_policy,
+            clock,
         } = self;
         state.set_celestia_connected(true);
 
-        let tx_client = TxClient::new(grpc_channel.clone());
         Ok(CelestiaClient {
-            grpc_channel,
-            tx_client,
-            signing_keys,
+            rpc: Arc::new(GrpcCelestiaRpc::new(grpc_channel)),
+            signer,
             address,
             chain_id,
+            resubmit_after_blocks,
+            max_resubmissions,
+            mempool_ttl_blocks,
+            confirmation_depth,
+            max_stale_latest_height_age,
+            error_policy,
+            sequence_tracker: Arc::new(SequenceTracker::default()),
+            broadcast_tracker: Arc::new(BroadcastTracker::default()),
+            latest_height_cache: Arc::new(LatestHeightCache::default()),
+            clock,
         })
     }
 
@@ -117,7 +188,7 @@ impl Builder {
     }
 }
 
-fn bech32_encode(address: &AccountId) -> Result<Bech32Address, BuilderError> {
+pub(super) fn bech32_encode(address: &AccountId) -> Result<Bech32Address, BuilderError> {
     // From https://github.com/celestiaorg/celestia-app/blob/v1.4.0/app/app.go#L104
     const ACCOUNT_ADDRESS_PREFIX: bech32::Hrp = bech32::Hrp::parse_unchecked("celestia");
 
@@ -4,7 +4,6 @@ use std::{
         Debug,
         Formatter,
     },
-    fs,
     path::Path,
 };
 
@@ -19,6 +18,9 @@ use tendermint::{
 };
 use thiserror::Error;
 use tracing::instrument;
+use zeroize::Zeroizing;
+
+use crate::secret::SecretString;
 
 /// Errors which can be returned when parsing a Celestia signing key file.
 ///
@@ -43,6 +45,22 @@ pub(crate) enum Error {
 #[error(transparent)]
 pub(crate) struct FromHexError(#[from] hex::FromHexError);
 
+/// Signs the transactions paying for blobs submitted to Celestia.
+///
+/// The signatures are secp256k1 ECDSA signatures, as required for Cosmos SDK accounts.
+pub(crate) trait TxSigner: Debug + Send + Sync + 'static {
+    /// Returns the key verifying the signatures.
+    fn verification_key(&self) -> VerificationKey;
+
+    /// Returns the signature over `data`.
+    fn sign(&self, data: &[u8]) -> Signature;
+
+    /// Returns the celestia account address; defined as SHA256(public key)[..20].
+    fn address(&self) -> AccountId {
+        AccountId::from(self.verification_key())
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct CelestiaKeys {
     /// The celestia account address; defined as SHA256(public key)[..20].
@@ -63,16 +81,27 @@ impl CelestiaKeys {
     /// --unarmored-hex`, not a keyfile generated by celestia app.
     #[instrument(skip_all, fields(path = %path.as_ref().display(), err))]
     pub(crate) fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
-        let hex_encoded = fs::read_to_string(path)?;
-        let bytes = hex::decode(hex_encoded.trim())
+        let hex_encoded = SecretString::read_file(path)?;
+        let bytes = hex::decode(hex_encoded.expose_secret().trim())
+            .map(Zeroizing::new)
             .map_err(|error| Error::DecodeFromHex(FromHexError(error)))?;
         let key = SigningKey::from_slice(&bytes).map_err(|_| Error::InvalidSigningKey)?;
         Ok(Self::from(key))
     }
+}
+
+impl TxSigner for CelestiaKeys {
+    fn verification_key(&self) -> VerificationKey {
+        self.verification_key
+    }
 
-    pub(crate) fn sign(&self, data: &[u8]) -> Signature {
+    fn sign(&self, data: &[u8]) -> Signature {
         self.signing_key.sign(data)
     }
+
+    fn address(&self) -> AccountId {
+        self.address
+    }
 }
 
 impl From<SigningKey> for CelestiaKeys {
@@ -100,6 +129,8 @@ impl Debug for CelestiaKeys {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use super::*;
 
     #[test]
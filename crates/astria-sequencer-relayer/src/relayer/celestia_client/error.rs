@@ -4,7 +4,10 @@ use std::{
         Display,
         Formatter,
     },
-    num::ParseFloatError,
+    num::{
+        ParseFloatError,
+        ParseIntError,
+    },
 };
 
 use prost::DecodeError;
@@ -60,6 +63,18 @@ pub(in crate::relayer) enum TrySubmitError {
     /// Failed to decode the received account info.
     #[error("failed to decode account info")]
     DecodeAccountInfo(#[source] ProtobufDecodeError),
+    /// The celestia app responded with the given error status to a `QueryBalanceRequest`.
+    #[error("failed to get account balance")]
+    FailedToGetBalance(#[source] GrpcResponseError),
+    /// The balance response was empty.
+    #[error("the account balance response was empty")]
+    EmptyBalance,
+    /// The balance could not be parsed as an integer amount.
+    #[error("the account balance `{balance}` could not be parsed as an integer")]
+    FailedToParseBalance {
+        balance: String,
+        source: ParseIntError,
+    },
     /// The celestia app responded with the given error status to a `BroadcastTxRequest`.
     #[error("failed to broadcast transaction")]
     FailedToBroadcastTx(#[source] GrpcResponseError),
@@ -94,9 +109,160 @@ pub(in crate::relayer) enum TrySubmitError {
         namespace: String,
         log: String,
     },
+    /// The broadcast transaction was not included in a Celestia block within the allotted
+    /// number of blocks.
+    #[error(
+        "transaction `{tx_hash}` with a fee of `{fee}utia` was not confirmed within \
+         {timeout_blocks} Celestia blocks"
+    )]
+    SubmissionNotConfirmed {
+        tx_hash: String,
+        fee: u64,
+        timeout_blocks: u64,
+    },
     /// The get transaction response specified a negative block height.
     #[error("get transaction response specifies a negative block height ({0})")]
     GetTxResponseNegativeBlockHeight(i64),
+    /// The celestia app responded with the given error status to a `GetLatestBlockRequest`.
+    #[error("failed to get latest block")]
+    FailedToGetLatestBlock(#[source] GrpcResponseError),
+    /// The latest block response did not contain a block header.
+    #[error("the latest block response did not contain a block header")]
+    EmptyLatestBlock,
+    /// The latest block response specified a negative block height.
+    #[error("latest block response specifies a negative block height ({0})")]
+    LatestBlockNegativeHeight(i64),
+}
+
+/// The code with which the Cosmos SDK rejects transactions whose fee the sender cannot pay.
+pub(super) const INSUFFICIENT_FUNDS_CODE: u32 = 5;
+
+/// The codespace of errors returned by the Cosmos SDK itself.
+pub(super) const SDK_CODESPACE: &str = "sdk";
+
+/// The names of all kinds of [`TrySubmitError`], as returned by [`TrySubmitError::kind`].
+pub(super) const TRY_SUBMIT_ERROR_KINDS: &[&str] = &[
+    "failed_to_get_blob_params",
+    "empty_blob_params",
+    "failed_to_get_auth_params",
+    "empty_auth_params",
+    "failed_to_get_min_gas_price",
+    "min_gas_price_bad_suffix",
+    "failed_to_parse_min_gas_price",
+    "blob_too_large",
+    "failed_to_get_account_info",
+    "empty_account_info",
+    "account_info_type_mismatch",
+    "decode_account_info",
+    "failed_to_get_balance",
+    "empty_balance",
+    "failed_to_parse_balance",
+    "failed_to_broadcast_tx",
+    "empty_broadcast_tx_response",
+    "broadcast_tx_response_error_code",
+    "failed_to_get_tx",
+    "empty_get_tx_response",
+    "get_tx_response_error_code",
+    "submission_not_confirmed",
+    "get_tx_response_negative_block_height",
+];
+
+impl TrySubmitError {
+    /// Returns the name of the kind of error, as used to override its class in an
+    /// [`super::ErrorPolicy`].
+    pub(in crate::relayer) fn kind(&self) -> &'static str {
+        match self {
+            Self::FailedToGetBlobParams(_) => "failed_to_get_blob_params",
+            Self::EmptyBlobParams => "empty_blob_params",
+            Self::FailedToGetAuthParams(_) => "failed_to_get_auth_params",
+            Self::EmptyAuthParams => "empty_auth_params",
+            Self::FailedToGetMinGasPrice(_) => "failed_to_get_min_gas_price",
+            Self::MinGasPriceBadSuffix {
+                ..
+            } => "min_gas_price_bad_suffix",
+            Self::FailedToParseMinGasPrice {
+                ..
+            } => "failed_to_parse_min_gas_price",
+            Self::BlobTooLarge {
+                ..
+            } => "blob_too_large",
+            Self::FailedToGetAccountInfo(_) => "failed_to_get_account_info",
+            Self::EmptyAccountInfo => "empty_account_info",
+            Self::AccountInfoTypeMismatch {
+                ..
+            } => "account_info_type_mismatch",
+            Self::DecodeAccountInfo(_) => "decode_account_info",
+            Self::FailedToGetBalance(_) => "failed_to_get_balance",
+            Self::EmptyBalance => "empty_balance",
+            Self::FailedToParseBalance {
+                ..
+            } => "failed_to_parse_balance",
+            Self::FailedToBroadcastTx(_) => "failed_to_broadcast_tx",
+            Self::EmptyBroadcastTxResponse => "empty_broadcast_tx_response",
+            Self::BroadcastTxResponseErrorCode {
+                ..
+            } => "broadcast_tx_response_error_code",
+            Self::FailedToGetTx(_) => "failed_to_get_tx",
+            Self::EmptyGetTxResponse => "empty_get_tx_response",
+            Self::GetTxResponseErrorCode {
+                ..
+            } => "get_tx_response_error_code",
+            Self::SubmissionNotConfirmed {
+                ..
+            } => "submission_not_confirmed",
+            Self::GetTxResponseNegativeBlockHeight(_) => "get_tx_response_negative_block_height",
+            Self::FailedToGetLatestBlock(_) => "failed_to_get_latest_block",
+            Self::EmptyLatestBlock => "empty_latest_block",
+            Self::LatestBlockNegativeHeight(_) => "latest_block_negative_height",
+        }
+    }
+
+    /// Returns the codespace and code of the error if Celestia rejected a transaction.
+    pub(in crate::relayer) fn abci_code(&self) -> Option<(&str, u32)> {
+        match self {
+            Self::BroadcastTxResponseErrorCode {
+                code,
+                namespace,
+                ..
+            }
+            | Self::GetTxResponseErrorCode {
+                code,
+                namespace,
+                ..
+            } => Some((namespace, *code)),
+            _ => None,
+        }
+    }
+
+    /// Returns the gRPC status code of the error if the Celestia app responded with an error
+    /// status.
+    pub(in crate::relayer) fn grpc_code(&self) -> Option<tonic::Code> {
+        match self {
+            Self::FailedToGetBlobParams(error)
+            | Self::FailedToGetAuthParams(error)
+            | Self::FailedToGetMinGasPrice(error)
+            | Self::FailedToGetAccountInfo(error)
+            | Self::FailedToGetBalance(error)
+            | Self::FailedToBroadcastTx(error)
+            | Self::FailedToGetTx(error)
+            | Self::FailedToGetLatestBlock(error) => Some(error.0.code()),
+            _ => None,
+        }
+    }
+
+    /// Returns the log of the broadcast response if the transaction was rejected because the
+    /// relayer's account could not pay its fee.
+    pub(in crate::relayer) fn insufficient_funds_log(&self) -> Option<&str> {
+        match self {
+            Self::BroadcastTxResponseErrorCode {
+                code,
+                namespace,
+                log,
+                ..
+            } if *code == INSUFFICIENT_FUNDS_CODE && namespace == SDK_CODESPACE => Some(log),
+            _ => None,
+        }
+    }
 }
 
 /// A gRPC status representing an error response from an RPC call.
@@ -0,0 +1,309 @@
+//! The classification of errors returned while submitting to Celestia.
+//!
+//! Every [`TrySubmitError`] is either retried, ends the submission and with it the relayer, or is
+//! retried while alerting an operator whose intervention is needed for the submission to succeed.
+//! The defaults in [`ErrorPolicy::default_class`] can be overridden per kind of error, per ABCI
+//! code a transaction was rejected with, or per gRPC status code returned by the Celestia app,
+//! so that unusual node behavior can be handled in the configuration.
+
+use std::{
+    fmt,
+    str::FromStr,
+    sync::Arc,
+};
+
+use astria_eyre::eyre::{
+    self,
+    bail,
+    ensure,
+    WrapErr as _,
+};
+
+use super::error::{
+    TrySubmitError,
+    INSUFFICIENT_FUNDS_CODE,
+    SDK_CODESPACE,
+    TRY_SUBMIT_ERROR_KINDS,
+};
+
+// From https://github.com/celestiaorg/cosmos-sdk/blob/v1.18.3-sdk-v0.46.14/types/errors/errors.go
+const UNAUTHORIZED_CODE: u32 = 4;
+const UNKNOWN_ADDRESS_CODE: u32 = 9;
+
+/// How the relayer responds to an error returned while submitting to Celestia.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorClass {
+    /// The submission is retried after a backoff.
+    Retryable,
+    /// The submission is abandoned and the relayer shuts down.
+    Fatal,
+    /// The submission is retried after a backoff, and an alert is raised because it will keep
+    /// failing until an operator intervenes.
+    RequiresOperator,
+}
+
+impl ErrorClass {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Retryable => "retryable",
+            Self::Fatal => "fatal",
+            Self::RequiresOperator => "requires_operator",
+        }
+    }
+}
+
+impl fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ErrorClass {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "retryable" => Ok(Self::Retryable),
+            "fatal" => Ok(Self::Fatal),
+            "requires_operator" => Ok(Self::RequiresOperator),
+            other => bail!(
+                "unknown error class `{other}`; expected `retryable`, `fatal`, or \
+                 `requires_operator`"
+            ),
+        }
+    }
+}
+
+/// Which errors an override of the [`ErrorPolicy`] applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Matcher {
+    /// Errors of the kind with this name, as returned by [`TrySubmitError::kind`].
+    Kind(&'static str),
+    /// Transactions rejected with this ABCI codespace and code.
+    AbciCode { codespace: String, code: u32 },
+    /// Requests answered with this gRPC status code.
+    GrpcCode(tonic::Code),
+}
+
+impl Matcher {
+    fn matches(&self, error: &TrySubmitError) -> bool {
+        match self {
+            Self::Kind(kind) => error.kind() == *kind,
+            Self::AbciCode {
+                codespace,
+                code,
+            } => error.abci_code() == Some((codespace.as_str(), *code)),
+            Self::GrpcCode(code) => error.grpc_code() == Some(*code),
+        }
+    }
+}
+
+impl FromStr for Matcher {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(abci) = s.strip_prefix("abci:") {
+            let Some((codespace, code)) = abci.rsplit_once(':') else {
+                bail!("ABCI error `{s}` is not of the form `abci:<codespace>:<code>`");
+            };
+            let code = code
+                .parse()
+                .wrap_err_with(|| format!("failed to parse the code of ABCI error `{s}`"))?;
+            return Ok(Self::AbciCode {
+                codespace: codespace.to_string(),
+                code,
+            });
+        }
+        if let Some(code) = s.strip_prefix("grpc:") {
+            let code: i32 = code
+                .parse()
+                .wrap_err_with(|| format!("failed to parse the status code of gRPC error `{s}`"))?;
+            let code = tonic::Code::from_i32(code);
+            ensure!(
+                code != tonic::Code::Unknown || s == "grpc:2",
+                "gRPC error `{s}` does not name a known status code"
+            );
+            return Ok(Self::GrpcCode(code));
+        }
+        match TRY_SUBMIT_ERROR_KINDS.iter().find(|kind| **kind == s) {
+            Some(kind) => Ok(Self::Kind(kind)),
+            None => bail!(
+                "`{s}` is neither a kind of error, nor of the form `abci:<codespace>:<code>` or \
+                 `grpc:<code>`"
+            ),
+        }
+    }
+}
+
+/// Classifies the errors returned while submitting to Celestia.
+///
+/// It is constructed from the `ASTRIA_SEQUENCER_RELAYER_CELESTIA_ERROR_CLASSES` env var, a comma
+/// separated list of `<error>=<class>` entries overriding the default classes. Entries are
+/// checked in order and the first one matching an error determines its class.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ErrorPolicy {
+    overrides: Arc<Vec<(Matcher, ErrorClass)>>,
+}
+
+impl ErrorPolicy {
+    /// Parses the overrides of the default classes from `input`.
+    ///
+    /// # Errors
+    /// Returns an error if an entry is malformed or names an unknown kind of error or class.
+    pub(crate) fn parse(input: &str) -> eyre::Result<Self> {
+        let mut overrides = vec![];
+        for entry in input.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let Some((matcher, class)) = entry.rsplit_once('=') else {
+                bail!(
+                    "entry `{entry}` in configured celestia_error_classes is not of the form \
+                     `<error>=<class>`"
+                );
+            };
+            let matcher = matcher.trim().parse().wrap_err_with(|| {
+                format!("failed to parse the error of entry `{entry}` in celestia_error_classes")
+            })?;
+            let class = class.trim().parse().wrap_err_with(|| {
+                format!("failed to parse the class of entry `{entry}` in celestia_error_classes")
+            })?;
+            overrides.push((matcher, class));
+        }
+        Ok(Self {
+            overrides: Arc::new(overrides),
+        })
+    }
+
+    /// Returns the class of `error`: the class of the first override matching it, or its
+    /// default class.
+    pub(in crate::relayer) fn classify(&self, error: &TrySubmitError) -> ErrorClass {
+        self.overrides
+            .iter()
+            .find(|(matcher, _)| matcher.matches(error))
+            .map_or_else(|| Self::default_class(error), |(_, class)| *class)
+    }
+
+    /// Returns the class of `error` if it is not overridden.
+    ///
+    /// Blobs exceeding the size limit can never be submitted. Malformed responses about the
+    /// relayer's account or the node's minimum gas price, rejections for lack of funds or
+    /// authorization, and an unknown account all point at a misconfigured relayer or node. All
+    /// other errors are assumed to be transient.
+    fn default_class(error: &TrySubmitError) -> ErrorClass {
+        match error {
+            TrySubmitError::BlobTooLarge {
+                ..
+            } => return ErrorClass::Fatal,
+            TrySubmitError::MinGasPriceBadSuffix {
+                ..
+            }
+            | TrySubmitError::FailedToParseMinGasPrice {
+                ..
+            }
+            | TrySubmitError::AccountInfoTypeMismatch {
+                ..
+            }
+            | TrySubmitError::DecodeAccountInfo(_) => return ErrorClass::RequiresOperator,
+            _ => {}
+        }
+        if let Some((SDK_CODESPACE, code)) = error.abci_code() {
+            if [UNAUTHORIZED_CODE, INSUFFICIENT_FUNDS_CODE, UNKNOWN_ADDRESS_CODE].contains(&code) {
+                return ErrorClass::RequiresOperator;
+            }
+        }
+        match error.grpc_code() {
+            Some(tonic::Code::Unauthenticated | tonic::Code::PermissionDenied) => {
+                ErrorClass::RequiresOperator
+            }
+            Some(tonic::Code::NotFound) if error.kind() == "failed_to_get_account_info" => {
+                ErrorClass::RequiresOperator
+            }
+            _ => ErrorClass::Retryable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Status;
+
+    use super::*;
+
+    fn broadcast_rejected(namespace: &str, code: u32) -> TrySubmitError {
+        TrySubmitError::BroadcastTxResponseErrorCode {
+            tx_hash: "abc".to_string(),
+            code,
+            namespace: namespace.to_string(),
+            log: String::new(),
+        }
+    }
+
+    #[test]
+    fn default_classes() {
+        let policy = ErrorPolicy::default();
+        assert_eq!(
+            ErrorClass::Fatal,
+            policy.classify(&TrySubmitError::BlobTooLarge {
+                byte_count: 1
+            })
+        );
+        assert_eq!(
+            ErrorClass::RequiresOperator,
+            policy.classify(&broadcast_rejected("sdk", INSUFFICIENT_FUNDS_CODE))
+        );
+        assert_eq!(
+            ErrorClass::RequiresOperator,
+            policy.classify(&TrySubmitError::FailedToGetAccountInfo(
+                Status::not_found("no account").into()
+            ))
+        );
+        assert_eq!(
+            ErrorClass::Retryable,
+            policy.classify(&TrySubmitError::FailedToBroadcastTx(
+                Status::unavailable("down").into()
+            ))
+        );
+        assert_eq!(ErrorClass::Retryable, policy.classify(&broadcast_rejected("sdk", 13)));
+    }
+
+    #[test]
+    fn first_matching_override_wins() {
+        let policy = ErrorPolicy::parse(
+            "abci:sdk:5=retryable, grpc:14=fatal, failed_to_broadcast_tx=requires_operator, \
+             empty_broadcast_tx_response=fatal",
+        )
+        .unwrap();
+        assert_eq!(
+            ErrorClass::Retryable,
+            policy.classify(&broadcast_rejected("sdk", INSUFFICIENT_FUNDS_CODE))
+        );
+        assert_eq!(
+            ErrorClass::Fatal,
+            policy.classify(&TrySubmitError::FailedToBroadcastTx(
+                Status::unavailable("down").into()
+            ))
+        );
+        assert_eq!(
+            ErrorClass::RequiresOperator,
+            policy.classify(&TrySubmitError::FailedToBroadcastTx(
+                Status::internal("oops").into()
+            ))
+        );
+        assert_eq!(ErrorClass::Fatal, policy.classify(&TrySubmitError::EmptyBroadcastTxResponse));
+        // Errors without a matching override keep their default class.
+        assert_eq!(ErrorClass::Retryable, policy.classify(&TrySubmitError::EmptyGetTxResponse));
+    }
+
+    #[test]
+    fn malformed_overrides_are_rejected() {
+        for input in [
+            "blob_too_large",
+            "no_such_error=fatal",
+            "blob_too_large=sometimes",
+            "abci:sdk=fatal",
+            "abci:sdk:five=fatal",
+            "grpc:99=fatal",
+        ] {
+            assert!(ErrorPolicy::parse(input).is_err(), "`{input}` should be rejected");
+        }
+        assert!(ErrorPolicy::parse("").unwrap().overrides.is_empty());
+    }
+}
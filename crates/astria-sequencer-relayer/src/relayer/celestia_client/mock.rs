@@ -0,0 +1,269 @@
+//! An in-memory Celestia app answering the requests of a [`CelestiaClient`], so that the write
+//! path can be tested without network access.
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError,
+    },
+    time::Duration,
+};
+
+use astria_core::generated::{
+    celestia::v1::Params as BlobParams,
+    cosmos::{
+        auth::v1beta1::{
+            BaseAccount,
+            Params as AuthParams,
+        },
+        tx::v1beta1::Tx,
+    },
+    tendermint::types::BlobTx,
+};
+use async_trait::async_trait;
+use prost::Message as _;
+use tendermint::private_key::Secp256k1 as SigningKey;
+use tokio::time::Instant;
+
+use super::{
+    builder::bech32_encode,
+    rpc::CelestiaRpc,
+    Bech32Address,
+    BroadcastTracker,
+    CelestiaClient,
+    CelestiaKeys,
+    ErrorPolicy,
+    LatestHeightCache,
+    SequenceTracker,
+    TrySubmitError,
+    TxHash,
+    TxSigner as _,
+};
+use crate::clock;
+
+/// The Celestia height every transaction is included at.
+pub(in crate::relayer) const INCLUSION_HEIGHT: u64 = 100;
+
+/// The number of Celestia blocks after which the clients of a [`MockCelestiaRpc`] assume a
+/// transaction that was not included to have been evicted.
+pub(in crate::relayer) const MEMPOOL_TTL_BLOCKS: u64 = 4;
+
+/// The maximum age of a stale latest height used by the clients of a [`MockCelestiaRpc`].
+pub(in crate::relayer) const MAX_STALE_LATEST_HEIGHT_AGE: Duration = Duration::from_secs(60);
+
+/// A transaction accepted into the mempool of the [`MockCelestiaRpc`].
+#[derive(Clone, Debug)]
+pub(in crate::relayer) struct RecordedBroadcast {
+    pub(in crate::relayer) sequence: u64,
+    pub(in crate::relayer) fee: u64,
+    pub(in crate::relayer) blob_count: usize,
+}
+
+/// Answers the requests of a [`CelestiaClient`] from memory.
+///
+/// Broadcasts succeed and their transactions are included in a block right away, unless
+/// scripted otherwise using [`MockCelestiaRpc::fail_next_broadcast`] and
+/// [`MockCelestiaRpc::include_next_broadcast_after`]. No new Celestia blocks are produced unless
+/// enabled with [`MockCelestiaRpc::produce_blocks_every`]. Inclusion and block production are
+/// timed on tokio's clock, so tests should pause it.
+#[derive(Debug, Default)]
+pub(in crate::relayer) struct MockCelestiaRpc {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    broadcast_errors: VecDeque<TrySubmitError>,
+    inclusion_delays: VecDeque<Option<Duration>>,
+    broadcasts: Vec<RecordedBroadcast>,
+    // When each broadcast transaction is included, keyed by its hash, or `None` if never.
+    inclusions: HashMap<String, Option<Instant>>,
+    // The balance of the account in utia, or `None` if it is unlimited.
+    balance: Option<u64>,
+    // The height of the latest Celestia block, or `None` if it is `INCLUSION_HEIGHT`.
+    latest_height: Option<u64>,
+    // Since when and how often a new block is produced on top of `latest_height`, if at all.
+    block_production: Option<(Instant, Duration)>,
+    latest_height_errors: VecDeque<TrySubmitError>,
+}
+
+impl MockCelestiaRpc {
+    /// Fails the next broadcast with `error` instead of accepting the transaction.
+    ///
+    /// Errors are returned in the order they were scripted, one per broadcast.
+    pub(in crate::relayer) fn fail_next_broadcast(&self, error: TrySubmitError) {
+        self.lock().broadcast_errors.push_back(error);
+    }
+
+    /// Includes the transaction of the next accepted broadcast `delay` after it was broadcast,
+    /// or never if `delay` is `None`.
+    pub(in crate::relayer) fn include_next_broadcast_after(&self, delay: Option<Duration>) {
+        self.lock().inclusion_delays.push_back(delay);
+    }
+
+    /// Sets the balance of the account to `balance` utia. The balance is unlimited otherwise.
+    pub(in crate::relayer) fn set_balance(&self, balance: u64) {
+        self.lock().balance = Some(balance);
+    }
+
+    /// Sets the height of the latest Celestia block to `height`. It is [`INCLUSION_HEIGHT`]
+    /// otherwise.
+    pub(in crate::relayer) fn set_latest_height(&self, height: u64) {
+        let mut inner = self.lock();
+        inner.latest_height = Some(height);
+        if let Some((since, _)) = &mut inner.block_production {
+            *since = Instant::now();
+        }
+    }
+
+    /// Produces a new Celestia block every `block_time` from now on, on top of the latest height.
+    pub(in crate::relayer) fn produce_blocks_every(&self, block_time: Duration) {
+        let mut inner = self.lock();
+        let latest_height = inner.latest_height();
+        inner.latest_height = Some(latest_height);
+        inner.block_production = Some((Instant::now(), block_time));
+    }
+
+    /// Fails the next request for the latest height with `error`.
+    ///
+    /// Errors are returned in the order they were scripted, one per request.
+    pub(in crate::relayer) fn fail_next_latest_height(&self, error: TrySubmitError) {
+        self.lock().latest_height_errors.push_back(error);
+    }
+
+    /// Returns the transactions accepted so far, in the order they were broadcast.
+    pub(in crate::relayer) fn broadcasts(&self) -> Vec<RecordedBroadcast> {
+        self.lock().broadcasts.clone()
+    }
+
+    /// Returns a client submitting to this Celestia app.
+    pub(in crate::relayer) fn client(
+        self: &Arc<Self>,
+        resubmit_after_blocks: u64,
+        max_resubmissions: u32,
+        error_policy: ErrorPolicy,
+    ) -> CelestiaClient {
+        let signer = CelestiaKeys::from(SigningKey::from_slice(&[1; 32]).unwrap());
+        let address = bech32_encode(&signer.address()).unwrap();
+        CelestiaClient {
+            rpc: self.clone(),
+            signer: Arc::new(signer),
+            address,
+            chain_id: "mock-celestia".to_string(),
+            resubmit_after_blocks,
+            max_resubmissions,
+            mempool_ttl_blocks: MEMPOOL_TTL_BLOCKS,
+            confirmation_depth: 0,
+            max_stale_latest_height_age: Some(MAX_STALE_LATEST_HEIGHT_AGE),
+            error_policy,
+            sequence_tracker: Arc::new(SequenceTracker::default()),
+            broadcast_tracker: Arc::new(BroadcastTracker::default()),
+            latest_height_cache: Arc::new(LatestHeightCache::default()),
+            clock: clock::system(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Inner {
+    fn latest_height(&self) -> u64 {
+        let height = self.latest_height.unwrap_or(INCLUSION_HEIGHT);
+        let Some((since, block_time)) = self.block_production else {
+            return height;
+        };
+        let produced = since.elapsed().as_millis() / block_time.as_millis();
+        height.saturating_add(u64::try_from(produced).unwrap())
+    }
+
+    fn is_included(&self, tx_hash: &str) -> bool {
+        self.inclusions
+            .get(tx_hash)
+            .copied()
+            .flatten()
+            .is_some_and(|included_at| included_at <= Instant::now())
+    }
+}
+
+#[async_trait]
+impl CelestiaRpc for MockCelestiaRpc {
+    async fn fetch_blob_params(&self) -> Result<BlobParams, TrySubmitError> {
+        Ok(BlobParams {
+            gas_per_blob_byte: 8,
+            gov_max_square_size: 64,
+        })
+    }
+
+    async fn fetch_auth_params(&self) -> Result<AuthParams, TrySubmitError> {
+        Ok(AuthParams {
+            tx_size_cost_per_byte: 10,
+            ..AuthParams::default()
+        })
+    }
+
+    async fn fetch_min_gas_price(&self) -> Result<f64, TrySubmitError> {
+        Ok(0.002)
+    }
+
+    /// Returns an account whose sequence is the number of transactions included so far.
+    async fn fetch_account(&self, address: &Bech32Address) -> Result<BaseAccount, TrySubmitError> {
+        let inner = self.lock();
+        let included = inner
+            .inclusions
+            .keys()
+            .filter(|tx_hash| inner.is_included(tx_hash))
+            .count();
+        Ok(BaseAccount {
+            address: address.0.clone(),
+            account_number: 1,
+            sequence: u64::try_from(included).unwrap(),
+            ..BaseAccount::default()
+        })
+    }
+
+    async fn fetch_balance(&self, _address: &Bech32Address) -> Result<u64, TrySubmitError> {
+        Ok(self.lock().balance.unwrap_or(u64::MAX))
+    }
+
+    async fn broadcast_tx(&self, blob_tx: BlobTx) -> Result<TxHash, TrySubmitError> {
+        let mut inner = self.lock();
+        if let Some(error) = inner.broadcast_errors.pop_front() {
+            return Err(error);
+        }
+        let tx = Tx::decode(blob_tx.tx).unwrap();
+        let auth_info = tx.auth_info.unwrap();
+        inner.broadcasts.push(RecordedBroadcast {
+            sequence: auth_info.signer_infos[0].sequence,
+            fee: auth_info.fee.unwrap().amount[0].amount.parse().unwrap(),
+            blob_count: blob_tx.blobs.len(),
+        });
+        let tx_hash = format!("{:064X}", inner.broadcasts.len());
+        let delay = inner.inclusion_delays.pop_front().unwrap_or(Some(Duration::ZERO));
+        inner
+            .inclusions
+            .insert(tx_hash.clone(), delay.map(|delay| Instant::now() + delay));
+        Ok(TxHash(tx_hash))
+    }
+
+    async fn get_tx(&self, tx_hash: TxHash) -> Result<Option<u64>, TrySubmitError> {
+        Ok(self
+            .lock()
+            .is_included(&tx_hash.0)
+            .then_some(INCLUSION_HEIGHT))
+    }
+
+    async fn fetch_latest_height(&self) -> Result<u64, TrySubmitError> {
+        let mut inner = self.lock();
+        if let Some(error) = inner.latest_height_errors.pop_front() {
+            return Err(error);
+        }
+        Ok(inner.latest_height())
+    }
+}
@@ -2,12 +2,24 @@ mod builder;
 mod celestia_cost_params;
 pub(crate) mod celestia_keys;
 mod error;
+mod error_policy;
+#[cfg(test)]
+pub(super) mod mock;
+mod rpc;
 #[cfg(test)]
 mod tests;
 
 use std::{
+    collections::{
+        BTreeSet,
+        HashMap,
+    },
     convert::TryInto,
-    sync::Arc,
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError,
+    },
     time::{
         Duration,
         Instant,
@@ -15,27 +27,16 @@ use std::{
 };
 
 use astria_core::generated::{
-    celestia::v1::{
-        query_client::QueryClient as BlobQueryClient,
-        MsgPayForBlobs,
-        Params as BlobParams,
-        QueryParamsRequest as QueryBlobParamsRequest,
-    },
+    celestia::v1::MsgPayForBlobs,
     cosmos::{
         auth::v1beta1::{
-            query_client::QueryClient as AuthQueryClient,
             BaseAccount,
-            Params as AuthParams,
-            QueryAccountRequest,
             QueryAccountResponse,
-            QueryParamsRequest as QueryAuthParamsRequest,
         },
+        bank::v1beta1::QueryBalanceResponse,
         base::{
-            node::v1beta1::{
-                service_client::ServiceClient as MinGasPriceClient,
-                ConfigRequest as MinGasPriceRequest,
-                ConfigResponse as MinGasPriceResponse,
-            },
+            node::v1beta1::ConfigResponse as MinGasPriceResponse,
+            tendermint::v1beta1::GetLatestBlockResponse,
             v1beta1::Coin,
         },
         crypto::secp256k1,
@@ -44,13 +45,9 @@ use astria_core::generated::{
                 Single,
                 Sum,
             },
-            service_client::ServiceClient as TxClient,
             AuthInfo,
-            BroadcastMode,
-            BroadcastTxRequest,
             BroadcastTxResponse,
             Fee,
-            GetTxRequest,
             GetTxResponse,
             ModeInfo,
             SignDoc,
@@ -70,21 +67,31 @@ pub(super) use builder::{
     BuilderError,
 };
 use celestia_cost_params::CelestiaCostParams;
-pub(crate) use celestia_keys::CelestiaKeys;
+pub(crate) use celestia_keys::{
+    CelestiaKeys,
+    TxSigner,
+};
 use celestia_types::Blob;
 pub(super) use error::{
     GrpcResponseError,
     ProtobufDecodeError,
     TrySubmitError,
 };
+pub(crate) use error_policy::{
+    ErrorClass,
+    ErrorPolicy,
+};
 use prost::{
     bytes::Bytes,
     Message as _,
     Name as _,
 };
+use rpc::{
+    CelestiaRpc,
+    GrpcCelestiaRpc,
+};
 use tokio::sync::watch;
 use tonic::{
-    transport::Channel,
     Response,
     Status,
 };
@@ -95,46 +102,497 @@ use tracing::{
     warn,
 };
 
+use super::submission::IdempotencyKey;
+use crate::clock::Clock;
+
 // From https://github.com/celestiaorg/cosmos-sdk/blob/v1.18.3-sdk-v0.46.14/types/errors/errors.go#L75
 const INSUFFICIENT_FEE_CODE: u32 = 13;
 
+// From https://github.com/celestiaorg/celestia-app/blob/v1.4.0/pkg/appconsts/global_consts.go#L76
+const FEE_DENOM: &str = "utia";
+
+/// How many times [`CelestiaClient::get_latest_height`] requests the latest height before falling
+/// back to the cached one.
+const LATEST_HEIGHT_ATTEMPTS: u32 = 3;
+
+/// How long [`CelestiaClient::get_latest_height`] waits after the first failed request; the delay
+/// doubles after every further failure.
+const LATEST_HEIGHT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Hands out account sequence numbers to concurrent submissions from the same account.
+///
+/// The sequence number of the account queried from the Celestia app only accounts for committed
+/// transactions. If several submissions are in flight at the same time, each of them has to use
+/// the sequence number following the ones still waiting in the mempool.
+///
+/// The tracker remembers the sequence numbers of all transactions still in flight. The next
+/// transaction uses the lowest sequence number at or above the committed one that is not in
+/// flight, so that a transaction that never made it into the mempool does not leave a gap before
+/// all following ones: its sequence number is handed out again to the next submission.
+#[derive(Debug, Default)]
+struct SequenceTracker {
+    in_flight: Mutex<BTreeSet<u64>>,
+}
+
+impl SequenceTracker {
+    /// Reserves the sequence number to use for the next transaction, given the sequence number of
+    /// the account as committed on Celestia.
+    ///
+    /// The sequence number is held until the returned reservation is dropped, which must not
+    /// happen before the transaction was either included or is known to not be in the mempool.
+    /// If neither is known yet, the reservation must be kept with [`SequenceReservation::keep`]
+    /// and the sequence number released once the transaction was evicted.
+    fn reserve(&self, committed_sequence: u64) -> SequenceReservation<'_> {
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Sequence numbers below the committed one were used by included transactions.
+        in_flight.retain(|sequence| *sequence >= committed_sequence);
+        // The in-flight sequence numbers are sorted, so the first one not matching its position
+        // marks the lowest free sequence number.
+        let mut sequence = committed_sequence;
+        for in_flight_sequence in &*in_flight {
+            if *in_flight_sequence != sequence {
+                break;
+            }
+            sequence = sequence.saturating_add(1);
+        }
+        in_flight.insert(sequence);
+        SequenceReservation {
+            tracker: self,
+            sequence,
+            kept: false,
+        }
+    }
+
+    fn release(&self, sequence: u64) {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&sequence);
+    }
+}
+
+/// A sequence number handed out by [`SequenceTracker::reserve`], released when dropped unless
+/// it was kept.
+#[derive(Debug)]
+struct SequenceReservation<'a> {
+    tracker: &'a SequenceTracker,
+    sequence: u64,
+    kept: bool,
+}
+
+impl SequenceReservation<'_> {
+    fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Keeps the sequence number reserved past the end of this reservation, until it is released
+    /// explicitly using [`SequenceTracker::release`].
+    fn keep(mut self) -> u64 {
+        self.kept = true;
+        self.sequence
+    }
+}
+
+impl Drop for SequenceReservation<'_> {
+    fn drop(&mut self) {
+        if !self.kept {
+            self.tracker.release(self.sequence);
+        }
+    }
+}
+
+/// Remembers the transactions broadcast for each submission, keyed by its idempotency key.
+///
+/// A transaction that was not confirmed in time can still be included in a later Celestia block.
+/// Before a submission is attempted again, the transactions broadcast by its earlier attempts
+/// are looked up so that the blobs are not paid for twice.
+#[derive(Debug, Default)]
+struct BroadcastTracker {
+    broadcasts: Mutex<HashMap<IdempotencyKey, Vec<Broadcast>>>,
+}
+
+/// A blob transaction broadcast to the Celestia app.
+#[derive(Clone, Debug)]
+struct Broadcast {
+    tx_hash: TxHash,
+    fee: u64,
+    gas_price: f64,
+    /// The sequence number of the transaction if it was given up on without being included and
+    /// might still be in the mempool, or `None` otherwise.
+    held_sequence: Option<HeldSequence>,
+}
+
+/// The sequence number of a transaction given up on, held until the transaction was evicted from
+/// the mempool of the Celestia app.
+#[derive(Clone, Copy, Debug)]
+struct HeldSequence {
+    sequence: u64,
+    /// The Celestia height from which on the transaction is assumed to have been evicted.
+    evicted_at_height: u64,
+}
+
+impl BroadcastTracker {
+    fn record(&self, key: IdempotencyKey, broadcast: Broadcast) {
+        self.broadcasts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key)
+            .or_default()
+            .push(broadcast);
+    }
+
+    fn get(&self, key: &IdempotencyKey) -> Vec<Broadcast> {
+        self.broadcasts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Records that the transaction `tx_hash` of the submission `key` was given up on while
+    /// holding the sequence number `held`.
+    fn hold_sequence(&self, key: &IdempotencyKey, tx_hash: &TxHash, held: HeldSequence) {
+        self.set_held_sequence(key, tx_hash, Some(held));
+    }
+
+    /// Records that the transaction `tx_hash` of the submission `key` no longer holds its
+    /// sequence number.
+    fn release_sequence(&self, key: &IdempotencyKey, tx_hash: &TxHash) {
+        self.set_held_sequence(key, tx_hash, None);
+    }
+
+    fn set_held_sequence(
+        &self,
+        key: &IdempotencyKey,
+        tx_hash: &TxHash,
+        held_sequence: Option<HeldSequence>,
+    ) {
+        if let Some(broadcast) = self
+            .broadcasts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(key)
+            .and_then(|broadcasts| {
+                broadcasts
+                    .iter_mut()
+                    .find(|broadcast| broadcast.tx_hash.0 == tx_hash.0)
+            })
+        {
+            broadcast.held_sequence = held_sequence;
+        }
+    }
+
+    /// Forgets the transactions broadcast for the submission `key`, returning them.
+    fn forget(&self, key: &IdempotencyKey) -> Vec<Broadcast> {
+        self.broadcasts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key)
+            .unwrap_or_default()
+    }
+}
+
+/// The height of the latest Celestia block fetched from the Celestia app, shared between all
+/// clones of a client.
+#[derive(Debug, Default)]
+struct LatestHeightCache {
+    last: Mutex<Option<(u64, Instant)>>,
+}
+
+impl LatestHeightCache {
+    fn record(&self, height: u64, fetched_at: Instant) {
+        *self.last.lock().unwrap_or_else(PoisonError::into_inner) = Some((height, fetched_at));
+    }
+
+    fn get(&self) -> Option<(u64, Instant)> {
+        *self.last.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The height of the latest Celestia block, as returned by
+/// [`CelestiaClient::get_latest_height`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum LatestHeight {
+    /// The height was just fetched from the Celestia app.
+    Fresh(u64),
+    /// The Celestia app could not be reached, and the height is the last one fetched from it,
+    /// `age` ago.
+    ///
+    /// Celestia heights only grow, so the actual latest height is at least this height.
+    Stale { height: u64, age: Duration },
+}
+
+impl LatestHeight {
+    pub(super) fn height(self) -> u64 {
+        match self {
+            Self::Fresh(height)
+            | Self::Stale {
+                height, ..
+            } => height,
+        }
+    }
+
+    pub(super) fn is_fresh(self) -> bool {
+        matches!(self, Self::Fresh(_))
+    }
+}
+
+/// A submission that was included in a Celestia block.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Inclusion {
+    /// The Celestia height the submission was included at.
+    pub(super) height: u64,
+    /// The fee paid for the submission in utia.
+    pub(super) fee: u64,
+    /// The fee paid per unit of gas in utia.
+    pub(super) gas_price: f64,
+}
+
+/// The funds of our Celestia account, compared to the fee currently paid for a submission.
+#[derive(Clone, Debug)]
+pub(crate) struct Funds {
+    /// The Bech32-encoded address of our Celestia account.
+    pub(crate) address: String,
+    /// The account number of our Celestia account.
+    pub(crate) account_number: u64,
+    /// The balance of our Celestia account in utia.
+    pub(crate) balance: u64,
+    /// The minimum gas price accepted by the Celestia app in utia.
+    pub(crate) min_gas_price: f64,
+    /// The gas limit of the submission.
+    pub(crate) gas_limit: u64,
+    /// The fee of the submission in utia.
+    pub(crate) fee: u64,
+}
+
+impl Funds {
+    /// Returns if the balance pays for the fee of the submission.
+    pub(crate) fn are_sufficient(&self) -> bool {
+        self.balance >= self.fee
+    }
+}
+
 /// A client using the gRPC interface of a remote Celestia app to submit blob data to the Celestia
 /// chain.
 ///
 /// It is constructed using a [`CelestiaClientBuilder`].
 #[derive(Debug, Clone)]
 pub(super) struct CelestiaClient {
-    /// The inner `tonic` gRPC channel shared by the various generated gRPC clients.
-    grpc_channel: Channel,
-    /// A gRPC client to broadcast and get transactions.
-    tx_client: TxClient<Channel>,
-    /// The crypto keys associated with our Celestia account.
-    signing_keys: CelestiaKeys,
+    /// The requests made to the Celestia app.
+    rpc: Arc<dyn CelestiaRpc>,
+    /// Signs the transactions paying for the blobs from our Celestia account.
+    signer: Arc<dyn TxSigner>,
     /// The Bech32-encoded address of our Celestia account.
     address: Bech32Address,
     /// The Celestia network ID.
     chain_id: String,
+    /// The number of Celestia blocks produced after a transaction was broadcast without it being
+    /// included before it is considered lost and resubmitted.
+    resubmit_after_blocks: u64,
+    /// The maximum number of times a submission is resubmitted after it was not confirmed within
+    /// `resubmit_after_blocks`.
+    max_resubmissions: u32,
+    /// The number of Celestia blocks produced after a transaction was broadcast after which it
+    /// is assumed to have been evicted from the mempool of the Celestia app if it was not
+    /// included.
+    mempool_ttl_blocks: u64,
+    /// The number of Celestia blocks that must be produced on top of the block a submission was
+    /// included in before the submission is treated as firm.
+    confirmation_depth: u64,
+    /// The maximum age of the cached latest height returned by
+    /// [`CelestiaClient::get_latest_height`] while the Celestia app cannot be reached, or `None`
+    /// if the error is returned instead.
+    max_stale_latest_height_age: Option<Duration>,
+    /// Classifies the errors returned while submitting.
+    error_policy: ErrorPolicy,
+    /// The account sequence numbers handed out to submissions, shared between all clones of
+    /// this client.
+    sequence_tracker: Arc<SequenceTracker>,
+    /// The transactions broadcast for submissions that were not yet confirmed, shared between
+    /// all clones of this client.
+    broadcast_tracker: Arc<BroadcastTracker>,
+    /// The latest height last fetched from the Celestia app, shared between all clones of this
+    /// client.
+    latest_height_cache: Arc<LatestHeightCache>,
+    /// The clock that confirmations are polled on.
+    clock: Arc<dyn Clock>,
 }
 
 impl CelestiaClient {
+    pub(super) fn max_resubmissions(&self) -> u32 {
+        self.max_resubmissions
+    }
+
+    pub(super) fn error_policy(&self) -> &ErrorPolicy {
+        &self.error_policy
+    }
+
+    pub(super) fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    /// Returns the funds of our Celestia account, together with the fee currently paid for a
+    /// submission of blobs whose data are `blob_sizes` bytes long.
+    ///
+    /// Nothing is broadcast. Fails if our account does not exist on Celestia.
+    pub(super) async fn check_funds(&self, blob_sizes: &[u32]) -> Result<Funds, TrySubmitError> {
+        let (blob_params, auth_params, min_gas_price, base_account, balance) = tokio::try_join!(
+            self.rpc.fetch_blob_params(),
+            self.rpc.fetch_auth_params(),
+            self.rpc.fetch_min_gas_price(),
+            self.rpc.fetch_account(&self.address),
+            self.rpc.fetch_balance(&self.address),
+        )?;
+        let cost_params = CelestiaCostParams::new(
+            blob_params.gas_per_blob_byte,
+            auth_params.tx_size_cost_per_byte,
+            min_gas_price,
+        );
+        let gas_limit = estimate_gas(blob_sizes, cost_params);
+        let fee = calculate_fee(cost_params, gas_limit, None);
+        Ok(Funds {
+            address: self.address.0.clone(),
+            account_number: base_account.account_number,
+            balance,
+            min_gas_price,
+            gas_limit: gas_limit.0,
+            fee,
+        })
+    }
+
+    /// Returns the height of the latest Celestia block.
+    ///
+    /// Requests failing with a retryable error are repeated with a backoff, up to
+    /// `LATEST_HEIGHT_ATTEMPTS` requests in total. If none of them succeeds, the height last
+    /// fetched is returned as [`LatestHeight::Stale`] as long as it is at most
+    /// `max_stale_latest_height_age` old, so that a briefly unreachable Celestia app does not
+    /// fail the caller. Otherwise, or if no maximum age is configured, the error of the last
+    /// request is returned.
+    pub(super) async fn get_latest_height(&self) -> Result<LatestHeight, TrySubmitError> {
+        let mut delay = LATEST_HEIGHT_RETRY_DELAY;
+        let mut attempt = 1;
+        let error = loop {
+            match self.rpc.fetch_latest_height().await {
+                Ok(height) => {
+                    self.latest_height_cache.record(height, self.clock.now());
+                    return Ok(LatestHeight::Fresh(height));
+                }
+                Err(error)
+                    if attempt < LATEST_HEIGHT_ATTEMPTS
+                        && self.error_policy.classify(&error) == ErrorClass::Retryable =>
+                {
+                    let error = Report::new(error);
+                    debug!(
+                        %error,
+                        attempt,
+                        retry_in_ms = delay.as_millis(),
+                        "failed to fetch the latest celestia height; retrying"
+                    );
+                    self.clock.sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                Err(error) => break error,
+            }
+        };
+        if let (Some(max_age), Some((height, fetched_at))) = (
+            self.max_stale_latest_height_age,
+            self.latest_height_cache.get(),
+        ) {
+            let age = self.clock.now().saturating_duration_since(fetched_at);
+            if age <= max_age {
+                let error = Report::new(error);
+                warn!(
+                    %error,
+                    height,
+                    age_seconds = age.as_secs_f32(),
+                    "failed to fetch the latest celestia height; using the last fetched height"
+                );
+                return Ok(LatestHeight::Stale {
+                    height,
+                    age,
+                });
+            }
+        }
+        Err(error)
+    }
+
+    /// Forgets the transactions broadcast for the submission `idempotency_key`, releasing the
+    /// sequence numbers still held by any of them.
+    ///
+    /// Must be called once the submission was confirmed or abandoned.
+    pub(super) fn forget_broadcasts(&self, idempotency_key: &IdempotencyKey) {
+        for broadcast in self.broadcast_tracker.forget(idempotency_key) {
+            if let Some(held) = broadcast.held_sequence {
+                self.sequence_tracker.release(held.sequence);
+            }
+        }
+    }
+
     /// Tries to submit the given blobs to the Celestia app.
     ///
     /// The `last_error_receiver` will provide the error from the previous attempt if this is not
     /// the first attempt for these blobs, or `None` if it is the first attempt.  The error can be
     /// used to obtain the appropriate fee in the case that the previous attempt failed due to a
-    /// low fee.
+    /// low fee, or to bump the fee if the previous attempt was never confirmed.
+    ///
+    /// If `may_resubmit` is set, the broadcast transaction is only awaited until
+    /// `resubmit_after_blocks` Celestia blocks were produced after it was broadcast. If it was not
+    /// included in a Celestia block by then,
+    /// [`TrySubmitError::SubmissionNotConfirmed`] is returned so that the caller can
+    /// resubmit the blobs. Otherwise the transaction is awaited indefinitely.
+    ///
+    /// All attempts to submit the same blobs must pass the same `idempotency_key`. If a
+    /// transaction broadcast by an earlier attempt was included in the meantime, its inclusion
+    /// is returned without broadcasting a new transaction. A transaction given up on by an
+    /// earlier attempt keeps its sequence number reserved and is awaited until it is either
+    /// included or evicted from the mempool, see [`CelestiaClient::await_earlier_broadcasts`].
+    ///
+    /// The fee is raised by `fee_bump_percent` on top of the fee determined otherwise, which
+    /// escalates submissions containing sequencer blocks past their deadline. Resubmissions of
+    /// transactions that were never included are raised the same way.
+    ///
+    /// Once the transaction was included, this only returns after `confirmation_depth` Celestia
+    /// blocks were produced on top of the inclusion height.
     // Copied from https://github.com/celestiaorg/celestia-app/blob/v1.4.0/x/blob/payforblob.go
     pub(super) async fn try_submit(
-        mut self,
+        self,
         blobs: Arc<Vec<Blob>>,
+        idempotency_key: IdempotencyKey,
         last_error_receiver: watch::Receiver<Option<TrySubmitError>>,
-    ) -> Result<u64, TrySubmitError> {
+        may_resubmit: bool,
+        fee_bump_percent: u32,
+    ) -> Result<Inclusion, TrySubmitError> {
+        if let Some(inclusion) = self.await_earlier_broadcasts(&idempotency_key).await? {
+            info!(
+                %idempotency_key,
+                height = inclusion.height,
+                "a transaction broadcast by an earlier attempt of this submission was included \
+                 on celestia; not submitting again"
+            );
+            self.wait_for_confirmation_depth(inclusion.height).await;
+            return Ok(inclusion);
+        }
+
+        // The height the resubmission timeout and the mempool eviction are measured from. It is
+        // fetched before broadcasting so that it cannot be later than the actual height the
+        // transaction entered the mempool at.
+        let broadcast_height = if may_resubmit {
+            Some(self.get_latest_height().await?.height())
+        } else {
+            None
+        };
+
         info!("fetching cost params and account info from celestia app");
         let (blob_params, auth_params, min_gas_price, base_account) = tokio::try_join!(
-            self.fetch_blob_params(),
-            self.fetch_auth_params(),
-            self.fetch_min_gas_price(),
-            self.fetch_account(),
+            self.rpc.fetch_blob_params(),
+            self.rpc.fetch_auth_params(),
+            self.rpc.fetch_min_gas_price(),
+            self.rpc.fetch_account(&self.address),
         )?;
 
         let gas_per_blob_byte = blob_params.gas_per_blob_byte;
@@ -155,7 +613,29 @@ impl CelestiaClient {
         let gas_limit = estimate_gas(&msg_pay_for_blobs.blob_sizes, cost_params);
         // Get the error from the last attempt to `try_submit`.
         let maybe_last_error = last_error_receiver.borrow().clone();
-        let fee = calculate_fee(cost_params, gas_limit, maybe_last_error);
+        let fee = escalate_fee(
+            calculate_fee(cost_params, gas_limit, maybe_last_error),
+            fee_bump_percent,
+        );
+        // allow: the fee and gas limit are only used to compute a gas price for tuning the
+        // batching, for which the precision of an f64 is more than sufficient
+        #[allow(clippy::cast_precision_loss)]
+        let gas_price = fee as f64 / gas_limit.0 as f64;
+
+        // The queried account only reflects committed transactions. Other submissions of this
+        // client might still be waiting in the mempool, so their sequence numbers are skipped.
+        let reservation = self.sequence_tracker.reserve(base_account.sequence);
+        let sequence = reservation.sequence();
+        if sequence != base_account.sequence {
+            info!(
+                committed_sequence = base_account.sequence,
+                sequence, "using sequence number following other in-flight submissions"
+            );
+        }
+        let base_account = BaseAccount {
+            sequence,
+            ..base_account
+        };
 
         let signed_tx = new_signed_tx(
             &msg_pay_for_blobs,
@@ -163,7 +643,7 @@ impl CelestiaClient {
             gas_limit,
             fee,
             self.chain_id.clone(),
-            &self.signing_keys,
+            &*self.signer,
         );
 
         let blob_tx = new_blob_tx(&signed_tx, blobs.iter());
@@ -173,110 +653,125 @@ impl CelestiaClient {
             fee_utia = fee,
             "broadcasting blob transaction to celestia app"
         );
-        let tx_hash = self.broadcast_tx(blob_tx).await?;
+        let tx_hash = self.rpc.broadcast_tx(blob_tx).await?;
         info!(tx_hash = %tx_hash.0, "broadcast blob transaction succeeded");
+        self.broadcast_tracker.record(
+            idempotency_key,
+            Broadcast {
+                tx_hash: tx_hash.clone(),
+                fee,
+                gas_price,
+                held_sequence: None,
+            },
+        );
 
-        let height = self.confirm_submission(tx_hash).await;
-        Ok(height)
-    }
-
-    async fn fetch_account(&self) -> Result<BaseAccount, TrySubmitError> {
-        let mut auth_query_client = AuthQueryClient::new(self.grpc_channel.clone());
-        let request = QueryAccountRequest {
-            address: self.address.0.clone(),
+        let give_up_height =
+            broadcast_height.map(|height| height.saturating_add(self.resubmit_after_blocks));
+        metrics::gauge!(crate::metrics_init::UNCONFIRMED_CELESTIA_SUBMISSIONS).increment(1);
+        let maybe_height = self
+            .confirm_submission(tx_hash.clone(), give_up_height)
+            .await;
+        metrics::gauge!(crate::metrics_init::UNCONFIRMED_CELESTIA_SUBMISSIONS).decrement(1);
+        let Some(height) = maybe_height else {
+            // The transaction might still be in the mempool, where Cosmos SDK chains do not
+            // replace it with one using the same sequence number. So the sequence number stays
+            // reserved until the transaction is assumed to have been evicted.
+            if let Some(broadcast_height) = broadcast_height {
+                let held = HeldSequence {
+                    sequence: reservation.keep(),
+                    evicted_at_height: broadcast_height.saturating_add(self.mempool_ttl_blocks),
+                };
+                self.broadcast_tracker
+                    .hold_sequence(&idempotency_key, &tx_hash, held);
+            }
+            return Err(TrySubmitError::SubmissionNotConfirmed {
+                tx_hash: tx_hash.0,
+                fee,
+                timeout_blocks: self.resubmit_after_blocks,
+            });
         };
-        let response = auth_query_client.account(request).await;
-        // trace-level logging, so using Debug format is ok.
-        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
-        {
-            trace!(?response);
-        }
-        account_from_response(response)
-    }
-
-    async fn fetch_blob_params(&self) -> Result<BlobParams, TrySubmitError> {
-        let mut blob_query_client = BlobQueryClient::new(self.grpc_channel.clone());
-        let response = blob_query_client.params(QueryBlobParamsRequest {}).await;
-        // trace-level logging, so using Debug format is ok.
-        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
-        {
-            trace!(?response);
-        }
-        response
-            .map_err(|status| {
-                TrySubmitError::FailedToGetBlobParams(GrpcResponseError::from(status))
-            })?
-            .into_inner()
-            .params
-            .ok_or_else(|| TrySubmitError::EmptyBlobParams)
-    }
-
-    async fn fetch_auth_params(&self) -> Result<AuthParams, TrySubmitError> {
-        let mut auth_query_client = AuthQueryClient::new(self.grpc_channel.clone());
-        let response = auth_query_client.params(QueryAuthParamsRequest {}).await;
-        // trace-level logging, so using Debug format is ok.
-        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
-        {
-            trace!(?response);
-        }
-        response
-            .map_err(|status| {
-                TrySubmitError::FailedToGetAuthParams(GrpcResponseError::from(status))
-            })?
-            .into_inner()
-            .params
-            .ok_or_else(|| TrySubmitError::EmptyAuthParams)
-    }
-
-    async fn fetch_min_gas_price(&self) -> Result<f64, TrySubmitError> {
-        let mut min_gas_price_client = MinGasPriceClient::new(self.grpc_channel.clone());
-        let response = min_gas_price_client.config(MinGasPriceRequest {}).await;
-        // trace-level logging, so using Debug format is ok.
-        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
-        {
-            trace!(?response);
-        }
-        min_gas_price_from_response(response)
+        let inclusion = Inclusion {
+            height,
+            fee,
+            gas_price,
+        };
+        self.wait_for_confirmation_depth(inclusion.height).await;
+        Ok(inclusion)
     }
 
-    /// Returns the tx hash if the tx is successfully placed into the node's mempool.
+    /// Returns the inclusion of a transaction broadcast by an earlier attempt of the submission
+    /// `idempotency_key` if one was included, or `None` if none was.
     ///
-    /// Note, we use `BroadcastTxSync`, i.e. `BroadcastMode::Sync` as recommended by
-    /// [`CometBFT`][cometbft].
+    /// Transactions given up on by earlier attempts might still be in the mempool of the
+    /// Celestia app. Since Cosmos SDK chains do not replace transactions in the mempool, a new
+    /// transaction using the same sequence number would be rejected until they are gone. Each of
+    /// them is therefore awaited until it is either included or evicted, and only then is its
+    /// sequence number released.
     ///
-    /// [cometbft]: https://github.com/cometbft/cometbft/blob/b139e139ad9ae6fccb9682aa5c2de4aa952fd055/rpc/openapi/openapi.yaml#L201-L204
-    async fn broadcast_tx(&mut self, blob_tx: BlobTx) -> Result<TxHash, TrySubmitError> {
-        let request = BroadcastTxRequest {
-            tx_bytes: Bytes::from(blob_tx.encode_to_vec()),
-            mode: i32::from(BroadcastMode::Sync),
-        };
-        let response = self.tx_client.broadcast_tx(request).await;
-        // trace-level logging, so using Debug format is ok.
-        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
-        {
-            trace!(?response);
-        }
-        tx_hash_from_response(response)
-    }
-
-    /// Returns `Some(height)` if the tx submission has completed, or `None` if it is still
-    /// pending.
-    async fn get_tx(&mut self, tx_hash: TxHash) -> Result<Option<u64>, TrySubmitError> {
-        let request = GetTxRequest {
-            hash: tx_hash.0.clone(),
-        };
-        let response = self.tx_client.get_tx(request).await;
-        // trace-level logging, so using Debug format is ok.
-        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+    /// There is no request telling whether a transaction is still in the mempool. Instead, the
+    /// Celestia app is assumed to evict transactions once `mempool_ttl_blocks` Celestia blocks
+    /// were produced after they were broadcast, as configured by the `ttl-num-blocks` setting of
+    /// its mempool.
+    async fn await_earlier_broadcasts(
+        &self,
+        idempotency_key: &IdempotencyKey,
+    ) -> Result<Option<Inclusion>, TrySubmitError> {
+        for Broadcast {
+            tx_hash,
+            fee,
+            gas_price,
+            held_sequence,
+        } in self.broadcast_tracker.get(idempotency_key)
         {
-            trace!(?response);
+            let inclusion = |height| Inclusion {
+                height,
+                fee,
+                gas_price,
+            };
+            if let Some(height) = self.rpc.get_tx(tx_hash.clone()).await? {
+                return Ok(Some(inclusion(height)));
+            }
+            let Some(held) = held_sequence else {
+                continue;
+            };
+            info!(
+                tx_hash = tx_hash.0,
+                sequence = held.sequence,
+                evicted_at_height = held.evicted_at_height,
+                "waiting for a transaction given up on by an earlier attempt of this submission \
+                 to be included or evicted before submitting again"
+            );
+            if let Some(height) = self
+                .confirm_submission(tx_hash.clone(), Some(held.evicted_at_height))
+                .await
+            {
+                return Ok(Some(inclusion(height)));
+            }
+            info!(
+                tx_hash = tx_hash.0,
+                sequence = held.sequence,
+                "transaction given up on by an earlier attempt of this submission is assumed to \
+                 have been evicted; releasing its sequence number"
+            );
+            self.broadcast_tracker
+                .release_sequence(idempotency_key, &tx_hash);
+            self.sequence_tracker.release(held.sequence);
         }
-        block_height_from_response(response)
+        Ok(None)
     }
 
     /// Repeatedly sends `GetTx` until a successful response is received.  Returns the height of the
     /// Celestia block in which the blobs were submitted.
-    async fn confirm_submission(&mut self, tx_hash: TxHash) -> u64 {
+    ///
+    /// Returns `None` if `give_up_height` is set and the latest Celestia height reached it without
+    /// the transaction being confirmed. The timeout is measured in Celestia heights rather than
+    /// wall-clock time, so that slow block production does not lead to resubmitting transactions
+    /// that are merely waiting for the next block.
+    async fn confirm_submission(
+        &self,
+        tx_hash: TxHash,
+        give_up_height: Option<u64>,
+    ) -> Option<u64> {
         // The min seconds to sleep after receiving a GetTx response and sending the next request.
         const MIN_POLL_INTERVAL_SECS: u64 = 1;
         // The max seconds to sleep after receiving a GetTx response and sending the next request.
@@ -286,28 +781,56 @@ impl CelestiaClient {
         // The minimum duration between logging errors.
         const LOG_ERROR_INTERVAL: Duration = Duration::from_secs(5);
 
-        let start = Instant::now();
+        let clock = self.clock.clone();
+        let start = clock.now();
         let mut logged_at = start;
+        let elapsed_since = |earlier| clock.now().saturating_duration_since(earlier);
 
         let mut log_if_due = |maybe_error: Option<TrySubmitError>| {
-            if start.elapsed() <= START_LOGGING_DELAY || logged_at.elapsed() <= LOG_ERROR_INTERVAL {
+            if elapsed_since(start) <= START_LOGGING_DELAY
+                || elapsed_since(logged_at) <= LOG_ERROR_INTERVAL
+            {
                 return;
             }
             let reason = maybe_error.map_or(Report::msg("transaction still pending"), Report::new);
             warn!(
                 %reason,
                 tx_hash = tx_hash.0,
-                elapsed_seconds = start.elapsed().as_secs_f32(),
+                elapsed_seconds = elapsed_since(start).as_secs_f32(),
                 "waiting to confirm blob submission"
             );
-            logged_at = Instant::now();
+            logged_at = clock.now();
         };
 
         let mut sleep_secs = MIN_POLL_INTERVAL_SECS;
         loop {
-            tokio::time::sleep(Duration::from_secs(sleep_secs)).await;
-            match self.get_tx(tx_hash.clone()).await {
-                Ok(Some(height)) => return height,
+            if let Some(give_up_height) = give_up_height {
+                match self.get_latest_height().await {
+                    Ok(latest) => {
+                        let latest_height = latest.height();
+                        if latest_height >= give_up_height {
+                            // The transaction might have been included in the block that was
+                            // just produced, so look it up once more before giving up on it.
+                            if let Ok(Some(height)) = self.rpc.get_tx(tx_hash.clone()).await {
+                                return Some(height);
+                            }
+                            warn!(
+                                tx_hash = tx_hash.0,
+                                give_up_height,
+                                latest_height,
+                                elapsed_seconds = elapsed_since(start).as_secs_f32(),
+                                "blob transaction was not confirmed in time; giving up waiting for \
+                                 it"
+                            );
+                            return None;
+                        }
+                    }
+                    Err(error) => log_if_due(Some(error)),
+                }
+            }
+            clock.sleep(Duration::from_secs(sleep_secs)).await;
+            match self.rpc.get_tx(tx_hash.clone()).await {
+                Ok(Some(height)) => return Some(height),
                 Ok(None) => {
                     sleep_secs = MIN_POLL_INTERVAL_SECS;
                     log_if_due(None);
@@ -320,6 +843,55 @@ impl CelestiaClient {
             }
         }
     }
+
+    /// Waits until `confirmation_depth` Celestia blocks were produced on top of
+    /// `inclusion_height`.
+    ///
+    /// Returns immediately if the confirmation depth is zero. A stale latest height is used as
+    /// long as it is available, since the actual latest height can only be higher. Failures to
+    /// fetch the latest height are logged and the request is repeated.
+    async fn wait_for_confirmation_depth(&self, inclusion_height: u64) {
+        // How long to sleep between two requests for the latest height.
+        const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+        if self.confirmation_depth == 0 {
+            return;
+        }
+        let firm_height = inclusion_height.saturating_add(self.confirmation_depth);
+        loop {
+            match self.get_latest_height().await {
+                Ok(latest_height) if latest_height.height() >= firm_height => {
+                    debug!(
+                        inclusion_height,
+                        latest_height = latest_height.height(),
+                        latest_height_is_fresh = latest_height.is_fresh(),
+                        confirmation_depth = self.confirmation_depth,
+                        "submission reached the confirmation depth"
+                    );
+                    return;
+                }
+                Ok(latest_height) => {
+                    debug!(
+                        inclusion_height,
+                        latest_height = latest_height.height(),
+                        latest_height_is_fresh = latest_height.is_fresh(),
+                        firm_height,
+                        "waiting for celestia blocks on top of the submission"
+                    );
+                }
+                Err(error) => {
+                    let error = Report::new(error);
+                    warn!(
+                        %error,
+                        inclusion_height,
+                        "failed to fetch the latest celestia height while waiting for the \
+                         confirmation depth; trying again"
+                    );
+                }
+            }
+            self.clock.sleep(POLL_INTERVAL).await;
+        }
+    }
 }
 
 fn new_msg_pay_for_blobs(
@@ -407,6 +979,24 @@ fn min_gas_price_from_response(
         })
 }
 
+/// Extracts the balance in utia from the given response.
+fn balance_from_response(
+    response: Result<Response<QueryBalanceResponse>, Status>,
+) -> Result<u64, TrySubmitError> {
+    let balance = response
+        .map_err(|status| TrySubmitError::FailedToGetBalance(GrpcResponseError::from(status)))?
+        .into_inner()
+        .balance
+        .ok_or_else(|| TrySubmitError::EmptyBalance)?
+        .amount;
+    balance
+        .parse::<u64>()
+        .map_err(|source| TrySubmitError::FailedToParseBalance {
+            balance,
+            source,
+        })
+}
+
 /// Extracts the tx hash from the given response.
 fn tx_hash_from_response(
     response: Result<Response<BroadcastTxResponse>, Status>,
@@ -475,6 +1065,20 @@ fn block_height_from_response(
     Ok(Some(height))
 }
 
+/// Extracts the height of the latest Celestia block from the given response.
+fn latest_height_from_response(
+    response: Result<Response<GetLatestBlockResponse>, Status>,
+) -> Result<u64, TrySubmitError> {
+    let height = response
+        .map_err(|status| TrySubmitError::FailedToGetLatestBlock(GrpcResponseError::from(status)))?
+        .into_inner()
+        .block
+        .and_then(|block| block.header)
+        .ok_or_else(|| TrySubmitError::EmptyLatestBlock)?
+        .height;
+    u64::try_from(height).map_err(|_| TrySubmitError::LatestBlockNegativeHeight(height))
+}
+
 // Copied from https://github.com/celestiaorg/celestia-app/blob/v1.4.0/x/blob/types/payforblob.go#L174
 //
 // `blob_sizes` is the collection of sizes in bytes of all the blobs' `data` fields.
@@ -537,23 +1141,51 @@ fn estimate_gas(blob_sizes: &[u32], cost_params: CelestiaCostParams) -> GasLimit
     )
 }
 
+/// Raises `fee` by `fee_bump_percent`, rounding up.
+///
+/// Because [`calculate_fee`] starts from the fee of an unconfirmed submission, the fee of an
+/// escalated submission grows with every resubmission.
+fn escalate_fee(fee: u64, fee_bump_percent: u32) -> u64 {
+    if fee_bump_percent == 0 {
+        return fee;
+    }
+    let escalated_fee = fee
+        .saturating_mul(100_u64.saturating_add(u64::from(fee_bump_percent)))
+        .div_ceil(100);
+    info!(
+        fee,
+        escalated_fee,
+        fee_bump_percent,
+        "raising fee of submission containing sequencer blocks past their deadline"
+    );
+    escalated_fee
+}
+
 /// Returns the fee for the signed tx.
 ///
 /// This is calculated as `min gas price * gas limit`, but if a required fee can be extracted from
 /// `maybe_last_error`, it will be returned rather than a calculated value.
+///
+/// If the last attempt was broadcast but never confirmed, the fee of that attempt is used instead
+/// if it is greater than the fee determined otherwise. It is not raised here: raising it is left
+/// to [`escalate_fee`], so that resubmissions are only raised by the configured percentage.
 fn calculate_fee(
     cost_params: CelestiaCostParams,
     gas_limit: GasLimit,
     maybe_last_error: Option<TrySubmitError>,
 ) -> u64 {
-    // Try to extract the required fee from the last error.
-    let maybe_required_fee = match maybe_last_error {
+    // Try to extract the required fee or the fee of an unconfirmed submission from the last
+    // error.
+    let (maybe_required_fee, maybe_unconfirmed_fee) = match maybe_last_error {
         Some(TrySubmitError::BroadcastTxResponseErrorCode {
             code,
             log,
             ..
-        }) if code == INSUFFICIENT_FEE_CODE => extract_required_fee_from_log(&log),
-        _ => None,
+        }) if code == INSUFFICIENT_FEE_CODE => (extract_required_fee_from_log(&log), None),
+        Some(TrySubmitError::SubmissionNotConfirmed {
+            fee, ..
+        }) => (None, Some(fee)),
+        _ => (None, None),
     };
 
     // Calculate the fee from the provided values.
@@ -569,6 +1201,14 @@ fn calculate_fee(
     )]
     let calculated_fee = (cost_params.min_gas_price() * gas_limit.0 as f64).ceil() as u64;
 
+    if let Some(unconfirmed_fee) = maybe_unconfirmed_fee {
+        info!(
+            unconfirmed_fee,
+            calculated_fee, "using at least the fee of the unconfirmed blob submission"
+        );
+        return std::cmp::max(unconfirmed_fee, calculated_fee);
+    }
+
     // If we have extracted the required fee from the last error, use that.  Otherwise use the
     // calculated one.
     match maybe_required_fee {
@@ -642,10 +1282,8 @@ fn new_signed_tx(
     gas_limit: GasLimit,
     fee: u64,
     chain_id: String,
-    signing_keys: &CelestiaKeys,
+    signer: &dyn TxSigner,
 ) -> Tx {
-    // From https://github.com/celestiaorg/celestia-app/blob/v1.4.0/pkg/appconsts/global_consts.go#L76
-    const FEE_DENOM: &str = "utia";
     // From https://github.com/celestiaorg/cosmos-sdk/blob/v1.18.3-sdk-v0.46.14/proto/cosmos/tx/signing/v1beta1/signing.proto#L24
     const SIGNING_MODE_INFO: Option<ModeInfo> = Some(ModeInfo {
         sum: Some(Sum::Single(Single {
@@ -665,8 +1303,8 @@ fn new_signed_tx(
 
     let public_key = secp256k1::PubKey {
         key: Bytes::from(
-            signing_keys
-                .verification_key
+            signer
+                .verification_key()
                 .to_encoded_point(true)
                 .as_bytes()
                 .to_vec(),
@@ -702,7 +1340,7 @@ fn new_signed_tx(
         account_number: base_account.account_number,
     }
     .encode_to_vec();
-    let signature = signing_keys.sign(&bytes_to_sign);
+    let signature = signer.sign(&bytes_to_sign);
     Tx {
         body: Some(tx_body),
         auth_info: Some(auth_info),
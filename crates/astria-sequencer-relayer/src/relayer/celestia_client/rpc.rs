@@ -0,0 +1,233 @@
+//! The requests the [`CelestiaClient`](super::CelestiaClient) makes to the Celestia app.
+//!
+//! Submitting blobs only ever needs the handful of queries and transaction requests of
+//! [`CelestiaRpc`]. The relayer sends them over gRPC using [`GrpcCelestiaRpc`]; tests substitute an
+//! implementation answering them from memory.
+
+use std::fmt::Debug;
+
+use astria_core::generated::{
+    celestia::v1::{
+        query_client::QueryClient as BlobQueryClient,
+        Params as BlobParams,
+        QueryParamsRequest as QueryBlobParamsRequest,
+    },
+    cosmos::{
+        auth::v1beta1::{
+            query_client::QueryClient as AuthQueryClient,
+            BaseAccount,
+            Params as AuthParams,
+            QueryAccountRequest,
+            QueryParamsRequest as QueryAuthParamsRequest,
+        },
+        bank::v1beta1::{
+            query_client::QueryClient as BankQueryClient,
+            QueryBalanceRequest,
+        },
+        base::{
+            node::v1beta1::{
+                service_client::ServiceClient as MinGasPriceClient,
+                ConfigRequest as MinGasPriceRequest,
+            },
+            tendermint::v1beta1::{
+                service_client::ServiceClient as TendermintClient,
+                GetLatestBlockRequest,
+            },
+        },
+        tx::v1beta1::{
+            service_client::ServiceClient as TxClient,
+            BroadcastMode,
+            BroadcastTxRequest,
+            GetTxRequest,
+        },
+    },
+    tendermint::types::BlobTx,
+};
+use async_trait::async_trait;
+use prost::{
+    bytes::Bytes,
+    Message as _,
+};
+use tonic::transport::Channel;
+use tracing::trace;
+
+use super::{
+    account_from_response,
+    balance_from_response,
+    block_height_from_response,
+    latest_height_from_response,
+    min_gas_price_from_response,
+    tx_hash_from_response,
+    Bech32Address,
+    GrpcResponseError,
+    TrySubmitError,
+    TxHash,
+    FEE_DENOM,
+};
+
+/// The requests made to the Celestia app while submitting blobs.
+#[async_trait]
+pub(super) trait CelestiaRpc: Debug + Send + Sync + 'static {
+    /// Returns the parameters of the blob module.
+    async fn fetch_blob_params(&self) -> Result<BlobParams, TrySubmitError>;
+
+    /// Returns the parameters of the auth module.
+    async fn fetch_auth_params(&self) -> Result<AuthParams, TrySubmitError>;
+
+    /// Returns the minimum gas price accepted by the Celestia app in utia.
+    async fn fetch_min_gas_price(&self) -> Result<f64, TrySubmitError>;
+
+    /// Returns the account at `address` as committed on Celestia.
+    async fn fetch_account(&self, address: &Bech32Address) -> Result<BaseAccount, TrySubmitError>;
+
+    /// Returns the balance of the account at `address` in utia.
+    async fn fetch_balance(&self, address: &Bech32Address) -> Result<u64, TrySubmitError>;
+
+    /// Returns the tx hash if the tx is successfully placed into the node's mempool.
+    async fn broadcast_tx(&self, blob_tx: BlobTx) -> Result<TxHash, TrySubmitError>;
+
+    /// Returns `Some(height)` if the tx submission has completed, or `None` if it is still
+    /// pending.
+    async fn get_tx(&self, tx_hash: TxHash) -> Result<Option<u64>, TrySubmitError>;
+
+    /// Returns the height of the latest Celestia block.
+    async fn fetch_latest_height(&self) -> Result<u64, TrySubmitError>;
+}
+
+/// Sends the requests of [`CelestiaRpc`] to the gRPC interface of a remote Celestia app.
+#[derive(Clone, Debug)]
+pub(super) struct GrpcCelestiaRpc {
+    /// The inner `tonic` gRPC channel shared by the various generated gRPC clients.
+    grpc_channel: Channel,
+}
+
+impl GrpcCelestiaRpc {
+    pub(super) fn new(grpc_channel: Channel) -> Self {
+        Self {
+            grpc_channel,
+        }
+    }
+}
+
+#[async_trait]
+impl CelestiaRpc for GrpcCelestiaRpc {
+    async fn fetch_blob_params(&self) -> Result<BlobParams, TrySubmitError> {
+        let mut blob_query_client = BlobQueryClient::new(self.grpc_channel.clone());
+        let response = blob_query_client.params(QueryBlobParamsRequest {}).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        response
+            .map_err(|status| {
+                TrySubmitError::FailedToGetBlobParams(GrpcResponseError::from(status))
+            })?
+            .into_inner()
+            .params
+            .ok_or_else(|| TrySubmitError::EmptyBlobParams)
+    }
+
+    async fn fetch_auth_params(&self) -> Result<AuthParams, TrySubmitError> {
+        let mut auth_query_client = AuthQueryClient::new(self.grpc_channel.clone());
+        let response = auth_query_client.params(QueryAuthParamsRequest {}).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        response
+            .map_err(|status| {
+                TrySubmitError::FailedToGetAuthParams(GrpcResponseError::from(status))
+            })?
+            .into_inner()
+            .params
+            .ok_or_else(|| TrySubmitError::EmptyAuthParams)
+    }
+
+    async fn fetch_min_gas_price(&self) -> Result<f64, TrySubmitError> {
+        let mut min_gas_price_client = MinGasPriceClient::new(self.grpc_channel.clone());
+        let response = min_gas_price_client.config(MinGasPriceRequest {}).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        min_gas_price_from_response(response)
+    }
+
+    async fn fetch_account(&self, address: &Bech32Address) -> Result<BaseAccount, TrySubmitError> {
+        let mut auth_query_client = AuthQueryClient::new(self.grpc_channel.clone());
+        let request = QueryAccountRequest {
+            address: address.0.clone(),
+        };
+        let response = auth_query_client.account(request).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        account_from_response(response)
+    }
+
+    async fn fetch_balance(&self, address: &Bech32Address) -> Result<u64, TrySubmitError> {
+        let mut bank_query_client = BankQueryClient::new(self.grpc_channel.clone());
+        let request = QueryBalanceRequest {
+            address: address.0.clone(),
+            denom: FEE_DENOM.to_string(),
+        };
+        let response = bank_query_client.balance(request).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        balance_from_response(response)
+    }
+
+    /// Note, we use `BroadcastTxSync`, i.e. `BroadcastMode::Sync` as recommended by
+    /// [`CometBFT`][cometbft].
+    ///
+    /// [cometbft]: https://github.com/cometbft/cometbft/blob/b139e139ad9ae6fccb9682aa5c2de4aa952fd055/rpc/openapi/openapi.yaml#L201-L204
+    async fn broadcast_tx(&self, blob_tx: BlobTx) -> Result<TxHash, TrySubmitError> {
+        let mut tx_client = TxClient::new(self.grpc_channel.clone());
+        let request = BroadcastTxRequest {
+            tx_bytes: Bytes::from(blob_tx.encode_to_vec()),
+            mode: i32::from(BroadcastMode::Sync),
+        };
+        let response = tx_client.broadcast_tx(request).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        tx_hash_from_response(response)
+    }
+
+    async fn get_tx(&self, tx_hash: TxHash) -> Result<Option<u64>, TrySubmitError> {
+        let mut tx_client = TxClient::new(self.grpc_channel.clone());
+        let request = GetTxRequest {
+            hash: tx_hash.0.clone(),
+        };
+        let response = tx_client.get_tx(request).await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        block_height_from_response(response)
+    }
+
+    async fn fetch_latest_height(&self) -> Result<u64, TrySubmitError> {
+        let mut tendermint_client = TendermintClient::new(self.grpc_channel.clone());
+        let response = tendermint_client
+            .get_latest_block(GetLatestBlockRequest {})
+            .await;
+        // trace-level logging, so using Debug format is ok.
+        #[cfg_attr(dylint_lib = "tracing_debug_field", allow(tracing_debug_field))]
+        {
+            trace!(?response);
+        }
+        latest_height_from_response(response)
+    }
+}
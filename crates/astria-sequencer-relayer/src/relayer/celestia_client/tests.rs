@@ -426,6 +426,222 @@ fn block_height_from_pending_response_should_return_none() {
     assert!(maybe_height.is_none());
 }
 
+fn latest_block_response(height: Option<i64>) -> Response<GetLatestBlockResponse> {
+    use astria_core::generated::tendermint::types::{
+        Block,
+        Header,
+    };
+    Response::new(GetLatestBlockResponse {
+        block: height.map(|height| Block {
+            header: Some(Header {
+                chain_id: "mock-celestia".to_string(),
+                height,
+            }),
+        }),
+    })
+}
+
+#[test]
+fn latest_height_from_good_response_should_succeed() {
+    let height = latest_height_from_response(Ok(latest_block_response(Some(9)))).unwrap();
+    assert_eq!(9, height);
+}
+
+#[test]
+fn latest_height_from_bad_response_should_fail() {
+    let error = latest_height_from_response(Err(Status::internal(""))).unwrap_err();
+    assert!(
+        matches!(error, TrySubmitError::FailedToGetLatestBlock(_)),
+        "unexpected error: {error:?}"
+    );
+
+    let error = latest_height_from_response(Ok(latest_block_response(None))).unwrap_err();
+    assert!(
+        matches!(error, TrySubmitError::EmptyLatestBlock),
+        "unexpected error: {error:?}"
+    );
+
+    let error = latest_height_from_response(Ok(latest_block_response(Some(-9)))).unwrap_err();
+    assert!(
+        matches!(error, TrySubmitError::LatestBlockNegativeHeight(-9)),
+        "unexpected error: {error:?}"
+    );
+}
+
+#[test]
+fn balance_from_good_response_should_succeed() {
+    let response = Response::new(QueryBalanceResponse {
+        balance: Some(Coin {
+            denom: FEE_DENOM.to_string(),
+            amount: "1234".to_string(),
+        }),
+    });
+    assert_eq!(1234, balance_from_response(Ok(response)).unwrap());
+}
+
+#[test]
+fn balance_from_bad_response_should_fail() {
+    let error = balance_from_response(Err(Status::internal(""))).unwrap_err();
+    assert!(
+        matches!(error, TrySubmitError::FailedToGetBalance(_)),
+        "unexpected error: {error:?}"
+    );
+
+    let response = Response::new(QueryBalanceResponse {
+        balance: None,
+    });
+    let error = balance_from_response(Ok(response)).unwrap_err();
+    assert!(
+        matches!(error, TrySubmitError::EmptyBalance),
+        "unexpected error: {error:?}"
+    );
+
+    let response = Response::new(QueryBalanceResponse {
+        balance: Some(Coin {
+            denom: FEE_DENOM.to_string(),
+            amount: "12.34".to_string(),
+        }),
+    });
+    let error = balance_from_response(Ok(response)).unwrap_err();
+    assert!(
+        matches!(error, TrySubmitError::FailedToParseBalance { .. }),
+        "unexpected error: {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn check_funds_should_compare_balance_to_fee() {
+    let rpc = Arc::new(mock::MockCelestiaRpc::default());
+    let client = rpc.client(1, 0, ErrorPolicy::default());
+
+    let funds = client.check_funds(&[1000]).await.unwrap();
+    assert!(funds.are_sufficient());
+    assert_eq!(1, funds.account_number);
+    assert!(funds.fee > 0);
+
+    rpc.set_balance(funds.fee - 1);
+    let funds = client.check_funds(&[1000]).await.unwrap();
+    assert!(!funds.are_sufficient());
+}
+
+#[tokio::test(start_paused = true)]
+async fn included_submission_waits_for_confirmation_depth() {
+    let rpc = Arc::new(mock::MockCelestiaRpc::default());
+    let client = CelestiaClient {
+        confirmation_depth: 2,
+        ..rpc.client(1, 0, ErrorPolicy::default())
+    };
+    let blobs = vec![Blob::new(Namespace::const_v0([1; 10]), vec![1; 100]).unwrap()];
+    let idempotency_key = IdempotencyKey::from_blobs(&blobs);
+    let (_, last_error_receiver) = watch::channel(None);
+    let submission = tokio::spawn(client.try_submit(
+        Arc::new(blobs),
+        idempotency_key,
+        last_error_receiver,
+        false,
+        0,
+    ));
+
+    // The transaction is included right away, but no blocks were built on top of it yet.
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    assert!(!submission.is_finished());
+
+    rpc.set_latest_height(mock::INCLUSION_HEIGHT + 2);
+    let inclusion = submission.await.unwrap().unwrap();
+    assert_eq!(mock::INCLUSION_HEIGHT, inclusion.height);
+}
+
+fn latest_block_unavailable() -> TrySubmitError {
+    TrySubmitError::FailedToGetLatestBlock(GrpcResponseError::from(Status::unavailable("")))
+}
+
+#[tokio::test]
+async fn latest_height_retries_transient_failures() {
+    let rpc = Arc::new(mock::MockCelestiaRpc::default());
+    let client = CelestiaClient {
+        clock: Arc::new(crate::clock::MockClock::new()),
+        ..rpc.client(1, 0, ErrorPolicy::default())
+    };
+    rpc.set_latest_height(7);
+    for _ in 1..LATEST_HEIGHT_ATTEMPTS {
+        rpc.fail_next_latest_height(latest_block_unavailable());
+    }
+    assert_eq!(
+        LatestHeight::Fresh(7),
+        client.get_latest_height().await.unwrap()
+    );
+
+    // Fatal errors are not retried.
+    let policy = ErrorPolicy::parse("failed_to_get_latest_block=fatal").unwrap();
+    let client = CelestiaClient {
+        error_policy: policy,
+        ..client
+    };
+    rpc.fail_next_latest_height(latest_block_unavailable());
+    assert!(client.get_latest_height().await.is_err());
+    assert_eq!(
+        LatestHeight::Fresh(7),
+        client.get_latest_height().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn latest_height_falls_back_to_recent_cached_height() {
+    let rpc = Arc::new(mock::MockCelestiaRpc::default());
+    let clock = crate::clock::MockClock::new();
+    let client = CelestiaClient {
+        clock: Arc::new(clock.clone()),
+        ..rpc.client(1, 0, ErrorPolicy::default())
+    };
+    let unreachable = || {
+        for _ in 0..LATEST_HEIGHT_ATTEMPTS {
+            rpc.fail_next_latest_height(latest_block_unavailable());
+        }
+    };
+
+    // Nothing was cached yet.
+    unreachable();
+    assert!(client.get_latest_height().await.is_err());
+
+    assert_eq!(
+        LatestHeight::Fresh(mock::INCLUSION_HEIGHT),
+        client.get_latest_height().await.unwrap()
+    );
+
+    clock.advance(Duration::from_secs(10));
+    unreachable();
+    let latest_height = client.get_latest_height().await.unwrap();
+    assert!(!latest_height.is_fresh());
+    assert_eq!(mock::INCLUSION_HEIGHT, latest_height.height());
+    assert!(
+        matches!(latest_height, LatestHeight::Stale { age, .. } if age >= Duration::from_secs(10)),
+        "unexpected latest height: {latest_height:?}"
+    );
+
+    clock.advance(mock::MAX_STALE_LATEST_HEIGHT_AGE);
+    unreachable();
+    assert!(client.get_latest_height().await.is_err());
+}
+
+#[tokio::test]
+async fn latest_height_does_not_fall_back_if_disabled() {
+    let rpc = Arc::new(mock::MockCelestiaRpc::default());
+    let client = CelestiaClient {
+        clock: Arc::new(crate::clock::MockClock::new()),
+        max_stale_latest_height_age: None,
+        ..rpc.client(1, 0, ErrorPolicy::default())
+    };
+    assert_eq!(
+        LatestHeight::Fresh(mock::INCLUSION_HEIGHT),
+        client.get_latest_height().await.unwrap()
+    );
+
+    for _ in 0..LATEST_HEIGHT_ATTEMPTS {
+        rpc.fail_next_latest_height(latest_block_unavailable());
+    }
+    assert!(client.get_latest_height().await.is_err());
+}
+
 #[test]
 fn should_use_calculated_fee() {
     // If no last error provided, should use calculated fee.
@@ -484,6 +700,67 @@ fn should_use_fee_from_error_log() {
     assert_eq!(fee, required_fee);
 }
 
+#[test]
+fn should_not_lower_fee_of_unconfirmed_submission() {
+    // If last error was `SubmissionNotConfirmed`, should use the previous fee if that exceeds the
+    // calculated fee. Raising it is left to `escalate_fee`.
+    let cost_params = CelestiaCostParams::new(8, 10, 0.1);
+    let error = TrySubmitError::SubmissionNotConfirmed {
+        tx_hash: String::new(),
+        fee: 1_000,
+        timeout_blocks: 10,
+    };
+    let fee = calculate_fee(cost_params, GasLimit(100), Some(error));
+    assert_eq!(fee, 1_000);
+}
+
+#[test]
+fn should_raise_fee_of_escalated_submission() {
+    assert_eq!(1_000, escalate_fee(1_000, 0));
+    assert_eq!(1_500, escalate_fee(1_000, 50));
+    assert_eq!(2, escalate_fee(1, 50));
+}
+
+#[test]
+fn sequence_tracker_should_skip_sequences_of_in_flight_submissions() {
+    let tracker = SequenceTracker::default();
+    let first = tracker.reserve(5);
+    let second = tracker.reserve(5);
+    assert_eq!(first.sequence(), 5);
+    assert_eq!(second.sequence(), 6);
+    // The committed sequence overtaking the tracked one takes precedence.
+    let third = tracker.reserve(10);
+    let fourth = tracker.reserve(10);
+    assert_eq!(third.sequence(), 10);
+    assert_eq!(fourth.sequence(), 11);
+}
+
+#[test]
+fn sequence_tracker_should_refill_freed_sequences_before_in_flight_ones() {
+    let tracker = SequenceTracker::default();
+    let first = tracker.reserve(5);
+    let second = tracker.reserve(5);
+    assert_eq!(first.sequence(), 5);
+    assert_eq!(second.sequence(), 6);
+    // The first transaction failing leaves a gap the second one waits on.
+    drop(first);
+    let third = tracker.reserve(5);
+    assert_eq!(third.sequence(), 5);
+    // The sequence of the second transaction, still in flight, is not handed out again.
+    let fourth = tracker.reserve(5);
+    assert_eq!(fourth.sequence(), 7);
+}
+
+#[test]
+fn sequence_tracker_should_use_committed_sequence_once_nothing_is_in_flight() {
+    let tracker = SequenceTracker::default();
+    let first = tracker.reserve(5);
+    let second = tracker.reserve(5);
+    drop(first);
+    drop(second);
+    assert_eq!(tracker.reserve(5).sequence(), 5);
+}
+
 #[test]
 fn extract_required_fee_from_log_should_succeed() {
     fn check(fee: u64) {
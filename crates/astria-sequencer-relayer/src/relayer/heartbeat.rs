@@ -0,0 +1,127 @@
+//! Signed heartbeats posted to Celestia.
+//!
+//! If enabled, the relayer adds a small signed heartbeat blob to a submission at most once every
+//! configured number of Celestia blocks. Heartbeats are written under their own namespace and
+//! carry the identity of the relayer, the latest sequencer height it confirmed on Celestia, and
+//! its software version, so that network monitors can detect stalled relayers by reading
+//! Celestia alone.
+//!
+//! Heartbeats are attached to submissions of sequencer blocks and never submitted on their own:
+//! a relayer that stops submitting blocks also stops posting heartbeats.
+//!
+//! The format of heartbeat blobs is defined in [`astria_core::heartbeat`].
+
+use astria_core::heartbeat::{
+    HeartbeatPayload,
+    SignedHeartbeat,
+};
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use celestia_types::{
+    nmt::Namespace,
+    Blob,
+};
+use tracing::warn;
+
+use crate::validator::Validator;
+
+/// Creates the heartbeat blobs of the relayer.
+pub(crate) struct Heartbeat {
+    namespace: Namespace,
+    key: Validator,
+    interval: u64,
+    last_celestia_height: Option<u64>,
+}
+
+impl Heartbeat {
+    /// Constructs heartbeats signed by `key`, written under `namespace` at most once every
+    /// `interval` Celestia blocks.
+    pub(crate) fn new(namespace: Namespace, key: Validator, interval: u64) -> Self {
+        Self {
+            namespace,
+            key,
+            interval,
+            last_celestia_height: None,
+        }
+    }
+
+    /// Returns the next heartbeat blob if none was returned yet, or if at least `interval`
+    /// Celestia blocks passed since the last one.
+    pub(crate) fn next_blob(
+        &mut self,
+        latest_confirmed_celestia_height: Option<u64>,
+        latest_confirmed_sequencer_height: Option<u64>,
+    ) -> Option<Blob> {
+        let celestia_height = latest_confirmed_celestia_height.unwrap_or_default();
+        if self
+            .last_celestia_height
+            .is_some_and(|last| celestia_height < last.saturating_add(self.interval))
+        {
+            return None;
+        }
+        let payload = HeartbeatPayload {
+            relayer_address: self.key.address.to_string(),
+            celestia_height: latest_confirmed_celestia_height,
+            last_relayed_sequencer_height: latest_confirmed_sequencer_height,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        match self.sign(&payload) {
+            Ok(blob) => {
+                self.last_celestia_height = Some(celestia_height);
+                Some(blob)
+            }
+            Err(error) => {
+                warn!(%error, "failed creating heartbeat blob; skipping it");
+                None
+            }
+        }
+    }
+
+    fn sign(&self, payload: &HeartbeatPayload) -> eyre::Result<Blob> {
+        let signed = SignedHeartbeat::sign(payload, &self.key.signing_key);
+        Blob::new(self.namespace, signed.encode()).wrap_err("failed creating heartbeat blob")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use astria_core::celestia::namespace_v0_from_first_10_bytes;
+    use ed25519_consensus::SigningKey;
+    use tendermint::account;
+
+    use super::*;
+
+    fn heartbeat(interval: u64) -> Heartbeat {
+        let signing_key = SigningKey::from([1; 32]);
+        let key = Validator {
+            address: account::Id::new([2; 20]),
+            verification_key: signing_key.verification_key(),
+            signing_key,
+        };
+        Heartbeat::new(namespace_v0_from_first_10_bytes(&[3; 10]), key, interval)
+    }
+
+    #[test]
+    fn heartbeats_are_posted_once_per_interval() {
+        let mut heartbeat = heartbeat(5);
+        assert!(heartbeat.next_blob(Some(10), Some(100)).is_some());
+        assert!(heartbeat.next_blob(Some(14), Some(101)).is_none());
+        assert!(heartbeat.next_blob(Some(15), Some(102)).is_some());
+    }
+
+    #[test]
+    fn heartbeat_is_signed_by_relayer() {
+        let blob = heartbeat(5).next_blob(Some(10), Some(100)).unwrap();
+        let signed = SignedHeartbeat::decode(&blob.data).unwrap();
+        assert_eq!(
+            SigningKey::from([1; 32]).verification_key().to_bytes(),
+            signed.verification_key(),
+        );
+        let payload = signed.verify().unwrap();
+        assert_eq!(Some(100), payload.last_relayed_sequencer_height);
+        assert_eq!(Some(10), payload.celestia_height);
+        assert_eq!(env!("CARGO_PKG_VERSION"), payload.version);
+    }
+}
@@ -0,0 +1,155 @@
+//! Watches how far the sequencer blocks confirmed on Celestia lag behind the sequencer.
+//!
+//! The lag is the difference between the newest sequencer height observed and the newest
+//! sequencer height whose submission was confirmed on Celestia, as tracked in the relayer's
+//! [`State`]. [`LagWatchdog`] compares it against the configured [`LagThresholds`] whenever the
+//! state changes. Crossing the warn threshold is logged and alerted; crossing the error
+//! threshold is alerted as critical and additionally marks the relayer as not ready until the
+//! lag falls below the error threshold again.
+
+use std::sync::Arc;
+
+use tokio::select;
+use tokio_util::sync::CancellationToken;
+use tracing::{
+    error,
+    info,
+    instrument,
+    warn,
+};
+
+use super::State;
+use crate::alerts::{
+    Alert,
+    Alerter,
+};
+
+/// The sequencer height lags above which the relayer warns and errors.
+///
+/// A threshold of 0 disables it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct LagThresholds {
+    pub(crate) warn: u64,
+    pub(crate) error: u64,
+}
+
+impl LagThresholds {
+    fn level(self, lag: u64) -> LagLevel {
+        if self.error > 0 && lag > self.error {
+            LagLevel::Error
+        } else if self.warn > 0 && lag > self.warn {
+            LagLevel::Warn
+        } else {
+            LagLevel::Normal
+        }
+    }
+
+    pub(super) fn is_disabled(self) -> bool {
+        self.warn == 0 && self.error == 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum LagLevel {
+    Normal,
+    Warn,
+    Error,
+}
+
+pub(super) struct LagWatchdog {
+    pub(super) state: Arc<State>,
+    pub(super) thresholds: LagThresholds,
+    pub(super) alerts: Alerter,
+    pub(super) shutdown_token: CancellationToken,
+}
+
+impl LagWatchdog {
+    #[instrument(skip_all)]
+    pub(super) async fn run(self) {
+        let Self {
+            state,
+            thresholds,
+            alerts,
+            shutdown_token,
+        } = self;
+        if thresholds.is_disabled() {
+            return;
+        }
+        let mut snapshots = state.subscribe();
+        let mut level = LagLevel::Normal;
+        loop {
+            select!(
+                () = shutdown_token.cancelled() => break,
+                changed = snapshots.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                }
+            );
+            let Some(lag) = snapshots.borrow_and_update().sequencer_height_lag() else {
+                continue;
+            };
+            let new_level = thresholds.level(lag);
+            if new_level == level {
+                continue;
+            }
+            match new_level {
+                LagLevel::Normal => info!(lag, "sequencer height lag is back below thresholds"),
+                LagLevel::Warn => warn!(
+                    lag,
+                    threshold = thresholds.warn,
+                    "sequencer height lag exceeds the warn threshold",
+                ),
+                LagLevel::Error => error!(
+                    lag,
+                    threshold = thresholds.error,
+                    "sequencer height lag exceeds the error threshold; reporting not ready",
+                ),
+            }
+            if new_level > level {
+                alerts.raise(Alert::SequencerHeightLag {
+                    lag,
+                    threshold: match new_level {
+                        LagLevel::Error => thresholds.error,
+                        _ => thresholds.warn,
+                    },
+                    critical: new_level == LagLevel::Error,
+                });
+            }
+            state.set_lagging(new_level == LagLevel::Error);
+            level = new_level;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LagLevel,
+        LagThresholds,
+    };
+
+    #[test]
+    fn lag_is_classified_by_the_thresholds_it_exceeds() {
+        let thresholds = LagThresholds {
+            warn: 10,
+            error: 100,
+        };
+        assert_eq!(LagLevel::Normal, thresholds.level(10));
+        assert_eq!(LagLevel::Warn, thresholds.level(11));
+        assert_eq!(LagLevel::Warn, thresholds.level(100));
+        assert_eq!(LagLevel::Error, thresholds.level(101));
+    }
+
+    #[test]
+    fn zero_thresholds_are_disabled() {
+        let only_error = LagThresholds {
+            warn: 0,
+            error: 100,
+        };
+        assert_eq!(LagLevel::Normal, only_error.level(100));
+        assert_eq!(LagLevel::Error, only_error.level(101));
+        assert!(LagThresholds::default().is_disabled());
+        assert_eq!(LagLevel::Normal, LagThresholds::default().level(u64::MAX));
+    }
+}
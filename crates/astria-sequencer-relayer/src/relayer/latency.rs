@@ -0,0 +1,404 @@
+//! Tracks how long sequencer blocks take to move through the relayer.
+//!
+//! Every sequencer block forwarded for submission is timestamped when it is observed,
+//! when its conversion to blobs is complete, when the submission containing it is started,
+//! and when that submission is confirmed on Celestia. The timestamps are keyed by the
+//! block hash. Once a block is confirmed, the durations between the stages are recorded as
+//! histograms and kept in a bounded list of recent blocks served by the admin API.
+//!
+//! The end-to-end latency approximates the soft-to-firm latency rollups experience: the
+//! time between a block becoming available on the sequencer and it being written to Celestia.
+//!
+//! If a [`SubmissionDeadline`] is configured, blocks whose end-to-end latency exceeds it are
+//! flagged as having missed their deadline, and blocks past it that are not yet confirmed are
+//! reported as overdue, both as metrics and by the admin API.
+
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use astria_core::primitive::v1::BlockHash;
+use sequencer_client::tendermint::block::Height as SequencerHeight;
+
+use crate::clock::Clock;
+
+/// The number of confirmed blocks whose latencies are kept for the admin API.
+const MAX_RECENT_BLOCKS: usize = 128;
+
+/// The latencies of a sequencer block that was confirmed on Celestia.
+#[derive(Clone, Debug, serde::Serialize)]
+pub(crate) struct BlockLatency {
+    sequencer_height: u64,
+    block_hash: String,
+    celestia_height: u64,
+    /// Seconds from observing the block until it was converted to blobs.
+    assembly_seconds: f64,
+    /// Seconds from converting the block until the submission containing it was started.
+    queue_seconds: f64,
+    /// Seconds from starting the submission until it was confirmed on Celestia.
+    confirmation_seconds: f64,
+    /// Seconds from observing the block until it was confirmed on Celestia.
+    end_to_end_seconds: f64,
+    /// Whether the block was confirmed after its submission deadline.
+    missed_deadline: bool,
+}
+
+/// A sequencer block past its submission deadline that is not yet confirmed on Celestia.
+#[derive(Clone, Debug, serde::Serialize)]
+pub(crate) struct OverdueBlock {
+    sequencer_height: u64,
+    block_hash: String,
+    /// Seconds since the block's submission deadline passed.
+    overdue_seconds: f64,
+}
+
+/// The time within which sequencer blocks must be confirmed on Celestia after they were
+/// observed.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SubmissionDeadline {
+    /// The time from observing a block until it must be confirmed on Celestia.
+    pub(crate) deadline: Duration,
+    /// The percentage by which the fee of a submission is raised while it contains a block past
+    /// its deadline. 0 disables raising the fee.
+    pub(crate) fee_bump_percent: u32,
+}
+
+struct Timestamps {
+    sequencer_height: SequencerHeight,
+    observed: Instant,
+    assembled: Option<Instant>,
+    submitted: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Inner {
+    pending: HashMap<[u8; 32], Timestamps>,
+    recent: VecDeque<BlockLatency>,
+}
+
+/// Records the stage timestamps of sequencer blocks, keyed by block hash.
+///
+/// Shared between the relayer, the write path, and the admin API.
+pub(crate) struct LatencyTracker {
+    inner: Mutex<Inner>,
+    clock: Arc<dyn Clock>,
+    deadline: Option<SubmissionDeadline>,
+}
+
+impl LatencyTracker {
+    pub(crate) fn new(clock: Arc<dyn Clock>, deadline: Option<SubmissionDeadline>) -> Self {
+        Self {
+            inner: Mutex::default(),
+            clock,
+            deadline,
+        }
+    }
+
+    /// Records that the block `block_hash` at `sequencer_height` was observed and forwarded for
+    /// submission.
+    pub(super) fn observed(&self, block_hash: [u8; 32], sequencer_height: SequencerHeight) {
+        let now = self.clock.now();
+        let mut inner = self.lock();
+        inner.pending.insert(
+            block_hash,
+            Timestamps {
+                sequencer_height,
+                observed: now,
+                assembled: None,
+                submitted: None,
+            },
+        );
+        // Blocks are observed once per sequencer block time, which is often enough to keep the
+        // number of overdue blocks up to date.
+        self.report_overdue(&inner, now);
+    }
+
+    /// Records that the block `block_hash` was converted to blobs.
+    pub(super) fn assembled(&self, block_hash: &[u8; 32]) {
+        let now = self.clock.now();
+        if let Some(timestamps) = self.lock().pending.get_mut(block_hash) {
+            timestamps.assembled.get_or_insert(now);
+        }
+    }
+
+    /// Records that a submission containing the blocks `block_hashes` was started.
+    pub(super) fn submitted<'a, I>(&self, block_hashes: I)
+    where
+        I: IntoIterator<Item = &'a [u8; 32]>,
+    {
+        let now = self.clock.now();
+        let mut inner = self.lock();
+        for block_hash in block_hashes {
+            if let Some(timestamps) = inner.pending.get_mut(block_hash) {
+                timestamps.submitted = Some(now);
+            }
+        }
+    }
+
+    /// Records that the submission containing the blocks `block_hashes` was confirmed at
+    /// `celestia_height`.
+    ///
+    /// The latencies of the blocks are recorded as metrics and kept for the admin API.
+    pub(super) fn confirmed<'a, I>(&self, block_hashes: I, celestia_height: u64)
+    where
+        I: IntoIterator<Item = &'a [u8; 32]>,
+    {
+        let now = self.clock.now();
+        let mut inner = self.lock();
+        for block_hash in block_hashes {
+            let Some(timestamps) = inner.pending.remove(block_hash) else {
+                continue;
+            };
+            let assembled = timestamps.assembled.unwrap_or(timestamps.observed);
+            let submitted = timestamps.submitted.unwrap_or(assembled);
+            let end_to_end = now.saturating_duration_since(timestamps.observed);
+            let missed_deadline = self
+                .deadline
+                .is_some_and(|deadline| end_to_end > deadline.deadline);
+            if missed_deadline {
+                metrics::counter!(crate::metrics_init::SEQUENCER_BLOCKS_MISSED_DEADLINE_COUNT)
+                    .increment(1);
+            }
+            let latency = BlockLatency {
+                sequencer_height: timestamps.sequencer_height.value(),
+                block_hash: BlockHash::new(*block_hash).to_string(),
+                celestia_height,
+                assembly_seconds: record(
+                    crate::metrics_init::SEQUENCER_BLOCK_ASSEMBLY_LATENCY,
+                    assembled.saturating_duration_since(timestamps.observed),
+                ),
+                queue_seconds: record(
+                    crate::metrics_init::SEQUENCER_BLOCK_QUEUE_LATENCY,
+                    submitted.saturating_duration_since(assembled),
+                ),
+                confirmation_seconds: record(
+                    crate::metrics_init::SEQUENCER_BLOCK_CONFIRMATION_LATENCY,
+                    now.saturating_duration_since(submitted),
+                ),
+                end_to_end_seconds: record(
+                    crate::metrics_init::SEQUENCER_BLOCK_END_TO_END_LATENCY,
+                    end_to_end,
+                ),
+                missed_deadline,
+            };
+            if inner.recent.len() == MAX_RECENT_BLOCKS {
+                inner.recent.pop_front();
+            }
+            inner.recent.push_back(latency);
+        }
+        self.report_overdue(&inner, now);
+    }
+
+    /// Returns the percentage by which the fee of a submission containing the blocks
+    /// `block_hashes` is raised, which is 0 unless one of them is past its deadline.
+    pub(super) fn fee_bump_percent<'a, I>(&self, block_hashes: I) -> u32
+    where
+        I: IntoIterator<Item = &'a [u8; 32]>,
+    {
+        let Some(deadline) = self.deadline else {
+            return 0;
+        };
+        if deadline.fee_bump_percent == 0 {
+            return 0;
+        }
+        let now = self.clock.now();
+        let inner = self.lock();
+        let any_overdue = block_hashes.into_iter().any(|block_hash| {
+            inner.pending.get(block_hash).is_some_and(|timestamps| {
+                now.saturating_duration_since(timestamps.observed) > deadline.deadline
+            })
+        });
+        if any_overdue {
+            deadline.fee_bump_percent
+        } else {
+            0
+        }
+    }
+
+    /// Returns the blocks past their deadline that are not yet confirmed, oldest first.
+    pub(crate) fn overdue(&self) -> Vec<OverdueBlock> {
+        let Some(deadline) = self.deadline else {
+            return Vec::new();
+        };
+        let now = self.clock.now();
+        let mut overdue: Vec<_> = self
+            .lock()
+            .pending
+            .iter()
+            .filter_map(|(block_hash, timestamps)| {
+                let overdue_by = now
+                    .saturating_duration_since(timestamps.observed)
+                    .checked_sub(deadline.deadline)
+                    .filter(|overdue_by| !overdue_by.is_zero())?;
+                Some(OverdueBlock {
+                    sequencer_height: timestamps.sequencer_height.value(),
+                    block_hash: BlockHash::new(*block_hash).to_string(),
+                    overdue_seconds: overdue_by.as_secs_f64(),
+                })
+            })
+            .collect();
+        overdue.sort_by_key(|block| block.sequencer_height);
+        overdue
+    }
+
+    /// Sets the gauge of blocks past their deadline that are not yet confirmed.
+    fn report_overdue(&self, inner: &Inner, now: Instant) {
+        let Some(deadline) = self.deadline else {
+            return;
+        };
+        let overdue = inner
+            .pending
+            .values()
+            .filter(|timestamps| {
+                now.saturating_duration_since(timestamps.observed) > deadline.deadline
+            })
+            .count();
+        // allow: the number of pending blocks is bounded by the relayer's queues and far below
+        // the precision of an f64
+        #[allow(clippy::cast_precision_loss)]
+        metrics::gauge!(crate::metrics_init::SEQUENCER_BLOCKS_OVERDUE).set(overdue as f64);
+    }
+
+    /// Stops tracking the block at `sequencer_height`, for example because it could not be
+    /// converted to blobs and will never be submitted.
+    pub(super) fn forget(&self, sequencer_height: SequencerHeight) {
+        self.lock()
+            .pending
+            .retain(|_, timestamps| timestamps.sequencer_height != sequencer_height);
+    }
+
+    /// Returns the latencies of the most recently confirmed blocks, oldest first.
+    pub(crate) fn recent(&self) -> Vec<BlockLatency> {
+        self.lock().recent.iter().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // A panic while holding the lock cannot leave the timestamps in an inconsistent state,
+        // so a poisoned lock is recovered.
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Records `duration` in the histogram `name`, returning it in seconds.
+fn record(name: &'static str, duration: Duration) -> f64 {
+    metrics::histogram!(name).record(duration);
+    duration.as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::Duration,
+    };
+
+    use super::{
+        LatencyTracker,
+        SubmissionDeadline,
+    };
+    use crate::clock::{
+        self,
+        MockClock,
+    };
+
+    #[test]
+    fn confirmed_block_is_reported_once() {
+        let tracker = LatencyTracker::new(clock::system(), None);
+        tracker.observed([1; 32], 5u32.into());
+        tracker.assembled(&[1; 32]);
+        tracker.submitted([&[1; 32]]);
+        tracker.confirmed([&[1; 32]], 42);
+        tracker.confirmed([&[1; 32]], 43);
+
+        let recent = tracker.recent();
+        assert_eq!(1, recent.len());
+        assert_eq!(5, recent[0].sequencer_height);
+        assert_eq!(42, recent[0].celestia_height);
+    }
+
+    #[test]
+    fn forgotten_block_is_not_reported() {
+        let tracker = LatencyTracker::new(clock::system(), None);
+        tracker.observed([1; 32], 5u32.into());
+        tracker.forget(5u32.into());
+        tracker.confirmed([&[1; 32]], 42);
+        assert!(tracker.recent().is_empty());
+    }
+
+    #[test]
+    fn only_the_most_recent_blocks_are_kept() {
+        let tracker = LatencyTracker::new(clock::system(), None);
+        for i in 0..=super::MAX_RECENT_BLOCKS {
+            let hash = [u8::try_from(i % 256).unwrap(); 32];
+            tracker.observed(hash, u32::try_from(i).unwrap().into());
+            tracker.confirmed([&hash], 1);
+        }
+        let recent = tracker.recent();
+        assert_eq!(super::MAX_RECENT_BLOCKS, recent.len());
+        assert_eq!(1, recent[0].sequencer_height);
+    }
+
+    #[test]
+    fn latencies_are_measured_between_stages() {
+        let clock = MockClock::new();
+        let tracker = LatencyTracker::new(Arc::new(clock.clone()), None);
+        tracker.observed([1; 32], 5u32.into());
+        clock.advance(Duration::from_secs(1));
+        tracker.assembled(&[1; 32]);
+        clock.advance(Duration::from_secs(2));
+        tracker.submitted([&[1; 32]]);
+        clock.advance(Duration::from_secs(4));
+        tracker.confirmed([&[1; 32]], 42);
+
+        let recent = tracker.recent();
+        assert_eq!(1, recent.len());
+        assert!((recent[0].assembly_seconds - 1.0).abs() < f64::EPSILON);
+        assert!((recent[0].queue_seconds - 2.0).abs() < f64::EPSILON);
+        assert!((recent[0].confirmation_seconds - 4.0).abs() < f64::EPSILON);
+        assert!((recent[0].end_to_end_seconds - 7.0).abs() < f64::EPSILON);
+        assert!(!recent[0].missed_deadline);
+    }
+
+    #[test]
+    fn blocks_past_their_deadline_are_overdue_and_escalated() {
+        let clock = MockClock::new();
+        let tracker = LatencyTracker::new(
+            Arc::new(clock.clone()),
+            Some(SubmissionDeadline {
+                deadline: Duration::from_secs(4),
+                fee_bump_percent: 50,
+            }),
+        );
+        tracker.observed([1; 32], 5u32.into());
+        clock.advance(Duration::from_secs(2));
+        tracker.observed([2; 32], 6u32.into());
+        assert!(tracker.overdue().is_empty());
+        assert_eq!(0, tracker.fee_bump_percent([&[1; 32], &[2; 32]]));
+
+        clock.advance(Duration::from_secs(3));
+        let overdue = tracker.overdue();
+        assert_eq!(1, overdue.len());
+        assert_eq!(5, overdue[0].sequencer_height);
+        assert_eq!(50, tracker.fee_bump_percent([&[1; 32], &[2; 32]]));
+        assert_eq!(0, tracker.fee_bump_percent([&[2; 32]]));
+
+        tracker.confirmed([&[1; 32], &[2; 32]], 42);
+        let recent = tracker.recent();
+        assert!(recent[0].missed_deadline);
+        assert!(!recent[1].missed_deadline);
+        assert!(tracker.overdue().is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+//! Leader election between relayer instances sharing a Postgres storage.
+//!
+//! Instances compete for the [`LEADER_LEASE`] in the shared storage. The instance holding it
+//! relays, all others wait on standby and try to acquire the lease every third of its duration.
+//! The leader renews the lease at the same interval. If it fails to do so before the lease
+//! expires it considers the lease lost and shuts down, so that a standby instance takes over
+//! once the lease expired in the database. On shutdown the leader releases the lease, letting a
+//! standby instance take over right away.
+//!
+//! Relaying twice is prevented by the storage itself: it only writes submission states while
+//! its instance holds the lease, and every submission is recorded before it is broadcast.
+
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use astria_eyre::eyre::{
+    self,
+    bail,
+};
+use tokio::time::{
+    sleep,
+    timeout_at,
+    Instant,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{
+    info,
+    warn,
+};
+
+use super::State;
+use crate::storage::{
+    Storage,
+    LEADER_LEASE,
+};
+
+/// Which instance competes for the lease, and how long the lease lasts.
+#[derive(Clone, Debug)]
+pub(crate) struct Settings {
+    pub(crate) instance_id: String,
+    pub(crate) lease_duration: Duration,
+}
+
+pub(super) struct LeaderElection {
+    storage: Arc<dyn Storage>,
+    settings: Settings,
+    state: Arc<State>,
+}
+
+impl LeaderElection {
+    pub(super) fn new(storage: Arc<dyn Storage>, settings: Settings, state: Arc<State>) -> Self {
+        Self {
+            storage,
+            settings,
+            state,
+        }
+    }
+
+    /// Waits on standby until this instance acquired the lease, returning when the lease
+    /// expires unless renewed.
+    ///
+    /// Returns `None` if `shutdown_token` was cancelled first. Failures to reach the storage are
+    /// logged and retried.
+    pub(super) async fn acquire(&self, shutdown_token: &CancellationToken) -> Option<Instant> {
+        self.state.set_standby(true);
+        report_leader(false);
+        info!(
+            instance_id = %self.settings.instance_id,
+            "waiting on standby until this instance acquires the leader lease",
+        );
+        loop {
+            let attempted_at = Instant::now();
+            match self.try_acquire().await {
+                Ok(true) => {
+                    info!(instance_id = %self.settings.instance_id, "acquired leader lease");
+                    metrics::counter!(
+                        crate::metrics_init::LEADERSHIP_CHANGES,
+                        "change" => "acquired",
+                    )
+                    .increment(1);
+                    report_leader(true);
+                    self.state.set_standby(false);
+                    return Some(attempted_at + self.settings.lease_duration);
+                }
+                Ok(false) => {}
+                Err(error) => warn!(%error, "failed trying to acquire leader lease; retrying"),
+            }
+            tokio::select! {
+                () = shutdown_token.cancelled() => return None,
+                () = sleep(self.renewal_interval()) => {}
+            }
+        }
+    }
+
+    /// Renews the lease until `shutdown_token` is cancelled, then releases it.
+    ///
+    /// `expires_at` is when the lease acquired by [`LeaderElection::acquire`] expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if another instance took over the lease, or if the lease could not be
+    /// renewed before it expired.
+    pub(super) async fn hold(
+        self,
+        mut expires_at: Instant,
+        shutdown_token: CancellationToken,
+    ) -> eyre::Result<()> {
+        loop {
+            tokio::select! {
+                () = shutdown_token.cancelled() => break,
+                () = sleep(self.renewal_interval()) => {}
+            }
+            let attempted_at = Instant::now();
+            // The lease is considered lost once it expired, even if a renewal is still pending.
+            match timeout_at(expires_at, self.try_acquire()).await {
+                Ok(Ok(true)) => expires_at = attempted_at + self.settings.lease_duration,
+                Ok(Ok(false)) => {
+                    self.report_lost();
+                    bail!("another relayer instance took over the leader lease");
+                }
+                Ok(Err(error)) if Instant::now() < expires_at => {
+                    warn!(%error, "failed renewing leader lease; retrying");
+                }
+                Ok(Err(_)) | Err(_) => {
+                    self.report_lost();
+                    bail!("failed renewing the leader lease before it expired");
+                }
+            }
+        }
+        report_leader(false);
+        match self
+            .storage
+            .release_lease(LEADER_LEASE, &self.settings.instance_id)
+            .await
+        {
+            Ok(()) => {
+                info!("released leader lease");
+                metrics::counter!(
+                    crate::metrics_init::LEADERSHIP_CHANGES,
+                    "change" => "released",
+                )
+                .increment(1);
+            }
+            Err(error) => warn!(
+                %error,
+                "failed releasing leader lease; another instance can only take over once it \
+                 expired",
+            ),
+        }
+        Ok(())
+    }
+
+    async fn try_acquire(&self) -> eyre::Result<bool> {
+        self.storage
+            .try_acquire_lease(
+                LEADER_LEASE,
+                &self.settings.instance_id,
+                self.settings.lease_duration,
+            )
+            .await
+    }
+
+    fn renewal_interval(&self) -> Duration {
+        self.settings.lease_duration / 3
+    }
+
+    fn report_lost(&self) {
+        warn!(instance_id = %self.settings.instance_id, "lost leader lease");
+        metrics::counter!(crate::metrics_init::LEADERSHIP_CHANGES, "change" => "lost").increment(1);
+        report_leader(false);
+    }
+}
+
+fn report_leader(is_leader: bool) {
+    metrics::gauge!(crate::metrics_init::IS_LEADER).set(if is_leader { 1.0 } else { 0.0 });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    /// Grants the lease according to `grants`, one entry per attempt.
+    #[derive(Debug)]
+    struct Leases {
+        grants: Mutex<Vec<bool>>,
+        released: Mutex<bool>,
+    }
+
+    impl Leases {
+        fn new(grants: &[bool]) -> Arc<Self> {
+            Arc::new(Self {
+                grants: Mutex::new(grants.iter().rev().copied().collect()),
+                released: Mutex::new(false),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Storage for Leases {
+        async fn get(&self, _key: &str) -> eyre::Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        async fn put(&self, _key: &str, _value: Vec<u8>) -> eyre::Result<()> {
+            Ok(())
+        }
+
+        async fn try_acquire_lease(
+            &self,
+            _name: &str,
+            _holder: &str,
+            _duration: Duration,
+        ) -> eyre::Result<bool> {
+            Ok(self.grants.lock().unwrap().pop().unwrap_or(true))
+        }
+
+        async fn release_lease(&self, _name: &str, _holder: &str) -> eyre::Result<()> {
+            *self.released.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    fn election(leases: Arc<Leases>) -> LeaderElection {
+        LeaderElection::new(
+            leases,
+            Settings {
+                instance_id: "a".to_string(),
+                lease_duration: Duration::from_secs(3),
+            },
+            Arc::new(State::new()),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn standby_acquires_lease_once_free() {
+        let election = election(Leases::new(&[false, false, true]));
+        let started = Instant::now();
+        let expires_at = election
+            .acquire(&CancellationToken::new())
+            .await
+            .expect("the lease is eventually free");
+        // Acquired on the third attempt, one renewal interval apart.
+        assert_eq!(Duration::from_secs(5), expires_at - started);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn leader_fails_once_lease_was_taken_over() {
+        let leases = Leases::new(&[true, false]);
+        let election = election(leases.clone());
+        let expires_at = election.acquire(&CancellationToken::new()).await.unwrap();
+        let _ = election
+            .hold(expires_at, CancellationToken::new())
+            .await
+            .expect_err("another instance took over the lease");
+        assert!(!*leases.released.lock().unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn leader_releases_lease_on_shutdown() {
+        let leases = Leases::new(&[true]);
+        let election = election(leases.clone());
+        let expires_at = election.acquire(&CancellationToken::new()).await.unwrap();
+        let shutdown_token = CancellationToken::new();
+        shutdown_token.cancel();
+        election.hold(expires_at, shutdown_token).await.unwrap();
+        assert!(*leases.released.lock().unwrap());
+    }
+}
@@ -1,105 +1,184 @@
 use std::{
-    path::{
-        Path,
-        PathBuf,
-    },
+    collections::HashMap,
     sync::Arc,
     time::Duration,
 };
 
-use astria_core::{
-    generated::sequencerblock::v1alpha1::sequencer_service_client::SequencerServiceClient,
-    sequencerblock::v1alpha1::SequencerBlock,
-};
+use astria_core::sequencerblock::v1alpha1::BlobFormat;
 use astria_eyre::eyre::{
     self,
-    bail,
-    eyre,
     WrapErr as _,
 };
-use futures::{
-    future::{
-        BoxFuture,
-        Fuse,
-        FusedFuture as _,
-    },
-    FutureExt as _,
-};
-use sequencer_client::{
-    tendermint::block::Height as SequencerHeight,
-    HttpClient as SequencerClient,
-};
 use tokio::{
     select,
-    sync::{
-        mpsc::error::TrySendError,
-        watch,
-    },
-    task::JoinHandle,
+    sync::watch,
 };
-use tokio_stream::StreamExt;
 use tokio_util::sync::CancellationToken;
-use tonic::transport::Channel;
 use tracing::{
     debug,
     error,
-    field::DisplayValue,
-    info,
     instrument,
-    warn,
 };
 
 use crate::{
-    validator::Validator,
+    alerts::Alerter,
+    attestations::AttestationStore,
+    config::{
+        EmptyBlockPolicy,
+        RollupQuotas,
+        SequencerNamespaces,
+    },
+    costs::CostLedger,
+    height_index::HeightIndex,
+    storage,
+    supervisor::{
+        RestartPolicy,
+        Supervisor,
+    },
     IncludeRollup,
 };
 
+mod auxiliary;
 mod builder;
 mod celestia_client;
+mod heartbeat;
+mod lag;
+mod latency;
+mod leader;
+mod network;
+mod preflight;
 mod read;
 mod state;
 mod submission;
+mod validation;
 mod write;
 
+pub(crate) use auxiliary::{
+    AuxiliaryBlobs,
+    InsertError as AuxiliaryBlobInsertError,
+    PendingAuxiliaryBlob,
+};
 pub(crate) use builder::Builder;
+pub(crate) use celestia_client::ErrorPolicy as CelestiaErrorPolicy;
 use celestia_client::{
     BuilderError,
     CelestiaClientBuilder,
     CelestiaKeys,
+    Inclusion,
     TrySubmitError,
 };
+use heartbeat::Heartbeat;
+pub(crate) use lag::LagThresholds;
+use lag::LagWatchdog;
+pub(crate) use latency::{
+    BlockLatency,
+    LatencyTracker,
+    OverdueBlock,
+    SubmissionDeadline,
+};
+pub(crate) use leader::Settings as LeaderElectionSettings;
+use leader::LeaderElection;
+use network::SequencerNetwork;
+pub(crate) use preflight::{
+    CelestiaConnection,
+    CelestiaPreflight,
+};
 use state::State;
 pub(crate) use state::StateSnapshot;
+pub(crate) use submission::ensure_valid_submission_files;
+pub(crate) use write::{
+    BatchingBounds,
+    RollupSigningKey,
+};
 
-use self::submission::SubmissionState;
+use self::submission::{
+    IdempotencyKey,
+    SubmissionState,
+    SubmissionStates,
+};
 
 pub(crate) struct Relayer {
     /// A token to notify relayer that it should shut down.
     shutdown_token: CancellationToken,
 
-    /// The client used to query the sequencer cometbft endpoint.
-    sequencer_cometbft_client: SequencerClient,
-
-    /// The client used to poll the sequencer via the sequencer gRPC API.
-    sequencer_grpc_client: SequencerServiceClient<Channel>,
+    /// The sequencer network configured at the top level of the relayer config.
+    primary_network: SequencerNetwork,
 
-    /// The poll period defines the fixed interval at which the sequencer is polled.
-    sequencer_poll_period: Duration,
+    /// The sequencer networks relayed in addition to the primary network, sharing its Celestia
+    /// account and batching.
+    additional_networks: Vec<SequencerNetwork>,
 
     /// The gRPC client for submitting sequencer blocks to celestia.
     celestia_client_builder: CelestiaClientBuilder,
 
-    /// If this is set, only relay blocks to DA which are proposed by the same validator key.
-    validator: Option<Validator>,
-
     /// The rollups whose data should be included in submissions.
     rollup_filter: IncludeRollup,
 
+    /// The Celestia namespaces under which the blocks of each sequencer network are written.
+    sequencer_namespaces: SequencerNamespaces,
+
+    /// The bounds within which the batching of blobs is adapted to Celestia gas prices and
+    /// inclusion latencies.
+    batching_bounds: BatchingBounds,
+
+    /// The maximum number of batches of blobs submitted to Celestia at the same time.
+    max_concurrent_submissions: usize,
+
+    /// The format in which sequencer header and rollup blobs are written to Celestia.
+    blob_format: BlobFormat,
+
+    /// What is written to Celestia for sequencer blocks without rollup data.
+    empty_block_policy: EmptyBlockPolicy,
+
+    /// The number of most recently included rollup blobs remembered to deduplicate repeated
+    /// rollup blobs. 0 disables deduplication.
+    rollup_blob_dedup_capacity: usize,
+
+    /// The keys with which the blobs of rollups are signed on their behalf.
+    rollup_signing_keys: write::RollupSigningKeys,
+
+    /// The maximum number of bytes written for individual rollups per sequencer block.
+    rollup_quotas: RollupQuotas,
+
+    /// Creates the signed heartbeats added to submissions, if enabled.
+    heartbeat: Option<Heartbeat>,
+
     /// A watch channel to track the state of the relayer. Used by the API service.
+    ///
+    /// Only reflects the primary network.
     state: Arc<State>,
 
-    pre_submit_path: PathBuf,
-    post_submit_path: PathBuf,
+    /// Tracks how long sequencer blocks take from being observed until they are confirmed on
+    /// Celestia. Used by the API service.
+    latencies: Arc<LatencyTracker>,
+
+    /// The operator defined auxiliary blobs waiting to be submitted with their sequencer
+    /// blocks. Filled by the API service.
+    auxiliary_blobs: Arc<AuxiliaryBlobs>,
+
+    /// Raises alerts when relaying needs the attention of an operator.
+    alerts: Alerter,
+
+    /// Records the data posted and fees paid for every confirmed submission, if configured.
+    /// Read by the API service.
+    cost_ledger: Option<CostLedger>,
+
+    /// Records the Celestia height of every confirmed sequencer block, if configured. Read by
+    /// the API service.
+    height_index: Option<Arc<HeightIndex>>,
+
+    /// Records a signed attestation of every confirmed submission, if configured. Read by the
+    /// API service.
+    attestations: Option<Arc<AttestationStore>>,
+
+    /// The sequencer height lags above which the relayer warns and reports not being ready.
+    lag_thresholds: LagThresholds,
+
+    /// Opens the storage holding the submission states of all sequencer networks.
+    storage: storage::Builder,
+
+    /// The settings of the leader election between instances sharing the storage, if enabled.
+    leader_election: Option<LeaderElectionSettings>,
 }
 
 impl Relayer {
@@ -107,6 +186,26 @@ impl Relayer {
         self.state.subscribe()
     }
 
+    pub(crate) fn latencies(&self) -> Arc<LatencyTracker> {
+        Arc::clone(&self.latencies)
+    }
+
+    pub(crate) fn auxiliary_blobs(&self) -> Arc<AuxiliaryBlobs> {
+        Arc::clone(&self.auxiliary_blobs)
+    }
+
+    pub(crate) fn cost_ledger(&self) -> Option<CostLedger> {
+        self.cost_ledger.clone()
+    }
+
+    pub(crate) fn height_index(&self) -> Option<Arc<HeightIndex>> {
+        self.height_index.clone()
+    }
+
+    pub(crate) fn attestations(&self) -> Option<Arc<AttestationStore>> {
+        self.attestations.clone()
+    }
+
     /// Runs the relayer worker.
     ///
     /// # Errors
@@ -115,219 +214,197 @@ impl Relayer {
     /// failed catastrophically (after `u32::MAX` retries).
     #[instrument(skip_all)]
     pub(crate) async fn run(self) -> eyre::Result<()> {
-        let submission_state = read_submission_state(&self.pre_submit_path, &self.post_submit_path)
-            .await
-            .wrap_err("failed reading submission state from files")?;
-
-        let last_submitted_sequencer_height = submission_state.last_submitted_height();
-
-        let mut latest_height_stream = {
-            use sequencer_client::StreamLatestHeight as _;
-            self.sequencer_cometbft_client
-                .stream_latest_height(self.sequencer_poll_period)
-        };
-
-        let (submitter_task, submitter) = spawn_submitter(
-            self.celestia_client_builder.clone(),
-            self.rollup_filter.clone(),
-            self.state.clone(),
-            submission_state,
-            self.shutdown_token.clone(),
-        );
-
-        let mut block_stream = read::BlockStream::builder()
-            .block_time(self.sequencer_poll_period)
-            .client(self.sequencer_grpc_client.clone())
-            .set_last_fetched_height(last_submitted_sequencer_height)
-            .state(self.state.clone())
-            .build();
-
-        // future to forward a sequencer block to the celestia-submission-task.
-        // gets set in the select-loop if the task is at capacity.
-        let mut forward_once_free: Fuse<
-            BoxFuture<Result<(), tokio::sync::mpsc::error::SendError<SequencerBlock>>>,
-        > = Fuse::terminated();
-
-        self.state.set_ready();
-
-        let reason = loop {
-            select!(
-                biased;
-
-                () = self.shutdown_token.cancelled() => {
-                    info!("received shutdown signal");
-                    break Ok("shutdown signal received");
-                }
-
-                res = &mut forward_once_free, if !forward_once_free.is_terminated() => {
-                    // XXX: exiting because submitter only returns an error after u32::MAX
-                    // retries, which is practically infinity.
-                    if res.is_err() {
-                        break Err(eyre!("submitter exited unexpectedly while trying to forward block"));
-                    }
-                    block_stream.resume();
-                    debug!("block stream resumed");
-                }
-
-                Some(res) = latest_height_stream.next() => {
-                    match res {
-                        Ok(height) => {
-                            self.state.set_latest_observed_sequencer_height(height.value());
-                            debug!(%height, "received latest height from sequencer");
-                            block_stream.set_latest_sequencer_height(height);
-                        }
-                        Err(error) => {
-                            metrics::counter!(crate::metrics_init::SEQUENCER_HEIGHT_FETCH_FAILURE_COUNT)
-                                .increment(1);
-                            self.state.set_sequencer_connected(false);
-                            warn!(
-                                %error,
-                                "failed fetching latest height from sequencer; waiting until next tick",
-                            );
-                        }
-                    }
-                }
-
-                Some((height, fetch_result)) = block_stream.next() => {
-                    let block = match fetch_result.wrap_err_with(||
-                        format!(
-                            "relayer ultimately failed fetching sequencer block at height {height}"
-                    )) {
-                        // XXX: exiting because the fetch in block_stream errors after u32::MAX
-                        // retries, which is practically infinity.
-                        Err(err) => break Err(err),
-                        Ok(block) => block,
+        let Self {
+            shutdown_token,
+            primary_network,
+            additional_networks,
+            celestia_client_builder,
+            rollup_filter,
+            sequencer_namespaces,
+            batching_bounds,
+            max_concurrent_submissions,
+            blob_format,
+            empty_block_policy,
+            rollup_blob_dedup_capacity,
+            rollup_signing_keys,
+            rollup_quotas,
+            heartbeat,
+            state,
+            latencies,
+            auxiliary_blobs,
+            alerts,
+            cost_ledger,
+            height_index,
+            attestations,
+            lag_thresholds,
+            storage,
+            leader_election,
+        } = self;
+
+        let mut supervisor = Supervisor::new();
+        if !lag_thresholds.is_disabled() {
+            let lag_watchdog_token = shutdown_token.child_token();
+            let state = state.clone();
+            let alerts = alerts.clone();
+            supervisor.spawn_restarting(
+                "lag watchdog",
+                lag_watchdog_token.clone(),
+                Duration::from_secs(1),
+                RestartPolicy::OnFailure {
+                    max_restarts: 10,
+                    backoff: Duration::from_secs(1),
+                },
+                move || {
+                    let lag_watchdog = LagWatchdog {
+                        state: state.clone(),
+                        thresholds: lag_thresholds,
+                        alerts: alerts.clone(),
+                        shutdown_token: lag_watchdog_token.clone(),
                     };
-                    self.state.set_latest_fetched_sequencer_height(height.value());
-                    if let Err(err) = self.forward_block_for_submission(
-                        height,
-                        block,
-                        &mut block_stream,
-                        submitter.clone(),
-                        &mut forward_once_free,
-                    ).wrap_err("submitter exited unexpectly while trying to forward block") {
-                        // XXX: exiting because there is no logic to restart the blob-submitter task.
-                        // With the current implementation of the task it should also never go down
-                        // unless it has exhausted all u32::MAX attempts to submit to Celestia and
-                        // ultimately failed (after what's practically years of trying...).
-                        break Err(err);
+                    async move {
+                        lag_watchdog.run().await;
+                        Ok(())
                     }
-                }
+                },
             );
-        };
-
-        match &reason {
-            Ok(reason) => info!(reason, "starting shutdown"),
-            Err(reason) => error!(%reason, "starting shutdown"),
         }
 
-        debug!("waiting for Celestia submission task to exit");
-        if let Err(error) = submitter_task.await {
-            error!(%error, "Celestia submission task failed while waiting for it to exit before shutdown");
+        let storage = storage.open().await.wrap_err("failed opening storage")?;
+        if let Some(settings) = leader_election {
+            let election = LeaderElection::new(storage.clone(), settings, state.clone());
+            let Some(expires_at) = election.acquire(&shutdown_token).await else {
+                debug!("shutdown requested while on standby");
+                supervisor.shutdown().await;
+                return Ok(());
+            };
+            // Not a child of the relayer's shutdown token: the lease must only be released once
+            // the submitter, spawned after it, has shut down.
+            let lease_token = CancellationToken::new();
+            supervisor.spawn(
+                "leader lease",
+                lease_token.clone(),
+                Duration::from_secs(2),
+                election.hold(expires_at, lease_token),
+            );
+        }
+        let primary_submission_state = primary_network
+            .read_submission_state(storage.clone())
+            .await
+            .wrap_err("failed reading submission state from storage")?;
+        let mut networks = vec![(
+            primary_submission_state.last_submitted_height(),
+            primary_network,
+        )];
+        let mut additional_submission_states = HashMap::new();
+        for network in additional_networks {
+            let chain_id = network
+                .chain_id
+                .clone()
+                .expect("additional sequencer networks always have a chain ID");
+            let submission_state = network
+                .read_submission_state(storage.clone())
+                .await
+                .wrap_err_with(|| {
+                    format!("failed reading submission state of sequencer network `{chain_id}`")
+                })?;
+            networks.push((submission_state.last_submitted_height(), network));
+            additional_submission_states.insert(chain_id, submission_state);
         }
 
-        reason.map(|_| ())
-    }
-
-    fn report_validator(&self) -> Option<DisplayValue<ReportValidator<'_>>> {
-        self.validator
-            .as_ref()
-            .map(ReportValidator)
-            .map(tracing::field::display)
-    }
-
-    fn block_does_not_match_validator(&self, block: &SequencerBlock) -> bool {
-        self.validator
-            .as_ref()
-            .is_some_and(|val| &val.address != block.header().proposer_address())
-    }
+        let submitter = spawn_submitter(
+            &mut supervisor,
+            celestia_client_builder,
+            write::ConversionSettings {
+                rollup_filter,
+                sequencer_namespaces,
+                auxiliary_blobs,
+                blob_format,
+                rollup_signing_keys,
+                rollup_quotas,
+                empty_block_policy,
+            },
+            batching_bounds,
+            max_concurrent_submissions,
+            rollup_blob_dedup_capacity,
+            heartbeat,
+            state,
+            latencies,
+            alerts,
+            cost_ledger,
+            height_index,
+            attestations,
+            SubmissionStates::new(primary_submission_state, additional_submission_states),
+            shutdown_token.child_token(),
+        );
 
-    #[instrument(skip_all, fields(%height))]
-    fn forward_block_for_submission(
-        &self,
-        height: SequencerHeight,
-        block: SequencerBlock,
-        block_stream: &mut read::BlockStream,
-        submitter: write::BlobSubmitterHandle,
-        forward: &mut Fuse<
-            BoxFuture<Result<(), tokio::sync::mpsc::error::SendError<SequencerBlock>>>,
-        >,
-    ) -> eyre::Result<()> {
-        assert!(
-            forward.is_terminated(),
-            "block stream must be paused and not yield blocks when the blob submitter is \
-             congested and this future is in-flight",
+        let relaying = futures::future::try_join_all(networks.into_iter().map(
+            |(last_submitted_height, network)| {
+                network.relay(last_submitted_height, submitter.clone())
+            },
+        ));
+        // The supervised tasks only exit on their own if they failed, or once shutdown started.
+        let reason = select!(
+            reason = relaying => reason.map(|_| ()),
+            (task_name, outcome) = supervisor.exited() => outcome
+                .and_then(|()| Err(eyre::eyre!("task exited unexpectedly")))
+                .wrap_err_with(|| format!("supervised task `{task_name}` exited")),
         );
 
-        if self.block_does_not_match_validator(&block) {
-            info!(
-                address.validator = self.report_validator(),
-                address.block_proposer = %block.header().proposer_address(),
-                "block proposer does not match internal validator; dropping",
-            );
-            return Ok(());
+        if let Err(reason) = &reason {
+            error!(%reason, "starting shutdown");
         }
-        if let Err(error) = submitter.try_send(block) {
-            debug!(
-                // Just print the error directly: TrySendError has no cause chain.
-                %error,
-                "failed forwarding sequencer block to submitter; \
-                pausing block stream and scheduling for later submission",
-            );
-            block_stream.pause();
-            debug!("block stream paused");
-
-            match error {
-                TrySendError::Full(block) => {
-                    *forward = async move { submitter.send(block).await }.boxed().fuse();
-                }
-                TrySendError::Closed(..) => bail!("blob submitter has shut down unexpectedly"),
-            }
-        }
-        Ok(())
-    }
-}
 
-async fn read_submission_state<P1: AsRef<Path>, P2: AsRef<Path>>(
-    pre: P1,
-    post: P2,
-) -> eyre::Result<SubmissionState> {
-    const LEANIENT_CONSISTENCY_CHECK: bool = true;
-    let pre = pre.as_ref().to_path_buf();
-    let post = post.as_ref().to_path_buf();
-    crate::utils::flatten(
-        tokio::task::spawn_blocking(move || {
-            SubmissionState::from_paths::<LEANIENT_CONSISTENCY_CHECK, _, _>(pre, post)
-        })
-        .await,
-    )
-    .wrap_err(
-        "failed reading submission state from the configured pre- and post-submit files. Refer to \
-         the values documented in `local.env.example` of the astria-sequencer-relayer service",
-    )
+        debug!("shutting down Celestia submission task and lag watchdog");
+        drop(submitter);
+        supervisor.shutdown().await;
+
+        reason
+    }
 }
 
+/// Spawns the Celestia submission task under `supervisor`, returning a handle to send it blocks.
+// allow: mirrors the arguments of `write::BlobSubmitter::new`.
+#[allow(clippy::too_many_arguments)]
 fn spawn_submitter(
+    supervisor: &mut Supervisor,
     client_builder: CelestiaClientBuilder,
-    rollup_filter: IncludeRollup,
+    conversion_settings: write::ConversionSettings,
+    batching_bounds: BatchingBounds,
+    max_concurrent_submissions: usize,
+    rollup_blob_dedup_capacity: usize,
+    heartbeat: Option<Heartbeat>,
     state: Arc<State>,
-    submission_state: SubmissionState,
+    latencies: Arc<LatencyTracker>,
+    alerts: Alerter,
+    cost_ledger: Option<CostLedger>,
+    height_index: Option<Arc<HeightIndex>>,
+    attestations: Option<Arc<AttestationStore>>,
+    submission_states: SubmissionStates,
     shutdown_token: CancellationToken,
-) -> (JoinHandle<eyre::Result<()>>, write::BlobSubmitterHandle) {
+) -> write::BlobSubmitterHandle {
     let (submitter, handle) = write::BlobSubmitter::new(
         client_builder,
-        rollup_filter,
+        conversion_settings,
+        batching_bounds,
+        max_concurrent_submissions,
+        rollup_blob_dedup_capacity,
+        heartbeat,
         state,
-        submission_state,
+        latencies,
+        alerts,
+        cost_ledger,
+        height_index,
+        attestations,
+        submission_states,
+        shutdown_token.clone(),
+    );
+    // Giving the submitter 20 of the 25 seconds the relayer has to shut down, so that it can
+    // finish its submissions in flight.
+    supervisor.spawn(
+        "celestia submitter",
         shutdown_token,
+        Duration::from_secs(20),
+        submitter.run(),
     );
-    (tokio::spawn(submitter.run()), handle)
-}
-
-struct ReportValidator<'a>(&'a Validator);
-
-impl<'a> std::fmt::Display for ReportValidator<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}", self.0.address))
-    }
+    handle
 }
@@ -0,0 +1,378 @@
+//! Reading sequencer blocks from a single sequencer network and forwarding them for submission.
+//!
+//! The relayer always reads from the sequencer network configured at the top level of its
+//! config, called the primary network. In aggregation mode it additionally reads from the
+//! networks listed in `additional_sequencer_networks_file`. Every network is read
+//! independently, but all of them forward their blocks to the same blob submitter, so that
+//! blocks of all networks are batched into the same Celestia transactions.
+
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use astria_core::{
+    generated::sequencerblock::v1alpha1::sequencer_service_client::SequencerServiceClient,
+    sequencerblock::v1alpha1::SequencerBlock,
+    signers::{
+        KnownSigners,
+        SignerDisplay,
+    },
+};
+use astria_eyre::eyre::{
+    self,
+    bail,
+    eyre,
+    WrapErr as _,
+};
+use futures::{
+    future::{
+        BoxFuture,
+        Fuse,
+        FusedFuture as _,
+    },
+    FutureExt as _,
+};
+use sequencer_client::{
+    tendermint::{
+        block::Height as SequencerHeight,
+        chain,
+    },
+    HttpClient as SequencerClient,
+};
+use tokio::{
+    select,
+    sync::mpsc::error::TrySendError,
+};
+use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
+use tonic::transport::Channel;
+use tracing::{
+    debug,
+    error,
+    field::DisplayValue,
+    info,
+    instrument,
+    warn,
+};
+
+use super::{
+    read,
+    validation,
+    write,
+    LatencyTracker,
+    State,
+    SubmissionState,
+};
+use crate::{
+    alerts::{
+        Alert,
+        Alerter,
+    },
+    storage::Storage,
+    validator::Validator,
+};
+
+pub(super) struct SequencerNetwork {
+    /// The chain ID blocks of this network must have, if known ahead of time. Always set for
+    /// additional networks, and never for the primary network.
+    pub(super) chain_id: Option<chain::Id>,
+
+    /// The client used to query the sequencer cometbft endpoint.
+    pub(super) sequencer_cometbft_client: SequencerClient,
+
+    /// The client used to poll the sequencer via the sequencer gRPC API.
+    pub(super) sequencer_grpc_client: SequencerServiceClient<Channel>,
+
+    /// The poll period defines the fixed interval at which the sequencer is polled.
+    pub(super) sequencer_poll_period: Duration,
+
+    /// If this is set, blocks are fetched from the sequencer cometbft endpoint and converted to
+    /// sequencer blocks instead of being fetched via the sequencer gRPC API.
+    pub(super) pull_blocks_from_cometbft: bool,
+
+    /// If this is set, only relay blocks to DA which are proposed by the same validator key.
+    pub(super) validator: Option<Validator>,
+
+    /// A watch channel to track the state of reading from this network.
+    pub(super) state: Arc<State>,
+
+    /// Tracks how long sequencer blocks take from being observed until they are confirmed on
+    /// Celestia.
+    pub(super) latencies: Arc<LatencyTracker>,
+
+    /// Raises alerts when blocks read from this network fail verification.
+    pub(super) alerts: Alerter,
+
+    /// Labels of sequencer validators, used to report block proposers.
+    pub(super) known_signers: Arc<KnownSigners>,
+
+    /// A token to notify the network that it should stop reading blocks.
+    pub(super) shutdown_token: CancellationToken,
+
+    pub(super) pre_submit_path: PathBuf,
+    pub(super) post_submit_path: PathBuf,
+}
+
+impl SequencerNetwork {
+    /// Reads the submission state of this network from `storage`.
+    ///
+    /// The file backend stores the state in the network's pre- and post-submit files.
+    pub(super) async fn read_submission_state(
+        &self,
+        storage: Arc<dyn Storage>,
+    ) -> eyre::Result<SubmissionState> {
+        const LEANIENT_CONSISTENCY_CHECK: bool = true;
+        let keys = storage.submission_keys(
+            self.chain_id.as_ref().map(chain::Id::as_str),
+            &self.pre_submit_path,
+            &self.post_submit_path,
+        );
+        SubmissionState::read::<LEANIENT_CONSISTENCY_CHECK>(storage, keys)
+            .await
+            .wrap_err(
+                "failed reading submission state from the configured storage. Refer to the values \
+                 documented in `local.env.example` of the astria-sequencer-relayer service",
+            )
+    }
+
+    /// Reads blocks from the sequencer network starting after `last_submitted_sequencer_height`
+    /// and forwards them to `submitter` until a shutdown is signaled.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors if sequencer block fetch failed catastrophically (after `u32::MAX`
+    /// retries), or if the blob submitter exited.
+    #[instrument(skip_all, fields(chain_id = self.chain_id.as_ref().map(tracing::field::display)))]
+    pub(super) async fn relay(
+        self,
+        last_submitted_sequencer_height: Option<SequencerHeight>,
+        submitter: write::BlobSubmitterHandle,
+    ) -> eyre::Result<()> {
+        if let Some(height) = last_submitted_sequencer_height {
+            self.state
+                .set_latest_confirmed_sequencer_height(height.value());
+        }
+
+        let mut latest_height_stream = {
+            use sequencer_client::StreamLatestHeight as _;
+            self.sequencer_cometbft_client
+                .stream_latest_height(self.sequencer_poll_period)
+        };
+
+        let mut block_stream = read::BlockStream::builder()
+            .block_time(self.sequencer_poll_period)
+            .client(self.sequencer_grpc_client.clone())
+            .pull_from_cometbft(
+                self.pull_blocks_from_cometbft
+                    .then(|| self.sequencer_cometbft_client.clone()),
+            )
+            .set_last_fetched_height(last_submitted_sequencer_height)
+            .state(self.state.clone())
+            .build();
+
+        // future to forward a sequencer block to the celestia-submission-task.
+        // gets set in the select-loop if the task is at capacity.
+        let mut forward_once_free: Fuse<
+            BoxFuture<Result<(), tokio::sync::mpsc::error::SendError<SequencerBlock>>>,
+        > = Fuse::terminated();
+
+        self.state.set_ready();
+
+        let reason = loop {
+            select!(
+                biased;
+
+                () = self.shutdown_token.cancelled() => {
+                    info!("received shutdown signal");
+                    break Ok("shutdown signal received");
+                }
+
+                res = &mut forward_once_free, if !forward_once_free.is_terminated() => {
+                    // XXX: exiting because submitter only returns an error after u32::MAX
+                    // retries, which is practically infinity.
+                    if res.is_err() {
+                        break Err(eyre!("submitter exited unexpectedly while trying to forward block"));
+                    }
+                    report_ingest_queue_depth(&submitter);
+                    block_stream.resume();
+                    debug!("block stream resumed");
+                }
+
+                Some(res) = latest_height_stream.next() => {
+                    match res {
+                        Ok(height) => {
+                            self.state.set_latest_observed_sequencer_height(height.value());
+                            self.state.report_sequencer_height_lag();
+                            debug!(%height, "received latest height from sequencer");
+                            block_stream.set_latest_sequencer_height(height);
+                        }
+                        Err(error) => {
+                            metrics::counter!(crate::metrics_init::SEQUENCER_HEIGHT_FETCH_FAILURE_COUNT)
+                                .increment(1);
+                            self.state.set_sequencer_connected(false);
+                            warn!(
+                                %error,
+                                "failed fetching latest height from sequencer; waiting until next tick",
+                            );
+                        }
+                    }
+                }
+
+                Some((height, fetch_result)) = block_stream.next() => {
+                    let block = match fetch_result.wrap_err_with(||
+                        format!(
+                            "relayer ultimately failed fetching sequencer block at height {height}"
+                    )) {
+                        // XXX: exiting because the fetch in block_stream errors after u32::MAX
+                        // retries, which is practically infinity.
+                        Err(err) => break Err(err),
+                        Ok(block) => block,
+                    };
+                    self.state.set_latest_fetched_sequencer_height(height.value());
+                    if let Err(err) = self.forward_block_for_submission(
+                        height,
+                        block,
+                        &mut block_stream,
+                        submitter.clone(),
+                        &mut forward_once_free,
+                    ).wrap_err("submitter exited unexpectly while trying to forward block") {
+                        // XXX: exiting because there is no logic to restart the blob-submitter task.
+                        // With the current implementation of the task it should also never go down
+                        // unless it has exhausted all u32::MAX attempts to submit to Celestia and
+                        // ultimately failed (after what's practically years of trying...).
+                        break Err(err);
+                    }
+                }
+            );
+        };
+
+        match &reason {
+            Ok(reason) => info!(reason, "stopped reading from sequencer network"),
+            Err(reason) => error!(%reason, "stopped reading from sequencer network"),
+        }
+        reason.map(|_| ())
+    }
+
+    fn report_validator(&self) -> Option<DisplayValue<SignerDisplay<'_>>> {
+        self.validator
+            .as_ref()
+            .map(|validator| self.known_signers.display(&validator.address))
+            .map(tracing::field::display)
+    }
+
+    /// Returns the label of the proposer of `block` to use in metrics.
+    ///
+    /// Proposers without a label are reported as `unknown` to bound the number of time series.
+    fn proposer_metric_label(&self, block: &SequencerBlock) -> String {
+        self.known_signers
+            .label(block.header().proposer_address())
+            .unwrap_or("unknown")
+            .to_string()
+    }
+
+    fn block_does_not_match_validator(&self, block: &SequencerBlock) -> bool {
+        self.validator
+            .as_ref()
+            .is_some_and(|val| &val.address != block.header().proposer_address())
+    }
+
+    fn block_does_not_match_chain_id(&self, block: &SequencerBlock) -> bool {
+        self.chain_id
+            .as_ref()
+            .is_some_and(|chain_id| chain_id != block.header().chain_id())
+    }
+
+    #[instrument(skip_all, fields(%height))]
+    fn forward_block_for_submission(
+        &self,
+        height: SequencerHeight,
+        block: SequencerBlock,
+        block_stream: &mut read::BlockStream,
+        submitter: write::BlobSubmitterHandle,
+        forward: &mut Fuse<
+            BoxFuture<Result<(), tokio::sync::mpsc::error::SendError<SequencerBlock>>>,
+        >,
+    ) -> eyre::Result<()> {
+        assert!(
+            forward.is_terminated(),
+            "block stream must be paused and not yield blocks when the blob submitter is \
+             congested and this future is in-flight",
+        );
+
+        metrics::counter!(
+            crate::metrics_init::SEQUENCER_BLOCKS_BY_PROPOSER,
+            "proposer" => self.proposer_metric_label(&block),
+        )
+        .increment(1);
+
+        if self.block_does_not_match_validator(&block) {
+            let proposer = self.known_signers.display(block.header().proposer_address());
+            info!(
+                address.validator = self.report_validator(),
+                address.block_proposer = %proposer,
+                "block proposer does not match internal validator; dropping",
+            );
+            return Ok(());
+        }
+        if self.block_does_not_match_chain_id(&block) {
+            metrics::counter!(crate::metrics_init::INVALID_SEQUENCER_BLOCK_COUNT).increment(1);
+            error!(
+                chain_id.block = %block.header().chain_id(),
+                "chain ID of sequencer block does not match the configured chain ID of the \
+                 sequencer network; dropping it instead of submitting it to Celestia",
+            );
+            self.alerts.raise(Alert::VerificationAnomaly {
+                chain_id: block.header().chain_id().to_string(),
+                sequencer_height: height.value(),
+                reason: "chain ID does not match the configured chain ID".to_string(),
+            });
+            return Ok(());
+        }
+        if let Err(error) = validation::validate(&block, height) {
+            metrics::counter!(crate::metrics_init::INVALID_SEQUENCER_BLOCK_COUNT).increment(1);
+            error!(
+                %error,
+                "sequencer block failed validation; dropping it instead of submitting it to \
+                 Celestia",
+            );
+            self.alerts.raise(Alert::VerificationAnomaly {
+                chain_id: block.header().chain_id().to_string(),
+                sequencer_height: height.value(),
+                reason: error.to_string(),
+            });
+            return Ok(());
+        }
+        self.latencies.observed(block.block_hash(), height);
+        let result = submitter.try_send(block);
+        report_ingest_queue_depth(&submitter);
+        if let Err(error) = result {
+            debug!(
+                // Just print the error directly: TrySendError has no cause chain.
+                %error,
+                "failed forwarding sequencer block to submitter; \
+                pausing block stream and scheduling for later submission",
+            );
+            block_stream.pause();
+            debug!("block stream paused");
+
+            match error {
+                TrySendError::Full(block) => {
+                    *forward = async move { submitter.send(block).await }.boxed().fuse();
+                }
+                TrySendError::Closed(..) => bail!("blob submitter has shut down unexpectedly"),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn report_ingest_queue_depth(submitter: &write::BlobSubmitterHandle) {
+    // allow: the number of queued blocks is bounded by the channel capacity and will not cause
+    // precision loss
+    #[allow(clippy::cast_precision_loss)]
+    metrics::gauge!(crate::metrics_init::INGEST_QUEUED_SEQUENCER_BLOCKS)
+        .set(submitter.num_queued_blocks() as f64);
+}
+
@@ -0,0 +1,106 @@
+//! Checks of the Celestia app and account the relayer submits with, run by the `check` subcommand
+//! before a relayer is started.
+//!
+//! The checks use the same client as the submission of blobs, but never broadcast a transaction.
+
+use std::sync::Arc;
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use tonic::transport::Uri;
+
+use super::{
+    celestia_client::{
+        CelestiaClient,
+        Funds,
+    },
+    CelestiaClientBuilder,
+    CelestiaErrorPolicy,
+    CelestiaKeys,
+    State,
+};
+use crate::clock;
+
+/// The Celestia account submitting to a Celestia app, before connecting to the app.
+pub(crate) struct CelestiaPreflight {
+    builder: CelestiaClientBuilder,
+}
+
+impl CelestiaPreflight {
+    /// Reads the signing key of the Celestia account from `key_file`.
+    ///
+    /// # Errors
+    /// Returns an error if `grpc_endpoint` is not a valid URI, or if the key could not be read.
+    pub(crate) fn new(grpc_endpoint: &str, key_file: &str) -> eyre::Result<Self> {
+        let uri: Uri = grpc_endpoint
+            .parse()
+            .wrap_err("failed parsing provided celestia app grpc endpoint as Uri")?;
+        let celestia_keys =
+            CelestiaKeys::from_path(key_file).wrap_err("failed to get celestia keys from file")?;
+        // Resubmissions and the error policy only apply to submissions, which are never made.
+        // The preflight check must report an unreachable Celestia app rather than fall back to a
+        // stale height.
+        let builder = CelestiaClientBuilder::new(
+            uri,
+            Arc::new(celestia_keys),
+            Arc::new(State::new()),
+            1,
+            0,
+            0,
+            None,
+            CelestiaErrorPolicy::default(),
+            clock::system(),
+        )
+        .wrap_err("failed to create celestia client builder")?;
+        Ok(Self {
+            builder,
+        })
+    }
+
+    /// Returns the Bech32-encoded address of the Celestia account.
+    pub(crate) fn address(&self) -> &str {
+        self.builder.address()
+    }
+
+    /// Connects to the Celestia app and fetches its chain ID.
+    ///
+    /// # Errors
+    /// Returns an error if the Celestia app could not be reached.
+    pub(crate) async fn connect(self) -> eyre::Result<CelestiaConnection> {
+        let client = self
+            .builder
+            .try_build()
+            .await
+            .wrap_err("failed to connect to the celestia app")?;
+        Ok(CelestiaConnection {
+            client,
+        })
+    }
+}
+
+/// A Celestia account connected to a Celestia app.
+pub(crate) struct CelestiaConnection {
+    client: CelestiaClient,
+}
+
+impl CelestiaConnection {
+    /// Returns the chain ID of the Celestia network.
+    pub(crate) fn chain_id(&self) -> &str {
+        self.client.chain_id()
+    }
+
+    /// Returns the funds of the Celestia account, together with the fee currently paid for a
+    /// submission of blobs whose data are `blob_sizes` bytes long.
+    ///
+    /// # Errors
+    /// Returns an error if the Celestia app could not be queried, or if the account does not
+    /// exist.
+    pub(crate) async fn check_funds(&self, blob_sizes: &[u32]) -> eyre::Result<Funds> {
+        self.client
+            .check_funds(blob_sizes)
+            .await
+            .wrap_err("failed to query the funds of the celestia account")
+    }
+}
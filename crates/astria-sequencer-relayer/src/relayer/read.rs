@@ -1,6 +1,12 @@
 //! A stream of sequencer blocks.
+//!
+//! Blocks are fetched from Sequencer's gRPC service by default. In pull mode they are instead
+//! fetched from its CometBFT RPC and converted to sequencer blocks by the relayer itself.
+//! Deposits are not part of CometBFT blocks, so blocks with deposits are fetched over gRPC even
+//! in pull mode.
 use std::{
-    future::Future as _,
+    collections::HashMap,
+    future::Future,
     pin::Pin,
     sync::Arc,
     task::Poll,
@@ -12,10 +18,14 @@ use astria_core::{
         sequencer_service_client::SequencerServiceClient,
         GetSequencerBlockRequest,
     },
-    sequencerblock::v1alpha1::SequencerBlock,
+    sequencerblock::v1alpha1::{
+        extract::group_rollup_data_by_rollup_id,
+        SequencerBlock,
+    },
 };
 use astria_eyre::eyre::{
     self,
+    bail,
     Report,
     WrapErr as _,
 };
@@ -25,7 +35,14 @@ use futures::{
     FutureExt as _,
 };
 use pin_project_lite::pin_project;
-use sequencer_client::tendermint::block::Height;
+use sequencer_client::{
+    tendermint::{
+        self,
+        block::Height,
+    },
+    Client as _,
+    HttpClient as SequencerClient,
+};
 use tokio_stream::Stream;
 use tracing::{
     info,
@@ -78,6 +95,7 @@ impl Heights {
 pin_project! {
     pub(super) struct BlockStream {
         client: SequencerServiceClient<tonic::transport::Channel>,
+        cometbft_client: Option<SequencerClient>,
         heights: Heights,
         #[pin]
         future: Option<BoxFuture<'static, eyre::Result<SequencerBlock>>>,
@@ -134,6 +152,7 @@ impl Stream for BlockStream {
                 this.future.set(Some(
                     fetch_block(
                         this.client.clone(),
+                        this.cometbft_client.clone(),
                         height,
                         *this.block_time,
                         this.state.clone(),
@@ -176,18 +195,72 @@ impl Stream for BlockStream {
 
 /// Fetch the sequencer block at `height`.
 ///
+/// If `cometbft_client` is set, the block is fetched from CometBFT and converted to a sequencer
+/// block. If the conversion fails it is fetched from Sequencer's gRPC service instead.
+///
 /// If fetching the block fails, then a new fetch is scheduled with exponential backoff,
 /// up to a maximum of `block_time` duration between subsequent requests.
 #[instrument(skip_all, fields(%height))]
 async fn fetch_block(
     client: SequencerServiceClient<tonic::transport::Channel>,
+    cometbft_client: Option<SequencerClient>,
     height: Height,
     block_time: Duration,
     state: Arc<super::State>,
 ) -> eyre::Result<SequencerBlock> {
+    if let Some(cometbft_client) = cometbft_client {
+        let response = retry_with_backoff(block_time, state.clone(), move || {
+            let client = cometbft_client.clone();
+            async move { client.block(height).await.map_err(Report::new) }
+        })
+        .await?;
+        match sequencer_block_from_cometbft(response.block) {
+            Ok(block) => return Ok(block),
+            Err(error) => warn!(
+                %error,
+                "failed converting cometbft block to sequencer block, possibly because it \
+                 contains deposits; fetching it from sequencer's gRPC service instead",
+            ),
+        }
+    }
+
+    let block = retry_with_backoff(block_time, state, move || {
+        let mut client = client.clone();
+
+        let request = GetSequencerBlockRequest {
+            height: height.value(),
+        };
+
+        async move {
+            client
+                .get_sequencer_block(request)
+                .await
+                .map_err(Report::new)
+        }
+    })
+    .await?;
+
+    let block = SequencerBlock::try_from_raw(block.into_inner())
+        .wrap_err("failed to parse raw proto block from grpc response")?;
+
+    Ok(block)
+}
+
+/// Calls `fetch` until it succeeds, backing off exponentially up to `block_time` between
+/// attempts.
+async fn retry_with_backoff<F, Fut, T>(
+    block_time: Duration,
+    state: Arc<super::State>,
+    fetch: F,
+) -> eyre::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = eyre::Result<T>>,
+{
     // Moving the span into `on_retry`, because tryhard spawns these in a tokio
     // task, losing the span.
     let span = Span::current();
+    let retry_state = state.clone();
     let retry_config = tryhard::RetryFutureConfig::new(u32::MAX)
         .exponential_backoff(Duration::from_millis(100))
         .max_delay(block_time)
@@ -196,7 +269,7 @@ async fn fetch_block(
                 metrics::counter!(crate::metrics_init::SEQUENCER_BLOCK_FETCH_FAILURE_COUNT)
                     .increment(1);
 
-                let state = Arc::clone(&state);
+                let state = Arc::clone(&retry_state);
                 state.set_sequencer_connected(false);
 
                 let wait_duration = next_delay
@@ -214,31 +287,54 @@ async fn fetch_block(
             },
         );
 
-    let block = tryhard::retry_fn(move || {
-        let mut client = client.clone();
-
-        let request = GetSequencerBlockRequest {
-            height: height.value(),
-        };
-
-        async move {
-            client
-                .get_sequencer_block(request)
-                .await
-                .map_err(Report::new)
-        }
-    })
-    .with_config(retry_config)
-    .in_current_span()
-    .await
-    .wrap_err("retry attempts exhausted; bailing")?;
+    let output = tryhard::retry_fn(fetch)
+        .with_config(retry_config)
+        .in_current_span()
+        .await
+        .wrap_err("retry attempts exhausted; bailing")?;
 
     state.set_sequencer_connected(true);
 
-    let block = SequencerBlock::try_from_raw(block.into_inner())
-        .wrap_err("failed to parse raw proto block from grpc response")?;
+    Ok(output)
+}
 
-    Ok(block)
+/// Converts a block read from CometBFT to a sequencer block.
+///
+/// Deposits are not part of CometBFT blocks but are kept in Sequencer's state. The conversion
+/// fails for blocks with deposits, because their rollup IDs and rollup transactions cannot be
+/// reconstructed without them.
+///
+/// Transactions that cannot be decoded are skipped by the conversion. They are reported and
+/// counted as quarantined.
+fn sequencer_block_from_cometbft(block: tendermint::Block) -> eyre::Result<SequencerBlock> {
+    let tendermint::Hash::Sha256(block_hash) = block.header.hash() else {
+        bail!("cometbft block hash is not a sha256 digest");
+    };
+    // The first two entries of the block data are the rollup transactions and rollup IDs
+    // commitments and not transactions.
+    let quarantined = group_rollup_data_by_rollup_id(block.data.iter().skip(2)).quarantined;
+    if !quarantined.is_empty() {
+        metrics::counter!(crate::metrics_init::QUARANTINED_SEQUENCER_TRANSACTIONS)
+            .increment(quarantined.len() as u64);
+    }
+    for tx in quarantined {
+        warn!(
+            tx.hash = %telemetry::display::hex(&tx.hash),
+            tx.index = tx.index,
+            error = &tx.error as &dyn std::error::Error,
+            "quarantined undecodable transaction in sequencer block; its data is not relayed",
+        );
+    }
+    SequencerBlock::try_from_block_info_and_data(
+        block_hash,
+        block.header.chain_id,
+        block.header.height,
+        block.header.time,
+        block.header.proposer_address,
+        block.data,
+        HashMap::new(),
+    )
+    .wrap_err("failed reconstructing sequencer block from cometbft block data")
 }
 
 pub(super) struct NoBlockTime;
@@ -252,6 +348,7 @@ pub(super) struct BlockStreamBuilder<TBlockTime = NoBlockTime, TClient = NoClien
 {
     block_time: TBlockTime,
     client: TClient,
+    cometbft_client: Option<SequencerClient>,
     last_fetched_height: Option<Height>,
     state: TState,
 }
@@ -263,6 +360,7 @@ impl<TBlockTime, TClient, TState> BlockStreamBuilder<TBlockTime, TClient, TState
     ) -> BlockStreamBuilder<WithBlockTime, TClient, TState> {
         let Self {
             client,
+            cometbft_client,
             last_fetched_height,
             state,
             ..
@@ -270,6 +368,7 @@ impl<TBlockTime, TClient, TState> BlockStreamBuilder<TBlockTime, TClient, TState
         BlockStreamBuilder {
             block_time: WithBlockTime(block_time),
             client,
+            cometbft_client,
             last_fetched_height,
             state,
         }
@@ -281,6 +380,7 @@ impl<TBlockTime, TClient, TState> BlockStreamBuilder<TBlockTime, TClient, TState
     ) -> BlockStreamBuilder<TBlockTime, WithClient, TState> {
         let Self {
             block_time,
+            cometbft_client,
             last_fetched_height,
             state,
             ..
@@ -288,6 +388,7 @@ impl<TBlockTime, TClient, TState> BlockStreamBuilder<TBlockTime, TClient, TState
         BlockStreamBuilder {
             block_time,
             client: WithClient(client),
+            cometbft_client,
             last_fetched_height,
             state,
         }
@@ -300,17 +401,31 @@ impl<TBlockTime, TClient, TState> BlockStreamBuilder<TBlockTime, TClient, TState
         let Self {
             block_time,
             client,
+            cometbft_client,
             state,
             ..
         } = self;
         BlockStreamBuilder {
             block_time,
             client,
+            cometbft_client,
             last_fetched_height,
             state,
         }
     }
 
+    /// Fetches blocks from CometBFT with `cometbft_client` instead of from Sequencer's gRPC
+    /// service, if set.
+    pub(super) fn pull_from_cometbft(
+        self,
+        cometbft_client: Option<SequencerClient>,
+    ) -> BlockStreamBuilder<TBlockTime, TClient, TState> {
+        BlockStreamBuilder {
+            cometbft_client,
+            ..self
+        }
+    }
+
     pub(super) fn state(
         self,
         state: Arc<super::State>,
@@ -318,12 +433,14 @@ impl<TBlockTime, TClient, TState> BlockStreamBuilder<TBlockTime, TClient, TState
         let Self {
             block_time,
             client,
+            cometbft_client,
             last_fetched_height,
             ..
         } = self;
         BlockStreamBuilder {
             block_time,
             client,
+            cometbft_client,
             last_fetched_height,
             state: WithState(state),
         }
@@ -335,6 +452,7 @@ impl BlockStreamBuilder {
         BlockStreamBuilder {
             block_time: NoBlockTime,
             client: NoClient,
+            cometbft_client: None,
             last_fetched_height: None,
             state: NoState,
         }
@@ -346,6 +464,7 @@ impl BlockStreamBuilder<WithBlockTime, WithClient, WithState> {
         let Self {
             block_time: WithBlockTime(block_time),
             client: WithClient(client),
+            cometbft_client,
             last_fetched_height,
             state: WithState(state),
         } = self;
@@ -369,6 +488,7 @@ impl BlockStreamBuilder<WithBlockTime, WithClient, WithState> {
         };
         BlockStream {
             client,
+            cometbft_client,
             heights: Heights {
                 last_observed: None,
                 next,
@@ -19,6 +19,22 @@ impl State {
     pub(super) fn subscribe(&self) -> watch::Receiver<StateSnapshot> {
         self.inner.subscribe()
     }
+
+    pub(super) fn snapshot(&self) -> StateSnapshot {
+        *self.inner.borrow()
+    }
+
+    /// Records the number of sequencer blocks the relayer is behind the sequencer.
+    ///
+    /// Does nothing if either the latest observed or the latest confirmed sequencer height is
+    /// not known yet.
+    pub(super) fn report_sequencer_height_lag(&self) {
+        if let Some(lag) = self.inner.borrow().sequencer_height_lag() {
+            // allow: the lag will always be low enough to not cause precision loss
+            #[allow(clippy::cast_precision_loss)]
+            metrics::gauge!(crate::metrics_init::SEQUENCER_HEIGHT_LAG).set(lag as f64);
+        }
+    }
 }
 
 macro_rules! forward_setter {
@@ -38,19 +54,27 @@ forward_setter!(
     [set_celestia_connected <- bool],
     [set_sequencer_connected <- bool],
     [set_latest_confirmed_celestia_height <- u64],
+    [set_latest_confirmed_sequencer_height <- u64],
     [set_latest_fetched_sequencer_height <- u64],
     [set_latest_observed_sequencer_height <- u64],
     [set_latest_requested_sequencer_height <- u64],
+    [set_lagging <- bool],
+    [set_standby <- bool],
 );
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Serialize)]
 pub(crate) struct StateSnapshot {
     ready: bool,
+    /// Whether the sequencer height lag exceeds the configured error threshold.
+    lagging: bool,
+    /// Whether this instance waits on standby until it acquires the leader lease.
+    standby: bool,
 
     celestia_connected: bool,
     sequencer_connected: bool,
 
     latest_confirmed_celestia_height: Option<u64>,
+    latest_confirmed_sequencer_height: Option<u64>,
 
     latest_fetched_sequencer_height: Option<u64>,
     latest_observed_sequencer_height: Option<u64>,
@@ -70,6 +94,14 @@ impl StateSnapshot {
         changed
     }
 
+    fn set_latest_confirmed_sequencer_height(&mut self, height: u64) -> bool {
+        let changed = self
+            .latest_confirmed_sequencer_height
+            .map_or(true, |h| h != height);
+        self.latest_confirmed_sequencer_height.replace(height);
+        changed
+    }
+
     fn set_latest_fetched_sequencer_height(&mut self, height: u64) -> bool {
         let changed = self
             .latest_fetched_sequencer_height
@@ -103,6 +135,24 @@ impl StateSnapshot {
         changed
     }
 
+    /// Sets whether the sequencer height lag exceeds the error threshold.
+    ///
+    /// Returns `true` if the previous state was modified.
+    fn set_lagging(&mut self, lagging: bool) -> bool {
+        let changed = self.lagging ^ lagging;
+        self.lagging = lagging;
+        changed
+    }
+
+    /// Sets whether this instance waits on standby.
+    ///
+    /// Returns `true` if the previous state was modified.
+    fn set_standby(&mut self, standby: bool) -> bool {
+        let changed = self.standby ^ standby;
+        self.standby = standby;
+        changed
+    }
+
     /// Sets the sequencer connected state to `connected`.
     ///
     /// Returns `true` if the previous state was modified.
@@ -112,11 +162,27 @@ impl StateSnapshot {
         changed
     }
 
+    pub(super) fn sequencer_height_lag(&self) -> Option<u64> {
+        let observed = self.latest_observed_sequencer_height?;
+        let confirmed = self.latest_confirmed_sequencer_height?;
+        Some(observed.saturating_sub(confirmed))
+    }
+
+    pub(crate) fn latest_confirmed_celestia_height(&self) -> Option<u64> {
+        self.latest_confirmed_celestia_height
+    }
+
+    pub(crate) fn latest_confirmed_sequencer_height(&self) -> Option<u64> {
+        self.latest_confirmed_sequencer_height
+    }
+
     pub(crate) fn is_ready(&self) -> bool {
-        self.ready
+        self.ready && !self.lagging
     }
 
+    /// Returns if the relayer is connected to Celestia and the sequencer. An instance on
+    /// standby does not connect to either and is always healthy.
     pub(crate) fn is_healthy(&self) -> bool {
-        self.celestia_connected && self.sequencer_connected
+        self.standby || (self.celestia_connected && self.sequencer_connected)
     }
 }
@@ -1,8 +1,9 @@
-//! Tracks the current submission state of sequencer-relayer and syncs it to disk.
+//! Tracks the current submission state of sequencer-relayer and syncs it to storage.
 
-use std::path::{
-    Path,
-    PathBuf,
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
 };
 
 use astria_eyre::eyre::{
@@ -11,16 +12,58 @@ use astria_eyre::eyre::{
     ensure,
     WrapErr as _,
 };
-use sequencer_client::tendermint::block::Height as SequencerHeight;
+use celestia_types::Blob;
+use sequencer_client::tendermint::{
+    block::Height as SequencerHeight,
+    chain,
+};
 use serde::{
+    de::DeserializeOwned,
     Deserialize,
     Serialize,
 };
+use sha2::{
+    Digest as _,
+    Sha256,
+};
 use tracing::{
     debug,
     warn,
 };
 
+use crate::storage::{
+    Storage,
+    SubmissionKeys,
+};
+
+/// Identifies a submission by the blobs it writes to Celestia.
+///
+/// All attempts to submit the same batch of blobs share the same key. This makes it possible to
+/// tell whether an earlier attempt whose outcome was unknown, for example because it was not
+/// confirmed in time after being broadcast, was included on Celestia after all, instead of paying
+/// for the same blobs twice.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub(super) struct IdempotencyKey(#[serde(with = "hex::serde")] [u8; 32]);
+
+impl IdempotencyKey {
+    /// Derives the key of a submission of `blobs` from their namespaces and commitments.
+    pub(super) fn from_blobs(blobs: &[Blob]) -> Self {
+        let mut hasher = Sha256::new();
+        for blob in blobs {
+            hasher.update(blob.namespace.as_bytes());
+            hasher.update(blob.commitment.0);
+        }
+        Self(hasher.finalize().into())
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "state")]
@@ -41,29 +84,9 @@ impl PostSubmission {
     fn is_submitted(&self) -> bool {
         matches!(self, PostSubmission::Submitted { .. })
     }
-
-    fn from_path<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
-        let file = std::fs::File::open(&path)
-            .wrap_err("failed opening provided file path for reading post-submission state")?;
-        let state = serde_json::from_reader(file)
-            .wrap_err("failed reading contents of post-submission file")?;
-        Ok(state)
-    }
-
-    fn write_to_path<P: AsRef<Path>>(&self, path: P) -> eyre::Result<()> {
-        let f = std::fs::File::options()
-            .write(true)
-            .truncate(true)
-            .open(path)
-            .wrap_err("failed opening file for writing state")?;
-        serde_json::to_writer(&f, self).wrap_err("failed writing json-serialized state to file")?;
-        f.sync_all()
-            .wrap_err("failed fully syncing state write to disk")?;
-        Ok(())
-    }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "state")]
 enum PreSubmission {
@@ -72,47 +95,97 @@ enum PreSubmission {
         #[serde(with = "as_number")]
         sequencer_height: SequencerHeight,
         last_submission: PostSubmission,
+        /// The key of the started submission. Absent in files written before keys were
+        /// recorded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        idempotency_key: Option<IdempotencyKey>,
+        /// The sequencer heights of all submissions started by [`SubmissionState::start`] that
+        /// were not yet finished when this state was written, in ascending order.
+        ///
+        /// Heights at or below the one in the post-submit state belong to submissions that
+        /// were finished since.
+        #[serde(default, skip_serializing_if = "Vec::is_empty", with = "as_numbers")]
+        in_flight: Vec<SequencerHeight>,
     },
 }
-impl PreSubmission {
-    fn from_path<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
-        let file = std::fs::File::open(&path)
-            .wrap_err("failed opening provided file path for reading pre-submission state")?;
-        let state = serde_json::from_reader(file)
-            .wrap_err("failed reading contents of pre-submission file")?;
-        Ok(state)
-    }
 
-    fn write_to_path<P: AsRef<Path>>(&self, path: P) -> eyre::Result<()> {
-        let f = std::fs::File::options()
-            .write(true)
-            .truncate(true)
-            .open(path)
-            .wrap_err("failed opening file for writing state")?;
-        serde_json::to_writer(&f, self).wrap_err("failed writing json-serialized state to file")?;
-        f.sync_all()
-            .wrap_err("failed fully syncing state write to disk")?;
-        Ok(())
-    }
+/// Reads the state stored under `key`, returning `None` if there is no record.
+async fn read_state<T: DeserializeOwned>(
+    storage: &dyn Storage,
+    key: &str,
+) -> eyre::Result<Option<T>> {
+    let Some(bytes) = storage
+        .get(key)
+        .await
+        .wrap_err("failed reading state from storage")?
+    else {
+        return Ok(None);
+    };
+    let state = serde_json::from_slice(&bytes).wrap_err("failed parsing contents of state")?;
+    Ok(Some(state))
+}
+
+/// Durably stores `state` under `key`.
+async fn write_state<T: Serialize>(
+    storage: &dyn Storage,
+    key: &str,
+    state: &T,
+) -> eyre::Result<()> {
+    let bytes = serde_json::to_vec(state).wrap_err("failed json-serializing state")?;
+    storage
+        .put(key, bytes)
+        .await
+        .wrap_err("failed writing json-serialized state to storage")
 }
 
 #[derive(Clone, Debug)]
 pub(super) struct SubmissionState {
     pre: PreSubmission,
     post: PostSubmission,
-    pre_path: PathBuf,
-    post_path: PathBuf,
+    storage: Arc<dyn Storage>,
+    keys: SubmissionKeys,
 }
 
 #[derive(Debug)]
 pub(super) struct Started(SubmissionState);
 
+/// The submission states of all sequencer networks relayed to Celestia, keyed by chain ID.
+///
+/// Blocks of chains without an entry of their own are tracked by the state of the primary
+/// network, which is the one configured at the top level of the relayer config.
+#[derive(Clone, Debug)]
+pub(super) struct SubmissionStates {
+    primary: SubmissionState,
+    additional: HashMap<chain::Id, SubmissionState>,
+}
+
+impl SubmissionStates {
+    pub(super) fn new(
+        primary: SubmissionState,
+        additional: HashMap<chain::Id, SubmissionState>,
+    ) -> Self {
+        Self {
+            primary,
+            additional,
+        }
+    }
+
+    /// Returns if blocks of `chain_id` are tracked by the state of the primary network.
+    pub(super) fn is_primary(&self, chain_id: &chain::Id) -> bool {
+        !self.additional.contains_key(chain_id)
+    }
+
+    pub(super) fn get_mut(&mut self, chain_id: &chain::Id) -> &mut SubmissionState {
+        self.additional.get_mut(chain_id).unwrap_or(&mut self.primary)
+    }
+}
+
 impl Started {
-    pub(super) fn finalize(self, celestia_height: u64) -> eyre::Result<SubmissionState> {
+    pub(super) async fn finalize(self, celestia_height: u64) -> eyre::Result<SubmissionState> {
         let Self(SubmissionState {
             pre,
-            pre_path,
-            post_path,
+            storage,
+            keys,
             ..
         }) = self;
 
@@ -132,17 +205,17 @@ impl Started {
                 celestia_height,
                 sequencer_height,
             },
-            pre_path,
-            post_path,
+            storage,
+            keys,
         };
 
         debug!(
             state = serde_json::to_string(&new.post).expect("type contains no non-ascii keys"),
-            "finalizing submission by writing post-submit state to file",
+            "finalizing submission by writing post-submit state to storage",
         );
-        new.post
-            .write_to_path(&new.post_path)
-            .wrap_err("failed comitting post-submission state to disk")?;
+        write_state(&*new.storage, &new.keys.post_submit, &new.post)
+            .await
+            .wrap_err("failed comitting post-submission state to storage")?;
         Ok(new)
     }
 }
@@ -157,7 +230,11 @@ impl SubmissionState {
         }
     }
 
-    pub(super) fn initialize(self, sequencer_height: SequencerHeight) -> eyre::Result<Started> {
+    pub(super) async fn initialize(
+        self,
+        sequencer_height: SequencerHeight,
+        idempotency_key: IdempotencyKey,
+    ) -> eyre::Result<Started> {
         if let PostSubmission::Submitted {
             sequencer_height: latest_submitted,
             ..
@@ -169,93 +246,192 @@ impl SubmissionState {
                  submitted"
             );
         }
+        self.write_started(sequencer_height, idempotency_key, Vec::new())
+            .await
+    }
+
+    async fn write_started(
+        self,
+        sequencer_height: SequencerHeight,
+        idempotency_key: IdempotencyKey,
+        in_flight: Vec<SequencerHeight>,
+    ) -> eyre::Result<Started> {
         let new = Self {
             pre: PreSubmission::Started {
                 sequencer_height,
                 last_submission: self.post,
+                idempotency_key: Some(idempotency_key),
+                in_flight,
             },
             ..self
         };
         debug!(
             state = serde_json::to_string(&new.pre).expect("type contains no non-ascii keys"),
-            "initializing submission by writing pre-submit state to file",
+            "initializing submission by writing pre-submit state to storage",
         );
-        new.pre
-            .write_to_path(&new.pre_path)
-            .wrap_err("failed commiting pre-submission state to disk")?;
+        write_state(&*new.storage, &new.keys.pre_submit, &new.pre)
+            .await
+            .wrap_err("failed commiting pre-submission state to storage")?;
         Ok(Started(new))
     }
 
-    pub(super) fn from_paths<const LEANIENT: bool, P1: AsRef<Path>, P2: AsRef<Path>>(
-        pre_path: P1,
-        post_path: P2,
+    /// Records that a submission of sequencer blocks up to `sequencer_height`, identified by
+    /// `idempotency_key`, was started.
+    ///
+    /// Unlike [`SubmissionState::initialize`] this does not require earlier submissions to be
+    /// finalized, so that several submissions can be in flight at the same time. The pre-submit
+    /// state always refers to the most recently started submission and records the heights of
+    /// all submissions still in flight. Each started submission must be completed with
+    /// [`SubmissionState::finish`] in the order they were started.
+    pub(super) async fn start(
+        self,
+        sequencer_height: SequencerHeight,
+        idempotency_key: IdempotencyKey,
     ) -> eyre::Result<Self> {
-        let pre_path = pre_path.as_ref().to_path_buf();
-        let post_path = post_path.as_ref().to_path_buf();
-
-        let mut pre = PreSubmission::from_path(&pre_path).wrap_err(
-            "failed constructing post submit state from provided path; if the post-submit state is
-                otherwise present and correctly formatted - and contains the correct information \
-             about the
-                last submitted sequencer height and its inclusion height on Celestia - then \
-             override the
-                contents of the pre-submission file with `{{\"state\": \"ignore\"}}`",
-        )?;
-        let post = PostSubmission::from_path(&post_path).wrap_err(
-            "failed constructing post submit state from provided path; if the pre-submit state is \
-             otherwise present and correctly formatted then this indicates a file corruption. \
-             Because the post-submit state is critical for determining the starting height for \
-             relaying sequencer blocks, make sure it's set correctly.",
-        )?;
-
-        let state = match (pre, post) {
-            (PreSubmission::Ignore, post) => Self {
-                pre,
-                post,
-                pre_path,
-                post_path,
+        let mut in_flight = Vec::new();
+        if let PreSubmission::Started {
+            sequencer_height: latest_started,
+            in_flight: previously_in_flight,
+            ..
+        } = &self.pre
+        {
+            ensure!(
+                sequencer_height > *latest_started,
+                "refusing to start a submission of sequencer blocks at heights below or at what \
+                 was already started"
+            );
+            in_flight = unfinished(previously_in_flight, self.post);
+        }
+        if let PostSubmission::Submitted {
+            sequencer_height: latest_submitted,
+            ..
+        } = self.post
+        {
+            ensure!(
+                sequencer_height > latest_submitted,
+                "refusing to submit a sequencer block at heights below or at what was already \
+                 submitted"
+            );
+        }
+        in_flight.push(sequencer_height);
+        let Started(new) = self
+            .write_started(sequencer_height, idempotency_key, in_flight)
+            .await?;
+        Ok(new)
+    }
+
+    /// Records that the submission of sequencer blocks up to `sequencer_height` was included in
+    /// Celestia at `celestia_height`.
+    ///
+    /// The pre-submit state is left untouched because later submissions might still be in
+    /// flight. The finished submission is no longer considered in flight because its height is
+    /// now recorded in the post-submit state.
+    pub(super) async fn finish(
+        self,
+        sequencer_height: SequencerHeight,
+        celestia_height: u64,
+    ) -> eyre::Result<Self> {
+        if let Some(last_submitted) = self.last_submitted_height() {
+            ensure!(
+                sequencer_height > last_submitted,
+                "refusing to finish a submission of sequencer blocks at heights below or at what \
+                 was already submitted"
+            );
+        }
+        let pre = self.pre;
+        let finalized = Started(Self {
+            pre: PreSubmission::Started {
+                sequencer_height,
+                last_submission: self.post,
+                idempotency_key: None,
+                in_flight: Vec::new(),
             },
+            ..self
+        })
+        .finalize(celestia_height)
+        .await?;
+        Ok(Self {
+            pre,
+            ..finalized
+        })
+    }
 
-            (
-                PreSubmission::Started {
-                    sequencer_height,
-                    last_submission,
-                },
-                post,
-            ) => {
-                if let Err(error) = ensure_consistent(sequencer_height, last_submission, post) {
-                    if LEANIENT {
-                        warn!(%error, "pre- and post-submission states were inconsistent. Setting pre-state to `ignore` and continuing from last post-state. This could to double submission!!");
-                        pre = PreSubmission::Ignore;
-                    } else {
-                        return Err(error).wrap_err("on-disk states are inconsistent");
-                    }
-                }
-                Self {
-                    pre,
-                    post,
-                    pre_path,
-                    post_path,
+    /// Reads the submission state stored under `keys` in `storage`.
+    ///
+    /// An absent pre-submission record is read as `ignore`, an absent post-submission record as
+    /// `fresh`. This can only happen with the database backends, the file backend fails reading a
+    /// file that does not exist.
+    pub(super) async fn read<const LEANIENT: bool>(
+        storage: Arc<dyn Storage>,
+        keys: SubmissionKeys,
+    ) -> eyre::Result<Self> {
+        let mut pre = read_state(&*storage, &keys.pre_submit)
+            .await
+            .map(|pre| pre.unwrap_or(PreSubmission::Ignore))
+            .wrap_err(
+                "failed constructing post submit state from provided path; if the post-submit \
+                 state is otherwise present and correctly formatted - and contains the correct \
+                 information about the last submitted sequencer height and its inclusion height \
+                 on Celestia - then override the contents of the pre-submission file with \
+                 `{{\"state\": \"ignore\"}}`",
+            )?;
+        let post = read_state(&*storage, &keys.post_submit)
+            .await
+            .map(|post| post.unwrap_or(PostSubmission::Fresh))
+            .wrap_err(
+                "failed constructing post submit state from provided path; if the pre-submit \
+                 state is otherwise present and correctly formatted then this indicates a file \
+                 corruption. Because the post-submit state is critical for determining the \
+                 starting height for relaying sequencer blocks, make sure it's set correctly.",
+            )?;
+
+        if let PreSubmission::Started {
+            sequencer_height,
+            last_submission,
+            idempotency_key,
+            in_flight,
+        } = &pre
+        {
+            if let Err(error) =
+                ensure_consistent(*sequencer_height, *last_submission, in_flight, post)
+            {
+                if LEANIENT {
+                    let idempotency_key = idempotency_key.map(tracing::field::display);
+                    let in_flight = unfinished(in_flight, post)
+                        .iter()
+                        .map(SequencerHeight::value)
+                        .collect::<Vec<_>>();
+                    warn!(%error, idempotency_key, ?in_flight, "pre- and post-submission states were inconsistent. Setting pre-state to `ignore` and continuing from last post-state. This could to double submission!!");
+                    pre = PreSubmission::Ignore;
+                } else {
+                    return Err(error).wrap_err("on-disk states are inconsistent");
                 }
             }
+        }
+        let state = Self {
+            pre,
+            post,
+            storage,
+            keys,
         };
 
-        // testing if the states can be written to the provided paths
-        state.pre.write_to_path(&state.pre_path).wrap_err_with(|| {
-            format!(
-                "failed writing just-read pre-submission state to disk; is the file writable? \
-                 Write destination: {}",
-                state.pre_path.display(),
-            )
-        })?;
-        state
-            .post
-            .write_to_path(&state.post_path)
+        // testing if the states can be written to the storage
+        write_state(&*state.storage, &state.keys.pre_submit, &state.pre)
+            .await
+            .wrap_err_with(|| {
+                format!(
+                    "failed writing just-read pre-submission state to storage; is it writable? \
+                     Write destination: {}",
+                    state.keys.pre_submit,
+                )
+            })?;
+        write_state(&*state.storage, &state.keys.post_submit, &state.post)
+            .await
             .wrap_err_with(|| {
                 format!(
-                    "failed writing just-read post-submission state to disk; is the file \
+                    "failed writing just-read post-submission state to storage; is it \
                      writable? Write destination: {}",
-                    state.post_path.display(),
+                    state.keys.post_submit,
                 )
             })?;
 
@@ -263,11 +439,52 @@ impl SubmissionState {
     }
 }
 
+/// Ensures that `pre` and `post` are the contents of a consistent pair of pre- and
+/// post-submission files.
+///
+/// Used to check the submission states of a snapshot before importing them.
+pub(crate) fn ensure_valid_submission_files(
+    pre: &serde_json::Value,
+    post: &serde_json::Value,
+) -> eyre::Result<()> {
+    let pre: PreSubmission = serde_json::from_value(pre.clone())
+        .wrap_err("failed parsing pre-submission state")?;
+    let post: PostSubmission = serde_json::from_value(post.clone())
+        .wrap_err("failed parsing post-submission state")?;
+    if let PreSubmission::Started {
+        sequencer_height,
+        last_submission,
+        in_flight,
+        ..
+    } = pre
+    {
+        ensure_consistent(sequencer_height, last_submission, &in_flight, post)
+            .wrap_err("pre- and post-submission states are inconsistent")?;
+    }
+    Ok(())
+}
+
+/// Returns the heights in `in_flight` of submissions not yet finished according to `post`.
+fn unfinished(in_flight: &[SequencerHeight], post: PostSubmission) -> Vec<SequencerHeight> {
+    match post {
+        PostSubmission::Fresh => in_flight.to_vec(),
+        PostSubmission::Submitted {
+            sequencer_height, ..
+        } => in_flight
+            .iter()
+            .copied()
+            .filter(|height| *height > sequencer_height)
+            .collect(),
+    }
+}
+
 fn ensure_consistent(
     sequencer_height_started: SequencerHeight,
     last_submission: PostSubmission,
+    in_flight: &[SequencerHeight],
     current_submission: PostSubmission,
 ) -> eyre::Result<()> {
+    ensure_no_submission_is_in_flight(in_flight, current_submission)?;
     ensure_height_pre_submission_is_height_post_submission(
         sequencer_height_started,
         current_submission,
@@ -278,6 +495,26 @@ fn ensure_consistent(
     Ok(())
 }
 
+fn ensure_no_submission_is_in_flight(
+    in_flight: &[SequencerHeight],
+    current_submission: PostSubmission,
+) -> eyre::Result<()> {
+    let unfinished = unfinished(in_flight, current_submission);
+    ensure!(
+        unfinished.is_empty(),
+        "the pre-submit file records submissions to Celestia of sequencer blocks up to heights \
+         {} that were started, but the post-submit file does not record them as finalized. This \
+         indicates that these submissions were in flight when the relayer stopped and might or \
+         might not have been included on Celestia.",
+        unfinished
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    Ok(())
+}
+
 fn ensure_height_pre_submission_is_height_post_submission(
     sequencer_height_started: SequencerHeight,
     current_submission: PostSubmission,
@@ -387,13 +624,66 @@ mod as_number {
     }
 }
 
+mod as_numbers {
+    //! Logic to serialize a list of sequencer heights as numbers, see [`super::as_number`].
+    use serde::{
+        Deserialize as _,
+        Deserializer,
+        Serialize as _,
+        Serializer,
+    };
+
+    use super::SequencerHeight;
+
+    // Allow: the function signature is dictated by the serde(with) attribute.
+    #[allow(clippy::ptr_arg)]
+    pub(super) fn serialize<S>(
+        heights: &Vec<SequencerHeight>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        heights
+            .iter()
+            .map(SequencerHeight::value)
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Vec<SequencerHeight>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        Vec::<u64>::deserialize(deserializer)?
+            .into_iter()
+            .map(|height| SequencerHeight::try_from(height).map_err(Error::custom))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use astria_eyre::eyre;
     use serde_json::json;
     use tempfile::NamedTempFile;
 
-    use super::SubmissionState;
-    use crate::relayer::submission::PostSubmission;
+    use super::{
+        IdempotencyKey,
+        SubmissionState,
+    };
+    use crate::{
+        relayer::submission::PostSubmission,
+        storage::{
+            FileStorage,
+            Storage as _,
+        },
+    };
+
+    const KEY: IdempotencyKey = IdempotencyKey([1; 32]);
 
     const STRICT_CONCISTENCY_CHECK: bool = false;
 
@@ -410,42 +700,52 @@ mod tests {
         serde_json::to_writer(f, val).expect("must be able to write state to run tests");
     }
 
-    #[test]
-    fn fresh_with_ignored_is_ok() {
+    async fn read<const LEANIENT: bool>(
+        pre: &NamedTempFile,
+        post: &NamedTempFile,
+    ) -> eyre::Result<SubmissionState> {
+        let keys = FileStorage.submission_keys(None, pre.path(), post.path());
+        SubmissionState::read::<LEANIENT>(Arc::new(FileStorage), keys).await
+    }
+
+    #[tokio::test]
+    async fn fresh_with_ignored_is_ok() {
         let (pre, post) = create_files();
         write(&pre, &json!({ "state": "ignore" }));
         write(&post, &json!({ "state": "fresh" }));
-        SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
+        read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
             .expect("states `ignore` and `fresh` give a working submission state");
     }
 
-    #[test]
-    fn submitted_with_ignored_is_ok() {
+    #[tokio::test]
+    async fn submitted_with_ignored_is_ok() {
         let (pre, post) = create_files();
         write(&pre, &json!({ "state": "ignore" }));
         write(
             &post,
             &json!({ "state": "submitted", "celestia_height": 5, "sequencer_height": 2 }),
         );
-        SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
+        read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
             .expect("states `ignore` and `submitted` give a working submission state");
     }
 
-    #[test]
-    fn started_with_same_fresh_in_last_and_current_is_err() {
+    #[tokio::test]
+    async fn started_with_same_fresh_in_last_and_current_is_err() {
         let (pre, post) = create_files();
         write(
             &pre,
             &json!({ "state": "started", "sequencer_height": 5, "last_submission": { "state": "fresh"} }),
         );
         write(&post, &json!({ "state": "fresh" }));
-        let _ =
-            SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
-                .expect_err("started state with `fresh` in last and current gives error");
+        let _ = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect_err("started state with `fresh` in last and current gives error");
     }
 
-    #[test]
-    fn started_with_height_before_current_is_err() {
+    #[tokio::test]
+    async fn started_with_height_before_current_is_err() {
         let (pre, post) = create_files();
         write(
             &pre,
@@ -455,16 +755,16 @@ mod tests {
             &post,
             &json!({ "state": "submitted", "sequencer_height": 6, "celestia_height": 2 }),
         );
-        let _ =
-            SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
-                .expect_err(
-                    "started state with sequencer height less then sequencer height recorded \
-                     submitted gives error",
-                );
+        let _ = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect_err(
+                "started state with sequencer height less then sequencer height recorded \
+                 submitted gives error",
+            );
     }
 
-    #[test]
-    fn started_with_same_submitted_in_last_and_current_is_err() {
+    #[tokio::test]
+    async fn started_with_same_submitted_in_last_and_current_is_err() {
         let (pre, post) = create_files();
         write(
             &pre,
@@ -474,15 +774,15 @@ mod tests {
             &post,
             &json!({ "state": "submitted", "celestia_height": 5, "sequencer_height": 2 }),
         );
-        let _ =
-            SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
-                .expect_err(
-                    "started state with the same `submitted` in last and current give an error",
-                );
+        let _ = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect_err(
+                "started state with the same `submitted` in last and current give an error",
+            );
     }
 
-    #[test]
-    fn started_with_different_last_fresh_and_current_submitted_is_ok() {
+    #[tokio::test]
+    async fn started_with_different_last_fresh_and_current_submitted_is_ok() {
         let (pre, post) = create_files();
         write(
             &pre,
@@ -492,16 +792,16 @@ mod tests {
             &post,
             &json!({ "state": "submitted", "celestia_height": 5, "sequencer_height": 2 }),
         );
-        let _ =
-            SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
-                .expect(
-                    "started state with the `fresh` in last and `submitted` in current gives \
-                     working submission state",
-                );
+        let _ = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect(
+                "started state with the `fresh` in last and `submitted` in current gives \
+                 working submission state",
+            );
     }
 
-    #[test]
-    fn submit_initialize_finalize_flow_works() {
+    #[tokio::test]
+    async fn submit_initialize_finalize_flow_works() {
         let (pre, post) = create_files();
         write(
             &pre,
@@ -511,14 +811,14 @@ mod tests {
             &post,
             &json!({ "state": "submitted", "celestia_height": 5, "sequencer_height": 2 }),
         );
-        let state =
-            SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
-                .expect(
-                    "started state with the `fresh` in last and `submitted` in current gives \
-                     working submission state",
-                );
-        let started = state.initialize(3u32.into()).unwrap();
-        let finalized = started.finalize(6).unwrap();
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect(
+                "started state with the `fresh` in last and `submitted` in current gives \
+                 working submission state",
+            );
+        let started = state.initialize(3u32.into(), KEY).await.unwrap();
+        let finalized = started.finalize(6).await.unwrap();
         let PostSubmission::Submitted {
             celestia_height,
             sequencer_height,
@@ -530,8 +830,84 @@ mod tests {
         assert_eq!(sequencer_height.value(), 3);
     }
 
-    #[test]
-    fn submit_old_blocks_gives_error() {
+    #[tokio::test]
+    async fn overlapping_submissions_leave_consistent_state() {
+        let (pre, post) = create_files();
+        write(&pre, &json!({ "state": "ignore" }));
+        write(&post, &json!({ "state": "fresh" }));
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post).await.unwrap();
+        let state = state.start(2u32.into(), KEY).await.unwrap();
+        let state = state.start(4u32.into(), KEY).await.unwrap();
+        let state = state.finish(2u32.into(), 5).await.unwrap();
+        assert_eq!(state.last_submitted_height().map(|h| h.value()), Some(2));
+        let state = state.finish(4u32.into(), 6).await.unwrap();
+        assert_eq!(state.last_submitted_height().map(|h| h.value()), Some(4));
+
+        read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect("all started submissions were finished, so the states must be consistent");
+    }
+
+    #[tokio::test]
+    async fn unfinished_overlapping_submissions_give_error() {
+        let (pre, post) = create_files();
+        write(&pre, &json!({ "state": "ignore" }));
+        write(&post, &json!({ "state": "fresh" }));
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post).await.unwrap();
+        let state = state.start(2u32.into(), KEY).await.unwrap();
+        let state = state.start(4u32.into(), KEY).await.unwrap();
+        let state = state.start(6u32.into(), KEY).await.unwrap();
+        let _ = state.finish(2u32.into(), 5).await.unwrap();
+
+        let _ = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect_err("submissions at heights 4 and 6 are still in flight");
+    }
+
+    #[tokio::test]
+    async fn started_submissions_record_heights_in_flight() {
+        let (pre, post) = create_files();
+        write(&pre, &json!({ "state": "ignore" }));
+        write(&post, &json!({ "state": "fresh" }));
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post).await.unwrap();
+        let state = state.start(2u32.into(), KEY).await.unwrap();
+        let state = state.start(4u32.into(), KEY).await.unwrap();
+        let state = state.finish(2u32.into(), 5).await.unwrap();
+        let _ = state.start(6u32.into(), KEY).await.unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_reader(std::fs::File::open(pre.path()).unwrap()).unwrap();
+        assert_eq!(json!([4, 6]), written["in_flight"]);
+    }
+
+    #[tokio::test]
+    async fn started_submission_records_idempotency_key() {
+        let (pre, post) = create_files();
+        write(&pre, &json!({ "state": "ignore" }));
+        write(&post, &json!({ "state": "fresh" }));
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post).await.unwrap();
+        let _ = state.start(2u32.into(), KEY).await.unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_reader(std::fs::File::open(pre.path()).unwrap()).unwrap();
+        assert_eq!(json!(hex::encode([1; 32])), written["idempotency_key"]);
+    }
+
+    #[tokio::test]
+    async fn start_below_in_flight_submission_gives_error() {
+        let (pre, post) = create_files();
+        write(&pre, &json!({ "state": "ignore" }));
+        write(&post, &json!({ "state": "fresh" }));
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post).await.unwrap();
+        let state = state.start(4u32.into(), KEY).await.unwrap();
+        let _ = state
+            .start(3u32.into(), KEY)
+            .await
+            .expect_err("starting a submission below an in-flight one is an error");
+    }
+
+    #[tokio::test]
+    async fn submit_old_blocks_gives_error() {
         let (pre, post) = create_files();
         write(
             &pre,
@@ -541,14 +917,15 @@ mod tests {
             &post,
             &json!({ "state": "submitted", "celestia_height": 5, "sequencer_height": 2 }),
         );
-        let state =
-            SubmissionState::from_paths::<STRICT_CONCISTENCY_CHECK, _, _>(pre.path(), post.path())
-                .expect(
-                    "started state with `fresh` in last and `submitted` in current gives working \
-                     submission state",
-                );
+        let state = read::<STRICT_CONCISTENCY_CHECK>(&pre, &post)
+            .await
+            .expect(
+                "started state with `fresh` in last and `submitted` in current gives working \
+                 submission state",
+            );
         let _ = state
-            .initialize(2u32.into())
+            .initialize(2u32.into(), KEY)
+            .await
             .expect_err("trying to submit the same sequencer height is an error");
     }
 
@@ -559,29 +936,27 @@ mod tests {
         use super::{
             create_files,
             json,
+            read,
             write,
-            SubmissionState,
         };
 
         const LEANIENT_CONCISTENCY_CHECK: bool = true;
 
-        #[test]
-        fn started_with_same_fresh_in_last_and_current_is_err() {
+        #[tokio::test]
+        async fn started_with_same_fresh_in_last_and_current_is_err() {
             let (pre, post) = create_files();
             write(
                 &pre,
                 &json!({ "state": "started", "sequencer_height": 5, "last_submission": { "state": "fresh"} }),
             );
             write(&post, &json!({ "state": "fresh" }));
-            let _ = SubmissionState::from_paths::<LEANIENT_CONCISTENCY_CHECK, _, _>(
-                pre.path(),
-                post.path(),
-            )
-            .expect("this test should not fail when doing a leaning concistency check");
+            let _ = read::<LEANIENT_CONCISTENCY_CHECK>(&pre, &post)
+                .await
+                .expect("this test should not fail when doing a leaning concistency check");
         }
 
-        #[test]
-        fn started_with_height_before_current_is_err() {
+        #[tokio::test]
+        async fn started_with_height_before_current_is_err() {
             let (pre, post) = create_files();
             write(
                 &pre,
@@ -591,15 +966,13 @@ mod tests {
                 &post,
                 &json!({ "state": "submitted", "sequencer_height": 6, "celestia_height": 2 }),
             );
-            let _ = SubmissionState::from_paths::<LEANIENT_CONCISTENCY_CHECK, _, _>(
-                pre.path(),
-                post.path(),
-            )
-            .expect("this test should not fail when doing a leaning concistency check");
+            let _ = read::<LEANIENT_CONCISTENCY_CHECK>(&pre, &post)
+                .await
+                .expect("this test should not fail when doing a leaning concistency check");
         }
 
-        #[test]
-        fn started_with_same_submitted_in_last_and_current_is_err() {
+        #[tokio::test]
+        async fn started_with_same_submitted_in_last_and_current_is_err() {
             let (pre, post) = create_files();
             write(
                 &pre,
@@ -609,11 +982,9 @@ mod tests {
                 &post,
                 &json!({ "state": "submitted", "celestia_height": 5, "sequencer_height": 2 }),
             );
-            let _ = SubmissionState::from_paths::<LEANIENT_CONCISTENCY_CHECK, _, _>(
-                pre.path(),
-                post.path(),
-            )
-            .expect("this test should not fail when doing a leaning concistency check");
+            let _ = read::<LEANIENT_CONCISTENCY_CHECK>(&pre, &post)
+                .await
+                .expect("this test should not fail when doing a leaning concistency check");
         }
     }
 }
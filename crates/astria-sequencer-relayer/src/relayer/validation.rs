@@ -0,0 +1,133 @@
+//! Consistency checks run on sequencer blocks before they are submitted to Celestia.
+//!
+//! Decoding a [`SequencerBlock`] from its protobuf representation already verifies the
+//! inclusion proofs of its rollup transactions and rollup IDs against the header's data hash.
+//! The checks here catch blocks that are well-formed on their own but should still not be paid
+//! for on Celestia.
+//!
+//! Note that the relayer does not have access to the `CometBFT` commit of a block, so it cannot
+//! check that the commit references the block's header.
+
+use astria_core::{
+    primitive::v1::derive_merkle_tree_from_rollup_txs,
+    sequencerblock::v1alpha1::SequencerBlock,
+};
+use sequencer_client::tendermint::block::Height as SequencerHeight;
+use telemetry::display::base64;
+use thiserror::Error;
+
+/// An error when validating a sequencer block before submitting it to Celestia.
+#[derive(Debug, Error)]
+pub(super) enum ValidationError {
+    /// The height of the block differs from the height at which it was requested.
+    #[error(
+        "the sequencer block was requested at height `{requested}`, but has height `{actual}`"
+    )]
+    HeightMismatch {
+        requested: SequencerHeight,
+        actual: SequencerHeight,
+    },
+    /// The block hash consists of zero bytes only.
+    #[error("the sequencer block hash is all zeros")]
+    EmptyBlockHash,
+    /// The chain ID of the block is empty, so its Celestia namespace would not identify the
+    /// sequencer network.
+    #[error("the chain ID of the sequencer block is empty")]
+    EmptyChainId,
+    /// The rollup transactions root in the header does not match the rollup transactions
+    /// contained in the block.
+    #[error(
+        "the rollup transactions root `{in_header}` in the sequencer block header does not match \
+         the root `{reconstructed}` reconstructed from the block's rollup transactions"
+    )]
+    RollupTransactionsRootMismatch {
+        in_header: String,
+        reconstructed: String,
+    },
+}
+
+/// Checks that `block` fetched at `requested_height` is consistent.
+pub(super) fn validate(
+    block: &SequencerBlock,
+    requested_height: SequencerHeight,
+) -> Result<(), ValidationError> {
+    if block.height() != requested_height {
+        return Err(ValidationError::HeightMismatch {
+            requested: requested_height,
+            actual: block.height(),
+        });
+    }
+    if block.block_hash() == [0; 32] {
+        return Err(ValidationError::EmptyBlockHash);
+    }
+    if block.header().chain_id().as_str().is_empty() {
+        return Err(ValidationError::EmptyChainId);
+    }
+    let reconstructed = derive_merkle_tree_from_rollup_txs(
+        block
+            .rollup_transactions()
+            .iter()
+            .map(|(rollup_id, txs)| (rollup_id, txs.transactions())),
+    )
+    .root();
+    let in_header = block.header().rollup_transactions_root();
+    if reconstructed != in_header {
+        return Err(ValidationError::RollupTransactionsRootMismatch {
+            in_header: base64(&in_header).to_string(),
+            reconstructed: base64(&reconstructed).to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use astria_core::protocol::test_utils::ConfigureSequencerBlock;
+
+    use super::{
+        validate,
+        ValidationError,
+    };
+
+    #[test]
+    fn valid_block_passes() {
+        let block = ConfigureSequencerBlock {
+            block_hash: Some([99; 32]),
+            height: 5,
+            sequence_data: vec![([1; 32].into(), vec![1, 2, 3])],
+            ..Default::default()
+        }
+        .make();
+        validate(&block, 5u32.into()).unwrap();
+    }
+
+    #[test]
+    fn block_at_unexpected_height_is_rejected() {
+        let block = ConfigureSequencerBlock {
+            block_hash: Some([99; 32]),
+            height: 5,
+            ..Default::default()
+        }
+        .make();
+        let error = validate(&block, 6u32.into()).unwrap_err();
+        assert!(
+            matches!(error, ValidationError::HeightMismatch { .. }),
+            "expected a height mismatch, got: {error:?}",
+        );
+    }
+
+    #[test]
+    fn block_with_empty_block_hash_is_rejected() {
+        let block = ConfigureSequencerBlock {
+            height: 5,
+            block_hash: Some([0; 32]),
+            ..Default::default()
+        }
+        .make();
+        let error = validate(&block, 5u32.into()).unwrap_err();
+        assert!(
+            matches!(error, ValidationError::EmptyBlockHash),
+            "expected an empty block hash, got: {error:?}",
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Adaptive batching of blobs into Celestia submissions.
+//!
+//! Every Celestia transaction pays for a fixed amount of gas on top of the gas for its blobs, so
+//! submitting more blobs per transaction lowers the cost of posting them, at the price of blobs
+//! waiting longer before they are submitted. [`AdaptiveBatching`] makes that trade based on the
+//! submissions confirmed on Celestia: while the gas price paid or the inclusion latency rise
+//! above their long-term averages, it widens the batching window and raises the number of blobs
+//! per submission up to the configured [`BatchingBounds`]. Once fees and latencies settle, both
+//! return to their minimums.
+//!
+//! Each signal is tracked by two exponentially weighted moving averages: a fast one following
+//! the latest submissions, and a slow one serving as its baseline. The pressure of a signal is 0
+//! while the fast average is at or below the baseline, and grows linearly to 1 as the fast
+//! average reaches [`SPIKE_RATIO`] times the baseline. The greater pressure of the two signals
+//! determines the batching parameters.
+
+use std::{
+    sync::{
+        Mutex,
+        PoisonError,
+    },
+    time::Duration,
+};
+
+use tracing::info;
+
+/// The weight of the latest observation in the fast moving averages.
+const FAST_WEIGHT: f64 = 0.3;
+
+/// The weight of the latest observation in the slow moving averages serving as baselines.
+const SLOW_WEIGHT: f64 = 0.02;
+
+/// The ratio of a signal's fast average to its baseline at which batching is at its maximum.
+const SPIKE_RATIO: f64 = 2.0;
+
+/// The configured bounds within which the batching parameters are chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BatchingBounds {
+    /// How long blobs are collected into a batch before it is submitted while fees and latencies
+    /// are normal.
+    pub(crate) min_window: Duration,
+    /// How long blobs are collected into a batch at most, while fees or latencies spike.
+    pub(crate) max_window: Duration,
+    /// The number of blobs after which a batch is submitted while fees and latencies are normal.
+    pub(crate) min_blobs: usize,
+    /// The number of blobs after which a batch is submitted at most, while fees or latencies
+    /// spike.
+    pub(crate) max_blobs: usize,
+}
+
+/// The parameters the assembly stage batches blobs with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct BatchingParams {
+    /// How long blobs are collected into a batch before it is submitted.
+    pub(super) window: Duration,
+    /// The number of blobs after which a batch is submitted right away.
+    pub(super) max_blobs: usize,
+}
+
+/// Chooses the batching parameters from the gas prices and inclusion latencies of confirmed
+/// submissions.
+#[derive(Debug)]
+pub(super) struct AdaptiveBatching {
+    bounds: BatchingBounds,
+    signals: Mutex<Signals>,
+}
+
+#[derive(Debug, Default)]
+struct Signals {
+    gas_price: Option<Averages>,
+    inclusion_latency: Option<Averages>,
+}
+
+impl Signals {
+    fn pressure(&self) -> f64 {
+        let gas_price = self.gas_price.map_or(0.0, Averages::pressure);
+        let inclusion_latency = self.inclusion_latency.map_or(0.0, Averages::pressure);
+        gas_price.max(inclusion_latency)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Averages {
+    fast: f64,
+    slow: f64,
+}
+
+impl Averages {
+    fn update(averages: &mut Option<Self>, value: f64) {
+        let averages = averages.get_or_insert(Self {
+            fast: value,
+            slow: value,
+        });
+        averages.fast += FAST_WEIGHT * (value - averages.fast);
+        averages.slow += SLOW_WEIGHT * (value - averages.slow);
+    }
+
+    fn pressure(self) -> f64 {
+        if self.slow <= 0.0 {
+            return 0.0;
+        }
+        ((self.fast / self.slow - 1.0) / (SPIKE_RATIO - 1.0)).clamp(0.0, 1.0)
+    }
+}
+
+impl AdaptiveBatching {
+    pub(super) fn new(bounds: BatchingBounds) -> Self {
+        let batching = Self {
+            bounds,
+            signals: Mutex::new(Signals::default()),
+        };
+        report(batching.params());
+        batching
+    }
+
+    /// Returns the batching parameters for the conditions observed so far.
+    pub(super) fn params(&self) -> BatchingParams {
+        let pressure = self
+            .signals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pressure();
+        self.params_at(pressure)
+    }
+
+    /// Records the inclusion latency and gas price of a confirmed submission, and updates the
+    /// batching parameters accordingly.
+    ///
+    /// Gas prices that are not positive, such as those of submissions whose gas limit could not
+    /// be determined, are ignored.
+    pub(super) fn observe(&self, inclusion_latency: Duration, gas_price: f64) {
+        let before = self.params();
+        {
+            let mut signals = self.signals.lock().unwrap_or_else(PoisonError::into_inner);
+            Averages::update(
+                &mut signals.inclusion_latency,
+                inclusion_latency.as_secs_f64(),
+            );
+            if gas_price.is_finite() && gas_price > 0.0 {
+                Averages::update(&mut signals.gas_price, gas_price);
+            }
+        }
+        let after = self.params();
+        if after != before {
+            info!(
+                window_ms = after.window.as_millis(),
+                max_blobs = after.max_blobs,
+                "adjusted batching of blobs to the observed Celestia gas prices and inclusion \
+                 latencies",
+            );
+            report(after);
+        }
+    }
+
+    fn params_at(&self, pressure: f64) -> BatchingParams {
+        let BatchingBounds {
+            min_window,
+            max_window,
+            min_blobs,
+            max_blobs,
+        } = self.bounds;
+        let window =
+            min_window.saturating_add(max_window.saturating_sub(min_window).mul_f64(pressure));
+        // allow: the number of blobs is bounded by the configured maximum, and `pressure` is
+        // within [0, 1], so the result is a small non-negative number
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let extra_blobs = (max_blobs.saturating_sub(min_blobs) as f64 * pressure).round() as usize;
+        BatchingParams {
+            window,
+            max_blobs: min_blobs.saturating_add(extra_blobs),
+        }
+    }
+}
+
+fn report(params: BatchingParams) {
+    metrics::gauge!(crate::metrics_init::BATCHING_WINDOW).set(params.window.as_secs_f64());
+    // allow: the number of blobs is bounded by the configured maximum and will not cause
+    // precision loss
+    #[allow(clippy::cast_precision_loss)]
+    metrics::gauge!(crate::metrics_init::BATCHING_MAX_BLOBS).set(params.max_blobs as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: BatchingBounds = BatchingBounds {
+        min_window: Duration::ZERO,
+        max_window: Duration::from_secs(10),
+        min_blobs: 10,
+        max_blobs: 110,
+    };
+
+    const MIN_PARAMS: BatchingParams = BatchingParams {
+        window: Duration::ZERO,
+        max_blobs: 10,
+    };
+
+    #[test]
+    fn steady_conditions_keep_batching_at_its_minimum() {
+        let batching = AdaptiveBatching::new(BOUNDS);
+        assert_eq!(MIN_PARAMS, batching.params());
+        for _ in 0..20 {
+            batching.observe(Duration::from_secs(12), 0.002);
+        }
+        assert_eq!(MIN_PARAMS, batching.params());
+    }
+
+    #[test]
+    fn gas_price_spike_widens_batching_within_bounds() {
+        let batching = AdaptiveBatching::new(BOUNDS);
+        batching.observe(Duration::from_secs(12), 0.002);
+        batching.observe(Duration::from_secs(12), 0.004);
+        let params = batching.params();
+        assert!(params.window > MIN_PARAMS.window, "{params:?}");
+        assert!(params.max_blobs > MIN_PARAMS.max_blobs, "{params:?}");
+
+        for _ in 0..10 {
+            batching.observe(Duration::from_secs(12), 0.1);
+        }
+        assert_eq!(
+            BatchingParams {
+                window: BOUNDS.max_window,
+                max_blobs: BOUNDS.max_blobs,
+            },
+            batching.params(),
+        );
+    }
+
+    #[test]
+    fn inclusion_latency_spike_widens_batching() {
+        let batching = AdaptiveBatching::new(BOUNDS);
+        batching.observe(Duration::from_secs(12), 0.002);
+        batching.observe(Duration::from_secs(60), 0.002);
+        assert!(batching.params().window > MIN_PARAMS.window);
+    }
+
+    #[test]
+    fn batching_returns_to_its_minimum_once_fees_settle() {
+        let batching = AdaptiveBatching::new(BOUNDS);
+        batching.observe(Duration::from_secs(12), 0.002);
+        batching.observe(Duration::from_secs(12), 0.01);
+        assert_ne!(MIN_PARAMS, batching.params());
+        for _ in 0..20 {
+            batching.observe(Duration::from_secs(12), 0.002);
+        }
+        assert_eq!(MIN_PARAMS, batching.params());
+    }
+
+    #[test]
+    fn invalid_gas_prices_are_ignored() {
+        let batching = AdaptiveBatching::new(BOUNDS);
+        batching.observe(Duration::from_secs(12), 0.002);
+        batching.observe(Duration::from_secs(12), f64::INFINITY);
+        batching.observe(Duration::from_secs(12), 0.0);
+        assert_eq!(MIN_PARAMS, batching.params());
+    }
+}
@@ -1,6 +1,22 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+};
+
 use astria_core::{
     brotli::compress_bytes,
+    celestia::HexNamespace,
+    generated::sequencerblock::v1alpha1 as raw,
     primitive::v1::RollupId,
+    sequencerblock::v1alpha1::{
+        celestia::UncheckedCelestiaRollupBlob,
+        BlobFormat,
+        CelestiaEmptyBlocksMarker,
+        CelestiaRollupBlob,
+        CelestiaSequencerBlob,
+        RollupVerificationKey,
+        SignatureSchemeId,
+    },
 };
 use astria_eyre::eyre::{
     self,
@@ -13,10 +29,25 @@ use celestia_types::{
 use prost::Message as _;
 use sequencer_client::SequencerBlock;
 use tendermint::block::Height as SequencerHeight;
-use tracing::debug;
+use tracing::{
+    debug,
+    warn,
+};
 
 use crate::{
+    config::{
+        EmptyBlockPolicy,
+        RollupQuotaPolicy,
+        RollupQuotas,
+        RollupSigningKeyFile,
+        SequencerNamespaces,
+    },
     metrics_init,
+    relayer::{
+        AuxiliaryBlobs,
+        CelestiaKeys,
+    },
+    validator::Validator,
     IncludeRollup,
 };
 
@@ -34,7 +65,7 @@ where
     S: serde::ser::Serializer,
 {
     use serde::ser::Serialize as _;
-    telemetry::display::base64(namespace.as_bytes()).serialize(serializer)
+    HexNamespace(*namespace).to_string().serialize(serializer)
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -43,6 +74,61 @@ pub(super) struct RollupInfo {
     #[serde(serialize_with = "serialize_namespace")]
     celestia_namespace: Namespace,
     sequencer_rollup_id: RollupId,
+    signed: bool,
+}
+
+#[cfg(test)]
+impl RollupInfo {
+    /// Returns the info of an included rollup without transactions, written under `namespace`.
+    pub(super) fn included_under(celestia_namespace: Namespace) -> Self {
+        Self {
+            number_of_transactions: 0,
+            celestia_namespace,
+            sequencer_rollup_id: RollupId::new([0; 32]),
+            signed: false,
+        }
+    }
+}
+
+/// What was done with the data of a rollup that exceeded its quota.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum QuotaAction {
+    /// None of the rollup's data was written.
+    Rejected,
+    /// Only a prefix of the rollup's transactions was written.
+    Truncated,
+}
+
+/// A rollup whose data in a sequencer block exceeded its quota.
+#[derive(Debug, serde::Serialize)]
+pub(super) struct QuotaReport {
+    sequencer_rollup_id: RollupId,
+    #[serde(serialize_with = "serialize_namespace")]
+    celestia_namespace: Namespace,
+    /// The quota of the rollup in bytes.
+    quota_bytes: usize,
+    /// The size of the rollup's encoded blob before enforcing the quota.
+    data_bytes: usize,
+    action: QuotaAction,
+    transactions_kept: usize,
+    transactions_dropped: usize,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub(super) struct RollupResultInfo {
+    sequencer_rollup_id: RollupId,
+    rollup_block_number: u64,
+    #[serde(serialize_with = "serialize_namespace")]
+    celestia_namespace: Namespace,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub(super) struct AuxiliaryInfo {
+    kind: String,
+    data_size: usize,
+    #[serde(serialize_with = "serialize_namespace")]
+    celestia_namespace: Namespace,
 }
 
 /// Information about a sequencer block that was converted to blobs.
@@ -50,39 +136,194 @@ pub(super) struct RollupInfo {
 pub(super) struct ConversionInfo {
     #[serde(serialize_with = "serialize_height")]
     pub(super) sequencer_height: SequencerHeight,
+    #[serde(serialize_with = "astria_core::serde::base64::serialize")]
+    pub(super) sequencer_block_hash: [u8; 32],
+    pub(super) sequencer_chain_id: tendermint::chain::Id,
     #[serde(serialize_with = "serialize_namespace")]
     pub(super) sequencer_namespace: Namespace,
     pub(super) rollups_included: Vec<RollupInfo>,
     pub(super) rollups_excluded: Vec<RollupInfo>,
+    pub(super) rollups_over_quota: Vec<QuotaReport>,
+    pub(super) auxiliary_blobs: Vec<AuxiliaryInfo>,
+    pub(super) rollup_results: Vec<RollupResultInfo>,
+}
+
+impl ConversionInfo {
+    /// Returns the namespaces under which the data of the included rollups was written.
+    pub(super) fn rollup_namespaces(&self) -> impl Iterator<Item = Namespace> + '_ {
+        self.rollups_included
+            .iter()
+            .map(|rollup| rollup.celestia_namespace)
+    }
+
+    /// Returns the namespaces the block was written under, each with whom the data under it was
+    /// posted for.
+    pub(super) fn namespace_owners(&self) -> Vec<(Namespace, String)> {
+        let mut owners = vec![(
+            self.sequencer_namespace,
+            format!("sequencer {}", self.sequencer_chain_id),
+        )];
+        owners.extend(self.rollups_included.iter().map(|rollup| {
+            (
+                rollup.celestia_namespace,
+                format!("rollup {}", rollup.sequencer_rollup_id),
+            )
+        }));
+        owners.extend(self.auxiliary_blobs.iter().map(|auxiliary| {
+            (
+                auxiliary.celestia_namespace,
+                format!("auxiliary {}", auxiliary.kind),
+            )
+        }));
+        owners.extend(self.rollup_results.iter().map(|result| {
+            (
+                result.celestia_namespace,
+                format!("rollup {}", result.sequencer_rollup_id),
+            )
+        }));
+        owners
+    }
+}
+
+/// The key with which the blobs of a rollup are signed on its behalf, in the signature scheme
+/// the rollup verifies.
+#[derive(Clone)]
+pub(crate) enum RollupSigningKey {
+    Ed25519(ed25519_consensus::SigningKey),
+    Secp256k1(k256::ecdsa::SigningKey),
+}
+
+impl RollupSigningKey {
+    /// Reads the key from `file`: a CometBFT private validator key file for ed25519 keys, or a
+    /// file containing the hex encoded secret key for secp256k1 keys.
+    pub(crate) fn from_file(file: &RollupSigningKeyFile) -> eyre::Result<Self> {
+        match file.scheme {
+            SignatureSchemeId::Ed25519 => {
+                let validator =
+                    Validator::from_path(&file.path).wrap_err("failed reading ed25519 key file")?;
+                Ok(Self::Ed25519(validator.signing_key.clone()))
+            }
+            SignatureSchemeId::Secp256k1 => {
+                let keys = CelestiaKeys::from_path(&file.path)
+                    .wrap_err("failed reading secp256k1 key file")?;
+                Ok(Self::Secp256k1(keys.signing_key))
+            }
+        }
+    }
+
+    /// Returns the key verifying the signatures of `self`.
+    pub(crate) fn verification_key(&self) -> RollupVerificationKey {
+        match self {
+            Self::Ed25519(signing_key) => signing_key.verification_key().into(),
+            Self::Secp256k1(signing_key) => (*signing_key.verifying_key()).into(),
+        }
+    }
+
+    fn sign(&self, blob: &mut CelestiaRollupBlob) {
+        match self {
+            Self::Ed25519(signing_key) => blob.sign(signing_key),
+            Self::Secp256k1(signing_key) => blob.sign(signing_key),
+        }
+    }
+}
+
+/// The keys with which the blobs of rollups are signed on their behalf.
+///
+/// Rollups without a key have their blobs written unsigned.
+#[derive(Clone, Default)]
+pub(crate) struct RollupSigningKeys(Arc<HashMap<RollupId, RollupSigningKey>>);
+
+impl RollupSigningKeys {
+    pub(crate) fn new(keys: HashMap<RollupId, RollupSigningKey>) -> Self {
+        Self(Arc::new(keys))
+    }
+
+    fn get(&self, rollup_id: &RollupId) -> Option<&RollupSigningKey> {
+        self.0.get(rollup_id)
+    }
+}
+
+/// Only lists the rollups that have a key, so that the keys themselves are never logged.
+impl std::fmt::Debug for RollupSigningKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// The settings applied when converting sequencer blocks to blobs.
+#[derive(Clone, Debug)]
+pub(crate) struct ConversionSettings {
+    /// The rollups whose data should be included in submissions.
+    pub(crate) rollup_filter: IncludeRollup,
+    /// The Celestia namespaces under which the blocks of each sequencer network are written.
+    pub(crate) sequencer_namespaces: SequencerNamespaces,
+    /// The operator defined auxiliary blobs waiting to be submitted with their sequencer blocks.
+    pub(crate) auxiliary_blobs: Arc<AuxiliaryBlobs>,
+    /// The format in which sequencer header and rollup blobs are encoded. Auxiliary blobs and
+    /// rollup results are always encoded as protobuf.
+    pub(crate) blob_format: BlobFormat,
+    /// The keys with which the blobs of rollups are signed on their behalf.
+    pub(crate) rollup_signing_keys: RollupSigningKeys,
+    /// The maximum number of bytes written for individual rollups per sequencer block.
+    pub(crate) rollup_quotas: RollupQuotas,
+    /// What is written for sequencer blocks without rollup data.
+    pub(crate) empty_block_policy: EmptyBlockPolicy,
 }
 
 /// The result of a sequencer block that was converted to blobs.
 pub(super) struct Converted {
     pub(super) blobs: Vec<Blob>,
     pub(super) info: ConversionInfo,
+    /// The sequencer blob of the block if it carried no rollup data, auxiliary blobs or rollup
+    /// results, and the empty block policy does not write such blocks like any other.
+    pub(super) empty_sequencer_blob: Option<CelestiaSequencerBlob>,
+}
+
+/// Sorts `blobs` into the order in which they are written to Celestia in a single submission.
+///
+/// Blobs are ordered by their namespace, compared byte by byte, and blobs under the same
+/// namespace by their chunk index, which is their position in `blobs` before sorting. Because
+/// blobs are queued in the order their sequencer blocks were received, the chunk index orders
+/// the blobs of a namespace by sequencer height. This order is part of the wire contract:
+/// every relayer produces identical submissions for the same sequencer blocks.
+pub(super) fn sort_for_submission(blobs: &mut [Blob]) {
+    // `sort_by` is stable, which preserves the chunk index of blobs sharing a namespace.
+    blobs.sort_by(|a, b| a.namespace.as_bytes().cmp(b.namespace.as_bytes()));
 }
 
 /// Convert the given sequencer block into a collection of blobs and related metadata.
 ///
-/// Only blobs from the rollups specified in `rollup_filter` will be included.
-// allow: we'd need static lifetime on a ref to avoid pass-by-value here.
-#[allow(clippy::needless_pass_by_value)]
+/// Only blobs from the rollups specified in the settings' `rollup_filter` will be included, and
+/// only as much of their data as their quota in the settings' `rollup_quotas` allows. The
+/// sequencer blob is written under the namespace configured for the block's sequencer network.
+/// The auxiliary blobs pending for the block are bound to its hash and written under their own
+/// namespaces. All queued rollup results are written under the result namespaces of their
+/// rollups.
 pub(super) fn convert(
     block: SequencerBlock,
-    rollup_filter: IncludeRollup,
+    settings: &ConversionSettings,
 ) -> eyre::Result<Converted> {
     let sequencer_height = block.height();
+    let sequencer_block_hash = block.block_hash();
+    let sequencer_chain_id = block.header().chain_id().clone();
     let mut total_data_uncompressed_size = 0;
     let mut total_data_compressed_size = 0;
 
     let (sequencer_blob, rollup_blobs) = block.into_celestia_blobs();
+    let mut empty_sequencer_blob = (settings.empty_block_policy != EmptyBlockPolicy::Post
+        && sequencer_blob.is_empty())
+    .then(|| sequencer_blob.clone());
     // Allocate extra space: one blob for the sequencer blob "header",
     // the rest for the rollup blobs.
     let mut blobs = Vec::with_capacity(rollup_blobs.len() + 1);
-    let sequencer_namespace = astria_core::celestia::namespace_v0_from_sha256_of_bytes(
-        sequencer_blob.header().chain_id().as_str(),
-    );
-    let sequencer_blob_raw = sequencer_blob.into_raw().encode_to_vec();
+    let sequencer_namespace = settings
+        .sequencer_namespaces
+        .get(sequencer_blob.header().chain_id().as_str());
+    let sequencer_blob_raw = settings
+        .blob_format
+        .supported_by::<raw::CelestiaSequencerBlob>()
+        .encode(&sequencer_blob.into_raw())
+        .wrap_err("failed encoding sequencer blob")?;
     total_data_uncompressed_size += sequencer_blob_raw.len();
     let compressed_sequencer_blob_raw =
         compress_bytes(&sequencer_blob_raw).wrap_err("failed compressing sequencer blob")?;
@@ -93,16 +334,52 @@ pub(super) fn convert(
     blobs.push(header_blob);
     let mut rollups_included = Vec::new();
     let mut rollups_excluded = Vec::new();
+    let mut rollups_over_quota = Vec::new();
     for blob in rollup_blobs {
         let rollup_id = blob.rollup_id();
         let namespace = astria_core::celestia::namespace_v0_from_rollup_id(rollup_id);
-        let info = RollupInfo {
+        let included = settings.rollup_filter.should_include(&rollup_id);
+        let signing_key = settings
+            .rollup_signing_keys
+            .get(&rollup_id)
+            .filter(|_| included);
+        let mut info = RollupInfo {
             number_of_transactions: blob.transactions().len(),
             celestia_namespace: namespace,
             sequencer_rollup_id: rollup_id,
+            signed: signing_key.is_some(),
         };
-        if rollup_filter.should_include(&rollup_id) {
-            let raw_blob = blob.into_raw().encode_to_vec();
+        if included {
+            let raw_blob = match settings.rollup_quotas.get(&rollup_id) {
+                None => encode_rollup_blob(blob, signing_key, settings.blob_format)?,
+                Some(quota_bytes) => {
+                    let (raw_blob, report) = enforce_quota(
+                        blob,
+                        signing_key,
+                        settings.blob_format,
+                        quota_bytes,
+                        settings.rollup_quotas.policy(),
+                    )?;
+                    if let Some(report) = report {
+                        warn!(
+                            %sequencer_height,
+                            rollup_id = %rollup_id,
+                            quota_bytes,
+                            data_bytes = report.data_bytes,
+                            action = ?report.action,
+                            transactions_dropped = report.transactions_dropped,
+                            "rollup data exceeds its quota",
+                        );
+                        metrics::counter!(metrics_init::ROLLUP_QUOTA_EXCEEDED_COUNT).increment(1);
+                        info.number_of_transactions = report.transactions_kept;
+                        rollups_over_quota.push(report);
+                    }
+                    let Some(raw_blob) = raw_blob else {
+                        continue;
+                    };
+                    raw_blob
+                }
+            };
             total_data_uncompressed_size += raw_blob.len();
             let compressed_blob = compress_bytes(&raw_blob)
                 .wrap_err_with(|| format!("failed compressing rollup `{rollup_id}`"))?;
@@ -116,6 +393,52 @@ pub(super) fn convert(
         }
     }
 
+    let mut auxiliary_blobs = Vec::new();
+    for pending in settings.auxiliary_blobs.take(sequencer_chain_id.as_str(), sequencer_height) {
+        let (namespace, blob) = pending.into_celestia_blob(sequencer_block_hash);
+        let info = AuxiliaryInfo {
+            kind: blob.kind().to_string(),
+            data_size: blob.data().len(),
+            celestia_namespace: namespace,
+        };
+        let raw_blob = blob.into_raw().encode_to_vec();
+        total_data_uncompressed_size += raw_blob.len();
+        let compressed_blob = compress_bytes(&raw_blob).wrap_err_with(|| {
+            format!("failed compressing auxiliary blob of kind `{}`", info.kind)
+        })?;
+        total_data_compressed_size += compressed_blob.len();
+        let blob = Blob::new(namespace, compressed_blob).wrap_err_with(|| {
+            format!("failed creating blob for auxiliary blob of kind `{}`", info.kind)
+        })?;
+        blobs.push(blob);
+        auxiliary_blobs.push(info);
+    }
+
+    let mut rollup_results = Vec::new();
+    for result in settings.auxiliary_blobs.take_rollup_results() {
+        let rollup_id = result.rollup_id();
+        let namespace = astria_core::celestia::rollup_result_namespace(rollup_id);
+        let info = RollupResultInfo {
+            sequencer_rollup_id: rollup_id,
+            rollup_block_number: result.rollup_block_number(),
+            celestia_namespace: namespace,
+        };
+        let raw_blob = result.into_raw().encode_to_vec();
+        total_data_uncompressed_size += raw_blob.len();
+        let compressed_blob = compress_bytes(&raw_blob)
+            .wrap_err_with(|| format!("failed compressing result of rollup `{rollup_id}`"))?;
+        total_data_compressed_size += compressed_blob.len();
+        let blob = Blob::new(namespace, compressed_blob)
+            .wrap_err_with(|| format!("failed creating blob for result of rollup `{rollup_id}`"))?;
+        blobs.push(blob);
+        rollup_results.push(info);
+    }
+
+    // Auxiliary blobs and rollup results are bound to the block, so it must be written as usual.
+    if !auxiliary_blobs.is_empty() || !rollup_results.is_empty() {
+        empty_sequencer_blob = None;
+    }
+
     // gauges require f64, it's okay if the metrics get messed up by overflow or precision loss
     #[allow(clippy::cast_precision_loss)]
     let compression_ratio = total_data_uncompressed_size as f64 / total_data_compressed_size as f64;
@@ -134,9 +457,246 @@ pub(super) fn convert(
         blobs,
         info: ConversionInfo {
             sequencer_height,
+            sequencer_block_hash,
+            sequencer_chain_id,
             sequencer_namespace,
             rollups_included,
             rollups_excluded,
+            rollups_over_quota,
+            auxiliary_blobs,
+            rollup_results,
         },
+        empty_sequencer_blob,
     })
 }
+
+/// Merges the sequencer blobs of empty blocks written under `namespace` into a single
+/// [`CelestiaEmptyBlocksMarker`] blob, encoded in `format` or as protobuf if markers have no
+/// encoding in `format`.
+pub(super) fn convert_empty_blocks(
+    namespace: Namespace,
+    empty_blocks: Vec<CelestiaSequencerBlob>,
+    format: BlobFormat,
+) -> eyre::Result<Blob> {
+    let marker = CelestiaEmptyBlocksMarker::new(empty_blocks)
+        .wrap_err("failed constructing marker of empty blocks")?;
+    let raw_marker = format
+        .supported_by::<raw::CelestiaEmptyBlocksMarker>()
+        .encode(&marker.into_raw())
+        .wrap_err("failed encoding marker of empty blocks")?;
+    let compressed_marker =
+        compress_bytes(&raw_marker).wrap_err("failed compressing marker of empty blocks")?;
+    Blob::new(namespace, compressed_marker)
+        .wrap_err("failed creating blob for marker of empty blocks")
+}
+
+/// Signs `blob` with `signing_key`, if set, and encodes it in `format`.
+fn encode_rollup_blob(
+    mut blob: CelestiaRollupBlob,
+    signing_key: Option<&RollupSigningKey>,
+    format: BlobFormat,
+) -> eyre::Result<Vec<u8>> {
+    let rollup_id = blob.rollup_id();
+    if let Some(signing_key) = signing_key {
+        signing_key.sign(&mut blob);
+    }
+    format
+        .encode(&blob.into_raw())
+        .wrap_err_with(|| format!("failed encoding rollup `{rollup_id}`"))
+}
+
+/// Encodes `blob` like [`encode_rollup_blob`], enforcing that the encoding is at most
+/// `quota_bytes` long.
+///
+/// Returns the encoding to write, if any, and a report if the blob exceeded the quota. If it
+/// did, the blob is rejected or its transactions truncated according to `policy`.
+fn enforce_quota(
+    blob: CelestiaRollupBlob,
+    signing_key: Option<&RollupSigningKey>,
+    format: BlobFormat,
+    quota_bytes: usize,
+    policy: RollupQuotaPolicy,
+) -> eyre::Result<(Option<Vec<u8>>, Option<QuotaReport>)> {
+    let blob = blob.into_unchecked();
+    let number_of_transactions = blob.transactions.len();
+    let encode_prefix = |len: usize| {
+        let prefix = UncheckedCelestiaRollupBlob {
+            sequencer_block_hash: blob.sequencer_block_hash,
+            rollup_id: blob.rollup_id,
+            transactions: blob.transactions[..len].to_vec(),
+            proof: blob.proof.clone(),
+            signature: None,
+        };
+        encode_rollup_blob(prefix.into_celestia_rollup_blob(), signing_key, format)
+    };
+
+    let raw_blob = encode_prefix(number_of_transactions)?;
+    if raw_blob.len() <= quota_bytes {
+        return Ok((Some(raw_blob), None));
+    }
+    let mut report = QuotaReport {
+        sequencer_rollup_id: blob.rollup_id,
+        celestia_namespace: astria_core::celestia::namespace_v0_from_rollup_id(blob.rollup_id),
+        quota_bytes,
+        data_bytes: raw_blob.len(),
+        action: QuotaAction::Rejected,
+        transactions_kept: 0,
+        transactions_dropped: number_of_transactions,
+    };
+    if policy == RollupQuotaPolicy::Reject {
+        return Ok((None, Some(report)));
+    }
+
+    // The encoding grows with every transaction, so the longest prefix fitting the quota is
+    // found by binary search. `fits` is the longest prefix known to fit, with its encoding in
+    // `truncated`, and `exceeds` the shortest prefix known to exceed the quota. An empty prefix
+    // is never written.
+    let (mut fits, mut exceeds) = (0, number_of_transactions);
+    let mut truncated = None;
+    while exceeds - fits > 1 {
+        let len = fits + (exceeds - fits) / 2;
+        let raw_blob = encode_prefix(len)?;
+        if raw_blob.len() <= quota_bytes {
+            fits = len;
+            truncated = Some(raw_blob);
+        } else {
+            exceeds = len;
+        }
+    }
+    if truncated.is_some() {
+        report.action = QuotaAction::Truncated;
+        report.transactions_kept = fits;
+        report.transactions_dropped = number_of_transactions - fits;
+    }
+    Ok((truncated, Some(report)))
+}
+
+#[cfg(test)]
+mod tests {
+    use astria_core::{
+        celestia::namespace_v0_from_first_10_bytes,
+        protocol::test_utils::ConfigureSequencerBlock,
+        sequencerblock::v1alpha1::{
+            BlobFormat,
+            CelestiaRollupBlob,
+        },
+    };
+    use celestia_types::{
+        nmt::Namespace,
+        Blob,
+    };
+
+    use super::{
+        encode_rollup_blob,
+        enforce_quota,
+        sort_for_submission,
+        QuotaAction,
+        RollupQuotaPolicy,
+    };
+
+    fn blob(namespace: u8, data: u8) -> Blob {
+        Blob::new(namespace_v0_from_first_10_bytes(&[namespace; 10]), vec![data]).unwrap()
+    }
+
+    fn namespaces_and_data(blobs: &[Blob]) -> Vec<(Namespace, Vec<u8>)> {
+        blobs
+            .iter()
+            .map(|blob| (blob.namespace, blob.data.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn blobs_are_ordered_by_namespace_then_chunk_index() {
+        let mut blobs = vec![blob(3, 0), blob(1, 1), blob(3, 2), blob(2, 3), blob(1, 4)];
+        sort_for_submission(&mut blobs);
+        assert_eq!(
+            namespaces_and_data(&[blob(1, 1), blob(1, 4), blob(2, 3), blob(3, 0), blob(3, 2)]),
+            namespaces_and_data(&blobs),
+        );
+    }
+
+    #[test]
+    fn order_does_not_depend_on_order_of_namespaces() {
+        let mut first = vec![blob(1, 0), blob(2, 1), blob(1, 2), blob(3, 3)];
+        let mut second = vec![blob(3, 3), blob(1, 0), blob(2, 1), blob(1, 2)];
+        sort_for_submission(&mut first);
+        sort_for_submission(&mut second);
+        assert_eq!(namespaces_and_data(&first), namespaces_and_data(&second));
+    }
+
+    fn rollup_blob_with_ten_transactions() -> CelestiaRollupBlob {
+        let block = ConfigureSequencerBlock {
+            block_hash: Some([99; 32]),
+            height: 5,
+            sequence_data: (0..10).map(|i| ([1; 32].into(), vec![i; 100])).collect(),
+            ..Default::default()
+        }
+        .make();
+        let (_, mut rollup_blobs) = block.into_celestia_blobs();
+        let blob = rollup_blobs.pop().unwrap();
+        assert_eq!(10, blob.transactions().len());
+        blob
+    }
+
+    #[test]
+    fn blob_within_quota_is_written_unchanged() {
+        let blob = rollup_blob_with_ten_transactions();
+        let full = encode_rollup_blob(blob.clone(), None, BlobFormat::Protobuf).unwrap();
+        let (raw_blob, report) = enforce_quota(
+            blob,
+            None,
+            BlobFormat::Protobuf,
+            full.len(),
+            RollupQuotaPolicy::Reject,
+        )
+        .unwrap();
+        assert_eq!(Some(full), raw_blob);
+        assert!(report.is_none());
+    }
+
+    #[test]
+    fn blob_exceeding_quota_is_rejected() {
+        let blob = rollup_blob_with_ten_transactions();
+        let full = encode_rollup_blob(blob.clone(), None, BlobFormat::Protobuf).unwrap();
+        let (raw_blob, report) = enforce_quota(
+            blob,
+            None,
+            BlobFormat::Protobuf,
+            full.len() - 1,
+            RollupQuotaPolicy::Reject,
+        )
+        .unwrap();
+        assert!(raw_blob.is_none());
+        let report = report.unwrap();
+        assert_eq!(QuotaAction::Rejected, report.action);
+        assert_eq!(full.len(), report.data_bytes);
+        assert_eq!(10, report.transactions_dropped);
+    }
+
+    #[test]
+    fn blob_exceeding_quota_is_truncated_to_longest_fitting_prefix() {
+        let blob = rollup_blob_with_ten_transactions();
+        let full = encode_rollup_blob(blob.clone(), None, BlobFormat::Protobuf).unwrap();
+        // Each transaction takes a little more than 100 bytes, so dropping the last three fits.
+        let quota_bytes = full.len() - 250;
+        let (raw_blob, report) = enforce_quota(
+            blob.clone(),
+            None,
+            BlobFormat::Protobuf,
+            quota_bytes,
+            RollupQuotaPolicy::Truncate,
+        )
+        .unwrap();
+        assert!(raw_blob.unwrap().len() <= quota_bytes);
+        let report = report.unwrap();
+        assert_eq!(QuotaAction::Truncated, report.action);
+        assert_eq!(7, report.transactions_kept);
+        assert_eq!(3, report.transactions_dropped);
+
+        let (raw_blob, report) =
+            enforce_quota(blob, None, BlobFormat::Protobuf, 1, RollupQuotaPolicy::Truncate)
+                .unwrap();
+        assert!(raw_blob.is_none());
+        assert_eq!(QuotaAction::Rejected, report.unwrap().action);
+    }
+}
@@ -4,23 +4,78 @@
 //! converts them to Celestia [`Blob`]s, and writes them to Celestia
 //! using the `blob.Submit` API.
 //!
+//! The write path is split into explicit stages, each connected to the next by a bounded
+//! channel:
+//!
+//! 1. ingest: sequencer blocks are sent to [`BlobSubmitter`] through a [`BlobSubmitterHandle`];
+//! 2. assembly: [`BlobSubmitter`] converts blocks to blobs and batches them, sealing a batch
+//!    once its batching window has passed or it holds the maximum number of blobs, both chosen
+//!    by [`AdaptiveBatching`] within the configured bounds;
+//! 3. submission and confirmation: [`SubmissionStage`] broadcasts batches to Celestia and waits
+//!    for them to be included in a block, with at most `max_concurrent_submissions` batches in
+//!    flight.
+//!
+//! Because every stage only accepts new work when it has capacity, a slow Celestia node
+//! fills up the queues stage by stage until the ingest channel is full, at which point the
+//! relayer stops reading blocks from the sequencer.
+//!
 //! [`BlobSubmitter`] submits converted blobs strictly in the order it
-//! receives blocks and imposes no extra ordering. This means that if
-//! another task sends sequencer blocks ordered by their heights, then
-//! they will be written in that order.
+//! receives blocks. This means that if another task sends sequencer blocks
+//! ordered by their heights, then they will be written in that order.
+//!
+//! Within a single submission the blobs of all batched blocks are ordered
+//! by namespace, then by chunk index, as described in
+//! [`conversion::sort_for_submission`].
+//!
+//! Blocks without rollup data are handled according to the configured [`EmptyBlockPolicy`].
+//! Unless they are posted like any other block, they are held back in the open batch and do
+//! not make it due on their own: they are submitted with the next block carrying rollup data,
+//! or once the batch holds the maximum number of blobs worth of them.
+//!
+//! If rollup blob deduplication is enabled, rollup blobs with exactly the same data as one
+//! included in an earlier submission are posted as references to it, see
+//! [`astria_celestia_client::dedup`].
 use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+        HashSet,
+    },
     future::Future,
     mem,
-    sync::Arc,
-    task::Poll,
-    time::Duration,
+    sync::{
+        atomic::{
+            AtomicU32,
+            Ordering,
+        },
+        Arc,
+    },
+    time::{
+        Duration,
+        SystemTime,
+    },
 };
 
+use astria_celestia_client::dedup::PostedBlobs;
+use astria_core::{
+    attestation::{
+        AttestedBlob,
+        AttestedSequencerBlock,
+    },
+    celestia::HexNamespace,
+    sequencerblock::v1alpha1::{
+        BlobFormat,
+        CelestiaSequencerBlob,
+    },
+};
 use astria_eyre::eyre::{
     self,
     WrapErr as _,
 };
-use celestia_types::Blob;
+use celestia_types::{
+    nmt::Namespace,
+    Blob,
+};
 use futures::{
     future::{
         BoxFuture,
@@ -30,9 +85,11 @@ use futures::{
     stream::FuturesOrdered,
     FutureExt as _,
 };
-use pin_project_lite::pin_project;
 use sequencer_client::{
-    tendermint::block::Height as SequencerHeight,
+    tendermint::{
+        block::Height as SequencerHeight,
+        chain,
+    },
     SequencerBlock,
 };
 use tokio::{
@@ -47,6 +104,11 @@ use tokio::{
         },
         watch,
     },
+    task::JoinHandle,
+    time::{
+        sleep_until,
+        Instant,
+    },
 };
 use tokio_util::sync::CancellationToken;
 use tracing::{
@@ -58,20 +120,60 @@ use tracing::{
     Instrument,
     Span,
 };
+use tryhard::{
+    backoff_strategies::BackoffStrategy,
+    RetryPolicy,
+};
 
 use super::{
-    celestia_client::CelestiaClient,
+    celestia_client::{
+        CelestiaClient,
+        ErrorClass,
+        ErrorPolicy,
+    },
     BuilderError,
     CelestiaClientBuilder,
+    Heartbeat,
+    IdempotencyKey,
+    Inclusion,
+    LatencyTracker,
     SubmissionState,
+    SubmissionStates,
     TrySubmitError,
 };
-use crate::IncludeRollup;
-
+use crate::{
+    alerts::{
+        Alert,
+        Alerter,
+    },
+    attestations::AttestationStore,
+    config::EmptyBlockPolicy,
+    costs::{
+        CostLedger,
+        CostRecord,
+        NamespaceBytes,
+    },
+    height_index::{
+        HeightIndex,
+        HeightMapping,
+    },
+};
+mod batching;
 mod conversion;
-
+#[cfg(test)]
+mod tests;
+
+use batching::AdaptiveBatching;
+pub(crate) use batching::BatchingBounds;
+pub(crate) use conversion::{
+    ConversionSettings,
+    RollupSigningKey,
+    RollupSigningKeys,
+};
 use conversion::{
     convert,
+    convert_empty_blocks,
+    sort_for_submission,
     ConversionInfo,
     Converted,
 };
@@ -79,14 +181,30 @@ use conversion::{
 struct QueuedConvertedBlocks {
     // The maximum number of blobs permitted to sit in the blob queue.
     max_blobs: usize,
+    // When the first of the queued blobs was queued.
+    opened_at: Option<Instant>,
     blobs: Vec<Blob>,
     infos: Vec<ConversionInfo>,
-    greatest_sequencer_height: Option<SequencerHeight>,
+    // The greatest height of the queued sequencer blocks, per sequencer chain.
+    greatest_sequencer_heights: HashMap<chain::Id, SequencerHeight>,
+    // What is written for queued blocks without rollup data.
+    empty_block_policy: EmptyBlockPolicy,
+    // The format in which markers of empty blocks are encoded.
+    blob_format: BlobFormat,
+    // Whether a block with rollup data or a blob not derived from a sequencer block is queued.
+    // Empty blocks held back by the policy do not make the queue due on their own.
+    holds_data: bool,
+    // The number of queued empty blocks held back by the policy.
+    num_held_empty: usize,
+    // The empty blocks held back by the `marker` policy, merged into one marker per namespace
+    // when the queue is taken. Each is kept with its regular blobs, which are written instead
+    // if the marker cannot be created.
+    held_for_marker: Vec<(Namespace, CelestiaSequencerBlob, Vec<Blob>)>,
 }
 
 impl QueuedConvertedBlocks {
     fn is_empty(&self) -> bool {
-        self.blobs.is_empty()
+        self.blobs.is_empty() && self.infos.is_empty()
     }
 
     fn num_blobs(&self) -> usize {
@@ -100,59 +218,138 @@ impl QueuedConvertedBlocks {
     fn with_max_blobs(max_blobs: usize) -> Self {
         Self {
             max_blobs,
+            opened_at: None,
             blobs: Vec::new(),
             infos: Vec::new(),
-            greatest_sequencer_height: None,
+            greatest_sequencer_heights: HashMap::new(),
+            empty_block_policy: EmptyBlockPolicy::Post,
+            blob_format: BlobFormat::default(),
+            holds_data: false,
+            num_held_empty: 0,
+            held_for_marker: Vec::new(),
+        }
+    }
+
+    fn with_empty_block_policy(
+        self,
+        empty_block_policy: EmptyBlockPolicy,
+        blob_format: BlobFormat,
+    ) -> Self {
+        Self {
+            empty_block_policy,
+            blob_format,
+            ..self
         }
     }
 
     fn has_capacity(&self) -> bool {
-        self.blobs.len() < self.max_blobs
+        self.blobs.len() < self.max_blobs && self.num_held_empty < self.max_blobs
+    }
+
+    fn set_max_blobs(&mut self, max_blobs: usize) {
+        self.max_blobs = max_blobs;
+    }
+
+    /// Returns when the queued blobs are due to be submitted, or `None` if none are queued or
+    /// only empty blocks held back by the empty block policy.
+    ///
+    /// A full queue is due right away, any other once `window` has passed since its first blob
+    /// was queued.
+    fn due_at(&self, window: Duration) -> Option<Instant> {
+        let opened_at = self.opened_at?;
+        if !self.has_capacity() {
+            Some(opened_at)
+        } else if self.holds_data {
+            Some(opened_at + window)
+        } else {
+            None
+        }
     }
 
     fn push(&mut self, mut converted: Converted) {
-        self.blobs.append(&mut converted.blobs);
+        self.opened_at.get_or_insert_with(Instant::now);
         let info = converted.info;
+        match (converted.empty_sequencer_blob, self.empty_block_policy) {
+            (None, _) | (Some(_), EmptyBlockPolicy::Post) => {
+                self.blobs.append(&mut converted.blobs);
+                self.holds_data = true;
+            }
+            (Some(_), EmptyBlockPolicy::Batch) => {
+                self.blobs.append(&mut converted.blobs);
+                self.num_held_empty += 1;
+            }
+            (Some(sequencer_blob), EmptyBlockPolicy::Marker) => {
+                self.held_for_marker.push((
+                    info.sequencer_namespace,
+                    sequencer_blob,
+                    converted.blobs,
+                ));
+                self.num_held_empty += 1;
+            }
+        }
         let greatest_height = self
-            .greatest_sequencer_height
-            .get_or_insert(info.sequencer_height);
+            .greatest_sequencer_heights
+            .entry(info.sequencer_chain_id.clone())
+            .or_insert(info.sequencer_height);
         *greatest_height = std::cmp::max(*greatest_height, info.sequencer_height);
         self.infos.push(info);
     }
 
-    /// Lazily move the currently queued blobs out of the queue.
-    ///
-    /// The main reason for this method to exist is to work around async-cancellation.
-    /// Only when the returned [`TakeQueued`] future is polled are the blobs moved
-    /// out of the queue.
-    fn take(&mut self) -> TakeQueued<'_> {
-        TakeQueued {
-            inner: Some(self),
-        }
+    /// Queues a blob that is not derived from a sequencer block.
+    fn push_blob(&mut self, blob: Blob) {
+        self.opened_at.get_or_insert_with(Instant::now);
+        self.blobs.push(blob);
+        self.holds_data = true;
     }
-}
 
-pin_project! {
-    struct TakeQueued<'a> {
-        inner: Option<&'a mut QueuedConvertedBlocks>,
+    /// Moves the currently queued blobs out of the queue, leaving an empty queue behind.
+    ///
+    /// Empty blocks held back by the `marker` policy are merged into one marker per namespace.
+    /// The returned blobs are sorted in submission order.
+    fn take(&mut self) -> Self {
+        let empty = Self::with_max_blobs(self.max_blobs)
+            .with_empty_block_policy(self.empty_block_policy, self.blob_format);
+        let mut queued = mem::replace(self, empty);
+        queued.merge_held_for_marker();
+        sort_for_submission(&mut queued.blobs);
+        queued
     }
-}
 
-impl<'a> Future for TakeQueued<'a> {
-    type Output = Option<QueuedConvertedBlocks>;
-
-    fn poll(self: std::pin::Pin<&mut Self>, _: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let queued = self
-            .project()
-            .inner
-            .take()
-            .expect("this future must not be polled twice");
-        let empty = QueuedConvertedBlocks::with_max_blobs(queued.max_blobs);
-        let queued = mem::replace(queued, empty);
-        if queued.is_empty() {
-            Poll::Ready(None)
-        } else {
-            Poll::Ready(Some(queued))
+    /// Replaces the empty blocks held back by the `marker` policy with one marker blob per
+    /// namespace.
+    ///
+    /// A single empty block under a namespace is written as its regular sequencer blob, as is
+    /// every block of a marker that could not be created.
+    fn merge_held_for_marker(&mut self) {
+        let mut by_namespace = BTreeMap::<_, Vec<_>>::new();
+        for held in self.held_for_marker.drain(..) {
+            by_namespace
+                .entry(held.0.as_bytes().to_vec())
+                .or_default()
+                .push(held);
+        }
+        for (_, held) in by_namespace {
+            if let [(_, _, blobs)] = &held[..] {
+                self.blobs.extend_from_slice(blobs);
+                continue;
+            }
+            let namespace = held[0].0;
+            let empty_blocks = held
+                .iter()
+                .map(|(_, sequencer_blob, _)| sequencer_blob.clone())
+                .collect();
+            match convert_empty_blocks(namespace, empty_blocks, self.blob_format) {
+                Ok(marker) => self.blobs.push(marker),
+                Err(error) => {
+                    warn!(
+                        namespace = %HexNamespace(namespace),
+                        %error,
+                        "failed merging empty blocks into a marker; writing them as regular \
+                         sequencer blobs",
+                    );
+                    self.blobs.extend(held.into_iter().flat_map(|(_, _, blobs)| blobs));
+                }
+            }
         }
     }
 }
@@ -186,14 +383,37 @@ impl BlobSubmitterHandle {
     ) -> Result<(), SendError<SequencerBlock>> {
         self.tx.send(block).await
     }
+
+    /// Returns the number of blocks sent to the blob submitter that it has not yet picked up.
+    pub(super) fn num_queued_blocks(&self) -> usize {
+        self.tx.max_capacity().saturating_sub(self.tx.capacity())
+    }
 }
 
+/// The number of sequencer blocks that can be sent to the blob submitter before
+/// [`BlobSubmitterHandle::try_send`] reports it as full.
+// XXX: The channel size here is just a number. It should probably be based on some
+// heuristic about the number of expected blobs in a block.
+const BLOCK_QUEUE_CAPACITY: usize = 128;
+
+/// The maximum number of sequencer blocks that are converted to blobs at the same time.
+const MAX_CONCURRENT_CONVERSIONS: usize = 8;
+
+/// The number of assembled batches that can wait for the submission stage while another batch
+/// is being submitted.
+///
+/// This is kept at 1 so that once a batch is waiting, the assembly stage keeps adding blobs to
+/// its open batch instead of sealing many small ones, which keeps the number of Celestia
+/// transactions low.
+const BATCH_QUEUE_CAPACITY: usize = 1;
+
 pub(super) struct BlobSubmitter {
     /// The builder for a client to submit blobs to Celestia.
     client_builder: CelestiaClientBuilder,
 
-    /// The rollups whose data should be included in submissions.
-    rollup_filter: IncludeRollup,
+    /// The rollups whose data should be included in submissions, and the namespaces under which
+    /// sequencer blobs are written.
+    conversion_settings: ConversionSettings,
 
     /// The channel over which sequencer blocks are received.
     blocks: mpsc::Receiver<SequencerBlock>,
@@ -205,12 +425,40 @@ pub(super) struct BlobSubmitter {
     /// Celestia blobs waiting to be submitted after conversion from sequencer blocks.
     blobs: QueuedConvertedBlocks,
 
+    /// Chooses how long blobs are batched and how many blobs a batch holds at most.
+    batching: Arc<AdaptiveBatching>,
+
+    /// The maximum number of batches of blobs submitted to Celestia at the same time.
+    max_concurrent_submissions: usize,
+
+    /// The number of most recently included rollup blobs remembered to deduplicate repeated
+    /// rollup blobs. 0 disables deduplication.
+    rollup_blob_dedup_capacity: usize,
+
+    /// Creates the signed heartbeats added to submissions, if enabled.
+    heartbeat: Option<Heartbeat>,
+
     /// The state of the relayer.
     state: Arc<super::State>,
 
-    /// Tracks the submission state and writes it to disk before and after each Celestia
-    /// submission.
-    submission_state: SubmissionState,
+    /// Tracks how long sequencer blocks take to move through the write path.
+    latencies: Arc<LatencyTracker>,
+
+    /// Raises alerts when submissions keep failing or are not confirmed.
+    alerts: Alerter,
+
+    /// Records the data posted and fees paid for every confirmed submission, if configured.
+    cost_ledger: Option<CostLedger>,
+
+    /// Records the Celestia height of every confirmed sequencer block, if configured.
+    height_index: Option<Arc<HeightIndex>>,
+
+    /// Records a signed attestation of every confirmed submission, if configured.
+    attestations: Option<Arc<AttestationStore>>,
+
+    /// The submission states read at startup, handed to the submission stage once the Celestia
+    /// client is initialized.
+    submission_states: SubmissionStates,
 
     /// The shutdown token to signal that blob submitter should finish its current submission and
     /// exit.
@@ -218,24 +466,49 @@ pub(super) struct BlobSubmitter {
 }
 
 impl BlobSubmitter {
+    // allow: the submitter is assembled from the parts it shares with the rest of the relayer;
+    // grouping them into a struct would only move the list of fields elsewhere.
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         client_builder: CelestiaClientBuilder,
-        rollup_filter: IncludeRollup,
+        conversion_settings: ConversionSettings,
+        batching_bounds: BatchingBounds,
+        max_concurrent_submissions: usize,
+        rollup_blob_dedup_capacity: usize,
+        heartbeat: Option<Heartbeat>,
         state: Arc<super::State>,
-        submission_state: SubmissionState,
+        latencies: Arc<LatencyTracker>,
+        alerts: Alerter,
+        cost_ledger: Option<CostLedger>,
+        height_index: Option<Arc<HeightIndex>>,
+        attestations: Option<Arc<AttestationStore>>,
+        submission_states: SubmissionStates,
         shutdown_token: CancellationToken,
     ) -> (Self, BlobSubmitterHandle) {
-        // XXX: The channel size here is just a number. It should probably be based on some
-        // heuristic about the number of expected blobs in a block.
-        let (tx, rx) = mpsc::channel(128);
+        let (tx, rx) = mpsc::channel(BLOCK_QUEUE_CAPACITY);
+        let batching = Arc::new(AdaptiveBatching::new(batching_bounds));
+        let blobs = QueuedConvertedBlocks::with_max_blobs(batching.params().max_blobs)
+            .with_empty_block_policy(
+                conversion_settings.empty_block_policy,
+                conversion_settings.blob_format,
+            );
         let submitter = Self {
             client_builder,
-            rollup_filter,
+            conversion_settings,
             blocks: rx,
-            conversions: Conversions::new(8),
-            blobs: QueuedConvertedBlocks::with_max_blobs(128),
+            conversions: Conversions::new(MAX_CONCURRENT_CONVERSIONS),
+            blobs,
+            batching,
+            max_concurrent_submissions,
+            rollup_blob_dedup_capacity,
+            heartbeat,
             state,
-            submission_state,
+            latencies,
+            alerts,
+            cost_ledger,
+            height_index,
+            attestations,
+            submission_states,
             shutdown_token,
         };
         let handle = BlobSubmitterHandle {
@@ -255,9 +528,31 @@ impl BlobSubmitter {
             error.wrap_err(message)
         })?;
 
-        let mut submission = Fuse::terminated();
+        let (batches, batches_rx) = mpsc::channel(BATCH_QUEUE_CAPACITY);
+        let mut submission_stage = SubmissionStage {
+            client,
+            batches: batches_rx,
+            max_concurrent_submissions: self.max_concurrent_submissions,
+            posted_blobs: (self.rollup_blob_dedup_capacity > 0)
+                .then(|| PostedBlobs::new(self.rollup_blob_dedup_capacity)),
+            batching: self.batching.clone(),
+            state: self.state.clone(),
+            latencies: Arc::clone(&self.latencies),
+            alerts: self.alerts.clone(),
+            cost_ledger: self.cost_ledger.clone(),
+            height_index: self.height_index.clone(),
+            attestations: self.attestations.clone(),
+            submission_states: self.submission_states.clone(),
+            shutdown_token: self.shutdown_token.clone(),
+        }
+        .spawn()
+        .fuse();
 
         let reason = loop {
+            let batching = self.batching.params();
+            self.blobs.set_max_blobs(batching.max_blobs);
+            let due_at = self.blobs.due_at(batching.window);
+            let is_due = due_at.is_some_and(|due_at| due_at <= Instant::now());
             select!(
                 biased;
 
@@ -266,29 +561,25 @@ impl BlobSubmitter {
                     break Ok("received shutdown signal");
                 }
 
-                // handle result of submitting blocks to Celestia, if in flight
-                submission_result = &mut submission, if !submission.is_terminated() => {
-                    // XXX: Breaks the select-loop and returns. With the current retry-logic in
-                    // `submit_blobs` this happens after u32::MAX retries which is effectively never.
-                    // self.submission_state = match submission_result.wrap_err("failed submitting blocks to Celestia")
-                    self.submission_state = match submission_result {
-                        Ok(state) => state,
-                        Err(err) => {
-                            // Use `wrap_err` on the return break value. Using it on the match-value causes
-                            // type inference to fail.
-                            break Err(err).wrap_err("failed submitting blocks to Celestia");
-                        }
+                // XXX: Breaks the select-loop and returns. With the current retry-logic in
+                // `submit_with_retry` the submission stage only exits with an error after u32::MAX
+                // retries which is effectively never, or once Celestia returns an error
+                // classified as fatal.
+                res = &mut submission_stage, if !submission_stage.is_terminated() => {
+                    break match crate::utils::flatten(res) {
+                        Ok(()) => Err(eyre::eyre!("submission stage exited unexpectedly")),
+                        Err(err) => Err(err).wrap_err("failed submitting blocks to Celestia"),
                     };
                 }
 
-                // submit blocks to Celestia, if no submission in flight
-                Some(blobs) = self.blobs.take(), if submission.is_terminated() => {
-                    submission = submit_blobs(
-                        client.clone(),
-                        blobs,
-                        self.state.clone(),
-                        self.submission_state.clone(),
-                    ).boxed().fuse();
+                // hand the queued blobs to the submission stage once they are due, if it can take
+                // another batch
+                Ok(permit) = batches.reserve(), if is_due => {
+                    self.queue_heartbeat();
+                    permit.send(self.blobs.take());
+                    metrics::gauge!(crate::metrics_init::IN_FLIGHT_CELESTIA_SUBMISSIONS)
+                        .increment(1);
+                    self.report_assembly_queue_depth();
                 }
 
                 // handle result of converting blocks to blobs
@@ -301,13 +592,20 @@ impl BlobSubmitter {
                         // have sequencer-relayer return with an error code (so that k8s can halt
                         // the chain)? This should probably be part of the protocol/sequencer
                         // proper.
-                        Err(error) => error!(
-                            %sequencer_height,
-                            %error,
-                            "failed converting sequencer blocks to celestia blobs",
-                        ),
-                        Ok(converted) => self.blobs.push(converted),
+                        Err(error) => {
+                            error!(
+                                %sequencer_height,
+                                %error,
+                                "failed converting sequencer blocks to celestia blobs",
+                            );
+                            self.latencies.forget(sequencer_height);
+                        }
+                        Ok(converted) => {
+                            self.latencies.assembled(&converted.info.sequencer_block_hash);
+                            self.blobs.push(converted);
+                        }
                     };
+                    self.report_assembly_queue_depth();
                 }
 
                 // enqueue new blocks for conversion to blobs if there is capacity
@@ -316,9 +614,14 @@ impl BlobSubmitter {
                         height = %block.height(),
                         "received sequencer block for submission",
                     );
-                    self.conversions.push(block, self.rollup_filter.clone());
+                    self.conversions.push(block, self.conversion_settings.clone());
+                    self.report_assembly_queue_depth();
                 }
 
+                // wake up once the batching window of the queued blobs has passed
+                () = sleep_until(due_at.unwrap_or_else(Instant::now)),
+                    if due_at.is_some() && !is_due => {}
+
             );
         };
 
@@ -327,101 +630,445 @@ impl BlobSubmitter {
             Err(reason) => error!(%reason, "starting shutdown"),
         }
 
-        if submission.is_terminated() {
-            info!("no submissions to Celestia were in flight, exiting now");
-        } else {
-            info!("a submission to Celestia is in flight; waiting for it to finish");
-            if let Err(error) = submission.await {
-                error!(%error, "last submission to Celestia failed before exiting");
+        // Dropping the sender lets the submission stage exit once it has no more batches.
+        drop(batches);
+        if !submission_stage.is_terminated() {
+            info!("waiting for the submission stage to finish");
+            if let Err(error) = crate::utils::flatten(submission_stage.await) {
+                error!(%error, "submission stage failed before exiting");
             }
         }
         reason.map(|_| ())
     }
 
+    /// Adds a heartbeat blob to the queued blobs if heartbeats are enabled and one is due.
+    fn queue_heartbeat(&mut self) {
+        let Some(heartbeat) = &mut self.heartbeat else {
+            return;
+        };
+        let state = self.state.snapshot();
+        if let Some(blob) = heartbeat.next_blob(
+            state.latest_confirmed_celestia_height(),
+            state.latest_confirmed_sequencer_height(),
+        ) {
+            self.blobs.push_blob(blob);
+        }
+    }
+
     /// Returns if the submitter has capacity for more blocks.
     fn has_capacity(&self) -> bool {
         self.conversions.has_capacity() && self.blobs.has_capacity()
     }
+
+    /// Records the number of blocks currently being converted or waiting to be batched.
+    fn report_assembly_queue_depth(&self) {
+        let num_blocks = self
+            .conversions
+            .num_active()
+            .saturating_add(self.blobs.num_converted());
+        // allow: the number of blocks is bounded by the queue capacities and will not cause
+        // precision loss
+        #[allow(clippy::cast_precision_loss)]
+        metrics::gauge!(crate::metrics_init::ASSEMBLY_QUEUED_SEQUENCER_BLOCKS)
+            .set(num_blocks as f64);
+    }
 }
 
-/// Submits new blobs Celestia.
+/// The stage submitting assembled batches of blobs to Celestia and waiting for their
+/// confirmation.
 ///
-/// # Panics
-/// Panics if `blocks` is empty. This function should only be called if there is something to
-/// submit.
-#[instrument(skip_all)]
-async fn submit_blobs(
+/// Up to `max_concurrent_submissions` batches are in flight at the same time. Further batches
+/// are held back in the bounded `batches` channel, which in turn stops the assembly stage from
+/// taking more blocks once its queues are full.
+///
+/// Submissions are completed in the order they were started, so that the post-submit file
+/// never records a sequencer height while submissions of lower heights are still outstanding.
+struct SubmissionStage {
+    /// The client to submit blobs to Celestia.
     client: CelestiaClient,
-    blocks: QueuedConvertedBlocks,
+
+    /// The channel over which batches of blobs are received from the assembly stage.
+    batches: mpsc::Receiver<QueuedConvertedBlocks>,
+
+    /// The maximum number of batches submitted to Celestia at the same time.
+    max_concurrent_submissions: usize,
+
+    /// The rollup blobs included in confirmed submissions, used to replace repeated rollup blobs
+    /// by references to them, if deduplication is enabled.
+    posted_blobs: Option<PostedBlobs>,
+
+    /// Informed of the gas price and inclusion latency of every confirmed submission.
+    batching: Arc<AdaptiveBatching>,
+
+    /// The state of the relayer.
     state: Arc<super::State>,
-    submission_state: SubmissionState,
-) -> eyre::Result<SubmissionState> {
-    info!(
-        blocks = %telemetry::display::json(&blocks.infos),
-        "initiated submission of sequencer blocks converted to Celestia blobs",
-    );
-
-    let start = std::time::Instant::now();
-
-    metrics::counter!(crate::metrics_init::CELESTIA_SUBMISSION_COUNT).increment(1);
-    // XXX: The number of sequencer blocks per celestia tx is equal to the number of heights passed
-    // into this function. This comes from the way that `QueuedBlocks::take` is implemented.
-    //
-    // allow: the number of blocks should always be low enough to not cause precision loss
-    #[allow(clippy::cast_precision_loss)]
-    let blocks_per_celestia_tx = blocks.num_converted() as f64;
-    metrics::gauge!(crate::metrics_init::BLOCKS_PER_CELESTIA_TX).set(blocks_per_celestia_tx);
-
-    // allow: the number of blobs should always be low enough to not cause precision loss
-    #[allow(clippy::cast_precision_loss)]
-    let blobs_per_celestia_tx = blocks.num_blobs() as f64;
-    metrics::gauge!(crate::metrics_init::BLOBS_PER_CELESTIA_TX).set(blobs_per_celestia_tx);
-
-    let largest_height = blocks.greatest_sequencer_height.expect(
-        "there should always be blobs and accompanying sequencer heights when this function is \
-         called",
-    );
-
-    let submission_started = match crate::utils::flatten(
-        tokio::task::spawn_blocking(move || submission_state.initialize(largest_height))
-            .in_current_span()
-            .await,
-    ) {
-        Err(error) => {
-            error!(%error, "failed to initialize submission; abandoning");
-            return Err(error);
+
+    /// Tracks how long sequencer blocks take to move through the write path.
+    latencies: Arc<LatencyTracker>,
+
+    /// Raises alerts when submissions keep failing or are not confirmed.
+    alerts: Alerter,
+
+    /// Records the data posted and fees paid for every confirmed submission, if configured.
+    cost_ledger: Option<CostLedger>,
+
+    /// Records the Celestia height of every confirmed sequencer block, if configured.
+    height_index: Option<Arc<HeightIndex>>,
+
+    /// Records a signed attestation of every confirmed submission, if configured.
+    attestations: Option<Arc<AttestationStore>>,
+
+    /// Tracks the submission state of each sequencer network and writes it to disk before and
+    /// after each Celestia submission.
+    submission_states: SubmissionStates,
+
+    /// The shutdown token to signal that the stage should not start new submissions.
+    shutdown_token: CancellationToken,
+}
+
+/// The sequencer blocks contained in a submission to Celestia.
+struct SubmittedBlocks {
+    /// The greatest sequencer height contained in the submission, per sequencer chain.
+    greatest_heights: Vec<(chain::Id, SequencerHeight)>,
+    /// The hashes of all sequencer blocks contained in the submission.
+    block_hashes: Vec<[u8; 32]>,
+    /// The chain ID, height and hash of the sequencer blocks written in the submission.
+    posted_blocks: Vec<(chain::Id, SequencerHeight, [u8; 32])>,
+    /// The bytes of blob data contained in the submission, per namespace.
+    posted: Vec<NamespaceBytes>,
+    /// The namespace and commitment of every blob contained in the submission.
+    blobs: Vec<AttestedBlob>,
+    /// The rollup blobs posted in full, remembered to deduplicate later submissions once the
+    /// submission was included.
+    rollup_blobs_in_full: Vec<Blob>,
+}
+
+/// A submission to Celestia in flight, resolving to the sequencer blocks it contains and where
+/// it was included.
+type InFlightSubmission = BoxFuture<'static, (SubmittedBlocks, eyre::Result<Inclusion>)>;
+
+impl SubmissionStage {
+    fn spawn(self) -> JoinHandle<eyre::Result<()>> {
+        tokio::spawn(self.run())
+    }
+
+    #[instrument(skip_all)]
+    async fn run(mut self) -> eyre::Result<()> {
+        use tokio_stream::StreamExt as _;
+
+        let mut in_flight: FuturesOrdered<InFlightSubmission> = FuturesOrdered::new();
+
+        let reason = loop {
+            select!(
+                biased;
+
+                () = self.shutdown_token.cancelled() => break Ok(()),
+
+                Some((submitted, result)) = in_flight.next() => {
+                    if let Err(error) = self.finish_submission(submitted, result).await {
+                        break Err(error);
+                    }
+                }
+
+                blocks = self.batches.recv(),
+                    if in_flight.len() < self.max_concurrent_submissions =>
+                {
+                    let Some(blocks) = blocks else {
+                        break Ok(());
+                    };
+                    match self.start_submission(blocks).await {
+                        Ok(submission) => in_flight.push_back(submission),
+                        Err(error) => break Err(error),
+                    }
+                }
+            );
+        };
+        // Submissions after a failed one must not be finalized, or the post-submit file would
+        // skip over the sequencer blocks of the failed submission.
+        reason?;
+
+        // Submissions in flight are always completed, even if a shutdown signal is received
+        // in the meantime, so that the pre- and post-submit files stay consistent.
+        if !in_flight.is_empty() {
+            info!(
+                number_of_submissions = in_flight.len(),
+                "waiting for submissions to Celestia in flight to finish",
+            );
         }
-        Ok(state) => state,
-    };
+        while let Some((submitted, result)) = in_flight.next().await {
+            self.finish_submission(submitted, result).await?;
+        }
+        info!("no submissions to Celestia are in flight, exiting now");
+        Ok(())
+    }
 
-    let celestia_height = match submit_with_retry(client, blocks.blobs, state.clone()).await {
-        Err(error) => {
-            let message = "failed submitting blobs to Celestia";
-            error!(%error, message);
-            return Err(error.wrap_err(message));
+    /// Records the start of the submission of `blocks` and returns the submission as a future.
+    ///
+    /// # Panics
+    /// Panics if `blocks` is empty. This function should only be called if there is something to
+    /// submit.
+    #[instrument(skip_all)]
+    async fn start_submission(
+        &mut self,
+        mut blocks: QueuedConvertedBlocks,
+    ) -> eyre::Result<InFlightSubmission> {
+        info!(
+            blocks = %telemetry::display::json(&blocks.infos),
+            "initiated submission of sequencer blocks converted to Celestia blobs",
+        );
+
+        metrics::counter!(crate::metrics_init::CELESTIA_SUBMISSION_COUNT).increment(1);
+        // XXX: The number of sequencer blocks per celestia tx is equal to the number of heights
+        // passed into this function. This comes from the way that `QueuedBlocks::take` is
+        // implemented.
+        //
+        // allow: the number of blocks should always be low enough to not cause precision loss
+        #[allow(clippy::cast_precision_loss)]
+        let blocks_per_celestia_tx = blocks.num_converted() as f64;
+        metrics::gauge!(crate::metrics_init::BLOCKS_PER_CELESTIA_TX).set(blocks_per_celestia_tx);
+
+        // allow: the number of blobs should always be low enough to not cause precision loss
+        #[allow(clippy::cast_precision_loss)]
+        let blobs_per_celestia_tx = blocks.num_blobs() as f64;
+        metrics::gauge!(crate::metrics_init::BLOBS_PER_CELESTIA_TX).set(blobs_per_celestia_tx);
+
+        assert!(
+            !blocks.greatest_sequencer_heights.is_empty(),
+            "there should always be blobs and accompanying sequencer heights when this function \
+             is called",
+        );
+        let greatest_heights: Vec<_> = blocks
+            .greatest_sequencer_heights
+            .iter()
+            .map(|(chain_id, height)| (chain_id.clone(), *height))
+            .collect();
+
+        let rollup_blobs_in_full = match &self.posted_blobs {
+            Some(posted_blobs) => {
+                deduplicate_rollup_blobs(posted_blobs, &mut blocks.blobs, &blocks.infos)
+            }
+            None => vec![],
+        };
+
+        let idempotency_key = IdempotencyKey::from_blobs(&blocks.blobs);
+        info!(%idempotency_key, "derived idempotency key of submission");
+
+        for (chain_id, height) in &greatest_heights {
+            let height = *height;
+            if let Err(error) = self
+                .update_submission_state(chain_id, move |state| {
+                    state.start(height, idempotency_key)
+                })
+                .await
+            {
+                error!(%error, %chain_id, "failed to initialize submission; abandoning");
+                return Err(error);
+            }
         }
-        Ok(height) => height,
-    };
-    metrics::counter!(crate::metrics_init::CELESTIA_SUBMISSION_HEIGHT).absolute(celestia_height);
-    metrics::histogram!(crate::metrics_init::CELESTIA_SUBMISSION_LATENCY).record(start.elapsed());
-
-    info!(%celestia_height, "successfully submitted blobs to Celestia");
-
-    state.set_celestia_connected(true);
-    state.set_latest_confirmed_celestia_height(celestia_height);
-
-    let final_state = match crate::utils::flatten(
-        tokio::task::spawn_blocking(move || submission_started.finalize(celestia_height))
-            .in_current_span()
-            .await,
-    ) {
-        Err(error) => {
-            error!(%error, "failed to finalize submission; abandoning");
-            return Err(error);
+
+        let block_hashes: Vec<_> = blocks
+            .infos
+            .iter()
+            .map(|info| info.sequencer_block_hash)
+            .collect();
+        self.latencies.submitted(&block_hashes);
+        let escalation = DeadlineEscalation {
+            latencies: Arc::clone(&self.latencies),
+            block_hashes: block_hashes.clone(),
+        };
+        let submitted = SubmittedBlocks {
+            greatest_heights,
+            block_hashes,
+            posted_blocks: blocks
+                .infos
+                .iter()
+                .map(|info| {
+                    (
+                        info.sequencer_chain_id.clone(),
+                        info.sequencer_height,
+                        info.sequencer_block_hash,
+                    )
+                })
+                .collect(),
+            posted: posted_bytes(&blocks.blobs, &blocks.infos),
+            blobs: blocks
+                .blobs
+                .iter()
+                .map(|blob| AttestedBlob {
+                    namespace: HexNamespace(blob.namespace).to_string(),
+                    commitment: blob.commitment.0,
+                })
+                .collect(),
+            rollup_blobs_in_full,
+        };
+
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let alerts = self.alerts.clone();
+        let batching = self.batching.clone();
+        let submission = async move {
+            let start = std::time::Instant::now();
+            let result = submit_with_retry(
+                client,
+                blocks.blobs,
+                idempotency_key,
+                state,
+                alerts,
+                Some(escalation),
+            )
+            .await;
+            if let Ok(inclusion) = &result {
+                metrics::histogram!(crate::metrics_init::CELESTIA_SUBMISSION_LATENCY)
+                    .record(start.elapsed());
+                batching.observe(start.elapsed(), inclusion.gas_price);
+            }
+            (submitted, result)
         }
-        Ok(state) => state,
-    };
-    Ok(final_state)
+        .in_current_span()
+        .boxed();
+        Ok(submission)
+    }
+
+    /// Records the result of a submission started by [`SubmissionStage::start_submission`].
+    #[instrument(skip_all)]
+    async fn finish_submission(
+        &mut self,
+        submitted: SubmittedBlocks,
+        result: eyre::Result<Inclusion>,
+    ) -> eyre::Result<()> {
+        metrics::gauge!(crate::metrics_init::IN_FLIGHT_CELESTIA_SUBMISSIONS).decrement(1);
+
+        let Inclusion {
+            height: celestia_height,
+            fee,
+            ..
+        } = match result {
+            Err(error) => {
+                let message = "failed submitting blobs to Celestia";
+                error!(%error, message);
+                return Err(error.wrap_err(message));
+            }
+            Ok(inclusion) => inclusion,
+        };
+        metrics::counter!(crate::metrics_init::CELESTIA_SUBMISSION_HEIGHT)
+            .absolute(celestia_height);
+
+        info!(%celestia_height, "successfully submitted blobs to Celestia");
+        self.latencies
+            .confirmed(&submitted.block_hashes, celestia_height);
+
+        self.state.set_celestia_connected(true);
+        self.state
+            .set_latest_confirmed_celestia_height(celestia_height);
+
+        if let Some(posted_blobs) = &mut self.posted_blobs {
+            posted_blobs.record(celestia_height, &submitted.rollup_blobs_in_full);
+        }
+
+        if let Some(cost_ledger) = self.cost_ledger.clone() {
+            let record = CostRecord {
+                timestamp: crate::costs::unix_seconds(SystemTime::now()),
+                celestia_height,
+                fee_utia: fee,
+                namespaces: submitted.posted,
+            };
+            // Failing to record costs must not stop the relayer: the submission itself succeeded.
+            if let Err(error) = crate::utils::flatten(
+                tokio::task::spawn_blocking(move || cost_ledger.append(&record))
+                    .in_current_span()
+                    .await,
+            ) {
+                warn!(%error, %celestia_height, "failed recording costs of submission");
+            }
+        }
+
+        if let Some(height_index) = self.height_index.clone() {
+            let mappings: Vec<_> = submitted
+                .posted_blocks
+                .iter()
+                .map(|(chain_id, sequencer_height, _)| HeightMapping {
+                    sequencer_chain_id: chain_id.to_string(),
+                    sequencer_height: sequencer_height.value(),
+                    celestia_height,
+                })
+                .collect();
+            // Like the cost ledger, the index is a record of submissions and must not stop the
+            // relayer if it cannot be written.
+            if let Err(error) = crate::utils::flatten(
+                tokio::task::spawn_blocking(move || height_index.record(&mappings))
+                    .in_current_span()
+                    .await,
+            ) {
+                warn!(%error, %celestia_height, "failed recording heights of submission");
+            }
+        }
+
+        if let Some(attestations) = self.attestations.clone() {
+            let sequencer_blocks: Vec<_> = submitted
+                .posted_blocks
+                .iter()
+                .map(|(chain_id, sequencer_height, block_hash)| AttestedSequencerBlock {
+                    chain_id: chain_id.to_string(),
+                    height: sequencer_height.value(),
+                    block_hash: *block_hash,
+                })
+                .collect();
+            let blobs = submitted.blobs;
+            let timestamp = crate::costs::unix_seconds(SystemTime::now());
+            // An attestation is evidence of a submission that already succeeded; failing to
+            // record it must not stop the relayer either.
+            if let Err(error) = crate::utils::flatten(
+                tokio::task::spawn_blocking(move || {
+                    attestations
+                        .attest(celestia_height, timestamp, sequencer_blocks, blobs)
+                        .map(|_| ())
+                })
+                .in_current_span()
+                .await,
+            ) {
+                warn!(%error, %celestia_height, "failed recording attestation of submission");
+            }
+        }
+
+        for (chain_id, sequencer_height) in submitted.greatest_heights {
+            if self.submission_states.is_primary(&chain_id) {
+                self.state
+                    .set_latest_confirmed_sequencer_height(sequencer_height.value());
+                self.state.report_sequencer_height_lag();
+            }
+            if let Err(error) = self
+                .update_submission_state(&chain_id, move |state| {
+                    state.finish(sequencer_height, celestia_height)
+                })
+                .await
+            {
+                error!(
+                    %error,
+                    %chain_id,
+                    %sequencer_height,
+                    "failed to finalize submission; abandoning",
+                );
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies `update` to the submission state of `chain_id`, which writes the updated state to
+    /// storage.
+    async fn update_submission_state<F, Fut>(
+        &mut self,
+        chain_id: &chain::Id,
+        update: F,
+    ) -> eyre::Result<()>
+    where
+        F: FnOnce(SubmissionState) -> Fut,
+        Fut: Future<Output = eyre::Result<SubmissionState>>,
+    {
+        let submission_state = self.submission_states.get_mut(chain_id);
+        *submission_state = update(submission_state.clone()).await?;
+        Ok(())
+    }
 }
 
 #[instrument(skip_all)]
@@ -456,11 +1103,19 @@ async fn init_with_retry(client_builder: CelestiaClientBuilder) -> eyre::Result<
     Ok(celestia_client)
 }
 
+/// Submits `blobs` until they are included in a Celestia block or the retries are exhausted.
+///
+/// All work done for the submission runs in a span whose `correlation_id` is the idempotency key
+/// of the blobs, so that the requests made to Celestia can be attributed to the submission.
+#[instrument(skip_all, fields(correlation_id = %idempotency_key))]
 async fn submit_with_retry(
     client: CelestiaClient,
     blobs: Vec<Blob>,
+    idempotency_key: IdempotencyKey,
     state: Arc<super::State>,
-) -> eyre::Result<u64> {
+    alerts: Alerter,
+    escalation: Option<DeadlineEscalation>,
+) -> eyre::Result<Inclusion> {
     // Moving the span into `on_retry`, because tryhard spawns these in a tokio
     // task, losing the span.
     let span = Span::current();
@@ -469,18 +1124,64 @@ async fn submit_with_retry(
     // `TrySubmitError` to the next attempt of the `retry_fn`.
     let (last_error_sender, last_error_receiver) = watch::channel(None);
 
+    // Tracks how often the blobs were resubmitted because a previous attempt was not confirmed.
+    let max_resubmissions = client.max_resubmissions();
+    let resubmissions = Arc::new(AtomicU32::new(0));
+
+    let error_policy = client.error_policy().clone();
     let retry_config = tryhard::RetryFutureConfig::new(u32::MAX)
-        .exponential_backoff(Duration::from_millis(100))
+        .custom_backoff(SubmitRetryStrategy::new(
+            Duration::from_millis(100),
+            error_policy.clone(),
+        ))
         // 12 seconds is the Celestia block time
         .max_delay(Duration::from_secs(12))
         .on_retry(
             |attempt: u32, next_delay: Option<Duration>, error: &TrySubmitError| {
+                let _ = last_error_sender.send(Some(error.clone()));
+
+                if let TrySubmitError::SubmissionNotConfirmed {
+                    tx_hash, ..
+                } = error
+                {
+                    let resubmission = resubmissions
+                        .fetch_add(1, Ordering::Relaxed)
+                        .saturating_add(1);
+                    metrics::counter!(crate::metrics_init::CELESTIA_RESUBMISSION_COUNT)
+                        .increment(1);
+                    alerts.raise(Alert::ConfirmationMissed {
+                        tx_hash: tx_hash.clone(),
+                        resubmission,
+                        max_resubmissions,
+                    });
+                    warn!(
+                        parent: &span,
+                        attempt,
+                        resubmission,
+                        max_resubmissions,
+                        error = %eyre::Report::new(error.clone()),
+                        "blob submission was not confirmed on Celestia; resubmitting once the lost \
+                         transaction was evicted",
+                    );
+                    return futures::future::ready(());
+                }
+
                 metrics::counter!(crate::metrics_init::CELESTIA_SUBMISSION_FAILURE_COUNT)
                     .increment(1);
+                alerts.submission_failed();
+                let class = error_policy.classify(error);
+                if let Some(log) = error.insufficient_funds_log() {
+                    alerts.raise(Alert::LowBalance {
+                        log: log.to_string(),
+                    });
+                } else if class == ErrorClass::RequiresOperator {
+                    alerts.raise(Alert::OperatorInterventionRequired {
+                        error: format!("{:#}", eyre::Report::new(error.clone())),
+                    });
+                }
 
                 let state = Arc::clone(&state);
                 state.set_celestia_connected(false);
-                let _ = last_error_sender.send(Some(error.clone()));
 
                 let wait_duration = next_delay
                     .map(humantime::format_duration)
@@ -490,6 +1191,7 @@ async fn submit_with_retry(
                     parent: &span,
                     attempt,
                     wait_duration,
+                    %class,
                     error = %eyre::Report::new(error.clone()),
                     "failed submitting blobs to Celestia; retrying after backoff",
                 );
@@ -499,16 +1201,157 @@ async fn submit_with_retry(
 
     let blobs = Arc::new(blobs);
 
-    let height = tryhard::retry_fn(move || {
-        client
-            .clone()
-            .try_submit(blobs.clone(), last_error_receiver.clone())
+    let resubmissions_so_far = Arc::clone(&resubmissions);
+    let submitting_client = client.clone();
+    let result = tryhard::retry_fn(move || {
+        let may_resubmit = resubmissions_so_far.load(Ordering::Relaxed) < max_resubmissions;
+        // Checked on every attempt, since blocks pass their deadline while being submitted.
+        let fee_bump_percent = escalation
+            .as_ref()
+            .map_or(0, DeadlineEscalation::fee_bump_percent);
+        if fee_bump_percent > 0 {
+            metrics::counter!(crate::metrics_init::CELESTIA_DEADLINE_ESCALATION_COUNT)
+                .increment(1);
+        }
+        submitting_client.clone().try_submit(
+            blobs.clone(),
+            idempotency_key,
+            last_error_receiver.clone(),
+            may_resubmit,
+            fee_bump_percent,
+        )
     })
     .with_config(retry_config)
     .in_current_span()
-    .await
-    .wrap_err("retry attempts exhausted; bailing")?;
-    Ok(height)
+    .await;
+    client.forget_broadcasts(&idempotency_key);
+    let inclusion = match result {
+        Ok(inclusion) => inclusion,
+        Err(error) if error_policy.classify(&error) == ErrorClass::Fatal => {
+            return Err(error).wrap_err("Celestia returned an error classified as fatal; bailing");
+        }
+        Err(error) => return Err(error).wrap_err("retry attempts exhausted; bailing"),
+    };
+    alerts.submission_succeeded();
+    Ok(inclusion)
+}
+
+/// Raises the fee of a submission while it contains sequencer blocks past their deadline.
+struct DeadlineEscalation {
+    latencies: Arc<LatencyTracker>,
+    /// The hashes of all sequencer blocks contained in the submission.
+    block_hashes: Vec<[u8; 32]>,
+}
+
+impl DeadlineEscalation {
+    fn fee_bump_percent(&self) -> u32 {
+        self.latencies.fee_bump_percent(&self.block_hashes)
+    }
+}
+
+/// Backs off exponentially between attempts to submit blobs, and stops retrying once an attempt
+/// failed with an error classified as fatal.
+struct SubmitRetryStrategy {
+    delay: Duration,
+    error_policy: ErrorPolicy,
+}
+
+impl SubmitRetryStrategy {
+    fn new(initial_duration: Duration, error_policy: ErrorPolicy) -> Self {
+        Self {
+            delay: initial_duration,
+            error_policy,
+        }
+    }
+}
+
+impl<'a> BackoffStrategy<'a, TrySubmitError> for SubmitRetryStrategy {
+    type Output = RetryPolicy;
+
+    fn delay(&mut self, _attempt: u32, error: &'a TrySubmitError) -> Self::Output {
+        if self.error_policy.classify(error) == ErrorClass::Fatal {
+            RetryPolicy::Break
+        } else {
+            let prev_delay = self.delay;
+            self.delay = self.delay.saturating_mul(2);
+            RetryPolicy::Delay(prev_delay)
+        }
+    }
+}
+
+/// Returns the bytes of blob data in `blobs` per namespace, each with the owner of the namespace
+/// as recorded in the conversion `infos` of the blocks the blobs were converted from.
+/// Replaces the blobs in `blobs` written under the namespace of an included rollup by references
+/// to their first inclusion, if `posted_blobs` remembers one.
+///
+/// Returns the rollup blobs that are still posted in full.
+fn deduplicate_rollup_blobs(
+    posted_blobs: &PostedBlobs,
+    blobs: &mut [Blob],
+    infos: &[ConversionInfo],
+) -> Vec<Blob> {
+    let rollup_namespaces: HashSet<_> = infos
+        .iter()
+        .flat_map(ConversionInfo::rollup_namespaces)
+        .collect();
+    let mut in_full = vec![];
+    let mut number_of_references: usize = 0;
+    for blob in blobs
+        .iter_mut()
+        .filter(|blob| rollup_namespaces.contains(&blob.namespace))
+    {
+        let Some(reference) = posted_blobs.find(blob) else {
+            in_full.push(blob.clone());
+            continue;
+        };
+        match Blob::new(blob.namespace, reference.encode()) {
+            Ok(reference) => {
+                *blob = reference;
+                number_of_references = number_of_references.saturating_add(1);
+            }
+            Err(error) => {
+                warn!(
+                    %error,
+                    "failed constructing a reference to a rollup blob included before; posting it \
+                     in full",
+                );
+                in_full.push(blob.clone());
+            }
+        }
+    }
+    if number_of_references > 0 {
+        info!(
+            number_of_references,
+            "replaced rollup blobs included before by references to them"
+        );
+    }
+    in_full
+}
+
+fn posted_bytes(blobs: &[Blob], infos: &[ConversionInfo]) -> Vec<NamespaceBytes> {
+    let owners: HashMap<_, _> = infos
+        .iter()
+        .flat_map(ConversionInfo::namespace_owners)
+        .map(|(namespace, owner)| (HexNamespace(namespace).to_string(), owner))
+        .collect();
+    let mut bytes = BTreeMap::<String, u64>::new();
+    for blob in blobs {
+        let posted = bytes
+            .entry(HexNamespace(blob.namespace).to_string())
+            .or_default();
+        *posted = posted.saturating_add(blob.data.len() as u64);
+    }
+    bytes
+        .into_iter()
+        .map(|(namespace, bytes)| NamespaceBytes {
+            owner: owners
+                .get(&namespace)
+                .cloned()
+                .unwrap_or_else(|| namespace.clone()),
+            namespace,
+            bytes,
+        })
+        .collect()
 }
 
 /// Currently running conversions of Sequencer blocks to Celestia blobs.
@@ -542,9 +1385,13 @@ impl Conversions {
         self.active.len() < self.max_conversions
     }
 
-    fn push(&mut self, block: SequencerBlock, rollup_filter: IncludeRollup) {
+    fn num_active(&self) -> usize {
+        self.active.len()
+    }
+
+    fn push(&mut self, block: SequencerBlock, settings: ConversionSettings) {
         let height = block.height();
-        let conversion = tokio::task::spawn_blocking(move || convert(block, rollup_filter));
+        let conversion = tokio::task::spawn_blocking(move || convert(block, &settings));
         let fut = async move {
             let res = crate::utils::flatten(conversion.await);
             (height, res)
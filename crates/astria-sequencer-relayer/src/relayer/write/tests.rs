@@ -0,0 +1,500 @@
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use astria_celestia_client::dedup::{
+    BlobReference,
+    PostedBlobs,
+};
+use astria_core::{
+    celestia::namespace_v0_from_first_10_bytes,
+    protocol::test_utils::ConfigureSequencerBlock,
+    sequencerblock::v1alpha1::BlobFormat,
+};
+use celestia_types::Blob;
+use sequencer_client::tendermint::{
+    block::Height as SequencerHeight,
+    chain,
+};
+use tokio::time::Instant;
+use tonic::Status;
+
+use super::{
+    conversion::RollupInfo,
+    deduplicate_rollup_blobs,
+    submit_with_retry,
+    ConversionInfo,
+    Converted,
+    QueuedConvertedBlocks,
+};
+use crate::{
+    alerts::{
+        self,
+        Alerter,
+    },
+    clock,
+    config::EmptyBlockPolicy,
+    relayer::{
+        celestia_client::{
+            mock::{
+                MockCelestiaRpc,
+                INCLUSION_HEIGHT,
+                MEMPOOL_TTL_BLOCKS,
+            },
+            ErrorPolicy,
+        },
+        IdempotencyKey,
+        State,
+        TrySubmitError,
+    },
+    secret::SecretString,
+};
+
+const RESUBMIT_AFTER_BLOCKS: u64 = 2;
+
+const BLOCK_TIME: Duration = Duration::from_secs(2);
+
+fn alerter() -> Alerter {
+    alerts::Builder {
+        notifier: String::new(),
+        webhook_url: SecretString::default(),
+        pagerduty_routing_key: SecretString::default(),
+        submission_failure_threshold: 1,
+        min_interval: Duration::from_secs(300),
+        clock: clock::system(),
+    }
+    .build()
+    .unwrap()
+}
+
+fn blob(namespace: u8, data: u8) -> Blob {
+    Blob::new(namespace_v0_from_first_10_bytes(&[namespace; 10]), vec![data; 100]).unwrap()
+}
+
+fn data(blobs: &[Blob]) -> Vec<Vec<u8>> {
+    blobs.iter().map(|blob| blob.data.clone()).collect()
+}
+
+fn chain_id(chain_id: &str) -> chain::Id {
+    chain_id.parse().unwrap()
+}
+
+fn converted(chain_id: &str, height: u32, namespace: u8) -> Converted {
+    let blob = blob(namespace, u8::try_from(height).unwrap());
+    Converted {
+        info: ConversionInfo {
+            sequencer_height: SequencerHeight::from(height),
+            sequencer_block_hash: [0; 32],
+            sequencer_chain_id: self::chain_id(chain_id),
+            sequencer_namespace: blob.namespace,
+            rollups_included: vec![],
+            rollups_excluded: vec![],
+            rollups_over_quota: vec![],
+            auxiliary_blobs: vec![],
+            rollup_results: vec![],
+        },
+        blobs: vec![blob],
+        empty_sequencer_blob: None,
+    }
+}
+
+/// Returns a block without rollup data, as converted under a policy other than `post`.
+fn empty_converted(chain_id: &str, height: u32, namespace: u8) -> Converted {
+    let (sequencer_blob, _) = ConfigureSequencerBlock {
+        chain_id: Some(chain_id.to_string()),
+        height,
+        ..ConfigureSequencerBlock::default()
+    }
+    .make()
+    .into_celestia_blobs();
+    Converted {
+        empty_sequencer_blob: Some(sequencer_blob),
+        ..converted(chain_id, height, namespace)
+    }
+}
+
+async fn submit(
+    rpc: &Arc<MockCelestiaRpc>,
+    max_resubmissions: u32,
+    error_policy: ErrorPolicy,
+    blobs: Vec<Blob>,
+) -> astria_eyre::eyre::Result<u64> {
+    let client = rpc.client(RESUBMIT_AFTER_BLOCKS, max_resubmissions, error_policy);
+    let idempotency_key = IdempotencyKey::from_blobs(&blobs);
+    submit_with_retry(
+        client,
+        blobs,
+        idempotency_key,
+        Arc::new(State::new()),
+        alerter(),
+        None,
+    )
+    .await
+    .map(|inclusion| inclusion.height)
+}
+
+#[tokio::test(start_paused = true)]
+async fn submission_is_confirmed() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    let height = submit(&rpc, 0, ErrorPolicy::default(), vec![blob(1, 1), blob(2, 2)])
+        .await
+        .unwrap();
+    assert_eq!(INCLUSION_HEIGHT, height);
+
+    let broadcasts = rpc.broadcasts();
+    assert_eq!(1, broadcasts.len());
+    assert_eq!(0, broadcasts[0].sequence);
+    assert_eq!(2, broadcasts[0].blob_count);
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_broadcast_is_retried_with_the_same_sequence() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    rpc.fail_next_broadcast(TrySubmitError::FailedToBroadcastTx(
+        Status::unavailable("down").into(),
+    ));
+    rpc.fail_next_broadcast(TrySubmitError::FailedToBroadcastTx(
+        Status::unavailable("still down").into(),
+    ));
+    submit(&rpc, 0, ErrorPolicy::default(), vec![blob(1, 1)])
+        .await
+        .unwrap();
+    // The sequences reserved by the failed attempts are released again.
+    let broadcasts = rpc.broadcasts();
+    assert_eq!(1, broadcasts.len());
+    assert_eq!(0, broadcasts[0].sequence);
+
+    // The next submission follows the now committed transaction.
+    submit(&rpc, 0, ErrorPolicy::default(), vec![blob(1, 2)])
+        .await
+        .unwrap();
+    assert_eq!(1, rpc.broadcasts()[1].sequence);
+}
+
+#[tokio::test(start_paused = true)]
+async fn error_classified_as_fatal_is_not_retried() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    rpc.fail_next_broadcast(TrySubmitError::FailedToBroadcastTx(
+        Status::unavailable("down").into(),
+    ));
+    let error_policy = ErrorPolicy::parse("failed_to_broadcast_tx=fatal").unwrap();
+    let error = submit(&rpc, 0, error_policy, vec![blob(1, 1)])
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{error:#}").contains("classified as fatal"),
+        "unexpected error: {error:#}"
+    );
+    assert!(rpc.broadcasts().is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn unconfirmed_submission_is_resubmitted_once_evicted() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    rpc.produce_blocks_every(BLOCK_TIME);
+    rpc.include_next_broadcast_after(None);
+    let start = Instant::now();
+    let height = submit(&rpc, 1, ErrorPolicy::default(), vec![blob(1, 1)])
+        .await
+        .unwrap();
+    assert_eq!(INCLUSION_HEIGHT, height);
+    // The lost transaction might have been in the mempool until it was evicted, so it was not
+    // resubmitted before.
+    let eviction = BLOCK_TIME * u32::try_from(MEMPOOL_TTL_BLOCKS).unwrap();
+    assert!(
+        start.elapsed() >= eviction,
+        "resubmitted before the lost transaction was evicted; took {:?}",
+        start.elapsed(),
+    );
+
+    let broadcasts = rpc.broadcasts();
+    assert_eq!(2, broadcasts.len());
+    // The resubmission replaces the evicted transaction, so it reuses its sequence.
+    assert_eq!(0, broadcasts[1].sequence);
+    // Without a deadline escalating it, the fee is not raised.
+    assert_eq!(broadcasts[0].fee, broadcasts[1].fee);
+}
+
+#[tokio::test(start_paused = true)]
+async fn submission_landing_before_its_eviction_is_not_submitted_again() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    rpc.produce_blocks_every(BLOCK_TIME);
+    // The transaction lands after confirming it was given up on, but before it would have been
+    // evicted from the mempool.
+    let timeout = BLOCK_TIME * u32::try_from(RESUBMIT_AFTER_BLOCKS).unwrap();
+    rpc.include_next_broadcast_after(Some(timeout + BLOCK_TIME));
+    let height = submit(&rpc, 1, ErrorPolicy::default(), vec![blob(1, 1)])
+        .await
+        .unwrap();
+    assert_eq!(INCLUSION_HEIGHT, height);
+    assert_eq!(1, rpc.broadcasts().len());
+}
+
+#[tokio::test(start_paused = true)]
+async fn submission_landing_after_its_timeout_is_not_submitted_again() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    rpc.produce_blocks_every(BLOCK_TIME);
+    // Confirmation is given up on the first poll after `RESUBMIT_AFTER_BLOCKS` blocks were
+    // produced. The transaction lands just after that, before the submission is retried.
+    let timeout = BLOCK_TIME * u32::try_from(RESUBMIT_AFTER_BLOCKS).unwrap();
+    rpc.include_next_broadcast_after(Some(timeout + Duration::from_millis(50)));
+    let height = submit(&rpc, 1, ErrorPolicy::default(), vec![blob(1, 1)])
+        .await
+        .unwrap();
+    assert_eq!(INCLUSION_HEIGHT, height);
+    assert_eq!(1, rpc.broadcasts().len());
+}
+
+#[tokio::test(start_paused = true)]
+async fn submission_is_not_resubmitted_while_celestia_produces_no_blocks() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    // Far longer than it takes to produce `RESUBMIT_AFTER_BLOCKS` blocks at the usual block time,
+    // but no block is produced in the meantime.
+    rpc.produce_blocks_every(Duration::from_secs(600));
+    rpc.include_next_broadcast_after(Some(Duration::from_secs(300)));
+    let height = submit(&rpc, 1, ErrorPolicy::default(), vec![blob(1, 1)])
+        .await
+        .unwrap();
+    assert_eq!(INCLUSION_HEIGHT, height);
+    assert_eq!(1, rpc.broadcasts().len());
+}
+
+#[tokio::test(start_paused = true)]
+async fn concurrent_submissions_overlap_and_use_consecutive_sequences() {
+    const INCLUSION_DELAY: Duration = Duration::from_secs(3);
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    for _ in 0..3 {
+        rpc.include_next_broadcast_after(Some(INCLUSION_DELAY));
+    }
+    let client = rpc.client(RESUBMIT_AFTER_BLOCKS, 0, ErrorPolicy::default());
+    let submit = |blobs: Vec<Blob>| {
+        let idempotency_key = IdempotencyKey::from_blobs(&blobs);
+        submit_with_retry(
+            client.clone(),
+            blobs,
+            idempotency_key,
+            Arc::new(State::new()),
+            alerter(),
+            None,
+        )
+    };
+
+    let start = Instant::now();
+    let (first, second, third) = tokio::join!(
+        submit(vec![blob(1, 1)]),
+        submit(vec![blob(1, 2)]),
+        submit(vec![blob(1, 3)]),
+    );
+    for result in [first, second, third] {
+        assert_eq!(INCLUSION_HEIGHT, result.unwrap().height);
+    }
+    // Submitted one after the other, each submission would wait for the inclusion of the one
+    // before it.
+    assert!(
+        start.elapsed() < INCLUSION_DELAY * 2,
+        "submissions did not overlap; took {:?}",
+        start.elapsed(),
+    );
+
+    // None of the transactions was committed when the others were broadcast, so each follows
+    // the sequence of the one broadcast before it.
+    let sequences: Vec<_> = rpc
+        .broadcasts()
+        .iter()
+        .map(|broadcast| broadcast.sequence)
+        .collect();
+    assert_eq!(vec![0, 1, 2], sequences);
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_broadcast_does_not_reuse_sequence_of_concurrent_submission() {
+    let rpc = Arc::new(MockCelestiaRpc::default());
+    rpc.include_next_broadcast_after(Some(Duration::from_secs(3)));
+    let client = rpc.client(RESUBMIT_AFTER_BLOCKS, 0, ErrorPolicy::default());
+
+    let blobs = vec![blob(1, 1)];
+    let in_flight = submit_with_retry(
+        client.clone(),
+        blobs.clone(),
+        IdempotencyKey::from_blobs(&blobs),
+        Arc::new(State::new()),
+        alerter(),
+        None,
+    );
+    let blobs = vec![blob(1, 2)];
+    let failing = async {
+        // Start broadcasting once the first submission holds its sequence.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        rpc.fail_next_broadcast(TrySubmitError::FailedToBroadcastTx(
+            Status::unavailable("down").into(),
+        ));
+        submit_with_retry(
+            client.clone(),
+            blobs.clone(),
+            IdempotencyKey::from_blobs(&blobs),
+            Arc::new(State::new()),
+            alerter(),
+            None,
+        )
+        .await
+    };
+    let (in_flight, failing) = tokio::join!(in_flight, failing);
+    in_flight.unwrap();
+    failing.unwrap();
+
+    let sequences: Vec<_> = rpc
+        .broadcasts()
+        .iter()
+        .map(|broadcast| broadcast.sequence)
+        .collect();
+    assert_eq!(vec![0, 1], sequences);
+}
+
+#[test]
+fn queue_has_capacity_until_max_blobs_are_queued() {
+    let mut queue = QueuedConvertedBlocks::with_max_blobs(3);
+    assert!(queue.is_empty());
+    queue.push(converted("chain-a", 1, 2));
+    queue.push_blob(blob(3, 0));
+    assert!(queue.has_capacity());
+    queue.push(converted("chain-a", 2, 1));
+    assert!(!queue.has_capacity());
+    assert_eq!(3, queue.num_blobs());
+    assert_eq!(2, queue.num_converted());
+}
+
+#[tokio::test(start_paused = true)]
+async fn queued_blobs_are_due_once_their_window_passed_or_the_queue_is_full() {
+    let window = Duration::from_secs(2);
+    let mut queue = QueuedConvertedBlocks::with_max_blobs(3);
+    assert_eq!(None, queue.due_at(window));
+
+    let opened_at = Instant::now();
+    queue.push(converted("chain-a", 1, 1));
+    tokio::time::advance(Duration::from_secs(1)).await;
+    // The window starts with the first queued blob, not the latest.
+    queue.push_blob(blob(3, 0));
+    assert_eq!(Some(opened_at + window), queue.due_at(window));
+
+    queue.set_max_blobs(2);
+    assert_eq!(Some(opened_at), queue.due_at(window));
+
+    queue.take();
+    assert_eq!(None, queue.due_at(window));
+}
+
+#[test]
+fn taken_batch_is_sorted_and_tracks_greatest_heights_per_chain() {
+    let mut queue = QueuedConvertedBlocks::with_max_blobs(10);
+    queue.push(converted("chain-a", 3, 3));
+    queue.push(converted("chain-b", 2, 1));
+    queue.push(converted("chain-a", 1, 2));
+
+    let batch = queue.take();
+    assert!(queue.is_empty());
+    assert_eq!(10, queue.max_blobs);
+
+    let namespaces: Vec<_> = batch.blobs.iter().map(|blob| blob.namespace).collect();
+    let expected: Vec<_> = [1, 2, 3]
+        .map(|namespace| namespace_v0_from_first_10_bytes(&[namespace; 10]))
+        .to_vec();
+    assert_eq!(expected, namespaces);
+    assert_eq!(
+        SequencerHeight::from(3_u32),
+        batch.greatest_sequencer_heights[&chain_id("chain-a")]
+    );
+    assert_eq!(
+        SequencerHeight::from(2_u32),
+        batch.greatest_sequencer_heights[&chain_id("chain-b")]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn batched_empty_blocks_wait_for_a_block_with_rollup_data() {
+    let window = Duration::from_secs(2);
+    let mut queue = QueuedConvertedBlocks::with_max_blobs(3)
+        .with_empty_block_policy(EmptyBlockPolicy::Batch, BlobFormat::Protobuf);
+    queue.push(empty_converted("chain-a", 1, 1));
+    queue.push(empty_converted("chain-a", 2, 1));
+    assert_eq!(None, queue.due_at(window));
+    assert!(queue.has_capacity());
+
+    queue.push(converted("chain-a", 3, 1));
+    assert!(queue.due_at(window).is_some());
+
+    let batch = queue.take();
+    assert_eq!(3, batch.num_blobs());
+    assert_eq!(3, batch.num_converted());
+}
+
+#[tokio::test(start_paused = true)]
+async fn held_empty_blocks_are_due_once_max_blobs_are_held() {
+    let window = Duration::from_secs(2);
+    let mut queue = QueuedConvertedBlocks::with_max_blobs(2)
+        .with_empty_block_policy(EmptyBlockPolicy::Marker, BlobFormat::Protobuf);
+    let opened_at = Instant::now();
+    queue.push(empty_converted("chain-a", 1, 1));
+    assert!(queue.has_capacity());
+    queue.push(empty_converted("chain-a", 2, 1));
+    assert!(!queue.has_capacity());
+    assert_eq!(Some(opened_at), queue.due_at(window));
+}
+
+#[test]
+fn empty_blocks_are_merged_into_one_marker_per_namespace() {
+    let mut queue = QueuedConvertedBlocks::with_max_blobs(10)
+        .with_empty_block_policy(EmptyBlockPolicy::Marker, BlobFormat::Protobuf);
+    queue.push(empty_converted("chain-a", 1, 1));
+    queue.push(empty_converted("chain-a", 2, 1));
+    queue.push(empty_converted("chain-b", 1, 2));
+    queue.push(converted("chain-a", 3, 1));
+
+    let batch = queue.take();
+    assert_eq!(4, batch.num_converted());
+    // The marker of chain-a's two empty blocks and its block with rollup data, and chain-b's
+    // single empty block written as its regular sequencer blob.
+    assert_eq!(3, batch.num_blobs());
+    let data = data(&batch.blobs);
+    assert!(data.contains(&blob(2, 1).data));
+    assert!(data.contains(&blob(1, 3).data));
+    assert!(!data.contains(&blob(1, 1).data));
+    assert!(!data.contains(&blob(1, 2).data));
+}
+
+#[test]
+fn rollup_blobs_included_before_are_replaced_by_references() {
+    let included_before = blob(2, 7);
+    let mut posted_blobs = PostedBlobs::new(10);
+    posted_blobs.record(INCLUSION_HEIGHT, &[included_before.clone()]);
+
+    let mut converted = converted("chain-a", 1, 1);
+    converted
+        .info
+        .rollups_included
+        .push(RollupInfo::included_under(included_before.namespace));
+    let not_included_before = blob(2, 8);
+    // the same data under a namespace that is not the one of an included rollup
+    let other_namespace = blob(3, 7);
+    let mut blobs = vec![
+        converted.blobs[0].clone(),
+        included_before.clone(),
+        not_included_before.clone(),
+        other_namespace.clone(),
+    ];
+
+    let in_full = deduplicate_rollup_blobs(&posted_blobs, &mut blobs, &[converted.info]);
+
+    let reference = BlobReference::decode(&blobs[1].data).unwrap();
+    assert_eq!(INCLUSION_HEIGHT, reference.celestia_height);
+    assert_eq!(included_before.commitment, reference.commitment);
+    assert_eq!(
+        data(&[
+            converted.blobs[0].clone(),
+            not_included_before.clone(),
+            other_namespace
+        ]),
+        data(&[blobs[0].clone(), blobs[2].clone(), blobs[3].clone()]),
+    );
+    assert_eq!(data(&[not_included_before]), data(&in_full));
+}
@@ -0,0 +1,111 @@
+//! A string holding a secret that must never end up in logs.
+//!
+//! [`SecretString`] formats and serializes as [`REDACTED`], so that a config or builder holding
+//! credentials can derive `Debug` and `Serialize` without leaking them. An empty secret is shown
+//! as empty, so that dumps still tell whether it is set. The string is zeroed when dropped.
+
+use std::{
+    fmt,
+    io,
+    path::Path,
+};
+
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use zeroize::Zeroize as _;
+
+/// What a non-empty secret is shown as.
+pub const REDACTED: &str = "<redacted>";
+
+/// A string whose contents are only accessible through [`SecretString::expose_secret`].
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Reads the entire file at `path` into a secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or is not valid UTF-8.
+    pub fn read_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        std::fs::read_to_string(path).map(Self)
+    }
+
+    /// Returns the secret. Must not be logged.
+    #[must_use]
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns if the secret is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn shown(&self) -> &'static str {
+        if self.is_empty() {
+            ""
+        } else {
+            REDACTED
+        }
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.shown(), f)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.shown())
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.shown())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_is_redacted_in_debug_display_and_serialization() {
+        let secret = SecretString::from("hunter2".to_string());
+        assert_eq!("\"<redacted>\"", format!("{secret:?}"));
+        assert_eq!(REDACTED, secret.to_string());
+        assert_eq!("\"<redacted>\"", serde_json::to_string(&secret).unwrap());
+        assert_eq!("hunter2", secret.expose_secret());
+
+        let empty = SecretString::default();
+        assert_eq!("\"\"", format!("{empty:?}"));
+        assert_eq!("\"\"", serde_json::to_string(&empty).unwrap());
+    }
+}
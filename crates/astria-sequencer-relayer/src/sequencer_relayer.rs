@@ -1,5 +1,6 @@
 use std::{
     net::SocketAddr,
+    sync::Arc,
     time::Duration,
 };
 
@@ -7,15 +8,6 @@ use astria_eyre::eyre::{
     self,
     WrapErr as _,
 };
-use tokio::{
-    select,
-    sync::oneshot,
-    task::{
-        JoinError,
-        JoinHandle,
-    },
-    time::timeout,
-};
 use tokio_util::sync::CancellationToken;
 use tracing::{
     error,
@@ -23,12 +15,15 @@ use tracing::{
 };
 
 use crate::{
+    alerts,
     api,
+    clock,
     config::Config,
     relayer::{
         self,
         Relayer,
     },
+    supervisor::Supervisor,
 };
 
 pub struct SequencerRelayer {
@@ -40,12 +35,32 @@ pub struct SequencerRelayer {
 impl SequencerRelayer {
     /// Instantiates a new `SequencerRelayer`.
     ///
+    /// If `telemetry` is set, its filter directives and trace sampling can be adjusted through
+    /// the `/admin` endpoints of the API.
+    ///
     /// # Errors
     ///
     /// Returns an error if constructing the inner relayer type failed.
-    pub fn new(cfg: Config) -> eyre::Result<(Self, ShutdownHandle)> {
+    pub fn new(
+        cfg: Config,
+        telemetry: Option<telemetry::Handle>,
+    ) -> eyre::Result<(Self, ShutdownHandle)> {
         let shutdown_handle = ShutdownHandle::new();
         let rollup_filter = cfg.only_include_rollups()?;
+        let additional_networks = cfg.additional_sequencer_networks()?;
+        let sequencer_namespaces = cfg.sequencer_namespaces(&additional_networks)?;
+        let known_signers = cfg.known_signers()?;
+        let rollup_signing_key_files = cfg.rollup_signing_key_files()?;
+        let rollup_quotas = cfg.rollup_quotas()?;
+        let celestia_error_policy = cfg.celestia_error_policy()?;
+        let batching_bounds = cfg.batching_bounds()?;
+        let heartbeat_namespace = cfg.heartbeat_namespace()?;
+        let cost_ledger = cfg.cost_ledger();
+        let height_index = cfg.height_index()?.map(Arc::new);
+        let attestations = cfg.attestation_store()?.map(Arc::new);
+        let storage = cfg.storage()?;
+        let leader_election = cfg.leader_election()?;
+        let sanitized_config = Arc::new(cfg.sanitized());
         let Config {
             cometbft_endpoint,
             sequencer_grpc_endpoint,
@@ -53,25 +68,97 @@ impl SequencerRelayer {
             celestia_app_key_file,
             block_time,
             relay_only_validator_key_blocks,
+            pull_blocks_from_cometbft,
             validator_key_file,
             api_addr,
             pre_submit_path,
             post_submit_path,
+            celestia_resubmit_after_blocks,
+            celestia_max_resubmissions,
+            celestia_mempool_ttl_blocks,
+            celestia_confirmation_depth,
+            celestia_max_stale_height_secs,
+            submission_deadline_blocks,
+            submission_deadline_fee_bump_percent,
+            max_concurrent_submissions,
+            celestia_blob_format,
+            empty_block_policy,
+            rollup_blob_dedup_capacity,
+            heartbeat_interval_celestia_blocks,
+            heartbeat_key_file,
+            alert_notifier,
+            alert_webhook_url,
+            alert_pagerduty_routing_key,
+            alert_submission_failure_threshold,
+            alert_min_interval_secs,
+            sequencer_height_lag_warn_threshold,
+            sequencer_height_lag_error_threshold,
             ..
         } = cfg;
 
+        let clock = clock::system();
+        let alerts = alerts::Builder {
+            notifier: alert_notifier,
+            webhook_url: alert_webhook_url,
+            pagerduty_routing_key: alert_pagerduty_routing_key,
+            submission_failure_threshold: alert_submission_failure_threshold,
+            min_interval: Duration::from_secs(alert_min_interval_secs),
+            clock: clock.clone(),
+        }
+        .build()
+        .wrap_err("failed to configure alerts")?;
+
         let validator_key_path = relay_only_validator_key_blocks.then_some(validator_key_file);
+        let sequencer_poll_period = Duration::from_millis(block_time);
+        let submission_deadline =
+            (submission_deadline_blocks > 0).then(|| relayer::SubmissionDeadline {
+                deadline: sequencer_poll_period.saturating_mul(submission_deadline_blocks),
+                fee_bump_percent: submission_deadline_fee_bump_percent,
+            });
         let relayer = relayer::Builder {
             shutdown_token: shutdown_handle.token(),
             celestia_app_grpc_endpoint,
             celestia_app_key_file,
             cometbft_endpoint,
-            sequencer_poll_period: Duration::from_millis(block_time),
+            sequencer_poll_period,
             sequencer_grpc_endpoint,
+            pull_blocks_from_cometbft,
             validator_key_path,
             rollup_filter,
+            sequencer_namespaces,
             pre_submit_path,
             post_submit_path,
+            celestia_resubmit_after_blocks,
+            celestia_max_resubmissions,
+            celestia_mempool_ttl_blocks,
+            celestia_confirmation_depth,
+            celestia_max_stale_height: (celestia_max_stale_height_secs > 0)
+                .then(|| Duration::from_secs(celestia_max_stale_height_secs)),
+            submission_deadline,
+            celestia_error_policy,
+            batching_bounds,
+            max_concurrent_submissions,
+            blob_format: celestia_blob_format,
+            empty_block_policy,
+            rollup_blob_dedup_capacity,
+            heartbeat_namespace,
+            heartbeat_interval_celestia_blocks,
+            heartbeat_key_path: heartbeat_key_file,
+            rollup_signing_key_files,
+            rollup_quotas,
+            additional_networks,
+            alerts,
+            cost_ledger,
+            height_index,
+            attestations,
+            lag_thresholds: relayer::LagThresholds {
+                warn: sequencer_height_lag_warn_threshold,
+                error: sequencer_height_lag_error_threshold,
+            },
+            known_signers,
+            clock,
+            storage,
+            leader_election,
         }
         .build()
         .wrap_err("failed to create relayer")?;
@@ -80,7 +167,17 @@ impl SequencerRelayer {
         let api_socket_addr = api_addr.parse::<SocketAddr>().wrap_err_with(|| {
             format!("failed to parse provided `api_addr` string as socket address: `{api_addr}`",)
         })?;
-        let api_server = api::start(api_socket_addr, state_rx);
+        let api_server = api::start(
+            api_socket_addr,
+            state_rx,
+            relayer.latencies(),
+            relayer.auxiliary_blobs(),
+            relayer.cost_ledger(),
+            relayer.height_index(),
+            relayer.attestations(),
+            telemetry,
+            sanitized_config,
+        );
         let relayer = Self {
             api_server,
             relayer,
@@ -100,34 +197,31 @@ impl SequencerRelayer {
             relayer,
             shutdown_token,
         } = self;
-        // Separate the API shutdown signal from the cancellation token because we want it to live
-        // until the very end.
-        let (api_shutdown_signal, api_shutdown_signal_rx) = oneshot::channel::<()>();
-        let mut api_task = tokio::spawn(async move {
-            api_server
-                .with_graceful_shutdown(async move {
-                    let _ = api_shutdown_signal_rx.await;
-                })
-                .await
-                .wrap_err("api server ended unexpectedly")
-        });
-        info!("spawned API server");
-
-        let mut relayer_task = tokio::spawn(relayer.run());
-        info!("spawned relayer task");
-
-        let shutdown = select!(
-            o = &mut api_task => {
-                report_exit("api server", o);
-                ShutDown { api_task: None, relayer_task: Some(relayer_task), api_shutdown_signal, shutdown_token }
-            }
-            o = &mut relayer_task => {
-                report_exit("relayer worker", o);
-                ShutDown { api_task: Some(api_task), relayer_task: None, api_shutdown_signal, shutdown_token }
-            }
+        let mut supervisor = Supervisor::new();
 
+        // Give the API server its own shutdown token because we want it to live until the very
+        // end. Being spawned first, it is shut down only after the relayer.
+        //
+        // Giving the API task 4 seconds and the relayer 25 seconds to shut down: 25s + 4s = 29s
+        // (out of 30s before Kubernetes issues a SIGKILL).
+        let api_shutdown_token = CancellationToken::new();
+        supervisor.spawn(
+            "api server",
+            api_shutdown_token.clone(),
+            Duration::from_secs(4),
+            async move {
+                api_server
+                    .with_graceful_shutdown(async move { api_shutdown_token.cancelled().await })
+                    .await
+                    .wrap_err("api server ended unexpectedly")
+            },
         );
-        shutdown.run().await;
+        supervisor.spawn("relayer", shutdown_token.clone(), Duration::from_secs(25), relayer.run());
+
+        let (task_name, outcome) = supervisor.exited().await;
+        report_exit(task_name, outcome);
+        shutdown_token.cancel();
+        supervisor.shutdown().await;
     }
 }
 
@@ -169,81 +263,9 @@ impl Drop for ShutdownHandle {
     }
 }
 
-fn report_exit(task_name: &str, outcome: Result<eyre::Result<()>, JoinError>) {
+fn report_exit(task_name: &str, outcome: eyre::Result<()>) {
     match outcome {
-        Ok(Ok(())) => info!(task = task_name, "task has exited"),
-        Ok(Err(error)) => {
-            error!(task = task_name, %error, "task returned with error");
-        }
-        Err(e) => {
-            error!(
-                task = task_name,
-                error = &e as &dyn std::error::Error,
-                "task failed to complete"
-            );
-        }
-    }
-}
-
-struct ShutDown {
-    api_task: Option<JoinHandle<eyre::Result<()>>>,
-    relayer_task: Option<JoinHandle<eyre::Result<()>>>,
-    api_shutdown_signal: oneshot::Sender<()>,
-    shutdown_token: CancellationToken,
-}
-
-impl ShutDown {
-    async fn run(self) {
-        let Self {
-            api_task,
-            relayer_task,
-            api_shutdown_signal,
-            shutdown_token,
-        } = self;
-        shutdown_token.cancel();
-        // Giving relayer 25 seconds to shutdown because Kubernetes issues a SIGKILL after 30.
-        if let Some(mut relayer_task) = relayer_task {
-            info!("waiting for relayer task to shut down");
-            let limit = Duration::from_secs(25);
-            match timeout(limit, &mut relayer_task)
-                .await
-                .map(crate::utils::flatten)
-            {
-                Ok(Ok(())) => info!("relayer exited gracefully"),
-                Ok(Err(error)) => error!(%error, "relayer exited with an error"),
-                Err(_) => {
-                    error!(
-                        timeout_secs = limit.as_secs(),
-                        "relayer did not shut down within timeout; killing it"
-                    );
-                    relayer_task.abort();
-                }
-            }
-        } else {
-            info!("relayer task was already dead");
-        }
-
-        // Giving the API task another 4 seconds. 25 for relayer + 4s = 29s (out of 30s for k8s).
-        if let Some(mut api_task) = api_task {
-            info!("sending shutdown signal to API server");
-            let _ = api_shutdown_signal.send(());
-            let limit = Duration::from_secs(4);
-            match timeout(limit, &mut api_task)
-                .await
-                .map(crate::utils::flatten)
-            {
-                Ok(Ok(())) => info!("API server exited gracefully"),
-                Ok(Err(error)) => error!(%error, "API server exited with an error"),
-                Err(_) => {
-                    error!(
-                        timeout_secs = limit.as_secs(),
-                        "API server did not shut down within timeout; killing it"
-                    );
-                    api_task.abort();
-                }
-            }
-        } else {
-            info!("API server was already dead");
-        }
+        Ok(()) => info!(task = task_name, "task has exited"),
+        Err(error) => error!(task = task_name, %error, "task exited with error"),
     }
 }
@@ -0,0 +1,475 @@
+//! Snapshots of the relayer's persistent state, to move a relayer to a new host.
+//!
+//! A [`Snapshot`] holds the pre- and post-submission states of every configured sequencer
+//! network, together with the height index, the cost ledger and the attestations if they are
+//! configured. It is written as a single JSON file by `snapshot export` and restored by
+//! `snapshot import`, both subcommands of the relayer binary that read the paths of the state
+//! from the relayer's configuration.
+//!
+//! Snapshots only cover submission states kept in files, the default `file` storage backend. The
+//! database backends are backed up with the tools of the database instead.
+//!
+//! The relayer must be stopped while a snapshot is exported, and must not be started on the old
+//! host again after it was imported on the new one: both relayers would continue from the same
+//! state and submit the same sequencer blocks twice.
+
+use std::{
+    fs,
+    io::{
+        ErrorKind,
+        Write as _,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::ExitCode,
+    time::SystemTime,
+};
+
+use astria_core::attestation::SignedSubmissionAttestation;
+use astria_eyre::eyre::{
+    self,
+    bail,
+    ensure,
+    WrapErr as _,
+};
+use clap::{
+    Parser,
+    Subcommand,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    attestations,
+    costs::{
+        self,
+        CostLedger,
+        CostRecord,
+    },
+    height_index::{
+        HeightIndex,
+        HeightMapping,
+    },
+    config::StorageBackend,
+    relayer::ensure_valid_submission_files,
+    Config,
+};
+
+/// The version of the snapshot format written by this relayer.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// The persistent state of a relayer.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Snapshot {
+    /// The version of the snapshot format.
+    pub version: u32,
+    /// The time the snapshot was exported, in seconds since the unix epoch.
+    pub exported_at: u64,
+    /// The submission states of the sequencer networks.
+    pub networks: Vec<NetworkSnapshot>,
+    /// The mappings of the height index, or `None` if no height index was configured.
+    pub height_index: Option<Vec<HeightMapping>>,
+    /// The records of the cost ledger, or `None` if no cost ledger was configured.
+    pub cost_ledger: Option<Vec<CostRecord>>,
+    /// The signed attestations of confirmed submissions, or `None` if attestations were not
+    /// configured.
+    #[serde(default)]
+    pub attestations: Option<Vec<SignedSubmissionAttestation>>,
+}
+
+/// The submission state of a single sequencer network.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct NetworkSnapshot {
+    /// The chain ID of an additional sequencer network, or `None` for the primary network.
+    pub chain_id: Option<String>,
+    /// The contents of the network's pre-submission file.
+    pub pre_submit: serde_json::Value,
+    /// The contents of the network's post-submission file.
+    pub post_submit: serde_json::Value,
+}
+
+/// The paths of the persistent state of a relayer.
+#[derive(Clone, Debug)]
+struct StatePaths {
+    networks: Vec<NetworkPaths>,
+    height_index: Option<PathBuf>,
+    cost_ledger: Option<PathBuf>,
+    attestations: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug)]
+struct NetworkPaths {
+    chain_id: Option<String>,
+    pre_submit: PathBuf,
+    post_submit: PathBuf,
+}
+
+impl StatePaths {
+    fn from_config(cfg: &Config) -> eyre::Result<Self> {
+        ensure!(
+            cfg.storage_backend == StorageBackend::File,
+            "snapshots require the `file` storage backend, but the relayer is configured to store \
+             its submission states in `{}`",
+            cfg.storage_backend,
+        );
+        let mut networks = vec![NetworkPaths {
+            chain_id: None,
+            pre_submit: cfg.pre_submit_path.clone(),
+            post_submit: cfg.post_submit_path.clone(),
+        }];
+        networks.extend(cfg.additional_sequencer_networks()?.into_iter().map(|network| {
+            NetworkPaths {
+                chain_id: Some(network.chain_id),
+                pre_submit: network.pre_submit_path,
+                post_submit: network.post_submit_path,
+            }
+        }));
+        Ok(Self {
+            networks,
+            height_index: (!cfg.height_index_path.is_empty())
+                .then(|| PathBuf::from(&cfg.height_index_path)),
+            cost_ledger: (!cfg.cost_ledger_path.is_empty())
+                .then(|| PathBuf::from(&cfg.cost_ledger_path)),
+            attestations: (!cfg.attestation_path.is_empty())
+                .then(|| PathBuf::from(&cfg.attestation_path)),
+        })
+    }
+}
+
+/// Reads the state at `paths` into a snapshot.
+fn export(paths: &StatePaths) -> eyre::Result<Snapshot> {
+    let mut networks = Vec::with_capacity(paths.networks.len());
+    for network in &paths.networks {
+        let pre_submit = read_json(&network.pre_submit)
+            .wrap_err_with(|| describe_network("failed reading pre-submission state", network))?;
+        let post_submit = read_json(&network.post_submit)
+            .wrap_err_with(|| describe_network("failed reading post-submission state", network))?;
+        networks.push(NetworkSnapshot {
+            chain_id: network.chain_id.clone(),
+            pre_submit,
+            post_submit,
+        });
+    }
+    let height_index = paths
+        .height_index
+        .as_ref()
+        .map(|path| HeightIndex::open(path).map(|index| index.mappings()))
+        .transpose()?;
+    let cost_ledger = paths
+        .cost_ledger
+        .as_ref()
+        .map(|path| CostLedger::new(path).records())
+        .transpose()?;
+    let attestations = paths
+        .attestations
+        .as_deref()
+        .map(attestations::read_all)
+        .transpose()?;
+    Ok(Snapshot {
+        version: SNAPSHOT_VERSION,
+        exported_at: costs::unix_seconds(SystemTime::now()),
+        networks,
+        height_index,
+        cost_ledger,
+        attestations,
+    })
+}
+
+/// Writes `snapshot` to the state at `paths`.
+///
+/// Everything is checked before the first file is written: the snapshot must contain exactly
+/// the configured sequencer networks with consistent submission states, and must not contain a
+/// height index, cost ledger or attestations that are not configured. Existing files that are not
+/// empty are only replaced if `force` is set.
+fn import(paths: &StatePaths, snapshot: &Snapshot, force: bool) -> eyre::Result<()> {
+    ensure!(
+        snapshot.version == SNAPSHOT_VERSION,
+        "snapshot has version {}, but only version {SNAPSHOT_VERSION} is supported",
+        snapshot.version,
+    );
+
+    let mut writes: Vec<(&Path, Vec<u8>)> = Vec::new();
+    for network in &paths.networks {
+        let Some(state) = snapshot
+            .networks
+            .iter()
+            .find(|state| state.chain_id == network.chain_id)
+        else {
+            bail!(describe_network("snapshot contains no state", network));
+        };
+        ensure_valid_submission_files(&state.pre_submit, &state.post_submit)
+            .wrap_err_with(|| describe_network("snapshot contains an invalid state", network))?;
+        writes.push((network.pre_submit.as_path(), serde_json::to_vec(&state.pre_submit)?));
+        writes.push((network.post_submit.as_path(), serde_json::to_vec(&state.post_submit)?));
+    }
+    for state in &snapshot.networks {
+        ensure!(
+            paths
+                .networks
+                .iter()
+                .any(|network| network.chain_id == state.chain_id),
+            "snapshot contains the state of sequencer network `{}`, which is not configured",
+            state.chain_id.as_deref().unwrap_or("<primary>"),
+        );
+    }
+    match (&paths.height_index, &snapshot.height_index) {
+        (Some(path), Some(mappings)) => writes.push((path.as_path(), to_json_lines(mappings)?)),
+        (None, Some(_)) => bail!("snapshot contains a height index, but none is configured"),
+        (_, None) => {}
+    }
+    match (&paths.cost_ledger, &snapshot.cost_ledger) {
+        (Some(path), Some(records)) => writes.push((path.as_path(), to_json_lines(records)?)),
+        (None, Some(_)) => bail!("snapshot contains a cost ledger, but none is configured"),
+        (_, None) => {}
+    }
+    match (&paths.attestations, &snapshot.attestations) {
+        (Some(path), Some(attestations)) => {
+            for attestation in attestations {
+                attestation
+                    .verify()
+                    .wrap_err("snapshot contains an attestation with an invalid signature")?;
+            }
+            let mut lines = Vec::new();
+            for attestation in attestations {
+                lines.extend(attestation.encode());
+                lines.push(b'\n');
+            }
+            writes.push((path.as_path(), lines));
+        }
+        (None, Some(_)) => bail!("snapshot contains attestations, but none are configured"),
+        (_, None) => {}
+    }
+
+    if !force {
+        for (path, _) in &writes {
+            ensure!(
+                is_missing_or_empty(path)?,
+                "refusing to replace `{}`, which is not empty; pass `--force` to replace it",
+                path.display(),
+            );
+        }
+    }
+    for (path, contents) in writes {
+        write_atomically(path, &contents)
+            .wrap_err_with(|| format!("failed writing `{}`", path.display()))?;
+    }
+    Ok(())
+}
+
+fn describe_network(message: &str, network: &NetworkPaths) -> String {
+    match &network.chain_id {
+        Some(chain_id) => format!("{message} for sequencer network `{chain_id}`"),
+        None => format!("{message} for the primary sequencer network"),
+    }
+}
+
+fn read_json(path: &Path) -> eyre::Result<serde_json::Value> {
+    let contents = fs::read(path).wrap_err_with(|| format!("failed reading `{}`", path.display()))?;
+    serde_json::from_slice(&contents)
+        .wrap_err_with(|| format!("failed parsing `{}` as JSON", path.display()))
+}
+
+fn to_json_lines<T: Serialize>(items: &[T]) -> eyre::Result<Vec<u8>> {
+    let mut lines = Vec::new();
+    for item in items {
+        serde_json::to_writer(&mut lines, item).wrap_err("failed encoding line as JSON")?;
+        lines.push(b'\n');
+    }
+    Ok(lines)
+}
+
+fn is_missing_or_empty(path: &Path) -> eyre::Result<bool> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(metadata.len() == 0),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(true),
+        Err(error) => {
+            Err(error).wrap_err_with(|| format!("failed reading metadata of `{}`", path.display()))
+        }
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path` and moves it to `path`, so that `path`
+/// never holds a partially written file.
+fn write_atomically(path: &Path, contents: &[u8]) -> eyre::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".import");
+    let temp_path = PathBuf::from(temp_path);
+    let mut file = fs::File::create(&temp_path).wrap_err("failed creating temporary file")?;
+    file.write_all(contents)
+        .wrap_err("failed writing temporary file")?;
+    file.sync_all()
+        .wrap_err("failed syncing temporary file to disk")?;
+    fs::rename(&temp_path, path).wrap_err("failed moving temporary file into place")
+}
+
+/// The arguments of the `snapshot` subcommand of the relayer binary.
+#[derive(Debug, Parser)]
+#[command(name = "snapshot", about = "Exports or imports the persistent state of the relayer")]
+pub struct SnapshotArgs {
+    #[command(subcommand)]
+    command: SnapshotCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum SnapshotCommand {
+    /// Writes the state at the configured paths to a snapshot file. Stop the relayer first.
+    Export {
+        /// The file to write the snapshot to.
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Restores the state at the configured paths from a snapshot file.
+    Import {
+        /// The file to read the snapshot from.
+        #[arg(long)]
+        input: PathBuf,
+        /// Replaces existing state files that are not empty.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Runs the `snapshot` subcommand with `args`, the first of which must be the subcommand's name.
+///
+/// The paths of the state are read from the relayer's configuration in the environment.
+pub fn run_cli<I, T>(args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let SnapshotArgs {
+        command,
+    } = SnapshotArgs::parse_from(args);
+    let result = config::get::<Config>()
+        .wrap_err("failed reading relayer configuration")
+        .and_then(|cfg| StatePaths::from_config(&cfg))
+        .and_then(|paths| match command {
+            SnapshotCommand::Export {
+                output,
+            } => {
+                let snapshot = export(&paths)?;
+                let contents = serde_json::to_vec_pretty(&snapshot)
+                    .wrap_err("failed encoding snapshot as JSON")?;
+                write_atomically(&output, &contents)
+                    .wrap_err_with(|| format!("failed writing snapshot to `{}`", output.display()))
+            }
+            SnapshotCommand::Import {
+                input,
+                force,
+            } => {
+                let contents = fs::read(&input)
+                    .wrap_err_with(|| format!("failed reading snapshot `{}`", input.display()))?;
+                let snapshot: Snapshot =
+                    serde_json::from_slice(&contents).wrap_err("failed decoding snapshot")?;
+                import(&paths, &snapshot, force)
+            }
+        });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("snapshot failed: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn paths_in(dir: &Path) -> StatePaths {
+        StatePaths {
+            networks: vec![
+                NetworkPaths {
+                    chain_id: None,
+                    pre_submit: dir.join("pre.json"),
+                    post_submit: dir.join("post.json"),
+                },
+                NetworkPaths {
+                    chain_id: Some("other".to_string()),
+                    pre_submit: dir.join("other-pre.json"),
+                    post_submit: dir.join("other-post.json"),
+                },
+            ],
+            height_index: Some(dir.join("heights.jsonl")),
+            cost_ledger: None,
+            attestations: None,
+        }
+    }
+
+    fn write_state(paths: &StatePaths) {
+        let post = json!({"state": "submitted", "celestia_height": 10, "sequencer_height": 5});
+        for network in &paths.networks {
+            fs::write(&network.pre_submit, json!({"state": "ignore"}).to_string()).unwrap();
+            fs::write(&network.post_submit, post.to_string()).unwrap();
+        }
+        HeightIndex::open(paths.height_index.as_ref().unwrap())
+            .unwrap()
+            .record(&[HeightMapping {
+                sequencer_chain_id: "other".to_string(),
+                sequencer_height: 5,
+                celestia_height: 10,
+            }])
+            .unwrap();
+    }
+
+    #[test]
+    fn exported_state_is_restored_on_a_new_host() {
+        let old_host = tempfile::tempdir().unwrap();
+        let old_paths = paths_in(old_host.path());
+        write_state(&old_paths);
+        let snapshot = export(&old_paths).unwrap();
+
+        let new_host = tempfile::tempdir().unwrap();
+        let new_paths = paths_in(new_host.path());
+        import(&new_paths, &snapshot, false).unwrap();
+        assert_eq!(
+            Snapshot {
+                exported_at: snapshot.exported_at,
+                ..export(&new_paths).unwrap()
+            },
+            snapshot,
+        );
+    }
+
+    #[test]
+    fn import_refuses_to_replace_state_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths_in(dir.path());
+        write_state(&paths);
+        let snapshot = export(&paths).unwrap();
+        assert!(import(&paths, &snapshot, false).is_err());
+        import(&paths, &snapshot, true).unwrap();
+    }
+
+    #[test]
+    fn import_rejects_snapshots_not_matching_the_configuration() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths_in(dir.path());
+        write_state(&paths);
+        let snapshot = export(&paths).unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        let mut missing_network = paths_in(target.path());
+        missing_network.networks.pop();
+        assert!(import(&missing_network, &snapshot, false).is_err());
+
+        let mut no_height_index = paths_in(target.path());
+        no_height_index.height_index = None;
+        assert!(import(&no_height_index, &snapshot, false).is_err());
+
+        let mut inconsistent = snapshot.clone();
+        inconsistent.networks[0].pre_submit = json!({
+            "state": "started",
+            "sequencer_height": 4,
+            "last_submission": {"state": "fresh"},
+        });
+        assert!(import(&paths_in(target.path()), &inconsistent, false).is_err());
+    }
+}
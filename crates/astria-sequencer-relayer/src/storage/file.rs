@@ -0,0 +1,72 @@
+use std::{
+    io::Write as _,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use async_trait::async_trait;
+use tokio::task::spawn_blocking;
+
+use super::{
+    Storage,
+    SubmissionKeys,
+};
+
+/// Stores every record in the file at the path given by its key.
+///
+/// Files are never created: reading or writing a record whose file does not exist fails, so that
+/// a misconfigured path is not mistaken for a sequencer network that nothing was submitted of.
+#[derive(Debug)]
+pub(crate) struct FileStorage;
+
+#[async_trait]
+impl Storage for FileStorage {
+    async fn get(&self, key: &str) -> eyre::Result<Option<Vec<u8>>> {
+        let path = PathBuf::from(key);
+        crate::utils::flatten(
+            spawn_blocking(move || {
+                std::fs::read(&path)
+                    .map(Some)
+                    .wrap_err("failed reading state from file")
+            })
+            .await,
+        )
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()> {
+        let path = PathBuf::from(key);
+        crate::utils::flatten(
+            spawn_blocking(move || {
+                let mut f = std::fs::File::options()
+                    .write(true)
+                    .truncate(true)
+                    .open(path)
+                    .wrap_err("failed opening file for writing state")?;
+                f.write_all(&value)
+                    .wrap_err("failed writing state to file")?;
+                f.sync_all()
+                    .wrap_err("failed fully syncing state write to disk")?;
+                Ok(())
+            })
+            .await,
+        )
+    }
+
+    fn submission_keys(
+        &self,
+        _chain_id: Option<&str>,
+        pre_submit_path: &Path,
+        post_submit_path: &Path,
+    ) -> SubmissionKeys {
+        SubmissionKeys {
+            pre_submit: pre_submit_path.display().to_string(),
+            post_submit: post_submit_path.display().to_string(),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Where the relayer persists the submission states of its sequencer networks.
+//!
+//! The pre- and post-submission states of every sequencer network are records stored under a
+//! key in a [`Storage`]. The backend is chosen by the relayer config:
+//!
+//! - `file`, the default, stores every record in the file at the path given by its key. The keys
+//!   are the pre- and post-submit paths configured for each sequencer network.
+//! - `rocksdb` stores all records in an embedded RocksDB database. Requires the `rocksdb` feature.
+//! - `postgres` stores all records in a table of a Postgres database, so that several relayer
+//!   instances can share their state. Requires the `postgres` feature.
+//!
+//! The database backends read records that were never written as absent, which the relayer
+//! treats as a sequencer network it has not submitted anything of yet. The file backend instead
+//! fails reading a file that does not exist.
+//!
+//! The postgres backend also holds the lease of the leader election between relayer instances
+//! sharing a database. Once fenced by the lease, it refuses to write records while its instance
+//! does not hold the lease, so that an instance that lost the lease cannot start submissions.
+
+use std::{
+    fmt::Debug,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use astria_eyre::eyre::{
+    self,
+    bail,
+    ensure,
+    WrapErr as _,
+};
+use async_trait::async_trait;
+
+use crate::{
+    config::StorageBackend,
+    secret::SecretString,
+};
+
+mod file;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "rocksdb")]
+mod rocksdb;
+
+pub(crate) use file::FileStorage;
+
+/// The name of the lease held by the leading relayer instance.
+pub(crate) const LEADER_LEASE: &str = "leader";
+
+/// A durable store of records keyed by strings.
+#[async_trait]
+pub(crate) trait Storage: Debug + Send + Sync {
+    /// Returns the record stored under `key`, or `None` if there is none.
+    async fn get(&self, key: &str) -> eyre::Result<Option<Vec<u8>>>;
+
+    /// Stores `value` under `key`, replacing the previous record. Returns once the record is
+    /// durably stored.
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()>;
+
+    /// Returns the keys of the pre- and post-submission states of the additional sequencer
+    /// network with `chain_id`, or of the primary network if it is `None`.
+    ///
+    /// The configured paths of the states are ignored by the database backends.
+    fn submission_keys(
+        &self,
+        chain_id: Option<&str>,
+        _pre_submit_path: &Path,
+        _post_submit_path: &Path,
+    ) -> SubmissionKeys {
+        let network = chain_id.map_or_else(|| "primary".to_string(), |id| format!("chain/{id}"));
+        SubmissionKeys {
+            pre_submit: format!("submission/{network}/pre_submit"),
+            post_submit: format!("submission/{network}/post_submit"),
+        }
+    }
+
+    /// Acquires the lease `name` for `holder` if it is not held by anyone else or expired, or
+    /// extends it if `holder` already holds it. The lease expires `duration` after this call.
+    ///
+    /// Returns if `holder` holds the lease afterwards.
+    async fn try_acquire_lease(
+        &self,
+        _name: &str,
+        _holder: &str,
+        _duration: Duration,
+    ) -> eyre::Result<bool> {
+        bail!("the storage backend does not support leases")
+    }
+
+    /// Releases the lease `name` if it is held by `holder`, so that another holder can acquire
+    /// it without waiting for it to expire.
+    async fn release_lease(&self, _name: &str, _holder: &str) -> eyre::Result<()> {
+        bail!("the storage backend does not support leases")
+    }
+}
+
+/// The keys under which the pre- and post-submission states of a sequencer network are stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SubmissionKeys {
+    pub(crate) pre_submit: String,
+    pub(crate) post_submit: String,
+}
+
+/// Opens the storage backend configured for the relayer.
+#[derive(Clone, Debug)]
+pub(crate) struct Builder {
+    backend: StorageBackend,
+    #[cfg_attr(not(feature = "rocksdb"), allow(dead_code))]
+    rocksdb_path: String,
+    #[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+    postgres_url: SecretString,
+    #[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+    lease_holder: Option<String>,
+}
+
+impl Builder {
+    /// Returns a builder of `backend`, checking that it was compiled in and that its location is
+    /// set.
+    pub(crate) fn new(
+        backend: StorageBackend,
+        rocksdb_path: String,
+        postgres_url: SecretString,
+    ) -> eyre::Result<Self> {
+        match backend {
+            StorageBackend::File => {}
+            StorageBackend::Rocksdb => {
+                ensure!(
+                    cfg!(feature = "rocksdb"),
+                    "the rocksdb storage backend requires the relayer to be built with the \
+                     `rocksdb` feature"
+                );
+                ensure!(
+                    !rocksdb_path.is_empty(),
+                    "a rocksdb path must be set if the rocksdb storage backend is used"
+                );
+            }
+            StorageBackend::Postgres => {
+                ensure!(
+                    cfg!(feature = "postgres"),
+                    "the postgres storage backend requires the relayer to be built with the \
+                     `postgres` feature"
+                );
+                ensure!(
+                    !postgres_url.is_empty(),
+                    "a postgres URL must be set if the postgres storage backend is used"
+                );
+            }
+        }
+        Ok(Self {
+            backend,
+            rocksdb_path,
+            postgres_url,
+            lease_holder: None,
+        })
+    }
+
+    /// Makes the storage refuse writing records while `holder` does not hold the
+    /// [`LEADER_LEASE`]. Only supported by the postgres backend.
+    pub(crate) fn fenced_by_leader_lease(self, holder: String) -> eyre::Result<Self> {
+        ensure!(
+            self.backend == StorageBackend::Postgres,
+            "leader election requires the postgres storage backend, but the {} backend is \
+             configured",
+            self.backend,
+        );
+        Ok(Self {
+            lease_holder: Some(holder),
+            ..self
+        })
+    }
+
+    /// Opens the storage, connecting to the database if one is used.
+    pub(crate) async fn open(self) -> eyre::Result<Arc<dyn Storage>> {
+        let storage: Arc<dyn Storage> = match self.backend {
+            StorageBackend::File => Arc::new(FileStorage),
+            #[cfg(feature = "rocksdb")]
+            StorageBackend::Rocksdb => Arc::new(
+                rocksdb::RocksDbStorage::open(self.rocksdb_path)
+                    .await
+                    .wrap_err("failed opening rocksdb storage")?,
+            ),
+            #[cfg(feature = "postgres")]
+            StorageBackend::Postgres => Arc::new(
+                postgres::PostgresStorage::connect(&self.postgres_url, self.lease_holder)
+                    .await
+                    .wrap_err("failed opening postgres storage")?,
+            ),
+            // Reachable if a database backend is not compiled in, which `Builder::new` rejects.
+            #[allow(unreachable_patterns)]
+            other => bail!("the {other} storage backend was not compiled in"),
+        };
+        Ok(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Database;
+
+    #[async_trait]
+    impl Storage for Database {
+        async fn get(&self, _key: &str) -> eyre::Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        async fn put(&self, _key: &str, _value: Vec<u8>) -> eyre::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn database_keys_are_distinct_per_network() {
+        let pre = Path::new("/pre");
+        let post = Path::new("/post");
+        let primary = Database.submission_keys(None, pre, post);
+        let additional = Database.submission_keys(Some("primary"), pre, post);
+        assert_eq!("submission/primary/pre_submit", primary.pre_submit);
+        assert_eq!("submission/chain/primary/post_submit", additional.post_submit);
+        assert_ne!(primary, additional);
+    }
+
+    #[test]
+    fn file_keys_are_the_configured_paths() {
+        let keys = FileStorage.submission_keys(None, Path::new("/pre"), Path::new("/post"));
+        assert_eq!("/pre", keys.pre_submit);
+        assert_eq!("/post", keys.post_submit);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    fmt,
+    time::Duration,
+};
+
+use astria_eyre::eyre::{
+    self,
+    ensure,
+    WrapErr as _,
+};
+use async_trait::async_trait;
+use tokio_postgres::{
+    Client,
+    NoTls,
+};
+use tracing::error;
+
+use super::{
+    Storage,
+    LEADER_LEASE,
+};
+use crate::secret::SecretString;
+
+/// Creates the tables holding the records and the leases if they do not exist yet.
+const CREATE_TABLES: &str = "CREATE TABLE IF NOT EXISTS sequencer_relayer_records (
+    key TEXT PRIMARY KEY,
+    value BYTEA NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS sequencer_relayer_leases (
+    name TEXT PRIMARY KEY,
+    holder TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+)";
+
+const SELECT_RECORD: &str = "SELECT value FROM sequencer_relayer_records WHERE key = $1";
+
+const UPSERT_RECORD: &str = "INSERT INTO sequencer_relayer_records (key, value) VALUES ($1, $2)
+    ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = now()";
+
+/// Like `UPSERT_RECORD`, but only writes the record if the lease `$3` is held by `$4`.
+const FENCED_UPSERT_RECORD: &str = "INSERT INTO sequencer_relayer_records (key, value)
+    SELECT $1, $2 WHERE EXISTS (
+        SELECT 1 FROM sequencer_relayer_leases
+        WHERE name = $3 AND holder = $4 AND expires_at > now()
+    )
+    ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = now()";
+
+/// Takes over the lease `$1` for `$2` if it is free, expired, or already held by `$2`, letting
+/// it expire after `$3` milliseconds. Returns a row only if the lease was taken over.
+const ACQUIRE_LEASE: &str = "INSERT INTO sequencer_relayer_leases (name, holder, expires_at)
+    VALUES ($1, $2, now() + $3::BIGINT * interval '1 millisecond')
+    ON CONFLICT (name) DO UPDATE SET holder = EXCLUDED.holder, expires_at = EXCLUDED.expires_at
+    WHERE sequencer_relayer_leases.holder = EXCLUDED.holder
+        OR sequencer_relayer_leases.expires_at <= now()
+    RETURNING holder";
+
+const RELEASE_LEASE: &str = "DELETE FROM sequencer_relayer_leases WHERE name = $1 AND holder = $2";
+
+/// Stores all records in the `sequencer_relayer_records` table of a Postgres database, and
+/// leases in the `sequencer_relayer_leases` table.
+///
+/// The connection is not re-established once it is lost: reading or writing records fails from
+/// then on, which stops the relayer.
+pub(crate) struct PostgresStorage {
+    client: Client,
+    /// If set, records are only written while this holder holds the leader lease.
+    lease_holder: Option<String>,
+}
+
+impl PostgresStorage {
+    /// Connects to the database at `url`, creating the records and leases tables if they do not
+    /// exist.
+    pub(super) async fn connect(
+        url: &SecretString,
+        lease_holder: Option<String>,
+    ) -> eyre::Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url.expose_secret(), NoTls)
+            .await
+            .wrap_err("failed connecting to postgres")?;
+        tokio::spawn(async move {
+            if let Err(error) = connection.await {
+                error!(%error, "connection to postgres failed");
+            }
+        });
+        client
+            .batch_execute(CREATE_TABLES)
+            .await
+            .wrap_err("failed creating records and leases tables")?;
+        Ok(Self {
+            client,
+            lease_holder,
+        })
+    }
+}
+
+impl fmt::Debug for PostgresStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresStorage")
+            .field("lease_holder", &self.lease_holder)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Storage for PostgresStorage {
+    async fn get(&self, key: &str) -> eyre::Result<Option<Vec<u8>>> {
+        let row = self
+            .client
+            .query_opt(SELECT_RECORD, &[&key])
+            .await
+            .wrap_err("failed reading record from postgres")?;
+        Ok(row.map(|row| row.get(0)))
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()> {
+        let Some(holder) = &self.lease_holder else {
+            self.client
+                .execute(UPSERT_RECORD, &[&key, &value])
+                .await
+                .wrap_err("failed writing record to postgres")?;
+            return Ok(());
+        };
+        let written = self
+            .client
+            .execute(FENCED_UPSERT_RECORD, &[&key, &value, &LEADER_LEASE, holder])
+            .await
+            .wrap_err("failed writing record to postgres")?;
+        ensure!(
+            written > 0,
+            "refusing to write record because this instance does not hold the leader lease"
+        );
+        Ok(())
+    }
+
+    async fn try_acquire_lease(
+        &self,
+        name: &str,
+        holder: &str,
+        duration: Duration,
+    ) -> eyre::Result<bool> {
+        let millis = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+        let row = self
+            .client
+            .query_opt(ACQUIRE_LEASE, &[&name, &holder, &millis])
+            .await
+            .wrap_err("failed acquiring lease in postgres")?;
+        Ok(row.is_some())
+    }
+
+    async fn release_lease(&self, name: &str, holder: &str) -> eyre::Result<()> {
+        self.client
+            .execute(RELEASE_LEASE, &[&name, &holder])
+            .await
+            .wrap_err("failed releasing lease in postgres")?;
+        Ok(())
+    }
+}
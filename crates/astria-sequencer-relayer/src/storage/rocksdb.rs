@@ -0,0 +1,99 @@
+use std::{
+    fmt,
+    sync::Arc,
+};
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use async_trait::async_trait;
+use rocksdb::{
+    Options,
+    WriteOptions,
+    DB,
+};
+use tokio::task::spawn_blocking;
+
+use super::Storage;
+
+/// Stores all records in an embedded RocksDB database.
+pub(crate) struct RocksDbStorage {
+    path: String,
+    db: Arc<DB>,
+}
+
+impl RocksDbStorage {
+    /// Opens the database at `path`, creating it if it does not exist.
+    pub(super) async fn open(path: String) -> eyre::Result<Self> {
+        let db = {
+            let path = path.clone();
+            crate::utils::flatten(
+                spawn_blocking(move || {
+                    let mut options = Options::default();
+                    options.create_if_missing(true);
+                    DB::open(&options, &path)
+                        .wrap_err_with(|| format!("failed opening rocksdb database at `{path}`"))
+                })
+                .await,
+            )?
+        };
+        Ok(Self {
+            path,
+            db: Arc::new(db),
+        })
+    }
+}
+
+impl fmt::Debug for RocksDbStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocksDbStorage")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Storage for RocksDbStorage {
+    async fn get(&self, key: &str) -> eyre::Result<Option<Vec<u8>>> {
+        let db = self.db.clone();
+        let key = key.to_string();
+        crate::utils::flatten(
+            spawn_blocking(move || db.get(key).wrap_err("failed reading record from rocksdb"))
+                .await,
+        )
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()> {
+        let db = self.db.clone();
+        let key = key.to_string();
+        crate::utils::flatten(
+            spawn_blocking(move || {
+                // Syncing every write, because the submission states must survive a crash.
+                let mut options = WriteOptions::default();
+                options.set_sync(true);
+                db.put_opt(key, value, &options)
+                    .wrap_err("failed writing record to rocksdb")
+            })
+            .await,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn records_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().display().to_string();
+        let storage = RocksDbStorage::open(path.clone()).await.unwrap();
+        assert_eq!(None, storage.get("key").await.unwrap());
+        storage.put("key", b"value".to_vec()).await.unwrap();
+        drop(storage);
+
+        let storage = RocksDbStorage::open(path).await.unwrap();
+        assert_eq!(Some(b"value".to_vec()), storage.get("key").await.unwrap());
+    }
+}
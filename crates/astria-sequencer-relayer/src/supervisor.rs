@@ -0,0 +1,326 @@
+//! Supervises the long running tasks of the relayer.
+//!
+//! Every task is spawned under a name together with the cancellation token it honors. A task
+//! that panics or returns an error is restarted according to its [`RestartPolicy`]. Once a task
+//! exits for good, [`Supervisor::exited`] reports it, so that its owner can shut down instead of
+//! running on without it. [`Supervisor::shutdown`] stops the tasks in the reverse order they were
+//! spawned in, giving each a timeout to exit gracefully before aborting it. Tasks spawned first
+//! are therefore available to the tasks spawned after them until those have shut down.
+
+use std::{
+    future::Future,
+    time::Duration,
+};
+
+use astria_eyre::eyre::{
+    self,
+    WrapErr as _,
+};
+use tokio::{
+    select,
+    task::{
+        JoinError,
+        JoinHandle,
+    },
+    time::{
+        sleep,
+        timeout,
+    },
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{
+    error,
+    info,
+    warn,
+};
+
+/// Whether a supervised task is restarted after it failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RestartPolicy {
+    /// The task is never restarted.
+    Never,
+    /// The task is restarted after it panicked or returned an error, at most `max_restarts`
+    /// times, waiting `backoff` before each restart. A task that returns successfully or fails
+    /// after shutdown started is not restarted.
+    OnFailure { max_restarts: u32, backoff: Duration },
+}
+
+/// The tasks spawned by a supervisor, in the order they were spawned.
+pub(crate) struct Supervisor {
+    tasks: Vec<SupervisedTask>,
+}
+
+struct SupervisedTask {
+    name: &'static str,
+    shutdown_token: CancellationToken,
+    shutdown_timeout: Duration,
+    handle: JoinHandle<eyre::Result<()>>,
+}
+
+impl Supervisor {
+    pub(crate) fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Spawns `task` under `name`, running it once.
+    ///
+    /// The task must exit once `shutdown_token` is cancelled; it is aborted if it takes longer
+    /// than `shutdown_timeout` to do so during [`Supervisor::shutdown`].
+    pub(crate) fn spawn<Fut>(
+        &mut self,
+        name: &'static str,
+        shutdown_token: CancellationToken,
+        shutdown_timeout: Duration,
+        task: Fut,
+    ) where
+        Fut: Future<Output = eyre::Result<()>> + Send + 'static,
+    {
+        let mut task = Some(task);
+        self.spawn_restarting(
+            name,
+            shutdown_token,
+            shutdown_timeout,
+            RestartPolicy::Never,
+            move || {
+                task.take()
+                    .expect("tasks that are never restarted are only made once")
+            },
+        );
+    }
+
+    /// Spawns the task made by `make_task` under `name`, making and running it again after a
+    /// failure as allowed by `restart_policy`.
+    ///
+    /// The task must exit once `shutdown_token` is cancelled; it is aborted if it takes longer
+    /// than `shutdown_timeout` to do so during [`Supervisor::shutdown`].
+    pub(crate) fn spawn_restarting<F, Fut>(
+        &mut self,
+        name: &'static str,
+        shutdown_token: CancellationToken,
+        shutdown_timeout: Duration,
+        restart_policy: RestartPolicy,
+        make_task: F,
+    ) where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = eyre::Result<()>> + Send + 'static,
+    {
+        let handle = tokio::spawn(supervise(
+            name,
+            restart_policy,
+            shutdown_token.clone(),
+            make_task,
+        ));
+        info!(task = name, "spawned task");
+        self.tasks.push(SupervisedTask {
+            name,
+            shutdown_token,
+            shutdown_timeout,
+            handle,
+        });
+    }
+
+    /// Waits until one of the tasks exits for good, returning its name and outcome. The task is
+    /// no longer supervised afterwards.
+    ///
+    /// Never resolves if there are no tasks left. This method is cancellation safe.
+    pub(crate) async fn exited(&mut self) -> (&'static str, eyre::Result<()>) {
+        if self.tasks.is_empty() {
+            return futures::future::pending().await;
+        }
+        let (outcome, index, _) =
+            futures::future::select_all(self.tasks.iter_mut().map(|task| &mut task.handle)).await;
+        let task = self.tasks.remove(index);
+        (task.name, crate::utils::flatten(outcome))
+    }
+
+    /// Shuts down all tasks, in the reverse order they were spawned in.
+    ///
+    /// Each task's shutdown token is cancelled in turn, and the next task is only shut down once
+    /// the previous one exited or was aborted after its shutdown timeout.
+    pub(crate) async fn shutdown(self) {
+        for task in self.tasks.into_iter().rev() {
+            let SupervisedTask {
+                name,
+                shutdown_token,
+                shutdown_timeout,
+                mut handle,
+            } = task;
+            shutdown_token.cancel();
+            info!(task = name, "waiting for task to shut down");
+            match timeout(shutdown_timeout, &mut handle)
+                .await
+                .map(crate::utils::flatten)
+            {
+                Ok(Ok(())) => info!(task = name, "task exited gracefully"),
+                Ok(Err(error)) => error!(task = name, %error, "task exited with an error"),
+                Err(_) => {
+                    error!(
+                        task = name,
+                        timeout_secs = shutdown_timeout.as_secs(),
+                        "task did not shut down within timeout; killing it"
+                    );
+                    handle.abort();
+                }
+            }
+        }
+    }
+}
+
+/// Runs the tasks made by `make_task`, each in its own tokio task so that panics are caught,
+/// until one exits without being restarted.
+async fn supervise<F, Fut>(
+    name: &'static str,
+    restart_policy: RestartPolicy,
+    shutdown_token: CancellationToken,
+    mut make_task: F,
+) -> eyre::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = eyre::Result<()>> + Send + 'static,
+{
+    let mut restarts = 0;
+    loop {
+        // Aborts the running task if the supervising task is aborted.
+        let mut task = AbortOnDrop(tokio::spawn(make_task()));
+        let error = match (&mut task.0).await {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(error)) => error,
+            Err(error) => join_error_report(error),
+        };
+        let RestartPolicy::OnFailure {
+            max_restarts,
+            backoff,
+        } = restart_policy
+        else {
+            return Err(error);
+        };
+        if shutdown_token.is_cancelled() {
+            return Err(error);
+        }
+        if restarts >= max_restarts {
+            return Err(error).wrap_err_with(|| format!("task failed after {restarts} restarts"));
+        }
+        restarts += 1;
+        warn!(
+            task = name,
+            restarts,
+            max_restarts,
+            error = %error,
+            "task failed; restarting it after backoff",
+        );
+        select!(
+            () = shutdown_token.cancelled() => return Err(error),
+            () = sleep(backoff) => {}
+        );
+    }
+}
+
+/// Turns the error of a task that panicked or was aborted into a report, keeping the panic
+/// message.
+fn join_error_report(error: JoinError) -> eyre::Report {
+    if !error.is_panic() {
+        return eyre::Report::new(error).wrap_err("task was aborted");
+    }
+    let payload = error.into_panic();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<non-string panic payload>".to_string());
+    eyre::eyre!("task panicked: {message}")
+}
+
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{
+            AtomicU32,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    };
+
+    use super::*;
+
+    fn on_failure(max_restarts: u32) -> RestartPolicy {
+        RestartPolicy::OnFailure {
+            max_restarts,
+            backoff: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn panicking_task_is_restarted_until_it_succeeds() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn_restarting(
+            "flaky",
+            CancellationToken::new(),
+            Duration::from_secs(1),
+            on_failure(3),
+            {
+                let runs = runs.clone();
+                move || {
+                    let run = runs.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        assert!(run >= 2, "failing run {run}");
+                        Ok(())
+                    }
+                }
+            },
+        );
+        let (name, outcome) = supervisor.exited().await;
+        assert_eq!("flaky", name);
+        outcome.unwrap();
+        assert_eq!(3, runs.load(Ordering::SeqCst));
+    }
+
+    async fn broken() -> eyre::Result<()> {
+        panic!("always broken")
+    }
+
+    #[tokio::test]
+    async fn task_exceeding_its_restarts_is_reported_with_its_panic() {
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn_restarting(
+            "broken",
+            CancellationToken::new(),
+            Duration::from_secs(1),
+            on_failure(2),
+            broken,
+        );
+        let (name, outcome) = supervisor.exited().await;
+        assert_eq!("broken", name);
+        let error = format!("{:#}", outcome.unwrap_err());
+        assert!(error.contains("after 2 restarts"), "{error}");
+        assert!(error.contains("always broken"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn tasks_are_shut_down_in_reverse_order() {
+        let exits = Arc::new(Mutex::new(Vec::new()));
+        let mut supervisor = Supervisor::new();
+        for name in ["first", "second", "third"] {
+            let token = CancellationToken::new();
+            let exits = exits.clone();
+            supervisor.spawn(name, token.clone(), Duration::from_secs(1), async move {
+                token.cancelled().await;
+                exits.lock().unwrap().push(name);
+                Ok(())
+            });
+        }
+        supervisor.shutdown().await;
+        assert_eq!(vec!["third", "second", "first"], *exits.lock().unwrap());
+    }
+}
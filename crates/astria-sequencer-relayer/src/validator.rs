@@ -1,4 +1,11 @@
-use std::path::Path;
+use std::{
+    fmt::{
+        self,
+        Debug,
+        Formatter,
+    },
+    path::Path,
+};
 
 use astria_eyre::eyre::{
     self,
@@ -12,6 +19,8 @@ use ed25519_consensus::{
 use tendermint::account;
 use tendermint_config::PrivValidatorKey;
 use tracing::instrument;
+
+use crate::secret::SecretString;
 use zeroize::{
     Zeroize,
     ZeroizeOnDrop,
@@ -19,7 +28,7 @@ use zeroize::{
 
 /// `Validator` holds the ed25519 keys to sign and verify tendermint
 /// messages. It also contains its address (`AccountId`) in the tendermint network.
-#[derive(Clone, Debug, Zeroize, ZeroizeOnDrop)]
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub(crate) struct Validator {
     /// The tendermint validator account address; defined as
     /// Sha256(verification_key)[..20].
@@ -41,8 +50,10 @@ impl Validator {
     /// the initialization of a tendermint node.
     #[instrument(skip_all, fields(path = %path.as_ref().display(), err))]
     pub(crate) fn from_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
-        let key = PrivValidatorKey::load_json_file(&path.as_ref())
+        let json = SecretString::read_file(path)
             .wrap_err("failed reading private validator key from file")?;
+        let key = PrivValidatorKey::parse_json(json.expose_secret())
+            .wrap_err("failed parsing private validator key")?;
         Self::from_priv_validator_key(key)
     }
 
@@ -73,6 +84,17 @@ impl Validator {
     }
 }
 
+impl Debug for Validator {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Validator")
+            .field("address", &self.address)
+            .field("signing_key", &"...")
+            .field("verification_key", &self.verification_key)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Validator;
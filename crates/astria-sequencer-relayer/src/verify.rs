@@ -0,0 +1,603 @@
+//! Verification of the blobs the relayer writes to Celestia.
+//!
+//! [`verify_signed_namespace_data`] checks a blob read from one of the namespaces the relayer
+//! writes sequencer blocks to, exactly as the relayer encodes them: brotli-compressed, in either
+//! of the [`BlobFormat`]s, as a [`CelestiaSequencerBlob`] or a [`CelestiaRollupBlob`]. It is
+//! meant to be embedded by explorers, bridges, and auditors, so that they accept the same data
+//! that rollups reading from Celestia accept.
+//!
+//! Relayers configured to mark sequencer blocks without rollup data write a single
+//! [`CelestiaEmptyBlocksMarker`] for a run of such blocks instead; [`verify_empty_blocks_marker`]
+//! checks those.
+//!
+//! Decoding a blob already verifies its inclusion proofs, the rollup transactions root and
+//! checksum, and the signature of a rollup blob if it carries one. [`VerifyOptions`] add limits
+//! protecting against oversized blobs, the sequencer chain the blobs must belong to, and the
+//! keys rollup blobs must be signed with.
+//!
+//! [`verify_all`] verifies many blobs at once, for example all blobs read at a Celestia height.
+//! Instead of stopping at the first invalid blob it returns a [`VerificationOutcome`] per blob,
+//! recording the [`FailureStage`] a blob failed at and how long verifying it took.
+
+use std::{
+    cmp::max,
+    collections::HashMap,
+    fmt,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use astria_core::{
+    brotli::decompress_bytes_with_limit,
+    generated::sequencerblock::v1alpha1 as raw,
+    primitive::v1::RollupId,
+    sequencerblock::v1alpha1::{
+        BlobFormat,
+        CelestiaEmptyBlocksMarker,
+        CelestiaRollupBlob,
+        CelestiaSequencerBlob,
+        RollupVerificationKey,
+    },
+};
+use astria_eyre::eyre::{
+    self,
+    ensure,
+    eyre,
+    WrapErr as _,
+};
+
+/// The default maximum size of a blob in bytes, both before and after decompression.
+pub const DEFAULT_MAX_BLOB_SIZE: usize = 16 * 1024 * 1024;
+
+/// The default maximum number of rollup transactions in a single rollup blob.
+pub const DEFAULT_MAX_ROLLUP_TXS_PER_BLOB: usize = 65536;
+
+/// Options of [`verify_signed_namespace_data`].
+///
+/// The defaults match the limits conductor applies to the blobs it reads, and accept blobs of
+/// any sequencer chain whether they are signed or not.
+#[derive(Clone, Debug)]
+pub struct VerifyOptions {
+    max_blob_size: usize,
+    max_rollup_txs_per_blob: usize,
+    expected_chain_id: Option<String>,
+    trusted_keys: HashMap<RollupId, RollupVerificationKey>,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+            max_rollup_txs_per_blob: DEFAULT_MAX_ROLLUP_TXS_PER_BLOB,
+            expected_chain_id: None,
+            trusted_keys: HashMap::new(),
+        }
+    }
+}
+
+impl VerifyOptions {
+    /// Sets the maximum size of a blob in bytes, both before and after decompression.
+    #[must_use]
+    pub fn max_blob_size(self, max_blob_size: usize) -> Self {
+        Self {
+            max_blob_size,
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of rollup transactions in a single rollup blob.
+    #[must_use]
+    pub fn max_rollup_txs_per_blob(self, max_rollup_txs_per_blob: usize) -> Self {
+        Self {
+            max_rollup_txs_per_blob,
+            ..self
+        }
+    }
+
+    /// Only accepts sequencer blobs whose header names the sequencer chain `chain_id`.
+    #[must_use]
+    pub fn expected_chain_id(self, chain_id: impl Into<String>) -> Self {
+        Self {
+            expected_chain_id: Some(chain_id.into()),
+            ..self
+        }
+    }
+
+    /// Only accepts blobs of the rollup `rollup_id` if they are signed by `key`.
+    ///
+    /// `key` can be the verification key of any supported signature scheme, like an ed25519
+    /// `VerificationKey` or a secp256k1 `VerifyingKey`. Blobs of rollups without a trusted key
+    /// are accepted whether they are signed or not.
+    #[must_use]
+    pub fn trusted_key(
+        mut self,
+        rollup_id: RollupId,
+        key: impl Into<RollupVerificationKey>,
+    ) -> Self {
+        self.trusted_keys.insert(rollup_id, key.into());
+        self
+    }
+}
+
+/// The data of a blob verified by [`verify_signed_namespace_data`].
+#[derive(Clone, Debug)]
+pub enum VerifiedData {
+    /// The header of a sequencer block.
+    SequencerBlob(CelestiaSequencerBlob),
+    /// The transactions of a single rollup in a sequencer block.
+    RollupBlob(CelestiaRollupBlob),
+}
+
+impl VerifiedData {
+    /// Returns the hash of the sequencer block the data belongs to.
+    #[must_use]
+    pub fn sequencer_block_hash(&self) -> [u8; 32] {
+        match self {
+            Self::SequencerBlob(blob) => blob.block_hash(),
+            Self::RollupBlob(blob) => blob.sequencer_block_hash(),
+        }
+    }
+}
+
+/// The stage of verification at which a blob was rejected.
+///
+/// The stages are ordered in the sequence they are run in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FailureStage {
+    /// The blob exceeded the configured limits, or could not be decompressed or decoded.
+    Decode,
+    /// The hashes, proofs, or checksums of the decoded blob did not verify.
+    Hash,
+    /// The sequencer blob belongs to another sequencer chain than the expected one.
+    ChainId,
+    /// The verification key or the signature of a rollup blob could not be parsed.
+    KeyParse,
+    /// The rollup blob was not signed by its trusted key, or its signature did not match its
+    /// contents.
+    Signature,
+}
+
+impl FailureStage {
+    /// Returns the name of the stage in snake case, as used in logs and metrics.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Decode => "decode",
+            Self::Hash => "hash",
+            Self::ChainId => "chain_id",
+            Self::KeyParse => "key_parse",
+            Self::Signature => "signature",
+        }
+    }
+}
+
+impl fmt::Display for FailureStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why a blob was rejected by [`verify_all`].
+#[derive(Debug)]
+pub struct VerificationFailure {
+    stage: FailureStage,
+    error: eyre::Report,
+}
+
+impl VerificationFailure {
+    /// Returns a closure attributing an error to `stage`, to be passed to `map_err`.
+    fn at(stage: FailureStage) -> impl FnOnce(eyre::Report) -> Self {
+        move |error| Self {
+            stage,
+            error,
+        }
+    }
+
+    /// Returns the stage at which the blob was rejected.
+    #[must_use]
+    pub fn stage(&self) -> FailureStage {
+        self.stage
+    }
+
+    /// Returns the error the blob was rejected with.
+    #[must_use]
+    pub fn error(&self) -> &eyre::Report {
+        &self.error
+    }
+}
+
+impl fmt::Display for VerificationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rejected at the {} stage: {:#}", self.stage, self.error)
+    }
+}
+
+/// The outcome of verifying a single blob with [`verify_all`].
+#[derive(Debug)]
+pub struct VerificationOutcome {
+    result: Result<VerifiedData, VerificationFailure>,
+    elapsed: Duration,
+}
+
+impl VerificationOutcome {
+    /// Returns the verified data of the blob, or why it was rejected.
+    pub fn result(&self) -> Result<&VerifiedData, &VerificationFailure> {
+        self.result.as_ref()
+    }
+
+    /// Returns if the blob was verified.
+    #[must_use]
+    pub fn is_verified(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Returns how long verifying the blob took.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the verified data of the blob, or why it was rejected.
+    ///
+    /// # Errors
+    /// Returns the failure if the blob was rejected.
+    pub fn into_result(self) -> Result<VerifiedData, VerificationFailure> {
+        self.result
+    }
+}
+
+/// Decompresses, decodes, and verifies `bytes`, the data of a blob written by the relayer.
+///
+/// The blob is accepted if it is a valid sequencer blob or a valid rollup blob that also
+/// satisfies `options`. A blob of one kind cannot be mistaken for the other, because the hashes
+/// and proofs of either kind are verified.
+///
+/// # Errors
+/// Returns an error if `bytes` exceed the configured maximum size before or after
+/// decompression, cannot be decompressed, or are neither a valid sequencer blob nor a valid
+/// rollup blob satisfying `options`.
+pub fn verify_signed_namespace_data(
+    bytes: &[u8],
+    options: &VerifyOptions,
+) -> eyre::Result<VerifiedData> {
+    verify(bytes, options).map_err(|failure| failure.error)
+}
+
+/// Verifies each of `blobs` like [`verify_signed_namespace_data`], returning their outcomes in
+/// the same order.
+///
+/// An invalid blob does not stop the remaining blobs from being verified.
+pub fn verify_all<B: AsRef<[u8]>>(
+    blobs: &[B],
+    options: &VerifyOptions,
+) -> Vec<VerificationOutcome> {
+    blobs
+        .iter()
+        .map(|blob| {
+            let start = Instant::now();
+            let result = verify(blob.as_ref(), options);
+            VerificationOutcome {
+                result,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect()
+}
+
+fn verify(bytes: &[u8], options: &VerifyOptions) -> Result<VerifiedData, VerificationFailure> {
+    let decompressed =
+        decompress(bytes, options).map_err(VerificationFailure::at(FailureStage::Decode))?;
+
+    let sequencer_failure = match verify_sequencer_blob(&decompressed, options) {
+        Ok(blob) => return Ok(VerifiedData::SequencerBlob(blob)),
+        Err(failure) => failure,
+    };
+    let rollup_failure = match verify_rollup_blob(&decompressed, options) {
+        Ok(blob) => return Ok(VerifiedData::RollupBlob(blob)),
+        Err(failure) => failure,
+    };
+    // The blob most likely is of the kind that passed more stages, so its stage is reported.
+    Err(VerificationFailure {
+        stage: max(sequencer_failure.stage, rollup_failure.stage),
+        error: eyre!(
+            "blob is neither a valid sequencer blob nor a valid rollup blob; as a sequencer \
+             blob: {:#}; as a rollup blob: {:#}",
+            sequencer_failure.error,
+            rollup_failure.error,
+        ),
+    })
+}
+
+/// Decompresses, decodes, and verifies `bytes` as a marker of sequencer blocks without rollup
+/// data, returning the sequencer blobs of the marked blocks ordered by sequencer height.
+///
+/// Every block of the marker is verified like a blob passed to
+/// [`verify_signed_namespace_data`], and must be of the expected sequencer chain if one is set.
+///
+/// # Errors
+/// Returns an error if `bytes` exceed the configured maximum size before or after
+/// decompression, cannot be decompressed, or are not a valid marker satisfying `options`.
+pub fn verify_empty_blocks_marker(
+    bytes: &[u8],
+    options: &VerifyOptions,
+) -> eyre::Result<Vec<CelestiaSequencerBlob>> {
+    let decompressed = decompress(bytes, options)?;
+    let (raw, _) = BlobFormat::decode::<raw::CelestiaEmptyBlocksMarker>(&decompressed)
+        .wrap_err("failed decoding blob")?;
+    let empty_blocks = CelestiaEmptyBlocksMarker::try_from_raw(raw)
+        .wrap_err("failed verifying marker")?
+        .into_empty_blocks();
+    for blob in &empty_blocks {
+        ensure_expected_chain_id(blob, options)?;
+    }
+    Ok(empty_blocks)
+}
+
+fn decompress(bytes: &[u8], options: &VerifyOptions) -> eyre::Result<Vec<u8>> {
+    ensure!(
+        bytes.len() <= options.max_blob_size,
+        "blob of {} bytes exceeds the maximum size of {} bytes",
+        bytes.len(),
+        options.max_blob_size,
+    );
+    decompress_bytes_with_limit(bytes, options.max_blob_size)
+        .wrap_err("failed decompressing blob with brotli")
+}
+
+fn verify_sequencer_blob(
+    decompressed: &[u8],
+    options: &VerifyOptions,
+) -> Result<CelestiaSequencerBlob, VerificationFailure> {
+    let (raw, _) = BlobFormat::decode::<raw::CelestiaSequencerBlob>(decompressed)
+        .wrap_err("failed decoding blob")
+        .map_err(VerificationFailure::at(FailureStage::Decode))?;
+    let blob = CelestiaSequencerBlob::try_from_raw(raw)
+        .wrap_err("failed verifying blob")
+        .map_err(VerificationFailure::at(FailureStage::Hash))?;
+    ensure_expected_chain_id(&blob, options)
+        .map_err(VerificationFailure::at(FailureStage::ChainId))?;
+    Ok(blob)
+}
+
+fn ensure_expected_chain_id(
+    blob: &CelestiaSequencerBlob,
+    options: &VerifyOptions,
+) -> eyre::Result<()> {
+    if let Some(expected_chain_id) = &options.expected_chain_id {
+        let chain_id = blob.header().chain_id();
+        ensure!(
+            chain_id.as_str() == expected_chain_id,
+            "blob belongs to sequencer chain `{chain_id}`, expected `{expected_chain_id}`"
+        );
+    }
+    Ok(())
+}
+
+fn verify_rollup_blob(
+    decompressed: &[u8],
+    options: &VerifyOptions,
+) -> Result<CelestiaRollupBlob, VerificationFailure> {
+    let (raw, _) = BlobFormat::decode::<raw::CelestiaRollupBlob>(decompressed)
+        .wrap_err("failed decoding blob")
+        .map_err(VerificationFailure::at(FailureStage::Decode))?;
+    if raw.transactions.len() > options.max_rollup_txs_per_blob {
+        return Err(VerificationFailure {
+            stage: FailureStage::Decode,
+            error: eyre!(
+                "blob contains {} rollup transactions, exceeding the maximum of {}",
+                raw.transactions.len(),
+                options.max_rollup_txs_per_blob,
+            ),
+        });
+    }
+    let blob = CelestiaRollupBlob::try_from_raw(raw).map_err(|error| {
+        let stage = if error.is_malformed_signature() {
+            FailureStage::KeyParse
+        } else if error.is_signature_mismatch() {
+            FailureStage::Signature
+        } else {
+            FailureStage::Hash
+        };
+        VerificationFailure {
+            stage,
+            error: eyre::Report::new(error).wrap_err("failed verifying blob"),
+        }
+    })?;
+    if let Some(trusted_key) = options.trusted_keys.get(&blob.rollup_id()) {
+        let error = match blob.signature() {
+            None => eyre!("blob of rollup `{}` is not signed", blob.rollup_id()),
+            Some(signature) if signature.verification_key() != trusted_key => eyre!(
+                "blob of rollup `{}` is signed by {} key `{}` instead of its trusted {} key `{}`",
+                blob.rollup_id(),
+                signature.scheme(),
+                hex::encode(signature.verification_key().as_bytes()),
+                trusted_key.scheme(),
+                hex::encode(trusted_key.as_bytes()),
+            ),
+            Some(_) => return Ok(blob),
+        };
+        return Err(VerificationFailure {
+            stage: FailureStage::Signature,
+            error,
+        });
+    }
+    Ok(blob)
+}
+
+#[cfg(test)]
+mod tests {
+    use astria_core::{
+        brotli::compress_bytes,
+        protocol::test_utils::ConfigureSequencerBlock,
+    };
+    use ed25519_consensus::SigningKey;
+    use prost::Message as _;
+
+    use super::*;
+
+    const ROLLUP_ID: RollupId = RollupId::new([2; 32]);
+
+    fn blobs() -> (CelestiaSequencerBlob, CelestiaRollupBlob) {
+        let (sequencer_blob, mut rollup_blobs) = ConfigureSequencerBlock {
+            chain_id: Some("sequencer-test".to_string()),
+            sequence_data: vec![(ROLLUP_ID, vec![1, 2, 3])],
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        (sequencer_blob, rollup_blobs.pop().unwrap())
+    }
+
+    #[test]
+    fn blobs_as_written_by_the_relayer_are_verified() {
+        let (sequencer_blob, rollup_blob) = blobs();
+        let options = VerifyOptions::default().expected_chain_id("sequencer-test");
+        for format in [BlobFormat::Protobuf, BlobFormat::Json] {
+            let bytes =
+                compress_bytes(&format.encode(&sequencer_blob.clone().into_raw()).unwrap())
+                    .unwrap();
+            let verified = verify_signed_namespace_data(&bytes, &options).unwrap();
+            assert!(matches!(verified, VerifiedData::SequencerBlob(_)));
+            assert_eq!(sequencer_blob.block_hash(), verified.sequencer_block_hash());
+
+            let bytes =
+                compress_bytes(&format.encode(&rollup_blob.clone().into_raw()).unwrap()).unwrap();
+            let verified = verify_signed_namespace_data(&bytes, &options).unwrap();
+            assert!(matches!(verified, VerifiedData::RollupBlob(_)));
+            assert_eq!(sequencer_blob.block_hash(), verified.sequencer_block_hash());
+        }
+    }
+
+    #[test]
+    fn sequencer_blob_of_another_chain_is_rejected() {
+        let (sequencer_blob, _) = blobs();
+        let bytes = compress_bytes(&sequencer_blob.into_raw().encode_to_vec()).unwrap();
+        let options = VerifyOptions::default().expected_chain_id("other-chain");
+        assert!(verify_signed_namespace_data(&bytes, &options).is_err());
+    }
+
+    #[test]
+    fn rollup_blob_must_be_signed_by_its_trusted_key() {
+        let (_, mut rollup_blob) = blobs();
+        let trusted_key = SigningKey::from([1; 32]);
+        let options =
+            VerifyOptions::default().trusted_key(ROLLUP_ID, trusted_key.verification_key());
+        let encode = |blob: &CelestiaRollupBlob| {
+            compress_bytes(&blob.clone().into_raw().encode_to_vec()).unwrap()
+        };
+
+        assert!(verify_signed_namespace_data(&encode(&rollup_blob), &options).is_err());
+
+        rollup_blob.sign(&SigningKey::from([2; 32]));
+        assert!(verify_signed_namespace_data(&encode(&rollup_blob), &options).is_err());
+
+        rollup_blob.sign(&trusted_key);
+        verify_signed_namespace_data(&encode(&rollup_blob), &options).unwrap();
+    }
+
+    #[test]
+    fn rollup_blob_signed_with_secp256k1_is_checked_against_its_trusted_key() {
+        let (_, mut rollup_blob) = blobs();
+        let trusted_key = k256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap();
+        let options = VerifyOptions::default().trusted_key(ROLLUP_ID, *trusted_key.verifying_key());
+        let encode = |blob: &CelestiaRollupBlob| {
+            compress_bytes(&blob.clone().into_raw().encode_to_vec()).unwrap()
+        };
+
+        // The same secret as ed25519 key is a different key.
+        rollup_blob.sign(&SigningKey::from([1; 32]));
+        assert!(verify_signed_namespace_data(&encode(&rollup_blob), &options).is_err());
+
+        rollup_blob.sign(&trusted_key);
+        verify_signed_namespace_data(&encode(&rollup_blob), &options).unwrap();
+    }
+
+    #[test]
+    fn empty_blocks_marker_is_verified_against_the_expected_chain() {
+        let empty_block = |height| {
+            ConfigureSequencerBlock {
+                chain_id: Some("sequencer-test".to_string()),
+                height,
+                ..ConfigureSequencerBlock::default()
+            }
+            .make()
+            .into_celestia_blobs()
+            .0
+        };
+        let marker = CelestiaEmptyBlocksMarker::new(vec![empty_block(2), empty_block(3)]).unwrap();
+        let bytes = compress_bytes(&marker.into_raw().encode_to_vec()).unwrap();
+
+        let options = VerifyOptions::default().expected_chain_id("sequencer-test");
+        let empty_blocks = verify_empty_blocks_marker(&bytes, &options).unwrap();
+        assert_eq!(2, empty_blocks.len());
+        assert!(verify_signed_namespace_data(&bytes, &options).is_err());
+
+        let options = VerifyOptions::default().expected_chain_id("other-chain");
+        assert!(verify_empty_blocks_marker(&bytes, &options).is_err());
+    }
+
+    #[test]
+    fn verify_all_reports_the_stage_every_blob_failed_at() {
+        let (sequencer_blob, rollup_blob) = blobs();
+        let (other_chain_blob, _) = ConfigureSequencerBlock {
+            chain_id: Some("other-chain".to_string()),
+            ..ConfigureSequencerBlock::default()
+        }
+        .make()
+        .into_celestia_blobs();
+        let trusted_key = SigningKey::from([1; 32]);
+        let mut signed_rollup_blob = rollup_blob.clone();
+        signed_rollup_blob.sign(&trusted_key);
+
+        let mut tampered = rollup_blob.clone().into_raw();
+        tampered.transactions.push(vec![4]);
+        let mut malformed_key = signed_rollup_blob.clone().into_raw();
+        malformed_key.verification_key.truncate(3);
+        let encode = |raw: Vec<u8>| compress_bytes(&raw).unwrap();
+        let blobs = [
+            encode(sequencer_blob.into_raw().encode_to_vec()),
+            b"not a blob".to_vec(),
+            encode(tampered.encode_to_vec()),
+            encode(other_chain_blob.into_raw().encode_to_vec()),
+            encode(malformed_key.encode_to_vec()),
+            encode(rollup_blob.into_raw().encode_to_vec()),
+            encode(signed_rollup_blob.into_raw().encode_to_vec()),
+        ];
+
+        let options = VerifyOptions::default()
+            .expected_chain_id("sequencer-test")
+            .trusted_key(ROLLUP_ID, trusted_key.verification_key());
+        let stages: Vec<_> = verify_all(&blobs, &options)
+            .iter()
+            .map(|outcome| outcome.result().err().map(VerificationFailure::stage))
+            .collect();
+        assert_eq!(
+            vec![
+                None,
+                Some(FailureStage::Decode),
+                Some(FailureStage::Hash),
+                Some(FailureStage::ChainId),
+                Some(FailureStage::KeyParse),
+                Some(FailureStage::Signature),
+                None,
+            ],
+            stages,
+        );
+    }
+
+    #[test]
+    fn oversized_blobs_are_rejected() {
+        let (sequencer_blob, rollup_blob) = blobs();
+        let raw = sequencer_blob.into_raw().encode_to_vec();
+        let bytes = compress_bytes(&raw).unwrap();
+        // The limit applies after decompression, even if the compressed blob is within it.
+        let options = VerifyOptions::default().max_blob_size(raw.len() - 1);
+        assert!(verify_signed_namespace_data(&bytes, &options).is_err());
+
+        let bytes = compress_bytes(&rollup_blob.into_raw().encode_to_vec()).unwrap();
+        let options = VerifyOptions::default().max_rollup_txs_per_blob(0);
+        assert!(verify_signed_namespace_data(&bytes, &options).is_err());
+    }
+}
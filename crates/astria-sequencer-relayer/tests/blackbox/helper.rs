@@ -30,7 +30,13 @@ use astria_core::{
     sequencerblock::v1alpha1::SequencerBlock,
 };
 use astria_sequencer_relayer::{
-    config::Config,
+    config::{
+        Config,
+        EmptyBlockPolicy,
+        RollupQuotaPolicy,
+        StorageBackend,
+    },
+    secret::SecretString,
     SequencerRelayer,
     ShutdownHandle,
 };
@@ -391,11 +397,13 @@ impl TestSequencerRelayerConfig {
             celestia_app_grpc_endpoint: format!("http://{celestia_addr}"),
             celestia_app_key_file: celestia_keyfile.path().to_string_lossy().to_string(),
             block_time: 1000,
+            pull_blocks_from_cometbft: false,
             relay_only_validator_key_blocks: self.relay_only_self,
             validator_key_file: validator_keyfile.path().to_string_lossy().to_string(),
             only_include_rollups,
             api_addr: "0.0.0.0:0".into(),
             log: String::new(),
+            trace_sampling: String::new(),
             force_stdout: false,
             no_otel: false,
             no_metrics: false,
@@ -403,11 +411,52 @@ impl TestSequencerRelayerConfig {
             pretty_print: true,
             pre_submit_path: pre_submit_file.path().to_owned(),
             post_submit_path: post_submit_file.path().to_owned(),
+            storage_backend: StorageBackend::File,
+            storage_rocksdb_path: String::new(),
+            storage_postgres_url: SecretString::default(),
+            leader_election_lease_secs: 0,
+            leader_election_instance_id: String::new(),
+            celestia_resubmit_after_blocks: 10,
+            celestia_max_resubmissions: 3,
+            celestia_mempool_ttl_blocks: 5,
+            celestia_confirmation_depth: 0,
+            celestia_max_stale_height_secs: 60,
+            submission_deadline_blocks: 0,
+            submission_deadline_fee_bump_percent: 0,
+            celestia_error_classes: String::new(),
+            max_concurrent_submissions: 1,
+            batching_window_min_ms: 0,
+            batching_window_max_ms: 0,
+            batching_min_blobs: 128,
+            batching_max_blobs: 128,
+            additional_sequencer_networks_file: String::new(),
+            celestia_sequencer_namespace: String::new(),
+            celestia_blob_format: astria_core::sequencerblock::v1alpha1::BlobFormat::Protobuf,
+            empty_block_policy: EmptyBlockPolicy::Post,
+            rollup_blob_dedup_capacity: 0,
+            heartbeat_interval_celestia_blocks: 0,
+            heartbeat_celestia_namespace: String::new(),
+            heartbeat_key_file: String::new(),
+            rollup_signing_keys: String::new(),
+            rollup_quotas: String::new(),
+            rollup_quota_policy: RollupQuotaPolicy::Reject,
+            alert_notifier: "noop".to_string(),
+            alert_webhook_url: SecretString::default(),
+            alert_pagerduty_routing_key: SecretString::default(),
+            alert_submission_failure_threshold: 10,
+            alert_min_interval_secs: 300,
+            known_signers: String::new(),
+            cost_ledger_path: String::new(),
+            height_index_path: String::new(),
+            attestation_path: String::new(),
+            attestation_key_file: String::new(),
+            sequencer_height_lag_warn_threshold: 0,
+            sequencer_height_lag_error_threshold: 0,
         };
 
         info!(config = serde_json::to_string(&config).unwrap());
         let (sequencer_relayer, relayer_shutdown_handle) =
-            SequencerRelayer::new(config.clone()).unwrap();
+            SequencerRelayer::new(config.clone(), None).unwrap();
         let api_address = sequencer_relayer.local_addr();
         let sequencer_relayer = tokio::task::spawn(sequencer_relayer.run());
 
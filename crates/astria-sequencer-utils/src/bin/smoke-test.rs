@@ -0,0 +1,21 @@
+use std::process::ExitCode;
+
+use astria_sequencer_utils::smoke_test::{
+    self,
+    Config,
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    astria_eyre::install()
+        .expect("the astria eyre install hook must be called before eyre reports are constructed");
+    let config = Config::get();
+
+    match smoke_test::run(config).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("smoke test failed: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
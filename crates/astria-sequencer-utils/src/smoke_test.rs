@@ -0,0 +1,202 @@
+//! An end-to-end smoke test of writing sequencer blocks to and reading them back from Celestia.
+//!
+//! A synthetic sequencer block is taken through every step the relayer and conductor perform:
+//! it is assembled into blobs, submitted to a Celestia node, retrieved from it, verified, and
+//! the retrieved blobs are checked to be equal to the ones that were submitted. Any failing step
+//! fails the test, so that deployment pipelines can run it against a devnet.
+
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use astria_celestia_client::{
+    celestia_namespace_v0_from_rollup_id,
+    celestia_namespace_v0_from_str,
+    celestia_types::blob::SubmitOptions,
+    client::SequencerBlobFilter,
+    CelestiaClientExt as _,
+    CelestiaRollupBlob,
+    ClientBuilder,
+    TracingSink,
+};
+use astria_core::{
+    primitive::v1::{
+        BlockHash,
+        RollupId,
+    },
+    protocol::test_utils::ConfigureSequencerBlock,
+    sequencerblock::v1alpha1::SequencerBlock,
+};
+use astria_eyre::eyre::{
+    self,
+    ensure,
+    eyre,
+    WrapErr as _,
+};
+use clap::Parser;
+use sha2::{
+    Digest as _,
+    Sha256,
+};
+
+/// The rollups the synthetic sequencer block carries data for.
+const ROLLUP_NAMES: [&str; 2] = ["smoke-test-rollup-1", "smoke-test-rollup-2"];
+
+#[derive(Debug, Parser)]
+pub struct Config {
+    /// The URL of the Celestia node's JSON-RPC API.
+    #[clap(long)]
+    pub celestia_node_endpoint: String,
+
+    /// The auth token of the Celestia node. Must allow submitting blobs.
+    #[clap(long)]
+    pub celestia_bearer_token: String,
+
+    /// The chain ID of the synthetic sequencer block, which determines the namespace it is
+    /// written to. Should not be the chain ID of a real sequencer network.
+    #[clap(long, default_value = "smoke-test")]
+    pub sequencer_chain_id: String,
+}
+
+impl Config {
+    #[must_use]
+    pub fn get() -> Self {
+        Config::parse()
+    }
+}
+
+/// Runs the smoke test against the Celestia node configured in `config`.
+///
+/// # Errors
+///
+/// Returns an error if any step of the test failed.
+pub async fn run(config: Config) -> eyre::Result<()> {
+    let Config {
+        celestia_node_endpoint,
+        celestia_bearer_token,
+        sequencer_chain_id,
+    } = config;
+    let client = ClientBuilder::new(celestia_node_endpoint)
+        .bearer_token(celestia_bearer_token)
+        .build()
+        .wrap_err("failed constructing Celestia JSONRPC HTTP Client")?;
+    let events = TracingSink::default();
+
+    println!("assembling synthetic sequencer block");
+    let block = synthetic_block(&sequencer_chain_id)?;
+    let (expected_sequencer_blob, mut expected_rollup_blobs) = block.clone().into_celestia_blobs();
+    println!(
+        "assembled sequencer block `{}` with {} rollup blobs",
+        BlockHash::new(block.block_hash()),
+        expected_rollup_blobs.len(),
+    );
+
+    println!("submitting sequencer block to Celestia");
+    let submission = client
+        .submit_sequencer_block(block, SubmitOptions::default(), &events)
+        .await
+        .wrap_err("failed submitting sequencer block to Celestia")?;
+    let celestia_height = submission.height;
+    println!(
+        "sequencer block was included at Celestia height {celestia_height} in {} blobs",
+        submission.blobs.len(),
+    );
+
+    println!("retrieving and verifying sequencer blob");
+    let sequencer_namespace = celestia_namespace_v0_from_str(&sequencer_chain_id);
+    let mut response = client
+        .get_sequencer_blobs(
+            celestia_height,
+            sequencer_namespace,
+            &SequencerBlobFilter::default(),
+            &events,
+        )
+        .await
+        .wrap_err("failed retrieving sequencer blobs from Celestia")?;
+    let sequencer_blobs = response.take_ordered_sequencer_blobs();
+    let sequencer_blob = sequencer_blobs
+        .get(expected_sequencer_blob.height().value())
+        .iter()
+        .find(|blob| blob.block_hash() == expected_sequencer_blob.block_hash())
+        .cloned()
+        .ok_or_else(|| {
+            eyre!(
+                "the submitted sequencer blob was not found or failed verification at Celestia \
+                 height {celestia_height}"
+            )
+        })?;
+
+    println!("retrieving and verifying rollup blobs");
+    let mut rollup_blobs = Vec::new();
+    for expected in &expected_rollup_blobs {
+        let rollup_id = expected.rollup_id();
+        ensure!(
+            sequencer_blob.contains_rollup_id(rollup_id),
+            "retrieved sequencer blob does not commit to rollup `{rollup_id}`"
+        );
+        let mut blobs = client
+            .get_rollup_blobs_matching_sequencer_blob(
+                celestia_height,
+                celestia_namespace_v0_from_rollup_id(rollup_id),
+                &sequencer_blob,
+                &events,
+            )
+            .await
+            .wrap_err_with(|| {
+                format!("failed retrieving blobs of rollup `{rollup_id}` from Celestia")
+            })?;
+        ensure!(
+            blobs.len() == 1,
+            "expected exactly 1 verified blob for rollup `{rollup_id}`, found {}",
+            blobs.len(),
+        );
+        rollup_blobs.append(&mut blobs);
+    }
+
+    println!("checking reconstructed blobs against submitted blobs");
+    ensure!(
+        sequencer_blob.into_raw() == expected_sequencer_blob.into_raw(),
+        "retrieved sequencer blob differs from the submitted one"
+    );
+    rollup_blobs.sort_by_key(CelestiaRollupBlob::rollup_id);
+    expected_rollup_blobs.sort_by_key(CelestiaRollupBlob::rollup_id);
+    for (retrieved, expected) in rollup_blobs.into_iter().zip(expected_rollup_blobs) {
+        let rollup_id = expected.rollup_id();
+        ensure!(
+            retrieved.into_raw() == expected.into_raw(),
+            "retrieved blob of rollup `{rollup_id}` differs from the submitted one"
+        );
+    }
+    println!("smoke test passed");
+    Ok(())
+}
+
+/// Returns a sequencer block with data for every rollup in [`ROLLUP_NAMES`].
+///
+/// The block is derived from the current time so that every run writes a distinct block.
+fn synthetic_block(chain_id: &str) -> eyre::Result<SequencerBlock> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .wrap_err("system time is before the unix epoch")?;
+    let height = u32::try_from(now.as_secs() % u64::from(u32::MAX))
+        .expect("the remainder of dividing by u32::MAX fits into u32");
+    let block_hash: [u8; 32] = Sha256::digest(now.as_nanos().to_le_bytes()).into();
+    let sequence_data = ROLLUP_NAMES
+        .iter()
+        .map(|name| {
+            (
+                RollupId::from_unhashed_bytes(name),
+                format!("{name} smoke test data at {}", now.as_nanos()).into_bytes(),
+            )
+        })
+        .collect();
+    Ok(ConfigureSequencerBlock {
+        block_hash: Some(block_hash),
+        chain_id: Some(chain_id.to_string()),
+        height: height.max(1),
+        sequence_data,
+        ..ConfigureSequencerBlock::default()
+    }
+    .make())
+}
@@ -0,0 +1,275 @@
+//! A JSON gateway to the sequencer gRPC services.
+//!
+//! Browser based explorers and simple scripts cannot easily speak gRPC. The gateway accepts
+//! `POST /json/<package>.<service>/<method>` requests with the JSON (proto3 JSON mapping)
+//! encoding of the request message as the body, and responds with the JSON encoding of the
+//! response message. It is served on the same address as the gRPC services.
+//!
+//! Failed calls are answered with an HTTP status derived from the gRPC status code and a body
+//! of the form `{"code": <grpc code>, "message": <message>}`.
+
+use std::{
+    convert::Infallible,
+    future::Future,
+    sync::Arc,
+    task::{
+        Context,
+        Poll,
+    },
+};
+
+use astria_core::generated::sequencerblock::v1alpha1::sequencer_service_server::SequencerService;
+use bytes::{
+    Bytes,
+    BytesMut,
+};
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+use tonic::{
+    body::BoxBody,
+    codegen::{
+        http::{
+            self,
+            header,
+            Method,
+            StatusCode,
+        },
+        Body as _,
+        BoxFuture,
+    },
+    server::NamedService,
+    transport::Body,
+    Code,
+    Request,
+    Response,
+    Status,
+};
+
+/// The maximum size of a request body accepted by the gateway.
+const MAX_REQUEST_BODY_SIZE: usize = 64 * 1024;
+
+const GET_SEQUENCER_BLOCK: &str =
+    "/json/astria.sequencerblock.v1alpha1.SequencerService/GetSequencerBlock";
+const GET_FILTERED_SEQUENCER_BLOCK: &str =
+    "/json/astria.sequencerblock.v1alpha1.SequencerService/GetFilteredSequencerBlock";
+
+/// Transcodes JSON requests to calls of a [`SequencerService`].
+pub(crate) struct JsonGateway<S> {
+    sequencer: Arc<S>,
+}
+
+impl<S> JsonGateway<S> {
+    pub(crate) fn new(sequencer: S) -> Self {
+        Self {
+            sequencer: Arc::new(sequencer),
+        }
+    }
+}
+
+impl<S> Clone for JsonGateway<S> {
+    fn clone(&self) -> Self {
+        Self {
+            sequencer: self.sequencer.clone(),
+        }
+    }
+}
+
+impl<S> NamedService for JsonGateway<S> {
+    const NAME: &'static str = "json";
+}
+
+impl<S: SequencerService> tower::Service<http::Request<Body>> for JsonGateway<S> {
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    type Response = http::Response<BoxBody>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let sequencer = self.sequencer.clone();
+        Box::pin(async move { Ok(handle(sequencer, request).await) })
+    }
+}
+
+async fn handle<S: SequencerService>(
+    sequencer: Arc<S>,
+    request: http::Request<Body>,
+) -> http::Response<BoxBody> {
+    if request.method() != Method::POST {
+        return error_response(&Status::unimplemented("only POST requests are supported"));
+    }
+    let path = request.uri().path().to_string();
+    let body = match read_body(request.into_body()).await {
+        Ok(body) => body,
+        Err(status) => return error_response(&status),
+    };
+    match path.as_str() {
+        GET_SEQUENCER_BLOCK => {
+            unary(body, |request| sequencer.get_sequencer_block(request)).await
+        }
+        GET_FILTERED_SEQUENCER_BLOCK => {
+            unary(body, |request| sequencer.get_filtered_sequencer_block(request)).await
+        }
+        _ => error_response(&Status::unimplemented(format!("unknown method `{path}`"))),
+    }
+}
+
+async fn read_body(mut body: Body) -> Result<Bytes, Status> {
+    let mut bytes = BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        let chunk =
+            chunk.map_err(|e| Status::invalid_argument(format!("failed reading body: {e}")))?;
+        if bytes.len() + chunk.len() > MAX_REQUEST_BODY_SIZE {
+            return Err(Status::invalid_argument(format!(
+                "request body exceeds the maximum of {MAX_REQUEST_BODY_SIZE} bytes"
+            )));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.freeze())
+}
+
+async fn unary<Req, Rsp, F, Fut>(body: Bytes, call: F) -> http::Response<BoxBody>
+where
+    Req: DeserializeOwned,
+    Rsp: Serialize,
+    F: FnOnce(Request<Req>) -> Fut,
+    Fut: Future<Output = Result<Response<Rsp>, Status>>,
+{
+    // An empty body is the JSON encoding of a message with all fields at their defaults.
+    let body = if body.is_empty() {
+        Bytes::from_static(b"{}")
+    } else {
+        body
+    };
+    let request: Req = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return error_response(&Status::invalid_argument(format!(
+                "failed decoding request body as JSON: {e}"
+            )));
+        }
+    };
+    match call(Request::new(request)).await {
+        Ok(response) => match serde_json::to_vec(response.get_ref()) {
+            Ok(json) => json_response(StatusCode::OK, json),
+            Err(e) => error_response(&Status::internal(format!(
+                "failed encoding response as JSON: {e}"
+            ))),
+        },
+        Err(status) => error_response(&status),
+    }
+}
+
+fn error_response(status: &Status) -> http::Response<BoxBody> {
+    #[derive(Serialize)]
+    struct Error<'a> {
+        code: i32,
+        message: &'a str,
+    }
+    let json = serde_json::to_vec(&Error {
+        code: status.code() as i32,
+        message: status.message(),
+    })
+    .expect("serializing a struct of an integer and a string to JSON cannot fail");
+    json_response(http_status(status.code()), json)
+}
+
+fn json_response(status: StatusCode, json: Vec<u8>) -> http::Response<BoxBody> {
+    http::Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(tonic::body::boxed(Body::from(json)))
+        .expect("the status and content type header are always valid")
+}
+
+/// Maps a gRPC status code to an HTTP status code as specified by the gRPC-HTTP mapping.
+fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::Cancelled => StatusCode::REQUEST_TIMEOUT,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use astria_core::protocol::test_utils::ConfigureSequencerBlock;
+    use cnidarium::StateDelta;
+    use tower::ServiceExt as _;
+
+    use super::*;
+    use crate::{
+        api_state_ext::StateWriteExt as _,
+        grpc::sequencer::SequencerServer,
+        state_ext::StateWriteExt as _,
+    };
+
+    async fn gateway() -> (JsonGateway<SequencerServer>, cnidarium::TempStorage) {
+        let block = ConfigureSequencerBlock {
+            height: 1,
+            ..Default::default()
+        }
+        .make();
+        let storage = cnidarium::TempStorage::new().await.unwrap();
+        let mut state_tx = StateDelta::new(storage.latest_snapshot());
+        state_tx.put_block_height(1);
+        state_tx.put_sequencer_block(block).unwrap();
+        storage.commit(state_tx).await.unwrap();
+        (JsonGateway::new(SequencerServer::new(storage.clone())), storage)
+    }
+
+    async fn post(
+        gateway: JsonGateway<SequencerServer>,
+        path: &str,
+        body: &'static str,
+    ) -> (StatusCode, serde_json::Value) {
+        let request = http::Request::post(path).body(Body::from(body)).unwrap();
+        let response = gateway.oneshot(request).await.unwrap();
+        let status = response.status();
+        let mut body = response.into_body();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.data().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+        }
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn sequencer_block_is_returned_as_json() {
+        let (gateway, _storage) = gateway().await;
+        let (status, json) = post(gateway, GET_SEQUENCER_BLOCK, r#"{"height": "1"}"#).await;
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!("1", json["header"]["height"]);
+    }
+
+    #[tokio::test]
+    async fn invalid_request_is_rejected() {
+        let (gateway, _storage) = gateway().await;
+        let (status, json) = post(gateway, GET_SEQUENCER_BLOCK, r#"{"height": "two"}"#).await;
+        assert_eq!(StatusCode::BAD_REQUEST, status);
+        assert_eq!(Code::InvalidArgument as i32, json["code"]);
+    }
+
+    #[tokio::test]
+    async fn unknown_method_is_rejected() {
+        let (gateway, _storage) = gateway().await;
+        let (status, _) = post(gateway, "/json/astria.Unknown/Method", "{}").await;
+        assert_eq!(StatusCode::NOT_IMPLEMENTED, status);
+    }
+}
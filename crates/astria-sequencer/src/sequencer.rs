@@ -31,7 +31,10 @@ use tracing::{
 use crate::{
     app::App,
     config::Config,
-    grpc::sequencer::SequencerServer,
+    grpc::{
+        json::JsonGateway,
+        sequencer::SequencerServer,
+    },
     ibc::host_interface::AstriaHost,
     service,
     state_ext::StateReadExt as _,
@@ -172,6 +175,7 @@ fn start_grpc_server(
 
     let ibc = penumbra_ibc::component::rpc::IbcQuery::<AstriaHost>::new(storage.clone());
     let sequencer_api = SequencerServer::new(storage.clone());
+    let json_gateway = JsonGateway::new(SequencerServer::new(storage.clone()));
     let cors_layer: CorsLayer = CorsLayer::permissive();
 
     // TODO: setup HTTPS?
@@ -195,7 +199,10 @@ fn start_grpc_server(
         .add_service(ClientQueryServer::new(ibc.clone()))
         .add_service(ChannelQueryServer::new(ibc.clone()))
         .add_service(ConnectionQueryServer::new(ibc.clone()))
-        .add_service(SequencerServiceServer::new(sequencer_api));
+        .add_service(SequencerServiceServer::new(sequencer_api))
+        // Transcodes JSON requests so that browsers and scripts can query the sequencer
+        // service without a gRPC stack.
+        .add_service(json_gateway);
 
     info!(grpc_addr = grpc_addr.to_string(), "starting grpc server");
     tokio::task::spawn(
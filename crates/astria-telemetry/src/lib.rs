@@ -14,6 +14,11 @@ use std::{
         AddrParseError,
         SocketAddr,
     },
+    sync::{
+        Arc,
+        PoisonError,
+        RwLock,
+    },
 };
 
 use metrics_exporter_prometheus::{
@@ -26,7 +31,11 @@ use opentelemetry::{
 };
 use opentelemetry_sdk::{
     runtime::Tokio,
-    trace::TracerProvider,
+    trace::{
+        self,
+        Sampler,
+        TracerProvider,
+    },
 };
 use opentelemetry_stdout::SpanExporter;
 use tracing_subscriber::{
@@ -35,15 +44,24 @@ use tracing_subscriber::{
         ParseError,
     },
     layer::SubscriberExt as _,
+    reload,
     util::{
         SubscriberInitExt as _,
         TryInitError,
     },
     EnvFilter,
+    Registry,
+};
+
+use crate::sampling::{
+    ModuleSampler,
+    SamplingRatios,
 };
 
 #[cfg(feature = "display")]
 pub mod display;
+pub mod metrics;
+mod sampling;
 
 /// The errors that can occur when initializing telemtry.
 #[derive(Debug, thiserror::Error)]
@@ -59,6 +77,16 @@ impl Error {
         Self(ErrorKind::FilterDirectives(source))
     }
 
+    fn trace_sampling(directive: &str) -> Self {
+        Self(ErrorKind::TraceSampling {
+            directive: directive.to_string(),
+        })
+    }
+
+    fn reload_filter(source: reload::Error) -> Self {
+        Self(ErrorKind::ReloadFilter(source))
+    }
+
     fn init_subscriber(source: TryInitError) -> Self {
         Self(ErrorKind::InitSubscriber(source))
     }
@@ -82,6 +110,13 @@ enum ErrorKind {
     Otlp(#[source] opentelemetry::trace::TraceError),
     #[error("failed to parse filter directives")]
     FilterDirectives(#[source] ParseError),
+    #[error(
+        "failed to parse trace sampling directive `{directive}`; expected `<ratio>` or \
+         `<module>=<ratio>` with a ratio between 0 and 1"
+    )]
+    TraceSampling { directive: String },
+    #[error("failed accessing the filter of the installed tracing subscriber")]
+    ReloadFilter(#[source] reload::Error),
     #[error("failed installing global tracing subscriber")]
     InitSubscriber(#[source] TryInitError),
     #[error("failed to parse metrics address")]
@@ -96,6 +131,66 @@ enum ErrorKind {
 pub fn configure() -> Config {
     Config::new()
 }
+
+/// A handle to the installed telemetry, returned by [`Config::try_init`].
+///
+/// Allows adjusting the filter directives and the per-module trace sampling ratios while the
+/// service is running.
+#[derive(Clone, Debug)]
+pub struct Handle {
+    filter: reload::Handle<EnvFilter, Registry>,
+    sampling: Arc<RwLock<SamplingRatios>>,
+}
+
+impl Handle {
+    /// Returns the filter directives currently in effect.
+    ///
+    /// # Errors
+    /// Fails if the tracing subscriber the filter was installed in was dropped.
+    pub fn filter_directives(&self) -> Result<String, Error> {
+        self.filter
+            .with_current(ToString::to_string)
+            .map_err(Error::reload_filter)
+    }
+
+    /// Replaces the filter directives, applying them to all spans and events from now on.
+    ///
+    /// # Errors
+    /// Fails if `filter_directives` could not be parsed or if the tracing subscriber the filter
+    /// was installed in was dropped. The current filter is kept in either case.
+    pub fn set_filter_directives(&self, filter_directives: &str) -> Result<(), Error> {
+        let env_filter = parse_filter_directives(filter_directives)?;
+        self.filter
+            .reload(env_filter)
+            .map_err(Error::reload_filter)
+    }
+
+    /// Returns the trace sampling directives currently in effect.
+    #[must_use]
+    pub fn trace_sampling(&self) -> String {
+        self.sampling
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_string()
+    }
+
+    /// Replaces the trace sampling directives, applying them to all traces started from now on.
+    ///
+    /// # Errors
+    /// Fails if `trace_sampling` could not be parsed. The current ratios are kept in that case.
+    pub fn set_trace_sampling(&self, trace_sampling: &str) -> Result<(), Error> {
+        let ratios = SamplingRatios::parse(trace_sampling)?;
+        *self.sampling.write().unwrap_or_else(PoisonError::into_inner) = ratios;
+        Ok(())
+    }
+}
+
+fn parse_filter_directives(filter_directives: &str) -> Result<EnvFilter, Error> {
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .parse(filter_directives)
+        .map_err(Error::filter_directives)
+}
 struct BoxedMakeWriter(Box<dyn MakeWriter + Send + Sync + 'static>);
 
 impl BoxedMakeWriter {
@@ -129,6 +224,7 @@ impl MakeWriter for BoxedMakeWriter {
 
 pub struct Config {
     filter_directives: String,
+    trace_sampling: String,
     force_stdout: bool,
     no_otel: bool,
     pretty_print: bool,
@@ -143,6 +239,7 @@ impl Config {
     fn new() -> Self {
         Self {
             filter_directives: String::new(),
+            trace_sampling: String::new(),
             force_stdout: false,
             no_otel: false,
             pretty_print: false,
@@ -163,6 +260,16 @@ impl Config {
         }
     }
 
+    /// Sets the trace sampling directives, see [`Handle::set_trace_sampling`]. All traces are
+    /// sampled by default.
+    #[must_use = "telemetry must be initialized to be useful"]
+    pub fn trace_sampling(self, trace_sampling: &str) -> Self {
+        Self {
+            trace_sampling: trace_sampling.to_string(),
+            ..self
+        }
+    }
+
     #[must_use = "telemetry must be initialized to be useful"]
     pub fn force_stdout(self) -> Self {
         self.set_force_stdout(true)
@@ -239,12 +346,15 @@ impl Config {
 
     /// Initialize telemetry, consuming the config.
     ///
+    /// Returns a [`Handle`] to adjust the filter directives and trace sampling afterwards.
+    ///
     /// # Errors
-    /// Fails if the filter directives could not be parsed, if communication with the OTLP
-    /// endpoint failed, or if the global tracing subscriber could not be installed.
-    pub fn try_init(self) -> Result<(), Error> {
+    /// Fails if the filter or trace sampling directives could not be parsed, if communication
+    /// with the OTLP endpoint failed, or if the global tracing subscriber could not be installed.
+    pub fn try_init(self) -> Result<Handle, Error> {
         let Self {
             filter_directives,
+            trace_sampling,
             force_stdout,
             no_otel,
             pretty_print,
@@ -254,14 +364,14 @@ impl Config {
             metric_buckets,
         } = self;
 
-        let env_filter = {
-            let builder = EnvFilter::builder().with_default_directive(LevelFilter::INFO.into());
-            builder
-                .parse(filter_directives)
-                .map_err(Error::filter_directives)?
-        };
+        let env_filter = parse_filter_directives(&filter_directives)?;
+        let sampling = Arc::new(RwLock::new(SamplingRatios::parse(&trace_sampling)?));
 
-        let mut tracer_provider = TracerProvider::builder();
+        let mut tracer_provider = TracerProvider::builder().with_config(
+            trace::config().with_sampler(Sampler::ParentBased(Box::new(ModuleSampler::new(
+                sampling.clone(),
+            )))),
+        );
         if !no_otel {
             // XXX: the endpoint is set by a hardcoded environment variable. This is a
             //      full list of variables that opentelementry_otlp currently reads:
@@ -301,10 +411,13 @@ impl Config {
         let _ = global::set_tracer_provider(tracer_provider);
 
         let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+        // The reloadable filter is layered directly on the registry so that its handle does not
+        // need to name the types of the other layers. It still filters globally.
+        let (env_filter, filter) = reload::Layer::new(env_filter);
         tracing_subscriber::registry()
+            .with(env_filter)
             .with(otel_layer)
             .with(pretty_printer)
-            .with(env_filter)
             .try_init()
             .map_err(Error::init_subscriber)?;
 
@@ -313,7 +426,8 @@ impl Config {
             let mut metrics_builder = PrometheusBuilder::new().with_http_listener(addr);
 
             if !service_name.is_empty() {
-                metrics_builder = metrics_builder.add_global_label("service", service_name);
+                metrics_builder =
+                    metrics_builder.add_global_label(metrics::SERVICE_LABEL, service_name);
             }
 
             if let Some(buckets) = metric_buckets {
@@ -325,6 +439,9 @@ impl Config {
             metrics_builder.install().map_err(Error::exporter_install)?;
         }
 
-        Ok(())
+        Ok(Handle {
+            filter,
+            sampling,
+        })
     }
 }
@@ -0,0 +1,46 @@
+//! Naming and labeling conventions shared by the metrics of all astria services.
+//!
+//! Dashboards and alerts are written once for all services, so their metrics follow the same
+//! rules:
+//!
+//! - Metric names are snake case and prefixed by the name of the crate that emits them, for
+//!   example `astria_sequencer_relayer_celestia_submission_count`. Use [`metric_name!`] to
+//!   construct them, which adds the prefix of the calling crate.
+//! - Histograms of durations end in `_latency` and are recorded in the unit given to
+//!   `describe_histogram!`. Names do not repeat the unit.
+//! - Every metric carries the `service` label with the package name of the service, which is
+//!   added by [`crate::Config::service_name`]. It is not set by hand.
+//! - Labels that mean the same thing in several services use the keys defined in this module.
+//!   Their values are lower snake case, except for identifiers like rollup IDs which are
+//!   emitted in their [`std::fmt::Display`] form.
+//!
+//! [`metric_name!`]: crate::metric_name
+
+/// The label carrying the name of the service that emitted a metric.
+pub const SERVICE_LABEL: &str = "service";
+
+/// The label carrying the rollup ID a metric refers to.
+pub const ROLLUP_ID_LABEL: &str = "rollup_id";
+
+/// The label carrying the outcome of the operation a metric counts, like `verified`.
+pub const RESULT_LABEL: &str = "result";
+
+/// The label carrying why an item was rejected or dropped.
+pub const REASON_LABEL: &str = "reason";
+
+/// Constructs the name of a metric of the calling crate.
+///
+/// Expands to a `&'static str` of the crate name followed by an underscore and `$name`, so it
+/// can be used to define constants.
+///
+/// # Examples
+/// ```
+/// const SUBMISSION_COUNT: &str = astria_telemetry::metric_name!("submission_count");
+/// assert!(SUBMISSION_COUNT.ends_with("_submission_count"));
+/// ```
+#[macro_export]
+macro_rules! metric_name {
+    ($name:literal) => {
+        concat!(env!("CARGO_CRATE_NAME"), "_", $name)
+    };
+}
@@ -0,0 +1,143 @@
+//! Trace sampling with ratios that are set per module and can be adjusted at runtime.
+//!
+//! Sampling directives are written like filter directives: a comma separated list of
+//! `<module>=<ratio>` entries, plus an optional bare `<ratio>` applying to all other modules.
+//! For example, `0.1,astria_sequencer_relayer::relayer::write=1` samples every trace started in
+//! the relayer's write module and one in ten traces everywhere else. The ratio of the longest
+//! module matching the module a root span was created in applies; child spans follow the sampling
+//! decision of their parent.
+
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        PoisonError,
+        RwLock,
+    },
+};
+
+use opentelemetry::{
+    trace::{
+        Link,
+        SamplingResult,
+        SpanKind,
+        TraceId,
+    },
+    Context,
+    KeyValue,
+};
+use opentelemetry_sdk::trace::{
+    Sampler,
+    ShouldSample,
+};
+
+use crate::Error;
+
+/// The span attribute that `tracing-opentelemetry` records the module path of a span under.
+const CODE_NAMESPACE: &str = "code.namespace";
+
+/// The sampling ratios of all modules.
+#[derive(Debug)]
+pub(crate) struct SamplingRatios {
+    default: f64,
+    /// The ratios of individual modules, longest module first.
+    modules: Vec<(String, f64)>,
+}
+
+impl SamplingRatios {
+    /// Parses sampling directives. Without a bare `<ratio>` directive, all traces in modules
+    /// without a ratio of their own are sampled.
+    pub(crate) fn parse(directives: &str) -> Result<Self, Error> {
+        let mut default = 1.0;
+        let mut modules = Vec::new();
+        for directive in directives.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (module, ratio) = match directive.split_once('=') {
+                Some((module, ratio)) => (Some(module.trim()), ratio.trim()),
+                None => (None, directive),
+            };
+            let ratio: f64 = ratio
+                .parse()
+                .ok()
+                .filter(|ratio| (0.0..=1.0).contains(ratio))
+                .ok_or_else(|| Error::trace_sampling(directive))?;
+            match module {
+                Some("") => return Err(Error::trace_sampling(directive)),
+                Some(module) => modules.push((module.to_string(), ratio)),
+                None => default = ratio,
+            }
+        }
+        modules.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        Ok(Self {
+            default,
+            modules,
+        })
+    }
+
+    fn ratio(&self, module: Option<&str>) -> f64 {
+        let Some(module) = module else {
+            return self.default;
+        };
+        self.modules
+            .iter()
+            .find(|(prefix, _)| {
+                module
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map_or(self.default, |(_, ratio)| *ratio)
+    }
+}
+
+impl fmt::Display for SamplingRatios {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.default)?;
+        for (module, ratio) in &self.modules {
+            write!(f, ",{module}={ratio}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Samples root spans with the ratio of the module they were created in.
+#[derive(Clone, Debug)]
+pub(crate) struct ModuleSampler {
+    ratios: Arc<RwLock<SamplingRatios>>,
+}
+
+impl ModuleSampler {
+    pub(crate) fn new(ratios: Arc<RwLock<SamplingRatios>>) -> Self {
+        Self {
+            ratios,
+        }
+    }
+}
+
+impl ShouldSample for ModuleSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let module = attributes
+            .iter()
+            .find(|attribute| attribute.key.as_str() == CODE_NAMESPACE)
+            .map(|attribute| attribute.value.as_str());
+        let ratio = self
+            .ratios
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .ratio(module.as_deref());
+        Sampler::TraceIdRatioBased(ratio).should_sample(
+            parent_context,
+            trace_id,
+            name,
+            span_kind,
+            attributes,
+            links,
+        )
+    }
+}
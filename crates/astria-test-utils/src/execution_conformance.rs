@@ -0,0 +1,409 @@
+//! A conformance suite for implementations of the `astria.execution.v1alpha2.ExecutionService`.
+//!
+//! Rollup teams implementing the execution gRPC interface can run [`Suite`] against their node
+//! to check that it behaves the way conductor expects: it reads the genesis info and commitment
+//! state, looks up blocks, executes a block on top of the soft commitment, and advances first the
+//! soft and then the firm commitment to the executed block. Along the way it checks that invalid
+//! requests are rejected and that repeated commitment state updates are idempotent.
+//!
+//! The suite changes the state of the node under test. It must only be run against a node that
+//! can be thrown away afterwards.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn run() {
+//! use astria_core::generated::execution::v1alpha2::execution_service_client::ExecutionServiceClient;
+//! use astria_test_utils::execution_conformance::Suite;
+//!
+//! let client = ExecutionServiceClient::connect("http://127.0.0.1:50051")
+//!     .await
+//!     .unwrap();
+//! let report = Suite::new(client).run().await;
+//! println!("{report}");
+//! assert!(report.is_success());
+//! # }
+//! ```
+
+use std::fmt;
+
+use astria_core::{
+    execution::v1alpha2::{
+        Block,
+        CommitmentState,
+        GenesisInfo,
+    },
+    generated::{
+        execution::v1alpha2::{
+            self as raw,
+            execution_service_client::ExecutionServiceClient,
+        },
+        sequencerblock::v1alpha1::RollupData,
+    },
+    Protobuf as _,
+};
+use astria_eyre::eyre::{
+    self,
+    bail,
+    ensure,
+    WrapErr as _,
+};
+use bytes::Bytes;
+use pbjson_types::Timestamp;
+use tonic::{
+    transport::Channel,
+    Code,
+};
+
+/// The outcome of a single check.
+#[derive(Debug)]
+pub enum Outcome {
+    Passed,
+    Failed(eyre::Report),
+}
+
+/// A named check and its outcome.
+#[derive(Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: Outcome,
+}
+
+/// The outcomes of all checks run by a [`Suite`].
+///
+/// Checks that later checks depend on end the run if they fail, so a failed run does not
+/// necessarily contain every check.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// Returns if every check that was run passed.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Returns the checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &Check> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.outcome, Outcome::Failed(_)))
+    }
+
+    fn record<T>(&mut self, name: &'static str, result: eyre::Result<T>) -> Option<T> {
+        let (outcome, value) = match result {
+            Ok(value) => (Outcome::Passed, Some(value)),
+            Err(error) => (Outcome::Failed(error), None),
+        };
+        self.checks.push(Check {
+            name,
+            outcome,
+        });
+        value
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.outcome {
+                Outcome::Passed => writeln!(f, "PASS {}", check.name)?,
+                Outcome::Failed(error) => writeln!(f, "FAIL {}: {error:#}", check.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the conformance checks against an execution service.
+pub struct Suite {
+    client: ExecutionServiceClient<Channel>,
+    transactions: Vec<RollupData>,
+}
+
+impl Suite {
+    #[must_use]
+    pub fn new(client: ExecutionServiceClient<Channel>) -> Self {
+        Self {
+            client,
+            transactions: Vec::new(),
+        }
+    }
+
+    /// Includes `transactions` in the block executed by the suite.
+    ///
+    /// By default the executed block is empty. Nodes that reject empty blocks or that should be
+    /// exercised with actual transactions can be given valid rollup transactions here.
+    #[must_use]
+    pub fn transactions<I: IntoIterator<Item = RollupData>>(mut self, transactions: I) -> Self {
+        self.transactions.extend(transactions);
+        self
+    }
+
+    /// Runs all checks and returns their outcomes.
+    pub async fn run(mut self) -> Report {
+        let mut report = Report::default();
+
+        let result = self.get_genesis_info().await;
+        report.record("get_genesis_info", result);
+        let result = self.get_commitment_state().await;
+        let Some(initial) = report.record("get_commitment_state", result) else {
+            return report;
+        };
+        let result = self.get_block_by_number(initial.soft()).await;
+        report.record("get_block_by_number", result);
+        let result = self.get_block_by_hash(initial.soft()).await;
+        report.record("get_block_by_hash", result);
+        let result = self.get_unknown_block().await;
+        report.record("get_unknown_block", result);
+        let result = self.batch_get_blocks(&initial).await;
+        report.record("batch_get_blocks", result);
+        let result = self.execute_block_on_unknown_parent(initial.soft()).await;
+        report.record("execute_block_on_unknown_parent", result);
+
+        let result = self.execute_block(initial.soft()).await;
+        let Some(executed) = report.record("execute_block", result) else {
+            return report;
+        };
+        let result = self.get_executed_block(&executed).await;
+        report.record("get_executed_block", result);
+
+        let soft_committed = CommitmentState::builder()
+            .firm(initial.firm().clone())
+            .soft(executed.clone())
+            .build()
+            .expect("the executed block is above the firm block");
+        let result = self.update_commitment_state(&soft_committed).await;
+        if report.record("update_soft_commitment", result).is_none() {
+            return report;
+        }
+        let result = self.update_commitment_state(&soft_committed).await;
+        report.record("update_commitment_state_is_idempotent", result);
+        let result = self
+            .reject_firm_exceeding_soft(initial.soft(), &executed)
+            .await;
+        report.record("reject_firm_exceeding_soft", result);
+
+        let firm_committed = CommitmentState::builder()
+            .firm(executed.clone())
+            .soft(executed)
+            .build()
+            .expect("firm and soft are the same block");
+        let result = self.update_commitment_state(&firm_committed).await;
+        report.record("update_firm_commitment", result);
+        report
+    }
+
+    async fn get_genesis_info(&mut self) -> eyre::Result<GenesisInfo> {
+        let raw = self
+            .client
+            .get_genesis_info(raw::GetGenesisInfoRequest {})
+            .await
+            .wrap_err("GetGenesisInfo failed")?
+            .into_inner();
+        GenesisInfo::try_from_raw(raw).wrap_err("GetGenesisInfo returned invalid genesis info")
+    }
+
+    async fn get_commitment_state(&mut self) -> eyre::Result<CommitmentState> {
+        let raw = self
+            .client
+            .get_commitment_state(raw::GetCommitmentStateRequest {})
+            .await
+            .wrap_err("GetCommitmentState failed")?
+            .into_inner();
+        CommitmentState::try_from_raw(raw)
+            .wrap_err("GetCommitmentState returned an invalid commitment state")
+    }
+
+    async fn get_block(&mut self, request: raw::GetBlockRequest) -> eyre::Result<Block> {
+        let raw = self
+            .client
+            .get_block(request)
+            .await
+            .wrap_err("GetBlock failed")?
+            .into_inner();
+        Block::try_from_raw(raw).wrap_err("GetBlock returned an invalid block")
+    }
+
+    async fn get_block_by_number(&mut self, expected: &Block) -> eyre::Result<()> {
+        let request = raw::GetBlockRequest::builder()
+            .block_number(expected.number())
+            .build()?;
+        let block = self.get_block(request).await?;
+        ensure!(
+            &block == expected,
+            "GetBlock returned {block:?} for the number of the soft block {expected:?}"
+        );
+        Ok(())
+    }
+
+    async fn get_block_by_hash(&mut self, expected: &Block) -> eyre::Result<()> {
+        let request = raw::GetBlockRequest::builder()
+            .block_hash(expected.hash().clone())
+            .build()?;
+        let block = self.get_block(request).await?;
+        ensure!(
+            &block == expected,
+            "GetBlock returned {block:?} for the hash of the soft block {expected:?}"
+        );
+        Ok(())
+    }
+
+    async fn get_unknown_block(&mut self) -> eyre::Result<()> {
+        let request = raw::GetBlockRequest::builder()
+            .block_number(u32::MAX)
+            .build()?;
+        match self.client.get_block(request).await {
+            Ok(response) => bail!(
+                "GetBlock returned {:?} for a block number that cannot exist",
+                response.into_inner()
+            ),
+            Err(status) if status.code() == Code::NotFound => Ok(()),
+            Err(status) => bail!(
+                "GetBlock failed with `{}` instead of `{}` for an unknown block",
+                status.code(),
+                Code::NotFound,
+            ),
+        }
+    }
+
+    async fn batch_get_blocks(&mut self, state: &CommitmentState) -> eyre::Result<()> {
+        let request = raw::BatchGetBlocksRequest::builder()
+            .block_numbers([state.soft().number(), state.firm().number()])
+            .build()?;
+        let blocks = self
+            .client
+            .batch_get_blocks(request)
+            .await
+            .wrap_err("BatchGetBlocks failed")?
+            .into_inner()
+            .blocks
+            .into_iter()
+            .map(Block::try_from_raw)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err("BatchGetBlocks returned an invalid block")?;
+        let expected = [state.soft().clone(), state.firm().clone()];
+        ensure!(
+            blocks == expected,
+            "BatchGetBlocks returned {blocks:?} instead of the soft and firm blocks {expected:?} \
+             in the requested order"
+        );
+        Ok(())
+    }
+
+    fn execute_block_request(
+        &self,
+        parent: Bytes,
+        timestamp: Timestamp,
+    ) -> eyre::Result<raw::ExecuteBlockRequest> {
+        raw::ExecuteBlockRequest::builder()
+            .prev_block_hash(parent)
+            .transactions(self.transactions.clone())
+            .timestamp(timestamp)
+            .build()
+            .wrap_err("failed building execute block request")
+    }
+
+    async fn execute_block_on_unknown_parent(&mut self, soft: &Block) -> eyre::Result<()> {
+        // Inverting every byte yields a hash of the expected length that cannot be the soft hash.
+        let unknown_parent: Bytes = soft.hash().iter().map(|byte| !byte).collect();
+        let request = self.execute_block_request(unknown_parent, next_timestamp(soft))?;
+        match self.client.execute_block(request).await {
+            Ok(response) => bail!(
+                "ExecuteBlock returned {:?} for a parent block that does not exist",
+                response.into_inner()
+            ),
+            Err(_) => Ok(()),
+        }
+    }
+
+    async fn execute_block(&mut self, soft: &Block) -> eyre::Result<Block> {
+        let request = self.execute_block_request(soft.hash().clone(), next_timestamp(soft))?;
+        let raw = self
+            .client
+            .execute_block(request)
+            .await
+            .wrap_err("ExecuteBlock failed")?
+            .into_inner();
+        let block = Block::try_from_raw(raw).wrap_err("ExecuteBlock returned an invalid block")?;
+        ensure!(
+            Some(block.number()) == soft.number().checked_add(1),
+            "the executed block has number {} instead of {}",
+            block.number(),
+            u64::from(soft.number()) + 1,
+        );
+        ensure!(
+            block.parent_block_hash() == soft.hash(),
+            "the parent of the executed block is not the soft block"
+        );
+        Ok(block)
+    }
+
+    async fn get_executed_block(&mut self, executed: &Block) -> eyre::Result<()> {
+        let request = raw::GetBlockRequest::builder()
+            .block_hash(executed.hash().clone())
+            .build()?;
+        let block = self.get_block(request).await?;
+        ensure!(
+            &block == executed,
+            "GetBlock returned {block:?} for the executed block {executed:?}"
+        );
+        Ok(())
+    }
+
+    async fn update_commitment_state(&mut self, state: &CommitmentState) -> eyre::Result<()> {
+        let raw = self
+            .client
+            .update_commitment_state(raw::UpdateCommitmentStateRequest::from(state.clone()))
+            .await
+            .wrap_err("UpdateCommitmentState failed")?
+            .into_inner();
+        let returned = CommitmentState::try_from_raw(raw)
+            .wrap_err("UpdateCommitmentState returned an invalid commitment state")?;
+        ensure!(
+            &returned == state,
+            "UpdateCommitmentState returned {returned:?} instead of the requested {state:?}"
+        );
+        let current = self.get_commitment_state().await?;
+        ensure!(
+            &current == state,
+            "GetCommitmentState returned {current:?} after updating it to {state:?}"
+        );
+        Ok(())
+    }
+
+    async fn reject_firm_exceeding_soft(
+        &mut self,
+        soft: &Block,
+        firm: &Block,
+    ) -> eyre::Result<()> {
+        let request = raw::UpdateCommitmentStateRequest {
+            commitment_state: Some(raw::CommitmentState {
+                soft: Some(soft.to_raw()),
+                firm: Some(firm.to_raw()),
+            }),
+        };
+        match self.client.update_commitment_state(request).await {
+            Ok(response) => bail!(
+                "UpdateCommitmentState accepted a firm block above the soft block and returned \
+                 {:?}",
+                response.into_inner()
+            ),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Returns a timestamp one second after that of `parent`.
+fn next_timestamp(parent: &Block) -> Timestamp {
+    let Timestamp {
+        seconds,
+        nanos,
+    } = parent.timestamp();
+    Timestamp {
+        seconds: seconds.saturating_add(1),
+        nanos,
+    }
+}
@@ -1 +1,3 @@
+#[cfg(feature = "execution-conformance")]
+pub mod execution_conformance;
 pub mod mock;